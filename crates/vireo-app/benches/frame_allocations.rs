@@ -0,0 +1,171 @@
+//! Regression benchmark for per-frame GPU allocations on the render path
+//!
+//! Draws a small simulation offscreen through `Renderer` and `FrameUniforms`,
+//! the way the viewer and the headless preview do, and fails if steady-state
+//! frames create any bind group or replace any uniform buffer. The old path,
+//! a fresh camera uniform buffer every frame, is timed alongside for comparison.
+//!
+//! cargo bench -p vireo-app --bench frame_allocations
+
+use std::time::{Duration, Instant};
+use wgpu::util::DeviceExt;
+use vireo_app::age_chart::AgeChart;
+use vireo_app::agent_view::AgentViewParams;
+use vireo_app::camera::Camera;
+use vireo_app::minimap::Minimap;
+use vireo_app::palette::AgentColorParams;
+use vireo_app::renderer::{FrameInputs, FrameUniforms, Renderer};
+use vireo_app::timeline::Timeline;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::SimulationConfig;
+
+const FRAMES: u32 = 300;
+const TARGET_SIZE: [u32; 2] = [512, 512];
+const TARGET_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+struct Bench {
+    gpu: GpuDevice,
+    sim: Simulation,
+    renderer: Renderer,
+    uniforms: FrameUniforms,
+    timeline: Timeline,
+    age_chart: AgeChart,
+    minimap: Minimap,
+    camera: Camera,
+    target: wgpu::TextureView,
+}
+
+impl Bench {
+    fn new() -> Self {
+        let gpu = pollster::block_on(GpuDevice::new());
+        let mut config = SimulationConfig::default();
+        config.world.size = [256, 256];
+        config.agents.herbivores = 2000;
+        let sim = Simulation::new(&gpu, config);
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: TARGET_FORMAT,
+            width: TARGET_SIZE[0],
+            height: TARGET_SIZE[1],
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        let renderer = Renderer::new(&gpu.device, &surface_config, &sim.layouts).expect("renderer");
+        let target = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("bench_target"),
+            size: wgpu::Extent3d { width: TARGET_SIZE[0], height: TARGET_SIZE[1], depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TARGET_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        let mut timeline = Timeline::new(&gpu.device);
+        timeline.visible = true;
+        let output = &sim.config.output;
+        let age_chart = AgeChart::new(&gpu.device, output.age_bin, output.age_classes);
+        Self {
+            uniforms: FrameUniforms::new(&gpu.device),
+            timeline,
+            age_chart,
+            minimap: Minimap::default(),
+            camera: Camera::fit(sim.config.world.size),
+            target: target.create_view(&wgpu::TextureViewDescriptor::default()),
+            renderer,
+            sim,
+            gpu,
+        }
+    }
+
+    /// Step the simulation (so frames alternate field views), then draw one frame
+    ///
+    /// With `fresh_camera` the camera uniform is allocated for the frame, as
+    /// `Viewer::render` used to do with its SimParams. Returns the time spent recording and submitting the draw.
+    fn frame(&mut self, fresh_camera: bool) -> Duration {
+        self.sim.step(&self.gpu);
+        let start = Instant::now();
+
+        let world = self.sim.config.world.size;
+        let camera = self.camera.uniform(world, TARGET_SIZE);
+        self.uniforms.write_camera(&self.gpu.queue, &camera);
+        self.uniforms.write_minimap_params(&self.gpu.queue, &self.minimap.params(&self.camera, TARGET_SIZE, world));
+        self.uniforms.write_agent_colors(&self.gpu.queue, &AgentColorParams::new(&self.sim.config.render));
+        let agent_view = AgentViewParams::new(&self.camera, world, TARGET_SIZE, self.sim.agent_count(), &self.sim.config.render);
+        self.uniforms.write_agent_view(&self.gpu.queue, &agent_view);
+        self.timeline.history.push(&[("bench", 1.0)]);
+        self.timeline.upload(&self.gpu.queue);
+        self.age_chart.upload(&self.gpu.queue);
+        let fresh = fresh_camera.then(|| {
+            self.gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("camera_frame"),
+                contents: bytemuck::cast_slice(&[camera]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            })
+        });
+
+        let (timeline_params, timeline_frames) = self.timeline.buffers();
+        let (age_params, age_bars) = self.age_chart.buffers();
+        let charts = [
+            (timeline_params, timeline_frames, self.timeline.viewport(TARGET_SIZE)),
+            (age_params, age_bars, self.age_chart.viewport(TARGET_SIZE)),
+        ];
+        let mut encoder = self.gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("bench_encoder"),
+        });
+        let frame = FrameInputs {
+            camera: fresh.as_ref().unwrap_or(self.uniforms.camera()),
+            particles: self.sim.agents_buffer().map(|buffer| (buffer, &agent_view)),
+            agent_colors: self.uniforms.agent_colors(),
+            agent_view: self.uniforms.agent_view(),
+            field_texture: self.sim.field.front_sample_view(),
+            field_sampler: self.sim.field_sampler(),
+            contours: None,
+            minimap: Some((self.uniforms.minimap_params(), self.minimap.viewport(TARGET_SIZE, world))),
+            charts: &charts,
+        };
+        self.renderer.render(&self.gpu.device, &mut encoder, &self.target, &self.sim.layouts, &frame).expect("render");
+        self.gpu.submit(encoder.finish());
+        let elapsed = start.elapsed();
+
+        self.gpu.wait();
+        elapsed
+    }
+
+    /// Run `FRAMES` frames; returns the mean draw time and the bind groups created
+    fn run(&mut self, fresh_camera: bool) -> (Duration, u64) {
+        let created = self.renderer.bind_groups_created();
+        let total: Duration = (0..FRAMES).map(|_| self.frame(fresh_camera)).sum();
+        (total / FRAMES, self.renderer.bind_groups_created() - created)
+    }
+}
+
+fn main() {
+    let mut bench = Bench::new();
+
+    // Warm up on both ping-pong field views
+    for _ in 0..2 {
+        bench.frame(false);
+    }
+    let camera_id = bench.uniforms.camera().global_id();
+    let minimap_params_id = bench.uniforms.minimap_params().global_id();
+    let agent_colors_id = bench.uniforms.agent_colors().global_id();
+
+    let (persistent, persistent_created) = bench.run(false);
+    println!("persistent uniforms: {:>8.3} ms/frame, {} bind groups created over {} frames",
+        persistent.as_secs_f64() * 1e3, persistent_created, FRAMES);
+    let (fresh, fresh_created) = bench.run(true);
+    println!("per-frame camera:     {:>7.3} ms/frame, {} bind groups created over {} frames",
+        fresh.as_secs_f64() * 1e3, fresh_created, FRAMES);
+
+    assert_eq!(persistent_created, 0, "steady-state frames must not create bind groups");
+    assert_eq!(bench.uniforms.camera().global_id(), camera_id);
+    assert_eq!(bench.uniforms.minimap_params().global_id(), minimap_params_id);
+    assert_eq!(bench.uniforms.agent_colors().global_id(), agent_colors_id);
+    // The counter does catch churn: every fresh buffer needs its own bind group
+    assert!(fresh_created >= FRAMES as u64, "{}", fresh_created);
+}
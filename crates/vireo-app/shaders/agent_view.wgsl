@@ -0,0 +1,76 @@
+// Agent view passes: cull agents outside the camera into an indirect draw list,
+// or count the living per cell and species for the density map and kernel density.
+
+struct AgentViewParams {
+    count: u32,            // Agent slots in use
+    show_dead: u32,        // 1 = dead agents are drawn
+    draw: u32,             // 0 = quads, 1 = density map, 2 = kernel density
+    saturation: f32,       // Agents per cell fully opaque, or at the top of the colormap
+    world_size: vec2<u32>,
+    bandwidth: f32,        // Kernel standard deviation in cells
+    _pad: u32,
+}
+
+struct CameraUniform {
+    view: mat4x4<f32>,     // World -> clip
+    inv_view: mat4x4<f32>, // Clip -> world
+    world_size: vec2<f32>,
+    viewport: vec2<f32>,   // Surface size in pixels
+}
+
+// wgpu::util::DrawIndirectArgs, counted up by `cull`
+struct DrawArgs {
+    vertex_count: atomic<u32>,
+    instance_count: atomic<u32>,
+    first_vertex: u32,
+    first_instance: u32,
+}
+
+@group(0) @binding(0) var<storage, read> particles: array<Agent>;
+@group(0) @binding(1) var<uniform> params: AgentViewParams;
+@group(0) @binding(2) var<uniform> camera: CameraUniform;
+@group(0) @binding(3) var<storage, read_write> slots: array<atomic<u32>>; // Visible slots, or 3 species counts per cell
+@group(0) @binding(4) var<storage, read_write> draw: DrawArgs;
+
+// Half the quad edge drawn by render.wgsl, in world units
+const QUAD_HALF: f32 = 0.5;
+
+// Append every drawn agent whose quad reaches the view to the visible list
+// (in no particular order). The draw arguments start zeroed.
+@compute @workgroup_size(128)
+fn cull(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let i = gid.x;
+    if (i == 0u) {
+        atomicStore(&draw.vertex_count, 6u);
+    }
+    if (i >= params.count) {
+        return;
+    }
+    let P = particles[i];
+    // Dead agents only show with dead_alpha; free slots never used sit at the origin
+    if (P.alive == 0u && (params.show_dead == 0u || all(P.pos == vec2<f32>(0.0, 0.0)))) {
+        return;
+    }
+    let clip = (camera.view * vec4<f32>(P.pos, 0.0, 1.0)).xy;
+    let margin = QUAD_HALF * vec2<f32>(abs(camera.view[0][0]), abs(camera.view[1][1]));
+    if (any(abs(clip) > vec2<f32>(1.0) + margin)) {
+        return;
+    }
+    let slot = atomicAdd(&draw.instance_count, 1u);
+    atomicStore(&slots[slot], i);
+}
+
+// Count every living agent into its cell; the counts start zeroed
+@compute @workgroup_size(128)
+fn deposit(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let i = gid.x;
+    if (i >= params.count) {
+        return;
+    }
+    let P = particles[i];
+    if (P.alive == 0u) {
+        return;
+    }
+    let cell = min(vec2<u32>(max(P.pos, vec2<f32>(0.0))), params.world_size - 1u);
+    atomicAdd(&slots[(cell.y * params.world_size.x + cell.x) * 3u + min(P.kind, 2u)], 1u);
+}
@@ -0,0 +1,53 @@
+// Contour overlay: iso-lines of R every `spacing`, a constant number of pixels thick.
+
+@group(0) @binding(0) var fieldTex: FieldTex; // declared by vireo_core::shaders::field_prelude
+@group(0) @binding(1) var fieldSamp: sampler;
+@group(0) @binding(2) var<uniform> params: ContourParams;
+@group(1) @binding(0) var<uniform> camera: CameraUniform;
+
+struct ContourParams {
+    spacing: f32,
+    line_px: f32,
+    _pad: vec2<f32>,
+    color: vec4<f32>,
+}
+
+struct CameraUniform {
+    view: mat4x4<f32>,     // World -> clip
+    inv_view: mat4x4<f32>, // Clip -> world
+    world_size: vec2<f32>,
+    viewport: vec2<f32>,   // Surface size in pixels
+}
+
+struct VSOut {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) clip: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vid: u32) -> VSOut {
+    var out: VSOut;
+    // Fullscreen triangle: NDC (-1,-1), (3,-1), (-1,3)
+    let p = vec2<f32>(f32((vid << 1u) & 2u), f32(vid & 2u));
+    let ndc = p * 2.0 - 1.0;
+    out.pos = vec4<f32>(ndc, 0.0, 1.0);
+    out.clip = ndc;
+    return out;
+}
+
+@fragment
+fn fs_main(@location(0) clip: vec2<f32>) -> @location(0) vec4<f32> {
+    let world = (camera.inv_view * vec4<f32>(clip, 0.0, 1.0)).xy;
+    let uv = world / camera.world_size;
+    let inside = all(uv >= vec2<f32>(0.0)) && all(uv <= vec2<f32>(1.0));
+
+    // Level coordinate and its change per pixel, both in uniform control flow
+    let level = field_sample(fieldTex, fieldSamp, uv, 0).x / params.spacing; // layer 0 holds R
+    let per_px = max(fwidth(level), 1e-6);
+
+    // Pixels to the nearest multiple of `spacing`; lines at 0 are left out (bare ground)
+    let px = abs(fract(level + 0.5) - 0.5) / per_px;
+    let coverage = clamp(params.line_px * 0.5 + 0.5 - px, 0.0, 1.0);
+    let drawn = inside && level > 0.5;
+    return vec4<f32>(params.color.rgb, select(0.0, coverage * params.color.a, drawn));
+}
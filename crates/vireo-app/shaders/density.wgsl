@@ -0,0 +1,56 @@
+// Agent density map: each cell under the camera in the species hues of render.wgsl,
+// weighted by its counts, and more opaque the more agents it holds.
+
+struct AgentViewParams {
+    count: u32,            // Agent slots in use
+    show_dead: u32,        // 1 = dead agents are drawn
+    draw: u32,             // 0 = quads, 1 = density map, 2 = kernel density
+    saturation: f32,       // Agents per cell fully opaque, or at the top of the colormap
+    world_size: vec2<u32>,
+    bandwidth: f32,        // Kernel standard deviation in cells
+    _pad: u32,
+}
+
+struct CameraUniform {
+    view: mat4x4<f32>,     // World -> clip
+    inv_view: mat4x4<f32>, // Clip -> world
+    world_size: vec2<f32>,
+    viewport: vec2<f32>,   // Surface size in pixels
+}
+
+@group(0) @binding(0) var<storage, read> counts: array<u32>; // Plants, herbivores, predators per cell
+@group(0) @binding(1) var<uniform> params: AgentViewParams;
+@group(1) @binding(0) var<uniform> camera: CameraUniform;
+
+struct VSOut {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) clip: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vid: u32) -> VSOut {
+    // Fullscreen triangle: NDC (-1,-1), (3,-1), (-1,3)
+    let p = vec2<f32>(f32((vid << 1u) & 2u), f32(vid & 2u));
+    var out: VSOut;
+    out.pos = vec4<f32>(p * 2.0 - 1.0, 0.0, 1.0);
+    out.clip = p * 2.0 - 1.0;
+    return out;
+}
+
+@fragment
+fn fs_main(@location(0) clip: vec2<f32>) -> @location(0) vec4<f32> {
+    let world = (camera.inv_view * vec4<f32>(clip, 0.0, 1.0)).xy;
+    if (any(world < vec2<f32>(0.0)) || any(world >= vec2<f32>(params.world_size))) {
+        discard;
+    }
+    let cell = vec2<u32>(world);
+    let base = (cell.y * params.world_size.x + cell.x) * 3u;
+    let n = vec3<f32>(f32(counts[base]), f32(counts[base + 1u]), f32(counts[base + 2u]));
+    let total = n.x + n.y + n.z;
+    if (total == 0.0) {
+        discard;
+    }
+    // Red plants, green herbivores, blue predators, as with `agent_color: species`
+    let alpha = clamp(0.25 + 0.75 * total / params.saturation, 0.0, 1.0);
+    return vec4<f32>(n / total, alpha);
+}
@@ -1,11 +1,19 @@
-// Field background: sample the current front field texture and paint a heat-map.
+// Field background: sample the current front field texture under the camera and paint a heat-map.
 
-@group(0) @binding(0) var fieldTex: texture_2d<f32>;
+@group(0) @binding(0) var fieldTex: FieldTex; // declared by vireo_core::shaders::field_prelude
 @group(0) @binding(1) var fieldSamp: sampler;
+@group(1) @binding(0) var<uniform> camera: CameraUniform;
+
+struct CameraUniform {
+    view: mat4x4<f32>,     // World -> clip
+    inv_view: mat4x4<f32>, // Clip -> world
+    world_size: vec2<f32>,
+    viewport: vec2<f32>,   // Surface size in pixels
+}
 
 struct VSOut {
     @builtin(position) pos: vec4<f32>,
-    @location(0) uv: vec2<f32>,
+    @location(0) clip: vec2<f32>,
 }
 
 @vertex
@@ -23,22 +31,27 @@ fn vs_main(@builtin(vertex_index) vid: u32) -> VSOut {
     let ndc = p * 2.0 - 1.0;
     
     out.pos = vec4<f32>(ndc, 0.0, 1.0);
-    // UVs that match the screen (flip Y to taste)
-    out.uv = vec2<f32>(p.x * 0.5, 1.0 - p.y * 0.5);
+    out.clip = ndc;
     return out;
 }
 
 @fragment
-fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
-    // Sample the field texture
-    let field_value = textureSample(fieldTex, fieldSamp, uv);
-    
+fn fs_main(@location(0) clip: vec2<f32>) -> @location(0) vec4<f32> {
     // Use black background instead of green
     let background = vec3<f32>(0.0, 0.0, 0.0); // Black background
     
+    // World position under this pixel; texel rows follow world y
+    let world = (camera.inv_view * vec4<f32>(clip, 0.0, 1.0)).xy;
+    let uv = world / camera.world_size;
+    let inside = all(uv >= vec2<f32>(0.0)) && all(uv <= vec2<f32>(1.0));
+    
+    // Sample the field texture (in uniform control flow, masked outside the world)
+    let field_value = field_sample(fieldTex, fieldSamp, uv, 0); // layer 0 holds R, W, soil damage
+    
     // Keep some subtle field visualization but make it very dark
-    let field_intensity = field_value.x * 0.1; // Very subtle field visualization
-    let field_color = vec3<f32>(0.0, field_intensity, 0.0); // Very dark green
+    let field_intensity = select(0.0, field_value.x * 0.1, inside); // Very subtle field visualization
+    let scar = select(0.0, field_value.z, inside); // Overgrazed soil tints brown
+    let field_color = vec3<f32>(0.3 * scar, field_intensity + 0.15 * scar, 0.0); // Very dark green
     
     // Blend background with field
     let final_color = mix(background, field_color, 0.3); // Keep field very subtle
@@ -0,0 +1,110 @@
+// Kernel density estimate of the agents: a Gaussian of `bandwidth` cells summed
+// over the per-cell counts of agent_view.wgsl, along rows in `smooth_rows` and
+// along columns in `fs_main`, which colormaps the result.
+
+struct AgentViewParams {
+    count: u32,            // Agent slots in use
+    show_dead: u32,        // 1 = dead agents are drawn
+    draw: u32,             // 0 = quads, 1 = density map, 2 = kernel density
+    saturation: f32,       // Agents per cell fully opaque, or at the top of the colormap
+    world_size: vec2<u32>,
+    bandwidth: f32,        // Kernel standard deviation in cells
+    _pad: u32,
+}
+
+struct CameraUniform {
+    view: mat4x4<f32>,     // World -> clip
+    inv_view: mat4x4<f32>, // Clip -> world
+    world_size: vec2<f32>,
+    viewport: vec2<f32>,   // Surface size in pixels
+}
+
+// Row pass (agent view layout)
+@group(0) @binding(1) var<uniform> params: AgentViewParams;
+@group(0) @binding(3) var<storage, read_write> counts: array<u32>;      // Plants, herbivores, predators per cell
+@group(0) @binding(4) var<storage, read_write> smoothed: array<f32>;    // Counts smoothed along rows
+// Draw pass (agent density layout, camera at group 1)
+@group(0) @binding(0) var<storage, read> rows: array<f32>;              // `smoothed`, read back
+@group(1) @binding(0) var<uniform> camera: CameraUniform;
+
+// Cells summed on either side: three bandwidths
+fn kernel_radius() -> i32 {
+    return i32(ceil(3.0 * params.bandwidth));
+}
+
+fn kernel_weight(d: i32) -> f32 {
+    let x = f32(d) / params.bandwidth;
+    return exp(-0.5 * x * x);
+}
+
+@compute @workgroup_size(8, 8)
+fn smooth_rows(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let size = params.world_size;
+    if (gid.x >= size.x || gid.y >= size.y) {
+        return;
+    }
+    let r = kernel_radius();
+    var sum = 0.0;
+    var norm = 0.0;
+    for (var d = -r; d <= r; d++) {
+        let w = kernel_weight(d);
+        norm += w;
+        let x = i32(gid.x) + d;
+        if (x >= 0 && x < i32(size.x)) {
+            let c = (gid.y * size.x + u32(x)) * 3u;
+            sum += w * f32(counts[c] + counts[c + 1u] + counts[c + 2u]);
+        }
+    }
+    smoothed[gid.y * size.x + gid.x] = sum / norm;
+}
+
+// Inferno colormap, polynomial fit (t in [0, 1])
+fn inferno(t: f32) -> vec3<f32> {
+    let c0 = vec3<f32>(0.00021894, 0.00165100, -0.01948090);
+    let c1 = vec3<f32>(0.10651342, 0.56395644, 3.93271239);
+    let c2 = vec3<f32>(11.60249308, -3.97285397, -15.94239411);
+    let c3 = vec3<f32>(-41.70399613, 17.43639888, 44.35414520);
+    let c4 = vec3<f32>(77.16293570, -33.40235894, -81.80730926);
+    let c5 = vec3<f32>(-71.31942824, 32.62606426, 73.20951986);
+    let c6 = vec3<f32>(25.13112622, -12.24266895, -23.07032500);
+    return clamp(c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6))))), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+struct VSOut {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) clip: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vid: u32) -> VSOut {
+    // Fullscreen triangle: NDC (-1,-1), (3,-1), (-1,3)
+    let p = vec2<f32>(f32((vid << 1u) & 2u), f32(vid & 2u));
+    var out: VSOut;
+    out.pos = vec4<f32>(p * 2.0 - 1.0, 0.0, 1.0);
+    out.clip = p * 2.0 - 1.0;
+    return out;
+}
+
+@fragment
+fn fs_main(@location(0) clip: vec2<f32>) -> @location(0) vec4<f32> {
+    let size = params.world_size;
+    let world = (camera.inv_view * vec4<f32>(clip, 0.0, 1.0)).xy;
+    if (any(world < vec2<f32>(0.0)) || any(world >= vec2<f32>(size))) {
+        discard;
+    }
+    let cell = vec2<i32>(world);
+    let r = kernel_radius();
+    var sum = 0.0;
+    var norm = 0.0;
+    for (var d = -r; d <= r; d++) {
+        let w = kernel_weight(d);
+        norm += w;
+        let y = cell.y + d;
+        if (y >= 0 && y < i32(size.y)) {
+            sum += w * rows[u32(y) * size.x + u32(cell.x)];
+        }
+    }
+    // Agents per cell, as a share of the top of the scale; the sparsest fade into the field
+    let t = sum / norm / params.saturation;
+    return vec4<f32>(inferno(clamp(t, 0.0, 1.0)), clamp(t * 8.0, 0.0, 1.0));
+}
@@ -0,0 +1,52 @@
+// Minimap: R field heat-map with the camera rectangle outlined, drawn into a corner viewport.
+
+@group(0) @binding(0) var fieldTex: FieldTex; // declared by vireo_core::shaders::field_prelude
+@group(0) @binding(1) var fieldSamp: sampler;
+@group(0) @binding(2) var<uniform> params: MinimapParams;
+
+struct MinimapParams {
+    view_min: vec2<f32>, // camera rectangle, normalised world coordinates
+    view_max: vec2<f32>,
+    outline: vec2<f32>,  // outline thickness, normalised world coordinates
+    _pad: vec2<f32>,
+}
+
+struct VSOut {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vid: u32) -> VSOut {
+    var out: VSOut;
+
+    // Fullscreen triangle over the minimap viewport
+    let p = vec2<f32>(
+        f32((vid << 1u) & 2u),
+        f32( vid        & 2u)
+    );
+
+    out.pos = vec4<f32>(p * 2.0 - 1.0, 0.0, 1.0);
+    // World y points up, same as the particle view
+    out.uv = p;
+    return out;
+}
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let r = field_sample(fieldTex, fieldSamp, uv, 0).x; // layer 0 holds R, W
+
+    // Heat-map: black -> green -> yellow
+    let t = clamp(r, 0.0, 1.0);
+    var color = mix(vec3<f32>(0.02, 0.02, 0.02), vec3<f32>(0.1, 0.8, 0.2), clamp(t * 2.0, 0.0, 1.0));
+    color = mix(color, vec3<f32>(1.0, 0.95, 0.3), clamp(t * 2.0 - 1.0, 0.0, 1.0));
+
+    // Camera rectangle outline
+    let inside = all(uv >= params.view_min - params.outline) && all(uv <= params.view_max + params.outline);
+    let interior = all(uv > params.view_min + params.outline) && all(uv < params.view_max - params.outline);
+    if (inside && !interior) {
+        color = vec3<f32>(1.0, 1.0, 1.0);
+    }
+
+    return vec4<f32>(color, 1.0);
+}
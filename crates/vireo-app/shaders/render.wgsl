@@ -8,23 +8,24 @@ struct VertexOutput {
     @location(0) color: vec4<f32>,
 }
 
-@group(0) @binding(0) var<uniform> sim_params: SimParams;
-@group(0) @binding(1) var<storage, read> particles: array<Particle>;
+@group(0) @binding(0) var<storage, read> particles: array<Agent>;
+@group(0) @binding(1) var<uniform> colors: AgentColorParams;
+@group(0) @binding(2) var<storage, read> visible: array<u32>; // Slots left by the cull pass in agent_view.wgsl
+@group(1) @binding(0) var<uniform> camera: CameraUniform;
 
-struct SimParams {
+struct CameraUniform {
+    view: mat4x4<f32>,     // World -> clip
+    inv_view: mat4x4<f32>, // Clip -> world
     world_size: vec2<f32>,
-    time: f32,
-    zoom: f32,
-    camera: vec2<f32>,
-    _pad0: vec2<f32>, // pad so struct size is multiple of 16
+    viewport: vec2<f32>,   // Surface size in pixels
 }
 
-struct Particle {
-    pos: vec2<f32>,
-    vel: vec2<f32>,
-    energy: f32,
-    alive: u32,
-    kind: u32,
+struct AgentColorParams {
+    starving: vec4<f32>,     // Color at energy_range.x and below
+    thriving: vec4<f32>,     // Color at energy_range.y and above
+    energy_range: vec2<f32>,
+    dead_alpha: f32,         // Opacity of dead agents (0 hides them)
+    by_energy: u32,          // 1 = energy gradient, 0 = species hues
 }
 
 @vertex
@@ -32,11 +33,13 @@ fn vs_main(
     @builtin(vertex_index) vid: u32,
     @builtin(instance_index) inst: u32,
 ) -> VertexOutput {
-    let P = particles[inst];
+    let P = particles[visible[inst]];
     
-    // Skip dead particles
-    if (P.alive == 0u) {
-        // Return a degenerate triangle for dead particles
+    // Dead agents fade to `dead_alpha` where they died; free slots never used
+    // sit at the origin and stay hidden
+    let dead = P.alive == 0u;
+    if (dead && (colors.dead_alpha <= 0.0 || all(P.pos == vec2<f32>(0.0, 0.0)))) {
+        // Return a degenerate triangle for hidden particles
         var output: VertexOutput;
         output.position = vec4<f32>(0.0, 0.0, 0.0, 0.0);
         output.color = vec4<f32>(0.0, 0.0, 0.0, 0.0);
@@ -44,7 +47,7 @@ fn vs_main(
     }
     
     // Create a small square for each particle
-    let quad_size = 0.05 * sim_params.zoom; // Much larger for better visibility
+    let quad_size = 0.5; // World units (half a cell); scales with the camera zoom
     
     // Simple square generation - map vertex index to square corners
     // This should create perfect squares, not triangles
@@ -55,13 +58,13 @@ fn vs_main(
     let world_pos = P.pos + vertex_pos;
     
     // Convert to clip space
-    let clip_pos = (world_pos - sim_params.camera) * sim_params.zoom;
+    let clip_pos = camera.view * vec4<f32>(world_pos, 0.0, 1.0);
     
     // Color based on particle kind - completely different colors
     let kind = P.kind;
     
     // Very different colors for each agent type
-    let color = select(
+    let species_color = select(
         vec4<f32>(1.0, 0.0, 0.0, 1.0),  // Bright red for plants (kind 0)
         select(
             vec4<f32>(0.0, 1.0, 0.0, 1.0),  // Bright green for herbivores (kind 1) 
@@ -71,8 +74,15 @@ fn vs_main(
         kind == 0u
     );
     
+    // Energy gradient: starving at the low end of the range, thriving at the high end
+    let t = clamp((P.energy - colors.energy_range.x) / (colors.energy_range.y - colors.energy_range.x), 0.0, 1.0);
+    let energy_color = mix(colors.starving, colors.thriving, t);
+    
+    var color = select(species_color, energy_color, colors.by_energy == 1u);
+    color.a = select(color.a, colors.dead_alpha, dead);
+    
     var output: VertexOutput;
-    output.position = vec4<f32>(clip_pos, 0.0, 1.0);
+    output.position = clip_pos;
     output.color = color;
     return output;
 }
@@ -0,0 +1,77 @@
+// Pass timeline: stacked area chart of per-pass frame times, drawn into a corner viewport.
+// The age chart reuses it with one column per age class and species counts for layers.
+
+@group(0) @binding(0) var<uniform> params: TimelineParams;
+@group(0) @binding(1) var<storage, read> stacked: array<f32>; // MAX_LAYERS cumulative tops per column, oldest first
+
+struct TimelineParams {
+    frames: u32,    // columns held, right-aligned
+    capacity: u32,  // column slots across the chart
+    layers: u32,    // named layers
+    scale: f32,     // value at the top edge
+    marker: f32,    // reference line value, 0 = none
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+const MAX_LAYERS: u32 = 8u;
+
+struct VSOut {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vid: u32) -> VSOut {
+    var out: VSOut;
+
+    // Fullscreen triangle over the timeline viewport
+    let p = vec2<f32>(
+        f32((vid << 1u) & 2u),
+        f32( vid        & 2u)
+    );
+
+    out.pos = vec4<f32>(p * 2.0 - 1.0, 0.0, 1.0);
+    // uv.y = 0 at the bottom (value 0)
+    out.uv = p;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VSOut) -> @location(0) vec4<f32> {
+    var palette = array<vec3<f32>, 8>(
+        vec3<f32>(0.25, 0.55, 0.95), // blue
+        vec3<f32>(0.95, 0.55, 0.15), // orange
+        vec3<f32>(0.3, 0.8, 0.35),   // green
+        vec3<f32>(0.9, 0.25, 0.25),  // red
+        vec3<f32>(0.65, 0.4, 0.9),   // purple
+        vec3<f32>(0.95, 0.85, 0.2),  // yellow
+        vec3<f32>(0.2, 0.85, 0.85),  // cyan
+        vec3<f32>(0.6, 0.6, 0.6),    // grey
+    );
+
+    var color = vec4<f32>(0.0, 0.0, 0.0, 0.55);
+    let value = in.uv.y * params.scale;
+    let px_value = params.scale * fwidth(in.uv.y); // one pixel, in chart units
+
+    // Column under this pixel; older slots stay empty until the history fills
+    let slot = min(u32(in.uv.x * f32(params.capacity)), params.capacity - 1u);
+    let first = params.capacity - params.frames;
+    if (slot >= first) {
+        let base = (slot - first) * MAX_LAYERS;
+        for (var k = 0u; k < MAX_LAYERS; k = k + 1u) {
+            if (value < stacked[base + k]) {
+                color = vec4<f32>(palette[k], 0.9);
+                break;
+            }
+        }
+    }
+
+    // Reference line (the frame budget on the pass timeline)
+    if (params.marker > 0.0 && params.marker < params.scale && abs(value - params.marker) < px_value) {
+        color = vec4<f32>(1.0, 1.0, 1.0, 0.8);
+    }
+
+    return color;
+}
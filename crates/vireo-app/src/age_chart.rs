@@ -0,0 +1,101 @@
+//! Age structure overlay: stacked bars of alive agents per age class and species
+
+use bytemuck::Zeroable;
+use wgpu::util::DeviceExt;
+use vireo_core::gpu::PassTimeline;
+use vireo_core::sim::AgeStructure;
+use vireo_core::SPECIES;
+use crate::timeline::{TimelineParams, LEGEND_COLOURS};
+
+/// Chart size as a fraction of the window (width, height)
+const AGE_CHART_FRACTION: [f32; 2] = [0.3, 0.2];
+/// Gap between the chart and the window edge, in pixels
+const AGE_CHART_MARGIN: f32 = 12.0;
+
+/// Latest age structure and the buffers the chart reads it from
+///
+/// Bars go from the youngest class on the left to the open-ended oldest on the
+/// right, one layer per species in the timeline palette. Both buffers are
+/// allocated once for `classes` bars; `upload` rewrites them in place.
+pub struct AgeChart {
+    pub visible: bool,
+    pub ages: AgeStructure,
+    params_buffer: wgpu::Buffer,
+    bars_buffer: wgpu::Buffer,
+}
+
+impl AgeChart {
+    pub fn new(device: &wgpu::Device, bin_steps: u32, classes: u32) -> Self {
+        let ages = AgeStructure::of(&[], bin_steps, classes);
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("age_chart_params"),
+            contents: bytemuck::cast_slice(&[TimelineParams::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let bars_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("age_chart_bars"),
+            size: (ages.counts.len() * PassTimeline::MAX_LAYERS * std::mem::size_of::<f32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self { visible: false, ages, params_buffer, bars_buffer }
+    }
+
+    /// Shader uniform for the held age structure
+    pub fn params(&self) -> TimelineParams {
+        let classes = self.ages.counts.len() as u32;
+        TimelineParams {
+            frames: classes,
+            capacity: classes,
+            layers: SPECIES.len() as u32,
+            scale: (self.ages.peak() as f32 * 1.1).max(1.0),
+            marker: 0.0,
+            _pad: [0; 3],
+        }
+    }
+
+    /// Cumulative species tops, `MAX_LAYERS` per class, youngest class first
+    pub fn stacked(&self) -> Vec<f32> {
+        let mut out = Vec::with_capacity(self.ages.counts.len() * PassTimeline::MAX_LAYERS);
+        for class in &self.ages.counts {
+            let mut top = 0.0;
+            for k in 0..PassTimeline::MAX_LAYERS {
+                top += class.get(k).copied().unwrap_or(0) as f32;
+                out.push(top);
+            }
+        }
+        out
+    }
+
+    /// Copy the held age structure into the chart buffers
+    pub fn upload(&self, queue: &wgpu::Queue) {
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[self.params()]));
+        queue.write_buffer(&self.bars_buffer, 0, bytemuck::cast_slice(&self.stacked()));
+    }
+
+    /// Params uniform and stacked class counts, in binding order
+    pub fn buffers(&self) -> (&wgpu::Buffer, &wgpu::Buffer) {
+        (&self.params_buffer, &self.bars_buffer)
+    }
+
+    /// Viewport in the bottom-right corner as (x, y, width, height) pixels
+    pub fn viewport(&self, surface_size: [u32; 2]) -> [f32; 4] {
+        let w = (surface_size[0] as f32 * AGE_CHART_FRACTION[0]).max(1.0);
+        let h = (surface_size[1] as f32 * AGE_CHART_FRACTION[1]).max(1.0);
+        let x = (surface_size[0] as f32 - w - AGE_CHART_MARGIN).max(0.0);
+        let y = (surface_size[1] as f32 - h - AGE_CHART_MARGIN).max(0.0);
+        [x, y, w, h]
+    }
+
+    /// One line per species: alive total, the share in the youngest class and the shader's colour
+    pub fn legend(&self) -> Vec<String> {
+        SPECIES.iter().enumerate()
+            .map(|(k, name)| {
+                let total = self.ages.total(k);
+                let young = self.ages.counts.first().map_or(0, |c| c[k]);
+                let share = if total > 0 { 100.0 * young as f32 / total as f32 } else { 0.0 };
+                format!("{} {}: {} alive, {:.1}% in the youngest class", LEGEND_COLOURS[k], name, total, share)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,67 @@
+//! How the particle pass draws agents: quads culled to the view when zoomed in,
+//! a per-cell density map when zoomed out, or a kernel density estimate on request
+
+use bytemuck::{Pod, Zeroable};
+use vireo_core::RenderConfig;
+use crate::camera::Camera;
+
+/// What stands for the agents on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentDraw {
+    /// A quad per agent on screen
+    Quads = 0,
+    /// Each cell in the species hues, weighted by its counts
+    Density = 1,
+    /// Gaussian kernel density of all agents, colormapped
+    Kde = 2,
+}
+
+/// Agent view uniform, read by the culling, density and kernel density passes
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct AgentViewParams {
+    pub count: u32,          // Agent slots in use
+    pub show_dead: u32,      // 1 = dead agents are drawn (`dead_alpha` > 0)
+    pub draw: u32,           // `AgentDraw`
+    pub saturation: f32,     // Agents per cell fully opaque (density) or at the top of the colormap (kernel density)
+    pub world_size: [u32; 2],
+    pub bandwidth: f32,      // Kernel standard deviation in cells
+    pub _pad: u32,
+}
+
+impl AgentViewParams {
+    /// View of `count` agent slots through `camera`: the kernel density with
+    /// `render.kde`, otherwise the density map once a cell is smaller than
+    /// `render.density_below` pixels
+    pub fn new(camera: &Camera, world_size: [u32; 2], surface_size: [u32; 2], count: u32, render: &RenderConfig) -> Self {
+        let draw = if render.kde {
+            AgentDraw::Kde
+        } else if camera.cell_pixels(surface_size) < render.density_below {
+            AgentDraw::Density
+        } else {
+            AgentDraw::Quads
+        };
+        Self {
+            count,
+            show_dead: (render.dead_alpha > 0.0) as u32,
+            draw: draw as u32,
+            saturation: render.density_saturation,
+            world_size,
+            bandwidth: render.kde_bandwidth,
+            _pad: 0,
+        }
+    }
+
+    pub fn draw(&self) -> AgentDraw {
+        match self.draw {
+            0 => AgentDraw::Quads,
+            1 => AgentDraw::Density,
+            _ => AgentDraw::Kde,
+        }
+    }
+
+    /// Whether agents are counted per cell rather than drawn as quads
+    pub fn counts_cells(&self) -> bool {
+        self.draw() != AgentDraw::Quads
+    }
+}
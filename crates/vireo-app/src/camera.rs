@@ -0,0 +1,106 @@
+//! Camera over the world and the uniform every world-space render pass reads
+
+use bytemuck::{Pod, Zeroable};
+
+/// View over the world: `clip = (world - center) * zoom`, applied on the GPU
+/// through `CameraUniform::view`
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub center: [f32; 2],
+    pub zoom: f32,
+}
+
+impl Camera {
+    /// Camera centred on the world with the whole world in view
+    pub fn fit(world_size: [u32; 2]) -> Self {
+        Self {
+            center: [world_size[0] as f32 * 0.5, world_size[1] as f32 * 0.5],
+            zoom: Self::fit_zoom(world_size),
+        }
+    }
+
+    fn fit_zoom(world_size: [u32; 2]) -> f32 {
+        2.0 / world_size[0].max(world_size[1]).max(1) as f32
+    }
+
+    /// Zoom by `factor`, clamped between half the fit zoom and 64× it
+    pub fn zoom_by(&mut self, factor: f32, world_size: [u32; 2]) {
+        let fit = Self::fit_zoom(world_size);
+        self.zoom = (self.zoom * factor).clamp(fit * 0.5, fit * 64.0);
+    }
+
+    /// Move by `clip` clip-space units (1.0 = half the view)
+    pub fn pan(&mut self, clip: [f32; 2]) {
+        self.center[0] += clip[0] / self.zoom;
+        self.center[1] += clip[1] / self.zoom;
+    }
+
+    /// World → clip transform, column-major like WGSL's `mat4x4<f32>`
+    pub fn view_matrix(&self) -> [[f32; 4]; 4] {
+        let [cx, cy] = self.center;
+        [
+            [self.zoom, 0.0, 0.0, 0.0],
+            [0.0, self.zoom, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [-cx * self.zoom, -cy * self.zoom, 0.0, 1.0],
+        ]
+    }
+
+    /// Clip → world transform, the inverse of `view_matrix`
+    pub fn inverse_view_matrix(&self) -> [[f32; 4]; 4] {
+        let [cx, cy] = self.center;
+        [
+            [1.0 / self.zoom, 0.0, 0.0, 0.0],
+            [0.0, 1.0 / self.zoom, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [cx, cy, 0.0, 1.0],
+        ]
+    }
+
+    /// World position at clip-space `clip`
+    pub fn clip_to_world(&self, clip: [f32; 2]) -> [f32; 2] {
+        let m = self.inverse_view_matrix();
+        [
+            m[0][0] * clip[0] + m[1][0] * clip[1] + m[3][0],
+            m[0][1] * clip[0] + m[1][1] * clip[1] + m[3][1],
+        ]
+    }
+
+    /// World position under `cursor` (window pixels)
+    pub fn screen_to_world(&self, cursor: [f32; 2], surface_size: [u32; 2]) -> [f32; 2] {
+        let clip_x = cursor[0] / surface_size[0].max(1) as f32 * 2.0 - 1.0;
+        let clip_y = 1.0 - cursor[1] / surface_size[1].max(1) as f32 * 2.0;
+        self.clip_to_world([clip_x, clip_y])
+    }
+
+    /// Visible world rectangle as (min, max) corners
+    pub fn visible_rect(&self) -> ([f32; 2], [f32; 2]) {
+        (self.clip_to_world([-1.0, -1.0]), self.clip_to_world([1.0, 1.0]))
+    }
+
+    /// On-screen size of one cell in pixels, along the surface's shorter side
+    pub fn cell_pixels(&self, surface_size: [u32; 2]) -> f32 {
+        self.zoom * surface_size[0].min(surface_size[1]) as f32 * 0.5
+    }
+
+    /// Shader uniform for drawing a `world_size` world onto a `surface_size` surface
+    pub fn uniform(&self, world_size: [u32; 2], surface_size: [u32; 2]) -> CameraUniform {
+        CameraUniform {
+            view: self.view_matrix(),
+            inv_view: self.inverse_view_matrix(),
+            world_size: [world_size[0] as f32, world_size[1] as f32],
+            viewport: [surface_size[0] as f32, surface_size[1] as f32],
+        }
+    }
+}
+
+/// Camera shader uniform shared by the field background and particle passes
+/// (group 1, `Layouts::camera`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct CameraUniform {
+    pub view: [[f32; 4]; 4],     // World → clip
+    pub inv_view: [[f32; 4]; 4], // Clip → world, for full-screen passes
+    pub world_size: [f32; 2],    // Cells
+    pub viewport: [f32; 2],      // Surface size in pixels
+}
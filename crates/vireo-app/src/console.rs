@@ -0,0 +1,120 @@
+//! Viewer console: a command line opened with the backtick, typed into the window title
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use vireo_core::SCHEDULABLE_PARAMS;
+
+/// Commands the console accepts, one per line
+pub const CONSOLE_USAGE: &str = "step N, seed N, set PARAM VALUE, snapshot PATH";
+
+/// A parsed console line
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleCommand {
+    Step(u32),                         // Pause and run exactly this many steps
+    Seed(u64),                         // Reset the world with this seed
+    Set { param: String, value: f32 }, // Dotted path from `SCHEDULABLE_PARAMS`
+    Snapshot(PathBuf),                 // Write the current state for `--load-snapshot`
+}
+
+impl ConsoleCommand {
+    /// Parse a line such as `step 100` or `set chi_R 6`
+    ///
+    /// `set` takes the dotted path or just the last part of it (`chi_R` for
+    /// `chemotaxis.chi_R`) when that names one parameter.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["step", n] => Ok(ConsoleCommand::Step(number(n, "a step count")?)),
+            ["seed", n] => Ok(ConsoleCommand::Seed(number(n, "a seed")?)),
+            ["set", name, value] => {
+                let value: f32 = number(value, "a number")?;
+                if !value.is_finite() {
+                    return Err(format!("`{}` must be finite", name));
+                }
+                Ok(ConsoleCommand::Set { param: resolve_param(name)?.to_string(), value })
+            }
+            ["snapshot", path] => Ok(ConsoleCommand::Snapshot(PathBuf::from(path))),
+            [] => Err(format!("empty command; use one of {}", CONSOLE_USAGE)),
+            [command, ..] => Err(format!("cannot parse `{}`; use one of {}", command, CONSOLE_USAGE)),
+        }
+    }
+}
+
+/// `word` as a number, or an error naming what was expected
+fn number<T: FromStr>(word: &str, what: &str) -> Result<T, String> {
+    word.parse().map_err(|_| format!("expected {}, got `{}`", what, word))
+}
+
+/// The parameter `name` refers to, by full path or unique last part
+fn resolve_param(name: &str) -> Result<&'static str, String> {
+    if let Some(param) = SCHEDULABLE_PARAMS.iter().find(|p| **p == name) {
+        return Ok(param);
+    }
+    let matches: Vec<&str> = SCHEDULABLE_PARAMS.iter().copied()
+        .filter(|p| p.rsplit('.').next() == Some(name))
+        .collect();
+    match matches.as_slice() {
+        [param] => Ok(param),
+        [] => Err(format!("unknown parameter `{}`; use one of {}", name, SCHEDULABLE_PARAMS.join(", "))),
+        _ => Err(format!("`{}` is ambiguous; use one of {}", name, matches.join(", "))),
+    }
+}
+
+/// The line being typed, `None` while the console is closed
+#[derive(Debug, Default)]
+pub struct Console {
+    line: Option<String>,
+    last: Option<String>, // Previous submitted line, recalled with the up arrow
+}
+
+impl Console {
+    pub fn is_open(&self) -> bool {
+        self.line.is_some()
+    }
+
+    /// Start an empty line
+    pub fn open(&mut self) {
+        self.line = Some(String::new());
+    }
+
+    /// Drop the line without running it
+    pub fn close(&mut self) {
+        self.line = None;
+    }
+
+    /// Append typed text
+    pub fn type_text(&mut self, text: &str) {
+        if let Some(line) = &mut self.line {
+            line.push_str(text);
+        }
+    }
+
+    /// Delete the last character
+    pub fn backspace(&mut self) {
+        if let Some(line) = &mut self.line {
+            line.pop();
+        }
+    }
+
+    /// Replace the line with the previous submitted one
+    pub fn recall(&mut self) {
+        if let (Some(line), Some(last)) = (&mut self.line, &self.last) {
+            line.clone_from(last);
+        }
+    }
+
+    /// Close the console and return its line, if not blank
+    pub fn submit(&mut self) -> Option<String> {
+        let line = self.line.take().filter(|l| !l.trim().is_empty())?;
+        self.last = Some(line.clone());
+        Some(line)
+    }
+
+    /// Window title showing the line and a cursor, or `base` while closed
+    pub fn title(&self, base: &str) -> String {
+        match &self.line {
+            Some(line) => format!("{} — > {}_", base, line),
+            None => base.to_string(),
+        }
+    }
+}
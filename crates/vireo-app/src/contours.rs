@@ -0,0 +1,51 @@
+//! Contour overlay: lines of equal resource drawn over the world view
+
+use bytemuck::{Pod, Zeroable};
+
+/// Line thickness in pixels, whatever the zoom
+const LINE_PX: f32 = 1.25;
+/// Most levels below the carrying capacity
+pub const MAX_CONTOUR_LEVELS: u32 = 64;
+
+/// Contour shader uniform (binding 2 of the contour layout)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct ContourParams {
+    pub spacing: f32,     // R between neighbouring lines
+    pub line_px: f32,     // Line thickness in pixels
+    pub _pad: [f32; 2],
+    pub color: [f32; 4],  // Straight alpha
+}
+
+/// Iso-lines of R at every multiple of `K_R / levels`
+///
+/// Lines continue above the carrying capacity at the same spacing, so peaks
+/// overshooting `K_R` show as tight rings.
+#[derive(Debug)]
+pub struct Contours {
+    pub visible: bool,
+    pub levels: u32,
+}
+
+impl Default for Contours {
+    fn default() -> Self {
+        Self { visible: false, levels: 8 }
+    }
+}
+
+impl Contours {
+    /// Halve or double the number of levels, within 1..=`MAX_CONTOUR_LEVELS`
+    pub fn scale_levels(&mut self, finer: bool) {
+        self.levels = if finer { self.levels * 2 } else { self.levels / 2 }.clamp(1, MAX_CONTOUR_LEVELS);
+    }
+
+    /// Shader uniform for a field with carrying capacity `k_r`
+    pub fn params(&self, k_r: f32) -> ContourParams {
+        ContourParams {
+            spacing: k_r.max(f32::MIN_POSITIVE) / self.levels.clamp(1, MAX_CONTOUR_LEVELS) as f32,
+            line_px: LINE_PX,
+            _pad: [0.0; 2],
+            color: [1.0, 1.0, 1.0, 0.6],
+        }
+    }
+}
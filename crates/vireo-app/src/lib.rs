@@ -0,0 +1,14 @@
+//! Rendering shared by the interactive viewer and the headless `--preview` window,
+//! the viewer's detached metrics window, its console and its per-user settings
+
+pub mod age_chart;
+pub mod agent_view;
+pub mod camera;
+pub mod console;
+pub mod contours;
+pub mod metrics_window;
+pub mod minimap;
+pub mod palette;
+pub mod renderer;
+pub mod settings;
+pub mod timeline;
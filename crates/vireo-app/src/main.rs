@@ -3,23 +3,81 @@
 //! Interactive GUI for the ecosystem simulation with real-time visualization.
 
 mod viewer;
-mod renderer;
 
 use clap::Parser;
 use std::path::PathBuf;
-use vireo_params::SimulationConfig;
 use anyhow::Result;
+use vireo_core::{AgentPopulation, GrowthPolicy, StateSnapshot, StepClock};
+use vireo_app::contours::MAX_CONTOUR_LEVELS;
+use vireo_app::settings::{ContourSettings, PresentMode, ViewerSettings};
+
+/// Config opened when neither `--config` nor the settings name one
+const DEFAULT_CONFIG: &str = "lab/configs/best-demo.yaml";
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    /// Configuration file path
-    #[arg(short, long, default_value = "lab/configs/best-demo.yaml")]
-    config: PathBuf,
+    /// Configuration file path (default: the last one opened, else lab/configs/best-demo.yaml)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
     
     /// Random seed for reproducible simulations
     #[arg(short, long, default_value = "1337")]
     seed: u64,
+    
+    /// Start from a headless state snapshot (e.g. results/snapshot_1000.bin) instead of a fresh seed
+    #[arg(long, value_name = "PATH")]
+    load_snapshot: Option<PathBuf>,
+    
+    /// Start from the agents of an exported agents_NNNN.csv instead of spawning them
+    #[arg(long, value_name = "CSV")]
+    init_agents: Option<PathBuf>,
+    
+    /// Record wall strokes, culls and cohort tags to a script for `vireo-headless --replay`
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+    
+    /// Simulation steps per second of real time at 1x speed, whatever the refresh rate
+    #[arg(long, default_value_t = StepClock::DEFAULT_STEPS_PER_SECOND, value_parser = parse_steps_per_second)]
+    steps_per_second: f64,
+    
+    /// Most steps run in one frame to catch up after a slow frame
+    #[arg(long, default_value_t = StepClock::DEFAULT_MAX_BURST, value_parser = clap::value_parser!(u32).range(1..))]
+    max_burst: u32,
+    
+    /// Start from default window, camera and overlays, and do not save them on exit
+    #[arg(long)]
+    no_settings: bool,
+    
+    /// Show R contour lines, N evenly spaced below the carrying capacity (l toggles, - and = halve or double)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=MAX_CONTOUR_LEVELS as i64))]
+    contour_levels: Option<u32>,
+    
+    /// How frames are presented (default: the last one used, else fifo)
+    #[arg(long, value_enum)]
+    present_mode: Option<PresentMode>,
+    
+    /// Frames queued ahead of the display, 1 (least lag) to 3 (smoothest); default: the last one used, else 2
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=3))]
+    frame_latency: Option<u32>,
+    
+    /// Most frames drawn per second, 0 for uncapped (default: the last one used, else 60)
+    #[arg(long, value_parser = parse_max_fps)]
+    max_fps: Option<f64>,
+}
+
+fn parse_steps_per_second(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(v) if v > 0.0 && v.is_finite() => Ok(v),
+        _ => Err(format!("expected a positive number of steps, got {}", s)),
+    }
+}
+
+fn parse_max_fps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(v) if v >= 0.0 && v.is_finite() => Ok(v),
+        _ => Err(format!("expected a frame rate of 0 or more, got {}", s)),
+    }
 }
 
 fn main() -> Result<()> {
@@ -28,22 +86,92 @@ fn main() -> Result<()> {
     
     let cli = Cli::parse();
     
+    // Window, camera and overlays of the last session
+    let settings_path = if cli.no_settings { None } else { ViewerSettings::default_path() };
+    let mut settings = settings_path.as_deref().map_or_else(|| Ok(ViewerSettings::default()), ViewerSettings::load)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: {}; starting from default viewer settings", e);
+            ViewerSettings::default()
+        });
+    
+    if let Some(levels) = cli.contour_levels {
+        settings.contours = ContourSettings { visible: true, levels };
+    }
+    
+    // Display options given here are remembered for the next launch
+    let display = &mut settings.display;
+    display.present_mode = cli.present_mode.unwrap_or(display.present_mode);
+    display.frame_latency = cli.frame_latency.unwrap_or(display.frame_latency).clamp(1, 3);
+    display.max_fps = cli.max_fps.unwrap_or(display.max_fps);
+    
     // Load configuration
-    println!("Loading configuration from {}", cli.config.display());
-    let mut config: SimulationConfig = serde_yaml::from_str(
-        &std::fs::read_to_string(&cli.config)?
-    )?;
+    let config_path = cli.config.clone()
+        .or_else(|| settings.last_config.clone().filter(|p| p.is_file()))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG));
+    println!("Loading configuration from {}", config_path.display());
+    let mut config = vireo_core::load_config(&config_path)?;
+    settings.last_config = Some(std::fs::canonicalize(&config_path).unwrap_or(config_path.clone()));
     
     // Override seed if provided
     config.world.seed = cli.seed;
     
+    // A dt the solvers cannot keep stable is lowered (or refused) before anything runs
+    if let Some(dt) = config.enforce_dt_limit()? {
+        eprintln!("Warning: world.dt = {} exceeds the stability limit {}, clamped to {}",
+            dt, config.dt_limit(), config.world.dt);
+    }
+    
+    // Imported agents get slots before the budget check sizes the buffers
+    let population = match &cli.init_agents {
+        Some(path) => {
+            let population = AgentPopulation::read_csv(path)
+                .map_err(|e| anyhow::anyhow!("cannot read agents {}: {}", path.display(), e))?;
+            population.fit_capacity(&mut config.agents);
+            config.validate().map_err(|e| anyhow::anyhow!("agents {} do not fit {}: {}", path.display(), config_path.display(), e))?;
+            Some(population)
+        }
+        None => None,
+    };
+    
     println!("Starting Vireo Interactive Viewer");
     println!("World size: {}x{}", config.world.size[0], config.world.size[1]);
-    println!("Agents: {}", config.agents.herbivores);
+    match &population {
+        Some(p) => println!("Agents: {:?} (plants, herbivores, predators) from --init-agents", p.counts()),
+        None => println!("Agents: {}", config.agents.herbivores),
+    }
     println!("Seed: {}", config.world.seed);
+    println!("Target: {} steps/s (catch-up burst {})", cli.steps_per_second, cli.max_burst);
+    if config.agents.growth.policy == GrowthPolicy::Grow {
+        println!("Agent growth: the viewer keeps every range at its capacity {:?}; births into a full range are rejected", config.agents.slots());
+    }
+
+    // Load the snapshot up front so a mismatched file fails before a window opens
+    let snapshot = match &cli.load_snapshot {
+        Some(path) => {
+            let snapshot = StateSnapshot::read(path)
+                .map_err(|e| anyhow::anyhow!("cannot load snapshot {}: {}", path.display(), e))?;
+            snapshot.check_compatible(&config)
+                .map_err(|e| anyhow::anyhow!("snapshot {} does not match {}: {}", path.display(), config_path.display(), e))?;
+            println!("Resuming from snapshot {} (step {})", path.display(), snapshot.step);
+            Some(snapshot)
+        }
+        None => None,
+    };
+    
+    // Scripts replay from a fresh seed into a fixed world
+    if cli.record.is_some() && (snapshot.is_some() || config.streaming.enabled) {
+        anyhow::bail!("--record needs a fresh start without streaming, so the script replays from step 0 headlessly");
+    }
+    if cli.init_agents.is_some() && snapshot.is_some() {
+        anyhow::bail!("--init-agents and --load-snapshot both replace the starting agents; pass one");
+    }
+    if let (Some(_), Some(path)) = (&cli.record, &cli.init_agents) {
+        println!("The script does not hold the imported agents: replay it with --init-agents {}", path.display());
+    }
     
     // Run the interactive viewer
-    pollster::block_on(viewer::run_viewer(config))?;
+    let step_clock = StepClock::new(cli.steps_per_second, cli.max_burst);
+    pollster::block_on(viewer::run_viewer(config, snapshot, step_clock, cli.record, population, settings, settings_path))?;
     
     Ok(())
 }
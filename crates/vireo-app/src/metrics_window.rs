@@ -0,0 +1,113 @@
+//! Detached metrics window: the charts on a second surface sharing the viewer's device
+
+use std::sync::Arc;
+use anyhow::Result;
+use winit::{
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
+    event_loop::EventLoopWindowTarget,
+    window::{Window, WindowBuilder, WindowId},
+};
+
+use crate::settings::WindowPlacement;
+
+/// Gap around and between the stacked charts, in pixels
+const METRICS_MARGIN: f32 = 12.0;
+
+/// A second window drawing only the charts, so the world view stays unobstructed
+///
+/// The surface is configured with the main surface's format, so the
+/// renderer's pipelines draw into it unchanged, and presents like it.
+pub struct MetricsWindow {
+    pub window: Arc<Window>,
+    surface: wgpu::Surface<'static>,
+    config: wgpu::SurfaceConfiguration,
+}
+
+impl MetricsWindow {
+    /// Open the window (at `placement`, if any) with a surface in the format,
+    /// present mode and frame latency of `main`
+    pub fn open<T>(
+        target: &EventLoopWindowTarget<T>,
+        instance: &wgpu::Instance,
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+        main: &wgpu::SurfaceConfiguration,
+        placement: Option<WindowPlacement>,
+    ) -> Result<Self> {
+        let mut builder = WindowBuilder::new().with_title("Vireo Metrics");
+        builder = match placement {
+            Some(p) if p.size[0] > 0 && p.size[1] > 0 => builder.with_inner_size(PhysicalSize::new(p.size[0], p.size[1])),
+            _ => builder.with_inner_size(LogicalSize::new(640.0, 480.0)),
+        };
+        if let Some([x, y]) = placement.and_then(|p| p.position) {
+            builder = builder.with_position(PhysicalPosition::new(x, y));
+        }
+        let window = Arc::new(builder.build(target)?);
+        let surface = instance.create_surface(window.clone())?;
+        let caps = surface.get_capabilities(adapter);
+        if !caps.formats.contains(&main.format) {
+            anyhow::bail!("the metrics window cannot present {:?}, the main window's format", main.format);
+        }
+        let size = window.inner_size();
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: main.format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: if caps.present_modes.contains(&main.present_mode) { main.present_mode } else { wgpu::PresentMode::Fifo },
+            alpha_mode: caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: main.desired_maximum_frame_latency,
+        };
+        surface.configure(device, &config);
+        Ok(Self { window, surface, config })
+    }
+
+    pub fn id(&self) -> WindowId {
+        self.window.id()
+    }
+
+    pub fn surface(&self) -> &wgpu::Surface<'static> {
+        &self.surface
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        [self.config.width, self.config.height]
+    }
+
+    /// Follow a window resize (ignored while minimised)
+    pub fn resize(&mut self, device: &wgpu::Device, size: PhysicalSize<u32>) {
+        if size.width > 0 && size.height > 0 {
+            self.config.width = size.width;
+            self.config.height = size.height;
+            self.surface.configure(device, &self.config);
+        }
+    }
+
+    /// Configure the surface for a new device after a device loss
+    pub fn reconfigure(&self, device: &wgpu::Device) {
+        self.surface.configure(device, &self.config);
+    }
+
+    /// Current size and position, to reopen it there next launch
+    pub fn placement(&self) -> WindowPlacement {
+        let size = self.window.inner_size();
+        WindowPlacement {
+            size: [size.width, size.height],
+            position: self.window.outer_position().ok().map(|p| [p.x, p.y]),
+        }
+    }
+
+    /// Viewports of `count` charts stacked top to bottom, each the full width
+    pub fn viewports(&self, count: usize) -> Vec<[f32; 4]> {
+        stacked_viewports(self.size(), count)
+    }
+}
+
+/// `count` equal rows filling `surface_size` as (x, y, width, height) pixels, top first
+pub fn stacked_viewports(surface_size: [u32; 2], count: usize) -> Vec<[f32; 4]> {
+    let w = (surface_size[0] as f32 - 2.0 * METRICS_MARGIN).max(1.0);
+    let free = surface_size[1] as f32 - METRICS_MARGIN * (count as f32 + 1.0);
+    let h = (free / count.max(1) as f32).max(1.0);
+    (0..count).map(|i| [METRICS_MARGIN, METRICS_MARGIN + i as f32 * (h + METRICS_MARGIN), w, h]).collect()
+}
@@ -0,0 +1,73 @@
+//! Minimap overlay: downsampled R field plus the camera rectangle
+
+use bytemuck::{Pod, Zeroable};
+use crate::camera::Camera;
+
+/// Longest minimap side as a fraction of the shorter window side
+const MINIMAP_FRACTION: f32 = 0.25;
+/// Gap between the minimap and the window edge, in pixels
+const MINIMAP_MARGIN: f32 = 12.0;
+/// Camera rectangle outline thickness, in pixels
+const OUTLINE_PX: f32 = 1.5;
+
+/// Minimap shader uniform (binding 2 of the minimap layout)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct MinimapParams {
+    pub view_min: [f32; 2], // Camera rectangle in normalised world coordinates
+    pub view_max: [f32; 2],
+    pub outline: [f32; 2],  // Outline thickness in normalised world coordinates
+    pub _pad: [f32; 2],
+}
+
+/// Minimap placement and hit testing
+#[derive(Debug)]
+pub struct Minimap {
+    pub visible: bool,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self { visible: true }
+    }
+}
+
+impl Minimap {
+    /// Viewport in the top-right corner as (x, y, width, height) pixels, keeping the world aspect
+    pub fn viewport(&self, surface_size: [u32; 2], world_size: [u32; 2]) -> [f32; 4] {
+        let side = surface_size[0].min(surface_size[1]) as f32 * MINIMAP_FRACTION;
+        let longest = world_size[0].max(world_size[1]).max(1) as f32;
+        let w = (side * world_size[0] as f32 / longest).max(1.0);
+        let h = (side * world_size[1] as f32 / longest).max(1.0);
+        let x = (surface_size[0] as f32 - w - MINIMAP_MARGIN).max(0.0);
+        [x, MINIMAP_MARGIN, w, h]
+    }
+
+    /// World position under `cursor` (window pixels), if it is over the minimap
+    pub fn hit(&self, cursor: [f32; 2], surface_size: [u32; 2], world_size: [u32; 2]) -> Option<[f32; 2]> {
+        if !self.visible {
+            return None;
+        }
+        let [x, y, w, h] = self.viewport(surface_size, world_size);
+        let u = (cursor[0] - x) / w;
+        let v = (cursor[1] - y) / h;
+        if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+            return None;
+        }
+        // Window y grows downwards, world y upwards
+        Some([u * world_size[0] as f32, (1.0 - v) * world_size[1] as f32])
+    }
+
+    /// Shader uniform for the current camera
+    pub fn params(&self, camera: &Camera, surface_size: [u32; 2], world_size: [u32; 2]) -> MinimapParams {
+        let [_, _, w, h] = self.viewport(surface_size, world_size);
+        let size = [world_size[0].max(1) as f32, world_size[1].max(1) as f32];
+        let (min, max) = camera.visible_rect();
+        MinimapParams {
+            view_min: [min[0] / size[0], min[1] / size[1]],
+            view_max: [max[0] / size[0], max[1] / size[1]],
+            outline: [OUTLINE_PX / w, OUTLINE_PX / h],
+            _pad: [0.0; 2],
+        }
+    }
+}
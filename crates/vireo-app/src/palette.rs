@@ -0,0 +1,50 @@
+//! Agent colors: fixed species hues or an energy gradient, from the `render` config
+
+use bytemuck::{Pod, Zeroable};
+use vireo_core::{AgentColoring, RenderConfig};
+
+/// RGBA of plants, herbivores and predators with `agent_color: species`
+pub const SPECIES_COLORS: [[f32; 4]; 3] = [
+    [1.0, 0.0, 0.0, 1.0],
+    [0.0, 1.0, 0.0, 1.0],
+    [0.0, 0.0, 1.0, 1.0],
+];
+
+/// Particle shader uniform (binding 1 of the particle render layout)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct AgentColorParams {
+    pub starving: [f32; 4],     // RGBA at energy_range[0] and below
+    pub thriving: [f32; 4],     // RGBA at energy_range[1] and above
+    pub energy_range: [f32; 2],
+    pub dead_alpha: f32,        // Opacity of dead agents (0 hides them)
+    pub by_energy: u32,         // 1 = energy gradient, 0 = species hues
+}
+
+impl AgentColorParams {
+    pub fn new(render: &RenderConfig) -> Self {
+        let [r, g, b] = render.starving_color;
+        let [tr, tg, tb] = render.thriving_color;
+        Self {
+            starving: [r, g, b, 1.0],
+            thriving: [tr, tg, tb, 1.0],
+            energy_range: render.energy_range,
+            dead_alpha: render.dead_alpha,
+            by_energy: (render.agent_color == AgentColoring::Energy) as u32,
+        }
+    }
+    /// RGBA of one agent, as the particle shader colors it (for CPU-drawn frames)
+    pub fn color(&self, kind: u32, energy: f32, alive: bool) -> [f32; 4] {
+        let mut color = if self.by_energy == 1 {
+            let [low, high] = self.energy_range;
+            let t = ((energy - low) / (high - low)).clamp(0.0, 1.0);
+            std::array::from_fn(|i| self.starving[i] + (self.thriving[i] - self.starving[i]) * t)
+        } else {
+            SPECIES_COLORS[(kind as usize).min(SPECIES_COLORS.len() - 1)]
+        };
+        if !alive {
+            color[3] = self.dead_alpha;
+        }
+        color
+    }
+}
@@ -1,14 +1,147 @@
 //! Renderer for the Vireo ecosystem simulation
 
-use wgpu::{SurfaceConfiguration, CommandEncoder, TextureView};
+use wgpu::{SurfaceConfiguration, CommandEncoder, TextureView, util::DeviceExt};
 use anyhow::Result;
 
 use vireo_core::gpu::layouts::Layouts;
+use crate::agent_view::{AgentDraw, AgentViewParams};
+use crate::camera::CameraUniform;
+use crate::contours::ContourParams;
+use crate::minimap::MinimapParams;
+use crate::palette::AgentColorParams;
+
+/// Bind groups kept across frames: every overlay over both ping-pong field views,
+/// plus the agent view passes of every `AgentDraw`
+const BIND_GROUP_CACHE: usize = 16;
+
+/// Bind group name plus the global ids of the resources it binds
+type BindGroupKey = (&'static str, [u64; 4]);
+
+/// Agents per agent view workgroup (`agent_view.wgsl`)
+const AGENT_VIEW_WORKGROUP: u32 = 128;
+
+/// Per-frame buffers and views `Renderer::render` draws from
+#[derive(Clone, Copy)]
+pub struct FrameInputs<'a> {
+    pub camera: &'a wgpu::Buffer,
+    pub particles: Option<(&'a wgpu::Buffer, &'a AgentViewParams)>, // Agent buffer and this frame's view of it, `None` without agents
+    pub agent_colors: &'a wgpu::Buffer,
+    pub agent_view: &'a wgpu::Buffer, // Written from the `particles` view this frame
+    pub field_texture: &'a TextureView,
+    pub field_sampler: &'a wgpu::Sampler,
+    pub contours: Option<&'a wgpu::Buffer>, // Contour params, drawn over the agents
+    pub minimap: Option<(&'a wgpu::Buffer, [f32; 4])>, // Params and (x, y, width, height) viewport in pixels
+    pub charts: &'a [(&'a wgpu::Buffer, &'a wgpu::Buffer, [f32; 4])], // Params, stacked values and viewport per chart, drawn in order
+}
+
+/// Uniforms the renderer reads each frame, allocated once and rewritten in place
+pub struct FrameUniforms {
+    camera: wgpu::Buffer,
+    minimap_params: wgpu::Buffer,
+    contour_params: wgpu::Buffer,
+    agent_colors: wgpu::Buffer,
+    agent_view: wgpu::Buffer,
+}
+
+impl FrameUniforms {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let camera = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("camera_frame"),
+            contents: bytemuck::cast_slice(&[<CameraUniform as bytemuck::Zeroable>::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let minimap_params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("minimap_params_frame"),
+            contents: bytemuck::cast_slice(&[<MinimapParams as bytemuck::Zeroable>::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let contour_params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("contour_params_frame"),
+            contents: bytemuck::cast_slice(&[<ContourParams as bytemuck::Zeroable>::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let agent_colors = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("agent_colors_frame"),
+            contents: bytemuck::cast_slice(&[<AgentColorParams as bytemuck::Zeroable>::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let agent_view = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("agent_view_frame"),
+            contents: bytemuck::cast_slice(&[<AgentViewParams as bytemuck::Zeroable>::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        Self { camera, minimap_params, contour_params, agent_colors, agent_view }
+    }
+
+    /// Write the CameraUniform read by the field background and particle passes
+    pub fn write_camera(&self, queue: &wgpu::Queue, camera: &CameraUniform) {
+        queue.write_buffer(&self.camera, 0, bytemuck::cast_slice(std::slice::from_ref(camera)));
+    }
+
+    pub fn write_minimap_params(&self, queue: &wgpu::Queue, params: &MinimapParams) {
+        queue.write_buffer(&self.minimap_params, 0, bytemuck::cast_slice(std::slice::from_ref(params)));
+    }
+
+    pub fn write_contour_params(&self, queue: &wgpu::Queue, params: &ContourParams) {
+        queue.write_buffer(&self.contour_params, 0, bytemuck::cast_slice(std::slice::from_ref(params)));
+    }
+
+    /// Write the AgentColorParams read by the particle pass
+    pub fn write_agent_colors(&self, queue: &wgpu::Queue, params: &AgentColorParams) {
+        queue.write_buffer(&self.agent_colors, 0, bytemuck::cast_slice(std::slice::from_ref(params)));
+    }
+
+    /// Write the AgentViewParams read by the culling, density and kernel density passes
+    pub fn write_agent_view(&self, queue: &wgpu::Queue, params: &AgentViewParams) {
+        queue.write_buffer(&self.agent_view, 0, bytemuck::cast_slice(std::slice::from_ref(params)));
+    }
+
+    pub fn camera(&self) -> &wgpu::Buffer {
+        &self.camera
+    }
+
+    pub fn minimap_params(&self) -> &wgpu::Buffer {
+        &self.minimap_params
+    }
+
+    pub fn contour_params(&self) -> &wgpu::Buffer {
+        &self.contour_params
+    }
+
+    pub fn agent_colors(&self) -> &wgpu::Buffer {
+        &self.agent_colors
+    }
+
+    pub fn agent_view(&self) -> &wgpu::Buffer {
+        &self.agent_view
+    }
+}
 
 /// Simple renderer for displaying particles
+///
+/// Agents go through a compute pass before they are drawn: zoomed in, it culls
+/// them to the view into an indirect draw list, so only visible quads reach the
+/// vertex shader; zoomed out (`AgentDraw::Density`), it counts them per cell
+/// and a full-screen pass draws those counts instead of a quad per agent. The
+/// kernel density (`AgentDraw::Kde`) smooths the same counts with a Gaussian,
+/// along rows in a second compute dispatch and along columns as it is drawn.
 pub struct Renderer {
     render_pipeline: wgpu::RenderPipeline,
+    density_pipeline: wgpu::RenderPipeline,
+    kde_pipeline: wgpu::RenderPipeline,
+    cull_pipeline: wgpu::ComputePipeline,
+    deposit_pipeline: wgpu::ComputePipeline,
+    smooth_rows_pipeline: wgpu::ComputePipeline,
+    draw_args: wgpu::Buffer,            // Indirect particle draw, filled by the cull pass
+    visible: Option<wgpu::Buffer>,      // Slots left by the cull pass, grown with the agent count
+    density: Option<wgpu::Buffer>,      // Per-cell species counts, made on the first zoomed-out frame
+    kde_rows: Option<wgpu::Buffer>,     // Counts smoothed along rows, made on the first kernel density frame
     field_bg_pipeline: wgpu::RenderPipeline,
+    minimap_pipeline: wgpu::RenderPipeline,
+    contour_pipeline: wgpu::RenderPipeline,
+    timeline_pipeline: wgpu::RenderPipeline,
+    bind_groups: Vec<(BindGroupKey, wgpu::BindGroup)>, // Least recently used first
+    bind_groups_created: u64,
 }
 
 impl Renderer {
@@ -21,13 +154,47 @@ impl Renderer {
         // Create particle shader
         let particle_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("particle_render_shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/render.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl(vireo_core::shaders::with_agent_prelude(include_str!("../shaders/render.wgsl")).into()),
         });
 
         // Create field background shader
         let field_bg_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("field_bg_shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/field_bg.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl(vireo_core::shaders::with_field_prelude(include_str!("../shaders/field_bg.wgsl")).into()),
+        });
+
+        // Create minimap shader
+        let minimap_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("minimap_shader"),
+            source: wgpu::ShaderSource::Wgsl(vireo_core::shaders::with_field_prelude(include_str!("../shaders/minimap.wgsl")).into()),
+        });
+
+        // Create contour overlay shader
+        let contour_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("contour_shader"),
+            source: wgpu::ShaderSource::Wgsl(vireo_core::shaders::with_field_prelude(include_str!("../shaders/contours.wgsl")).into()),
+        });
+
+        // Create agent view (culling and density deposit) and density map shaders
+        let agent_view_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("agent_view_shader"),
+            source: wgpu::ShaderSource::Wgsl(vireo_core::shaders::with_agent_prelude(include_str!("../shaders/agent_view.wgsl")).into()),
+        });
+        let density_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("density_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/density.wgsl").into()),
+        });
+
+        // Create kernel density shader (row smoothing pass and colormapped draw)
+        let kde_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("kde_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/kde.wgsl").into()),
+        });
+
+        // Create pass timeline shader
+        let timeline_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("timeline_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/timeline.wgsl").into()),
         });
 
         // Use the centralized particle render layout
@@ -37,14 +204,47 @@ impl Renderer {
         // Create particle pipeline layout
         let particle_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("particle_pipeline_layout"),
-            bind_group_layouts: &[particle_bind_group_layout],
+            bind_group_layouts: &[particle_bind_group_layout, &layouts.camera],
             push_constant_ranges: &[],
         });
 
         // Create field background pipeline layout
         let field_bg_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("field_bg_pipeline_layout"),
-            bind_group_layouts: &[field_bg_bind_group_layout],
+            bind_group_layouts: &[field_bg_bind_group_layout, &layouts.camera],
+            push_constant_ranges: &[],
+        });
+
+        // Create minimap pipeline layout
+        let minimap_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("minimap_pipeline_layout"),
+            bind_group_layouts: &[&layouts.minimap],
+            push_constant_ranges: &[],
+        });
+
+        // Create contour overlay pipeline layout
+        let contour_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("contour_pipeline_layout"),
+            bind_group_layouts: &[&layouts.contours, &layouts.camera],
+            push_constant_ranges: &[],
+        });
+
+        // Create agent view and density map pipeline layouts
+        let agent_view_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("agent_view_pipeline_layout"),
+            bind_group_layouts: &[&layouts.agent_view],
+            push_constant_ranges: &[],
+        });
+        let density_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("density_pipeline_layout"),
+            bind_group_layouts: &[&layouts.agent_density, &layouts.camera],
+            push_constant_ranges: &[],
+        });
+
+        // Create pass timeline pipeline layout
+        let timeline_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("timeline_pipeline_layout"),
+            bind_group_layouts: &[&layouts.timeline],
             push_constant_ranges: &[],
         });
 
@@ -84,6 +284,105 @@ impl Renderer {
             multiview: None,
         });
 
+        // Create agent culling and density deposit compute pipelines
+        let cull_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("agent_cull_pipeline"),
+            layout: Some(&agent_view_pipeline_layout),
+            module: &agent_view_shader,
+            entry_point: "cull",
+        });
+        let deposit_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("agent_density_deposit_pipeline"),
+            layout: Some(&agent_view_pipeline_layout),
+            module: &agent_view_shader,
+            entry_point: "deposit",
+        });
+        let smooth_rows_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("agent_kde_rows_pipeline"),
+            layout: Some(&agent_view_pipeline_layout),
+            module: &kde_shader,
+            entry_point: "smooth_rows",
+        });
+
+        // Create agent density map render pipeline (translucent cells over the field, zoomed out)
+        let density_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("density_pipeline"),
+            layout: Some(&density_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &density_shader,
+                entry_point: "vs_main",
+                buffers: &[], // Fullscreen triangle
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &density_shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        // Create kernel density render pipeline (colormapped, fading into the field where agents are sparse)
+        let kde_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("kde_pipeline"),
+            layout: Some(&density_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &kde_shader,
+                entry_point: "vs_main",
+                buffers: &[], // Fullscreen triangle
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &kde_shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        let draw_args = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("agent_draw_args"),
+            size: std::mem::size_of::<wgpu::util::DrawIndirectArgs>() as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         // Create field background render pipeline
         let field_bg_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("field_bg_pipeline"),
@@ -120,34 +419,199 @@ impl Renderer {
             multiview: None,
         });
 
+        // Create minimap render pipeline (drawn into a corner viewport)
+        let minimap_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("minimap_pipeline"),
+            layout: Some(&minimap_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &minimap_shader,
+                entry_point: "vs_main",
+                buffers: &[], // Fullscreen triangle, clipped to the viewport
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &minimap_shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: None, // Opaque overlay
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        // Create contour overlay render pipeline (translucent lines over the whole view)
+        let contour_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("contour_pipeline"),
+            layout: Some(&contour_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &contour_shader,
+                entry_point: "vs_main",
+                buffers: &[], // Fullscreen triangle
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &contour_shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        // Create pass timeline render pipeline (translucent chart in a corner viewport)
+        let timeline_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("timeline_pipeline"),
+            layout: Some(&timeline_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &timeline_shader,
+                entry_point: "vs_main",
+                buffers: &[], // Fullscreen triangle, clipped to the viewport
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &timeline_shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
         Ok(Self {
             render_pipeline,
+            density_pipeline,
+            kde_pipeline,
+            cull_pipeline,
+            deposit_pipeline,
+            smooth_rows_pipeline,
+            draw_args,
+            visible: None,
+            density: None,
+            kde_rows: None,
             field_bg_pipeline,
+            minimap_pipeline,
+            contour_pipeline,
+            timeline_pipeline,
+            bind_groups: Vec::with_capacity(BIND_GROUP_CACHE),
+            bind_groups_created: 0,
         })
     }
     
-    /// Render the field background and particles
+    /// Bind groups created since `new`; flat once every field view has been drawn
+    /// unless callers pass freshly allocated resources each frame
+    pub fn bind_groups_created(&self) -> u64 {
+        self.bind_groups_created
+    }
+    
+    /// Create the bind group for `key` unless it is cached, evicting the least recently
+    /// used when full (never one prepared for the current frame)
+    fn prepare_bind_group(&mut self, key: BindGroupKey, create: impl FnOnce() -> wgpu::BindGroup) {
+        if !self.reuse_bind_group(&key) {
+            self.insert_bind_group(key, create());
+        }
+    }
+    
+    /// Mark the bind group for `key` most recently used, `false` if it is not cached
+    fn reuse_bind_group(&mut self, key: &BindGroupKey) -> bool {
+        let Some(i) = self.bind_groups.iter().position(|(k, _)| k == key) else { return false };
+        let hit = self.bind_groups.remove(i);
+        self.bind_groups.push(hit);
+        true
+    }
+    
+    fn insert_bind_group(&mut self, key: BindGroupKey, bind_group: wgpu::BindGroup) {
+        if self.bind_groups.len() == BIND_GROUP_CACHE {
+            self.bind_groups.remove(0);
+        }
+        self.bind_groups.push((key, bind_group));
+        self.bind_groups_created += 1;
+    }
+    
+    fn bind_group(&self, key: &BindGroupKey) -> &wgpu::BindGroup {
+        let (_, bind_group) = self.bind_groups.iter().find(|(k, _)| k == key).expect("bind group prepared this frame");
+        bind_group
+    }
+    
+    /// Render the field background, particles and (optionally) contours, the minimap and stacked charts
+    ///
+    /// `frame` holds this frame's buffers and views (see `FrameInputs`); the
+    /// bind groups over them use the matching `layouts`. Bind groups are reused
+    /// for as long as the same resources are passed in.
     pub fn render(
-        &self,
+        &mut self,
         device: &wgpu::Device,
         encoder: &mut CommandEncoder,
         view: &TextureView,
-        sim_params_buffer: &wgpu::Buffer,
-        particles_buffer: &wgpu::Buffer,
-        particle_count: u32,
-        render_layout: &wgpu::BindGroupLayout,
-        field_bg_layout: &wgpu::BindGroupLayout,
-        field_texture: &wgpu::TextureView,
-        field_sampler: &wgpu::Sampler,
+        layouts: &Layouts,
+        frame: &FrameInputs,
     ) -> Result<()> {
-        // Debug: log render call
-        println!("Renderer: Starting render pass");
-        println!("Renderer: Particle count: {}", particle_count);
-        
-        // Create bind group for field background rendering
-        let field_bg_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        let FrameInputs {
+            camera: camera_buffer,
+            particles,
+            agent_colors: agent_colors_buffer,
+            agent_view: agent_view_buffer,
+            field_texture,
+            field_sampler,
+            contours,
+            minimap,
+            charts,
+        } = *frame;
+
+        // Bind group for field background rendering
+        let field_bg_key = ("field_bg", [field_texture.global_id().inner(), field_sampler.global_id().inner(), 0, 0]);
+        self.prepare_bind_group(field_bg_key, || device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("field_bg_bind_group"),
-            layout: field_bg_layout,
+            layout: &layouts.field_render,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
@@ -158,23 +622,106 @@ impl Renderer {
                     resource: wgpu::BindingResource::Sampler(field_sampler),
                 },
             ],
-        });
+        }));
 
-        // Create bind group for particle rendering
-        let particle_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("particle_render_bind_group"),
-            layout: render_layout,
+        // Camera bind group shared by the field background and particles (group 1)
+        let camera_key = ("camera", [camera_buffer.global_id().inner(), 0, 0, 0]);
+        self.prepare_bind_group(camera_key, || device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("camera_bind_group"),
+            layout: &layouts.camera,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: sim_params_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: particles_buffer.as_entire_binding(),
+                    resource: camera_buffer.as_entire_binding(),
                 },
             ],
+        }));
+
+        // Agents: culled to the view and drawn as quads, or counted per cell for the density map or kernel density
+        let agent_keys = particles.map(|(particles_buffer, agent_view)| self.prepare_agent_view(
+            device,
+            (particles_buffer, agent_view),
+            [agent_colors_buffer, agent_view_buffer, camera_buffer],
+            [&layouts.particle_render, &layouts.agent_view, &layouts.agent_density],
+        ));
+        if let (Some((compute_key, smooth_key, _)), Some((_, agent_view))) = (&agent_keys, particles) {
+            let (pipeline, cleared) = if agent_view.counts_cells() {
+                (&self.deposit_pipeline, self.density.as_ref().expect("density counts prepared this frame"))
+            } else {
+                (&self.cull_pipeline, &self.draw_args)
+            };
+            encoder.clear_buffer(cleared, 0, None);
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some(match agent_view.draw() {
+                    AgentDraw::Quads => "agent cull pass",
+                    AgentDraw::Density => "agent density pass",
+                    AgentDraw::Kde => "agent kernel density pass",
+                }),
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(pipeline);
+            cpass.set_bind_group(0, self.bind_group(compute_key), &[]);
+            cpass.dispatch_workgroups(agent_view.count.div_ceil(AGENT_VIEW_WORKGROUP), 1, 1);
+            if let Some(key) = smooth_key {
+                let [w, h] = agent_view.world_size;
+                cpass.set_pipeline(&self.smooth_rows_pipeline);
+                cpass.set_bind_group(0, self.bind_group(key), &[]);
+                cpass.dispatch_workgroups(w.div_ceil(8), h.div_ceil(8), 1);
+            }
+        }
+
+        // Bind group for the contour overlay
+        let contour_key = contours.map(|contour_params_buffer| {
+            ("contours", [field_texture.global_id().inner(), field_sampler.global_id().inner(), contour_params_buffer.global_id().inner(), 0])
         });
+        if let (Some(key), Some(contour_params_buffer)) = (contour_key, contours) {
+            self.prepare_bind_group(key, || device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("contour_bind_group"),
+                layout: &layouts.contours,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(field_texture),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(field_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: contour_params_buffer.as_entire_binding(),
+                    },
+                ],
+            }));
+        }
+
+        // Bind group for the minimap overlay
+        let minimap_key = minimap.map(|(minimap_params_buffer, _)| {
+            ("minimap", [field_texture.global_id().inner(), field_sampler.global_id().inner(), minimap_params_buffer.global_id().inner(), 0])
+        });
+        if let (Some(key), Some((minimap_params_buffer, _))) = (minimap_key, minimap) {
+            self.prepare_bind_group(key, || device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("minimap_bind_group"),
+                layout: &layouts.minimap,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(field_texture),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(field_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: minimap_params_buffer.as_entire_binding(),
+                    },
+                ],
+            }));
+        }
+
+        // Bind groups for the stacked chart overlays
+        self.prepare_chart_bind_groups(device, &layouts.timeline, charts);
         
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("render_pass"),
@@ -198,15 +745,243 @@ impl Renderer {
 
         // 1. Draw field background first
         render_pass.set_pipeline(&self.field_bg_pipeline);
-        render_pass.set_bind_group(0, &field_bg_bind_group, &[]);
+        render_pass.set_bind_group(0, self.bind_group(&field_bg_key), &[]);
+        render_pass.set_bind_group(1, self.bind_group(&camera_key), &[]);
         render_pass.draw(0..3, 0..1); // Fullscreen triangle
 
-        // 2. Draw particles on top
-        render_pass.set_pipeline(&self.render_pipeline);
-        render_pass.set_bind_group(0, &particle_bind_group, &[]);
-        render_pass.draw(0..6, 0..particle_count); // 6 vertices per quad, particle_count instances
+        // 2. Draw particles on top: a quad per visible agent, the density map or the kernel density
+        if let (Some((_, _, draw_key)), Some((_, agent_view))) = (&agent_keys, particles) {
+            render_pass.set_bind_group(0, self.bind_group(draw_key), &[]);
+            render_pass.set_bind_group(1, self.bind_group(&camera_key), &[]);
+            match agent_view.draw() {
+                AgentDraw::Quads => {
+                    render_pass.set_pipeline(&self.render_pipeline);
+                    render_pass.draw_indirect(&self.draw_args, 0); // 6 vertices per quad, one instance per visible agent
+                }
+                AgentDraw::Density => {
+                    render_pass.set_pipeline(&self.density_pipeline);
+                    render_pass.draw(0..3, 0..1);
+                }
+                AgentDraw::Kde => {
+                    render_pass.set_pipeline(&self.kde_pipeline);
+                    render_pass.draw(0..3, 0..1);
+                }
+            }
+        }
+
+        // 3. Contour lines over the agents
+        if let Some(key) = &contour_key {
+            render_pass.set_pipeline(&self.contour_pipeline);
+            render_pass.set_bind_group(0, self.bind_group(key), &[]);
+            render_pass.set_bind_group(1, self.bind_group(&camera_key), &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        // 4. Minimap overlay in its corner viewport
+        if let (Some(key), Some((_, [x, y, w, h]))) = (&minimap_key, minimap) {
+            render_pass.set_viewport(x, y, w, h, 0.0, 1.0);
+            render_pass.set_pipeline(&self.minimap_pipeline);
+            render_pass.set_bind_group(0, self.bind_group(key), &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        // 5. Stacked chart overlays in their corner viewports
+        self.draw_charts(&mut render_pass, charts);
 
-        println!("Renderer: Render pass completed");
         Ok(())
     }
+
+    /// Allocate the agent view buffers `agent_view` needs and prepare its bind
+    /// groups, returning the keys of the compute pass's, the kernel density row
+    /// pass's (if any) and the draw's
+    ///
+    /// `uniforms` are the agent color, agent view and camera buffers, `layouts`
+    /// the particle render, agent view and agent density layouts.
+    ///
+    /// The visible list grows in powers of two with the agent count; the
+    /// density counts and smoothed rows are made on the first frame that needs
+    /// them and kept.
+    fn prepare_agent_view(
+        &mut self,
+        device: &wgpu::Device,
+        (particles_buffer, agent_view): (&wgpu::Buffer, &AgentViewParams),
+        uniforms: [&wgpu::Buffer; 3],
+        layouts: [&wgpu::BindGroupLayout; 3],
+    ) -> (BindGroupKey, Option<BindGroupKey>, BindGroupKey) {
+        let [agent_colors_buffer, agent_view_buffer, camera_buffer] = uniforms;
+        let [render_layout, agent_view_layout, agent_density_layout] = layouts;
+        let storage = |label, bytes: u64| device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size: bytes.max(4),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        // Out of `self` while their bind groups are made, put back below
+        let cells = agent_view.world_size[0] as u64 * agent_view.world_size[1] as u64;
+        let output = if agent_view.counts_cells() {
+            self.density.take().filter(|counts| counts.size() == cells * 3 * 4)
+                .unwrap_or_else(|| storage("agent_density_counts", cells * 3 * 4))
+        } else {
+            let bytes = agent_view.count.max(1).next_power_of_two() as u64 * 4;
+            self.visible.take().filter(|visible| visible.size() >= bytes)
+                .unwrap_or_else(|| storage("agent_visible_slots", bytes))
+        };
+        let rows = (agent_view.draw() == AgentDraw::Kde).then(|| {
+            self.kde_rows.take().filter(|rows| rows.size() == cells * 4)
+                .unwrap_or_else(|| storage("agent_kde_rows", cells * 4))
+        });
+
+        let compute_key = (
+            if agent_view.counts_cells() { "agent_density_deposit" } else { "agent_cull" },
+            [particles_buffer.global_id().inner(), agent_view_buffer.global_id().inner(),
+                camera_buffer.global_id().inner(), output.global_id().inner()],
+        );
+        if !self.reuse_bind_group(&compute_key) {
+            let bind_group = Self::agent_view_bind_group(device, agent_view_layout,
+                [particles_buffer, agent_view_buffer, camera_buffer, &output, &self.draw_args]);
+            self.insert_bind_group(compute_key, bind_group);
+        }
+        let smooth_key = rows.as_ref().map(|rows| {
+            let key = ("agent_kde_rows", [particles_buffer.global_id().inner(), agent_view_buffer.global_id().inner(),
+                output.global_id().inner(), rows.global_id().inner()]);
+            self.prepare_bind_group(key, || Self::agent_view_bind_group(device, agent_view_layout,
+                [particles_buffer, agent_view_buffer, camera_buffer, &output, rows]));
+            key
+        });
+
+        // Density map and kernel density both draw one storage buffer of cells
+        let mut draw_cells = |name, cells: &wgpu::Buffer| {
+            let key = (name, [cells.global_id().inner(), agent_view_buffer.global_id().inner(), 0, 0]);
+            self.prepare_bind_group(key, || device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("agent_density_bind_group"),
+                layout: agent_density_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: cells.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: agent_view_buffer.as_entire_binding(),
+                    },
+                ],
+            }));
+            key
+        };
+        let draw_key = match (agent_view.draw(), &rows) {
+            (AgentDraw::Kde, Some(rows)) => draw_cells("agent_kde", rows),
+            (AgentDraw::Density, _) => draw_cells("agent_density", &output),
+            _ => {
+                let key = ("particle_render", [particles_buffer.global_id().inner(), agent_colors_buffer.global_id().inner(),
+                    output.global_id().inner(), 0]);
+                self.prepare_bind_group(key, || device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("particle_render_bind_group"),
+                    layout: render_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: particles_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: agent_colors_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: output.as_entire_binding(),
+                        },
+                    ],
+                }));
+                key
+            }
+        };
+        if agent_view.counts_cells() {
+            self.density = Some(output);
+        } else {
+            self.visible = Some(output);
+        }
+        if rows.is_some() {
+            self.kde_rows = rows;
+        }
+        (compute_key, smooth_key, draw_key)
+    }
+
+    /// Agent view layout bind group: agents, view params, camera, then the two read-write buffers
+    fn agent_view_bind_group(device: &wgpu::Device, layout: &wgpu::BindGroupLayout, buffers: [&wgpu::Buffer; 5]) -> wgpu::BindGroup {
+        let entries: Vec<_> = buffers.iter().enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry { binding: binding as u32, resource: buffer.as_entire_binding() })
+            .collect();
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("agent_view_bind_group"),
+            layout,
+            entries: &entries,
+        })
+    }
+
+    /// Render only `charts` (as in `render`) over a plain background, for the detached metrics window
+    ///
+    /// The target must have the format the renderer was created for.
+    pub fn render_charts(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut CommandEncoder,
+        view: &TextureView,
+        timeline_layout: &wgpu::BindGroupLayout,
+        charts: &[(&wgpu::Buffer, &wgpu::Buffer, [f32; 4])],
+    ) {
+        self.prepare_chart_bind_groups(device, timeline_layout, charts);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("metrics_render_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.05, g: 0.05, b: 0.05, a: 1.0 }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        self.draw_charts(&mut render_pass, charts);
+    }
+
+    fn chart_key(chart_params_buffer: &wgpu::Buffer, stacked_buffer: &wgpu::Buffer) -> BindGroupKey {
+        ("timeline", [chart_params_buffer.global_id().inner(), stacked_buffer.global_id().inner(), 0, 0])
+    }
+
+    fn prepare_chart_bind_groups(
+        &mut self,
+        device: &wgpu::Device,
+        timeline_layout: &wgpu::BindGroupLayout,
+        charts: &[(&wgpu::Buffer, &wgpu::Buffer, [f32; 4])],
+    ) {
+        for &(chart_params_buffer, stacked_buffer, _) in charts {
+            self.prepare_bind_group(Self::chart_key(chart_params_buffer, stacked_buffer), || device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("timeline_bind_group"),
+                layout: timeline_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: chart_params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: stacked_buffer.as_entire_binding(),
+                    },
+                ],
+            }));
+        }
+    }
+
+    /// Draw each chart with the timeline pipeline in its viewport
+    fn draw_charts<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, charts: &[(&wgpu::Buffer, &wgpu::Buffer, [f32; 4])]) {
+        for &(chart_params_buffer, stacked_buffer, [x, y, w, h]) in charts {
+            render_pass.set_viewport(x, y, w, h, 0.0, 1.0);
+            render_pass.set_pipeline(&self.timeline_pipeline);
+            render_pass.set_bind_group(0, self.bind_group(&Self::chart_key(chart_params_buffer, stacked_buffer)), &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
 }
@@ -0,0 +1,172 @@
+//! Per-user viewer settings kept between launches
+//!
+//! The viewer loads `viewer.yaml` from the user's config directory on startup
+//! and writes it back on exit: window placement, the last config opened, the
+//! camera, overlay and contour toggles, which panels are shown, whether the metrics
+//! window was open (and where) and how frames are presented. Nothing here
+//! affects the simulation itself.
+
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+/// Window placement in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowPlacement {
+    pub size: [u32; 2],
+    pub position: Option<[i32; 2]>, // Outer top-left corner (`None` where the platform cannot report it)
+}
+
+/// Camera of the last session, kept only for a world of the same size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraState {
+    pub world_size: [u32; 2],
+    pub center: [f32; 2],
+    pub zoom: f32,
+}
+
+/// Field overlay toggles (at most one is on)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Overlays {
+    pub r_field: bool,
+    pub w_field: bool,
+    pub occupancy: bool,
+    pub gradients: bool,
+}
+
+impl Default for Overlays {
+    fn default() -> Self {
+        Self { r_field: true, w_field: false, occupancy: false, gradients: false }
+    }
+}
+
+/// Panels drawn over the world view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Panels {
+    pub minimap: bool,
+    pub timeline: bool,
+    pub age_chart: bool,
+}
+
+impl Default for Panels {
+    fn default() -> Self {
+        Self { minimap: true, timeline: false, age_chart: false }
+    }
+}
+
+/// Contour overlay toggle and density
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContourSettings {
+    pub visible: bool,
+    pub levels: u32, // Lines below the carrying capacity
+}
+
+impl Default for ContourSettings {
+    fn default() -> Self {
+        Self { visible: false, levels: 8 }
+    }
+}
+
+/// How finished frames reach the screen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum PresentMode {
+    /// Wait for vertical blank: no tearing, capped at the refresh rate (supported everywhere)
+    #[default]
+    Fifo,
+    /// Replace the queued frame at vertical blank: no tearing, lower latency
+    Mailbox,
+    /// Present at once: lowest latency, may tear
+    Immediate,
+}
+
+impl PresentMode {
+    pub fn to_wgpu(self) -> wgpu::PresentMode {
+        match self {
+            PresentMode::Fifo => wgpu::PresentMode::Fifo,
+            PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+            PresentMode::Immediate => wgpu::PresentMode::Immediate,
+        }
+    }
+
+    /// The wgpu mode, or Fifo when the surface does not offer it
+    pub fn choose(self, supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+        Some(self.to_wgpu()).filter(|m| supported.contains(m)).unwrap_or(wgpu::PresentMode::Fifo)
+    }
+}
+
+/// Present mode, queue depth and frame rate of the viewer's surfaces
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub present_mode: PresentMode,
+    pub frame_latency: u32, // Frames queued ahead of the display (1 = least lag, 3 = smoothest)
+    pub max_fps: f64,       // Frames drawn per second at most (0 = uncapped); steps keep `--steps-per-second`
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self { present_mode: PresentMode::Fifo, frame_latency: 2, max_fps: 60.0 }
+    }
+}
+
+impl DisplaySettings {
+    /// Shortest time between frames (zero when uncapped)
+    pub fn frame_interval(&self) -> std::time::Duration {
+        if self.max_fps > 0.0 && self.max_fps.is_finite() {
+            std::time::Duration::from_secs_f64(1.0 / self.max_fps)
+        } else {
+            std::time::Duration::ZERO
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewerSettings {
+    pub window: Option<WindowPlacement>,
+    pub last_config: Option<PathBuf>,
+    pub camera: Option<CameraState>,
+    pub overlays: Overlays,
+    pub panels: Panels,
+    pub contours: ContourSettings,
+    pub metrics_window: Option<WindowPlacement>, // Reopened at launch; `None` when it was closed
+    pub display: DisplaySettings,                 // Last `--present-mode`, `--frame-latency` and `--max-fps`
+}
+
+impl ViewerSettings {
+    /// `vireo/viewer.yaml` under `$XDG_CONFIG_HOME`, `~/.config` or `%APPDATA%`
+    /// (`None` when none of them is set)
+    pub fn default_path() -> Option<PathBuf> {
+        let env = |name| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+        let dir = env("XDG_CONFIG_HOME")
+            .or_else(|| env("HOME").map(|home| home.join(".config")))
+            .or_else(|| env("APPDATA"))?;
+        Some(dir.join("vireo").join("viewer.yaml"))
+    }
+
+    /// Settings stored at `path`; a missing file gives the defaults
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_yaml::from_str(&text).map_err(|e| format!("cannot parse {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Write the settings to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(path, text).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+
+    /// The saved camera if it was for a world of `world_size`
+    pub fn camera_for(&self, world_size: [u32; 2]) -> Option<CameraState> {
+        self.camera.filter(|c| c.world_size == world_size && c.zoom.is_finite() && c.zoom > 0.0)
+    }
+}
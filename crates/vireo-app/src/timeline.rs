@@ -0,0 +1,104 @@
+//! Pass timeline overlay: stacked area chart of per-pass frame times
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+use vireo_core::gpu::PassTimeline;
+
+/// Frames shown across the chart
+pub const TIMELINE_FRAMES: usize = 300;
+/// Chart size as a fraction of the window (width, height)
+const TIMELINE_FRACTION: [f32; 2] = [0.45, 0.2];
+/// Gap between the chart and the window edge, in pixels
+const TIMELINE_MARGIN: f32 = 12.0;
+/// Smallest vertical range, in ms (keeps an idle chart from magnifying noise)
+const MIN_SCALE_MS: f32 = 2.0;
+/// Frame budget marked across the chart, in ms (60 FPS)
+const FRAME_BUDGET_MS: f32 = 16.667;
+
+/// Timeline shader uniform (binding 0 of the timeline layout)
+///
+/// The age chart draws through the same shader, with head counts for values.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct TimelineParams {
+    pub frames: u32,   // Columns held, right-aligned in the chart
+    pub capacity: u32, // Column slots across the chart
+    pub layers: u32,   // Named layers (legend entries)
+    pub scale: f32,    // Value at the top edge
+    pub marker: f32,   // Value of the horizontal reference line (0 = none)
+    pub _pad: [u32; 3],
+}
+
+/// Rolling per-pass timings and the buffers the chart reads them from
+///
+/// Both buffers are allocated once; `upload` rewrites them in place.
+pub struct Timeline {
+    pub visible: bool,
+    pub history: PassTimeline,
+    params_buffer: wgpu::Buffer,
+    frames_buffer: wgpu::Buffer,
+}
+
+impl Timeline {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let history = PassTimeline::new(TIMELINE_FRAMES);
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("timeline_params"),
+            contents: bytemuck::cast_slice(&[TimelineParams::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let frames_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("timeline_frames"),
+            size: (TIMELINE_FRAMES * PassTimeline::MAX_LAYERS * std::mem::size_of::<f32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self { visible: false, history, params_buffer, frames_buffer }
+    }
+
+    /// Shader uniform for the frames held now
+    pub fn params(&self) -> TimelineParams {
+        TimelineParams {
+            frames: self.history.len() as u32,
+            capacity: self.history.capacity() as u32,
+            layers: self.history.names().len() as u32,
+            scale: (self.history.peak() * 1.1).max(MIN_SCALE_MS),
+            marker: FRAME_BUDGET_MS,
+            _pad: [0; 3],
+        }
+    }
+
+    /// Copy the held frames into the chart buffers
+    pub fn upload(&self, queue: &wgpu::Queue) {
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[self.params()]));
+        let stacked = self.history.stacked();
+        if !stacked.is_empty() {
+            queue.write_buffer(&self.frames_buffer, 0, bytemuck::cast_slice(&stacked));
+        }
+    }
+
+    /// Params uniform and stacked frame times, in binding order
+    pub fn buffers(&self) -> (&wgpu::Buffer, &wgpu::Buffer) {
+        (&self.params_buffer, &self.frames_buffer)
+    }
+
+    /// Viewport in the bottom-left corner as (x, y, width, height) pixels
+    pub fn viewport(&self, surface_size: [u32; 2]) -> [f32; 4] {
+        let w = (surface_size[0] as f32 * TIMELINE_FRACTION[0]).max(1.0);
+        let h = (surface_size[1] as f32 * TIMELINE_FRACTION[1]).max(1.0);
+        let y = (surface_size[1] as f32 - h - TIMELINE_MARGIN).max(0.0);
+        [TIMELINE_MARGIN, y, w, h]
+    }
+
+    /// One line per pass: mean over the held frames and the shader's colour
+    pub fn legend(&self) -> Vec<String> {
+        self.history.names().iter().zip(self.history.means()).enumerate()
+            .map(|(k, (name, mean))| format!("{} {}: {:.3} ms", LEGEND_COLOURS[k], name, mean))
+            .collect()
+    }
+}
+
+/// Colour names of the layers, in the order of `PALETTE` in timeline.wgsl
+pub(crate) const LEGEND_COLOURS: [&str; PassTimeline::MAX_LAYERS] = [
+    "[blue]", "[orange]", "[green]", "[red]", "[purple]", "[yellow]", "[cyan]", "[grey]",
+];
@@ -1,35 +1,63 @@
 //! Interactive viewer for the Vireo ecosystem simulation
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use winit::{
-    event::{Event, WindowEvent, ElementState, KeyEvent},
+    event::{Event, WindowEvent, ElementState, KeyEvent, MouseButton, MouseScrollDelta},
     event_loop::EventLoop,
     window::{Window, WindowBuilder},
-    dpi::LogicalSize,
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
 };
 use wgpu::{Instance, Device, Queue, Surface, SurfaceConfiguration, RequestAdapterOptions, util::DeviceExt};
 use anyhow::Result;
 use bytemuck;
 
-use vireo_params::SimulationConfig;
+use vireo_core::SimulationConfig;
 use vireo_core::{
-    gpu::{FieldPingPong, ComputePipelines},
+    gpu::{FieldPingPong, ComputePipelines, AgentBindInputs, AgentCuller, AgentRanges, BloomInjector, DemographyPass, DeviceWatchdog, DiffusionMap, EnergyProbe, EventCounters, ForagingTally, GpuProfiler, ObstacleMap, OccupancyClear, RdBindInputs, RewindRing, Steering, TerrainMap, read_buffer_from},
     gpu::layouts::Layouts,
-    sim::{FieldManager, AgentManager},
-    RDParams, AgentParams,
+    sim::{Agent, AgentPopulation, AgeStructure, EnergyBudget, FieldManager, AgentManager, ChunkStreamer, CohortTracker, CullRegion, Interaction, InteractionScript, ObstacleBrush, ParamForcing, ParamPerturbation, SimRng, StateSnapshot, StepClock},
+    RDParams, AgentParams, TimeUniform,
 };
 
-use crate::renderer::Renderer;
+use vireo_app::renderer::{FrameInputs, FrameUniforms, Renderer};
+use vireo_app::camera::Camera;
+use vireo_app::console::{Console, ConsoleCommand, CONSOLE_USAGE};
+use vireo_app::metrics_window::MetricsWindow;
+use vireo_app::settings::{CameraState, ContourSettings, Overlays, Panels, ViewerSettings, WindowPlacement};
+use vireo_app::age_chart::AgeChart;
+use vireo_app::agent_view::AgentViewParams;
+use vireo_app::contours::Contours;
+use vireo_app::minimap::Minimap;
+use vireo_app::palette::AgentColorParams;
+use vireo_app::timeline::Timeline;
+
+/// Main window title (the console line is appended while it is open)
+const WINDOW_TITLE: &str = "Vireo Ecosystem Simulation";
+
+/// Radius (cells) of the disc tagged around the cursor with `T`
+const TAG_RADIUS: f32 = 8.0;
+
+/// Radius (cells) of the wall tool brush
+const WALL_BRUSH_RADIUS: f32 = 3.0;
+
+/// Arrow-key pan step as a fraction of the half view (clip units)
+const PAN_STEP: f32 = 0.25;
 
 /// Simulation controls for play/pause/speed/reset
 #[derive(Debug)]
 struct Controls {
     paused: bool,
     speed: f32,
-    step_once: bool,
+    steps_pending: u32, // Steps to run on top of the clock (`s`, console `step N`)
     emissions_enabled: bool,
     reset_requested: bool,
+    tag_requested: bool,
+    inspect_requested: bool,
+    rewind_requested: bool,
+    metrics_window_requested: bool, // Open or close the metrics window (needs the event loop)
+    command_requested: Option<ConsoleCommand>, // Console command to run on the next update
 }
 
 impl Default for Controls {
@@ -37,31 +65,53 @@ impl Default for Controls {
         Self {
             paused: false,
             speed: 1.0,
-            step_once: false,
+            steps_pending: 0,
             emissions_enabled: true,
             reset_requested: false,
+            tag_requested: false,
+            inspect_requested: false,
+            rewind_requested: false,
+            metrics_window_requested: false,
+            command_requested: None,
         }
     }
 }
 
-/// Fixed timestep clock for stable simulation
+/// Shape culled by a left-drag in the world view
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CullTool {
+    Off,
+    Rect,   // Drag corner to corner
+    Circle, // Drag from centre to edge
+}
+
+/// What a left-drag does to the obstacle walls
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WallTool {
+    Off,
+    Draw,
+    Erase,
+}
+
+/// Fixed timestep clock for stable simulation, independent of the frame rate
 #[derive(Debug)]
 struct Clock {
     last: Instant,
-    acc: f32,
+    steps: StepClock,
 }
 
 impl Clock {
-    fn new() -> Self {
+    fn new(steps: StepClock) -> Self {
         Self {
             last: Instant::now(),
-            acc: 0.0,
+            steps,
         }
     }
     
-    fn advance(&mut self) -> f32 {
+    /// Real time since the previous call
+    fn advance(&mut self) -> Duration {
         let now = Instant::now();
-        let dt = (now - self.last).as_secs_f32();
+        let dt = now - self.last;
         self.last = now;
         dt
     }
@@ -73,6 +123,34 @@ pub struct GpuContext {
     pub queue: Queue,
     pub surface: Surface<'static>,
     pub config: SurfaceConfiguration,
+    pub watchdog: DeviceWatchdog,
+}
+
+impl GpuContext {
+    /// Replace a lost device with a new one from `adapter` and reconfigure the surface
+    pub fn recreate_device(&mut self, adapter: &wgpu::Adapter) {
+        let (device, queue) = pollster::block_on(request_device(adapter));
+        self.watchdog = DeviceWatchdog::attach(&device);
+        self.device = device;
+        self.queue = queue;
+        self.surface.configure(&self.device, &self.config);
+    }
+}
+
+/// Device with the features the viewer uses (timestamps when the adapter has them)
+async fn request_device(adapter: &wgpu::Adapter) -> (Device, Queue) {
+    adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                // Timestamps feed the pass timeline when the adapter has them
+                required_features: adapter.features() & wgpu::Features::TIMESTAMP_QUERY,
+                required_limits: wgpu::Limits::default(),
+                label: None,
+            },
+            None,
+        )
+        .await
+        .expect("Failed to create device")
 }
 
 /// Main viewer state
@@ -87,15 +165,38 @@ pub struct Viewer {
     agent_manager: AgentManager,
     field_textures: FieldPingPong,
     compute_pipelines: ComputePipelines,
+    blooms: BloomInjector,
+    obstacles: ObstacleMap,
+    terrain: TerrainMap,
+    diffusion: DiffusionMap,
+    agent_ranges: AgentRanges,
+    cohorts: CohortTracker,
+    perturbation: Option<ParamPerturbation>,
+    forcing: Option<ParamForcing>,
+    streamer: Option<ChunkStreamer>,
+    population: Option<AgentPopulation>, // `--init-agents`, placed instead of spawning on start and reset
     
     // GPU buffers
     rd_params_buffer: wgpu::Buffer,
     agent_params_buffer: wgpu::Buffer,
-    agents_buffer: wgpu::Buffer,
+    time_buffer: wgpu::Buffer,
+    // Agent buffer, cull and steering passes and agent bind groups are `None` without agent slots
+    agents_buffer: Option<wgpu::Buffer>,
     occupancy_buffer: wgpu::Buffer,
+    occupancy_clear: OccupancyClear,
+    culler: Option<AgentCuller>,
+    demography: Option<DemographyPass>,
+    steering: Option<Steering>,
+    foraging: Option<ForagingTally>,
+    events: EventCounters, // Never counted: the viewer writes no event rasters
+    energy_probe: EnergyProbe, // Energy log of the agent selected with `I`
+    
+    // Agent pass bind groups reading field A / field B (rebuilt with the field textures)
+    agent_bind_groups: Option<[wgpu::BindGroup; 2]>,
     
-    // Field sampler for rendering
+    // Field sampler and per-frame uniforms for rendering
     field_sampler: wgpu::Sampler,
+    frame_uniforms: FrameUniforms,
     
     // Simulation parameters
     sim_config: SimulationConfig,
@@ -106,6 +207,42 @@ pub struct Viewer {
     controls: Controls,
     clock: Clock,
     
+    // Latest state to rebuild from after a device loss (every gpu.checkpoint_every steps)
+    checkpoint: Option<StateSnapshot>,
+    
+    // Recent field states on the GPU to rewind to (every gpu.rewind_every steps)
+    rewind: Option<RewindRing>,
+    
+    // Camera, minimap and contour overlay
+    camera: Camera,
+    minimap: Minimap,
+    contours: Contours,
+    cursor: [f32; 2],
+    
+    // Per-pass timings and their timeline chart
+    profiler: GpuProfiler,
+    timeline: Timeline,
+    
+    // Age structure chart, refreshed with the HUD
+    age_chart: AgeChart,
+    
+    // Second window the charts move to while it is open
+    metrics_window: Option<MetricsWindow>,
+    
+    // Cull tool: active shape and world-space drag start
+    cull_tool: CullTool,
+    cull_drag: Option<[f32; 2]>,
+    
+    // Wall tool: active mode and whether the left button is painting
+    wall_tool: WallTool,
+    wall_painting: bool,
+    
+    // Command line opened with the backtick
+    console: Console,
+    
+    // Interaction script written by --record: path and the events so far
+    recording: Option<(PathBuf, InteractionScript)>,
+    
     // Overlay state
     show_r_field: bool,
     show_w_field: bool,
@@ -120,22 +257,33 @@ impl Viewer {
         window: Arc<Window>, 
         gpu: &GpuContext,
         sim_config: SimulationConfig,
+        step_clock: StepClock,
+        population: Option<AgentPopulation>,
     ) -> Result<Self> {
         // Create centralized layouts first
         let layouts = Layouts::new(&gpu.device);
         
         // Create simulation components
         let field_manager = FieldManager::new(sim_config.world.size);
-        let agent_manager = AgentManager::new(
-            sim_config.agents.herbivores,
-            [sim_config.world.size[0] as f32, sim_config.world.size[1] as f32],
-            sim_config.agents.E0,
-            sim_config.world.seed,
-        );
+        let mut agent_manager = Self::initial_agents(&sim_config, population.as_ref());
+        let agent_ranges = AgentRanges::new(&gpu.device, &agent_manager.layout);
+        
+        // Bake the obstacle distance field and move agents out of the walls
+        let obstacles = ObstacleMap::new(&gpu.device, &gpu.queue, &sim_config);
+        obstacles.field.push_out(&mut agent_manager.agents);
+        let terrain = TerrainMap::new(&gpu.device, &gpu.queue, &sim_config);
+        let diffusion = DiffusionMap::new(&gpu.device, &gpu.queue, &sim_config);
         
         // Seed the field
         let mut field_manager = field_manager;
-        field_manager.seed_resources(sim_config.world.seed);
+        field_manager.seed_resources_with(&mut SimRng::from_world(&sim_config.world));
+        
+        // Streaming mode replaces the seeded field with the procedural landscape
+        let streamer = sim_config.streaming.enabled.then(|| {
+            let mut streamer = ChunkStreamer::new(&sim_config.streaming, &sim_config.world);
+            streamer.fill(&mut field_manager);
+            streamer
+        });
         
         // Create GPU resources using centralized layouts
         let compute_pipelines = ComputePipelines::new(&gpu.device, &layouts);
@@ -156,10 +304,19 @@ impl Viewer {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
         
-        let agents_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("agents_buffer"),
-            contents: bytemuck::cast_slice(&agent_manager.agents),
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        let time_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("time"),
+            contents: bytemuck::cast_slice(&[TimeUniform::new(&sim_config.world, 0)]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        
+        // wgpu rejects zero-sized buffers, so a world without agents gets none
+        let agents_buffer = (!agent_manager.agents.is_empty()).then(|| {
+            gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("agents_buffer"),
+                contents: bytemuck::cast_slice(&agent_manager.agents),
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            })
         });
         
         // Create occupancy buffer
@@ -171,6 +328,34 @@ impl Viewer {
             mapped_at_creation: false,
         });
         
+        // Create the GPU clear pass for the occupancy grid
+        let occupancy_clear = OccupancyClear::new(&gpu.device, &layouts, &occupancy_buffer, sim_config.world.size);
+        
+        // Create the region cull pass for the cull tool
+        let culler = agents_buffer.as_ref().map(|buffer| {
+            AgentCuller::new(&gpu.device, &layouts, buffer, agent_manager.agents.len() as u32)
+        });
+        
+        // Create the (idle) event counters the agent and demography passes bind
+        let events = EventCounters::new(&gpu.device, sim_config.world.size, false);
+        let energy_probe = EnergyProbe::new(&gpu.device);
+        
+        // Create the demographic noise pass when enabled
+        let demography = agents_buffer.as_ref().filter(|_| sim_config.demography.enabled).map(|buffer| {
+            DemographyPass::new(&gpu.device, &layouts, &sim_config, buffer, &agent_ranges, &events)
+        });
+        
+        // Create the steering weights and neighbour grid of the agent pass
+        let steering = agents_buffer.as_ref().map(|buffer| {
+            Steering::new(&gpu.device, &layouts, &sim_config, buffer, agent_manager.agents.len() as u32)
+        });
+        
+        // Create the per-slot consumption and distance tally of the agent pass
+        let foraging = agents_buffer.as_ref().map(|_| ForagingTally::new(&gpu.device, &agent_manager.forage));
+        
+        // Create bloom injector (owns the sigma_R boost map read by the RD pass)
+        let blooms = BloomInjector::new(&gpu.device, &layouts, &sim_config);
+        
         // Create field sampler
         let field_sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("field_sampler"),
@@ -185,41 +370,260 @@ impl Viewer {
         });
         
         // Create FieldPingPong with centralized layouts
-        let field_textures = FieldPingPong::new(
-            &gpu.device,
-            sim_config.world.size,
-            &layouts,
-            &rd_params_buffer,
-            &occupancy_buffer,
-            &field_sampler,
-        );
+        let rd_inputs = RdBindInputs {
+            rd_params: &rd_params_buffer,
+            occupancy: &occupancy_buffer,
+            sigma_boost: blooms.boost_buffer(),
+            obstacles: obstacles.view(),
+            elevation: terrain.view(),
+            diffusion: diffusion.view(),
+            time: &time_buffer,
+        };
+        let field_textures = FieldPingPong::new(&gpu.device, sim_config.world.size, &layouts, &rd_inputs, &field_sampler);
         
         // Upload initial data
-        field_textures.upload_field_data(&gpu.queue, &field_manager);
+        field_textures.upload_field_data(&gpu.queue, &field_manager).map_err(anyhow::Error::msg)?;
         
-        Ok(Self {
+        let agent_bind_groups = agents_buffer.as_ref().zip(steering.as_ref()).zip(foraging.as_ref()).map(|((buffer, steering), foraging)| Self::create_agent_bind_groups(
+            &gpu.device, &layouts, &field_textures, &AgentBindInputs {
+                agents: buffer,
+                agent_params: &agent_params_buffer,
+                occupancy: &occupancy_buffer,
+                obstacles: obstacles.view(),
+                elevation: terrain.view(),
+                time: &time_buffer,
+                ranges: &agent_ranges,
+                steering,
+                foraging,
+                events: &events,
+                energy_probe: &energy_probe,
+            },
+        ));
+        
+        let mut viewer = Self {
             window,
             layouts,
             field_manager,
             agent_manager,
             field_textures,
             compute_pipelines,
+            blooms,
+            obstacles,
+            terrain,
+            diffusion,
+            agent_ranges,
+            cohorts: CohortTracker::new(),
+            streamer,
+            population,
             rd_params_buffer,
             agent_params_buffer,
+            time_buffer,
             agents_buffer,
             occupancy_buffer,
+            occupancy_clear,
+            culler,
+            demography,
+            steering,
+            foraging,
+            events,
+            energy_probe,
+            agent_bind_groups,
             field_sampler,
-            sim_config,
+            frame_uniforms: FrameUniforms::new(&gpu.device),
             current_step: 0,
             frame_count: 0,
             controls: Controls::default(),
-            clock: Clock::new(),
+            clock: Clock::new(step_clock),
+            checkpoint: None,
+            rewind: (sim_config.gpu.rewind_slots > 0)
+                .then(|| RewindRing::new(&gpu.device, sim_config.world.size, sim_config.gpu.rewind_slots)),
+            camera: Camera::fit(sim_config.world.size),
+            minimap: Minimap::default(),
+            contours: Contours::default(),
+            cursor: [0.0, 0.0],
+            profiler: GpuProfiler::new(&gpu.device, &gpu.queue),
+            timeline: Timeline::new(&gpu.device),
+            age_chart: AgeChart::new(&gpu.device, sim_config.output.age_bin, sim_config.output.age_classes),
+            metrics_window: None,
+            cull_tool: CullTool::Off,
+            cull_drag: None,
+            wall_tool: WallTool::Off,
+            wall_painting: false,
+            console: Console::default(),
+            recording: None,
+            perturbation: ParamPerturbation::new(&sim_config),
+            forcing: ParamForcing::new(&sim_config),
+            sim_config,
             show_r_field: true,
             show_w_field: false,
             show_occupancy: false,
             show_gradients: false,
             scenario_mode: None,
-        })
+        };
+        viewer.checkpoint = viewer.checkpoints_enabled().then(|| viewer.cpu_state());
+        viewer.capture_rewind(gpu);
+        Ok(viewer)
+    }
+    
+    fn checkpoints_enabled(&self) -> bool {
+        self.sim_config.gpu.checkpoint_every > 0
+    }
+    
+    /// Field and agents as last held on the CPU
+    fn cpu_state(&self) -> StateSnapshot {
+        StateSnapshot {
+            step: self.current_step,
+            size: self.sim_config.world.size,
+            field: self.field_manager.cells(),
+            agents: self.agent_manager.agents.clone(),
+        }
+    }
+    
+    /// Read back the field and agents for recovery after a device loss
+    fn take_checkpoint(&mut self, gpu: &GpuContext) {
+        // Reading back from a lost device would panic; keep the last good state
+        if gpu.watchdog.check(&gpu.device).is_some() {
+            return;
+        }
+        if let Err(e) = self.field_textures.download_field_data(&gpu.device, &gpu.queue, &mut self.field_manager) {
+            eprintln!("Warning: checkpoint skipped, field readback failed: {}", e);
+            return;
+        }
+        self.sync_agents(gpu);
+        self.checkpoint = Some(self.cpu_state());
+    }
+    
+    /// Copy the front field into the rewind ring with the current agents
+    fn capture_rewind(&mut self, gpu: &GpuContext) {
+        if self.rewind.is_none() {
+            return;
+        }
+        self.sync_agents(gpu);
+        let Some(ring) = self.rewind.as_mut() else { return };
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("rewind_capture_encoder"),
+        });
+        ring.capture(&mut encoder, &self.field_textures, self.current_step, self.agent_manager.agents.clone());
+        gpu.queue.submit(Some(encoder.finish()));
+    }
+    
+    /// Go back to the latest rewind point before the current step, dropping the later ones
+    fn rewind_to_previous(&mut self, gpu: &GpuContext) {
+        let Some(ring) = self.rewind.as_mut() else {
+            println!("Rewind is off (gpu.rewind_slots = 0)");
+            return;
+        };
+        let back = ring.points().last().map_or(0, |p| usize::from(p.step >= self.current_step));
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("rewind_encoder"),
+        });
+        let Some(point) = ring.rewind(&mut encoder, &self.field_textures, back) else {
+            println!("Nothing earlier to rewind to");
+            return;
+        };
+        gpu.queue.submit(Some(encoder.finish()));
+        let held = ring.len();
+        
+        self.current_step = point.step;
+        self.clock.steps.reset();
+        self.agent_manager.agents = point.agents;
+        self.upload_agents(gpu);
+        self.restart_foraging(gpu);
+        if let Err(e) = self.field_textures.download_field_data(&gpu.device, &gpu.queue, &mut self.field_manager) {
+            eprintln!("Warning: field readback after rewind failed: {}", e);
+        }
+        if self.checkpoints_enabled() {
+            self.checkpoint = Some(self.cpu_state());
+        }
+        self.rewind_recording();
+        println!("Rewound to step {} ({} rewind points left)", self.current_step, held);
+    }
+    
+    /// Take the camera, overlays and panels from the last session's settings
+    pub fn apply_settings(&mut self, settings: &ViewerSettings) {
+        if let Some(c) = settings.camera_for(self.sim_config.world.size) {
+            self.camera = Camera { center: c.center, zoom: c.zoom };
+        }
+        let o = settings.overlays;
+        (self.show_r_field, self.show_w_field, self.show_occupancy, self.show_gradients) = (o.r_field, o.w_field, o.occupancy, o.gradients);
+        let p = settings.panels;
+        (self.minimap.visible, self.timeline.visible, self.age_chart.visible) = (p.minimap, p.timeline, p.age_chart);
+        (self.contours.visible, self.contours.levels) = (settings.contours.visible, settings.contours.levels);
+    }
+
+    /// Record the window, camera, overlays and panels into `settings` for the next launch
+    pub fn capture_settings(&self, settings: &mut ViewerSettings) {
+        let size = self.window.inner_size();
+        settings.window = Some(WindowPlacement {
+            size: [size.width, size.height],
+            position: self.window.outer_position().ok().map(|p| [p.x, p.y]),
+        });
+        settings.camera = Some(CameraState {
+            world_size: self.sim_config.world.size,
+            center: self.camera.center,
+            zoom: self.camera.zoom,
+        });
+        settings.overlays = Overlays {
+            r_field: self.show_r_field,
+            w_field: self.show_w_field,
+            occupancy: self.show_occupancy,
+            gradients: self.show_gradients,
+        };
+        settings.panels = Panels {
+            minimap: self.minimap.visible,
+            timeline: self.timeline.visible,
+            age_chart: self.age_chart.visible,
+        };
+        settings.contours = ContourSettings { visible: self.contours.visible, levels: self.contours.levels };
+        settings.metrics_window = self.metrics_window.as_ref().map(MetricsWindow::placement);
+    }
+    
+    /// Rebuild the viewer on a new device from the latest checkpoint
+    ///
+    /// Controls, camera and overlays start from their defaults; the step clock
+    /// and the (possibly downscaled) config carry over.
+    pub fn recover(&mut self, gpu: &GpuContext) -> Result<()> {
+        let checkpoint = self.checkpoint.take()
+            .ok_or_else(|| anyhow::anyhow!("no checkpoint to recover from (gpu.checkpoint_every = 0)"))?;
+        let recording = self.recording.take();
+        let metrics_window = self.metrics_window.take();
+        *self = Viewer::new(self.window.clone(), gpu, self.sim_config.clone(), self.clock.steps.clone(), self.population.take())?;
+        self.recording = recording;
+        if let Some(metrics) = &metrics_window {
+            metrics.reconfigure(&gpu.device);
+        }
+        self.metrics_window = metrics_window;
+        self.load_snapshot(gpu, &checkpoint)?;
+        self.rewind_recording();
+        Ok(())
+    }
+    
+    /// Open the metrics window (at `placement`, if any) or close it if open
+    ///
+    /// The pass timeline and age chart move to it; if neither was shown, both are.
+    pub fn toggle_metrics_window<T>(
+        &mut self,
+        target: &winit::event_loop::EventLoopWindowTarget<T>,
+        instance: &Instance,
+        adapter: &wgpu::Adapter,
+        gpu: &GpuContext,
+        placement: Option<WindowPlacement>,
+    ) -> Result<()> {
+        if self.metrics_window.take().is_some() {
+            println!("Metrics window closed; charts back in the main window");
+            return Ok(());
+        }
+        self.metrics_window = Some(MetricsWindow::open(target, instance, adapter, &gpu.device, &gpu.config, placement)?);
+        if !self.timeline.visible && !self.age_chart.visible {
+            (self.timeline.visible, self.age_chart.visible) = (true, true);
+        }
+        println!("Metrics window opened; press d or close it to bring the charts back");
+        Ok(())
+    }
+    
+    /// Most device losses survived before the viewer gives up
+    pub fn max_recoveries(&self) -> u32 {
+        self.sim_config.gpu.max_recoveries
     }
     
     /// Handle window resize
@@ -243,25 +647,64 @@ impl Viewer {
             });
             
             // Recreate field textures and bind groups using centralized layouts
-            self.field_textures.recreate(
-                &gpu.device,
-                &self.layouts,
-                &self.rd_params_buffer,
-                &self.occupancy_buffer,
-                &self.field_sampler,
-            );
+            let rd_inputs = RdBindInputs {
+                rd_params: &self.rd_params_buffer,
+                occupancy: &self.occupancy_buffer,
+                sigma_boost: self.blooms.boost_buffer(),
+                obstacles: self.obstacles.view(),
+                elevation: self.terrain.view(),
+                diffusion: self.diffusion.view(),
+                time: &self.time_buffer,
+            };
+            self.field_textures.recreate(&gpu.device, &self.layouts, &rd_inputs, &self.field_sampler);
+            self.agent_bind_groups = self.agents_buffer.as_ref().zip(self.steering.as_ref()).zip(self.foraging.as_ref()).map(|((buffer, steering), foraging)| Self::create_agent_bind_groups(
+                &gpu.device, &self.layouts, &self.field_textures, &AgentBindInputs {
+                    agents: buffer,
+                    agent_params: &self.agent_params_buffer,
+                    occupancy: &self.occupancy_buffer,
+                    obstacles: self.obstacles.view(),
+                    elevation: self.terrain.view(),
+                    time: &self.time_buffer,
+                    ranges: &self.agent_ranges,
+                    steering,
+                    foraging,
+                    events: &self.events,
+                    energy_probe: &self.energy_probe,
+                },
+            ));
         }
     }
     
+    /// Agent pass bind groups sampling field A and field B
+    fn create_agent_bind_groups(
+        device: &Device,
+        layouts: &Layouts,
+        field_textures: &FieldPingPong,
+        inputs: &AgentBindInputs,
+    ) -> [wgpu::BindGroup; 2] {
+        [("agent_a_bind_group", field_textures.a_sample_view()), ("agent_b_bind_group", field_textures.b_sample_view())]
+            .map(|(label, field_view)| inputs.bind_group(device, layouts, label, field_view))
+    }
+    
     /// Update the simulation state
     pub fn update(&mut self, gpu: &GpuContext) -> Result<()> {
         println!("Update: starting simulation update");
-        // Fixed timestep simulation
-        const BASE_DT: f32 = 1.0 / 60.0; // sim seconds per logical step
-        
+        // Fixed timestep simulation: whole steps due for the real time elapsed
         let real_dt = self.clock.advance();
-        self.clock.acc += real_dt * self.controls.speed;
-        println!("Update: real_dt={:.6}, acc={:.6}, speed={:.2}", real_dt, self.clock.acc, self.controls.speed);
+        let mut steps_due = if self.controls.paused {
+            self.clock.steps.reset();
+            0
+        } else {
+            self.clock.steps.advance(real_dt, self.controls.speed)
+        };
+        if self.controls.steps_pending > 0 {
+            // Asked-for steps run a burst per frame, so a long `step N` keeps the window responsive
+            let burst = self.controls.steps_pending.min(self.clock.steps.max_burst());
+            self.controls.steps_pending -= burst;
+            steps_due = steps_due.max(burst);
+        }
+        println!("Update: real_dt={:.6}, steps_due={}, backlog={:.3}, speed={:.2}",
+            real_dt.as_secs_f32(), steps_due, self.clock.steps.backlog(), self.controls.speed);
         
         // Handle reset request
         if self.controls.reset_requested {
@@ -271,13 +714,41 @@ impl Viewer {
             println!("Update: reset completed");
         }
         
+        // Handle console command
+        if let Some(command) = self.controls.command_requested.take() {
+            self.run_command(gpu, command)?;
+        }
+        
+        // Handle cohort tagging request
+        if std::mem::take(&mut self.controls.tag_requested) {
+            self.tag_cohort_at_cursor(gpu);
+        }
+        
+        // Handle agent inspection request
+        if std::mem::take(&mut self.controls.inspect_requested) {
+            self.inspect_agent_at_cursor(gpu);
+        }
+        
+        // Handle rewind request
+        if std::mem::take(&mut self.controls.rewind_requested) {
+            self.rewind_to_previous(gpu);
+        }
+        
+        // Streaming mode: follow the camera with the world window
+        self.stream_world(gpu);
+        
         // Run simulation steps
         let mut steps_run = 0;
-        while (!self.controls.paused || std::mem::take(&mut self.controls.step_once)) && self.clock.acc >= BASE_DT {
+        while steps_run < steps_due {
             println!("Update: running simulation step {}", steps_run + 1);
             self.run_simulation_step(gpu)?;
-            self.clock.acc -= BASE_DT;
             steps_run += 1;
+            if self.checkpoints_enabled() && self.current_step.is_multiple_of(self.sim_config.gpu.checkpoint_every) {
+                self.take_checkpoint(gpu);
+            }
+            if self.current_step.is_multiple_of(self.sim_config.gpu.rewind_every) {
+                self.capture_rewind(gpu);
+            }
         }
         println!("Update: completed {} simulation steps", steps_run);
         
@@ -288,13 +759,22 @@ impl Viewer {
     fn run_simulation_step(&mut self, gpu: &GpuContext) -> Result<()> {
         println!("Step: starting simulation step {}", self.current_step + 1);
         
-        // Update uniform buffers every frame
+        // Update uniform buffers every frame, after moving scheduled, forced and perturbed params
         println!("Step: updating uniform buffers");
+        self.sim_config.apply_schedules(self.current_step);
+        if let Some(forcing) = &self.forcing {
+            forcing.apply(&mut self.sim_config, self.current_step);
+        }
+        if let Some(perturbation) = &self.perturbation {
+            perturbation.apply(&mut self.sim_config, self.current_step);
+        }
         let rd_params = RDParams::from(&self.sim_config);
         let agent_params = AgentParams::from(&self.sim_config);
         
         gpu.queue.write_buffer(&self.rd_params_buffer, 0, bytemuck::cast_slice(&[rd_params]));
         gpu.queue.write_buffer(&self.agent_params_buffer, 0, bytemuck::cast_slice(&[agent_params]));
+        let time = TimeUniform::new(&self.sim_config.world, self.current_step);
+        gpu.queue.write_buffer(&self.time_buffer, 0, bytemuck::cast_slice(&[time]));
         println!("Step: uniform buffers updated");
         
         // Run agent pass
@@ -302,6 +782,26 @@ impl Viewer {
         self.run_agent_pass(gpu)?;
         println!("Step: agent pass completed");
         
+        // Random deaths and births (demography.enabled)
+        if let (Some(demography), Some(agents_buffer)) = (self.demography.as_mut(), &self.agents_buffer) {
+            let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("demography_encoder"),
+            });
+            self.profiler.begin_scope(&mut encoder, "demography");
+            demography.apply(&gpu.device, &gpu.queue, &mut encoder, &self.compute_pipelines, agents_buffer, self.current_step);
+            self.profiler.end_scope(&mut encoder);
+            gpu.queue.submit(Some(encoder.finish()));
+        }
+        
+        // Bloom injection (only dispatches when a bloom starts or expires or rain cells move)
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("bloom_encoder"),
+        });
+        self.profiler.begin_scope(&mut encoder, "blooms");
+        self.blooms.update(&gpu.queue, &mut encoder, &self.compute_pipelines, self.current_step);
+        self.profiler.end_scope(&mut encoder);
+        gpu.queue.submit(Some(encoder.finish()));
+        
         // Run RD pass
         println!("Step: running RD pass");
         self.run_rd_pass(gpu)?;
@@ -318,7 +818,7 @@ impl Viewer {
         println!("Step: ping-pong buffers swapped");
         
         // Debug: log the swap
-        if self.current_step % 10 == 0 {
+        if self.current_step % self.sim_config.output.debug_every == 0 {
             println!("Ping-pong swapped, front_is_a: {}", self.field_textures.front_is_a());
         }
         
@@ -327,36 +827,137 @@ impl Viewer {
         Ok(())
     }
     
+    /// The imported population if there is one, else agents spawned from the seed
+    fn initial_agents(sim_config: &SimulationConfig, population: Option<&AgentPopulation>) -> AgentManager {
+        match population {
+            Some(population) => population.manager(&sim_config.agents),
+            None => AgentManager::spawn(
+                &sim_config.agents,
+                [sim_config.world.size[0] as f32, sim_config.world.size[1] as f32],
+                &mut SimRng::from_world(&sim_config.world),
+            ),
+        }
+    }
+    
     /// Reset the world to initial state
     pub fn reset_world(&mut self, gpu: &GpuContext) -> Result<()> {
         // Reset simulation state
         self.current_step = 0;
-        self.clock.acc = 0.0;
+        self.clock.steps.reset();
         
         // Re-seed the field
-        self.field_manager.seed_resources(self.sim_config.world.seed);
+        self.field_manager.seed_resources_with(&mut SimRng::from_world(&self.sim_config.world));
+        if self.streamer.is_some() {
+            let mut streamer = ChunkStreamer::new(&self.sim_config.streaming, &self.sim_config.world);
+            streamer.fill(&mut self.field_manager);
+            self.streamer = Some(streamer);
+            self.camera = Camera::fit(self.sim_config.world.size);
+        }
         
         // Re-seed agents
-        self.agent_manager = AgentManager::new(
-            self.sim_config.agents.herbivores,
-            [self.sim_config.world.size[0] as f32, self.sim_config.world.size[1] as f32],
-            self.sim_config.agents.E0,
-            self.sim_config.world.seed,
-        );
+        self.agent_manager = Self::initial_agents(&self.sim_config, self.population.as_ref());
+        self.obstacles.field.push_out(&mut self.agent_manager.agents);
+        self.rewind_recording();
         
         // Upload initial data
-        self.field_textures.upload_field_data(&gpu.queue, &self.field_manager);
+        self.field_textures.upload_field_data(&gpu.queue, &self.field_manager).map_err(anyhow::Error::msg)?;
+        self.cohorts = CohortTracker::new();
+        self.energy_probe.select(&gpu.queue, None);
+        
+        // Clear blooms and restart their schedule
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("bloom_reset_encoder"),
+        });
+        self.blooms.reset(&gpu.queue, &mut encoder, &self.compute_pipelines, &self.sim_config);
+        gpu.queue.submit(Some(encoder.finish()));
         
         // Update agent buffer
-        gpu.queue.write_buffer(&self.agents_buffer, 0, bytemuck::cast_slice(&self.agent_manager.agents));
+        self.upload_agents(gpu);
+        self.restart_foraging(gpu);
+        if self.checkpoints_enabled() {
+            self.checkpoint = Some(self.cpu_state());
+        }
+        if let Some(ring) = self.rewind.as_mut() {
+            ring.clear();
+        }
+        self.capture_rewind(gpu);
         
         println!("World reset to initial state");
         Ok(())
     }
     
+    /// Replace the seeded world with a headless state snapshot and continue from its step
+    pub fn load_snapshot(&mut self, gpu: &GpuContext, snapshot: &StateSnapshot) -> Result<()> {
+        snapshot.check_compatible(&self.sim_config).map_err(anyhow::Error::msg)?;
+        self.current_step = snapshot.step;
+        self.field_manager.set_cells(&snapshot.field);
+        self.agent_manager.agents.copy_from_slice(&snapshot.agents);
+        self.field_textures.upload_field_data(&gpu.queue, &self.field_manager).map_err(anyhow::Error::msg)?;
+        self.upload_agents(gpu);
+        self.cohorts = CohortTracker::new();
+        self.energy_probe.select(&gpu.queue, None);
+        if self.checkpoints_enabled() {
+            self.checkpoint = Some(snapshot.clone());
+        }
+        if let Some(ring) = self.rewind.as_mut() {
+            ring.clear();
+        }
+        self.capture_rewind(gpu);
+        println!("Loaded snapshot at step {}", snapshot.step);
+        Ok(())
+    }
+    
+    /// Shift the streamed world window once the camera leaves its central chunk
+    fn stream_world(&mut self, gpu: &GpuContext) {
+        let Some(delta) = self.streamer.as_ref()
+            .map(|s| s.shift_for(self.camera.center))
+            .filter(|d| *d != [0, 0])
+        else {
+            return;
+        };
+        
+        // Shifting a stale field would lose the changes since the last readback; retry next frame
+        let readback = Instant::now();
+        if let Err(e) = self.field_textures.download_field_data(&gpu.device, &gpu.queue, &mut self.field_manager) {
+            eprintln!("Warning: world shift postponed, field readback failed: {}", e);
+            return;
+        }
+        self.profiler.record_cpu("readback", readback.elapsed().as_secs_f32() * 1e3);
+        self.sync_agents(gpu);
+        
+        let Some(streamer) = self.streamer.as_mut() else { return };
+        let lost = streamer.shift(delta, &mut self.field_manager, &mut self.agent_manager.agents, &mut self.cohorts);
+        let chunk = self.sim_config.streaming.chunk as f32;
+        self.camera.center[0] -= delta[0] as f32 * chunk;
+        self.camera.center[1] -= delta[1] as f32 * chunk;
+        self.cull_drag = None;
+        let origin = streamer.origin_cells();
+        let cached = streamer.cached_chunks();
+        
+        if let Err(e) = self.field_textures.upload_field_data(&gpu.queue, &self.field_manager) {
+            eprintln!("Warning: shifted field upload failed: {}", e);
+        }
+        self.upload_agents(gpu);
+        
+        // Earlier rewind points belong to the old window
+        if let Some(ring) = self.rewind.as_mut() {
+            ring.clear();
+        }
+        self.capture_rewind(gpu);
+        
+        println!("Streamed world by {:?} chunks: origin ({}, {}), {} chunks cached, {} agents left behind",
+            delta, origin[0], origin[1], cached, lost);
+    }
+    
     /// Render the current frame
-    pub fn render(&mut self, gpu: &GpuContext, renderer: &Renderer) -> Result<()> {
+    pub fn render(&mut self, gpu: &GpuContext, renderer: &mut Renderer) -> Result<()> {
         println!("Render: starting frame render");
+        
+        // Frames whose timings have come back since the last render
+        while let Some(samples) = self.profiler.poll(&gpu.device) {
+            self.timeline.history.push(&samples);
+        }
+        
         let output = gpu.surface.get_current_texture()?;
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
         println!("Render: got surface texture and view");
@@ -376,38 +977,77 @@ impl Viewer {
             label: Some("render_encoder"),
         });
         
-        // Create SimParams buffer for this frame
-        println!("Render: creating sim params buffer");
-        let sim_params = [
-            self.sim_config.world.size[0] as f32,  // world_size.x
-            self.sim_config.world.size[1] as f32,  // world_size.y
-            self.current_step as f32 * 0.016,      // time: 60 FPS
-            1.0,                                   // zoom: default zoom
-            0.0,                                   // camera.x: centered
-            0.0,                                   // camera.y: centered
-            0.0,                                   // _pad0.x
-            0.0,                                   // _pad0.y
-        ];
-        let sim_params_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("sim_params_frame"),
-            contents: bytemuck::cast_slice(&sim_params),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        // Rewrite this frame's uniforms in place
+        let surface_size = [gpu.config.width, gpu.config.height];
+        let world_size = self.sim_config.world.size;
+        self.frame_uniforms.write_camera(&gpu.queue, &self.camera.uniform(world_size, surface_size));
+        self.frame_uniforms.write_minimap_params(&gpu.queue, &self.minimap.params(&self.camera, surface_size, world_size));
+        self.frame_uniforms.write_agent_colors(&gpu.queue, &AgentColorParams::new(&self.sim_config.render));
+        let agent_view = AgentViewParams::new(
+            &self.camera, world_size, surface_size, self.agent_manager.agents.len() as u32, &self.sim_config.render,
+        );
+        self.frame_uniforms.write_agent_view(&gpu.queue, &agent_view);
+        self.frame_uniforms.write_contour_params(&gpu.queue, &self.contours.params(self.sim_config.field.K_R));
+        let minimap = self.minimap.visible.then(|| {
+            (self.frame_uniforms.minimap_params(), self.minimap.viewport(surface_size, world_size))
         });
         
-        // Render the field background and particles
+        // Pass timeline chart (its buffers persist across frames)
+        if self.timeline.visible {
+            self.timeline.upload(&gpu.queue);
+        }
+        let mut charts = Vec::with_capacity(2);
+        if self.timeline.visible {
+            let (params, frames) = self.timeline.buffers();
+            charts.push((params, frames, self.timeline.viewport(surface_size)));
+        }
+        
+        // Age structure chart (refreshed from the agents with the HUD)
+        if self.age_chart.visible {
+            self.age_chart.upload(&gpu.queue);
+            let (params, bars) = self.age_chart.buffers();
+            charts.push((params, bars, self.age_chart.viewport(surface_size)));
+        }
+        
+        // With the metrics window open the charts fill it, stacked, instead of the corners
+        let metrics_output = match &self.metrics_window {
+            Some(metrics) => match metrics.surface().get_current_texture() {
+                Ok(output) => {
+                    let viewports = metrics.viewports(charts.len());
+                    for (chart, viewport) in charts.iter_mut().zip(viewports) {
+                        chart.2 = viewport;
+                    }
+                    let metrics_view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+                    renderer.render_charts(&gpu.device, &mut encoder, &metrics_view, &self.layouts.timeline, &charts);
+                    Some(output)
+                }
+                Err(e) => {
+                    log::warn!("Metrics window skipped this frame: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        if self.metrics_window.is_some() {
+            charts.clear();
+        }
+        
+        // Render the field background, particles, minimap and charts
         println!("Render: calling renderer.render");
-        renderer.render(
-            &gpu.device, 
-            &mut encoder, 
-            &view, 
-            &sim_params_buffer, 
-            &self.agents_buffer, 
-            self.sim_config.agents.herbivores, 
-            &self.layouts.particle_render,
-            &self.layouts.field_render,
-            self.field_textures.front_sample_view(),
-            &self.field_sampler,
-        )?;
+        self.profiler.begin_scope(&mut encoder, "render");
+        let frame = FrameInputs {
+            camera: self.frame_uniforms.camera(),
+            particles: self.agents_buffer.as_ref().map(|buffer| (buffer, &agent_view)),
+            agent_colors: self.frame_uniforms.agent_colors(),
+            agent_view: self.frame_uniforms.agent_view(),
+            field_texture: self.field_textures.front_sample_view(),
+            field_sampler: &self.field_sampler,
+            contours: self.contours.visible.then(|| self.frame_uniforms.contour_params()),
+            minimap,
+            charts: &charts,
+        };
+        renderer.render(&gpu.device, &mut encoder, &view, &self.layouts, &frame)?;
+        self.profiler.end_scope(&mut encoder);
         println!("Render: renderer.render completed");
         
         println!("Render: submitting command buffer");
@@ -416,12 +1056,16 @@ impl Viewer {
         
         println!("Render: presenting output");
         output.present();
+        if let Some(metrics_output) = metrics_output {
+            metrics_output.present();
+        }
         println!("Render: output presented");
         
         self.frame_count += 1;
         
-        // Display HUD info every 30 frames (about once per second at 60 FPS)
-        if self.frame_count % 30 == 0 {
+        // Display HUD info every `output.hud_every` frames (default 30, about twice a second at 60 FPS)
+        if self.frame_count % self.sim_config.output.hud_every == 0 {
+            self.refresh_stats(gpu);
             let (alive_agents, mean_r, mean_gradient, foraging_efficiency) = self.get_stats();
             
             // Count agents by type
@@ -448,6 +1092,10 @@ impl Viewer {
                 alive_agents);
             println!("Agent Types: Plants={}, Herbivores={}, Predators={}", 
                 plant_count, herbivore_count, predator_count);
+            if self.sim_config.world.day_period > 0.0 {
+                let time = TimeUniform::new(&self.sim_config.world, self.current_step);
+                println!("Day phase: {:.2} | Light: {:.2}", time.day_phase, time.light);
+            }
             
             // Debug: Show first 5 particles' kind values
             println!("First 5 particles: ");
@@ -466,113 +1114,108 @@ impl Viewer {
             if let Some(scenario) = &self.scenario_mode {
                 println!("Scenario: {}", scenario);
             }
+            if let Some(streamer) = &self.streamer {
+                let origin = streamer.origin_cells();
+                println!("Streaming: window origin ({}, {}), {} chunks cached", origin[0], origin[1], streamer.cached_chunks());
+            }
+            if self.timeline.visible {
+                println!("Pass timeline (mean over {} frames, peak {:.3} ms):", self.timeline.history.len(), self.timeline.history.peak());
+                for line in self.timeline.legend() {
+                    println!("  {}", line);
+                }
+            }
+            if self.age_chart.visible {
+                let output = &self.sim_config.output;
+                self.age_chart.ages = AgeStructure::of(&self.agent_manager.agents, output.age_bin, output.age_classes);
+                println!("Age structure ({} classes of {} steps, last open-ended):", output.age_classes, output.age_bin);
+                for line in self.age_chart.legend() {
+                    println!("  {}", line);
+                }
+            }
+            if let Some(slot) = self.energy_probe.selected() {
+                let agent = &self.agent_manager.agents[slot as usize];
+                let budget = EnergyBudget::of(&self.energy_probe.read(&gpu.device, &gpu.queue));
+                println!("Agent {} ({}): energy {:.3}{}", slot, vireo_core::SPECIES.get(agent.kind as usize).unwrap_or(&"?"),
+                    agent.energy, if agent.is_alive() { "" } else { ", dead" });
+                println!("  Last {} steps ({}..={}): uptake +{:.4}, metabolism -{:.4}, movement -{:.4}, predation -{:.4}, net {:+.4}",
+                    budget.steps, budget.first_step, budget.last_step, budget.uptake, budget.metabolism, budget.movement, budget.predation, budget.net());
+                if self.sim_config.evolution.enabled {
+                    let c = &self.sim_config.chemotaxis;
+                    println!("  Traits: chi_R {:.3} (x{:.3}), kappa {:.3} (x{:.3})",
+                        c.chi_R * agent.chi_gain, agent.chi_gain, c.kappa * agent.kappa_gain, agent.kappa_gain);
+                }
+            }
+            if !self.cohorts.tags().is_empty() {
+                self.sync_agents(gpu);
+                for c in self.cohorts.stats(&self.agent_manager.agents) {
+                    println!("Cohort {}: {}/{} alive ({:.0}%), dispersal mean {:.1} max {:.1}",
+                        c.cohort, c.alive, c.tagged, c.survival * 100.0, c.mean_dispersal, c.max_dispersal);
+                }
+            }
+            if let Some(metrics) = &self.metrics_window {
+                metrics.window.set_title(&format!("Vireo Metrics - step {}, {} alive, mean R {:.3}",
+                    self.current_step, alive_agents, mean_r));
+            }
             println!("==================");
         }
         
+        // Resolve this frame's timestamps; they are read back a frame or two later
+        self.profiler.finish_frame(&gpu.device, &gpu.queue);
+        
         println!("Render: frame {} completed", self.frame_count);
         Ok(())
     }
     
     /// Clear the occupancy buffer
-    fn clear_occupancy_buffer(&self, gpu: &GpuContext) -> Result<()> {
+    fn clear_occupancy_buffer(&mut self, gpu: &GpuContext) -> Result<()> {
         let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("clear_occupancy_encoder"),
         });
         
-        // Create a dimensions buffer for the clear occupancy shader
-        let dimensions = [self.sim_config.world.size[0], self.sim_config.world.size[1]];
-        let dimensions_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("clear_occupancy_dims"),
-            contents: bytemuck::cast_slice(&dimensions),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-        
-        // Create bind group for clear occupancy pass using centralized layouts
-        let clear_bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("clear_occupancy_bind_group"),
-            layout: &self.layouts.clear_occupancy,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: self.occupancy_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Buffer(dimensions_buffer.as_entire_buffer_binding()),
-                },
-            ],
-        });
-        
-        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some("clear_occupancy_pass"),
-            timestamp_writes: None,
-        });
-        
-        compute_pass.set_pipeline(&self.compute_pipelines.clear_occupancy_pipeline);
-        compute_pass.set_bind_group(0, &clear_bind_group, &[]);
-        
-        // Dispatch clear occupancy compute pass
-        let total_cells = self.sim_config.world.size[0] * self.sim_config.world.size[1];
-        let workgroup_size = 128;
-        let workgroup_count = (total_cells + workgroup_size - 1) / workgroup_size;
-        
-        compute_pass.dispatch_workgroups(workgroup_count, 1, 1);
-        drop(compute_pass);
+        self.profiler.begin_scope(&mut encoder, "occupancy");
+        self.occupancy_clear.encode(&mut encoder, &self.compute_pipelines);
+        self.profiler.end_scope(&mut encoder);
         
         gpu.queue.submit(Some(encoder.finish()));
         Ok(())
     }
     
     /// Run the agent simulation pass
-    fn run_agent_pass(&self, gpu: &GpuContext) -> Result<()> {
+    fn run_agent_pass(&mut self, gpu: &GpuContext) -> Result<()> {
         let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("agent_encoder"),
         });
         
-        // Create bind group for agent pass using centralized layouts
-        let agent_bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("agent_bind_group"),
-            layout: &self.layouts.agent,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: self.agents_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(self.field_textures.front_sample_view()),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: self.agent_params_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: self.occupancy_buffer.as_entire_binding(),
-                },
-            ],
-        });
+        // Bind group sampling the current front field (nothing to move without agents)
+        let Some(agent_bind_groups) = &self.agent_bind_groups else { return Ok(()) };
+        let agent_bind_group = &agent_bind_groups[if self.field_textures.front_is_a() { 0 } else { 1 }];
         
+        self.profiler.begin_scope(&mut encoder, "agents");
+        // Neighbour counts and headings before anyone moves (crowding and flocking only)
+        if let Some(steering) = &self.steering {
+            steering.fill(&gpu.device, &mut encoder, &self.compute_pipelines);
+        }
         let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: Some("agent_pass"),
             timestamp_writes: None,
         });
         
         compute_pass.set_pipeline(&self.compute_pipelines.agent_pipeline);
-        compute_pass.set_bind_group(0, &agent_bind_group, &[]);
-        
-        // Dispatch agent compute pass
-        let agent_count = self.sim_config.agents.herbivores;
-        let workgroup_size = 128;
-        let workgroup_count = (agent_count + workgroup_size - 1) / workgroup_size;
         
-        compute_pass.dispatch_workgroups(workgroup_count, 1, 1);
+        // Dispatch agent compute pass, one species range at a time
+        let mut workgroup_count = 0;
+        for (offset, workgroups) in self.agent_ranges.dispatches() {
+            compute_pass.set_bind_group(0, agent_bind_group, &[offset]);
+            compute_pass.dispatch_workgroups(workgroups, 1, 1);
+            workgroup_count += workgroups;
+        }
         drop(compute_pass);
+        self.profiler.end_scope(&mut encoder);
         
         gpu.queue.submit(Some(encoder.finish()));
         
         // Debug: log agent pass completion
-        if self.current_step % 10 == 0 {
+        if self.current_step % self.sim_config.output.debug_every == 0 {
             println!("Agent pass completed, workgroups: {}", workgroup_count);
         }
         
@@ -580,12 +1223,13 @@ impl Viewer {
     }
     
     /// Run the reaction-diffusion pass
-    fn run_rd_pass(&self, gpu: &GpuContext) -> Result<()> {
+    fn run_rd_pass(&mut self, gpu: &GpuContext) -> Result<()> {
         let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("rd_encoder"),
         });
         
         // Use the centralized bind group from FieldPingPong
+        self.profiler.begin_scope(&mut encoder, "rd");
         let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: Some("rd_pass"),
             timestamp_writes: None,
@@ -602,11 +1246,12 @@ impl Viewer {
         
         compute_pass.dispatch_workgroups(workgroup_count_x, workgroup_count_y, 1);
         drop(compute_pass);
+        self.profiler.end_scope(&mut encoder);
         
         gpu.queue.submit(Some(encoder.finish()));
         
         // Debug: log RD pass completion
-        if self.current_step % 10 == 0 {
+        if self.current_step % self.sim_config.output.debug_every == 0 {
             println!("RD pass completed, workgroups: {}x{}", workgroup_count_x, workgroup_count_y);
         }
         
@@ -615,6 +1260,10 @@ impl Viewer {
 
     /// Handle key press for overlay toggles and scenario modes
     pub fn handle_key(&mut self, key: &winit::keyboard::Key) -> Result<()> {
+        if self.console.is_open() {
+            self.handle_console_key(key);
+            return Ok(());
+        }
         match key {
             // Simulation controls
             winit::keyboard::Key::Named(winit::keyboard::NamedKey::Space) => {
@@ -626,9 +1275,14 @@ impl Viewer {
                 self.controls.reset_requested = true;
             }
             winit::keyboard::Key::Character(ch) if ch == "s" || ch == "S" => {
-                self.controls.step_once = true;
+                self.controls.steps_pending += 1;
                 println!("Single step requested");
             }
+            winit::keyboard::Key::Character(ch) if ch == "`" => {
+                self.console.open();
+                self.window.set_title(&self.console.title(WINDOW_TITLE));
+                println!("Console: {} (Enter runs, Esc cancels, Up recalls the last command)", CONSOLE_USAGE);
+            }
             winit::keyboard::Key::Character(ch) if ch == "[" => {
                 self.controls.speed = (self.controls.speed / 1.5).max(0.0625);
                 println!("Speed: {:.2}x", self.controls.speed);
@@ -649,6 +1303,90 @@ impl Viewer {
                 self.print_help();
             }
             
+            // Camera and minimap
+            winit::keyboard::Key::Named(
+                arrow @ (winit::keyboard::NamedKey::ArrowLeft
+                    | winit::keyboard::NamedKey::ArrowRight
+                    | winit::keyboard::NamedKey::ArrowUp
+                    | winit::keyboard::NamedKey::ArrowDown),
+            ) => {
+                let step = match arrow {
+                    winit::keyboard::NamedKey::ArrowLeft => [-PAN_STEP, 0.0],
+                    winit::keyboard::NamedKey::ArrowRight => [PAN_STEP, 0.0],
+                    winit::keyboard::NamedKey::ArrowUp => [0.0, PAN_STEP],
+                    _ => [0.0, -PAN_STEP],
+                };
+                self.camera.pan(step);
+            }
+            winit::keyboard::Key::Character(ch) if ch == "c" || ch == "C" => {
+                self.camera = Camera::fit(self.sim_config.world.size);
+                println!("Camera reset");
+            }
+            winit::keyboard::Key::Character(ch) if ch == "t" || ch == "T" => {
+                self.controls.tag_requested = true;
+            }
+            winit::keyboard::Key::Character(ch) if ch == "i" || ch == "I" => {
+                self.controls.inspect_requested = true;
+            }
+            winit::keyboard::Key::Named(winit::keyboard::NamedKey::Backspace) => {
+                self.controls.rewind_requested = true;
+            }
+            winit::keyboard::Key::Character(ch) if ch == "x" || ch == "X" => {
+                self.cull_tool = match self.cull_tool {
+                    CullTool::Off => CullTool::Rect,
+                    CullTool::Rect => CullTool::Circle,
+                    CullTool::Circle => CullTool::Off,
+                };
+                self.cull_drag = None;
+                self.wall_tool = WallTool::Off;
+                println!("Cull tool: {:?}", self.cull_tool);
+            }
+            winit::keyboard::Key::Character(ch) if ch == "o" || ch == "O" => {
+                // Walls are fixed to the grid, which streaming scrolls under them
+                if self.streamer.is_some() {
+                    println!("Wall tool unavailable while streaming");
+                    return Ok(());
+                }
+                self.wall_tool = match self.wall_tool {
+                    WallTool::Off => WallTool::Draw,
+                    WallTool::Draw => WallTool::Erase,
+                    WallTool::Erase => WallTool::Off,
+                };
+                self.wall_painting = false;
+                self.cull_tool = CullTool::Off;
+                println!("Wall tool: {:?}", self.wall_tool);
+            }
+            winit::keyboard::Key::Character(ch) if ch == "m" || ch == "M" => {
+                self.minimap.visible = !self.minimap.visible;
+                println!("Minimap {}", if self.minimap.visible { "shown" } else { "hidden" });
+            }
+            winit::keyboard::Key::Character(ch) if ch == "k" || ch == "K" => {
+                let render = &mut self.sim_config.render;
+                render.kde = !render.kde;
+                println!("Kernel density view {} (bandwidth {} cells)", if render.kde { "on" } else { "off" }, render.kde_bandwidth);
+            }
+            winit::keyboard::Key::Character(ch) if ch == "l" || ch == "L" => {
+                self.contours.visible = !self.contours.visible;
+                println!("Contours {} ({} levels below K_R)", if self.contours.visible { "shown" } else { "hidden" }, self.contours.levels);
+            }
+            winit::keyboard::Key::Character(ch) if ch == "-" || ch == "=" || ch == "+" => {
+                self.contours.scale_levels(ch != "-");
+                println!("Contours: {} levels below K_R", self.contours.levels);
+            }
+            winit::keyboard::Key::Character(ch) if ch == "p" || ch == "P" => {
+                self.timeline.visible = !self.timeline.visible;
+                println!("Pass timeline {}{}", if self.timeline.visible { "shown" } else { "hidden" },
+                    if self.profiler.is_enabled() { "" } else { " (no GPU timestamps on this adapter, host times only)" });
+            }
+            winit::keyboard::Key::Character(ch) if ch == "d" || ch == "D" => {
+                self.controls.metrics_window_requested = true;
+            }
+            winit::keyboard::Key::Character(ch) if ch == "a" || ch == "A" => {
+                self.age_chart.visible = !self.age_chart.visible;
+                println!("Age chart {} (refreshed every {} frames)", if self.age_chart.visible { "shown" } else { "hidden" },
+                    self.sim_config.output.hud_every);
+            }
+            
             // Overlay toggles
             winit::keyboard::Key::Character(c) if c == "1" => {
                 self.show_r_field = !self.show_r_field;
@@ -697,18 +1435,235 @@ impl Viewer {
         Ok(())
     }
 
-    /// Get current simulation statistics for HUD display
-    pub fn get_stats(&self) -> (u32, f32, f32, f32) {
-        let alive_agents = self.agent_manager.agents.iter()
-            .filter(|a| a.alive == 1)
-            .count() as u32;
+    /// Edit the console line; Enter queues its command for the next update
+    fn handle_console_key(&mut self, key: &winit::keyboard::Key) {
+        use winit::keyboard::{Key, NamedKey};
+        match key {
+            Key::Named(NamedKey::Enter) => {
+                if let Some(line) = self.console.submit() {
+                    match ConsoleCommand::parse(&line) {
+                        Ok(command) => self.controls.command_requested = Some(command),
+                        Err(e) => println!("Console: {}", e),
+                    }
+                }
+            }
+            Key::Named(NamedKey::Escape) => self.console.close(),
+            Key::Named(NamedKey::Backspace) => self.console.backspace(),
+            Key::Named(NamedKey::ArrowUp) => self.console.recall(),
+            Key::Named(NamedKey::Space) => self.console.type_text(" "),
+            Key::Character(text) => self.console.type_text(text),
+            _ => {}
+        }
+        self.window.set_title(&self.console.title(WINDOW_TITLE));
+    }
+    
+    /// Run a console command
+    ///
+    /// `set` changes the live config, which the next step's uniforms are built
+    /// from, and is recorded like a viewer interaction; `seed` resets the world.
+    fn run_command(&mut self, gpu: &GpuContext, command: ConsoleCommand) -> Result<()> {
+        match command {
+            ConsoleCommand::Step(steps) => {
+                self.controls.paused = true;
+                self.controls.steps_pending = steps;
+                println!("Running {} steps from step {}, then pausing", steps, self.current_step);
+            }
+            ConsoleCommand::Seed(seed) => {
+                self.sim_config.world.seed = seed;
+                if let Some((_, script)) = self.recording.as_mut() {
+                    script.seed = Some(seed);
+                }
+                self.reset_world(gpu)?;
+                println!("Seed: {}", seed);
+            }
+            ConsoleCommand::Set { param, value } => {
+                let previous = self.sim_config.set_scalar(&param, value).map_err(anyhow::Error::msg)?;
+                println!("{} = {} (was {})", param, value, previous);
+                if self.sim_config.schedules.iter().any(|s| s.param == param) {
+                    println!("Note: {} follows a schedule, which sets it again every step", param);
+                }
+                let limit = self.sim_config.dt_limit();
+                if self.sim_config.world.dt > limit.max_dt() {
+                    println!("Warning: world.dt = {} is now above the stability limit {}", self.sim_config.world.dt, limit);
+                }
+                self.record(Interaction::Param { param, value });
+            }
+            ConsoleCommand::Snapshot(path) => {
+                self.field_textures.download_field_data(&gpu.device, &gpu.queue, &mut self.field_manager)
+                    .map_err(anyhow::Error::msg)?;
+                self.sync_agents(gpu);
+                self.cpu_state().write(&path)
+                    .map_err(|e| anyhow::anyhow!("cannot write snapshot {}: {}", path.display(), e))?;
+                println!("Snapshot of step {} written to {} (open with --load-snapshot)", self.current_step, path.display());
+            }
+        }
+        Ok(())
+    }
+    
+    /// Track the cursor position (window pixels), painting walls along a wall tool drag
+    pub fn handle_cursor_moved(&mut self, gpu: &GpuContext, position: winit::dpi::PhysicalPosition<f64>) {
+        self.cursor = [position.x as f32, position.y as f32];
+        if self.wall_painting {
+            self.paint_wall_at_cursor(gpu);
+        }
+    }
+    
+    /// Left press: jump the camera when on the minimap, otherwise start a cull drag or wall stroke
+    pub fn handle_mouse_press(&mut self, gpu: &GpuContext) {
+        let surface_size = [gpu.config.width, gpu.config.height];
+        if let Some(world_pos) = self.minimap.hit(self.cursor, surface_size, self.sim_config.world.size) {
+            self.camera.center = world_pos;
+            println!("Camera moved to ({:.1}, {:.1})", world_pos[0], world_pos[1]);
+        } else if self.cull_tool != CullTool::Off {
+            self.cull_drag = Some(self.camera.screen_to_world(self.cursor, surface_size));
+        } else if self.wall_tool != WallTool::Off {
+            self.wall_painting = true;
+            self.paint_wall_at_cursor(gpu);
+        }
+    }
+    
+    /// Draw or erase a disc of wall under the cursor
+    ///
+    /// The brush edits the obstacle texture the RD and agent passes already
+    /// bind, so the next step diffuses around and steers away from the new
+    /// walls. A world configured without obstacles turns them on here.
+    fn paint_wall_at_cursor(&mut self, gpu: &GpuContext) {
+        let center = self.camera.screen_to_world(self.cursor, [gpu.config.width, gpu.config.height]);
+        let brush = ObstacleBrush { center, radius: WALL_BRUSH_RADIUS, erase: self.wall_tool == WallTool::Erase };
+        self.obstacles.paint(&gpu.device, &gpu.queue, &self.layouts, &self.compute_pipelines, brush);
+        self.sim_config.obstacles.enabled = true;
+        self.record(Interaction::Wall { center, radius: brush.radius, erase: brush.erase });
+    }
+    
+    /// Append an interaction at the current step to the `--record` script and rewrite the file
+    fn record(&mut self, action: Interaction) {
+        let Some((path, script)) = self.recording.as_mut() else { return };
+        script.push(self.current_step, action);
+        if let Err(e) = script.save(path) {
+            log::error!("{}", e);
+        }
+    }
+    
+    /// Bring the `--record` script back to the current step after a rewind, reset or recovery
+    fn rewind_recording(&mut self) {
+        let Some((path, script)) = self.recording.as_mut() else { return };
+        script.rewind_to(self.current_step);
+        if let Err(e) = script.save(path) {
+            log::error!("{}", e);
+        }
+    }
+    
+    /// Left release: end a wall stroke, or finish a cull drag and kill the agents in the dragged region
+    pub fn handle_mouse_release(&mut self, gpu: &GpuContext) {
+        self.wall_painting = false;
+        let Some(start) = self.cull_drag.take() else { return };
+        let end = self.camera.screen_to_world(self.cursor, [gpu.config.width, gpu.config.height]);
         
-        // For now, return placeholder values - we'll implement proper metrics later
-        let mean_r = 0.5; // Placeholder
-        let mean_gradient = 0.1; // Placeholder
-        let foraging_efficiency = 0.8; // Placeholder
+        let region = match self.cull_tool {
+            CullTool::Off => return,
+            CullTool::Rect => CullRegion::Rect { a: start, b: end },
+            CullTool::Circle => {
+                let radius = ((end[0] - start[0]).powi(2) + (end[1] - start[1]).powi(2)).sqrt();
+                CullRegion::Circle { center: start, radius }
+            }
+        };
+        
+        let Some(culler) = &self.culler else { return };
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("cull_encoder"),
+        });
+        culler.cull(&gpu.device, &gpu.queue, &mut encoder, &self.compute_pipelines, region);
+        gpu.queue.submit(Some(encoder.finish()));
+        self.record(Interaction::Cull { region });
+        
+        println!("Culled agents in {:?}", region);
+    }
+    
+    /// Tag alive agents around the cursor with a fresh cohort id
+    pub fn tag_cohort_at_cursor(&mut self, gpu: &GpuContext) {
+        let surface_size = [gpu.config.width, gpu.config.height];
+        let center = self.camera.screen_to_world(self.cursor, surface_size);
+        
+        self.sync_agents(gpu);
+        let cohort = self.cohorts.next_id();
+        let tagged = self.cohorts.tag_within(
+            &mut self.agent_manager.agents, cohort, self.current_step, center, TAG_RADIUS, None,
+        );
+        self.upload_agents(gpu);
+        self.record(Interaction::Tag { id: cohort, center, radius: TAG_RADIUS });
         
-        (alive_agents, mean_r, mean_gradient, foraging_efficiency)
+        println!("Tagged {} agents as cohort {} around ({:.1}, {:.1})", tagged, cohort, center[0], center[1]);
+    }
+    
+    /// Select the alive agent nearest the cursor for the energy panel, or clear the selection if none is near
+    fn inspect_agent_at_cursor(&mut self, gpu: &GpuContext) {
+        let surface_size = [gpu.config.width, gpu.config.height];
+        let at = self.camera.screen_to_world(self.cursor, surface_size);
+        self.sync_agents(gpu);
+        let slot = vireo_core::sim::nearest_alive(&self.agent_manager.agents, at, TAG_RADIUS);
+        self.energy_probe.select(&gpu.queue, slot);
+        match slot {
+            Some(slot) => println!("Inspecting agent {}: energy budget over the last {} steps in the HUD", slot, EnergyProbe::STEPS),
+            None => println!("No agent within {} cells of ({:.1}, {:.1}); selection cleared", TAG_RADIUS, at[0], at[1]),
+        }
+    }
+    
+    /// Download the agent buffer and foraging tally into `agent_manager` (host time shows as "readback" in the timeline)
+    fn sync_agents(&mut self, gpu: &GpuContext) {
+        let Some(agents_buffer) = &self.agents_buffer else { return };
+        let readback = Instant::now();
+        let size = std::mem::size_of_val(self.agent_manager.agents.as_slice()) as u64;
+        self.agent_manager.agents = read_buffer_from::<Agent>(&gpu.device, &gpu.queue, agents_buffer, size);
+        if let Some(foraging) = &self.foraging {
+            self.agent_manager.forage = foraging.read(&gpu.device, &gpu.queue);
+        }
+        self.profiler.record_cpu("readback", readback.elapsed().as_secs_f32() * 1e3);
+    }
+    
+    /// Upload `agent_manager` to the agent buffer after CPU-side edits
+    fn upload_agents(&self, gpu: &GpuContext) {
+        if let Some(agents_buffer) = &self.agents_buffer {
+            gpu.queue.write_buffer(agents_buffer, 0, bytemuck::cast_slice(&self.agent_manager.agents));
+        }
+    }
+    
+    /// Zero the foraging tally, e.g. after jumping to another point of the run
+    fn restart_foraging(&mut self, gpu: &GpuContext) {
+        self.agent_manager.forage.fill([0.0; 2]);
+        self.agent_manager.stats.consumed = 0.0;
+        self.agent_manager.stats.distance = 0.0;
+        if let Some(foraging) = &self.foraging {
+            foraging.write(&gpu.queue, &self.agent_manager.forage);
+        }
+    }
+    
+    /// Mouse wheel zooms the camera
+    pub fn handle_scroll(&mut self, delta: MouseScrollDelta) {
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(p) => p.y as f32 / 40.0,
+        };
+        self.camera.zoom_by(1.25f32.powf(lines), self.sim_config.world.size);
+    }
+
+    /// Read back the field and agents and recompute their stats (once per HUD refresh)
+    fn refresh_stats(&mut self, gpu: &GpuContext) {
+        let readback = Instant::now();
+        if let Err(e) = self.field_textures.download_field_data(&gpu.device, &gpu.queue, &mut self.field_manager) {
+            eprintln!("Warning: field readback failed, stats use the previous field: {}", e);
+        }
+        self.profiler.record_cpu("readback", readback.elapsed().as_secs_f32() * 1e3);
+        self.sync_agents(gpu);
+        self.field_manager.update_stats();
+        self.agent_manager.update_stats();
+    }
+
+    /// Simulation statistics for the HUD as of the last `refresh_stats`:
+    /// (alive agents, mean R, mean |∇R|, foraging efficiency)
+    pub fn get_stats(&self) -> (u32, f32, f32, f32) {
+        let field = &self.field_manager.stats;
+        let agents = &self.agent_manager.stats;
+        (agents.alive_count, field.mean_R, field.mean_grad_R, agents.foraging_efficiency)
     }
 
     /// Print available controls to the console
@@ -720,22 +1675,50 @@ impl Viewer {
         println!("Single Step: s");
         println!("Emissions: e");
         println!("Overlays: 1 - R field, 2 - W field, 3 - Occupancy, g - Gradients");
+        println!("Contours: l - toggle R contour lines, - / = - halve / double the levels");
         println!("Scenario: F1 - Baseline, F2 - Clumpy, F3 - Flat");
+        println!("Camera: wheel - zoom, arrows - pan, c - reset, m - toggle minimap, click minimap - jump");
+        println!("Agents: k - toggle the kernel density view");
+        println!("Cohorts: t - tag agents under the cursor");
+        println!("Inspect: i - log the energy budget of the agent under the cursor (again on empty ground to clear)");
+        println!("Profiling: p - toggle the per-pass timeline chart");
+        println!("Ages: a - toggle the age structure chart");
+        println!("Metrics window: d - open/close a second window holding the charts");
+        println!("Cull: x - cycle tool (off/rect/circle), drag - kill agents in region");
+        println!("Walls: o - cycle tool (off/draw/erase), drag - paint obstacles");
+        println!("Console: ` - type a command ({}) into the title bar, Enter - run, Esc - cancel", CONSOLE_USAGE);
         println!("========================");
     }
 }
 
 /// Run the interactive viewer
-pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
+///
+/// `settings` restore the last session's window, camera and overlays; they are
+/// captured again on exit and written to `settings_path` (if any). A
+/// `population` replaces the spawned agents, at start and on every reset.
+pub async fn run_viewer(
+    mut sim_config: SimulationConfig,
+    snapshot: Option<StateSnapshot>,
+    step_clock: StepClock,
+    record: Option<PathBuf>,
+    population: Option<AgentPopulation>,
+    mut settings: ViewerSettings,
+    settings_path: Option<PathBuf>,
+) -> Result<()> {
     println!("Creating event loop...");
     let event_loop = EventLoop::new()?;
     
     // Create window and wrap in Arc for proper ownership
     println!("Creating window...");
-    let window = Arc::new(WindowBuilder::new()
-        .with_title("Vireo Ecosystem Simulation")
-        .with_inner_size(LogicalSize::new(1024.0, 768.0))
-        .build(&event_loop)?);
+    let mut builder = WindowBuilder::new().with_title(WINDOW_TITLE);
+    builder = match settings.window {
+        Some(w) if w.size[0] > 0 && w.size[1] > 0 => builder.with_inner_size(PhysicalSize::new(w.size[0], w.size[1])),
+        _ => builder.with_inner_size(LogicalSize::new(1024.0, 768.0)),
+    };
+    if let Some([x, y]) = settings.window.and_then(|w| w.position) {
+        builder = builder.with_position(PhysicalPosition::new(x, y));
+    }
+    let window = Arc::new(builder.build(&event_loop)?);
     
     println!("Creating viewer...");
     let instance = Instance::default();
@@ -749,18 +1732,22 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
         .await
         .expect("Failed to find an appropriate adapter");
     
-    let (device, queue) = adapter
-        .request_device(
-            &wgpu::DeviceDescriptor {
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::default(),
-                label: None,
-            },
-            None,
-        )
-        .await
-        .expect("Failed to create device");
+    let (device, queue) = request_device(&adapter).await;
+    let watchdog = DeviceWatchdog::attach(&device);
+
+    // Fail (or shrink the world) before allocating anything that would not fit
+    let (estimate, downscale) = vireo_core::fit_to_budget(&mut sim_config, &device.limits())?;
+    if let Some(d) = downscale {
+        eprintln!("Warning: world {}x{} with {} herbivores does not fit the GPU, downscaled to {}x{} with {}",
+            d.from_size[0], d.from_size[1], d.from_herbivores,
+            sim_config.world.size[0], sim_config.world.size[1], sim_config.agents.herbivores);
+    }
+    println!("GPU memory estimate: {}", estimate);
+    if let Some(population) = &population {
+        population.check_compatible(&sim_config).map_err(|e| anyhow::anyhow!("initial agents do not fit the world: {}", e))?;
+    }
     
+    let display = settings.display;
     let surface_caps = surface.get_capabilities(&adapter);
     let surface_format = surface_caps.formats.iter()
         .copied()
@@ -772,11 +1759,17 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
         format: surface_format,
         width: window.inner_size().width,
         height: window.inner_size().height,
-        present_mode: surface_caps.present_modes[0],
+        present_mode: display.present_mode.choose(&surface_caps.present_modes),
         alpha_mode: surface_caps.alpha_modes[0],
         view_formats: vec![],
-        desired_maximum_frame_latency: 2,
+        desired_maximum_frame_latency: display.frame_latency,
     };
+    if config.present_mode != display.present_mode.to_wgpu() {
+        eprintln!("Warning: present mode {:?} is not supported here (only {:?}); using Fifo",
+            display.present_mode, surface_caps.present_modes);
+    }
+    println!("Present mode {:?}, frame latency {}, {}", config.present_mode, config.desired_maximum_frame_latency,
+        if display.max_fps > 0.0 { format!("up to {} fps", display.max_fps) } else { "uncapped".to_string() });
     surface.configure(&device, &config);
 
     let mut gpu = GpuContext {
@@ -784,6 +1777,7 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
         queue,
         surface,
         config,
+        watchdog,
     };
 
     // Check RGBA16Float filtering support for runtime fallback
@@ -798,8 +1792,23 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
         println!("WARNING: RGBA16Float does not support filtering on this GPU. Consider implementing non-filtering fallback.");
     }
 
-    let mut viewer = Viewer::new(window.clone(), &gpu, sim_config)?;
-    let renderer = Renderer::new(&gpu.device, &gpu.config, &viewer.layouts)?;
+    let mut viewer = Viewer::new(window.clone(), &gpu, sim_config, step_clock, population)?;
+    if let Some(snapshot) = &snapshot {
+        viewer.load_snapshot(&gpu, snapshot)?;
+    }
+    viewer.apply_settings(&settings);
+    if let Some(placement) = settings.metrics_window {
+        if let Err(e) = viewer.toggle_metrics_window(&event_loop, &instance, &adapter, &gpu, Some(placement)) {
+            eprintln!("Warning: metrics window not reopened: {}", e);
+        }
+    }
+    if let Some(path) = record {
+        println!("Recording interactions to {}", path.display());
+        let script = InteractionScript { seed: Some(viewer.sim_config.world.seed), events: Vec::new() };
+        viewer.recording = Some((path, script));
+        viewer.rewind_recording();
+    }
+    let mut renderer = Renderer::new(&gpu.device, &gpu.config, &viewer.layouts)?;
     println!("Viewer created successfully!");
     
     // Print controls help
@@ -812,8 +1821,8 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
     
     // Use a simple timer-based approach with ControlFlow::Poll
     let mut last_update = Instant::now();
-    let target_fps = 60.0;
-    let frame = Duration::from_secs_f64(1.0 / target_fps);
+    let mut recoveries = 0;
+    let frame = display.frame_interval();
     
     event_loop.run(move |event, elwt| {
         // Set control flow to Poll for continuous updates
@@ -838,7 +1847,7 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
                             ..
                         },
                         ..
-                    } => {
+                    } if !viewer.console.is_open() => {
                         println!("Escape key pressed");
                         elwt.exit();
                     }
@@ -855,6 +1864,62 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
                             log::error!("Key handling error: {}", e);
                         }
                     }
+                    WindowEvent::CursorMoved { position, .. } => {
+                        viewer.handle_cursor_moved(&gpu, position);
+                    }
+                    WindowEvent::MouseInput {
+                        state: ElementState::Pressed,
+                        button: MouseButton::Left,
+                        ..
+                    } => {
+                        viewer.handle_mouse_press(&gpu);
+                    }
+                    WindowEvent::MouseInput {
+                        state: ElementState::Released,
+                        button: MouseButton::Left,
+                        ..
+                    } => {
+                        viewer.handle_mouse_release(&gpu);
+                    }
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        viewer.handle_scroll(delta);
+                    }
+                    _ => {}
+                }
+            }
+            
+            // The metrics window only resizes and closes; keys act as in the main window
+            Event::WindowEvent { window_id, event } if viewer.metrics_window.as_ref().is_some_and(|m| m.id() == window_id) => {
+                match event {
+                    WindowEvent::CloseRequested
+                    | WindowEvent::KeyboardInput {
+                        event: KeyEvent {
+                            logical_key: winit::keyboard::Key::Named(winit::keyboard::NamedKey::Escape),
+                            state: ElementState::Pressed,
+                            ..
+                        },
+                        ..
+                    } => {
+                        viewer.metrics_window = None;
+                        println!("Metrics window closed; charts back in the main window");
+                    }
+                    WindowEvent::Resized(physical_size) => {
+                        if let Some(metrics) = &mut viewer.metrics_window {
+                            metrics.resize(&gpu.device, physical_size);
+                        }
+                    }
+                    WindowEvent::KeyboardInput {
+                        event: KeyEvent {
+                            logical_key,
+                            state: ElementState::Pressed,
+                            ..
+                        },
+                        ..
+                    } => {
+                        if let Err(e) = viewer.handle_key(&logical_key) {
+                            log::error!("Key handling error: {}", e);
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -883,9 +1948,39 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
                         println!("Simulation update completed");
                     }
                     
+                    // Windows can only be opened or closed here, with the event loop at hand
+                    if std::mem::take(&mut viewer.controls.metrics_window_requested) {
+                        if let Err(e) = viewer.toggle_metrics_window(elwt, &instance, &adapter, &gpu, settings.metrics_window) {
+                            eprintln!("Warning: cannot open the metrics window: {}", e);
+                        }
+                    }
+                    
+                    // Device lost: rebuild everything on a new device from the last checkpoint
+                    if let Some(cause) = gpu.watchdog.check(&gpu.device) {
+                        log::error!("GPU {}", cause);
+                        if recoveries >= viewer.max_recoveries() {
+                            eprintln!("GPU {}; giving up after {} recoveries", cause, recoveries);
+                            elwt.exit();
+                            return;
+                        }
+                        recoveries += 1;
+                        eprintln!("Warning: GPU {}; recreating the device (recovery {} of {})", cause, recoveries, viewer.max_recoveries());
+                        gpu.recreate_device(&adapter);
+                        let rebuilt = viewer.recover(&gpu)
+                            .and_then(|()| Renderer::new(&gpu.device, &gpu.config, &viewer.layouts));
+                        match rebuilt {
+                            Ok(new_renderer) => renderer = new_renderer,
+                            Err(e) => {
+                                eprintln!("Recovery failed: {}", e);
+                                elwt.exit();
+                                return;
+                            }
+                        }
+                    }
+                    
                     println!("Starting render");
                     // Render frame
-                    if let Err(e) = viewer.render(&gpu, &renderer) {
+                    if let Err(e) = viewer.render(&gpu, &mut renderer) {
                         log::error!("Render error: {}", e);
                         println!("Render failed: {}", e);
                     } else {
@@ -897,6 +1992,17 @@ pub async fn run_viewer(sim_config: SimulationConfig) -> Result<()> {
                 }
             }
             
+            // Remember the window, camera and overlays for the next launch
+            Event::LoopExiting => {
+                if let Some(path) = &settings_path {
+                    viewer.capture_settings(&mut settings);
+                    match settings.save(path) {
+                        Ok(()) => println!("Viewer settings saved to {}", path.display()),
+                        Err(e) => eprintln!("Warning: viewer settings not saved: {}", e),
+                    }
+                }
+            }
+            
             _ => {
                 println!("Other event: {:?}", event);
             }
@@ -0,0 +1,123 @@
+//! Benchmark for `FieldManager::update_stats` on large grids
+//!
+//! Times the banded, parallel reduction against the scalar double loop it
+//! replaced (kept below for comparison) on seeded 1024² and 2048² fields, and
+//! fails if the moments stray from an f64 reference or the extremes differ.
+//!
+//! cargo bench -p vireo-core --bench field_stats
+
+use std::time::{Duration, Instant};
+use vireo_core::sim::{FieldManager, FieldStats};
+use vireo_core::FieldChannel;
+
+const ROUNDS: u32 = 10;
+
+/// The previous implementation: one pass over every cell with f32 accumulators
+fn scalar_stats(field: &FieldManager) -> FieldStats {
+    let (mut sum_R, mut sum_W, mut sum_R_sq, mut sum_W_sq, mut sum_grad_R) = (0.0f32, 0.0f32, 0.0f32, 0.0f32, 0.0f32);
+    let (mut max_R, mut max_W, mut min_R, mut min_W) = (f32::NEG_INFINITY, f32::NEG_INFINITY, f32::INFINITY, f32::INFINITY);
+    let count = field.len() as f32;
+    let resource = field.channel_f32(FieldChannel::Resource);
+    let waste = field.channel_f32(FieldChannel::Waste);
+    let at = |x: u32, y: u32| (y * field.size[0] + x) as usize;
+
+    for y in 0..field.size[1] {
+        for x in 0..field.size[0] {
+            let (R, W) = (resource[at(x, y)], waste[at(x, y)]);
+            sum_R += R;
+            sum_W += W;
+            sum_R_sq += R * R;
+            sum_W_sq += W * W;
+            max_R = max_R.max(R);
+            max_W = max_W.max(W);
+            min_R = min_R.min(R);
+            min_W = min_W.min(W);
+            if x > 0 && x < field.size[0] - 1 && y > 0 && y < field.size[1] - 1 {
+                let dx = (resource[at(x + 1, y)] - resource[at(x - 1, y)]) / 2.0;
+                let dy = (resource[at(x, y + 1)] - resource[at(x, y - 1)]) / 2.0;
+                sum_grad_R += (dx * dx + dy * dy).sqrt();
+            }
+        }
+    }
+
+    let (mean_R, mean_W) = (sum_R / count, sum_W / count);
+    FieldStats {
+        mean_R,
+        mean_W,
+        var_R: (sum_R_sq / count - mean_R * mean_R).max(0.0),
+        var_W: (sum_W_sq / count - mean_W * mean_W).max(0.0),
+        mean_grad_R: sum_grad_R / count,
+        max_R,
+        max_W,
+        min_R,
+        min_W,
+        mean_soil: 0.0,
+        max_soil: 0.0,
+    }
+}
+
+/// Mean wall time of `f` over `ROUNDS` calls, after one warm-up call
+fn time(mut f: impl FnMut()) -> Duration {
+    f();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    start.elapsed() / ROUNDS
+}
+
+/// Mean R, variance of R and mean gradient magnitude, summed in f64
+fn exact_moments(field: &FieldManager) -> [f64; 3] {
+    let r = field.channel_f32(FieldChannel::Resource);
+    let [w, h] = [field.size[0] as usize, field.size[1] as usize];
+    let n = r.len() as f64;
+    let mean = r.iter().map(|&v| v as f64).sum::<f64>() / n;
+    let var = r.iter().map(|&v| (v as f64) * (v as f64)).sum::<f64>() / n - mean * mean;
+    let mut grad = 0.0;
+    for y in 1..h - 1 {
+        for x in 1..w - 1 {
+            let dx = (r[y * w + x + 1] - r[y * w + x - 1]) as f64 / 2.0;
+            let dy = (r[(y + 1) * w + x] - r[(y - 1) * w + x]) as f64 / 2.0;
+            grad += (dx * dx + dy * dy).sqrt();
+        }
+    }
+    [mean, var, grad / n]
+}
+
+fn relative_error(value: f32, exact: f64) -> f64 {
+    (value as f64 - exact).abs() / exact.abs().max(1e-9)
+}
+
+fn main() {
+    for side in [1024, 2048] {
+        let mut field = FieldManager::new([side, side]);
+        field.seed_resources(1337);
+        field.add_noise(0.05, 7);
+
+        let exact = exact_moments(&field);
+        let reference = scalar_stats(&field);
+        field.update_stats();
+        let s = field.stats.clone();
+        let error = |stats: &FieldStats| {
+            [stats.mean_R, stats.var_R, stats.mean_grad_R].iter().zip(exact)
+                .map(|(&v, e)| relative_error(v, e))
+                .fold(0.0, f64::max)
+        };
+        assert!(error(&s) < 1e-5, "stats off the f64 reference by {:e}: {:?} vs {:?}", error(&s), s, exact);
+        assert_eq!([s.max_R, s.min_R, s.max_W, s.min_W], [reference.max_R, reference.min_R, reference.max_W, reference.min_W]);
+
+        let scalar = time(|| {
+            std::hint::black_box(scalar_stats(&field));
+        });
+        let banded = time(|| field.update_stats());
+        println!(
+            "{0}x{0}: scalar {1:.2} ms (max rel. error {2:.1e}), banded {3:.2} ms ({4:.1e}), {5:.1}x",
+            side,
+            scalar.as_secs_f64() * 1e3,
+            error(&reference),
+            banded.as_secs_f64() * 1e3,
+            error(&s),
+            scalar.as_secs_f64() / banded.as_secs_f64(),
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use serde_yaml::Value;
+use vireo_params::{ConfigError, ParamSchedule, SimulationConfig, PRESETS};
+
+/// Why a configuration file could not be turned into a runnable `SimulationConfig`
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read
+    Io { path: PathBuf, source: std::io::Error },
+    /// The YAML is malformed or does not match the config schema
+    Parse(serde_yaml::Error),
+    /// The config parsed but a value is out of range
+    Invalid(ConfigError),
+    /// A forcing series file could not be read
+    Forcing { path: PathBuf, reason: String },
+    /// `preset` does not name one of `PRESETS`
+    UnknownPreset(String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, source } => write!(f, "cannot read config {}: {}", path.display(), source),
+            LoadError::Parse(e) => write!(f, "cannot parse config: {}", e),
+            LoadError::Invalid(e) => write!(f, "{}", e),
+            LoadError::Forcing { path, reason } => write!(f, "cannot read forcing series {}: {}", path.display(), reason),
+            LoadError::UnknownPreset(name) => write!(f, "unknown preset `{}` (expected one of {})", name, PRESETS.join(", ")),
+        }
+    }
+}
+
+// Display already carries the underlying message, so no `source` chain
+impl std::error::Error for LoadError {}
+
+/// Parse and validate a YAML configuration
+///
+/// Anything the file leaves out keeps its `SimulationConfig::default()`
+/// value, or the value of the named `preset:` (see `PRESETS`), so a config
+/// only needs the overrides. Inline schedules (`chi_R: {schedule: linear, ...}`)
+/// are moved into `schedules`, leaving their `from` value in place.
+pub fn parse_config(yaml: &str) -> Result<SimulationConfig, LoadError> {
+    let mut value: Value = serde_yaml::from_str(yaml).map_err(LoadError::Parse)?;
+    let preset = take_preset(&mut value)?;
+    let inline = take_inline_schedules(&mut value).map_err(LoadError::Parse)?;
+    let mut config: SimulationConfig = match preset {
+        Some(base) => {
+            let mut merged = serde_yaml::to_value(&base).map_err(LoadError::Parse)?;
+            overlay(&mut merged, value);
+            serde_yaml::from_value(merged)
+        }
+        // Parsing the text again keeps line numbers in schema errors
+        None if inline.is_empty() => serde_yaml::from_str(yaml),
+        None => serde_yaml::from_value(value),
+    }.map_err(LoadError::Parse)?;
+    config.schedules.extend(inline);
+    config.validate().map_err(LoadError::Invalid)?;
+    Ok(config)
+}
+
+/// Remove the top-level `preset: <name>` and return that preset
+fn take_preset(value: &mut Value) -> Result<Option<SimulationConfig>, LoadError> {
+    let Some(preset) = value.as_mapping_mut().and_then(|m| m.remove("preset")) else { return Ok(None) };
+    let name = match &preset {
+        Value::String(name) => name.clone(),
+        other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+    };
+    SimulationConfig::preset(&name).map(Some).ok_or(LoadError::UnknownPreset(name))
+}
+
+/// Merge `top` into `base`: mappings key by key, anything else (values,
+/// lists such as `schedules`) replaced whole
+fn overlay(base: &mut Value, top: Value) {
+    match (base, top) {
+        (Value::Mapping(base), Value::Mapping(top)) => {
+            for (key, value) in top {
+                match base.get_mut(&key) {
+                    Some(slot) => overlay(slot, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, top) => *base = top,
+    }
+}
+
+/// Replace every `section.key: {schedule: ...}` with its `from` value and
+/// return the schedules, named `section.key`
+fn take_inline_schedules(value: &mut Value) -> Result<Vec<ParamSchedule>, serde_yaml::Error> {
+    let mut schedules = Vec::new();
+    let Value::Mapping(sections) = value else { return Ok(schedules) };
+    for (section, body) in sections.iter_mut() {
+        let Value::Mapping(params) = body else { continue };
+        for (key, param) in params.iter_mut() {
+            let Value::Mapping(fields) = param else { continue };
+            if !fields.contains_key("schedule") {
+                continue;
+            }
+            let mut fields = fields.clone();
+            let name = format!("{}.{}", section.as_str().unwrap_or_default(), key.as_str().unwrap_or_default());
+            fields.insert("param".into(), name.into());
+            let schedule: ParamSchedule = serde_yaml::from_value(Value::Mapping(fields))?;
+            *param = (schedule.from as f64).into();
+            schedules.push(schedule);
+        }
+    }
+    Ok(schedules)
+}
+
+/// Read, parse and validate a YAML configuration file
+///
+/// Forcing series are read from their CSV files (relative to the config's
+/// directory) and validated with the rest of the config.
+pub fn load_config(path: &Path) -> Result<SimulationConfig, LoadError> {
+    let yaml = std::fs::read_to_string(path)
+        .map_err(|source| LoadError::Io { path: path.to_path_buf(), source })?;
+    let mut config = parse_config(&yaml)?;
+    if !config.forcing.is_empty() {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for forcing in &mut config.forcing {
+            forcing.file = dir.join(&forcing.file);
+            forcing.points = read_forcing_series(&forcing.file, &forcing.step_column, &forcing.value_column)
+                .map_err(|reason| LoadError::Forcing { path: forcing.file.clone(), reason })?;
+        }
+        config.validate().map_err(LoadError::Invalid)?;
+    }
+    Ok(config)
+}
+
+/// (step, value) samples from two named columns of a CSV file with a header row
+pub fn read_forcing_series(path: &Path, step_column: &str, value_column: &str) -> Result<Vec<[f32; 2]>, String> {
+    let mut reader = csv::Reader::from_path(path).map_err(|e| e.to_string())?;
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let column = |name: &str| headers.iter().position(|h| h.trim() == name)
+        .ok_or_else(|| format!("no `{}` column (found {})", name, headers.iter().collect::<Vec<_>>().join(", ")));
+    let (step, value) = (column(step_column)?, column(value_column)?);
+    let mut points = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| e.to_string())?;
+        let parse = |i: usize, name: &str| record.get(i).unwrap_or_default().trim().parse::<f32>()
+            .map_err(|_| format!("row {}: `{}` is not a number in column `{}`", row + 1, record.get(i).unwrap_or_default(), name));
+        points.push([parse(step, step_column)?, parse(value, value_column)?]);
+    }
+    if points.is_empty() {
+        return Err("no samples".to_string());
+    }
+    Ok(points)
+}
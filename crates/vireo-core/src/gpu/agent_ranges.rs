@@ -0,0 +1,56 @@
+use wgpu::{Buffer, Device, util::DeviceExt};
+use vireo_params::AgentRange;
+use crate::sim::AgentLayout;
+
+/// Threads per workgroup of the per-agent passes (agent_step.wgsl, demography.wgsl)
+const WORKGROUP_SIZE: u32 = 128;
+
+/// Per-species slot ranges of the agent buffer as one uniform buffer, one
+/// `AgentRange` per kind at the device's dynamic offset alignment
+///
+/// The agent and demography passes bind it with a dynamic offset and dispatch
+/// each non-empty species separately, sized by that species' capacity.
+pub struct AgentRanges {
+    layout: AgentLayout,
+    buffer: Buffer,
+    stride: u32,
+}
+
+impl AgentRanges {
+    pub fn new(device: &Device, layout: &AgentLayout) -> Self {
+        let stride = device.limits().min_uniform_buffer_offset_alignment;
+        let mut contents = vec![0u8; 3 * stride as usize];
+        for kind in 0..3 {
+            let range = layout.range(kind);
+            let entry = AgentRange { offset: range.start, count: range.len() as u32, _pad: [0; 2] };
+            let at = (kind * stride) as usize;
+            contents[at..at + std::mem::size_of::<AgentRange>()].copy_from_slice(bytemuck::bytes_of(&entry));
+        }
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("agent_ranges"),
+            contents: &contents,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        Self { layout: layout.clone(), buffer, stride }
+    }
+
+    /// Binding of a single `AgentRange`; the dynamic offset picks the species
+    pub fn binding(&self) -> wgpu::BindingResource<'_> {
+        wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+            buffer: &self.buffer,
+            offset: 0,
+            size: wgpu::BufferSize::new(std::mem::size_of::<AgentRange>() as u64),
+        })
+    }
+
+    /// (dynamic offset, workgroups) of every species with at least one slot
+    pub fn dispatches(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (0..3)
+            .filter(|&kind| self.layout.capacity(kind) > 0)
+            .map(|kind| (kind * self.stride, self.layout.capacity(kind).div_ceil(WORKGROUP_SIZE)))
+    }
+
+    pub fn layout(&self) -> &AgentLayout {
+        &self.layout
+    }
+}
@@ -0,0 +1,104 @@
+use wgpu::{BindGroup, Buffer, CommandEncoder, Device, Queue, util::DeviceExt};
+use crate::SimulationConfig;
+use crate::gpu::ComputePipelines;
+use crate::gpu::layouts::Layouts;
+use crate::sim::{BloomScheduler, RainScheduler};
+
+/// Bloom injection pass: rasterizes the scheduler's active blooms and the
+/// moving rain cells into a per-cell sigma_R boost map that the RD pass reads
+/// at binding 4
+///
+/// The map only changes when a bloom starts or expires or while rain cells
+/// move, so the pass is dispatched on those steps alone.
+pub struct BloomInjector {
+    pub scheduler: BloomScheduler,
+    pub rain: RainScheduler,
+    params_buffer: Buffer,
+    boost_buffer: Buffer,
+    bind_group: BindGroup,
+    size: [u32; 2],
+}
+
+impl BloomInjector {
+    pub fn new(device: &Device, layouts: &Layouts, config: &SimulationConfig) -> Self {
+        let scheduler = BloomScheduler::new(config.blooms.clone(), &config.world);
+        let rain = RainScheduler::new(config.rain.clone(), &config.world);
+        let size = config.world.size;
+
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("bloom_params"),
+            contents: bytemuck::bytes_of(&scheduler.to_params()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        // Zero-initialized by wgpu: no boost until the first bloom
+        let boost_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sigma_boost"),
+            size: (size[0] * size[1]) as u64 * 4, // f32 per cell
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("bloom_bg"),
+            layout: &layouts.bloom,
+            entries: &[
+                // @binding(0) BloomParams uniform
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                // @binding(1) sigma_R boost buffer
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: boost_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            scheduler,
+            rain,
+            params_buffer,
+            boost_buffer,
+            bind_group,
+            size,
+        }
+    }
+
+    /// Boost map bound by the RD pass
+    pub fn boost_buffer(&self) -> &Buffer {
+        &self.boost_buffer
+    }
+
+    /// Tick the schedulers for `step` and encode the injection pass if the boost map changed
+    pub fn update(&mut self, queue: &Queue, encoder: &mut CommandEncoder, pipelines: &ComputePipelines, step: u32) {
+        let blooms = self.scheduler.tick(step);
+        let rain = self.rain.tick(step);
+        if !(blooms || rain) {
+            return;
+        }
+        self.inject(queue, encoder, pipelines);
+    }
+
+    /// Clear all blooms and rain cells (world reset) and encode a pass zeroing the boost map
+    pub fn reset(&mut self, queue: &Queue, encoder: &mut CommandEncoder, pipelines: &ComputePipelines, config: &SimulationConfig) {
+        self.scheduler.reset(&config.world);
+        self.rain.reset(&config.world);
+        self.inject(queue, encoder, pipelines);
+    }
+
+    fn inject(&self, queue: &Queue, encoder: &mut CommandEncoder, pipelines: &ComputePipelines) {
+        let mut params = self.scheduler.to_params();
+        self.rain.pack(&mut params);
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("bloom inject pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&pipelines.bloom_pipeline);
+        cpass.set_bind_group(0, &self.bind_group, &[]);
+        cpass.dispatch_workgroups(self.size[0].div_ceil(8), self.size[1].div_ceil(8), 1);
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use vireo_params::{SimulationConfig, MIN_WORLD_SIZE};
+use crate::gpu::layouts::Layouts;
+use crate::sim::Agent;
+
+/// Bytes per field texel layer (RGBA16F)
+const TEXEL_BYTES: u64 = 8;
+
+/// Estimated GPU allocations of a `Simulation` for a given config
+///
+/// Uniforms, samplers and per-workgroup partials are a few KiB and left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    /// Ping-pong field textures (A and B)
+    pub field_textures: u64,
+    /// Staging buffer for a full field readback
+    pub field_readback: u64,
+    /// Occupancy counts (4 B), bloom boost map (4 B), obstacle distance field (4 B),
+    /// terrain elevation (4 B), movement velocity grid (12 B), when some species
+    /// crowds, flocks or follows a density the neighbour grid (24 B), with
+    /// `diffusion.enabled` the diffusion tensor (16 B), with `refuges.enabled`
+    /// the refuge mask (4 B) and with `separation.enabled` the hash cell ranges (8 B) per cell
+    pub cell_buffers: u64,
+    /// Agent storage buffer (twice over with demography, which keeps a pre-pass copy),
+    /// plus 32 B per slot for the separation hash and sort with `separation.enabled`
+    pub agents: u64,
+    /// Largest single storage buffer (velocity grid or agents)
+    pub largest_storage: u64,
+}
+
+impl MemoryEstimate {
+    pub fn for_config(config: &SimulationConfig) -> Self {
+        let cells = config.world.size[0] as u64 * config.world.size[1] as u64;
+        let field_layer = cells * TEXEL_BYTES * Layouts::FIELD_LAYERS as u64;
+        let vel_grid = cells * 12;
+        let neighbour_grid = if config.steering.needs_neighbours() { cells * 24 } else { 0 };
+        let diffusion_tensor = if config.diffusion.enabled { cells * 16 } else { 0 };
+        let refuge_mask = if config.refuges.enabled { cells * 4 } else { 0 };
+        let cell_ranges = if config.separation.enabled { cells * 8 } else { 0 };
+        let agents = config.agents.total_slots() * std::mem::size_of::<Agent>() as u64;
+        let copies = if config.demography.enabled { 2 } else { 1 };
+        let hash = if config.separation.enabled { config.agents.total_slots() * 32 } else { 0 };
+        Self {
+            field_textures: 2 * field_layer,
+            field_readback: field_layer,
+            cell_buffers: cells * 16 + vel_grid + neighbour_grid + diffusion_tensor + refuge_mask + cell_ranges,
+            agents: copies * agents + hash,
+            largest_storage: vel_grid.max(agents),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.field_textures + self.field_readback + self.cell_buffers + self.agents
+    }
+}
+
+impl fmt::Display for MemoryEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (field textures {}, field readback {}, cell buffers {}, agents {})",
+            mib(self.total()),
+            mib(self.field_textures),
+            mib(self.field_readback),
+            mib(self.cell_buffers),
+            mib(self.agents),
+        )
+    }
+}
+
+fn mib(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1u64 << 20) as f64)
+}
+
+/// A config that would not fit the device, found before allocating anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetError {
+    pub resource: String, // What does not fit, e.g. "field texture width"
+    pub required: u64,
+    pub limit: u64,
+    pub bytes: bool,      // `required`/`limit` are byte counts (printed as MiB)
+}
+
+impl fmt::Display for BudgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (required, limit) = if self.bytes {
+            (mib(self.required), mib(self.limit))
+        } else {
+            (self.required.to_string(), self.limit.to_string())
+        };
+        write!(
+            f,
+            "{} needs {} but the limit is {}; shrink world.size, agents.herbivores or agents.capacity, or set gpu.auto_downscale",
+            self.resource, required, limit,
+        )
+    }
+}
+
+impl std::error::Error for BudgetError {}
+
+/// Compare the estimate for `config` with the device limits and `gpu.budget_mb`
+pub fn check_budget(config: &SimulationConfig, limits: &wgpu::Limits) -> Result<MemoryEstimate, BudgetError> {
+    let estimate = MemoryEstimate::for_config(config);
+    let exceeds = |resource: &str, required: u64, limit: u64, bytes: bool| {
+        (required > limit).then(|| BudgetError { resource: resource.to_string(), required, limit, bytes })
+    };
+
+    let [w, h] = config.world.size;
+    let max_dim = limits.max_texture_dimension_2d as u64;
+    let checks = [
+        exceeds("field texture width", w as u64, max_dim, false),
+        exceeds("field texture height", h as u64, max_dim, false),
+        exceeds("field texture layers", Layouts::FIELD_LAYERS as u64, limits.max_texture_array_layers as u64, false),
+        exceeds("largest storage buffer", estimate.largest_storage, limits.max_storage_buffer_binding_size as u64, true),
+        exceeds("field readback buffer", estimate.field_readback, limits.max_buffer_size, true),
+        exceeds("total GPU memory (gpu.budget_mb)", estimate.total(), budget_bytes(config), true),
+    ];
+    match checks.into_iter().flatten().next() {
+        Some(error) => Err(error),
+        None => Ok(estimate),
+    }
+}
+
+fn budget_bytes(config: &SimulationConfig) -> u64 {
+    match config.gpu.budget_mb {
+        0 => u64::MAX,
+        mb => (mb as u64) << 20,
+    }
+}
+
+/// A config shrunk by `fit_to_budget`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Downscale {
+    pub from_size: [u32; 2],
+    pub from_herbivores: u32,
+}
+
+/// `check_budget`, but with `gpu.auto_downscale` halve the world edges (and
+/// quarter the herbivores, keeping density) until the config fits
+///
+/// Fails with the original error if the world would drop below
+/// `MIN_WORLD_SIZE` or the shrunk config is no longer valid.
+pub fn fit_to_budget(
+    config: &mut SimulationConfig,
+    limits: &wgpu::Limits,
+) -> Result<(MemoryEstimate, Option<Downscale>), BudgetError> {
+    let error = match check_budget(config, limits) {
+        Ok(estimate) => return Ok((estimate, None)),
+        Err(error) if config.gpu.auto_downscale => error,
+        Err(error) => return Err(error),
+    };
+
+    let mut shrunk = config.clone();
+    loop {
+        let [w, h] = shrunk.world.size;
+        if w / 2 < MIN_WORLD_SIZE || h / 2 < MIN_WORLD_SIZE {
+            return Err(error);
+        }
+        shrunk.world.size = [w / 2, h / 2];
+        shrunk.agents.herbivores = (shrunk.agents.herbivores / 4).max(1);
+        let capacity = &mut shrunk.agents.capacity;
+        for slots in [&mut capacity.plants, &mut capacity.herbivores, &mut capacity.predators] {
+            *slots = if *slots == 0 { 0 } else { (*slots / 4).max(1) };
+        }
+        if let Ok(estimate) = check_budget(&shrunk, limits) {
+            if shrunk.validate().is_err() {
+                return Err(error);
+            }
+            let downscale = Downscale { from_size: config.world.size, from_herbivores: config.agents.herbivores };
+            *config = shrunk;
+            return Ok((estimate, Some(downscale)));
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use wgpu::{BindGroup, Buffer, Device, Queue, TextureView};
+use vireo_params::CellGatherParams;
+use crate::gpu::{ComputePipelines, read_buffer_from};
+use crate::gpu::layouts::Layouts;
+use crate::sim::CellSample;
+
+/// Workgroup size of the cell gather shader
+const GATHER_GROUP: u32 = 64;
+
+/// GPU pass reading the field at a list of cells (`output.sampling`)
+///
+/// Each listed cell comes back as R, W, soil damage and |∇R|, 16 bytes no
+/// matter how large the world is. Lists longer than the buffers are gathered
+/// in several dispatches.
+pub struct CellGather {
+    params_buffer: Buffer,
+    cells: Buffer,
+    samples: Buffer,
+    bind_group_a: BindGroup,
+    bind_group_b: BindGroup,
+    size: [u32; 2],
+    capacity: u32,
+}
+
+impl CellGather {
+    /// `field_a`/`field_b` are the sampled views of the two ping-pong textures;
+    /// `capacity` is the most cells one dispatch reads
+    pub fn new(device: &Device, layouts: &Layouts, field_a: &TextureView, field_b: &TextureView, size: [u32; 2], capacity: u32) -> Self {
+        let capacity = capacity.max(1);
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("cell_gather_params"),
+            size: std::mem::size_of::<CellGatherParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let cells = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("cell_gather_cells"),
+            size: capacity as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let samples = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("cell_gather_samples"),
+            size: capacity as u64 * std::mem::size_of::<CellSample>() as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let create_bind_group = |label, field_view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &layouts.cell_gather,
+                entries: &[
+                    // @binding(0) field texture (sampled)
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(field_view),
+                    },
+                    // @binding(1) CellGatherParams uniform
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    // @binding(2) cells to read
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: cells.as_entire_binding(),
+                    },
+                    // @binding(3) one sample per listed cell
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: samples.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+        let bind_group_a = create_bind_group("cell_gather_a_bg", field_a);
+        let bind_group_b = create_bind_group("cell_gather_b_bg", field_b);
+
+        Self { params_buffer, cells, samples, bind_group_a, bind_group_b, size, capacity }
+    }
+
+    /// Read the front field at `cells` (x, y), in list order
+    pub fn gather(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        pipelines: &ComputePipelines,
+        front_is_a: bool,
+        cells: &[[u32; 2]],
+    ) -> Vec<CellSample> {
+        let bind_group = if front_is_a { &self.bind_group_a } else { &self.bind_group_b };
+        let mut samples = Vec::with_capacity(cells.len());
+        for chunk in cells.chunks(self.capacity as usize) {
+            let indices: Vec<u32> = chunk.iter().map(|&[x, y]| y * self.size[0] + x).collect();
+            let params = CellGatherParams { size: self.size, count: chunk.len() as u32, _pad: 0 };
+            queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+            queue.write_buffer(&self.cells, 0, bytemuck::cast_slice(&indices));
+
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("cell_gather"),
+            });
+            {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("cell gather pass"),
+                    timestamp_writes: None,
+                });
+                cpass.set_pipeline(pipelines.cell_gather_pipeline.get(device));
+                cpass.set_bind_group(0, bind_group, &[]);
+                cpass.dispatch_workgroups(params.count.div_ceil(GATHER_GROUP), 1, 1);
+            }
+            queue.submit(Some(encoder.finish()));
+
+            let bytes = chunk.len() as u64 * std::mem::size_of::<CellSample>() as u64;
+            let values: Vec<f32> = read_buffer_from(device, queue, &self.samples, bytes);
+            samples.extend(values.chunks_exact(4).map(|v| [v[0], v[1], v[2], v[3]]));
+        }
+        samples
+    }
+}
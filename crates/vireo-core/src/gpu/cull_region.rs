@@ -0,0 +1,57 @@
+use wgpu::{BindGroup, Buffer, CommandEncoder, Device, Queue, util::DeviceExt};
+use crate::gpu::ComputePipelines;
+use crate::gpu::layouts::Layouts;
+use crate::sim::CullRegion;
+
+/// Region cull pass: sets `alive = 0` for agents inside a `CullRegion`
+pub struct AgentCuller {
+    params_buffer: Buffer,
+    bind_group: BindGroup,
+    agent_count: u32,
+}
+
+impl AgentCuller {
+    pub fn new(device: &Device, layouts: &Layouts, agents_buffer: &Buffer, agent_count: u32) -> Self {
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("cull_params"),
+            contents: bytemuck::bytes_of(&CullRegion::Circle { center: [0.0; 2], radius: 0.0 }.to_params()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("cull_bg"),
+            layout: &layouts.cull,
+            entries: &[
+                // @binding(0) agents storage buffer
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: agents_buffer.as_entire_binding(),
+                },
+                // @binding(1) CullParams uniform
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            params_buffer,
+            bind_group,
+            agent_count,
+        }
+    }
+
+    /// Upload `region` and encode the cull pass
+    pub fn cull(&self, device: &Device, queue: &Queue, encoder: &mut CommandEncoder, pipelines: &ComputePipelines, region: CullRegion) {
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&region.to_params()));
+
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("cull pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(pipelines.cull_pipeline.get(device));
+        cpass.set_bind_group(0, &self.bind_group, &[]);
+        cpass.dispatch_workgroups(self.agent_count.div_ceil(128), 1, 1);
+    }
+}
@@ -0,0 +1,114 @@
+use wgpu::{BindGroup, Buffer, CommandEncoder, Device, Queue, util::DeviceExt};
+use vireo_params::DemographyParams;
+use crate::SimulationConfig;
+use crate::gpu::{AgentRanges, ComputePipelines, EventCounters};
+use crate::gpu::layouts::Layouts;
+use crate::sim::RefugeMask;
+
+/// Demographic noise pass: random deaths and births drawn per agent on the GPU
+///
+/// Runs after the agent pass. The agents are first copied into `prev` so every
+/// thread reads a stable pre-pass state and the outcome is deterministic for a
+/// given seed and step. Each species is dispatched over its own slot range.
+/// With `refuges.enabled` the death draws are scaled by the refuge mask.
+pub struct DemographyPass {
+    params: DemographyParams,
+    params_buffer: Buffer,
+    prev_buffer: Buffer,
+    _refuge_buffer: Buffer,
+    bind_group: BindGroup,
+    agents_bytes: u64,
+    dispatches: Vec<(u32, u32)>,
+}
+
+impl DemographyPass {
+    pub fn new(device: &Device, layouts: &Layouts, config: &SimulationConfig, agents_buffer: &Buffer, ranges: &AgentRanges, events: &EventCounters) -> Self {
+        let params = DemographyParams::new(&config.demography, &config.evolution, &config.refuges, config.world.seed, config.world.size);
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("demography_params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let agents_bytes = agents_buffer.size();
+        let prev_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("demography_prev_agents"),
+            size: agents_bytes,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Without refuges the shader never reads the mask, so one texel stands in
+        let mask = RefugeMask::new(&config.refuges, config.world.size);
+        let mortality = if config.refuges.enabled { mask.mortality() } else { &[1.0] };
+        let refuge_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("demography_refuge_mask"),
+            contents: bytemuck::cast_slice(mortality),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("demography_bg"),
+            layout: &layouts.demography,
+            entries: &[
+                // @binding(0) agents storage buffer
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: agents_buffer.as_entire_binding(),
+                },
+                // @binding(1) agents before this pass
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: prev_buffer.as_entire_binding(),
+                },
+                // @binding(2) DemographyParams uniform
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                // @binding(3) AgentRange uniform (dynamic offset)
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: ranges.binding(),
+                },
+                // @binding(4) births and random deaths per cell
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: events.buffer().as_entire_binding(),
+                },
+                // @binding(5) refuge death multiplier per cell
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: refuge_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            params,
+            params_buffer,
+            prev_buffer,
+            _refuge_buffer: refuge_buffer,
+            bind_group,
+            agents_bytes,
+            dispatches: ranges.dispatches().collect(),
+        }
+    }
+
+    /// Encode the deaths and births of `step` into `agents_buffer`
+    pub fn apply(&mut self, device: &Device, queue: &Queue, encoder: &mut CommandEncoder, pipelines: &ComputePipelines, agents_buffer: &Buffer, step: u32) {
+        self.params.step = step;
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&self.params));
+        encoder.copy_buffer_to_buffer(agents_buffer, 0, &self.prev_buffer, 0, self.agents_bytes);
+
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("demography pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(pipelines.demography_pipeline.get(device));
+        for &(offset, workgroups) in &self.dispatches {
+            cpass.set_bind_group(0, &self.bind_group, &[offset]);
+            cpass.dispatch_workgroups(workgroups, 1, 1);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use wgpu::{Adapter, Device, Instance, Queue, RequestAdapterOptions};
-use crate::{RDParams, AgentParams};
+use crate::{RDParams, AgentParams, TimeUniform};
 use crate::sim::Agent;
+use crate::gpu::DeviceWatchdog;
 use wgpu::util::DeviceExt;
 use bytemuck;
 
@@ -10,21 +11,31 @@ pub struct GpuDevice {
     pub adapter: Adapter,
     pub device: Device,
     pub queue: Queue,
+    pub watchdog: DeviceWatchdog,
 }
 
 impl GpuDevice {
-    /// Create a new GPU device for headless compute
+    /// Create a new GPU device for headless compute, panicking when there is none
     pub async fn new() -> Self {
+        Self::try_new().await.unwrap_or_else(|e| panic!("{}", e))
+    }
+    
+    /// Create a device on the preferred adapter, or on wgpu's software fallback
+    /// adapter when no GPU is present
+    ///
+    /// The fallback (lavapipe, llvmpipe, WARP) runs the same shaders on the
+    /// CPU, so results match a GPU run up to float rounding, only slower; check
+    /// `is_software` to warn about it. Fails only when neither exists.
+    pub async fn try_new() -> Result<Self, String> {
         let instance = Instance::default();
         
-        let adapter = instance
-            .request_adapter(&RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::HighPerformance,
-                compatible_surface: None, // Headless, no surface needed
-                force_fallback_adapter: false,
-            })
-            .await
-            .expect("Failed to find an appropriate adapter");
+        let adapter = match Self::request_adapter(&instance, false).await {
+            Some(adapter) => adapter,
+            None => Self::request_adapter(&instance, true).await.ok_or_else(|| {
+                "No GPU adapter found and no software fallback adapter is available \
+                 (install Mesa's lavapipe or llvmpipe to run on the CPU)".to_string()
+            })?,
+        };
         
         let (device, queue) = adapter
             .request_device(
@@ -36,14 +47,36 @@ impl GpuDevice {
                 None,
             )
             .await
-            .expect("Failed to create device");
+            .map_err(|e| format!("Failed to create a device on {}: {}", adapter.get_info().name, e))?;
+        let watchdog = DeviceWatchdog::attach(&device);
         
-        Self {
+        Ok(Self {
             instance,
             adapter,
             device,
             queue,
-        }
+            watchdog,
+        })
+    }
+    
+    async fn request_adapter(instance: &Instance, fallback: bool) -> Option<Adapter> {
+        instance
+            .request_adapter(&RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: None, // Headless, no surface needed
+                force_fallback_adapter: fallback,
+            })
+            .await
+    }
+    
+    /// Whether the adapter is a software implementation running on the CPU
+    pub fn is_software(&self) -> bool {
+        self.adapter.get_info().device_type == wgpu::DeviceType::Cpu
+    }
+    
+    /// Cause of a device loss since creation, if any (see `DeviceWatchdog`)
+    pub fn lost(&self) -> Option<String> {
+        self.watchdog.check(&self.device)
     }
     
     /// Get device info for logging
@@ -89,11 +122,20 @@ impl GpuDevice {
         )
     }
     
+    /// Create a uniform buffer for the simulation clock
+    pub fn create_time_buffer(&self, time: &TimeUniform) -> wgpu::Buffer {
+        self.create_buffer_with_data(
+            "time",
+            wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            &[*time],
+        )
+    }
+    
     /// Create a storage buffer for agents
     pub fn create_agents_buffer(&self, agents: &[Agent]) -> wgpu::Buffer {
         self.create_buffer_with_data(
             "agents",
-            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
             agents,
         )
     }
@@ -108,10 +150,9 @@ impl GpuDevice {
         )
     }
     
-    /// Clear the occupancy buffer to zero (called each frame before agent update)
-    pub fn clear_occupancy_buffer(&self, buffer: &wgpu::Buffer, size: [u32; 2]) {
-        let zero_data = vec![0u32; (size[0] * size[1]) as usize];
-        self.queue.write_buffer(buffer, 0, bytemuck::cast_slice(&zero_data));
+    /// Copy a GPU buffer into a staging buffer and read it back as `T`s
+    pub fn read_buffer<T: bytemuck::Pod>(&self, buffer: &wgpu::Buffer, size: u64) -> Vec<T> {
+        read_buffer_from(&self.device, &self.queue, buffer, size)
     }
     
     /// Submit commands to the GPU
@@ -125,3 +166,31 @@ impl GpuDevice {
         self.device.poll(wgpu::Maintain::Wait);
     }
 }
+
+/// Copy a GPU buffer into a staging buffer and read it back as `T`s
+///
+/// Free-standing so frontends that own a bare device/queue (the viewer) can read back too.
+pub fn read_buffer_from<T: bytemuck::Pod>(device: &Device, queue: &Queue, buffer: &wgpu::Buffer, size: u64) -> Vec<T> {
+    let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("readback_staging"),
+        size,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("readback_copy"),
+    });
+    encoder.copy_buffer_to_buffer(buffer, 0, &staging_buffer, 0, size);
+    queue.submit(Some(encoder.finish()));
+    
+    staging_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
+    device.poll(wgpu::Maintain::Wait);
+    
+    let data = staging_buffer.slice(..).get_mapped_range();
+    let result = bytemuck::cast_slice::<u8, T>(&data).to_vec();
+    drop(data);
+    staging_buffer.unmap();
+    
+    result
+}
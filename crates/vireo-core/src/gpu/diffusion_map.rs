@@ -0,0 +1,58 @@
+use wgpu::{Device, Queue, Texture, TextureView};
+use crate::SimulationConfig;
+use crate::gpu::layouts::Layouts;
+use crate::sim::DiffusionTensorField;
+
+/// Diffusion tensor field on the GPU, read by the RD pass at binding 8
+///
+/// The tensor is static, so the texture is filled once from `field`. Without
+/// `diffusion.enabled` the RD uniform turns the tensor off and the texture is
+/// a single unused texel.
+pub struct DiffusionMap {
+    pub field: DiffusionTensorField,
+    _texture: Texture,
+    view: TextureView,
+}
+
+impl DiffusionMap {
+    pub fn new(device: &Device, queue: &Queue, config: &SimulationConfig) -> Self {
+        let size = if config.diffusion.enabled { config.world.size } else { [1, 1] };
+        let field = DiffusionTensorField::new(&config.diffusion, size);
+        let [width, height] = size;
+        let extent = wgpu::Extent3d { width, height, depth_or_array_layers: 1 };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("diffusion_tensor"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Layouts::DIFFUSION_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytemuck::cast_slice(field.tensors()),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 16), // 4 × f32 per cell
+                rows_per_image: Some(height),
+            },
+            extent,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self { field, _texture: texture, view }
+    }
+
+    /// Tensor texture view for the RD bind groups
+    pub fn view(&self) -> &TextureView {
+        &self.view
+    }
+}
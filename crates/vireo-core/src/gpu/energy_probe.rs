@@ -0,0 +1,75 @@
+use wgpu::{Buffer, Device, Queue, util::DeviceExt};
+use crate::gpu::read_buffer_from;
+use crate::sim::EnergyRecord;
+
+/// Energy log of one selected agent slot
+///
+/// A 16-byte header holding the selected slot (`u32::MAX` for none) followed
+/// by a ring of `EnergyProbe::STEPS` records. The agent pass compares every
+/// slot with the header and only the selected one writes, at `step % STEPS`,
+/// so the log costs one uniform-like load per agent while nothing is selected.
+pub struct EnergyProbe {
+    buffer: Buffer,
+    selected: Option<u32>,
+}
+
+impl EnergyProbe {
+    /// Steps of history kept for the selected agent
+    pub const STEPS: u32 = 256;
+    /// Header value meaning no agent is selected
+    pub const NONE: u32 = u32::MAX;
+    const HEADER: u64 = 16;
+
+    pub fn new(device: &Device) -> Self {
+        // Zeroed memory would select slot 0, so the buffer starts with nothing selected
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("energy_probe"),
+            contents: &Self::contents(None),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+        });
+        Self { buffer, selected: None }
+    }
+
+    /// Storage buffer bound to the agent pass (@binding(12))
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Slot being logged
+    pub fn selected(&self) -> Option<u32> {
+        self.selected
+    }
+
+    /// Log `slot` from the next step on (or stop logging), dropping the history
+    pub fn select(&mut self, queue: &Queue, slot: Option<u32>) {
+        self.selected = slot;
+        queue.write_buffer(&self.buffer, 0, &Self::contents(slot));
+    }
+
+    /// Keep logging the same agent after its slot moved, history kept
+    pub fn follow(&mut self, queue: &Queue, slot: Option<u32>) {
+        self.selected = slot;
+        queue.write_buffer(&self.buffer, 0, &Self::header(slot));
+    }
+
+    /// Download the logged steps, oldest first
+    pub fn read(&self, device: &Device, queue: &Queue) -> Vec<EnergyRecord> {
+        let words: Vec<u32> = read_buffer_from(device, queue, &self.buffer, self.buffer.size());
+        let mut records: Vec<EnergyRecord> = bytemuck::cast_slice(&words[Self::HEADER as usize / 4..]).to_vec();
+        records.retain(|r| !r.is_empty());
+        records.sort_by_key(|r| r.step);
+        records
+    }
+
+    /// Header for `slot` and an empty log
+    fn contents(slot: Option<u32>) -> Vec<u8> {
+        let empty = EnergyRecord { step: EnergyRecord::EMPTY, uptake: 0.0, metabolism: 0.0, movement: 0.0 };
+        let mut bytes = Self::header(slot).to_vec();
+        bytes.extend_from_slice(bytemuck::cast_slice(&vec![empty; Self::STEPS as usize]));
+        bytes
+    }
+
+    fn header(slot: Option<u32>) -> [u8; Self::HEADER as usize] {
+        bytemuck::cast([slot.unwrap_or(Self::NONE), 0, 0, 0])
+    }
+}
@@ -0,0 +1,48 @@
+use wgpu::{Buffer, Device, Queue};
+use crate::gpu::read_buffer_from;
+use crate::sim::EventMaps;
+
+/// Per-cell counts of births and deaths (`output.event_window`)
+///
+/// Three planes of one `u32` per cell, row-major like the occupancy grid:
+/// births, starvation deaths (agent pass) and random deaths (demography
+/// pass). Both passes add with atomics at the cell the event happened in,
+/// so the counts do not depend on thread order. Without a window the buffer
+/// holds a single dummy cell and the shaders skip counting.
+pub struct EventCounters {
+    buffer: Buffer,
+    size: [u32; 2],
+}
+
+impl EventCounters {
+    pub fn new(device: &Device, size: [u32; 2], enabled: bool) -> Self {
+        let cells = if enabled { size[0] as u64 * size[1] as u64 } else { 1 };
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("event_counters"),
+            size: cells * EventMaps::PLANES as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self { buffer, size }
+    }
+
+    /// Storage buffer bound to the agent (@binding(11)) and demography (@binding(4)) passes
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Download the counts since the last `clear`
+    pub fn read(&self, device: &Device, queue: &Queue) -> EventMaps {
+        EventMaps::from_planes(self.size, read_buffer_from(device, queue, &self.buffer, self.buffer.size()))
+    }
+
+    /// Replace the counts (with an earlier `read`, when resuming from a checkpoint)
+    pub fn write(&self, queue: &Queue, maps: &EventMaps) {
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(&maps.planes()));
+    }
+
+    /// Start a new window
+    pub fn clear(&self, queue: &Queue) {
+        queue.write_buffer(&self.buffer, 0, &vec![0; self.buffer.size() as usize]);
+    }
+}
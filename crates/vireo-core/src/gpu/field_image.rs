@@ -0,0 +1,163 @@
+use wgpu::{BindGroup, Buffer, Device, Queue, Texture, TextureView};
+use vireo_params::FieldImageParams;
+use crate::gpu::{ComputePipelines, read_buffer_from};
+use crate::gpu::layouts::Layouts;
+
+/// Workgroup size (per axis) of the field image shader
+const CELL_GROUP: u32 = 8;
+
+/// What `FieldImage::render` draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageKind {
+    /// R scaled to its min/max in red, W in green, soil damage in blue
+    Field,
+    /// Occupancy counts of the last agent pass in grey, scaled to the busiest cell
+    Occupancy,
+}
+
+/// GPU pass colour-mapping the field or the occupancy grid into an RGBA8 image
+///
+/// Replaces the CPU min/max scan and per-pixel conversion of snapshot PNGs:
+/// the range is reduced and the colour map applied on the GPU, and only the
+/// finished 4-byte texels are read back.
+pub struct FieldImage {
+    params_buffer: Buffer,
+    bounds: Buffer,
+    texture: Texture,
+    bind_group_a: BindGroup,
+    bind_group_b: BindGroup,
+    size: [u32; 2],
+}
+
+impl FieldImage {
+    /// `field_a`/`field_b` are the sampled views of the two ping-pong textures
+    pub fn new(device: &Device, layouts: &Layouts, field_a: &TextureView, field_b: &TextureView, occupancy: &Buffer, size: [u32; 2]) -> Self {
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("field_image_params"),
+            size: std::mem::size_of::<FieldImageParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bounds = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("field_image_bounds"),
+            size: 16,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("field_image"),
+            size: wgpu::Extent3d { width: size[0], height: size[1], depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Layouts::IMAGE_FORMAT,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let image_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let create_bind_group = |label, field_view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &layouts.field_image,
+                entries: &[
+                    // @binding(0) field texture (sampled)
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(field_view),
+                    },
+                    // @binding(1) occupancy buffer
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: occupancy.as_entire_binding(),
+                    },
+                    // @binding(2) FieldImageParams uniform
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    // @binding(3) value range
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: bounds.as_entire_binding(),
+                    },
+                    // @binding(4) RGBA8 image
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(&image_view),
+                    },
+                ],
+            })
+        };
+        let bind_group_a = create_bind_group("field_image_a_bg", field_a);
+        let bind_group_b = create_bind_group("field_image_b_bg", field_b);
+
+        Self { params_buffer, bounds, texture, bind_group_a, bind_group_b, size }
+    }
+
+    /// Draw `kind` from the current front field (or the occupancy grid) and
+    /// read back tightly packed RGBA8 rows, top row first
+    pub fn render(&self, device: &Device, queue: &Queue, pipelines: &ComputePipelines, front_is_a: bool, kind: ImageKind) -> Vec<u8> {
+        let [width, height] = self.size;
+        let params = FieldImageParams {
+            size: self.size,
+            kind: match kind { ImageKind::Field => 0, ImageKind::Occupancy => 1 },
+            _pad: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+        queue.write_buffer(&self.bounds, 0, bytemuck::cast_slice(&[u32::MAX, 0, 0, 0]));
+        let bind_group = if front_is_a { &self.bind_group_a } else { &self.bind_group_b };
+
+        // Copies out of a texture need 256-byte aligned rows
+        let row_bytes = width * 4;
+        let padded_row_bytes = row_bytes.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("field_image_rows"),
+            size: padded_row_bytes as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("field_image"),
+        });
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("field image pass"),
+                timestamp_writes: None,
+            });
+            cpass.set_bind_group(0, bind_group, &[]);
+            let groups = [width.div_ceil(CELL_GROUP), height.div_ceil(CELL_GROUP)];
+            cpass.set_pipeline(pipelines.field_image_bounds_pipeline.get(device));
+            cpass.dispatch_workgroups(groups[0], groups[1], 1);
+            cpass.set_pipeline(pipelines.field_image_colormap_pipeline.get(device));
+            cpass.dispatch_workgroups(groups[0], groups[1], 1);
+        }
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &padded,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let rows: Vec<u8> = read_buffer_from(device, queue, &padded, padded.size());
+        rows.chunks_exact(padded_row_bytes as usize)
+            .flat_map(|row| &row[..row_bytes as usize])
+            .copied()
+            .collect()
+    }
+}
@@ -0,0 +1,40 @@
+use wgpu::{Buffer, Device, Queue, util::DeviceExt};
+use crate::gpu::read_buffer_from;
+
+/// Per-slot resource intake and distance moved, accumulated by the agent pass
+///
+/// Each agent thread adds the energy it drew from the resource that step
+/// (eta_R·R·dt) and the length of its move to its own slot, so no atomics are
+/// needed. The totals run from spawn and stay with the slot when a birth
+/// reuses it, so sums over slots are population totals (see
+/// `AgentManager::update_stats`).
+pub struct ForagingTally {
+    buffer: Buffer,
+}
+
+impl ForagingTally {
+    /// A tally starting from `totals` (consumed, distance) per slot
+    pub fn new(device: &Device, totals: &[[f32; 2]]) -> Self {
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("foraging_tally"),
+            contents: bytemuck::cast_slice(totals),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+        });
+        Self { buffer }
+    }
+
+    /// Storage buffer bound to the agent pass (@binding(10))
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Download the (consumed, distance) totals of every slot
+    pub fn read(&self, device: &Device, queue: &Queue) -> Vec<[f32; 2]> {
+        read_buffer_from(device, queue, &self.buffer, self.buffer.size())
+    }
+
+    /// Replace the totals, e.g. zeroed after a restore
+    pub fn write(&self, queue: &Queue, totals: &[[f32; 2]]) {
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(totals));
+    }
+}
@@ -1,4 +1,5 @@
-use wgpu::{Device, BindGroupLayout};
+use wgpu::{Device, BindGroupLayout, TextureFormat, TextureViewDimension};
+use vireo_params::{AgentRange, FieldChannel, FieldImageParams, MipLevelParams, SeparationParams, SteeringParams, TimeUniform};
 
 /// Centralized registry that owns all bind group layouts
 /// 
@@ -15,28 +16,205 @@ pub struct Layouts {
     /// Clear occupancy compute shader layout
     pub clear_occupancy: BindGroupLayout,
     
+    /// Occupancy total compute shader layout
+    pub accumulate_occupancy: BindGroupLayout,
+    
+    /// Bloom injection compute shader layout
+    pub bloom: BindGroupLayout,
+    
+    /// Movement metrics compute shader layout
+    pub movement: BindGroupLayout,
+    
+    /// Utility map compute shader layout
+    pub utility: BindGroupLayout,
+    
+    /// Resource mip chain compute shader layout
+    pub resource_mip: BindGroupLayout,
+    
+    /// Cell gather compute shader layout
+    pub cell_gather: BindGroupLayout,
+    
+    /// Field image export compute shader layout
+    pub field_image: BindGroupLayout,
+    
+    /// Region cull compute shader layout
+    pub cull: BindGroupLayout,
+    
+    /// Radix sort compute shader layout
+    pub radix_sort: BindGroupLayout,
+    
+    /// Demography compute shader layout
+    pub demography: BindGroupLayout,
+    
+    /// Neighbour grid deposit compute shader layout
+    pub neighbour: BindGroupLayout,
+    
+    /// Separation (spatial hash and push) compute shader layout
+    pub separation: BindGroupLayout,
+    
+    /// Obstacle brush compute shader layout
+    pub obstacle_brush: BindGroupLayout,
+    
     /// Field render shader layout (sampler + sampled field)
     pub field_render: BindGroupLayout,
     
-    /// Particle render shader layout (uniform + storage buffer)
+    /// Particle render shader layout (agent storage buffer, visible list)
     pub particle_render: BindGroupLayout,
+    
+    /// Agent view compute shader layout (view culling and density deposit)
+    pub agent_view: BindGroupLayout,
+    
+    /// Agent density render layout (per-cell counts + view params uniform)
+    pub agent_density: BindGroupLayout,
+    
+    /// Camera uniform shared by the world-space render passes (group 1)
+    pub camera: BindGroupLayout,
+    
+    /// Minimap overlay layout (sampled field + sampler + camera uniform)
+    pub minimap: BindGroupLayout,
+    
+    /// Contour overlay layout (sampled field + sampler + level spacing uniform)
+    pub contours: BindGroupLayout,
+    
+    /// Pass timeline chart layout (uniform + stacked frame times)
+    pub timeline: BindGroupLayout,
 }
 
 impl Layouts {
+    /// Texel format of every field texture layer
+    pub const FIELD_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+    
+    /// Plain 2D while every channel fits in one RGBA layer, 2D array beyond
+    /// that; shaders go through `shaders::field_prelude` so they need not care
+    pub const FIELD_VIEW_DIMENSION: TextureViewDimension = if FieldChannel::LAYERS == 1 {
+        TextureViewDimension::D2
+    } else {
+        TextureViewDimension::D2Array
+    };
+    
+    /// Array layers per field texture (four channels per layer)
+    pub const FIELD_LAYERS: u32 = FieldChannel::LAYERS;
+    
+    /// Texel format of the obstacle signed distance field (one plain 2D layer)
+    pub const OBSTACLE_FORMAT: TextureFormat = TextureFormat::R32Float;
+    
+    /// Texel format of the terrain elevation field (one plain 2D layer)
+    pub const ELEVATION_FORMAT: TextureFormat = TextureFormat::R32Float;
+    
+    /// Texel format of the diffusion tensor field: (Dxx, Dyy, Dxy, unused)
+    pub const DIFFUSION_FORMAT: TextureFormat = TextureFormat::Rgba32Float;
+    
+    /// Texel format of exported field and occupancy images
+    pub const IMAGE_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+    
+    /// Per-species `AgentRange` uniform, selected by a dynamic offset for each dispatch
+    fn agent_range_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: true,
+                min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<AgentRange>() as u64),
+            },
+            count: None,
+        }
+    }
+    
+    /// Read-only static map entry (obstacle distance, elevation, diffusion tensor) shared by the RD, agent and brush layouts
+    fn static_map_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        }
+    }
+    
+    /// `TimeUniform` clock shared by the RD and agent layouts
+    fn time_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<TimeUniform>() as u64),
+            },
+            count: None,
+        }
+    }
+    
+    /// `SteeringParams` uniform shared by the agent and neighbour grid layouts
+    fn steering_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<SteeringParams>() as u64),
+            },
+            count: None,
+        }
+    }
+    
     /// Create all bind group layouts once
     pub fn new(device: &Device) -> Self {
         let rd = Self::create_rd_layout(device);
         let agent = Self::create_agent_layout(device);
         let clear_occupancy = Self::create_clear_occupancy_layout(device);
+        let accumulate_occupancy = Self::create_accumulate_occupancy_layout(device);
+        let bloom = Self::create_bloom_layout(device);
+        let movement = Self::create_movement_layout(device);
+        let utility = Self::create_utility_layout(device);
+        let resource_mip = Self::create_resource_mip_layout(device);
+        let cell_gather = Self::create_cell_gather_layout(device);
+        let field_image = Self::create_field_image_layout(device);
+        let cull = Self::create_cull_layout(device);
+        let radix_sort = Self::create_radix_sort_layout(device);
+        let demography = Self::create_demography_layout(device);
+        let neighbour = Self::create_neighbour_layout(device);
+        let separation = Self::create_separation_layout(device);
+        let obstacle_brush = Self::create_obstacle_brush_layout(device);
         let field_render = Self::create_field_render_layout(device);
         let particle_render = Self::create_particle_render_layout(device);
+        let agent_view = Self::create_agent_view_layout(device);
+        let agent_density = Self::create_agent_density_layout(device);
+        let camera = Self::create_camera_layout(device);
+        let minimap = Self::create_minimap_layout(device);
+        let contours = Self::create_contours_layout(device);
+        let timeline = Self::create_timeline_layout(device);
         
         Self {
             rd,
             agent,
             clear_occupancy,
+            accumulate_occupancy,
+            bloom,
+            movement,
+            utility,
+            resource_mip,
+            cell_gather,
+            field_image,
+            cull,
+            radix_sort,
+            demography,
+            neighbour,
+            separation,
+            obstacle_brush,
             field_render,
             particle_render,
+            agent_view,
+            agent_density,
+            camera,
+            minimap,
+            contours,
+            timeline,
         }
     }
     
@@ -45,25 +223,25 @@ impl Layouts {
         device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("rd_bgl"),
             entries: &[
-                // @binding(0) src sampled texture (R, W channels)
+                // @binding(0) src sampled field texture (R, W channels)
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
                         multisampled: false,
                     },
                     count: None,
                 },
-                // @binding(1) dst storage texture
+                // @binding(1) dst storage field texture
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::StorageTexture {
                         access: wgpu::StorageTextureAccess::WriteOnly,
-                        format: wgpu::TextureFormat::Rgba16Float,
-                        view_dimension: wgpu::TextureViewDimension::D2,
+                        format: Self::FIELD_FORMAT,
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
                     },
                     count: None,
                 },
@@ -89,6 +267,25 @@ impl Layouts {
                     },
                     count: None,
                 },
+                // @binding(4) bloom sigma_R boost buffer (storage read-only)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(5) obstacle signed distance field
+                Self::static_map_entry(5),
+                // @binding(6) terrain elevation
+                Self::static_map_entry(6),
+                // @binding(7) simulation clock (day/night light)
+                Self::time_entry(7),
+                // @binding(8) diffusion tensor
+                Self::static_map_entry(8),
             ],
         })
     }
@@ -115,7 +312,7 @@ impl Layouts {
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
                         multisampled: false,
                     },
                     count: None,
@@ -142,30 +339,195 @@ impl Layouts {
                     },
                     count: None,
                 },
+                // @binding(4) obstacle signed distance field
+                Self::static_map_entry(4),
+                // @binding(5) species slot range (dynamic offset)
+                Self::agent_range_entry(5),
+                // @binding(6) terrain elevation
+                Self::static_map_entry(6),
+                // @binding(7) simulation clock (day/night light)
+                Self::time_entry(7),
+                // @binding(8) per-species steering weights
+                Self::steering_entry(8),
+                // @binding(9) neighbour grid filled before the pass (storage read-only)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 9,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(10) per-slot foraging tally (read_write)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 10,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(11) per-cell event counters (atomic)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 11,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(12) energy log of the selected agent (read_write)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 12,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         })
     }
     
-    /// Create the clear occupancy compute shader layout
-    fn create_clear_occupancy_layout(device: &Device) -> BindGroupLayout {
+    /// Create the neighbour grid deposit compute shader layout
+    fn create_neighbour_layout(device: &Device) -> BindGroupLayout {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
         device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("clear_occupancy_bgl"),
+            label: Some("neighbour_bgl"),
             entries: &[
-                // @binding(0) occupancy buffer (storage write-only)
+                // @binding(0) agents storage buffer (read-only)
+                storage(0, true),
+                // @binding(1) fixed-point neighbour grid
+                storage(1, false),
+                // @binding(2) SteeringParams uniform (world size)
+                Self::steering_entry(2),
+            ],
+        })
+    }
+    
+    /// Create the separation compute shader layout
+    fn create_separation_layout(device: &Device) -> BindGroupLayout {
+        let storage = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("separation_bgl"),
+            entries: &[
+                // @binding(0) agents storage buffer
+                storage(0),
+                // @binding(1) SeparationParams uniform
                 wgpu::BindGroupLayoutEntry {
-                    binding: 0,
+                    binding: 1,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<SeparationParams>() as u64),
+                    },
+                    count: None,
+                },
+                // @binding(2) hash keys (cell per slot, sorted in place)
+                storage(2),
+                // @binding(3) slots carried along with their keys
+                storage(3),
+                // @binding(4) [first, end) of each cell in the sorted pairs
+                storage(4),
+                // @binding(5) (pos, kind, alive) per slot before the push
+                storage(5),
+            ],
+        })
+    }
+    
+    /// Create the obstacle brush compute shader layout
+    fn create_obstacle_brush_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("obstacle_brush_bgl"),
+            entries: &[
+                // @binding(0) copy of the distance field before the stroke
+                Self::static_map_entry(0),
+                // @binding(1) obstacle distance field (storage write)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: Self::OBSTACLE_FORMAT,
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                // @binding(2) ObstacleBrushParams uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
                         min_binding_size: None,
                     },
                     count: None,
                 },
-                // @binding(1) dimensions uniform buffer
+            ],
+        })
+    }
+    
+    /// Create the movement metrics compute shader layout
+    fn create_movement_layout(device: &Device) -> BindGroupLayout {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("movement_bgl"),
+            entries: &[
+                // @binding(0) agents storage buffer (read-only)
+                storage(0, true),
+                // @binding(1) field texture (sampled)
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
                     visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // @binding(2) MovementParams uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -173,57 +535,670 @@ impl Layouts {
                     },
                     count: None,
                 },
+                // @binding(3) fixed-point velocity grid
+                storage(3, false),
+                // @binding(4) per-workgroup partial sums
+                storage(4, false),
             ],
         })
     }
     
-    /// Create the field render shader layout
-    fn create_field_render_layout(device: &Device) -> BindGroupLayout {
+    /// Create the utility map compute shader layout
+    fn create_utility_layout(device: &Device) -> BindGroupLayout {
         device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("field_render_bgl"),
+            label: Some("utility_bgl"),
             entries: &[
                 // @binding(0) field texture (sampled)
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
                         multisampled: false,
                     },
                     count: None,
                 },
-                // @binding(1) sampler
+                // @binding(1) UtilityParams uniform
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(2) per-cell utility output
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
                     count: None,
                 },
             ],
         })
     }
     
-    /// Create the particle render shader layout
-    fn create_particle_render_layout(device: &Device) -> BindGroupLayout {
+    /// Create the resource mip chain compute shader layout
+    fn create_resource_mip_layout(device: &Device) -> BindGroupLayout {
         device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("particle_render_bgl"),
+            label: Some("resource_mip_bgl"),
             entries: &[
-                // @binding(0) SimParams uniform buffer
+                // @binding(0) field texture (sampled)
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // @binding(1) every mip level, back to back
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
                         has_dynamic_offset: false,
                         min_binding_size: None,
                     },
                     count: None,
                 },
-                // @binding(1) particles storage buffer (read-only)
+                // @binding(2) MipLevelParams uniform, dynamic offset per level
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<MipLevelParams>() as u64),
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the cell gather compute shader layout
+    fn create_cell_gather_layout(device: &Device) -> BindGroupLayout {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("cell_gather_bgl"),
+            entries: &[
+                // @binding(0) field texture (sampled)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // @binding(1) CellGatherParams uniform
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(2) cells to read (row-major indices)
+                storage(2, true),
+                // @binding(3) one sample per listed cell
+                storage(3, false),
+            ],
+        })
+    }
+    
+    /// Create the field image export compute shader layout
+    fn create_field_image_layout(device: &Device) -> BindGroupLayout {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("field_image_bgl"),
+            entries: &[
+                // @binding(0) field texture (sampled)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // @binding(1) herbivore occupancy buffer (storage read-only)
+                storage(1, true),
+                // @binding(2) FieldImageParams uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<FieldImageParams>() as u64),
+                    },
+                    count: None,
+                },
+                // @binding(3) value range the image is scaled to
+                storage(3, false),
+                // @binding(4) RGBA8 image (storage write-only)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: Self::IMAGE_FORMAT,
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the region cull compute shader layout
+    fn create_cull_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("cull_bgl"),
+            entries: &[
+                // @binding(0) agents storage buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(1) CullParams uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the demography compute shader layout
+    fn create_demography_layout(device: &Device) -> BindGroupLayout {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("demography_bgl"),
+            entries: &[
+                // @binding(0) agents storage buffer
+                storage(0, false),
+                // @binding(1) agents before this pass (storage read-only)
+                storage(1, true),
+                // @binding(2) DemographyParams uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(3) species slot range (dynamic offset)
+                Self::agent_range_entry(3),
+                // @binding(4) per-cell event counters (atomic)
+                storage(4, false),
+                // @binding(5) refuge death multiplier per cell (read-only)
+                storage(5, true),
+            ],
+        })
+    }
+    
+    /// Create the radix sort compute shader layout
+    fn create_radix_sort_layout(device: &Device) -> BindGroupLayout {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("radix_sort_bgl"),
+            entries: &[
+                // @binding(0) input keys (read-only)
+                storage(0, true),
+                // @binding(1) input values (read-only)
+                storage(1, true),
+                // @binding(2) output keys
+                storage(2, false),
+                // @binding(3) output values
+                storage(3, false),
+                // @binding(4) digit histogram / scatter offsets
+                storage(4, false),
+                // @binding(5) RadixSortParams uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the clear occupancy compute shader layout
+    fn create_clear_occupancy_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("clear_occupancy_bgl"),
+            entries: &[
+                // @binding(0) occupancy buffer (storage write-only)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(1) dimensions uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the occupancy total compute shader layout
+    fn create_accumulate_occupancy_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("accumulate_occupancy_bgl"),
+            entries: &[
+                // @binding(0) occupancy buffer (storage read-only)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(1) occupancy totals (storage read-write)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(2) dimensions uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the bloom injection compute shader layout
+    fn create_bloom_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bloom_bgl"),
+            entries: &[
+                // @binding(0) BloomParams uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(1) sigma_R boost buffer (storage read_write)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the field render shader layout
+    fn create_field_render_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("field_render_bgl"),
+            entries: &[
+                // @binding(0) field texture (sampled)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // @binding(1) sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the particle render shader layout
+    fn create_particle_render_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("particle_render_bgl"),
+            entries: &[
+                // @binding(0) particles storage buffer (read-only)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(1) AgentColorParams uniform (species or energy gradient)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(2) visible agent slots, one per instance (read-only)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the agent view compute shader layout, shared by view culling and the density deposit
+    fn create_agent_view_layout(device: &Device) -> BindGroupLayout {
+        let uniform = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("agent_view_bgl"),
+            entries: &[
+                // @binding(0) particles storage buffer (read-only)
+                storage(0, true),
+                // @binding(1) AgentViewParams uniform (slot count, dead agents shown, world size)
+                uniform(1),
+                // @binding(2) CameraUniform
+                uniform(2),
+                // @binding(3) visible slot list or per-cell species counts (read-write)
+                storage(3, false),
+                // @binding(4) indirect draw arguments (read-write)
+                storage(4, false),
+            ],
+        })
+    }
+    
+    /// Create the agent density render layout
+    fn create_agent_density_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("agent_density_bgl"),
+            entries: &[
+                // @binding(0) per-cell species counts (read-only storage)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(1) AgentViewParams uniform (world size, saturation)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the camera layout bound at group 1 by the field background and particle passes
+    fn create_camera_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("camera_bgl"),
+            entries: &[
+                // @binding(0) CameraUniform (view matrix, world size, viewport)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the minimap overlay layout
+    fn create_minimap_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("minimap_bgl"),
+            entries: &[
+                // @binding(0) field texture (sampled)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // @binding(1) sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // @binding(2) MinimapParams uniform (camera rectangle)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the contour overlay layout
+    fn create_contours_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("contours_bgl"),
+            entries: &[
+                // @binding(0) field texture (sampled)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: Self::FIELD_VIEW_DIMENSION,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // @binding(1) sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // @binding(2) ContourParams uniform (level spacing, line width)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+    
+    /// Create the pass timeline chart layout
+    fn create_timeline_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("timeline_bgl"),
+            entries: &[
+                // @binding(0) TimelineParams uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // @binding(1) stacked per-pass frame times (read-only storage)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage { read_only: true },
                         has_dynamic_offset: false,
@@ -2,7 +2,55 @@ pub mod device;
 pub mod pipelines;
 pub mod textures;
 pub mod layouts;
+pub mod simulation;
+pub mod bloom_inject;
+pub mod occupancy;
+pub mod movement;
+pub mod utility;
+pub mod resource_mips;
+pub mod cell_gather;
+pub mod field_image;
+pub mod cull_region;
+pub mod radix_sort;
+pub mod budget;
+pub mod demography;
+pub mod profiler;
+pub mod obstacle_map;
+pub mod terrain_map;
+pub mod diffusion_map;
+pub mod agent_ranges;
+pub mod watchdog;
+pub mod rewind;
+pub mod steering;
+pub mod separation;
+pub mod foraging;
+pub mod event_counters;
+pub mod energy_probe;
 
-pub use device::GpuDevice;
-pub use pipelines::ComputePipelines;
-pub use textures::FieldPingPong;
+pub use device::{GpuDevice, read_buffer_from};
+pub use pipelines::{ComputePipelines, LazyPipeline};
+pub use textures::{FieldPingPong, RdBindInputs, TexelRows};
+pub use simulation::{AgentBindInputs, GrowthEvent, Simulation, StepPass};
+pub use bloom_inject::BloomInjector;
+pub use occupancy::{OccupancyClear, OccupancyTotal};
+pub use movement::MovementMetrics;
+pub use utility::UtilityMap;
+pub use resource_mips::ResourceMips;
+pub use cell_gather::CellGather;
+pub use field_image::{FieldImage, ImageKind};
+pub use cull_region::AgentCuller;
+pub use radix_sort::RadixSort;
+pub use demography::DemographyPass;
+pub use profiler::{GpuProfiler, PassTimeline};
+pub use obstacle_map::ObstacleMap;
+pub use terrain_map::TerrainMap;
+pub use diffusion_map::DiffusionMap;
+pub use agent_ranges::AgentRanges;
+pub use watchdog::DeviceWatchdog;
+pub use rewind::{RewindPoint, RewindRing};
+pub use steering::Steering;
+pub use separation::Separation;
+pub use foraging::ForagingTally;
+pub use event_counters::EventCounters;
+pub use energy_probe::EnergyProbe;
+pub use budget::{BudgetError, Downscale, MemoryEstimate, check_budget, fit_to_budget};
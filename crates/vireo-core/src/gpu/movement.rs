@@ -0,0 +1,160 @@
+use wgpu::{BindGroup, Buffer, Device, Queue, TextureView, util::DeviceExt};
+use vireo_params::MovementParams;
+use crate::gpu::{ComputePipelines, read_buffer_from};
+use crate::gpu::layouts::Layouts;
+use crate::sim::MovementStats;
+
+/// Workgroup sizes of the `deposit` and `divergence` entry points
+const AGENT_GROUP: u32 = 64;
+const CELL_GROUP: u32 = 8;
+
+/// GPU pass measuring how agents move relative to the resource field
+///
+/// Deposits agent velocities into a fixed-point cell grid, then reduces
+/// velocity/∇R alignment, up-gradient flux and the divergence of the mean
+/// cell velocity into per-workgroup partial sums that are added on the host.
+pub struct MovementMetrics {
+    _params_buffer: Buffer,
+    _vel_grid: Buffer,
+    partials: Buffer,
+    bind_group_a: BindGroup,
+    bind_group_b: BindGroup,
+    agent_groups: u32,
+    cell_groups: [u32; 2],
+    clear_groups: [u32; 2],
+}
+
+impl MovementMetrics {
+    /// `field_a`/`field_b` are the sampled views of the two ping-pong textures
+    pub fn new(
+        device: &Device,
+        layouts: &Layouts,
+        agents_buffer: &Buffer,
+        agent_count: u32,
+        field_a: &TextureView,
+        field_b: &TextureView,
+        size: [u32; 2],
+    ) -> Self {
+        let agent_groups = agent_count.div_ceil(AGENT_GROUP).max(1);
+        let cell_groups = [size[0].div_ceil(CELL_GROUP), size[1].div_ceil(CELL_GROUP)];
+        let grid_words = size[0] * size[1] * 3; // (vx, vy, n) per cell
+        // 2D so the clear stays within the 65535 workgroup limit on large worlds
+        let clear_groups = [(size[0] * 3).div_ceil(AGENT_GROUP), size[1]];
+
+        let params = MovementParams { size, agent_groups, _pad: 0 };
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("movement_params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let vel_grid = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("movement_vel_grid"),
+            size: grid_words as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let partial_count = agent_groups + cell_groups[0] * cell_groups[1];
+        let partials = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("movement_partials"),
+            size: partial_count as u64 * 16, // vec4<f32> per workgroup
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let create_bind_group = |label, field_view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &layouts.movement,
+                entries: &[
+                    // @binding(0) agents storage buffer
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: agents_buffer.as_entire_binding(),
+                    },
+                    // @binding(1) field texture (sampled)
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(field_view),
+                    },
+                    // @binding(2) MovementParams uniform
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    // @binding(3) velocity grid
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: vel_grid.as_entire_binding(),
+                    },
+                    // @binding(4) partial sums
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: partials.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+        let bind_group_a = create_bind_group("movement_a_bg", field_a);
+        let bind_group_b = create_bind_group("movement_b_bg", field_b);
+
+        Self {
+            _params_buffer: params_buffer,
+            _vel_grid: vel_grid,
+            partials,
+            bind_group_a,
+            bind_group_b,
+            agent_groups,
+            cell_groups,
+            clear_groups,
+        }
+    }
+
+    /// Run the passes against the current front field and read back the totals
+    pub fn measure(&self, device: &Device, queue: &Queue, pipelines: &ComputePipelines, front_is_a: bool) -> MovementStats {
+        let bind_group = if front_is_a { &self.bind_group_a } else { &self.bind_group_b };
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("movement_metrics"),
+        });
+
+        // Separate passes so each stage sees the previous one's writes
+        let stages = [
+            (pipelines.movement_clear_pipeline.get(device), self.clear_groups),
+            (pipelines.movement_deposit_pipeline.get(device), [self.agent_groups, 1]),
+            (pipelines.movement_divergence_pipeline.get(device), self.cell_groups),
+        ];
+        for (pipeline, [x, y]) in stages {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("movement metrics pass"),
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(pipeline);
+            cpass.set_bind_group(0, bind_group, &[]);
+            cpass.dispatch_workgroups(x, y, 1);
+        }
+        queue.submit(Some(encoder.finish()));
+
+        let partials: Vec<[f32; 4]> = read_buffer_from(device, queue, &self.partials, self.partials.size());
+        let (agents, cells) = partials.split_at(self.agent_groups as usize);
+        let sum = |parts: &[[f32; 4]]| {
+            parts.iter().fold([0.0f64; 4], |mut acc, p| {
+                for (a, v) in acc.iter_mut().zip(p) {
+                    *a += *v as f64;
+                }
+                acc
+            })
+        };
+        let [alignment, flux, directed, alive] = sum(agents);
+        let [div, abs_div, occupied, _] = sum(cells);
+
+        let ratio = |num: f64, den: f64| if den > 0.0 { (num / den) as f32 } else { 0.0 };
+        MovementStats {
+            mean_divergence: ratio(div, occupied),
+            mean_abs_divergence: ratio(abs_div, occupied),
+            net_flux: ratio(flux, alive),
+            mean_alignment: ratio(alignment, directed),
+        }
+    }
+}
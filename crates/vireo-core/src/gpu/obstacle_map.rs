@@ -0,0 +1,148 @@
+use wgpu::{BindGroup, Buffer, Device, Queue, Texture, TextureView, util::DeviceExt};
+use crate::SimulationConfig;
+use crate::gpu::ComputePipelines;
+use crate::gpu::layouts::Layouts;
+use crate::sim::{ObstacleBrush, ObstacleField};
+
+/// Obstacle signed distance field on the GPU, read by the agent pass at
+/// binding 4 and the RD pass at binding 5
+///
+/// The texture is filled once from `field` and only changes when `paint`
+/// runs the brush over it. Without obstacles every texel holds
+/// `ObstacleField::FAR` and both passes skip it.
+pub struct ObstacleMap {
+    pub field: ObstacleField,
+    texture: Texture,
+    view: TextureView,
+    extent: wgpu::Extent3d,
+
+    // Brush pass, built on the first stroke
+    brush: Option<BrushPass>,
+}
+
+/// Copy of the field the brush reads while it writes the texture
+struct BrushPass {
+    previous: Texture,
+    params_buffer: Buffer,
+    bind_group: BindGroup,
+}
+
+impl ObstacleMap {
+    pub fn new(device: &Device, queue: &Queue, config: &SimulationConfig) -> Self {
+        let field = ObstacleField::new(&config.obstacles, config.world.size);
+        let [width, height] = config.world.size;
+        let extent = wgpu::Extent3d { width, height, depth_or_array_layers: 1 };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("obstacle_sdf"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Layouts::OBSTACLE_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::STORAGE_BINDING
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytemuck::cast_slice(field.distances()),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4), // f32 per cell
+                rows_per_image: Some(height),
+            },
+            extent,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self { field, texture, view, extent, brush: None }
+    }
+
+    /// Distance texture view for the agent and RD bind groups
+    pub fn view(&self) -> &TextureView {
+        &self.view
+    }
+
+    /// Draw or erase a disk of wall, on the GPU and in `field`
+    ///
+    /// The texture is edited in place, so the next RD and agent passes see the
+    /// new walls through their existing bind groups. Painting into a world
+    /// that started without obstacles only has an effect once the caller
+    /// turns obstacles on in the params (see `Simulation::paint_obstacles`).
+    pub fn paint(&mut self, device: &Device, queue: &Queue, layouts: &Layouts, pipelines: &ComputePipelines, brush: ObstacleBrush) {
+        self.field.paint(&brush);
+
+        let (texture, view, extent) = (&self.texture, &self.view, self.extent);
+        let pass = self.brush.get_or_insert_with(|| BrushPass::new(device, layouts, view, extent));
+        queue.write_buffer(&pass.params_buffer, 0, bytemuck::bytes_of(&brush.to_params()));
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("obstacle_brush_encoder"),
+        });
+        encoder.copy_texture_to_texture(texture.as_image_copy(), pass.previous.as_image_copy(), extent);
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("obstacle brush pass"),
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(pipelines.obstacle_brush_pipeline.get(device));
+            cpass.set_bind_group(0, &pass.bind_group, &[]);
+            cpass.dispatch_workgroups(extent.width.div_ceil(8), extent.height.div_ceil(8), 1);
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+}
+
+impl BrushPass {
+    fn new(device: &Device, layouts: &Layouts, view: &TextureView, extent: wgpu::Extent3d) -> Self {
+        let previous = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("obstacle_sdf_previous"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Layouts::OBSTACLE_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let previous_view = previous.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("obstacle_brush_params"),
+            contents: bytemuck::bytes_of(&ObstacleBrush { center: [0.0; 2], radius: 0.0, erase: false }.to_params()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("obstacle_brush_bg"),
+            layout: &layouts.obstacle_brush,
+            entries: &[
+                // @binding(0) copy of the distance field before the stroke
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&previous_view),
+                },
+                // @binding(1) obstacle distance field (storage write)
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+                // @binding(2) ObstacleBrushParams uniform
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: params_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self { previous, params_buffer, bind_group }
+    }
+}
@@ -0,0 +1,136 @@
+use wgpu::{BindGroup, Buffer, CommandEncoder, Device, Queue, util::DeviceExt};
+use crate::gpu::read_buffer_from;
+use crate::gpu::ComputePipelines;
+use crate::gpu::layouts::Layouts;
+
+/// Occupancy clear pass: zeroes the occupancy grid on the GPU
+///
+/// Owns the dimensions uniform and bind group so frontends only encode a
+/// dispatch each step instead of uploading a zeroed grid from the host.
+pub struct OccupancyClear {
+    _dims_buffer: Buffer,
+    bind_group: BindGroup,
+    size: [u32; 2],
+}
+
+impl OccupancyClear {
+    pub fn new(device: &Device, layouts: &Layouts, occupancy_buffer: &Buffer, size: [u32; 2]) -> Self {
+        let dims_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("clear_occupancy_dims"),
+            contents: bytemuck::cast_slice(&size),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("clear_occupancy_bg"),
+            layout: &layouts.clear_occupancy,
+            entries: &[
+                // @binding(0) occupancy buffer
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: occupancy_buffer.as_entire_binding(),
+                },
+                // @binding(1) dimensions uniform
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: dims_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            _dims_buffer: dims_buffer,
+            bind_group,
+            size,
+        }
+    }
+
+    /// Encode the clear pass; must precede the agent pass that deposits into the grid
+    pub fn encode(&self, encoder: &mut CommandEncoder, pipelines: &ComputePipelines) {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("clear occupancy pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&pipelines.clear_occupancy_pipeline);
+        cpass.set_bind_group(0, &self.bind_group, &[]);
+        cpass.dispatch_workgroups(self.size[0].div_ceil(128), self.size[1], 1);
+    }
+}
+
+/// Run-long occupancy totals (`output.occupancy_total`)
+///
+/// Adds every step's occupancy grid into a persistent per-cell sum on the
+/// GPU, so habitat use over the whole run costs one readback at the end.
+/// Counts saturate at `u32::MAX` rather than wrap.
+pub struct OccupancyTotal {
+    _dims_buffer: Buffer,
+    total_buffer: Buffer,
+    bind_group: BindGroup,
+    size: [u32; 2],
+}
+
+impl OccupancyTotal {
+    pub fn new(device: &Device, layouts: &Layouts, occupancy_buffer: &Buffer, size: [u32; 2]) -> Self {
+        let dims_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("occupancy_total_dims"),
+            contents: bytemuck::cast_slice(&size),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let total_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("occupancy_total"),
+            size: size[0] as u64 * size[1] as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("accumulate_occupancy_bg"),
+            layout: &layouts.accumulate_occupancy,
+            entries: &[
+                // @binding(0) this step's occupancy
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: occupancy_buffer.as_entire_binding(),
+                },
+                // @binding(1) running totals
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: total_buffer.as_entire_binding(),
+                },
+                // @binding(2) dimensions uniform
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: dims_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            _dims_buffer: dims_buffer,
+            total_buffer,
+            bind_group,
+            size,
+        }
+    }
+
+    /// Encode the accumulation; must follow every pass that changes occupancy
+    pub fn encode(&self, device: &Device, encoder: &mut CommandEncoder, pipelines: &ComputePipelines) {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("accumulate occupancy pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(pipelines.accumulate_occupancy_pipeline.get(device));
+        cpass.set_bind_group(0, &self.bind_group, &[]);
+        cpass.dispatch_workgroups(self.size[0].div_ceil(128), self.size[1], 1);
+    }
+
+    /// Download the totals, row-major like the occupancy grid
+    pub fn read(&self, device: &Device, queue: &Queue) -> Vec<u32> {
+        read_buffer_from(device, queue, &self.total_buffer, self.total_buffer.size())
+    }
+
+    /// Replace the totals (with an earlier `read`, when resuming from a checkpoint)
+    pub fn write(&self, queue: &Queue, totals: &[u32]) {
+        queue.write_buffer(&self.total_buffer, 0, bytemuck::cast_slice(totals));
+    }
+}
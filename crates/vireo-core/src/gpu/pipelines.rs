@@ -1,32 +1,131 @@
-use wgpu::{Device, ComputePipeline};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use wgpu::{Device, ComputePipeline, PipelineLayout};
 use crate::gpu::layouts::Layouts;
+use crate::shaders;
 
 /// Compute pipelines for the simulation
+///
+/// The four passes every step dispatches are compiled up front. Everything
+/// else (demography, steering, metrics, viewer interactions, ...) is a
+/// `LazyPipeline` compiled the first time it is dispatched, so a short run
+/// only pays for the shaders its config actually uses.
 pub struct ComputePipelines {
     pub rd_pipeline: ComputePipeline,
     pub agent_pipeline: ComputePipeline,
     pub clear_occupancy_pipeline: ComputePipeline,
+    pub bloom_pipeline: ComputePipeline,
+    pub accumulate_occupancy_pipeline: LazyPipeline,
+    pub movement_clear_pipeline: LazyPipeline,
+    pub movement_deposit_pipeline: LazyPipeline,
+    pub movement_divergence_pipeline: LazyPipeline,
+    pub utility_pipeline: LazyPipeline,
+    pub resource_mip_copy_pipeline: LazyPipeline,
+    pub resource_mip_reduce_pipeline: LazyPipeline,
+    pub cell_gather_pipeline: LazyPipeline,
+    pub field_image_bounds_pipeline: LazyPipeline,
+    pub field_image_colormap_pipeline: LazyPipeline,
+    pub cull_pipeline: LazyPipeline,
+    pub radix_histogram_pipeline: LazyPipeline,
+    pub radix_scan_pipeline: LazyPipeline,
+    pub radix_scatter_pipeline: LazyPipeline,
+    pub demography_pipeline: LazyPipeline,
+    pub neighbour_pipeline: LazyPipeline,
+    pub separation_hash_pipeline: LazyPipeline,
+    pub separation_ranges_pipeline: LazyPipeline,
+    pub separation_push_pipeline: LazyPipeline,
+    pub obstacle_brush_pipeline: LazyPipeline,
+
+    compile_times: Vec<(&'static str, Duration)>, // Eager pipelines, in creation order
 }
 
 impl ComputePipelines {
-    /// Create all compute pipelines using centralized layouts
+    /// Compile the per-step pipelines and prepare the lazy ones using centralized layouts
     pub fn new(device: &Device, layouts: &Layouts) -> Self {
-        let rd_pipeline = Self::create_rd_pipeline(device, &layouts.rd);
-        let agent_pipeline = Self::create_agent_pipeline(device, &layouts.agent);
-        let clear_occupancy_pipeline = Self::create_clear_occupancy_pipeline(device, &layouts.clear_occupancy);
-        
+        let mut compile_times = Vec::new();
+        let mut timed = |name, create: &dyn Fn() -> ComputePipeline| {
+            let start = Instant::now();
+            let pipeline = create();
+            compile_times.push((name, start.elapsed()));
+            pipeline
+        };
+        let rd_pipeline = timed("rd", &|| Self::create_rd_pipeline(device, &layouts.rd));
+        let agent_pipeline = timed("agent", &|| Self::create_agent_pipeline(device, &layouts.agent));
+        let clear_occupancy_pipeline = timed("clear_occupancy", &|| Self::create_clear_occupancy_pipeline(device, &layouts.clear_occupancy));
+        let bloom_pipeline = timed("bloom", &|| Self::create_bloom_pipeline(device, &layouts.bloom));
+
+        let lazy = |name, layout, source, entry_point| LazyPipeline::new(device, name, layout, source, entry_point);
+        let movement = |name, entry_point| lazy(name, &layouts.movement, shaders::movement_metrics, entry_point);
+        let resource_mip = |name, entry_point| lazy(name, &layouts.resource_mip, shaders::resource_mip, entry_point);
+        let separation = |name, entry_point| lazy(name, &layouts.separation, shaders::separation, entry_point);
+        let radix = |name, entry_point| lazy(name, &layouts.radix_sort, || shaders::radix_sort().to_string(), entry_point);
+
         Self {
             rd_pipeline,
             agent_pipeline,
             clear_occupancy_pipeline,
+            bloom_pipeline,
+            accumulate_occupancy_pipeline: lazy("accumulate_occupancy", &layouts.accumulate_occupancy,
+                || shaders::accumulate_occupancy().to_string(), "main"),
+            movement_clear_pipeline: movement("movement_clear_grid", "clear_grid"),
+            movement_deposit_pipeline: movement("movement_deposit", "deposit"),
+            movement_divergence_pipeline: movement("movement_divergence", "divergence"),
+            utility_pipeline: lazy("utility", &layouts.utility, shaders::utility_map, "main"),
+            resource_mip_copy_pipeline: resource_mip("resource_mip_copy_field", "copy_field"),
+            resource_mip_reduce_pipeline: resource_mip("resource_mip_reduce", "reduce"),
+            cell_gather_pipeline: lazy("cell_gather", &layouts.cell_gather, shaders::cell_gather, "main"),
+            field_image_bounds_pipeline: lazy("field_image_bounds", &layouts.field_image, shaders::field_image, "find_bounds"),
+            field_image_colormap_pipeline: lazy("field_image_colormap", &layouts.field_image, shaders::field_image, "colormap"),
+            cull_pipeline: lazy("cull", &layouts.cull, shaders::cull_region, "main"),
+            radix_histogram_pipeline: radix("radix_histogram", "histogram"),
+            radix_scan_pipeline: radix("radix_scan", "scan"),
+            radix_scatter_pipeline: radix("radix_scatter", "scatter"),
+            demography_pipeline: lazy("demography", &layouts.demography, shaders::demography, "main"),
+            neighbour_pipeline: lazy("neighbour", &layouts.neighbour, shaders::neighbour_grid, "main"),
+            separation_hash_pipeline: separation("separation_hash", "hash"),
+            separation_ranges_pipeline: separation("separation_ranges", "find_ranges"),
+            separation_push_pipeline: separation("separation_push", "push"),
+            obstacle_brush_pipeline: lazy("obstacle_brush", &layouts.obstacle_brush,
+                || shaders::obstacle_brush().to_string(), "main"),
+            compile_times,
         }
     }
+
+    /// Time spent compiling each pipeline built so far: the per-step ones
+    /// from `new`, then every lazy one that has been dispatched
+    pub fn compile_times(&self) -> Vec<(&'static str, Duration)> {
+        let lazy = [
+            &self.accumulate_occupancy_pipeline,
+            &self.movement_clear_pipeline,
+            &self.movement_deposit_pipeline,
+            &self.movement_divergence_pipeline,
+            &self.utility_pipeline,
+            &self.resource_mip_copy_pipeline,
+            &self.resource_mip_reduce_pipeline,
+            &self.cell_gather_pipeline,
+            &self.field_image_bounds_pipeline,
+            &self.field_image_colormap_pipeline,
+            &self.cull_pipeline,
+            &self.radix_histogram_pipeline,
+            &self.radix_scan_pipeline,
+            &self.radix_scatter_pipeline,
+            &self.demography_pipeline,
+            &self.neighbour_pipeline,
+            &self.separation_hash_pipeline,
+            &self.separation_ranges_pipeline,
+            &self.separation_push_pipeline,
+            &self.obstacle_brush_pipeline,
+        ];
+        self.compile_times.iter().copied()
+            .chain(lazy.into_iter().filter_map(|p| p.compile_time().map(|t| (p.name, t))))
+            .collect()
+    }
     
     /// Create the reaction-diffusion compute pipeline
     fn create_rd_pipeline(device: &Device, rd_layout: &wgpu::BindGroupLayout) -> ComputePipeline {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("rd_shader"),
-            source: wgpu::ShaderSource::Wgsl(crate::shaders::rd_step().into()),
+            source: wgpu::ShaderSource::Wgsl(shaders::rd_step().into()),
         });
         
         let pl = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -47,7 +146,7 @@ impl ComputePipelines {
     fn create_agent_pipeline(device: &Device, agent_layout: &wgpu::BindGroupLayout) -> ComputePipeline {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("agent_shader"),
-            source: wgpu::ShaderSource::Wgsl(crate::shaders::agent_step().into()),
+            source: wgpu::ShaderSource::Wgsl(shaders::agent_step().into()),
         });
         
         let pl = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -68,7 +167,7 @@ impl ComputePipelines {
     fn create_clear_occupancy_pipeline(device: &Device, clear_layout: &wgpu::BindGroupLayout) -> ComputePipeline {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("clear_occupancy_shader"),
-            source: wgpu::ShaderSource::Wgsl(crate::shaders::clear_occupancy().into()),
+            source: wgpu::ShaderSource::Wgsl(shaders::clear_occupancy().into()),
         });
         
         let pl = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -84,4 +183,76 @@ impl ComputePipelines {
             entry_point: "main",
         })
     }
+    
+    /// Create the bloom injection compute pipeline
+    fn create_bloom_pipeline(device: &Device, bloom_layout: &wgpu::BindGroupLayout) -> ComputePipeline {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("bloom_inject_shader"),
+            source: wgpu::ShaderSource::Wgsl(shaders::bloom_inject().into()),
+        });
+        
+        let pl = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("bloom_pl"),
+            bind_group_layouts: &[bloom_layout],
+            push_constant_ranges: &[],
+        });
+        
+        device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("bloom_pipeline"),
+            layout: Some(&pl),
+            module: &shader,
+            entry_point: "main",
+        })
+    }
+}
+
+/// A compute pipeline compiled on first use
+///
+/// The pipeline layout is made up front; the shader module and pipeline,
+/// where drivers spend their time, wait for the first `get`.
+pub struct LazyPipeline {
+    name: &'static str,
+    source: fn() -> String,
+    entry_point: &'static str,
+    layout: PipelineLayout,
+    pipeline: OnceLock<(ComputePipeline, Duration)>,
+}
+
+impl LazyPipeline {
+    fn new(device: &Device, name: &'static str, layout: &wgpu::BindGroupLayout, source: fn() -> String, entry_point: &'static str) -> Self {
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&format!("{}_pl", name)),
+            bind_group_layouts: &[layout],
+            push_constant_ranges: &[],
+        });
+        Self { name, source, entry_point, layout, pipeline: OnceLock::new() }
+    }
+
+    /// The pipeline, compiled now if this is its first use
+    pub fn get(&self, device: &Device) -> &ComputePipeline {
+        &self.pipeline.get_or_init(|| {
+            let start = Instant::now();
+            let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(&format!("{}_shader", self.name)),
+                source: wgpu::ShaderSource::Wgsl((self.source)().into()),
+            });
+            let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(&format!("{}_pipeline", self.name)),
+                layout: Some(&self.layout),
+                module: &shader,
+                entry_point: self.entry_point,
+            });
+            (pipeline, start.elapsed())
+        }).0
+    }
+
+    /// Name used in labels and `ComputePipelines::compile_times`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Time its compilation took (`None` until the first `get`)
+    pub fn compile_time(&self) -> Option<Duration> {
+        self.pipeline.get().map(|(_, t)| *t)
+    }
 }
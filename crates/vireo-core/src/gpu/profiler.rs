@@ -0,0 +1,282 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use wgpu::{Buffer, CommandEncoder, Device, QuerySet, Queue};
+
+/// Timestamp scopes recorded per frame (two queries each)
+pub const MAX_SCOPES: u32 = 32;
+/// Frames whose timestamps can be in flight at once
+const READBACK_SLOTS: usize = 4;
+
+/// One frame's readback buffer and the scope names it holds
+struct Slot {
+    buffer: Buffer,
+    names: Vec<&'static str>,
+    cpu: Vec<(&'static str, f32)>,
+    mapped: Arc<AtomicBool>, // Set by the map callback
+    pending: bool,           // Copied into and waiting for the map
+}
+
+/// Per-frame GPU pass timings from timestamp queries, read back without stalling
+///
+/// Passes are wrapped in `begin_scope`/`end_scope` on whatever encoder records
+/// them; `finish_frame` resolves the frame's queries into one of a few
+/// readback buffers and `poll` returns each frame's timings (in ms, summed
+/// per scope name) once its buffer has mapped, usually a frame or two later.
+/// Host-side durations added with `record_cpu` ride along with the same frame.
+///
+/// Without `Features::TIMESTAMP_QUERY` on the device the scopes are no-ops and
+/// frames carry only their CPU samples.
+pub struct GpuProfiler {
+    queries: Option<QuerySet>,
+    resolve_buffer: Option<Buffer>,
+    slots: Vec<Slot>,
+    period_ms: f32,
+    names: Vec<&'static str>,
+    open: bool,
+    cpu: Vec<(&'static str, f32)>,
+    ready: VecDeque<Vec<(&'static str, f32)>>,
+}
+
+impl GpuProfiler {
+    pub fn new(device: &Device, queue: &Queue) -> Self {
+        let enabled = device.features().contains(wgpu::Features::TIMESTAMP_QUERY);
+        let bytes = MAX_SCOPES as u64 * 2 * 8;
+        let queries = enabled.then(|| {
+            device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("profiler_queries"),
+                ty: wgpu::QueryType::Timestamp,
+                count: MAX_SCOPES * 2,
+            })
+        });
+        let resolve_buffer = enabled.then(|| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("profiler_resolve"),
+                size: bytes,
+                usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            })
+        });
+        let slots = if enabled {
+            (0..READBACK_SLOTS)
+                .map(|_| Slot {
+                    buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("profiler_readback"),
+                        size: bytes,
+                        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                        mapped_at_creation: false,
+                    }),
+                    names: Vec::new(),
+                    cpu: Vec::new(),
+                    mapped: Arc::new(AtomicBool::new(false)),
+                    pending: false,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            queries,
+            resolve_buffer,
+            slots,
+            period_ms: queue.get_timestamp_period() * 1e-6,
+            names: Vec::new(),
+            open: false,
+            cpu: Vec::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Whether GPU timestamps are recorded (the device has `TIMESTAMP_QUERY`)
+    pub fn is_enabled(&self) -> bool {
+        self.queries.is_some()
+    }
+
+    /// Start timing the commands recorded next into `encoder` under `name`
+    ///
+    /// Scopes do not nest; past `MAX_SCOPES` in one frame they are dropped.
+    pub fn begin_scope(&mut self, encoder: &mut CommandEncoder, name: &'static str) {
+        let Some(queries) = &self.queries else { return };
+        if self.open || self.names.len() as u32 >= MAX_SCOPES {
+            return;
+        }
+        encoder.write_timestamp(queries, self.names.len() as u32 * 2);
+        self.names.push(name);
+        self.open = true;
+    }
+
+    /// Close the scope opened by `begin_scope` (on the same or a later encoder)
+    pub fn end_scope(&mut self, encoder: &mut CommandEncoder) {
+        let Some(queries) = &self.queries else { return };
+        if !std::mem::take(&mut self.open) {
+            return;
+        }
+        encoder.write_timestamp(queries, self.names.len() as u32 * 2 - 1);
+    }
+
+    /// Attach a host-side duration (ms) to the current frame
+    pub fn record_cpu(&mut self, name: &'static str, ms: f32) {
+        self.cpu.push((name, ms));
+    }
+
+    /// Resolve this frame's timestamps after all its work has been submitted
+    ///
+    /// If every readback buffer is still in flight the frame is dropped rather
+    /// than waited for.
+    pub fn finish_frame(&mut self, device: &Device, queue: &Queue) {
+        let names = std::mem::take(&mut self.names);
+        let cpu = std::mem::take(&mut self.cpu);
+        self.open = false;
+        let (Some(queries), Some(resolve_buffer)) = (&self.queries, &self.resolve_buffer) else {
+            self.ready.push_back(cpu);
+            return;
+        };
+        if names.is_empty() {
+            self.ready.push_back(cpu);
+            return;
+        }
+        let Some(slot) = self.slots.iter_mut().find(|s| !s.pending) else {
+            return;
+        };
+
+        let count = names.len() as u32 * 2;
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("profiler_resolve_encoder"),
+        });
+        encoder.resolve_query_set(queries, 0..count, resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(resolve_buffer, 0, &slot.buffer, 0, count as u64 * 8);
+        queue.submit(Some(encoder.finish()));
+
+        slot.names = names;
+        slot.cpu = cpu;
+        slot.pending = true;
+        let mapped = slot.mapped.clone();
+        slot.buffer.slice(..count as u64 * 8).map_async(wgpu::MapMode::Read, move |result| {
+            mapped.store(result.is_ok(), Ordering::Release);
+        });
+    }
+
+    /// Timings of the oldest finished frame not returned yet, as (scope, ms)
+    ///
+    /// GPU scopes with the same name are summed (e.g. several simulation steps
+    /// in one frame); CPU samples follow in the order they were recorded.
+    pub fn poll(&mut self, device: &Device) -> Option<Vec<(&'static str, f32)>> {
+        if self.slots.iter().any(|s| s.pending) {
+            device.poll(wgpu::Maintain::Poll);
+        }
+        for slot in self.slots.iter_mut().filter(|s| s.pending && s.mapped.load(Ordering::Acquire)) {
+            let count = slot.names.len() as u64 * 2;
+            let ticks: Vec<u64> = {
+                let view = slot.buffer.slice(..count * 8).get_mapped_range();
+                bytemuck::cast_slice(&view).to_vec()
+            };
+            slot.buffer.unmap();
+            slot.mapped.store(false, Ordering::Release);
+            slot.pending = false;
+
+            let mut samples: Vec<(&'static str, f32)> = Vec::new();
+            for (name, pair) in slot.names.iter().zip(ticks.chunks_exact(2)) {
+                let ms = pair[1].saturating_sub(pair[0]) as f32 * self.period_ms;
+                match samples.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, total)) => *total += ms,
+                    None => samples.push((name, ms)),
+                }
+            }
+            samples.append(&mut slot.cpu);
+            self.ready.push_back(samples);
+        }
+        self.ready.pop_front()
+    }
+}
+
+/// Rolling per-frame timings of named passes, oldest first
+///
+/// Layers are the pass names in the order they were first seen, capped at
+/// `MAX_LAYERS` (later names are folded into the last layer).
+#[derive(Debug, Clone)]
+pub struct PassTimeline {
+    names: Vec<String>,
+    frames: VecDeque<Vec<f32>>,
+    capacity: usize,
+}
+
+impl PassTimeline {
+    /// Layers a timeline distinguishes
+    pub const MAX_LAYERS: usize = 8;
+
+    pub fn new(capacity: usize) -> Self {
+        Self { names: Vec::new(), frames: VecDeque::with_capacity(capacity), capacity: capacity.max(1) }
+    }
+
+    /// Append one frame of (pass, ms) samples, dropping the oldest past capacity
+    pub fn push(&mut self, samples: &[(&str, f32)]) {
+        let mut frame = vec![0.0; self.names.len()];
+        for &(name, ms) in samples {
+            let layer = match self.names.iter().position(|n| n == name) {
+                Some(layer) => layer,
+                None if self.names.len() < Self::MAX_LAYERS => {
+                    self.names.push(name.to_string());
+                    frame.push(0.0);
+                    self.names.len() - 1
+                }
+                None => Self::MAX_LAYERS - 1,
+            };
+            frame[layer] += ms;
+        }
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Pass names, one per layer
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Frames held (at most the capacity)
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Time of `layer` in frame `frame` (0 = oldest), 0 for passes first seen later
+    pub fn get(&self, frame: usize, layer: usize) -> f32 {
+        self.frames.get(frame).and_then(|f| f.get(layer)).copied().unwrap_or(0.0)
+    }
+
+    /// Cumulative layer tops, `MAX_LAYERS` per frame, oldest frame first
+    ///
+    /// Entry `frame * MAX_LAYERS + k` is the sum of layers `0..=k`; unused
+    /// layers repeat the frame total. This is the chart's storage buffer.
+    pub fn stacked(&self) -> Vec<f32> {
+        let mut out = Vec::with_capacity(self.frames.len() * Self::MAX_LAYERS);
+        for frame in &self.frames {
+            let mut top = 0.0;
+            for k in 0..Self::MAX_LAYERS {
+                top += frame.get(k).copied().unwrap_or(0.0);
+                out.push(top);
+            }
+        }
+        out
+    }
+
+    /// Largest frame total
+    pub fn peak(&self) -> f32 {
+        self.frames.iter().map(|f| f.iter().sum::<f32>()).fold(0.0, f32::max)
+    }
+
+    /// Mean time of each layer over the held frames
+    pub fn means(&self) -> Vec<f32> {
+        let n = self.frames.len().max(1) as f32;
+        (0..self.names.len()).map(|k| (0..self.frames.len()).map(|f| self.get(f, k)).sum::<f32>() / n).collect()
+    }
+}
@@ -0,0 +1,175 @@
+use wgpu::{BindGroup, Buffer, CommandEncoder, Device, Queue};
+use vireo_params::RadixSortParams;
+use crate::gpu::ComputePipelines;
+use crate::gpu::layouts::Layouts;
+
+/// Buckets per digit pass (8-bit digits)
+const RADIX: u32 = 256;
+/// Elements per workgroup of the `histogram` and `scatter` entry points
+const BLOCK: u32 = 1024;
+/// Digit passes needed for a full 32-bit key
+const MAX_PASSES: usize = 4;
+
+/// Stable GPU radix sort over `(u32 key, u32 value)` pairs
+///
+/// Owns a ping-pong pair of key/value buffers: callers write (or copy) their
+/// pairs into `keys()`/`values()`, encode `sort`, and read the sorted pairs
+/// back from the same buffers. Passes are always run in pairs so the result
+/// lands where the input was, e.g. cell-index keys with agent-index values
+/// for spatial hashing.
+pub struct RadixSort {
+    keys: Buffer,
+    values: Buffer,
+    _keys_tmp: Buffer,
+    _values_tmp: Buffer,
+    _hist: Buffer,
+    params_buffers: [Buffer; MAX_PASSES],
+    bind_groups: [BindGroup; MAX_PASSES],
+    capacity: u32,
+}
+
+impl RadixSort {
+    /// Allocate buffers for sorting up to `capacity` pairs
+    pub fn new(device: &Device, layouts: &Layouts, capacity: u32) -> Self {
+        let capacity = capacity.max(1);
+        let max_blocks = capacity.div_ceil(BLOCK);
+
+        let pair_buffer = |label| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: capacity as u64 * 4,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            })
+        };
+        let keys = pair_buffer("radix_keys");
+        let values = pair_buffer("radix_values");
+        let keys_tmp = pair_buffer("radix_keys_tmp");
+        let values_tmp = pair_buffer("radix_values_tmp");
+
+        let hist = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("radix_hist"),
+            size: (RADIX * max_blocks) as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        // One uniform per pass so every pass of a sort can be encoded up front
+        let params_buffers = std::array::from_fn(|_| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("radix_params"),
+                size: std::mem::size_of::<RadixSortParams>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        });
+
+        // Even passes read the primary buffers, odd passes the temporaries
+        let bind_groups = std::array::from_fn(|pass| {
+            let (src, dst) = if pass % 2 == 0 {
+                ((&keys, &values), (&keys_tmp, &values_tmp))
+            } else {
+                ((&keys_tmp, &values_tmp), (&keys, &values))
+            };
+            let params: &Buffer = &params_buffers[pass];
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("radix_sort_bg"),
+                layout: &layouts.radix_sort,
+                entries: &[
+                    // @binding(0) input keys
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: src.0.as_entire_binding(),
+                    },
+                    // @binding(1) input values
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: src.1.as_entire_binding(),
+                    },
+                    // @binding(2) output keys
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: dst.0.as_entire_binding(),
+                    },
+                    // @binding(3) output values
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: dst.1.as_entire_binding(),
+                    },
+                    // @binding(4) digit histogram
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: hist.as_entire_binding(),
+                    },
+                    // @binding(5) RadixSortParams uniform
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: params.as_entire_binding(),
+                    },
+                ],
+            })
+        });
+
+        Self {
+            keys,
+            values,
+            _keys_tmp: keys_tmp,
+            _values_tmp: values_tmp,
+            _hist: hist,
+            params_buffers,
+            bind_groups,
+            capacity,
+        }
+    }
+
+    /// Keys to sort; holds the sorted keys once the encoded sort has run
+    pub fn keys(&self) -> &Buffer {
+        &self.keys
+    }
+
+    /// Values carried along with their keys
+    pub fn values(&self) -> &Buffer {
+        &self.values
+    }
+
+    /// Maximum number of pairs per sort
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Upload pass parameters and encode a stable sort of the first `len` pairs
+    ///
+    /// Only the low `key_bits` bits of each key take part (rounded up to a
+    /// multiple of 16, i.e. two 8-bit digit passes), so small keys such as
+    /// cell indices sort in half the passes of a full 32-bit key.
+    pub fn sort(&self, device: &Device, queue: &Queue, encoder: &mut CommandEncoder, pipelines: &ComputePipelines, len: u32, key_bits: u32) {
+        assert!(len <= self.capacity, "radix sort of {} pairs exceeds capacity {}", len, self.capacity);
+        if len == 0 {
+            return;
+        }
+
+        let blocks = len.div_ceil(BLOCK);
+        let passes = (key_bits.clamp(1, 32).div_ceil(16) * 2) as usize;
+        let stages = [
+            (pipelines.radix_histogram_pipeline.get(device), blocks),
+            (pipelines.radix_scan_pipeline.get(device), 1),
+            (pipelines.radix_scatter_pipeline.get(device), blocks),
+        ];
+
+        for pass in 0..passes {
+            let params = RadixSortParams { len, shift: pass as u32 * 8, blocks, _pad: 0 };
+            queue.write_buffer(&self.params_buffers[pass], 0, bytemuck::bytes_of(&params));
+
+            // Separate compute passes so each stage sees the previous one's writes
+            for (pipeline, groups) in stages {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("radix sort pass"),
+                    timestamp_writes: None,
+                });
+                cpass.set_pipeline(pipeline);
+                cpass.set_bind_group(0, &self.bind_groups[pass], &[]);
+                cpass.dispatch_workgroups(groups, 1, 1);
+            }
+        }
+    }
+}
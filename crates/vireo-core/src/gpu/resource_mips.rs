@@ -0,0 +1,155 @@
+use wgpu::{BindGroup, Buffer, Device, Queue, TextureView, util::DeviceExt};
+use vireo_params::MipLevelParams;
+use crate::gpu::{ComputePipelines, read_buffer_from};
+use crate::gpu::layouts::Layouts;
+
+/// Workgroup size (per axis) of the mip shader
+const CELL_GROUP: u32 = 8;
+
+/// GPU mip chain of the resource channel
+///
+/// Level 0 is R at full resolution and level k is 2^k coarser, down to a
+/// single cell; every level is a box average of the one before, so on sizes
+/// divisible by 2^k level k matches `FieldManager::downsample(1 << k)` for R
+/// (up to the f16 rounding of the CPU mirror).
+/// All levels share one storage buffer and are rebuilt together by `generate`,
+/// so observers only read back the level they need.
+pub struct ResourceMips {
+    levels: Buffer,
+    readback: Buffer,
+    stride: u32,
+    sizes: Vec<[u32; 2]>,
+    offsets: Vec<u32>,
+    bind_group_a: BindGroup,
+    bind_group_b: BindGroup,
+}
+
+impl ResourceMips {
+    /// `field_a`/`field_b` are the sampled views of the two ping-pong textures
+    pub fn new(device: &Device, layouts: &Layouts, field_a: &TextureView, field_b: &TextureView, size: [u32; 2]) -> Self {
+        let mut sizes = vec![size];
+        while let Some(&[w, h]) = sizes.last().filter(|s| s[0] > 1 || s[1] > 1) {
+            sizes.push([w.div_ceil(2), h.div_ceil(2)]);
+        }
+        let offsets: Vec<u32> = sizes.iter()
+            .scan(0, |next, s| {
+                let offset = *next;
+                *next += s[0] * s[1];
+                Some(offset)
+            })
+            .collect();
+        let cells = offsets.last().unwrap() + 1;
+
+        // One parameter block per level at the dynamic offset alignment
+        let stride = device.limits().min_uniform_buffer_offset_alignment;
+        let mut contents = vec![0u8; sizes.len() * stride as usize];
+        for level in 0..sizes.len() {
+            let src = level.saturating_sub(1);
+            let entry = MipLevelParams {
+                src_size: sizes[src],
+                dst_size: sizes[level],
+                src_offset: offsets[src],
+                dst_offset: offsets[level],
+                _pad: [0; 2],
+            };
+            let at = level * stride as usize;
+            contents[at..at + std::mem::size_of::<MipLevelParams>()].copy_from_slice(bytemuck::bytes_of(&entry));
+        }
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("resource_mip_params"),
+            contents: &contents,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let levels = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("resource_mip_levels"),
+            size: cells as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        // Sized for level 0; coarser levels use a prefix of it
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("resource_mip_readback"),
+            size: (size[0] * size[1]) as u64 * 4,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let create_bind_group = |label, field_view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &layouts.resource_mip,
+                entries: &[
+                    // @binding(0) field texture (sampled)
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(field_view),
+                    },
+                    // @binding(1) every mip level, back to back
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: levels.as_entire_binding(),
+                    },
+                    // @binding(2) MipLevelParams of one level
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                            buffer: &params_buffer,
+                            offset: 0,
+                            size: wgpu::BufferSize::new(std::mem::size_of::<MipLevelParams>() as u64),
+                        }),
+                    },
+                ],
+            })
+        };
+        let bind_group_a = create_bind_group("resource_mip_a_bg", field_a);
+        let bind_group_b = create_bind_group("resource_mip_b_bg", field_b);
+
+        Self { levels, readback, stride, sizes, offsets, bind_group_a, bind_group_b }
+    }
+
+    /// Number of levels, the last one being a single cell
+    pub fn level_count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Cells per axis of `level`
+    pub fn level_size(&self, level: usize) -> [u32; 2] {
+        self.sizes[level]
+    }
+
+    /// Rebuild every level from the current front field
+    pub fn generate(&self, device: &Device, queue: &Queue, pipelines: &ComputePipelines, front_is_a: bool) {
+        let bind_group = if front_is_a { &self.bind_group_a } else { &self.bind_group_b };
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("resource_mips"),
+        });
+        for (level, size) in self.sizes.iter().enumerate() {
+            // One pass per level so each reduction sees the previous one complete
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("resource mip pass"),
+                timestamp_writes: None,
+            });
+            let pipeline = if level == 0 { pipelines.resource_mip_copy_pipeline.get(device) } else { pipelines.resource_mip_reduce_pipeline.get(device) };
+            cpass.set_pipeline(pipeline);
+            cpass.set_bind_group(0, bind_group, &[level as u32 * self.stride]);
+            cpass.dispatch_workgroups(size[0].div_ceil(CELL_GROUP), size[1].div_ceil(CELL_GROUP), 1);
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+
+    /// Read back `level` as generated last, row-major
+    pub fn read_level(&self, device: &Device, queue: &Queue, level: usize) -> Vec<f32> {
+        let [w, h] = self.sizes[level];
+        let bytes = (w * h) as u64 * 4;
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("resource_mip_level_copy"),
+        });
+        encoder.copy_buffer_to_buffer(&self.levels, self.offsets[level] as u64 * 4, &self.readback, 0, bytes);
+        queue.submit(Some(encoder.finish()));
+
+        read_buffer_from(device, queue, &self.readback, bytes)
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+use wgpu::{CommandEncoder, Device, Texture};
+use crate::gpu::FieldPingPong;
+use crate::gpu::layouts::Layouts;
+use crate::sim::Agent;
+
+/// One captured world state: the field stays in the ring texture, the agents on the CPU
+#[derive(Debug, Clone)]
+pub struct RewindPoint {
+    pub step: u32,
+    pub agents: Vec<Agent>,
+    slot: u32,
+}
+
+/// The last few field states kept on the GPU for instant rewind
+///
+/// A capture copies the front field texture into one slot of a texture array,
+/// so neither capturing nor rewinding reads the field back; the agents come
+/// from the caller's CPU mirror. Rewinding drops every point after the one
+/// restored, so the ring always holds the history of the current branch.
+/// Bloom and cohort state are not captured.
+pub struct RewindRing {
+    texture: Texture,
+    size: [u32; 2],
+    capacity: u32,
+    points: VecDeque<RewindPoint>, // Oldest first
+}
+
+impl RewindRing {
+    pub fn new(device: &Device, size: [u32; 2], capacity: u32) -> Self {
+        let capacity = capacity.max(1);
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("rewind_ring"),
+            size: wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth_or_array_layers: capacity * Layouts::FIELD_LAYERS,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Layouts::FIELD_FORMAT,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        Self { texture, size, capacity, points: VecDeque::new() }
+    }
+
+    /// Most points held at once
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Held points, oldest first
+    pub fn points(&self) -> impl Iterator<Item = &RewindPoint> {
+        self.points.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Forget every point, e.g. when the world it belongs to is replaced
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    /// Encode a copy of the front field into a free slot (the oldest point's
+    /// when full) and record it with `agents` as taken at `step`
+    pub fn capture(&mut self, encoder: &mut CommandEncoder, field: &FieldPingPong, step: u32, agents: Vec<Agent>) {
+        let slot = if self.points.len() as u32 == self.capacity {
+            self.points.pop_front().map_or(0, |p| p.slot)
+        } else {
+            (0..self.capacity).find(|s| self.points.iter().all(|p| p.slot != *s)).unwrap_or(0)
+        };
+        encoder.copy_texture_to_texture(
+            Self::layers(field.front_texture(), 0),
+            Self::layers(&self.texture, slot),
+            self.extent(),
+        );
+        self.points.push_back(RewindPoint { step, agents, slot });
+    }
+
+    /// Encode a copy of the point `back` captures before the newest (0 = the
+    /// newest) into the front field and drop the points after it
+    ///
+    /// Returns the restored point, whose agents and step the caller puts back;
+    /// `None` (and nothing encoded) when the ring holds fewer points.
+    pub fn rewind(&mut self, encoder: &mut CommandEncoder, field: &FieldPingPong, back: usize) -> Option<RewindPoint> {
+        let index = self.points.len().checked_sub(back + 1)?;
+        self.points.truncate(index + 1);
+        let point = self.points[index].clone();
+        encoder.copy_texture_to_texture(
+            Self::layers(&self.texture, point.slot),
+            Self::layers(field.front_texture(), 0),
+            self.extent(),
+        );
+        Some(point)
+    }
+
+    /// Copy target covering every field layer of `slot`
+    fn layers(texture: &Texture, slot: u32) -> wgpu::ImageCopyTexture<'_> {
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d { x: 0, y: 0, z: slot * Layouts::FIELD_LAYERS },
+            aspect: wgpu::TextureAspect::All,
+        }
+    }
+
+    fn extent(&self) -> wgpu::Extent3d {
+        wgpu::Extent3d {
+            width: self.size[0],
+            height: self.size[1],
+            depth_or_array_layers: Layouts::FIELD_LAYERS,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use wgpu::{BindGroup, Buffer, CommandEncoder, Device, Queue, util::DeviceExt};
+use vireo_params::SeparationParams;
+use crate::SimulationConfig;
+use crate::gpu::{ComputePipelines, RadixSort};
+use crate::gpu::layouts::Layouts;
+
+/// Workgroup size of every separation entry point (one thread per agent slot)
+const SLOT_GROUP: u32 = 128;
+
+/// Soft-body separation pass (`separation.enabled`)
+///
+/// Hashes every slot into its world cell, radix sorts the (cell, slot) pairs,
+/// finds each cell's run in the sorted pairs and pushes overlapping agents
+/// apart, all on the GPU. Positions are copied before the push so the outcome
+/// does not depend on thread order. Runs right before the agent pass.
+pub struct Separation {
+    sort: RadixSort,
+    _params_buffer: Buffer,
+    ranges: Buffer,
+    _positions: Buffer,
+    bind_group: BindGroup,
+    slots: u32,
+    key_bits: u32,
+}
+
+impl Separation {
+    pub fn new(device: &Device, layouts: &Layouts, config: &SimulationConfig, agents_buffer: &Buffer, slots: u32) -> Self {
+        let size = config.world.size;
+        let params = SeparationParams::new(&config.separation, size, slots);
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("separation_params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let sort = RadixSort::new(device, layouts, slots);
+        let cells = size[0] * size[1];
+        let ranges = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("separation_cell_ranges"),
+            size: cells as u64 * 8,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let positions = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("separation_positions"),
+            size: slots.max(1) as u64 * 16,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("separation_bg"),
+            layout: &layouts.separation,
+            entries: &[
+                // @binding(0) agents storage buffer
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: agents_buffer.as_entire_binding(),
+                },
+                // @binding(1) SeparationParams uniform
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                // @binding(2) hash keys
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: sort.keys().as_entire_binding(),
+                },
+                // @binding(3) slots
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: sort.values().as_entire_binding(),
+                },
+                // @binding(4) cell ranges
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: ranges.as_entire_binding(),
+                },
+                // @binding(5) positions before the push
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: positions.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            sort,
+            _params_buffer: params_buffer,
+            ranges,
+            _positions: positions,
+            bind_group,
+            slots,
+            // Dead slots take the past-the-end key `cells`, which must sort too
+            key_bits: u32::BITS - cells.leading_zeros(),
+        }
+    }
+
+    /// Encode one hash, sort and push over every agent slot
+    pub fn apply(&self, device: &Device, queue: &Queue, encoder: &mut CommandEncoder, pipelines: &ComputePipelines) {
+        if self.slots == 0 {
+            return;
+        }
+        let workgroups = self.slots.div_ceil(SLOT_GROUP);
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("separation hash pass"),
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(pipelines.separation_hash_pipeline.get(device));
+            cpass.set_bind_group(0, &self.bind_group, &[]);
+            cpass.dispatch_workgroups(workgroups, 1, 1);
+        }
+
+        self.sort.sort(device, queue, encoder, pipelines, self.slots, self.key_bits);
+        encoder.clear_buffer(&self.ranges, 0, None);
+
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("separation push pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_bind_group(0, &self.bind_group, &[]);
+        cpass.set_pipeline(pipelines.separation_ranges_pipeline.get(device));
+        cpass.dispatch_workgroups(workgroups, 1, 1);
+        cpass.set_pipeline(pipelines.separation_push_pipeline.get(device));
+        cpass.dispatch_workgroups(workgroups, 1, 1);
+    }
+}
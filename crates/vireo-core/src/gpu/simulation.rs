@@ -0,0 +1,816 @@
+use wgpu::{BindGroup, Buffer, Sampler};
+use crate::{RDParams, AgentParams, FieldChannel, GrowthPolicy, SimulationConfig, SpeciesCapacity, StationConfig, TimeUniform, SPECIES};
+use crate::gpu::{GpuDevice, ComputePipelines, FieldPingPong, RdBindInputs, AgentCuller, AgentRanges, BloomInjector, CellGather, DemographyPass, DiffusionMap, EnergyProbe, EventCounters, FieldImage, ForagingTally, MovementMetrics, ObstacleMap, OccupancyClear, OccupancyTotal, ResourceMips, Separation, Steering, TerrainMap, UtilityMap, ImageKind};
+use crate::gpu::layouts::Layouts;
+use crate::sim::{Agent, AgentLayout, AgentManager, AgentPopulation, CohortTracker, CullRegion, EnergyRecord, EventMaps, ParamForcing, ParamPerturbation, FieldManager, Interaction, MovementStats, ObstacleBrush, SampledField, SimRng, StateSnapshot, StationSample, sample_field, sampling_rng};
+
+/// Salt mixed into the world seed for the positions of scripted spawns
+const SPAWN_SEED_SALT: u64 = 0x5350_4157_4E45_4453;
+
+/// Resources the agent pass binds besides the sampled field view (see `Layouts::agent`)
+#[derive(Clone, Copy)]
+pub struct AgentBindInputs<'a> {
+    pub agents: &'a Buffer,
+    pub agent_params: &'a Buffer,
+    pub occupancy: &'a Buffer,
+    pub obstacles: &'a wgpu::TextureView, // Obstacle signed distance field
+    pub elevation: &'a wgpu::TextureView,
+    pub time: &'a Buffer,
+    pub ranges: &'a AgentRanges,
+    pub steering: &'a Steering,
+    pub foraging: &'a ForagingTally,
+    pub events: &'a EventCounters,
+    pub energy_probe: &'a EnergyProbe,
+}
+
+impl AgentBindInputs<'_> {
+    /// Agent pass bind group sampling `field_view`
+    pub fn bind_group(&self, device: &wgpu::Device, layouts: &Layouts, label: &str, field_view: &wgpu::TextureView) -> BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout: &layouts.agent,
+            entries: &[
+                // @binding(0) agents storage buffer
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.agents.as_entire_binding(),
+                },
+                // @binding(1) field texture (sampled)
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(field_view),
+                },
+                // @binding(2) AgentParams uniform
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.agent_params.as_entire_binding(),
+                },
+                // @binding(3) occupancy buffer
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.occupancy.as_entire_binding(),
+                },
+                // @binding(4) obstacle signed distance field
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(self.obstacles),
+                },
+                // @binding(5) AgentRange uniform (dynamic offset per species)
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: self.ranges.binding(),
+                },
+                // @binding(6) terrain elevation
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(self.elevation),
+                },
+                // @binding(7) simulation clock
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: self.time.as_entire_binding(),
+                },
+                // @binding(8) per-species steering weights
+                wgpu::BindGroupEntry {
+                    binding: 8,
+                    resource: self.steering.params_buffer().as_entire_binding(),
+                },
+                // @binding(9) neighbour grid
+                wgpu::BindGroupEntry {
+                    binding: 9,
+                    resource: self.steering.grid().as_entire_binding(),
+                },
+                // @binding(10) foraging tally
+                wgpu::BindGroupEntry {
+                    binding: 10,
+                    resource: self.foraging.buffer().as_entire_binding(),
+                },
+                // @binding(11) starvation deaths per cell
+                wgpu::BindGroupEntry {
+                    binding: 11,
+                    resource: self.events.buffer().as_entire_binding(),
+                },
+                // @binding(12) energy log of the selected agent
+                wgpu::BindGroupEntry {
+                    binding: 12,
+                    resource: self.energy_probe.buffer().as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
+
+/// One GPU-resident world: layouts, pipelines, field ping-pong and buffers
+///
+/// `step` advances the world entirely on the GPU. The CPU mirrors
+/// (`field_manager`, `agent_manager`) are only refreshed by the explicit
+/// `sync_*` calls, so callers decide how often to pay for a readback.
+pub struct Simulation {
+    pub config: SimulationConfig,
+    pub layouts: Layouts,
+    pub pipelines: ComputePipelines,
+    pub field: FieldPingPong,
+    pub field_manager: FieldManager,
+    pub agent_manager: AgentManager,
+    pub blooms: BloomInjector,
+    pub obstacles: ObstacleMap,
+    pub terrain: TerrainMap,
+    pub diffusion: DiffusionMap,
+    pub cohorts: CohortTracker,
+    pub perturbation: Option<ParamPerturbation>, // `perturbation.enabled` jitter, reapplied every step
+    pub forcing: Option<ParamForcing>,           // `forcing` time series, reapplied every step
+    pub skip: Vec<StepPass>,                     // Passes left out of `step`, for debugging
+
+    /// Uniforms written to the GPU at the start of every step (rebuilt from
+    /// `config` each step when it has schedules)
+    pub rd_params: RDParams,
+    pub agent_params: AgentParams,
+
+    rd_params_buffer: Buffer,
+    agent_params_buffer: Buffer,
+    time_buffer: Buffer,
+    occupancy_buffer: Buffer,
+    occupancy_clear: OccupancyClear,
+    occupancy_total: Option<OccupancyTotal>, // `output.occupancy_total`
+    events: EventCounters,                   // Births and deaths per cell (`output.event_window`)
+    energy_probe: EnergyProbe,               // Energy log of one selected agent
+    utility: UtilityMap,
+    resource_mips: ResourceMips,
+    cell_gather: CellGather,                 // Sampled metrics (`output.sampling`)
+    field_image: FieldImage,                 // Snapshot images
+
+    // Sampler for the field render bind groups owned by FieldPingPong
+    field_sampler: Sampler,
+
+    // Agent buffer and passes, `None` for a world without agent slots
+    agents: Option<AgentPasses>,
+    growth_events: Vec<GrowthEvent>,
+
+    step: u32,
+}
+
+/// One reallocation of the agent buffer under `agents.growth.policy: grow`
+///
+/// Slots move when a range grows (the ranges stay consecutive), so anything
+/// keyed by slot index follows them with `from.remap(slot, &to)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrowthEvent {
+    pub step: u32,        // Completed steps when the buffer grew
+    pub alive: [u32; 3],  // Agents per kind that triggered it
+    pub from: AgentLayout,
+    pub to: AgentLayout,
+}
+
+/// A pass of `Simulation::step` that can be left out to see what it contributes
+///
+/// Without `Rd` the field is not written and the front texture stays as it was;
+/// without `Agents` nothing moves or deposits occupancy, so the RD pass sees no herbivores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepPass {
+    /// Neighbour grid for crowding, flocking and cross-attraction
+    Steering,
+    /// Agent movement (after separation), feeding and occupancy deposit
+    Agents,
+    /// Random deaths and births (`demography.enabled`)
+    Demography,
+    /// Bloom boost map refresh
+    Blooms,
+    /// Reaction-diffusion of the field
+    Rd,
+}
+
+impl StepPass {
+    pub const ALL: [StepPass; 5] = [StepPass::Steering, StepPass::Agents, StepPass::Demography, StepPass::Blooms, StepPass::Rd];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StepPass::Steering => "steering",
+            StepPass::Agents => "agents",
+            StepPass::Demography => "demography",
+            StepPass::Blooms => "blooms",
+            StepPass::Rd => "rd",
+        }
+    }
+}
+
+impl std::str::FromStr for StepPass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|p| p.name() == s).ok_or_else(|| {
+            format!("unknown pass '{}' (expected one of {})", s, Self::ALL.map(StepPass::name).join(", "))
+        })
+    }
+}
+
+/// Everything that reads or writes the agent buffer
+///
+/// wgpu rejects zero-sized buffers and bindings, so a pure reaction-diffusion
+/// world (`herbivores: 0` and no other species) builds none of this.
+struct AgentPasses {
+    buffer: Buffer,
+    ranges: AgentRanges,
+    movement: MovementMetrics,
+    culler: AgentCuller,
+    demography: Option<DemographyPass>,
+    separation: Option<Separation>, // `separation.enabled`
+    steering: Steering,
+    foraging: ForagingTally,
+
+    // Agent pass bind groups reading field A or field B
+    a_bg: BindGroup,
+    b_bg: BindGroup,
+}
+
+impl Simulation {
+    /// Seed a world from `config` and upload it to the GPU
+    pub fn new(gpu: &GpuDevice, config: SimulationConfig) -> Self {
+        let agent_manager = AgentManager::spawn(
+            &config.agents,
+            [config.world.size[0] as f32, config.world.size[1] as f32],
+            &mut SimRng::from_world(&config.world),
+        );
+        Self::with_agents(gpu, config, agent_manager)
+    }
+
+    /// Seed the field from `config` but start from an imported population
+    /// (`--init-agents`) instead of spawning one
+    ///
+    /// Fails if the population does not fit the world or the slots of
+    /// `config` (see `AgentPopulation::fit_capacity`).
+    pub fn with_population(gpu: &GpuDevice, config: SimulationConfig, population: &AgentPopulation) -> Result<Self, String> {
+        population.check_compatible(&config)?;
+        let agent_manager = population.manager(&config.agents);
+        Ok(Self::with_agents(gpu, config, agent_manager))
+    }
+
+    fn with_agents(gpu: &GpuDevice, config: SimulationConfig, mut agent_manager: AgentManager) -> Self {
+        let mut field_manager = FieldManager::new(config.world.size);
+        field_manager.seed_resources_with(&mut SimRng::from_world(&config.world));
+
+        // Walls are fixed for the run; nothing may spawn inside them
+        let obstacles = ObstacleMap::new(&gpu.device, &gpu.queue, &config);
+        obstacles.field.push_out(&mut agent_manager.agents);
+        let terrain = TerrainMap::new(&gpu.device, &gpu.queue, &config);
+        let diffusion = DiffusionMap::new(&gpu.device, &gpu.queue, &config);
+
+        let layouts = Layouts::new(&gpu.device);
+        let pipelines = ComputePipelines::new(&gpu.device, &layouts);
+
+        let rd_params = RDParams::from(&config);
+        let agent_params = AgentParams::from(&config);
+
+        let rd_params_buffer = gpu.create_rd_params_buffer(&rd_params);
+        let agent_params_buffer = gpu.create_agent_params_buffer(&agent_params);
+        let time_buffer = gpu.create_time_buffer(&TimeUniform::new(&config.world, 0));
+        let occupancy_buffer = gpu.create_occupancy_buffer(config.world.size);
+        let blooms = BloomInjector::new(&gpu.device, &layouts, &config);
+        let occupancy_clear = OccupancyClear::new(&gpu.device, &layouts, &occupancy_buffer, config.world.size);
+        let events = EventCounters::new(&gpu.device, config.world.size, config.output.event_window > 0);
+        let energy_probe = EnergyProbe::new(&gpu.device);
+        let occupancy_total = config.output.occupancy_total
+            .then(|| OccupancyTotal::new(&gpu.device, &layouts, &occupancy_buffer, config.world.size));
+
+        let field_sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("field_sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            compare: None,
+            ..Default::default()
+        });
+
+        let rd_inputs = RdBindInputs {
+            rd_params: &rd_params_buffer,
+            occupancy: &occupancy_buffer,
+            sigma_boost: blooms.boost_buffer(),
+            obstacles: obstacles.view(),
+            elevation: terrain.view(),
+            diffusion: diffusion.view(),
+            time: &time_buffer,
+        };
+        let field = FieldPingPong::new(&gpu.device, config.world.size, &layouts, &rd_inputs, &field_sampler);
+        // The mirror is sized from the same config as the textures
+        if let Err(e) = field.upload_field_data(&gpu.queue, &field_manager) {
+            log::error!("initial field upload failed: {}", e);
+        }
+
+        let utility = UtilityMap::new(&gpu.device, &layouts, field.a_sample_view(), field.b_sample_view(), config.world.size);
+        let resource_mips = ResourceMips::new(&gpu.device, &layouts, field.a_sample_view(), field.b_sample_view(), config.world.size);
+        let cell_gather = CellGather::new(&gpu.device, &layouts, field.a_sample_view(), field.b_sample_view(),
+            config.world.size, config.output.sampling.max_cells);
+        let field_image = FieldImage::new(&gpu.device, &layouts, field.a_sample_view(), field.b_sample_view(),
+            &occupancy_buffer, config.world.size);
+        let perturbation = ParamPerturbation::new(&config);
+        let forcing = ParamForcing::new(&config);
+
+        let mut sim = Self {
+            config,
+            layouts,
+            pipelines,
+            field,
+            field_manager,
+            agent_manager,
+            blooms,
+            obstacles,
+            terrain,
+            diffusion,
+            cohorts: CohortTracker::new(),
+            perturbation,
+            forcing,
+            skip: Vec::new(),
+            rd_params,
+            agent_params,
+            rd_params_buffer,
+            agent_params_buffer,
+            time_buffer,
+            occupancy_buffer,
+            occupancy_clear,
+            occupancy_total,
+            events,
+            energy_probe,
+            utility,
+            resource_mips,
+            cell_gather,
+            field_image,
+            field_sampler,
+            agents: None,
+            growth_events: Vec::new(),
+            step: 0,
+        };
+        sim.agents = sim.create_agent_passes(gpu);
+        sim
+    }
+
+    /// Agent buffer and passes for the current `agent_manager` (`None` without slots)
+    fn create_agent_passes(&self, gpu: &GpuDevice) -> Option<AgentPasses> {
+        let agent_manager = &self.agent_manager;
+        if agent_manager.agents.is_empty() {
+            return None;
+        }
+        let buffer = gpu.create_agents_buffer(&agent_manager.agents);
+        let ranges = AgentRanges::new(&gpu.device, &agent_manager.layout);
+        let steering = Steering::new(&gpu.device, &self.layouts, &self.config, &buffer, agent_manager.agents.len() as u32);
+        let foraging = ForagingTally::new(&gpu.device, &agent_manager.forage);
+        let inputs = AgentBindInputs {
+            agents: &buffer,
+            agent_params: &self.agent_params_buffer,
+            occupancy: &self.occupancy_buffer,
+            obstacles: self.obstacles.view(),
+            elevation: self.terrain.view(),
+            time: &self.time_buffer,
+            ranges: &ranges,
+            steering: &steering,
+            foraging: &foraging,
+            events: &self.events,
+            energy_probe: &self.energy_probe,
+        };
+        let a_bg = inputs.bind_group(&gpu.device, &self.layouts, "agent_a_bg", self.field.a_sample_view());
+        let b_bg = inputs.bind_group(&gpu.device, &self.layouts, "agent_b_bg", self.field.b_sample_view());
+        let movement = MovementMetrics::new(
+            &gpu.device,
+            &self.layouts,
+            &buffer,
+            agent_manager.agents.len() as u32,
+            self.field.a_sample_view(),
+            self.field.b_sample_view(),
+            self.config.world.size,
+        );
+        let culler = AgentCuller::new(&gpu.device, &self.layouts, &buffer, agent_manager.agents.len() as u32);
+        let demography = self.config.demography.enabled.then(|| {
+            DemographyPass::new(&gpu.device, &self.layouts, &self.config, &buffer, &ranges, &self.events)
+        });
+        let separation = self.config.separation.enabled.then(|| {
+            Separation::new(&gpu.device, &self.layouts, &self.config, &buffer, agent_manager.agents.len() as u32)
+        });
+        Some(AgentPasses { buffer, ranges, movement, culler, demography, separation, steering, foraging, a_bg, b_bg })
+    }
+
+    /// Number of completed steps
+    pub fn current_step(&self) -> u32 {
+        self.step
+    }
+
+    /// Clock of the next step, including the day/night light level
+    pub fn time(&self) -> TimeUniform {
+        TimeUniform::new(&self.config.world, self.step)
+    }
+
+    /// Number of agent slots on the GPU (alive or dead)
+    pub fn agent_count(&self) -> u32 {
+        self.agent_manager.agents.len() as u32
+    }
+
+    /// Occupancy buffer written by the agent pass and read by the RD pass
+    pub fn occupancy_buffer(&self) -> &Buffer {
+        &self.occupancy_buffer
+    }
+
+    /// Agent storage buffer, e.g. for drawing the agents without a readback
+    /// (`None` when the world has no agent slots)
+    pub fn agents_buffer(&self) -> Option<&Buffer> {
+        self.agents.as_ref().map(|a| &a.buffer)
+    }
+
+    /// Linear sampler for drawing the front field texture
+    pub fn field_sampler(&self) -> &Sampler {
+        &self.field_sampler
+    }
+
+    /// Advance the world by one step: clear occupancy → neighbour grid → separation → agents → demography → occupancy totals → blooms → reaction-diffusion → swap
+    pub fn step(&mut self, gpu: &GpuDevice) {
+        // Cohort tagging events scheduled for this step (needs a readback + upload)
+        if self.config.cohorts.iter().any(|e| e.step == self.step) {
+            self.sync_agents(gpu);
+            for event in self.config.cohorts.iter().filter(|e| e.step == self.step) {
+                self.cohorts.apply(&mut self.agent_manager.agents, event);
+            }
+            self.upload_agents(gpu);
+        }
+
+        // Scheduled, forced and perturbed params replace the uniforms (including caller tweaks) every step
+        let scheduled = self.config.apply_schedules(self.step);
+        if let Some(forcing) = &self.forcing {
+            forcing.apply(&mut self.config, self.step);
+        }
+        if let Some(perturbation) = &self.perturbation {
+            perturbation.apply(&mut self.config, self.step);
+        }
+        if scheduled || self.forcing.is_some() || self.perturbation.is_some() {
+            self.rd_params = RDParams::from(&self.config);
+            self.agent_params = AgentParams::from(&self.config);
+        }
+
+        // Refresh uniforms so callers can tweak params between steps
+        gpu.queue.write_buffer(&self.rd_params_buffer, 0, bytemuck::bytes_of(&self.rd_params));
+        gpu.queue.write_buffer(&self.agent_params_buffer, 0, bytemuck::bytes_of(&self.agent_params));
+        gpu.queue.write_buffer(&self.time_buffer, 0, bytemuck::bytes_of(&self.time()));
+
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("simulation_step"),
+        });
+
+        // Zero occupancy before agents deposit into it
+        self.occupancy_clear.encode(&mut encoder, &self.pipelines);
+
+        let run = |pass| !self.skip.contains(&pass);
+        let (steering, agent_pass, demography, blooms, rd) =
+            (run(StepPass::Steering), run(StepPass::Agents), run(StepPass::Demography), run(StepPass::Blooms), run(StepPass::Rd));
+
+        if let Some(agents) = self.agents.as_mut() {
+            // Neighbour counts and headings before anyone moves (crowding and flocking only)
+            if steering {
+                agents.steering.fill(&gpu.device, &mut encoder, &self.pipelines);
+            }
+
+            // Overlapping agents push apart before they move (separation.enabled)
+            if let Some(separation) = agents.separation.as_ref().filter(|_| agent_pass) {
+                separation.apply(&gpu.device, &gpu.queue, &mut encoder, &self.pipelines);
+            }
+
+            // Agents pass -> occupancy
+            if agent_pass {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("agent pass"),
+                    timestamp_writes: None,
+                });
+                cpass.set_pipeline(&self.pipelines.agent_pipeline);
+                let bind_group = if self.field.front_is_a() { &agents.a_bg } else { &agents.b_bg };
+                // One dispatch per species, sized by its capacity
+                for (offset, workgroups) in agents.ranges.dispatches() {
+                    cpass.set_bind_group(0, bind_group, &[offset]);
+                    cpass.dispatch_workgroups(workgroups, 1, 1);
+                }
+            }
+
+            // Random deaths and births (demography.enabled)
+            if let Some(demography) = agents.demography.as_mut().filter(|_| demography) {
+                demography.apply(&gpu.device, &gpu.queue, &mut encoder, &self.pipelines, &agents.buffer, self.step);
+            }
+        }
+
+        // Run-long occupancy totals (output.occupancy_total)
+        if let Some(total) = &self.occupancy_total {
+            total.encode(&gpu.device, &mut encoder, &self.pipelines);
+        }
+
+        // Bloom injection: refresh the sigma_R boost map when blooms start or expire or rain cells move
+        if blooms {
+            self.blooms.update(&gpu.queue, &mut encoder, &self.pipelines, self.step);
+        }
+
+        // RD pass reads occupancy and the front field, writes the back field
+        if rd {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("rd pass"),
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(&self.pipelines.rd_pipeline);
+            cpass.set_bind_group(0, self.field.rd_bind_group(), &[]);
+
+            let gx = self.config.world.size[0].div_ceil(8);
+            let gy = self.config.world.size[1].div_ceil(8);
+            cpass.dispatch_workgroups(gx, gy, 1);
+        }
+
+        gpu.submit(encoder.finish());
+
+        if rd {
+            self.field.swap();
+        }
+        self.step += 1;
+
+        let growth = &self.config.agents.growth;
+        if growth.policy == GrowthPolicy::Grow && self.step.is_multiple_of(growth.check_every) {
+            self.grow_agents(gpu);
+        }
+    }
+
+    /// Read the agents back and move them into a larger buffer if a range
+    /// filled past `agents.growth.fill` (see `GrowthConfig::grown_slots`)
+    fn grow_agents(&mut self, gpu: &GpuDevice) {
+        if self.agents.is_none() {
+            return;
+        }
+        self.sync_agents(gpu);
+        let from = self.agent_manager.layout.clone();
+        let alive: [u32; 3] = std::array::from_fn(|kind| {
+            let range = from.range(kind as u32);
+            self.agent_manager.agents[range.start as usize..range.end as usize].iter().filter(|a| a.is_alive()).count() as u32
+        });
+        let slots: [u32; 3] = std::array::from_fn(|kind| from.capacity(kind as u32));
+        let grown = self.config.agents.growth.grown_slots(slots, alive);
+        if grown == slots {
+            return;
+        }
+
+        let to = AgentLayout::new(grown);
+        log::info!("Agent buffer grew at step {}: slots {:?} -> {:?} (alive {:?})", self.step, slots, grown, alive);
+        self.agent_manager.agents = from.relayout(&self.agent_manager.agents, &to, Agent::free);
+        self.agent_manager.forage = from.relayout(&self.agent_manager.forage, &to, |_| [0.0; 2]);
+        if let Some(slot) = self.energy_probe.selected() {
+            self.energy_probe.follow(&gpu.queue, from.remap(slot, &to));
+        }
+        self.agent_manager.layout = to.clone();
+        self.config.agents.capacity = SpeciesCapacity { plants: grown[0], herbivores: grown[1], predators: grown[2] };
+        self.agents = self.create_agent_passes(gpu);
+        self.growth_events.push(GrowthEvent { step: self.step, alive, from, to });
+    }
+
+    /// Every reallocation of the agent buffer so far, oldest first
+    pub fn growth_events(&self) -> &[GrowthEvent] {
+        &self.growth_events
+    }
+
+    /// Download the front field into `field_manager`
+    ///
+    /// A failed readback (e.g. on a lost device) is logged and leaves the
+    /// previous mirror in place.
+    pub fn sync_field(&mut self, gpu: &GpuDevice) {
+        if let Err(e) = self.field.download_field_data(&gpu.device, &gpu.queue, &mut self.field_manager) {
+            log::warn!("field readback failed, keeping the previous field: {}", e);
+        }
+    }
+
+    /// Download the agent buffer and foraging tally into `agent_manager`
+    pub fn sync_agents(&mut self, gpu: &GpuDevice) {
+        let Some(agents) = &self.agents else { return };
+        let size = std::mem::size_of_val(self.agent_manager.agents.as_slice()) as u64;
+        self.agent_manager.agents = gpu.read_buffer::<Agent>(&agents.buffer, size);
+        self.agent_manager.forage = agents.foraging.read(&gpu.device, &gpu.queue);
+    }
+
+    /// Download the current field and agents as a `StateSnapshot`
+    pub fn snapshot(&mut self, gpu: &GpuDevice) -> StateSnapshot {
+        self.sync_field(gpu);
+        self.sync_agents(gpu);
+        StateSnapshot {
+            step: self.step,
+            size: self.config.world.size,
+            field: self.field_manager.cells(),
+            agents: self.agent_manager.agents.clone(),
+        }
+    }
+
+    /// Download the current state and hash it (`StateSnapshot::state_hash`)
+    pub fn state_hash(&mut self, gpu: &GpuDevice) -> u64 {
+        self.snapshot(gpu).state_hash()
+    }
+
+    /// Replace the field, agents and step counter with a saved state
+    ///
+    /// Cohort bookkeeping and the foraging tally restart (tags on the agents are
+    /// kept) and the bloom schedule continues from its current state.
+    pub fn restore(&mut self, gpu: &GpuDevice, snapshot: &StateSnapshot) -> Result<(), String> {
+        snapshot.check_compatible(&self.config)?;
+        self.field_manager.set_cells(&snapshot.field);
+        self.field.upload_field_data(&gpu.queue, &self.field_manager)?;
+        self.agent_manager.agents.copy_from_slice(&snapshot.agents);
+        self.upload_agents(gpu);
+        self.agent_manager.forage.fill([0.0; 2]);
+        self.agent_manager.stats.consumed = 0.0;
+        self.agent_manager.stats.distance = 0.0;
+        if let Some(agents) = &self.agents {
+            agents.foraging.write(&gpu.queue, &self.agent_manager.forage);
+        }
+        self.cohorts = CohortTracker::new();
+        self.energy_probe.select(&gpu.queue, None);
+        self.step = snapshot.step;
+        Ok(())
+    }
+
+    /// Upload `agent_manager` to the GPU agent buffer (after CPU-side edits such as tagging)
+    pub fn upload_agents(&self, gpu: &GpuDevice) {
+        if let Some(agents) = &self.agents {
+            gpu.queue.write_buffer(&agents.buffer, 0, bytemuck::cast_slice(&self.agent_manager.agents));
+        }
+    }
+
+    /// Velocity divergence, up-gradient flux and ∇R alignment of the current agents
+    /// (all zero without agents)
+    pub fn movement_stats(&self, gpu: &GpuDevice) -> MovementStats {
+        self.agents.as_ref().map_or_else(MovementStats::default, |a| {
+            a.movement.measure(&gpu.device, &gpu.queue, &self.pipelines, self.field.front_is_a())
+        })
+    }
+
+    /// Perceived utility chi_R·S(R) − chi_W·S(W) of every cell under the live
+    /// `agent_params`, row-major like the field
+    pub fn utility_map(&self, gpu: &GpuDevice) -> Vec<f32> {
+        self.utility.compute(&gpu.device, &gpu.queue, &self.pipelines, self.field.front_is_a(), &self.agent_params)
+    }
+
+    /// Resource channel box-averaged over 2^`level` × 2^`level` blocks,
+    /// row-major, with the size of that level
+    ///
+    /// Built on the GPU from the current front field; only the requested level
+    /// is read back. Levels run from 0 (full resolution) to
+    /// `resource_mip_levels() - 1` (one cell, the mean R).
+    pub fn resource_mip(&self, gpu: &GpuDevice, level: usize) -> ([u32; 2], Vec<f32>) {
+        self.resource_mips.generate(&gpu.device, &gpu.queue, &self.pipelines, self.field.front_is_a());
+        (self.resource_mips.level_size(level), self.resource_mips.read_level(&gpu.device, &gpu.queue, level))
+    }
+
+    /// Number of levels `resource_mip` accepts
+    pub fn resource_mip_levels(&self) -> usize {
+        self.resource_mips.level_count()
+    }
+
+    /// Field stats estimated from an adaptive quadtree sample of the front
+    /// field (`output.sampling`), without syncing `field_manager`
+    ///
+    /// The sampled cells depend only on the world seed and the current step,
+    /// so reruns read the same cells.
+    pub fn sample_field(&self, gpu: &GpuDevice) -> SampledField {
+        let mut rng = sampling_rng(&self.config.world, self.step);
+        sample_field(self.config.world.size, &self.config.output.sampling, &mut rng, |cells| {
+            self.cell_gather.gather(&gpu.device, &gpu.queue, &self.pipelines, self.field.front_is_a(), cells)
+        })
+    }
+
+    /// Readings of `stations` from the front field and `agent_manager`, in order
+    ///
+    /// Only the station cells are read from the field; sync the agents first
+    /// for current occupancy.
+    pub fn read_stations(&self, gpu: &GpuDevice, stations: &[&StationConfig]) -> Vec<StationSample> {
+        let cells: Vec<[u32; 2]> = stations.iter().map(|s| s.cell()).collect();
+        let samples = self.cell_gather.gather(&gpu.device, &gpu.queue, &self.pipelines, self.field.front_is_a(), &cells);
+        stations.iter().zip(samples)
+            .map(|(station, cell)| StationSample::read(station, cell, &self.agent_manager.agents))
+            .collect()
+    }
+
+    /// RGBA8 image of the front field or of the last step's occupancy,
+    /// row-major like the field
+    ///
+    /// Colour-mapped on the GPU, so `field_manager` need not be synced first.
+    pub fn render_image(&self, gpu: &GpuDevice, kind: ImageKind) -> Vec<u8> {
+        self.field_image.render(&gpu.device, &gpu.queue, &self.pipelines, self.field.front_is_a(), kind)
+    }
+
+    /// Kill every agent inside `region` (takes effect before the next step)
+    pub fn cull(&self, gpu: &GpuDevice, region: CullRegion) {
+        let Some(agents) = &self.agents else { return };
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("cull"),
+        });
+        agents.culler.cull(&gpu.device, &gpu.queue, &mut encoder, &self.pipelines, region);
+        gpu.submit(encoder.finish());
+    }
+
+    /// Draw or erase a disk of wall (takes effect from the next step)
+    ///
+    /// A world that started without obstacles turns them on with the
+    /// configured avoidance, so the first stroke already closes RD faces and
+    /// steers agents. Painted walls are not part of a `StateSnapshot`; a world
+    /// rebuilt from `config` only has `config.obstacles.shapes`.
+    pub fn paint_obstacles(&mut self, gpu: &GpuDevice, brush: ObstacleBrush) {
+        self.obstacles.paint(&gpu.device, &gpu.queue, &self.layouts, &self.pipelines, brush);
+        let o = &mut self.config.obstacles;
+        if !o.enabled {
+            o.enabled = true;
+            self.rd_params.obstacles = 1;
+            self.agent_params.avoid_distance = o.avoid_distance;
+            self.agent_params.avoid_strength = o.avoid_strength;
+        }
+    }
+
+    /// Make one recorded viewer interaction (takes effect from the next step)
+    pub fn apply_interaction(&mut self, gpu: &GpuDevice, action: &Interaction) -> Result<(), String> {
+        match action {
+            Interaction::Param { param, value } => {
+                let slot = self.config.scalar_mut(param).ok_or_else(|| format!("`{}` cannot be set", param))?;
+                *slot = *value;
+                self.rd_params = RDParams::from(&self.config);
+                self.agent_params = AgentParams::from(&self.config);
+            }
+            &Interaction::Wall { center, radius, erase } => self.paint_obstacles(gpu, ObstacleBrush { center, radius, erase }),
+            Interaction::Cull { region } => self.cull(gpu, *region),
+            Interaction::Tag { id, center, radius } => {
+                self.sync_agents(gpu);
+                self.cohorts.tag_within(&mut self.agent_manager.agents, *id, self.step, *center, *radius, None);
+                self.upload_agents(gpu);
+            }
+            Interaction::Spawn { species, center, radius, count } => {
+                let kind = SPECIES.iter().position(|s| s == species).ok_or_else(|| format!("unknown species `{}`", species))? as u32;
+                let mut rng = SimRng::new(self.config.world.rng, self.config.world.seed ^ SPAWN_SEED_SALT ^ (self.step as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+                self.sync_agents(gpu);
+                let spawned = self.agent_manager.spawn_within(&self.config, kind, *center, *radius, *count, &mut rng);
+                if spawned < *count {
+                    log::warn!("step {}: only {} of {} {} fit their free slots", self.step, spawned, count, species);
+                }
+                self.upload_agents(gpu);
+            }
+            &Interaction::Inject { center, radius, amount } => {
+                self.sync_field(gpu);
+                let size = self.config.world.size;
+                let lo = |c: f32| (c - radius).floor().max(0.0) as u32;
+                let hi = |c: f32, n: u32| ((c + radius).ceil().max(0.0) as u32).min(n);
+                for y in lo(center[1])..hi(center[1], size[1]) {
+                    for x in lo(center[0])..hi(center[0], size[0]) {
+                        let (dx, dy) = (x as f32 + 0.5 - center[0], y as f32 + 0.5 - center[1]);
+                        if dx * dx + dy * dy <= radius * radius {
+                            let index = self.field_manager.get_index(x, y);
+                            let r = self.field_manager.channel(index, FieldChannel::Resource);
+                            self.field_manager.set_channel(index, FieldChannel::Resource, (r + amount).max(0.0));
+                        }
+                    }
+                }
+                self.field.upload_field_data(&gpu.queue, &self.field_manager)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Births and deaths per cell since the last `clear_events` (`None` without `output.event_window`)
+    pub fn read_events(&self, gpu: &GpuDevice) -> Option<EventMaps> {
+        (self.config.output.event_window > 0).then(|| self.events.read(&gpu.device, &gpu.queue))
+    }
+
+    /// Zero the event counters to start a new window
+    pub fn clear_events(&self, gpu: &GpuDevice) {
+        self.events.clear(&gpu.queue);
+    }
+
+    /// Replace the event counters, e.g. with those read alongside a checkpoint
+    pub fn restore_events(&self, gpu: &GpuDevice, maps: &EventMaps) {
+        self.events.write(&gpu.queue, maps);
+    }
+
+    /// Log the energy budget of the agent in `slot` from the next step on
+    /// (`None` stops logging); the previous log is dropped
+    pub fn select_energy_probe(&mut self, gpu: &GpuDevice, slot: Option<u32>) {
+        self.energy_probe.select(&gpu.queue, slot.filter(|&s| s < self.agent_count()));
+    }
+
+    /// Slot whose energy budget is being logged
+    pub fn energy_probe_slot(&self) -> Option<u32> {
+        self.energy_probe.selected()
+    }
+
+    /// The selected agent's logged steps, oldest first (at most `EnergyProbe::STEPS`)
+    pub fn read_energy_probe(&self, gpu: &GpuDevice) -> Vec<EnergyRecord> {
+        self.energy_probe.read(&gpu.device, &gpu.queue)
+    }
+
+    /// Read back the occupancy summed over every step so far (`None` without `output.occupancy_total`)
+    pub fn read_occupancy_total(&self, gpu: &GpuDevice) -> Option<Vec<u32>> {
+        self.occupancy_total.as_ref().map(|t| t.read(&gpu.device, &gpu.queue))
+    }
+
+    /// Replace the occupancy totals, e.g. with those read alongside a checkpoint
+    pub fn restore_occupancy_total(&self, gpu: &GpuDevice, totals: &[u32]) {
+        if let Some(t) = &self.occupancy_total {
+            t.write(&gpu.queue, totals);
+        }
+    }
+
+    /// Read back the occupancy counts produced by the last agent pass
+    pub fn read_occupancy(&self, gpu: &GpuDevice) -> Vec<u32> {
+        let cells = (self.config.world.size[0] * self.config.world.size[1]) as u64;
+        gpu.read_buffer::<u32>(&self.occupancy_buffer, cells * 4)
+    }
+}
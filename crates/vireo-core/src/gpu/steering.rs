@@ -0,0 +1,104 @@
+use wgpu::{BindGroup, Buffer, CommandEncoder, Device, util::DeviceExt};
+use vireo_params::SteeringParams;
+use crate::SimulationConfig;
+use crate::gpu::ComputePipelines;
+use crate::gpu::layouts::Layouts;
+
+/// Threads per workgroup of neighbour_grid.wgsl
+const WORKGROUP_SIZE: u32 = 128;
+
+/// Per-species steering weights and the neighbour grid they may read
+///
+/// When some species crowds, flocks or follows a density, `fill` runs before
+/// the agent pass and sums every alive agent into a fixed-point (count, vx, vy)
+/// grid with a count per species alongside, so the agent pass reads the
+/// neighbourhood as it was at the start of the step rather than as other
+/// threads rewrite it. Integer atomics keep the sums independent of thread
+/// order. Without any of these terms the grid is a single unused cell and
+/// `fill` encodes nothing.
+pub struct Steering {
+    params: SteeringParams,
+    params_buffer: Buffer,
+    grid: Buffer,
+    bind_group: BindGroup,
+    agent_groups: u32,
+}
+
+impl Steering {
+    pub fn new(device: &Device, layouts: &Layouts, config: &SimulationConfig, agents_buffer: &Buffer, agent_count: u32) -> Self {
+        let params = SteeringParams::new(config);
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("steering_params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let cells = if params.neighbours == 1 { config.world.size[0] as u64 * config.world.size[1] as u64 } else { 1 };
+        let grid = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("neighbour_grid"),
+            size: cells * 24, // (n, vx, vy, plants, herbivores, predators) per cell
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("neighbour_bg"),
+            layout: &layouts.neighbour,
+            entries: &[
+                // @binding(0) agents storage buffer
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: agents_buffer.as_entire_binding(),
+                },
+                // @binding(1) neighbour grid
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: grid.as_entire_binding(),
+                },
+                // @binding(2) SteeringParams uniform
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: params_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            params,
+            params_buffer,
+            grid,
+            bind_group,
+            agent_groups: agent_count.div_ceil(WORKGROUP_SIZE),
+        }
+    }
+
+    /// Weights uploaded to the agent pass
+    pub fn params(&self) -> &SteeringParams {
+        &self.params
+    }
+
+    /// `SteeringParams` uniform for the agent pass bind groups
+    pub fn params_buffer(&self) -> &Buffer {
+        &self.params_buffer
+    }
+
+    /// Neighbour grid for the agent pass bind groups (read-only there)
+    pub fn grid(&self) -> &Buffer {
+        &self.grid
+    }
+
+    /// Encode the grid rebuild from the current agents (nothing without crowding, flocking or attraction)
+    pub fn fill(&self, device: &Device, encoder: &mut CommandEncoder, pipelines: &ComputePipelines) {
+        if self.params.neighbours == 0 {
+            return;
+        }
+        encoder.clear_buffer(&self.grid, 0, None);
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("neighbour grid pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(pipelines.neighbour_pipeline.get(device));
+        cpass.set_bind_group(0, &self.bind_group, &[]);
+        cpass.dispatch_workgroups(self.agent_groups, 1, 1);
+    }
+}
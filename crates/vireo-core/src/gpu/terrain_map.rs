@@ -0,0 +1,56 @@
+use wgpu::{Device, Queue, Texture, TextureView};
+use crate::SimulationConfig;
+use crate::gpu::layouts::Layouts;
+use crate::sim::ElevationField;
+
+/// Elevation field on the GPU, read by the agent and RD passes at binding 6
+///
+/// Terrain is static, so the texture is filled once from `field`. Without
+/// terrain every texel is 0 and the uniforms turn both passes' terms off.
+pub struct TerrainMap {
+    pub field: ElevationField,
+    _texture: Texture,
+    view: TextureView,
+}
+
+impl TerrainMap {
+    pub fn new(device: &Device, queue: &Queue, config: &SimulationConfig) -> Self {
+        let field = ElevationField::new(&config.terrain, config.world.size);
+        let [width, height] = config.world.size;
+        let extent = wgpu::Extent3d { width, height, depth_or_array_layers: 1 };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("elevation"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Layouts::ELEVATION_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytemuck::cast_slice(field.heights()),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4), // f32 per cell
+                rows_per_image: Some(height),
+            },
+            extent,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self { field, _texture: texture, view }
+    }
+
+    /// Elevation texture view for the agent and RD bind groups
+    pub fn view(&self) -> &TextureView {
+        &self.view
+    }
+}
@@ -1,7 +1,20 @@
+use half::f16;
 use wgpu::{Device, Queue, Texture, TextureView, TextureViewDescriptor, BindGroup};
 use crate::sim::FieldManager;
 use crate::gpu::layouts::Layouts;
 
+/// Resources the RD pass binds besides the two field views (see `Layouts::rd`)
+#[derive(Clone, Copy)]
+pub struct RdBindInputs<'a> {
+    pub rd_params: &'a wgpu::Buffer,
+    pub occupancy: &'a wgpu::Buffer,
+    pub sigma_boost: &'a wgpu::Buffer, // Bloom multiplier on sigma_R per cell
+    pub obstacles: &'a TextureView,    // Obstacle signed distance field
+    pub elevation: &'a TextureView,
+    pub diffusion: &'a TextureView,    // Diffusion tensor per cell
+    pub time: &'a wgpu::Buffer,
+}
+
 /// Centralized ping-pong struct that owns textures, views, and bind groups
 pub struct FieldPingPong {
     // textures + views
@@ -35,176 +48,24 @@ impl FieldPingPong {
         device: &Device, 
         size: [u32; 2],
         layouts: &Layouts,
-        rd_params_buffer: &wgpu::Buffer,
-        occupancy_buffer: &wgpu::Buffer,
+        inputs: &RdBindInputs,
         sampler: &wgpu::Sampler,
     ) -> Self {
-        let format = wgpu::TextureFormat::Rgba16Float;
-        let usage = wgpu::TextureUsages::TEXTURE_BINDING
-            | wgpu::TextureUsages::STORAGE_BINDING
-            | wgpu::TextureUsages::COPY_DST
-            | wgpu::TextureUsages::COPY_SRC;
-        
-        let extent = wgpu::Extent3d {
-            width: size[0],
-            height: size[1],
-            depth_or_array_layers: 1,
-        };
-        
-        let tex_a = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("field_a"),
-            size: extent,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format,
-            usage,
-            view_formats: &[],
-        });
-        
-        let tex_b = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("field_b"),
-            size: extent,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format,
-            usage,
-            view_formats: &[],
-        });
-        
-        let view_a_sample = tex_a.create_view(&TextureViewDescriptor {
-            label: Some("field_a_sample"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: Some(1),
-            base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
-        let view_b_sample = tex_b.create_view(&TextureViewDescriptor {
-            label: Some("field_b_sample"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: Some(1),
-            base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
+        let tex_a = Self::create_texture(device, size, "field_a");
+        let tex_b = Self::create_texture(device, size, "field_b");
         
-        let view_a_store = tex_a.create_view(&TextureViewDescriptor {
-            label: Some("field_a_store"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: Some(1),
-            base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
-        
-        let view_b_store = tex_b.create_view(&TextureViewDescriptor {
-            label: Some("field_b_store"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: Some(1),
-            base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
+        let view_a_sample = Self::create_view(&tex_a, "field_a_sample");
+        let view_b_sample = Self::create_view(&tex_b, "field_b_sample");
+        let view_a_store = Self::create_view(&tex_a, "field_a_store");
+        let view_b_store = Self::create_view(&tex_b, "field_b_store");
 
         // Create bind groups for RD compute (A→B and B→A) using borrowed layouts
-        let rd_a2b_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("rd_a2b_bg"),
-            layout: &layouts.rd, // borrow the layout
-            entries: &[
-                // @binding(0) src A (sampled)
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view_a_sample),
-                },
-                // @binding(1) dst B (storage)
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(&view_b_store),
-                },
-                // @binding(2) RDParams uniform
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Buffer(rd_params_buffer.as_entire_buffer_binding()),
-                },
-                // @binding(3) occupancy buffer
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: wgpu::BindingResource::Buffer(occupancy_buffer.as_entire_buffer_binding()),
-                },
-            ],
-        });
-
-        let rd_b2a_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("rd_b2a_bg"),
-            layout: &layouts.rd, // borrow the layout
-            entries: &[
-                // @binding(0) src B (sampled)
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view_b_sample),
-                },
-                // @binding(1) dst A (storage)
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(&view_a_store),
-                },
-                // @binding(2) RDParams uniform
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Buffer(rd_params_buffer.as_entire_buffer_binding()),
-                },
-                // @binding(3) occupancy buffer
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: wgpu::BindingResource::Buffer(occupancy_buffer.as_entire_buffer_binding()),
-                },
-            ],
-        });
+        let rd_a2b_bg = Self::create_rd_bind_group(device, layouts, "rd_a2b_bg", (&view_a_sample, &view_b_store), inputs);
+        let rd_b2a_bg = Self::create_rd_bind_group(device, layouts, "rd_b2a_bg", (&view_b_sample, &view_a_store), inputs);
 
         // Create bind groups for rendering (show A and show B) using borrowed layouts
-        let show_a_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("show_a_bg"),
-            layout: &layouts.field_render, // borrow the field render layout
-            entries: &[
-                // @binding(0) field A texture
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view_a_sample),
-                },
-                // @binding(1) sampler
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(sampler),
-                },
-            ],
-        });
-
-        let show_b_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("show_b_bg"),
-            layout: &layouts.field_render, // borrow the field render layout
-            entries: &[
-                // @binding(1) field B texture
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view_b_sample),
-                },
-                // @binding(1) sampler
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(sampler),
-                },
-            ],
-        });
+        let show_a_bg = Self::create_show_bind_group(device, layouts, "show_a_bg", &view_a_sample, sampler);
+        let show_b_bg = Self::create_show_bind_group(device, layouts, "show_b_bg", &view_b_sample, sampler);
 
         Self {
             tex_a,
@@ -228,167 +89,134 @@ impl FieldPingPong {
         &mut self,
         device: &Device,
         layouts: &Layouts,
-        rd_params_buffer: &wgpu::Buffer,
-        occupancy_buffer: &wgpu::Buffer,
+        inputs: &RdBindInputs,
         sampler: &wgpu::Sampler,
     ) {
-        let format = wgpu::TextureFormat::Rgba16Float;
-        let usage = wgpu::TextureUsages::TEXTURE_BINDING
-            | wgpu::TextureUsages::STORAGE_BINDING
-            | wgpu::TextureUsages::COPY_DST
-            | wgpu::TextureUsages::COPY_SRC;
-        
-        let extent = wgpu::Extent3d {
-            width: self.size[0],
-            height: self.size[1],
-            depth_or_array_layers: 1,
-        };
-        
-        // Recreate textures
-        self.tex_a = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("field_a"),
-            size: extent,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format,
-            usage,
-            view_formats: &[],
-        });
-        
-        self.tex_b = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("field_b"),
-            size: extent,
+        let front_is_a = self.front_is_a;
+        *self = Self::new(device, self.size, layouts, inputs, sampler);
+        self.front_is_a = front_is_a;
+    }
+    
+    /// Create one field texture with a layer per four registered channels
+    fn create_texture(device: &Device, size: [u32; 2], label: &str) -> Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth_or_array_layers: Layouts::FIELD_LAYERS,
+            },
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format,
-            usage,
+            format: Layouts::FIELD_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::STORAGE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
-        });
-        
-        // Recreate views
-        self.view_a_sample = self.tex_a.create_view(&TextureViewDescriptor {
-            label: Some("field_a_sample"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: Some(1),
-            base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
-        self.view_b_sample = self.tex_b.create_view(&TextureViewDescriptor {
-            label: Some("field_b_sample"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: Some(1),
-            base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
-        
-        self.view_a_store = self.tex_a.create_view(&TextureViewDescriptor {
-            label: Some("field_a_store"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: Some(1),
-            base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
-        
-        self.view_b_store = self.tex_b.create_view(&TextureViewDescriptor {
-            label: Some("field_b_store"),
-            format: Some(format),
-            dimension: Some(wgpu::TextureViewDimension::D2),
+        })
+    }
+    
+    /// Create an array view over every layer of a field texture
+    fn create_view(texture: &Texture, label: &str) -> TextureView {
+        texture.create_view(&TextureViewDescriptor {
+            label: Some(label),
+            format: Some(Layouts::FIELD_FORMAT),
+            dimension: Some(Layouts::FIELD_VIEW_DIMENSION),
             aspect: wgpu::TextureAspect::All,
             base_mip_level: 0,
             mip_level_count: Some(1),
             base_array_layer: 0,
-            array_layer_count: Some(1),
-        });
-
-        // Rebuild bind groups using borrowed layouts
-        self.rd_a2b_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("rd_a2b_bg"),
-            layout: &layouts.rd,
+            array_layer_count: Some(Layouts::FIELD_LAYERS),
+        })
+    }
+    
+    /// Create an RD bind group reading `src` and writing `dst`
+    fn create_rd_bind_group(
+        device: &Device,
+        layouts: &Layouts,
+        label: &str,
+        (src, dst): (&TextureView, &TextureView),
+        inputs: &RdBindInputs,
+    ) -> BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout: &layouts.rd, // borrow the layout
             entries: &[
+                // @binding(0) src (sampled)
                 wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&self.view_a_sample),
+                    resource: wgpu::BindingResource::TextureView(src),
                 },
+                // @binding(1) dst (storage)
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::TextureView(&self.view_b_store),
+                    resource: wgpu::BindingResource::TextureView(dst),
                 },
+                // @binding(2) RDParams uniform
                 wgpu::BindGroupEntry {
                     binding: 2,
-                    resource: wgpu::BindingResource::Buffer(rd_params_buffer.as_entire_buffer_binding()),
+                    resource: wgpu::BindingResource::Buffer(inputs.rd_params.as_entire_buffer_binding()),
                 },
+                // @binding(3) occupancy buffer
                 wgpu::BindGroupEntry {
                     binding: 3,
-                    resource: wgpu::BindingResource::Buffer(occupancy_buffer.as_entire_buffer_binding()),
-                },
-            ],
-        });
-
-        self.rd_b2a_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("rd_b2a_bg"),
-            layout: &layouts.rd,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&self.view_b_sample),
+                    resource: wgpu::BindingResource::Buffer(inputs.occupancy.as_entire_buffer_binding()),
                 },
+                // @binding(4) bloom sigma_R boost buffer
                 wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(&self.view_a_store),
+                    binding: 4,
+                    resource: wgpu::BindingResource::Buffer(inputs.sigma_boost.as_entire_buffer_binding()),
                 },
+                // @binding(5) obstacle signed distance field
                 wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Buffer(rd_params_buffer.as_entire_buffer_binding()),
+                    binding: 5,
+                    resource: wgpu::BindingResource::TextureView(inputs.obstacles),
                 },
+                // @binding(6) terrain elevation
                 wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: wgpu::BindingResource::Buffer(occupancy_buffer.as_entire_buffer_binding()),
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(inputs.elevation),
                 },
-            ],
-        });
-
-        self.show_a_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("show_a_bg"),
-            layout: &layouts.field_render,
-            entries: &[
+                // @binding(7) simulation clock
                 wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&self.view_a_sample),
+                    binding: 7,
+                    resource: wgpu::BindingResource::Buffer(inputs.time.as_entire_buffer_binding()),
                 },
+                // @binding(8) diffusion tensor
                 wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(sampler),
+                    binding: 8,
+                    resource: wgpu::BindingResource::TextureView(inputs.diffusion),
                 },
             ],
-        });
-
-        self.show_b_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("show_b_bg"),
-            layout: &layouts.field_render,
+        })
+    }
+    
+    /// Create a field render bind group showing `view`
+    fn create_show_bind_group(
+        device: &Device,
+        layouts: &Layouts,
+        label: &str,
+        view: &TextureView,
+        sampler: &wgpu::Sampler,
+    ) -> BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout: &layouts.field_render, // borrow the field render layout
             entries: &[
+                // @binding(0) field texture
                 wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&self.view_b_sample),
+                    resource: wgpu::BindingResource::TextureView(view),
                 },
+                // @binding(1) sampler
                 wgpu::BindGroupEntry {
                     binding: 1,
                     resource: wgpu::BindingResource::Sampler(sampler),
                 },
             ],
-        });
-        
-
+        })
     }
 
     /// Get the RD bind group for the current frame (read from front, write to back)
@@ -430,6 +258,18 @@ impl FieldPingPong {
         &self.view_a_sample
     }
     
+    /// Get the B sample view (for agent pass)
+    #[inline] 
+    pub fn b_sample_view(&self) -> &TextureView {
+        &self.view_b_sample
+    }
+    
+    /// Get the front texture (for copies and debug readbacks)
+    #[inline] 
+    pub fn front_texture(&self) -> &Texture {
+        if self.front_is_a { &self.tex_a } else { &self.tex_b }
+    }
+    
     /// Swap the ping-pong state (call this after RD pass, before render)
     #[inline] 
     pub fn swap(&mut self) { 
@@ -443,13 +283,18 @@ impl FieldPingPong {
     
 
     
-    /// Upload field data to texture A
-    pub fn upload_field_data(&self, queue: &Queue, field_manager: &FieldManager) {
+    /// Upload field data to every layer of the front texture
+    ///
+    /// The front is what the next RD and agent passes read, so re-uploads
+    /// after any number of swaps (reset, streaming) take effect immediately.
+    /// Fails, leaving the texture untouched, when the mirror is not the size
+    /// of the texture.
+    pub fn upload_field_data(&self, queue: &Queue, field_manager: &FieldManager) -> Result<(), String> {
         println!("FieldPingPong: Starting texture upload");
         println!("FieldPingPong: Field size: {:?}", self.size);
         
-        let data = field_manager.to_rgba16f();
-        println!("FieldPingPong: Converted {} RGBA16F values", data.len());
+        let data = field_manager.as_rgba16f();
+        println!("FieldPingPong: Uploading {} RGBA16F values", data.len());
         
         // Debug: check first few values
         if data.len() >= 4 {
@@ -457,44 +302,26 @@ impl FieldPingPong {
                 data[0].to_f32(), data[1].to_f32(), data[2].to_f32(), data[3].to_f32());
         }
         
-        // Convert f16 to bytes manually since bytemuck doesn't support half::f16
-        let mut bytes = Vec::with_capacity(data.len() * 2);
-        for &f in &data {
-            bytes.extend_from_slice(&f.to_le_bytes());
-        }
-        
-        // Calculate padded bytes per row (wgpu requires 256-byte alignment)
-        let bytes_per_row = self.size[0] * 8; // 4 channels × 2 bytes (f16)
-        let padded_bytes_per_row = ((bytes_per_row + 255) / 256) * 256;
-        
-        let mut padded_bytes = Vec::with_capacity((padded_bytes_per_row * self.size[1]) as usize);
-        for row in 0..self.size[1] {
-            let start = (row * bytes_per_row) as usize;
-            let end = start + bytes_per_row as usize;
-            padded_bytes.extend_from_slice(&bytes[start..end]);
-            
-            // Pad to alignment boundary
-            let padding = padded_bytes_per_row - bytes_per_row;
-            padded_bytes.extend(std::iter::repeat(0u8).take(padding as usize));
-        }
-        
+        // Layers are stacked row-wise in `data`; rows are padded to the copy alignment
+        let rows = TexelRows::new(self.size, Layouts::FIELD_LAYERS);
+        let padded_bytes = rows.pad(data)?;
         println!("FieldPingPong: Uploading {} bytes to texture", padded_bytes.len());
-        
+
         let layout = wgpu::ImageDataLayout {
             offset: 0,
-            bytes_per_row: Some(padded_bytes_per_row),
+            bytes_per_row: Some(rows.padded_bytes_per_row()),
             rows_per_image: Some(self.size[1]),
         };
         
         let size = wgpu::Extent3d {
             width: self.size[0],
             height: self.size[1],
-            depth_or_array_layers: 1,
+            depth_or_array_layers: Layouts::FIELD_LAYERS,
         };
         
         queue.write_texture(
             wgpu::ImageCopyTexture {
-                texture: &self.tex_a,
+                texture: self.front_texture(),
                 mip_level: 0,
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
@@ -505,15 +332,19 @@ impl FieldPingPong {
         );
         
         println!("FieldPingPong: Texture upload completed");
+        Ok(())
     }
     
-    /// Download field data from the front texture
-    pub fn download_field_data(&self, device: &Device, queue: &Queue, field_manager: &mut FieldManager) {
-        // Create a staging buffer to read the texture
-        let buffer_size = (self.size[0] * self.size[1] * 8) as u64; // 4 channels × 2 bytes (f16)
+    /// Download field data from every layer of the front texture
+    ///
+    /// Fails, leaving `field_manager` untouched, when the readback cannot be
+    /// mapped or does not match the mirror's size.
+    pub fn download_field_data(&self, device: &Device, queue: &Queue, field_manager: &mut FieldManager) -> Result<(), String> {
+        // Buffer copies need 256-byte aligned rows, so the staging buffer holds padded rows
+        let rows = TexelRows::new(self.size, Layouts::FIELD_LAYERS);
         let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("field_download_staging"),
-            size: buffer_size,
+            size: rows.padded_len() as u64,
             usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
             mapped_at_creation: false,
         });
@@ -538,41 +369,106 @@ impl FieldPingPong {
                 buffer: &staging_buffer,
                 layout: wgpu::ImageDataLayout {
                     offset: 0,
-                    bytes_per_row: Some(self.size[0] * 8),
+                    bytes_per_row: Some(rows.padded_bytes_per_row()),
                     rows_per_image: Some(self.size[1]),
                 },
             },
             wgpu::Extent3d {
                 width: self.size[0],
                 height: self.size[1],
-                depth_or_array_layers: 1,
+                depth_or_array_layers: rows.layers,
             },
         );
         
         queue.submit(Some(encoder.finish()));
         
         // Map the buffer and read the data
-        staging_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
+        let (sender, receiver) = std::sync::mpsc::channel();
+        staging_buffer.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
         device.poll(wgpu::Maintain::Wait);
+        match receiver.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(format!("cannot map the field readback: {}", e)),
+            Err(_) => return Err("the field readback was never mapped".to_string()),
+        }
         
-        let data = staging_buffer.slice(..).get_mapped_range();
-        let half_data: Vec<half::f16> = data
-            .chunks_exact(8) // 4 channels × 2 bytes
-            .map(|chunk| {
-                let mut result = Vec::with_capacity(4);
-                for i in 0..4 {
-                    let bytes = [chunk[i * 2], chunk[i * 2 + 1]];
-                    result.push(half::f16::from_le_bytes(bytes));
-                }
-                result
-            })
-            .flatten()
-            .collect();
-        
-        drop(data);
+        let texels = rows.unpad(&staging_buffer.slice(..).get_mapped_range());
         staging_buffer.unmap();
-        
-        // Convert to field data
-        field_manager.from_rgba16f(&half_data);
+        field_manager.from_rgba16f(&texels?)
+    }
+}
+
+/// Row layout of the field's RGBA16F texels in a texture copy
+///
+/// Buffer copies of a texture need every row to start on a 256-byte boundary
+/// (`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`). At 8 bytes per texel that holds
+/// only for widths that are multiples of 32; other rows end in padding.
+/// `pad` and `unpad` convert between that layout and the packed, layer-major
+/// texels of `FieldManager::as_rgba16f`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TexelRows {
+    pub size: [u32; 2],
+    pub layers: u32,
+}
+
+impl TexelRows {
+    /// RGBA × f16
+    pub const BYTES_PER_TEXEL: u32 = 8;
+
+    pub fn new(size: [u32; 2], layers: u32) -> Self {
+        Self { size, layers }
+    }
+
+    /// Bytes of texel data in one row
+    pub fn bytes_per_row(&self) -> u32 {
+        self.size[0] * Self::BYTES_PER_TEXEL
+    }
+
+    /// Row stride of the copy, rounded up to the copy alignment
+    pub fn padded_bytes_per_row(&self) -> u32 {
+        self.bytes_per_row().next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+    }
+
+    /// Rows of every layer, stacked
+    pub fn rows(&self) -> usize {
+        self.size[1] as usize * self.layers as usize
+    }
+
+    /// f16 values of the packed texels
+    pub fn texel_len(&self) -> usize {
+        self.size[0] as usize * self.rows() * 4
+    }
+
+    /// Bytes of the padded copy
+    pub fn padded_len(&self) -> usize {
+        self.padded_bytes_per_row() as usize * self.rows()
+    }
+
+    /// Packed texels laid out in padded rows
+    pub fn pad(&self, texels: &[f16]) -> Result<Vec<u8>, String> {
+        if texels.len() != self.texel_len() {
+            return Err(format!("expected {} f16 values for {}x{}x{} texels, got {}",
+                self.texel_len(), self.size[0], self.size[1], self.layers, texels.len()));
+        }
+        let (row, stride) = (self.bytes_per_row() as usize, self.padded_bytes_per_row() as usize);
+        let mut padded = vec![0; self.padded_len()];
+        for (dst, src) in padded.chunks_exact_mut(stride).zip(bytemuck::cast_slice::<_, u8>(texels).chunks_exact(row)) {
+            dst[..row].copy_from_slice(src);
+        }
+        Ok(padded)
+    }
+
+    /// Packed texels of a padded copy, row padding dropped
+    pub fn unpad(&self, padded: &[u8]) -> Result<Vec<f16>, String> {
+        if padded.len() != self.padded_len() {
+            return Err(format!("expected {} bytes for {}x{}x{} texels in {}-byte rows, got {}",
+                self.padded_len(), self.size[0], self.size[1], self.layers, self.padded_bytes_per_row(), padded.len()));
+        }
+        let row = self.bytes_per_row() as usize;
+        Ok(padded.chunks_exact(self.padded_bytes_per_row() as usize)
+            .flat_map(|r| r[..row].chunks_exact(2).map(|b| f16::from_le_bytes([b[0], b[1]])))
+            .collect())
     }
 }
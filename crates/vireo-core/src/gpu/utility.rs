@@ -0,0 +1,96 @@
+use wgpu::{BindGroup, Buffer, Device, Queue, TextureView};
+use vireo_params::{AgentParams, UtilityParams};
+use crate::gpu::{ComputePipelines, read_buffer_from};
+use crate::gpu::layouts::Layouts;
+
+/// Workgroup size (per axis) of the utility map shader
+const CELL_GROUP: u32 = 8;
+
+/// GPU pass mapping the field to the landscape agents perceive
+///
+/// Writes chi_R·S(R) − chi_W·S(W) per cell, with S the kappa saturation of
+/// the chemotaxis pass, from whatever `AgentParams` are live when `compute`
+/// is called (so schedules and debug overrides are reflected).
+pub struct UtilityMap {
+    params_buffer: Buffer,
+    output: Buffer,
+    bind_group_a: BindGroup,
+    bind_group_b: BindGroup,
+    size: [u32; 2],
+}
+
+impl UtilityMap {
+    /// `field_a`/`field_b` are the sampled views of the two ping-pong textures
+    pub fn new(device: &Device, layouts: &Layouts, field_a: &TextureView, field_b: &TextureView, size: [u32; 2]) -> Self {
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("utility_params"),
+            size: std::mem::size_of::<UtilityParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let output = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("utility_map"),
+            size: (size[0] * size[1]) as u64 * 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let create_bind_group = |label, field_view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &layouts.utility,
+                entries: &[
+                    // @binding(0) field texture (sampled)
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(field_view),
+                    },
+                    // @binding(1) UtilityParams uniform
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    // @binding(2) per-cell utility
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: output.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+        let bind_group_a = create_bind_group("utility_a_bg", field_a);
+        let bind_group_b = create_bind_group("utility_b_bg", field_b);
+
+        Self { params_buffer, output, bind_group_a, bind_group_b, size }
+    }
+
+    /// Map the current front field and read back one value per cell, row-major
+    pub fn compute(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        pipelines: &ComputePipelines,
+        front_is_a: bool,
+        agent_params: &AgentParams,
+    ) -> Vec<f32> {
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&UtilityParams::from(agent_params)));
+        let bind_group = if front_is_a { &self.bind_group_a } else { &self.bind_group_b };
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("utility_map"),
+        });
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("utility map pass"),
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(pipelines.utility_pipeline.get(device));
+            cpass.set_bind_group(0, bind_group, &[]);
+            cpass.dispatch_workgroups(self.size[0].div_ceil(CELL_GROUP), self.size[1].div_ceil(CELL_GROUP), 1);
+        }
+        queue.submit(Some(encoder.finish()));
+
+        read_buffer_from(device, queue, &self.output, self.output.size())
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::{Arc, Mutex};
+use wgpu::{Device, DeviceLostReason};
+
+/// Records the loss of a device instead of letting the next call panic
+///
+/// `attach` installs a device-lost callback and an uncaptured error handler.
+/// Out-of-memory errors, errors from calls on the lost device and any error
+/// after a loss count as the loss; other errors still panic like wgpu's
+/// default handler. Frontends `check` after
+/// each step and rebuild from their latest checkpoint when it reports a cause.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceWatchdog {
+    cause: Arc<Mutex<Option<String>>>,
+}
+
+impl DeviceWatchdog {
+    pub fn attach(device: &Device) -> Self {
+        let watchdog = Self::default();
+
+        let cause = watchdog.cause.clone();
+        device.set_device_lost_callback(move |reason, message| {
+            // Dropping the device or replacing this callback also invokes it
+            if matches!(reason, DeviceLostReason::Unknown | DeviceLostReason::Destroyed) {
+                cause.lock().unwrap().get_or_insert(format!("device lost ({:?}): {}", reason, message));
+            }
+        });
+
+        let cause = watchdog.cause.clone();
+        device.on_uncaptured_error(Box::new(move |error| {
+            let mut cause = cause.lock().unwrap();
+            if cause.is_some() {
+                return; // Fallout of the loss already recorded
+            }
+            let message = error.to_string();
+            if let wgpu::Error::OutOfMemory { .. } = error {
+                *cause = Some(format!("device out of memory: {}", message));
+                return;
+            }
+            // The lost callback can run after the first call on the lost device fails
+            if message.contains("device is lost") {
+                *cause = Some(format!("device lost: {}", message.trim()));
+                return;
+            }
+            drop(cause);
+            panic!("wgpu error: {}", error);
+        }));
+
+        watchdog
+    }
+
+    /// Cause of the loss, if the device was lost
+    pub fn lost(&self) -> Option<String> {
+        self.cause.lock().unwrap().clone()
+    }
+
+    /// Process pending device callbacks, then report a loss
+    pub fn check(&self, device: &Device) -> Option<String> {
+        device.poll(wgpu::Maintain::Poll);
+        self.lost()
+    }
+}
@@ -5,11 +5,13 @@
 pub mod gpu;
 pub mod sim;
 pub mod shaders;
+pub mod config;
 
 // Re-export main types
 pub use gpu::*;
 pub use sim::*;
 pub use shaders::*;
+pub use config::{LoadError, load_config, parse_config, read_forcing_series};
 
 // Re-export params from vireo-params
 pub use vireo_params::*;
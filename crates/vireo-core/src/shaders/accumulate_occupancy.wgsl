@@ -0,0 +1,17 @@
+// Occupancy totals: adds this step's occupancy grid into a run-long per-cell sum.
+// Dispatched after the agent pass and demography, before the RD pass.
+
+@group(0) @binding(0) var<storage, read> occ : array<u32>;
+@group(0) @binding(1) var<storage, read_write> total : array<u32>;
+@group(0) @binding(2) var<uniform> dims : vec2<u32>;
+
+@compute @workgroup_size(128)
+fn main(@builtin(global_invocation_id) gid : vec3<u32>) {
+    // One workgroup row per cell row, like the clear pass
+    if (gid.x >= dims.x || gid.y >= dims.y) { return; }
+    let i = gid.y * dims.x + gid.x;
+    let sum = total[i];
+    let add = occ[i];
+    // Saturate instead of wrapping on very long runs
+    total[i] = select(sum + add, 0xffffffffu, add > 0xffffffffu - sum);
+}
@@ -8,82 +8,395 @@ struct AgentParams {
     eta_R: f32,    // Energy gain from resource
     dt: f32,       // Time step
     size: vec2<f32>, // World size
-    _pad: vec2<f32>, // Padding for alignment
+    sampling: u32,   // 0 = nearest cell + difference stencil, 1 = bicubic
+    gradient: u32,   // Stencil under sampling 0: 0 = central, 1 = forward, 2 = Sobel, 3 = Scharr
+    memory_weight: f32, // Homing gain toward the remembered site (0 = memoryless)
+    memory_decay: f32,  // Remembered value decay per time unit
+    avoid_distance: f32, // Wall steering range in cells (0 = no obstacles)
+    avoid_strength: f32, // Wall repulsion at the surface
+    climb_cost: f32,     // Energy per unit of elevation climbed (0 = flat terrain)
+    descent_gain: f32,   // Energy per unit of elevation descended
+    disperse_energy: f32, // Energy below which foragers disperse (0 = never)
+    settle_resource: f32, // Local R at which dispersers resume foraging
+    disperse_speed: f32,  // v_max multiplier while dispersing
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
 }
 
-struct Agent {
-    pos: vec2<f32>,     // Position (x, y)
-    vel: vec2<f32>,     // Velocity (vx, vy)
-    energy: f32,        // Current energy
-    alive: u32,         // Alive flag (1 = alive, 0 = dead)
+struct AgentRange {
+    offset: u32, // First slot of the species being stepped
+    count: u32,  // Slots of that species
+    _pad0: u32,
+    _pad1: u32,
+}
+
+struct TimeUniform {
+    step: u32,
+    time: f32,      // step * dt
+    day_phase: f32, // 0 = midnight, 0.5 = noon
+    light: f32,     // Day/night multiplier on growth and top speed (1 without a cycle)
+}
+
+struct EnergyRecord {
+    step: u32,
+    uptake: f32,
+    metabolism: f32,
+    movement: f32,
+}
+
+struct EnergyProbe {
+    selected: u32, // Slot to log (0xffffffff = none)
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+    records: array<EnergyRecord>, // Ring indexed by step
+}
+
+struct SteeringParams {
+    chemotaxis: vec4<f32>, // Per-kind weights of each steering term (w unused)
+    noise: vec4<f32>,
+    crowding: vec4<f32>,
+    flocking: vec4<f32>,
+    memory: vec4<f32>,
+    obstacles: vec4<f32>,
+    attraction: array<vec4<f32>, 3>, // [j][k]: weight of kind k on kind j's density gradient
+    size: vec2<u32>,       // World size in cells
+    seed: u32,             // Low 32 bits of world.seed
+    neighbours: u32,       // 1 when the neighbour grid is filled this step
 }
 
 @group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
-@group(0) @binding(1) var fieldTex: texture_2d<f32>;
+@group(0) @binding(1) var fieldTex: FieldTex;
 @group(0) @binding(2) var<uniform> params: AgentParams;
-@group(0) @binding(3) var<storage, read_write> herbOcc: array<u32>; // Herbivore occupancy
+@group(0) @binding(3) var<storage, read_write> herbOcc: array<atomic<u32>>; // Herbivore occupancy
+@group(0) @binding(4) var obstacleSdf: texture_2d<f32>; // Signed distance to the nearest wall (negative inside)
+@group(0) @binding(5) var<uniform> range: AgentRange; // Species dispatched (dynamic offset)
+@group(0) @binding(6) var elevation: texture_2d<f32>; // Terrain height at cell centres
+@group(0) @binding(7) var<uniform> time: TimeUniform;
+@group(0) @binding(8) var<uniform> steering: SteeringParams;
+@group(0) @binding(9) var<storage, read> neighbours: array<i32>; // (n, vx, vy, plants, herbivores, predators) per cell at the start of the step
+@group(0) @binding(10) var<storage, read_write> forage: array<vec2<f32>>; // (resource consumed, distance moved) per slot
+@group(0) @binding(11) var<storage, read_write> events: array<atomic<u32>>; // Per-cell event counts (births, starved, random deaths)
+@group(0) @binding(12) var<storage, read_write> probe: EnergyProbe; // Energy log of the selected slot
+
+const VEL_SCALE: f32 = 1024.0; // Fixed-point scale of the neighbour velocity sums
 
 fn sample_field(p: vec2<f32>) -> vec2<f32> {
     // Convert world coordinates to texture coordinates
     let uv = vec2<f32>(p.x / params.size.x, p.y / params.size.y);
-    let v = textureLoad(fieldTex, vec2<i32>(uv * params.size), 0);
+    let v = field_load(fieldTex, vec2<i32>(uv * params.size), 0); // layer 0 holds R, W
     return vec2<f32>(v.r, v.g); // R, W channels
 }
 
-fn gradient(p: vec2<f32>) -> vec2<f32> {
-    let eps = vec2<f32>(1.0, 1.0);
-    
-    let fpx = sample_field(p + vec2<f32>(eps.x, 0.0));
-    let fmx = sample_field(p - vec2<f32>(eps.x, 0.0));
-    let fpy = sample_field(p + vec2<f32>(0.0, eps.y));
-    let fmy = sample_field(p - vec2<f32>(0.0, eps.y));
-    
-    let gx = (fpx.r - fmx.r) / (2.0 * eps.x); // Resource gradient
-    let gy = (fpy.r - fmy.r) / (2.0 * eps.y);
-    
-    return vec2<f32>(gx, gy);
+struct FieldGradients {
+    grad_r: vec2<f32>, // dR/dx, dR/dy
+    grad_w: vec2<f32>, // dW/dx, dW/dy
 }
 
-fn gradient_waste(p: vec2<f32>) -> vec2<f32> {
-    let eps = vec2<f32>(1.0, 1.0);
-    
-    let fpx = sample_field(p + vec2<f32>(eps.x, 0.0));
-    let fmx = sample_field(p - vec2<f32>(eps.x, 0.0));
-    let fpy = sample_field(p + vec2<f32>(0.0, eps.y));
-    let fmy = sample_field(p - vec2<f32>(0.0, eps.y));
-    
-    let gx = (fpx.g - fmx.g) / (2.0 * eps.x); // Waste gradient
-    let gy = (fpy.g - fmy.g) / (2.0 * eps.y);
-    
-    return vec2<f32>(gx, gy);
+// Finite differences of the containing cells under the configured stencil
+fn stencil_gradients(p: vec2<f32>) -> FieldGradients {
+    var g: FieldGradients;
+    switch params.gradient {
+        case 1u: {
+            // Forward: one-sided, two points per axis
+            let f = sample_field(p);
+            let dx = sample_field(p + vec2<f32>(1.0, 0.0)) - f;
+            let dy = sample_field(p + vec2<f32>(0.0, 1.0)) - f;
+            g.grad_r = vec2<f32>(dx.x, dy.x);
+            g.grad_w = vec2<f32>(dx.y, dy.y);
+        }
+        case 2u, 3u: {
+            // Sobel (1:2:1) or Scharr (3:10:3) smoothing across the differenced axis
+            let side = select(1.0, 3.0, params.gradient == 3u);
+            let mid = select(2.0, 10.0, params.gradient == 3u);
+            g.grad_r = vec2<f32>(0.0, 0.0);
+            g.grad_w = vec2<f32>(0.0, 0.0);
+            for (var k = -1; k <= 1; k++) {
+                let w = select(side, mid, k == 0);
+                let o = f32(k);
+                let dx = sample_field(p + vec2<f32>(1.0, o)) - sample_field(p + vec2<f32>(-1.0, o));
+                let dy = sample_field(p + vec2<f32>(o, 1.0)) - sample_field(p + vec2<f32>(o, -1.0));
+                g.grad_r += w * vec2<f32>(dx.x, dy.x);
+                g.grad_w += w * vec2<f32>(dx.y, dy.y);
+            }
+            let norm = 1.0 / (2.0 * (2.0 * side + mid));
+            g.grad_r *= norm;
+            g.grad_w *= norm;
+        }
+        default: {
+            // Central: one cell either side per axis
+            let fpx = sample_field(p + vec2<f32>(1.0, 0.0));
+            let fmx = sample_field(p - vec2<f32>(1.0, 0.0));
+            let fpy = sample_field(p + vec2<f32>(0.0, 1.0));
+            let fmy = sample_field(p - vec2<f32>(0.0, 1.0));
+            g.grad_r = vec2<f32>(fpx.r - fmx.r, fpy.r - fmy.r) / 2.0;
+            g.grad_w = vec2<f32>(fpx.g - fmx.g, fpy.g - fmy.g) / 2.0;
+        }
+    }
+    return g;
+}
+
+// Static map value, bilinear between the cell centres (ObstacleField / ElevationField::sample on the CPU)
+fn bilinear_map(tex: texture_2d<f32>, p: vec2<f32>) -> f32 {
+    let t = p - vec2<f32>(0.5, 0.5);
+    let base = vec2<i32>(floor(t));
+    let f = t - floor(t);
+    let hi = vec2<i32>(params.size) - vec2<i32>(1, 1);
+    let d00 = textureLoad(tex, clamp(base, vec2<i32>(0, 0), hi), 0).r;
+    let d10 = textureLoad(tex, clamp(base + vec2<i32>(1, 0), vec2<i32>(0, 0), hi), 0).r;
+    let d01 = textureLoad(tex, clamp(base + vec2<i32>(0, 1), vec2<i32>(0, 0), hi), 0).r;
+    let d11 = textureLoad(tex, clamp(base + vec2<i32>(1, 1), vec2<i32>(0, 0), hi), 0).r;
+    return mix(mix(d00, d10, f.x), mix(d01, d11, f.x), f.y);
+}
+
+fn obstacle_distance(p: vec2<f32>) -> f32 {
+    return bilinear_map(obstacleSdf, p);
+}
+
+fn elevation_at(p: vec2<f32>) -> f32 {
+    return bilinear_map(elevation, p);
+}
+
+// Unit distance gradient, pointing away from the nearest wall (zero where flat)
+fn obstacle_normal(p: vec2<f32>) -> vec2<f32> {
+    let g = vec2<f32>(
+        obstacle_distance(p + vec2<f32>(1.0, 0.0)) - obstacle_distance(p - vec2<f32>(1.0, 0.0)),
+        obstacle_distance(p + vec2<f32>(0.0, 1.0)) - obstacle_distance(p - vec2<f32>(0.0, 1.0)),
+    );
+    let len = length(g);
+    if (len > 1e-6) {
+        return g / len;
+    }
+    return vec2<f32>(0.0, 0.0);
+}
+
+// Catmull-Rom weights for the four samples around fractional offset f
+fn cubic_weights(f: f32) -> vec4<f32> {
+    let f2 = f * f;
+    let f3 = f2 * f;
+    return 0.5 * vec4<f32>(
+        -f3 + 2.0 * f2 - f,
+        3.0 * f3 - 5.0 * f2 + 2.0,
+        -3.0 * f3 + 4.0 * f2 + f,
+        f3 - f2,
+    );
+}
+
+// Derivatives of cubic_weights with respect to f
+fn cubic_weight_derivs(f: f32) -> vec4<f32> {
+    let f2 = f * f;
+    return 0.5 * vec4<f32>(
+        -3.0 * f2 + 4.0 * f - 1.0,
+        9.0 * f2 - 10.0 * f,
+        -9.0 * f2 + 8.0 * f + 1.0,
+        3.0 * f2 - 2.0 * f,
+    );
+}
+
+// Analytic gradients of the Catmull-Rom interpolant through the texel centres
+fn sample_bicubic(p: vec2<f32>) -> FieldGradients {
+    let t = p - vec2<f32>(0.5, 0.5);
+    let base = floor(t);
+    let f = t - base;
+    let wx = cubic_weights(f.x);
+    let wy = cubic_weights(f.y);
+    let dx = cubic_weight_derivs(f.x);
+    let dy = cubic_weight_derivs(f.y);
+    let hi = vec2<i32>(params.size) - vec2<i32>(1, 1);
+
+    var s: FieldGradients;
+    s.grad_r = vec2<f32>(0.0, 0.0);
+    s.grad_w = vec2<f32>(0.0, 0.0);
+    for (var j = 0; j < 4; j++) {
+        for (var i = 0; i < 4; i++) {
+            let cell = clamp(vec2<i32>(base) + vec2<i32>(i - 1, j - 1), vec2<i32>(0, 0), hi);
+            let v = field_load(fieldTex, cell, 0);
+            let gx = dx[i] * wy[j];
+            let gy = wx[i] * dy[j];
+            s.grad_r += vec2<f32>(gx, gy) * v.r;
+            s.grad_w += vec2<f32>(gx, gy) * v.g;
+        }
+    }
+    return s;
+}
+
+// Steering terms: each returns an acceleration that `main` scales by the
+// species' weight in `steering` and adds to the velocity over dt
+
+// Saturated attraction up ∇R and repulsion down ∇W, with the agent's own
+// heritable multipliers on chi_R and kappa
+fn chemotaxis_term(s: FieldGradients, a: Agent) -> vec2<f32> {
+    let kappa = params.kappa * a.kappa_gain;
+    let fR = s.grad_r / (1.0 + kappa * length(s.grad_r));
+    let fW = s.grad_w / (1.0 + kappa * length(s.grad_w));
+    return params.chi_R * a.chi_gain * fR - params.chi_W * fW;
+}
+
+// Homing toward the remembered site, saturating like the gradient terms
+fn memory_term(a: Agent) -> vec2<f32> {
+    let home = a.memory_pos - a.pos;
+    return params.memory_weight * home / (1.0 + length(home));
+}
+
+// Unit vector in a direction drawn for `slot` this step
+fn noise_term(slot: u32) -> vec2<f32> {
+    let h = pcg(pcg(pcg(steering.seed) + time.step) + slot);
+    let angle = f32(h >> 8u) * (6.2831853 / 16777216.0);
+    return vec2<f32>(cos(angle), sin(angle));
+}
+
+// Index of a neighbour grid cell's first entry, clamped to the world
+fn neighbour_index(cell: vec2<i32>) -> u32 {
+    let c = clamp(cell, vec2<i32>(0, 0), vec2<i32>(steering.size) - vec2<i32>(1, 1));
+    return (u32(c.y) * steering.size.x + u32(c.x)) * 6u;
+}
+
+// Neighbour grid cell (n, vx, vy)
+fn neighbour_cell(cell: vec2<i32>) -> vec3<f32> {
+    let idx = neighbour_index(cell);
+    return vec3<f32>(f32(neighbours[idx]), f32(neighbours[idx + 1u]), f32(neighbours[idx + 2u]));
+}
+
+// Agents of kind j in a neighbour grid cell
+fn species_count(cell: vec2<i32>, j: u32) -> f32 {
+    return f32(neighbours[neighbour_index(cell) + 3u + j]);
+}
+
+fn grid_cell(p: vec2<f32>) -> vec2<i32> {
+    return vec2<i32>(clamp(p, vec2<f32>(0.0), vec2<f32>(steering.size) - 1.0));
+}
+
+// Down the agent count gradient, saturating like the chemotaxis terms
+fn crowding_term(p: vec2<f32>) -> vec2<f32> {
+    let c = grid_cell(p);
+    let g = vec2<f32>(
+        neighbour_cell(c + vec2<i32>(1, 0)).x - neighbour_cell(c - vec2<i32>(1, 0)).x,
+        neighbour_cell(c + vec2<i32>(0, 1)).x - neighbour_cell(c - vec2<i32>(0, 1)).x,
+    ) / 2.0;
+    return -g / (1.0 + length(g));
+}
+
+// Up (weight > 0) or down (weight < 0) the count gradient of each species,
+// each saturating like crowding
+fn attraction_term(p: vec2<f32>, k: u32) -> vec2<f32> {
+    let c = grid_cell(p);
+    var f = vec2<f32>(0.0, 0.0);
+    for (var j = 0u; j < 3u; j++) {
+        let w = steering.attraction[j][k];
+        if (w != 0.0) {
+            let g = vec2<f32>(
+                species_count(c + vec2<i32>(1, 0), j) - species_count(c - vec2<i32>(1, 0), j),
+                species_count(c + vec2<i32>(0, 1), j) - species_count(c - vec2<i32>(0, 1), j),
+            ) / 2.0;
+            f += w * g / (1.0 + length(g));
+        }
+    }
+    return f;
+}
+
+// Toward the mean velocity of the agents in the surrounding 3×3 cells
+fn flocking_term(p: vec2<f32>, v: vec2<f32>) -> vec2<f32> {
+    let c = grid_cell(p);
+    var sum = vec3<f32>(0.0);
+    for (var j = -1; j <= 1; j++) {
+        for (var i = -1; i <= 1; i++) {
+            sum += neighbour_cell(c + vec2<i32>(i, j));
+        }
+    }
+    if (sum.x == 0.0) {
+        return vec2<f32>(0.0, 0.0);
+    }
+    return sum.yz / (VEL_SCALE * sum.x) - v;
+}
+
+// Wall avoidance along the distance gradient, fading out at avoid_distance
+fn obstacle_term(p: vec2<f32>) -> vec2<f32> {
+    if (params.avoid_distance > 0.0) {
+        let d = obstacle_distance(p);
+        if (d < params.avoid_distance) {
+            let push = params.avoid_strength * (1.0 - max(d, 0.0) / params.avoid_distance);
+            return push * obstacle_normal(p);
+        }
+    }
+    return vec2<f32>(0.0, 0.0);
 }
 
 @compute @workgroup_size(128)
 fn main(@builtin(global_invocation_id) id: vec3<u32>) {
-    let i = id.x;
-    if (i >= arrayLength(&agents)) { return; }
+    if (id.x >= range.count) { return; }
+    let i = range.offset + id.x;
 
     var a = agents[i];
     if (a.alive == 0u) { return; }
 
-    // Sample gradients
-    let gR = gradient(a.pos);
-    let gW = gradient_waste(a.pos);
+    // Behaviour switch: starving foragers on poor ground disperse, dispersers
+    // settle again once they reach a rich enough cell
+    let here = sample_field(a.pos).r;
+    if (params.disperse_energy <= 0.0) {
+        a.state = 0u;
+    } else if (a.state == 0u && a.energy < params.disperse_energy && here < params.settle_resource) {
+        a.state = 1u;
+    } else if (a.state == 1u && here >= params.settle_resource) {
+        a.state = 0u;
+    }
+    let dispersing = a.state == 1u;
+
+    // Top speed, lowered at night and raised while dispersing
+    let v_max = params.v_max * time.light * select(1.0, params.disperse_speed, dispersing);
 
-    // Chemotactic forces with saturation
-    let fR = gR / (1.0 + params.kappa * length(gR));
-    let fW = gW / (1.0 + params.kappa * length(gW));
+    // Sample gradients
+    var s: FieldGradients;
+    if (params.sampling == 1u) {
+        s = sample_bicubic(a.pos);
+    } else {
+        s = stencil_gradients(a.pos);
+    }
 
-    // Update velocity with chemotaxis
+    // Steering: weighted sum of the species' terms (terms weighted 0 are skipped)
+    let k = min(a.kind, 3u);
     var v = a.vel;
-    v += (params.chi_R * fR - params.chi_W * fW) * params.dt;
+    if (dispersing) {
+        // Ballistic: keep the heading at full dispersal speed, gradients ignored;
+        // from rest pick a heading by golden-angle steps over slot and step
+        if (length(v) < 1e-6) {
+            let angle = f32((i + time.step) % 4096u) * 2.3999632;
+            v = vec2<f32>(cos(angle), sin(angle));
+        }
+        v = normalize(v) * v_max;
+    } else {
+        // Foragers follow the field, their memory and their neighbours' heading
+        if (steering.chemotaxis[k] != 0.0) {
+            v += steering.chemotaxis[k] * chemotaxis_term(s, a) * params.dt;
+        }
+        if (params.memory_weight > 0.0 && steering.memory[k] != 0.0) {
+            v += steering.memory[k] * memory_term(a) * params.dt;
+        }
+        if (steering.neighbours == 1u && steering.flocking[k] != 0.0) {
+            v += steering.flocking[k] * flocking_term(a.pos, v) * params.dt;
+        }
+    }
+    if (steering.noise[k] != 0.0) {
+        v += steering.noise[k] * noise_term(i) * params.dt;
+    }
+    if (steering.neighbours == 1u && steering.crowding[k] != 0.0) {
+        v += steering.crowding[k] * crowding_term(a.pos) * params.dt;
+    }
+    if (steering.neighbours == 1u) {
+        v += attraction_term(a.pos, k) * params.dt;
+    }
+    if (steering.obstacles[k] != 0.0) {
+        v += steering.obstacles[k] * obstacle_term(a.pos) * params.dt;
+    }
     
-    // Apply damping
-    v *= (1.0 - params.gamma);
+    // Apply damping (dispersers coast)
+    if (!dispersing) {
+        v *= (1.0 - params.gamma);
+    }
     
     // Clamp to maximum velocity
-    if (length(v) > params.v_max) {
-        v = normalize(v) * params.v_max;
+    if (length(v) > v_max) {
+        v = normalize(v) * v_max;
     }
 
     // Update position
@@ -108,13 +421,48 @@ fn main(@builtin(global_invocation_id) id: vec3<u32>) {
         v.y = -v.y * bounce_damping;
     }
 
+    // Steering could not stop the agent: project it back onto the wall and bounce
+    if (params.avoid_distance > 0.0) {
+        let d = obstacle_distance(x);
+        if (d < 0.0) {
+            let n = obstacle_normal(x);
+            x = clamp(x - d * n, vec2<f32>(0.0, 0.0), params.size - vec2<f32>(0.1, 0.1));
+            let vn = dot(v, n);
+            if (vn < 0.0) {
+                v -= (1.0 + bounce_damping) * vn * n;
+            }
+        }
+    }
+
     // Energy management
     let local_field = sample_field(x);
     let R = local_field.r;
     let energy_gain = params.eta_R * R * params.dt;
-    let energy_drain = params.eps0 * params.dt;
+    let metabolism = params.eps0 * params.dt;
+    var energy_drain = metabolism;
+
+    // Terrain: climbing costs energy, descending returns part of it (ElevationField::move_cost)
+    var movement_cost = 0.0;
+    if (params.climb_cost > 0.0 || params.descent_gain > 0.0) {
+        let dh = elevation_at(x) - elevation_at(a.pos);
+        movement_cost = select(params.descent_gain, params.climb_cost, dh > 0.0) * dh;
+        energy_drain += movement_cost;
+    }
     
     a.energy += energy_gain - energy_drain;
+    forage[i] += vec2<f32>(energy_gain, length(x - a.pos));
+
+    // Only the selected slot logs its budget
+    if (i == probe.selected) {
+        probe.records[time.step % arrayLength(&probe.records)] = EnergyRecord(time.step, energy_gain, metabolism, movement_cost);
+    }
+
+    // Memory: the remembered value fades, and a richer cell replaces the site
+    a.memory_value *= max(0.0, 1.0 - params.memory_decay * params.dt);
+    if (R > a.memory_value) {
+        a.memory_value = R;
+        a.memory_pos = x;
+    }
     
     // Death on zero energy
     if (a.energy <= 0.0) {
@@ -124,16 +472,21 @@ fn main(@builtin(global_invocation_id) id: vec3<u32>) {
     // Update agent
     a.pos = x;
     a.vel = v;
+    a.age += 1u;
     agents[i] = a;
 
-    // Write occupancy to grid (simple increment for now)
+    let cell_x = u32(clamp(floor(x.x), 0.0, params.size.x - 1.0));
+    let cell_y = u32(clamp(floor(x.y), 0.0, params.size.y - 1.0));
+    let cell_idx = cell_y * u32(params.size.x) + cell_x;
+
+    // Write occupancy to grid; integer atomics keep the count independent of thread order
     if (a.alive == 1u) {
-        let cell_x = u32(clamp(floor(x.x), 0.0, params.size.x - 1.0));
-        let cell_y = u32(clamp(floor(x.y), 0.0, params.size.y - 1.0));
-        let cell_idx = cell_y * u32(params.size.x) + cell_x;
-        
-        // Simple increment (in a real implementation, use atomic operations)
-        // For now, this works for single-threaded agent updates
-        herbOcc[cell_idx] += 1u;
+        atomicAdd(&herbOcc[cell_idx], 1u);
+    } else {
+        // Starvation deaths per cell (plane 1), when the event counters are on
+        let cells = u32(params.size.x) * u32(params.size.y);
+        if (arrayLength(&events) >= 3u * cells) {
+            atomicAdd(&events[cells + cell_idx], 1u);
+        }
     }
 }
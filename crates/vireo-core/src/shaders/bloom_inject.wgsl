@@ -0,0 +1,32 @@
+struct BloomParams {
+    size: vec2<u32>,   // Grid size
+    count: u32,        // Active blooms
+    rain_count: u32,   // Active rain cells
+    blooms: array<vec4<f32>, 8>, // (center x, center y, radius, amplitude), MAX_BLOOMS
+    rain: array<vec4<f32>, 8>,   // (center x, center y, standard deviation, amplitude), MAX_RAIN_CELLS
+}
+
+@group(0) @binding(0) var<uniform> params: BloomParams;
+@group(0) @binding(1) var<storage, read_write> sigmaBoost: array<f32>; // read by the RD pass
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.size.x || gid.y >= params.size.y) { return; }
+
+    let p = vec2<f32>(gid.xy) + vec2<f32>(0.5, 0.5);
+    var boost = 0.0;
+    for (var i = 0u; i < params.count; i = i + 1u) {
+        let b = params.blooms[i];
+        let d = distance(p, b.xy);
+        // Flat top with a soft rim over the outer 20% of the radius
+        boost += b.w * (1.0 - smoothstep(0.8 * b.z, b.z, d));
+    }
+    // Rain cells are Gaussian, centred where they have drifted to this step
+    for (var i = 0u; i < params.rain_count; i = i + 1u) {
+        let c = params.rain[i];
+        let d = distance(p, c.xy);
+        boost += c.w * exp(-0.5 * d * d / (c.z * c.z));
+    }
+
+    sigmaBoost[gid.y * params.size.x + gid.x] = boost;
+}
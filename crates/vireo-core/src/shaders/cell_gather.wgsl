@@ -0,0 +1,34 @@
+// Cell gather: the field at a list of cells, for sampled metrics.
+//
+// One thread per listed cell (row-major index) writes R, W, soil damage and
+// the central-difference |∇R| used by the full-field stats, which is 0 on the
+// border rows and columns.
+
+struct CellGatherParams {
+    size: vec2<u32>,
+    count: u32,
+    _pad: u32,
+}
+
+@group(0) @binding(0) var fieldTex: FieldTex;
+@group(0) @binding(1) var<uniform> params: CellGatherParams;
+@group(0) @binding(2) var<storage, read> cells: array<u32>;
+@group(0) @binding(3) var<storage, read_write> samples: array<vec4<f32>>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.count) {
+        return;
+    }
+    let cell = cells[gid.x];
+    let p = vec2<i32>(i32(cell % params.size.x), i32(cell / params.size.x));
+    let here = field_load(fieldTex, p, 0); // layer 0 holds R, W, soil
+    var grad = 0.0;
+    let size = vec2<i32>(params.size);
+    if (p.x > 0 && p.y > 0 && p.x < size.x - 1 && p.y < size.y - 1) {
+        let dx = (field_load(fieldTex, p + vec2<i32>(1, 0), 0).r - field_load(fieldTex, p - vec2<i32>(1, 0), 0).r) * 0.5;
+        let dy = (field_load(fieldTex, p + vec2<i32>(0, 1), 0).r - field_load(fieldTex, p - vec2<i32>(0, 1), 0).r) * 0.5;
+        grad = sqrt(dx * dx + dy * dy);
+    }
+    samples[gid.x] = vec4<f32>(here.r, here.g, here.b, grad);
+}
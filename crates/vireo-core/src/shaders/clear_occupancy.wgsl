@@ -3,9 +3,8 @@
 
 @compute @workgroup_size(128)
 fn main(@builtin(global_invocation_id) gid : vec3<u32>) {
-    let idx = gid.x;
-    let total = dims.x * dims.y;
-    if (idx < total) { 
-        occ[idx] = 0u; 
+    // One workgroup row per cell row, so large worlds stay within the dispatch limit
+    if (gid.x < dims.x && gid.y < dims.y) {
+        occ[gid.y * dims.x + gid.x] = 0u;
     }
 }
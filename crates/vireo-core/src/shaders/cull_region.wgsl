@@ -0,0 +1,30 @@
+// Cull agents inside a region: sets alive = 0 for every agent in a rectangle or circle.
+
+struct CullParams {
+    shape: u32,        // 0 = rectangle [a, b], 1 = circle centred at a with radius b.x
+    _pad0: u32,
+    a: vec2<f32>,
+    b: vec2<f32>,
+    _pad1: vec2<f32>,
+}
+
+@group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
+@group(0) @binding(1) var<uniform> params: CullParams;
+
+fn inside(p: vec2<f32>) -> bool {
+    if (params.shape == 0u) {
+        return all(p >= params.a) && all(p <= params.b);
+    }
+    let d = p - params.a;
+    return dot(d, d) <= params.b.x * params.b.x;
+}
+
+@compute @workgroup_size(128)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let i = gid.x;
+    if (i >= arrayLength(&agents)) { return; }
+
+    if (agents[i].alive == 1u && inside(agents[i].pos)) {
+        agents[i].alive = 0u;
+    }
+}
@@ -0,0 +1,150 @@
+// Demographic stochasticity: per-agent random deaths and births for one step.
+//
+// Every thread reads the post-movement agents from `prev` and writes only its
+// own slot, so the result does not depend on thread order. Random numbers are
+// a counter-based hash of (seed, step, slot, stream).
+//
+// The pass is dispatched once per species over that species' slot range.
+// Births pair every slot i of the range with partner (i + offset) % count for
+// a per-step random offset. An alive parent whose birth draw succeeds fills
+// its partner slot if that slot is free after this step's deaths, handing
+// over half of its energy. Each agent thus gets one birth attempt per step,
+// and offspring stay inside their species' range.
+//
+// With `output.event_window` set, births are counted at the newborn's cell
+// and random deaths at the cell the agent died in (planes 0 and 2 of the
+// event counters; the agent pass counts starvation in plane 1).
+//
+// With `refuges.enabled` the death probability is scaled by the refuge mask
+// at the cell the agent stands in.
+//
+// Newborns inherit their parent's chemotaxis multipliers; with `evolution`
+// each is multiplied by exp(sd * z) for a standard normal z (Box-Muller over
+// two draws), so it stays positive.
+
+struct DemographyParams {
+    death: vec4<f32>, // Per-step death probability by kind
+    birth: vec4<f32>, // Per-step birth probability by kind
+    seed: u32,
+    step: u32,
+    size: vec2<u32>, // World size in cells
+    refuges: u32,    // 1 = scale deaths by `refuge`
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+    chi_mutation: vec4<f32>,   // Log-normal step on the chi_R multiplier by kind
+    kappa_mutation: vec4<f32>, // Log-normal step on the kappa multiplier by kind
+}
+
+struct AgentRange {
+    offset: u32, // First slot of the species being drawn
+    count: u32,  // Slots of that species
+    _pad0: u32,
+    _pad1: u32,
+}
+
+@group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
+@group(0) @binding(1) var<storage, read> prev: array<Agent>;
+@group(0) @binding(2) var<uniform> params: DemographyParams;
+@group(0) @binding(3) var<uniform> range: AgentRange; // Species dispatched (dynamic offset)
+@group(0) @binding(4) var<storage, read_write> events: array<atomic<u32>>; // Per-cell event counts, one plane per kind of event
+@group(0) @binding(5) var<storage, read> refuge: array<f32>; // Death probability multiplier per cell (one texel without refuges)
+
+const EVENT_BIRTH: u32 = 0u;
+const EVENT_RANDOM_DEATH: u32 = 2u;
+
+// Uniform draw in [0, 1) for `slot` on `stream` this step
+fn slot_draw(slot: u32, stream: u32) -> f32 {
+    return draw(params.seed, params.step, slot, stream);
+}
+
+// Row-major cell index of `pos`, clamped to the grid
+fn cell_index(pos: vec2<f32>) -> u32 {
+    let cell = min(vec2<u32>(max(pos, vec2<f32>(0.0, 0.0))), params.size - vec2<u32>(1u, 1u));
+    return cell.y * params.size.x + cell.x;
+}
+
+// Two independent standard normal draws for `slot` (Box-Muller)
+fn normals(slot: u32) -> vec2<f32> {
+    let radius = sqrt(-2.0 * log(1.0 - slot_draw(slot, STREAM_MUTATION_RADIUS)));
+    let angle = 6.28318530718 * slot_draw(slot, STREAM_MUTATION_ANGLE);
+    return radius * vec2<f32>(cos(angle), sin(angle));
+}
+
+// Alive after this step's death draw
+fn survives(slot: u32) -> bool {
+    let a = prev[slot];
+    var death = params.death[min(a.kind, 3u)];
+    if (params.refuges == 1u) {
+        death *= refuge[cell_index(a.pos)];
+    }
+    return a.alive == 1u && slot_draw(slot, STREAM_DEATH) >= death;
+}
+
+// `slot` is alive, wins its birth draw and its partner slot is free
+fn gives_birth(slot: u32, partner: u32) -> bool {
+    return survives(slot)
+        && slot_draw(slot, STREAM_BIRTH) < params.birth[min(prev[slot].kind, 3u)]
+        && !survives(partner);
+}
+
+// Count an event at `pos` (a no-op when the counters hold no full planes)
+fn count_event(plane: u32, pos: vec2<f32>) {
+    let cells = params.size.x * params.size.y;
+    if (arrayLength(&events) < 3u * cells) { return; }
+    atomicAdd(&events[plane * cells + cell_index(pos)], 1u);
+}
+
+@compute @workgroup_size(128)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let n = range.count;
+    if (id.x >= n) { return; }
+    let i = range.offset + id.x;
+
+    // A single-slot species can only die
+    var parent = i;
+    var child = i;
+    if (n >= 2u) {
+        let offset = 1u + pcg(params.seed ^ pcg(params.step) ^ pcg(range.offset)) % (n - 1u);
+        parent = range.offset + (id.x + n - offset) % n; // Slot whose partner is i
+        child = range.offset + (id.x + offset) % n;      // Partner slot of i
+    }
+
+    var a = prev[i];
+    let lives = survives(i);
+    if (a.alive == 1u && !lives) {
+        count_event(EVENT_RANDOM_DEATH, a.pos);
+    }
+    if (lives) {
+        if (gives_birth(i, child)) {
+            a.energy *= 0.5;
+        }
+    } else if (gives_birth(parent, i)) {
+        let p = prev[parent];
+        let jitter = vec2<f32>(slot_draw(i, STREAM_JITTER_X), slot_draw(i, STREAM_JITTER_Y)) - vec2<f32>(0.5, 0.5);
+        a.pos = max(p.pos + jitter, vec2<f32>(0.0, 0.0));
+        a.vel = vec2<f32>(0.0, 0.0);
+        a.energy = p.energy * 0.5;
+        a.alive = 1u;
+        a.kind = p.kind;
+        a.cohort = 0u; // Offspring are not part of their parent's marked cohort
+        a.memory_pos = a.pos; // ...and start without a remembered site
+        a.memory_value = 0.0;
+        a.state = 0u; // Newborns forage
+        a.age = 0u;
+        a.chi_gain = p.chi_gain;
+        a.kappa_gain = p.kappa_gain;
+        a.parent_gains = pack2x16float(vec2<f32>(p.chi_gain, p.kappa_gain)); // For the parent-offspring regression
+        let kind = min(p.kind, 3u);
+        let sd = vec2<f32>(params.chi_mutation[kind], params.kappa_mutation[kind]);
+        if (any(sd > vec2<f32>(0.0, 0.0))) {
+            let step = exp(sd * normals(i));
+            a.chi_gain *= step.x;
+            a.kappa_gain *= step.y;
+        }
+        count_event(EVENT_BIRTH, a.pos);
+    } else {
+        a.alive = 0u;
+    }
+    agents[i] = a;
+}
@@ -0,0 +1,13 @@
+// Field storage prelude: every channel fits in one RGBA layer (plain 2D texture).
+// `layer` is accepted for parity with field_array.wgsl and ignored.
+
+alias FieldTex = texture_2d<f32>;
+alias FieldStorage = texture_storage_2d<rgba16float, write>;
+
+fn field_load(tex: FieldTex, p: vec2<i32>, layer: i32) -> vec4<f32> {
+    return textureLoad(tex, p, 0);
+}
+
+fn field_sample(tex: FieldTex, samp: sampler, uv: vec2<f32>, layer: i32) -> vec4<f32> {
+    return textureSample(tex, samp, uv);
+}
@@ -0,0 +1,12 @@
+// Field storage prelude: more than four channels, one RGBA layer per four (2D array).
+
+alias FieldTex = texture_2d_array<f32>;
+alias FieldStorage = texture_storage_2d_array<rgba16float, write>;
+
+fn field_load(tex: FieldTex, p: vec2<i32>, layer: i32) -> vec4<f32> {
+    return textureLoad(tex, p, layer, 0);
+}
+
+fn field_sample(tex: FieldTex, samp: sampler, uv: vec2<f32>, layer: i32) -> vec4<f32> {
+    return textureSample(tex, samp, uv, layer);
+}
@@ -0,0 +1,85 @@
+// Field image export: colour-maps the field or the occupancy grid into an RGBA8 texture.
+//
+// `find_bounds` reduces the range the image is scaled to (min/max R for the
+// field, the busiest cell for occupancy) with one global atomic per
+// workgroup; `colormap` then writes one texel per cell. Channels are
+// truncated to 8 bits like the CPU writer did: red = (R - min) / (max - min),
+// green = W, blue = soil damage; occupancy is grey, scaled to the busiest cell.
+
+struct FieldImageParams {
+    size: vec2<u32>,
+    kind: u32, // 0 = field, 1 = occupancy
+    _pad: u32,
+}
+
+@group(0) @binding(0) var fieldTex: FieldTex;
+@group(0) @binding(1) var<storage, read> occupancy: array<u32>;
+@group(0) @binding(2) var<uniform> params: FieldImageParams;
+@group(0) @binding(3) var<storage, read_write> bounds: array<atomic<u32>, 4>; // min R, max R (as ordered keys), max occupancy, unused
+@group(0) @binding(4) var image: texture_storage_2d<rgba8unorm, write>;
+
+var<workgroup> group_min: atomic<u32>;
+var<workgroup> group_max: atomic<u32>;
+
+// f32 bits remapped so that unsigned order matches float order
+fn ordered_key(v: f32) -> u32 {
+    let bits = bitcast<u32>(v);
+    return select(bits | 0x80000000u, ~bits, (bits & 0x80000000u) != 0u);
+}
+
+fn key_value(key: u32) -> f32 {
+    return bitcast<f32>(select(~key, key & 0x7fffffffu, (key & 0x80000000u) != 0u));
+}
+
+// Truncate to the 8-bit level below, as `(v * 255.0) as u8` would
+fn quantize(v: f32) -> f32 {
+    return floor(clamp(v, 0.0, 1.0) * 255.0) / 255.0;
+}
+
+@compute @workgroup_size(8, 8)
+fn find_bounds(@builtin(global_invocation_id) gid: vec3<u32>, @builtin(local_invocation_index) lid: u32) {
+    if (lid == 0u) {
+        atomicStore(&group_min, 0xffffffffu);
+        atomicStore(&group_max, 0u);
+    }
+    workgroupBarrier();
+
+    if (gid.x < params.size.x && gid.y < params.size.y) {
+        if (params.kind == 0u) {
+            let key = ordered_key(field_load(fieldTex, vec2<i32>(gid.xy), 0).r);
+            atomicMin(&group_min, key);
+            atomicMax(&group_max, key);
+        } else {
+            atomicMax(&group_max, occupancy[gid.y * params.size.x + gid.x]);
+        }
+    }
+    workgroupBarrier();
+
+    if (lid == 0u) {
+        if (params.kind == 0u) {
+            atomicMin(&bounds[0], atomicLoad(&group_min));
+            atomicMax(&bounds[1], atomicLoad(&group_max));
+        } else {
+            atomicMax(&bounds[2], atomicLoad(&group_max));
+        }
+    }
+}
+
+@compute @workgroup_size(8, 8)
+fn colormap(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.size.x || gid.y >= params.size.y) {
+        return;
+    }
+    var color: vec3<f32>;
+    if (params.kind == 0u) {
+        let low = key_value(atomicLoad(&bounds[0]));
+        let span = key_value(atomicLoad(&bounds[1])) - low;
+        let cell = field_load(fieldTex, vec2<i32>(gid.xy), 0); // layer 0 holds R, W, soil
+        color = vec3<f32>(quantize((cell.r - low) / select(1.0, span, span > 0.0)), quantize(cell.g), quantize(cell.b));
+    } else {
+        let top = f32(atomicLoad(&bounds[2]));
+        let count = f32(occupancy[gid.y * params.size.x + gid.x]);
+        color = vec3<f32>(select(0.0, quantize(count / top), top > 0.0));
+    }
+    textureStore(image, vec2<i32>(gid.xy), vec4<f32>(color, 1.0));
+}
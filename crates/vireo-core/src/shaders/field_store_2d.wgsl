@@ -0,0 +1,7 @@
+// Field store helper for shaders declaring `var dstTex: FieldStorage`.
+// naga does not accept storage textures as function arguments, so this
+// writes the module-scope `dstTex` directly.
+
+fn field_store(p: vec2<i32>, layer: i32, v: vec4<f32>) {
+    textureStore(dstTex, p, v);
+}
@@ -1,14 +1,139 @@
+use vireo_params::FieldChannel;
+use crate::sim::Agent;
+
+/// Field storage prelude for the current channel registry
+///
+/// Declares `FieldTex`, `FieldStorage` and the `field_load`/`field_sample`
+/// accessors, backed by a 2D texture or a 2D array depending on
+/// `FieldChannel::LAYERS`.
+pub fn field_prelude() -> &'static str {
+    if FieldChannel::LAYERS == 1 {
+        include_str!("field_2d.wgsl")
+    } else {
+        include_str!("field_array.wgsl")
+    }
+}
+
+/// `field_store` helper for shaders that bind `dstTex: FieldStorage`
+pub fn field_store_prelude() -> &'static str {
+    if FieldChannel::LAYERS == 1 {
+        include_str!("field_store_2d.wgsl")
+    } else {
+        include_str!("field_store_array.wgsl")
+    }
+}
+
+/// Prepend the field storage prelude to a shader body
+pub fn with_field_prelude(body: &str) -> String {
+    format!("{}\n{}", field_prelude(), body)
+}
+
+/// Random number prelude: `pcg`, the `draw` hash and the `STREAM_*` ids
+///
+/// The one WGSL copy of the generator, so every shader that draws hashes the
+/// same way and the stream ids sit in a single list.
+pub fn rng_prelude() -> &'static str {
+    include_str!("rng.wgsl")
+}
+
+/// Prepend the random number prelude to a shader body
+pub fn with_rng_prelude(body: &str) -> String {
+    format!("{}\n{}", rng_prelude(), body)
+}
+
+/// `struct Agent` generated from `Agent::WGSL_FIELDS`
+///
+/// Every shader that binds the agent buffer declares the struct through this
+/// prelude rather than its own copy.
+pub fn agent_prelude() -> String {
+    let fields: String = Agent::WGSL_FIELDS
+        .iter()
+        .map(|(name, ty, offset)| format!("    {}: {}, // offset {}\n", name, ty, offset))
+        .collect();
+    format!("struct Agent {{ // {} bytes, from vireo_core::Agent\n{}}}\n", std::mem::size_of::<Agent>(), fields)
+}
+
+/// Prepend the agent struct prelude to a shader body
+pub fn with_agent_prelude(body: &str) -> String {
+    format!("{}\n{}", agent_prelude(), body)
+}
+
 /// Reaction-diffusion step shader
-pub fn rd_step() -> &'static str {
-    include_str!("rd_step.wgsl")
+pub fn rd_step() -> String {
+    with_field_prelude(&format!("{}\n{}", field_store_prelude(), with_rng_prelude(include_str!("rd_step.wgsl"))))
 }
 
 /// Agent chemotaxis step shader
-pub fn agent_step() -> &'static str {
-    include_str!("agent_step.wgsl")
+pub fn agent_step() -> String {
+    with_field_prelude(&with_agent_prelude(&with_rng_prelude(include_str!("agent_step.wgsl"))))
 }
 
 /// Clear occupancy buffer shader
 pub fn clear_occupancy() -> &'static str {
     include_str!("clear_occupancy.wgsl")
 }
+
+/// Occupancy total shader (adds each step's occupancy into a run-long sum)
+pub fn accumulate_occupancy() -> &'static str {
+    include_str!("accumulate_occupancy.wgsl")
+}
+
+/// Bloom injection shader (writes the per-cell sigma_R boost map)
+pub fn bloom_inject() -> &'static str {
+    include_str!("bloom_inject.wgsl")
+}
+
+/// Movement metrics shader (alignment, up-gradient flux, velocity divergence)
+pub fn movement_metrics() -> String {
+    with_field_prelude(&with_agent_prelude(include_str!("movement_metrics.wgsl")))
+}
+
+/// Utility map shader (chi_R·S(R) − chi_W·S(W) per cell)
+pub fn utility_map() -> String {
+    with_field_prelude(include_str!("utility_map.wgsl"))
+}
+
+/// Resource mip chain shader (level 0 copy and 2×2 box reduction entry points)
+pub fn resource_mip() -> String {
+    with_field_prelude(include_str!("resource_mip.wgsl"))
+}
+
+/// Cell gather shader (R, W, soil and |∇R| at a list of cells, for sampled metrics)
+pub fn cell_gather() -> String {
+    with_field_prelude(include_str!("cell_gather.wgsl"))
+}
+
+/// Region cull shader (kills agents inside a rectangle or circle)
+pub fn cull_region() -> String {
+    with_agent_prelude(include_str!("cull_region.wgsl"))
+}
+
+/// Neighbour grid shader (per-cell agent count and velocity sum for crowding and flocking)
+pub fn neighbour_grid() -> String {
+    with_agent_prelude(include_str!("neighbour_grid.wgsl"))
+}
+
+/// Obstacle brush shader (unions or subtracts a disk in the obstacle distance field)
+pub fn obstacle_brush() -> &'static str {
+    include_str!("obstacle_brush.wgsl")
+}
+
+/// Radix sort shader (histogram, scan and scatter over u32 key/value pairs)
+pub fn radix_sort() -> &'static str {
+    include_str!("radix_sort.wgsl")
+}
+
+/// Demography shader (random per-agent deaths and births)
+pub fn demography() -> String {
+    with_agent_prelude(&with_rng_prelude(include_str!("demography.wgsl")))
+}
+
+/// Field image export shader (range reduction and colour map into an RGBA8 texture)
+pub fn field_image() -> String {
+    with_field_prelude(include_str!("field_image.wgsl"))
+}
+
+/// Separation shader (spatial hash of agent cells and the pairwise push apart)
+pub fn separation() -> String {
+    with_agent_prelude(&with_rng_prelude(include_str!("separation.wgsl")))
+}
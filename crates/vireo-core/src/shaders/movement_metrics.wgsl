@@ -0,0 +1,134 @@
+// Movement metrics: velocity/∇R alignment, up-gradient flux and velocity divergence.
+//
+// Three entry points, dispatched in order in separate passes:
+//   clear_grid  - zero the fixed-point velocity grid, one workgroup row per cell row
+//   deposit     - per agent: alignment + flux partial sums, deposit velocity into the grid
+//   divergence  - per cell: ∇·v̄ of the mean cell velocity over occupied cells
+// Each workgroup of `deposit`/`divergence` writes one vec4 partial sum; the host adds them up.
+
+struct MovementParams {
+    size: vec2<u32>,    // World size in cells
+    agent_groups: u32,  // Workgroups used by `deposit` (offset of the divergence partials)
+    _pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read> agents: array<Agent>;
+@group(0) @binding(1) var fieldTex: FieldTex;
+@group(0) @binding(2) var<uniform> params: MovementParams;
+@group(0) @binding(3) var<storage, read_write> velGrid: array<atomic<i32>>; // (vx, vy, n) per cell, fixed-point
+@group(0) @binding(4) var<storage, read_write> partials: array<vec4<f32>>;
+
+const VEL_SCALE: f32 = 1024.0; // Fixed-point scale of velGrid
+const GRAD_EPS: f32 = 1e-6;    // |∇R| or |v| below this carries no direction
+
+var<workgroup> scratch: array<vec4<f32>, 64>;
+
+fn load_r(p: vec2<i32>) -> f32 {
+    let q = clamp(p, vec2<i32>(0), vec2<i32>(params.size) - 1);
+    return field_load(fieldTex, q, 0).r; // layer 0 holds R, W
+}
+
+fn grad_r(p: vec2<i32>) -> vec2<f32> {
+    return vec2<f32>(
+        load_r(p + vec2<i32>(1, 0)) - load_r(p - vec2<i32>(1, 0)),
+        load_r(p + vec2<i32>(0, 1)) - load_r(p - vec2<i32>(0, 1)),
+    ) * 0.5;
+}
+
+fn cell_index(p: vec2<u32>) -> u32 {
+    return p.y * params.size.x + p.x;
+}
+
+// Tree-reduce `scratch` and write the workgroup's partial sum
+fn reduce_and_store(lid: u32, slot: u32) {
+    workgroupBarrier();
+    for (var stride = 32u; stride > 0u; stride = stride >> 1u) {
+        if (lid < stride) {
+            scratch[lid] += scratch[lid + stride];
+        }
+        workgroupBarrier();
+    }
+    if (lid == 0u) {
+        partials[slot] = scratch[0];
+    }
+}
+
+@compute @workgroup_size(64)
+fn clear_grid(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let row = params.size.x * 3u;
+    if (gid.x < row && gid.y < params.size.y) {
+        atomicStore(&velGrid[gid.y * row + gid.x], 0);
+    }
+}
+
+@compute @workgroup_size(64)
+fn deposit(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_index) lid: u32,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    // (alignment sum, flux sum, agents with a direction, alive agents)
+    var acc = vec4<f32>(0.0);
+
+    let i = gid.x;
+    if (i < arrayLength(&agents) && agents[i].alive == 1u) {
+        let a = agents[i];
+        let cell = vec2<u32>(clamp(a.pos, vec2<f32>(0.0), vec2<f32>(params.size) - 1.0));
+        let g = grad_r(vec2<i32>(cell));
+        let g_len = length(g);
+        let v_len = length(a.vel);
+
+        acc.w = 1.0;
+        if (g_len > GRAD_EPS) {
+            let g_hat = g / g_len;
+            acc.y = dot(a.vel, g_hat); // speed up the resource gradient
+            if (v_len > GRAD_EPS) {
+                acc.x = dot(a.vel / v_len, g_hat);
+                acc.z = 1.0;
+            }
+        }
+
+        let idx = cell_index(cell) * 3u;
+        atomicAdd(&velGrid[idx], i32(round(a.vel.x * VEL_SCALE)));
+        atomicAdd(&velGrid[idx + 1u], i32(round(a.vel.y * VEL_SCALE)));
+        atomicAdd(&velGrid[idx + 2u], 1);
+    }
+
+    scratch[lid] = acc;
+    reduce_and_store(lid, wid.x);
+}
+
+fn mean_velocity(p: vec2<i32>) -> vec2<f32> {
+    let q = clamp(p, vec2<i32>(0), vec2<i32>(params.size) - 1);
+    let idx = cell_index(vec2<u32>(q)) * 3u;
+    let n = atomicLoad(&velGrid[idx + 2u]);
+    if (n == 0) {
+        return vec2<f32>(0.0);
+    }
+    let v = vec2<f32>(f32(atomicLoad(&velGrid[idx])), f32(atomicLoad(&velGrid[idx + 1u])));
+    return v / (VEL_SCALE * f32(n));
+}
+
+@compute @workgroup_size(8, 8)
+fn divergence(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_index) lid: u32,
+    @builtin(workgroup_id) wid: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    // (divergence sum, |divergence| sum, occupied cells, _)
+    var acc = vec4<f32>(0.0);
+
+    if (gid.x < params.size.x && gid.y < params.size.y) {
+        let p = vec2<i32>(gid.xy);
+        if (atomicLoad(&velGrid[cell_index(gid.xy) * 3u + 2u]) > 0) {
+            let dvx = mean_velocity(p + vec2<i32>(1, 0)).x - mean_velocity(p - vec2<i32>(1, 0)).x;
+            let dvy = mean_velocity(p + vec2<i32>(0, 1)).y - mean_velocity(p - vec2<i32>(0, 1)).y;
+            let div = 0.5 * (dvx + dvy);
+            acc = vec4<f32>(div, abs(div), 1.0, 0.0);
+        }
+    }
+
+    scratch[lid] = acc;
+    reduce_and_store(lid, params.agent_groups + wid.y * groups.x + wid.x);
+}
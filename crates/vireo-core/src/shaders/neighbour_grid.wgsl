@@ -0,0 +1,36 @@
+// Neighbour grid: every alive agent adds (1, vx, vy) to its cell, fixed-point,
+// and 1 to its species' count. Filled before the agent pass whenever some
+// species crowds, flocks or follows a density.
+
+struct SteeringParams {
+    chemotaxis: vec4<f32>, // Per-kind weights (w unused)
+    noise: vec4<f32>,
+    crowding: vec4<f32>,
+    flocking: vec4<f32>,
+    memory: vec4<f32>,
+    obstacles: vec4<f32>,
+    attraction: array<vec4<f32>, 3>, // [j][k]: weight of kind k on kind j's density gradient
+    size: vec2<u32>,       // World size in cells
+    seed: u32,
+    neighbours: u32,       // 1 when the grid is filled
+}
+
+@group(0) @binding(0) var<storage, read> agents: array<Agent>;
+@group(0) @binding(1) var<storage, read_write> grid: array<atomic<i32>>; // (n, vx, vy, plants, herbivores, predators) per cell
+@group(0) @binding(2) var<uniform> steering: SteeringParams;
+
+const VEL_SCALE: f32 = 1024.0; // Fixed-point scale of the velocity sums
+
+@compute @workgroup_size(128)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= arrayLength(&agents) || agents[i].alive == 0u) { return; }
+
+    let a = agents[i];
+    let cell = vec2<u32>(clamp(a.pos, vec2<f32>(0.0), vec2<f32>(steering.size) - 1.0));
+    let idx = (cell.y * steering.size.x + cell.x) * 6u;
+    atomicAdd(&grid[idx], 1);
+    atomicAdd(&grid[idx + 1u], i32(round(a.vel.x * VEL_SCALE)));
+    atomicAdd(&grid[idx + 2u], i32(round(a.vel.y * VEL_SCALE)));
+    atomicAdd(&grid[idx + 3u + min(a.kind, 2u)], 1);
+}
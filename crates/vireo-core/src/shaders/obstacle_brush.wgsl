@@ -0,0 +1,25 @@
+// Obstacle brush: union a disk into the obstacle distance field, or subtract it.
+// Reads a copy of the field taken just before the pass, since an r32float
+// storage texture cannot be read and written in the same pass.
+
+struct ObstacleBrushParams {
+    center: vec2<f32>, // Disk centre in cells
+    radius: f32,
+    erase: u32,        // 0 = add the disk to the walls, 1 = cut it out
+}
+
+@group(0) @binding(0) var previous: texture_2d<f32>;
+@group(0) @binding(1) var obstacleSdf: texture_storage_2d<r32float, write>;
+@group(0) @binding(2) var<uniform> brush: ObstacleBrushParams;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(previous);
+    if (id.x >= size.x || id.y >= size.y) { return; }
+
+    let xy = vec2<i32>(id.xy);
+    let d = textureLoad(previous, xy, 0).r;
+    let disk = length(vec2<f32>(id.xy) + 0.5 - brush.center) - brush.radius;
+    let painted = select(min(d, disk), max(d, -disk), brush.erase == 1u);
+    textureStore(obstacleSdf, xy, vec4<f32>(painted, 0.0, 0.0, 0.0));
+}
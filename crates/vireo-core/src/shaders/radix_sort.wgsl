@@ -0,0 +1,133 @@
+// LSD radix sort over (key, value) u32 pairs, one 8-bit digit per pass.
+//
+// Three entry points, dispatched in order in separate passes for every digit:
+//   histogram - per block: count each digit into hist[digit * blocks + block]
+//   scan      - single workgroup: exclusive prefix sum over the whole histogram
+//   scatter   - per block: stable scatter of (key, value) to its global offset
+// Flattening the histogram digit-major makes its prefix sum the scatter base of
+// every (digit, block) pair. Stability within a block comes from per-digit
+// 256-bit lane masks: an element's rank is the number of earlier lanes with the
+// same digit.
+
+struct RadixSortParams {
+    len: u32,     // Elements to sort
+    shift: u32,   // Bit offset of this pass's digit
+    blocks: u32,  // Blocks of BLOCK elements (workgroups of `histogram`/`scatter`)
+    _pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read> keysIn: array<u32>;
+@group(0) @binding(1) var<storage, read> valuesIn: array<u32>;
+@group(0) @binding(2) var<storage, read_write> keysOut: array<u32>;
+@group(0) @binding(3) var<storage, read_write> valuesOut: array<u32>;
+@group(0) @binding(4) var<storage, read_write> hist: array<u32>;
+@group(0) @binding(5) var<uniform> params: RadixSortParams;
+
+const RADIX: u32 = 256u;      // Buckets per digit (= workgroup size)
+const BLOCK: u32 = 1024u;     // Elements per histogram/scatter workgroup
+const MASK_WORDS: u32 = 8u;   // u32 words in a 256-lane mask
+
+var<workgroup> counts: array<atomic<u32>, 256>;
+var<workgroup> masks: array<atomic<u32>, 2048>; // RADIX * MASK_WORDS
+var<workgroup> totals: array<u32, 256>;
+
+fn digit_of(key: u32) -> u32 {
+    return (key >> params.shift) & (RADIX - 1u);
+}
+
+@compute @workgroup_size(256)
+fn histogram(
+    @builtin(local_invocation_index) lid: u32,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    atomicStore(&counts[lid], 0u);
+    workgroupBarrier();
+
+    let start = wid.x * BLOCK;
+    let end = min(start + BLOCK, params.len);
+    for (var i = start + lid; i < end; i += RADIX) {
+        atomicAdd(&counts[digit_of(keysIn[i])], 1u);
+    }
+    workgroupBarrier();
+
+    hist[lid * params.blocks + wid.x] = atomicLoad(&counts[lid]);
+}
+
+@compute @workgroup_size(256)
+fn scan(@builtin(local_invocation_index) lid: u32) {
+    // Each thread scans its digit's row of per-block counts
+    let row = lid * params.blocks;
+    var sum = 0u;
+    for (var b = 0u; b < params.blocks; b++) {
+        let c = hist[row + b];
+        hist[row + b] = sum;
+        sum += c;
+    }
+    totals[lid] = sum;
+    workgroupBarrier();
+
+    // Inclusive Hillis-Steele scan of the row totals
+    for (var stride = 1u; stride < RADIX; stride = stride << 1u) {
+        var t = 0u;
+        if (lid >= stride) {
+            t = totals[lid - stride];
+        }
+        workgroupBarrier();
+        totals[lid] += t;
+        workgroupBarrier();
+    }
+
+    let base = totals[lid] - sum;
+    for (var b = 0u; b < params.blocks; b++) {
+        hist[row + b] += base;
+    }
+}
+
+@compute @workgroup_size(256)
+fn scatter(
+    @builtin(local_invocation_index) lid: u32,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    // totals[d]: next output slot for digit d in this block
+    totals[lid] = hist[lid * params.blocks + wid.x];
+
+    let start = wid.x * BLOCK;
+    let word = lid / 32u;
+    let bit = 1u << (lid % 32u);
+    for (var chunk = start; chunk < start + BLOCK; chunk += RADIX) {
+        for (var w = 0u; w < MASK_WORDS; w++) {
+            atomicStore(&masks[lid * MASK_WORDS + w], 0u);
+        }
+        workgroupBarrier();
+
+        let i = chunk + lid;
+        let valid = i < params.len;
+        var key = 0u;
+        var d = 0u;
+        if (valid) {
+            key = keysIn[i];
+            d = digit_of(key);
+            atomicOr(&masks[d * MASK_WORDS + word], bit);
+        }
+        workgroupBarrier();
+
+        if (valid) {
+            var rank = countOneBits(atomicLoad(&masks[d * MASK_WORDS + word]) & (bit - 1u));
+            for (var w = 0u; w < word; w++) {
+                rank += countOneBits(atomicLoad(&masks[d * MASK_WORDS + w]));
+            }
+            let dst = totals[d] + rank;
+            keysOut[dst] = key;
+            valuesOut[dst] = valuesIn[i];
+        }
+        workgroupBarrier();
+
+        // Thread `lid` advances digit `lid` past this chunk's elements
+        var n = 0u;
+        for (var w = 0u; w < MASK_WORDS; w++) {
+            n += countOneBits(atomicLoad(&masks[lid * MASK_WORDS + w]));
+        }
+        totals[lid] += n;
+        workgroupBarrier();
+    }
+}
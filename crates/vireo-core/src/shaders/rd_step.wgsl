@@ -1,7 +1,7 @@
 struct RDParams {
     D_R: f32,      // Resource diffusion coefficient
     D_W: f32,      // Waste diffusion coefficient
-    sigma_R: f32,  // Resource replenishment rate
+    sigma_R: f32,  // Resource logistic growth rate
     alpha_H: f32,  // Herbivore resource uptake rate
     beta_H: f32,   // Herbivore waste emission rate
     lambda_R: f32, // Resource decay rate
@@ -9,18 +9,118 @@ struct RDParams {
     dt: f32,       // Time step
     size: vec2<u32>, // Grid size
     H_SCALE: f32,  // Herbivore density scale factor
-    _pad: u32,     // Padding for alignment
+    K_R: f32,      // Resource carrying capacity per cell
+    obstacles: u32, // 1 = close cell faces by the obstacle distance field
+    anisotropy: f32, // Cross-slope diffusion damping from the elevation (0 = isotropic)
+    soil_threshold: f32,   // Uptake rate the soil tolerates
+    soil_degradation: f32, // Damage per unit of excess uptake (0 = soil never degrades)
+    soil_recovery: f32,    // Fraction of the damage healed per time unit
+    tensor: u32,           // 1 = scale diffusion by the per-cell tensor in `diffusion`
+    noise: f32,            // Standard deviation of R added per √time unit (0 = none)
+    seed: u32,             // Low 32 bits of world.seed
 }
 
-@group(0) @binding(0) var srcTex: texture_2d<f32>;
-@group(0) @binding(1) var dstTex: texture_storage_2d<rgba16float, write>;
+struct TimeUniform {
+    step: u32,
+    time: f32,      // step * dt
+    day_phase: f32, // 0 = midnight, 0.5 = noon
+    light: f32,     // Day/night multiplier on growth and top speed (1 without a cycle)
+}
+
+@group(0) @binding(0) var srcTex: FieldTex;
+@group(0) @binding(1) var dstTex: FieldStorage;
 @group(0) @binding(2) var<uniform> params: RDParams;
 @group(0) @binding(3) var<storage, read> herbDensity: array<u32>; // Herbivore occupancy
+@group(0) @binding(4) var<storage, read> sigmaBoost: array<f32>; // Bloom multiplier on sigma_R
+@group(0) @binding(5) var obstacleSdf: texture_2d<f32>; // Signed distance to the nearest wall (negative inside)
+@group(0) @binding(6) var elevation: texture_2d<f32>; // Terrain height at cell centres
+@group(0) @binding(7) var<uniform> time: TimeUniform;
+@group(0) @binding(8) var diffusion: texture_2d<f32>; // (Dxx, Dyy, Dxy) multipliers of D per cell
+
+// Array layer holding the R, W and soil channels (FieldChannel::Resource/Waste/Soil)
+const RW_LAYER: i32 = 0;
+
+// Standard normal draw for `cell` this step (Box-Muller)
+fn normal(cell: u32) -> f32 {
+    let radius = sqrt(-2.0 * log(1.0 - draw(params.seed, time.step, cell, STREAM_NOISE_RADIUS)));
+    return radius * cos(6.28318530718 * draw(params.seed, time.step, cell, STREAM_NOISE_ANGLE));
+}
 
 fn clamp_i(v: i32, lo: i32, hi: i32) -> i32 {
     return max(lo, min(hi, v));
 }
 
+fn wall_distance(xy: vec2<i32>) -> f32 {
+    return textureLoad(obstacleSdf, xy, 0).r;
+}
+
+// Open fraction of the face between two cells: the distance interpolated to
+// the face midpoint, open half a cell from a wall and closed half a cell inside
+fn face_aperture(phi_a: f32, phi_b: f32) -> f32 {
+    return clamp(0.5 * (phi_a + phi_b) + 0.5, 0.0, 1.0);
+}
+
+// Diffusion weight of a face from the elevation step across it, so spreading
+// along contours is faster than across slopes (ElevationField::face_conductance)
+fn face_conductance(h_a: f32, h_b: f32) -> f32 {
+    return 1.0 / (1.0 + params.anisotropy * abs(h_a - h_b));
+}
+
+// Flux of (R, W) from the neighbour cell nxy into cxy through their shared face.
+// A neighbour whose centre is inside a wall mirrors c, so nothing crosses the interface.
+fn face_flux(c: vec4<f32>, n: vec4<f32>, cxy: vec2<i32>, nxy: vec2<i32>) -> vec2<f32> {
+    var k = 1.0;
+    if (params.obstacles == 1u) {
+        let phi_n = wall_distance(nxy);
+        if (phi_n <= 0.0) {
+            return vec2<f32>(0.0, 0.0);
+        }
+        k = face_aperture(wall_distance(cxy), phi_n);
+    }
+    if (params.anisotropy > 0.0) {
+        k *= face_conductance(textureLoad(elevation, cxy, 0).r, textureLoad(elevation, nxy, 0).r);
+    }
+    if (params.tensor == 1u) {
+        let axis = select(1, 0, nxy.x != cxy.x);
+        k *= 0.5 * (axis_weight(cxy, axis) + axis_weight(nxy, axis));
+    }
+    return k * (n.rg - c.rg);
+}
+
+// Weight of the x (axis 0) or y (axis 1) face of a cell under its diffusion
+// tensor. The |Dxy| moved onto the diagonal links is taken off, floored at 0
+// so every weight stays non-negative and no cell can go negative.
+fn axis_weight(xy: vec2<i32>, axis: i32) -> f32 {
+    let t = textureLoad(diffusion, xy, 0);
+    return max(t[axis] - abs(t.b), 0.0);
+}
+
+// Flux of (R, W) into cxy along the diagonal links that carry the mixed Dxy
+// term: (+1, +1) and (-1, -1) for Dxy > 0, (+1, -1) and (-1, +1) for Dxy < 0,
+// each weighted by |Dxy| averaged over the two cells. A link is closed when
+// it or either cell at its corner is inside a wall.
+fn diagonal_flux(c: vec4<f32>, cxy: vec2<i32>) -> vec2<f32> {
+    let hi = vec2<i32>(params.size) - vec2<i32>(1, 1);
+    let dxy_c = textureLoad(diffusion, cxy, 0).b;
+    var flux = vec2<f32>(0.0, 0.0);
+    for (var i = 0; i < 4; i++) {
+        let o = vec2<i32>(select(-1, 1, (i & 1) == 0), select(-1, 1, i < 2));
+        let nxy = clamp(cxy + o, vec2<i32>(0, 0), hi);
+        if (params.obstacles == 1u) {
+            let corner_x = clamp(cxy + vec2<i32>(o.x, 0), vec2<i32>(0, 0), hi);
+            let corner_y = clamp(cxy + vec2<i32>(0, o.y), vec2<i32>(0, 0), hi);
+            if (min(wall_distance(nxy), min(wall_distance(corner_x), wall_distance(corner_y))) <= 0.0) {
+                continue;
+            }
+        }
+        // Dxy > 0 couples (+1, +1) and (-1, -1), Dxy < 0 the other pair
+        let sign = f32(o.x * o.y);
+        let w = 0.5 * (max(sign * dxy_c, 0.0) + max(sign * textureLoad(diffusion, nxy, 0).b, 0.0));
+        flux += w * (field_load(srcTex, nxy, RW_LAYER).rg - c.rg);
+    }
+    return flux;
+}
+
 @compute @workgroup_size(8, 8)
 fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
     let xy = vec2<i32>(gid.xy);
@@ -30,35 +130,78 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
     let h = i32(params.size.y);
 
     // Load current R, W values
-    let v = textureLoad(srcTex, xy, 0);
+    let v = field_load(srcTex, xy, RW_LAYER);
     var R = v.r; // Resource channel
     var W = v.g; // Waste channel
+    var S = v.b; // Soil damage channel
 
     // 5-point Laplacian with Neumann boundary conditions
     let cx = clamp_i(xy.x, 0, w-1);
     let cy = clamp_i(xy.y, 0, h-1);
     
-    let c = textureLoad(srcTex, vec2<i32>(cx, cy), 0);
-    let l = textureLoad(srcTex, vec2<i32>(clamp_i(cx-1, 0, w-1), cy), 0);
-    let r = textureLoad(srcTex, vec2<i32>(clamp_i(cx+1, 0, w-1), cy), 0);
-    let u = textureLoad(srcTex, vec2<i32>(cx, clamp_i(cy-1, 0, h-1)), 0);
-    let d = textureLoad(srcTex, vec2<i32>(cx, clamp_i(cy+1, 0, h-1)), 0);
+    let c = field_load(srcTex, vec2<i32>(cx, cy), RW_LAYER);
+    let l = field_load(srcTex, vec2<i32>(clamp_i(cx-1, 0, w-1), cy), RW_LAYER);
+    let r = field_load(srcTex, vec2<i32>(clamp_i(cx+1, 0, w-1), cy), RW_LAYER);
+    let u = field_load(srcTex, vec2<i32>(cx, clamp_i(cy-1, 0, h-1)), RW_LAYER);
+    let d = field_load(srcTex, vec2<i32>(cx, clamp_i(cy+1, 0, h-1)), RW_LAYER);
 
-    let lapR = (l.r + r.r + u.r + d.r - 4.0 * c.r);
-    let lapW = (l.g + r.g + u.g + d.g - 4.0 * c.g);
+    var lapR = (l.r + r.r + u.r + d.r - 4.0 * c.r);
+    var lapW = (l.g + r.g + u.g + d.g - 4.0 * c.g);
+
+    // Obstacles: cells inside a wall hold nothing, and faces toward a wall are
+    // closed by the fraction the distance field puts inside it (no-flux at the interface).
+    // Terrain: faces are weighted by the elevation step across them.
+    // Diffusion tensor: faces are weighted by Dxx or Dyy, and diagonal links carry Dxy.
+    if (params.obstacles == 1u || params.anisotropy > 0.0 || params.tensor == 1u) {
+        let cxy = vec2<i32>(cx, cy);
+        if (params.obstacles == 1u && wall_distance(cxy) <= 0.0) {
+            field_store(xy, RW_LAYER, vec4<f32>(0.0, 0.0, 0.0, 0.0));
+            return;
+        }
+        let flux = face_flux(c, l, cxy, vec2<i32>(clamp_i(cx-1, 0, w-1), cy))
+            + face_flux(c, r, cxy, vec2<i32>(clamp_i(cx+1, 0, w-1), cy))
+            + face_flux(c, u, cxy, vec2<i32>(cx, clamp_i(cy-1, 0, h-1)))
+            + face_flux(c, d, cxy, vec2<i32>(cx, clamp_i(cy+1, 0, h-1)));
+        lapR = flux.x;
+        lapW = flux.y;
+        if (params.tensor == 1u) {
+            let diagonal = diagonal_flux(c, cxy);
+            lapR += diagonal.x;
+            lapW += diagonal.y;
+        }
+    }
 
     // Get herbivore density at this cell
     let cell_idx = u32(cy * w + cx);
     let H = min(f32(herbDensity[cell_idx]) * params.H_SCALE, 1.0); // Normalize occupancy with scale
 
+    // Bloom events scale the growth rate locally (boost is 0 outside blooms),
+    // daylight scales it everywhere and damaged soil holds it back
+    let sigma_R = params.sigma_R * (1.0 + sigmaBoost[cell_idx]) * time.light * (1.0 - S);
+
+    // Logistic growth toward the carrying capacity; empty cells regrow by diffusion only
+    let growth = sigma_R * R * (1.0 - R / params.K_R);
+
     // Reaction-diffusion equations
-    let dR = params.D_R * lapR + params.sigma_R - params.alpha_H * H * R - params.lambda_R * R;
+    let uptake = params.alpha_H * H * R;
+    let dR = params.D_R * lapR + growth - uptake - params.lambda_R * R;
     let dW = params.D_W * lapW + params.beta_H * H - params.lambda_W * W;
 
+    // Environmental noise: a Wiener increment of noise·√dt per step, so the
+    // variance it adds grows as noise²·t whatever the time step
+    var noise = 0.0;
+    if (params.noise > 0.0) {
+        noise = params.noise * sqrt(params.dt) * normal(cell_idx);
+    }
+
     // Update with explicit Euler, clamp to non-negative
-    R = max(0.0, R + params.dt * dR);
+    R = max(0.0, R + params.dt * dR + noise);
     W = max(0.0, W + params.dt * dW);
 
+    // Soil: uptake beyond what the soil tolerates scars it, and scars heal slowly
+    let dS = params.soil_degradation * max(uptake - params.soil_threshold, 0.0) - params.soil_recovery * S;
+    S = clamp(S + params.dt * dS, 0.0, 1.0);
+
     // Store result
-    textureStore(dstTex, xy, vec4<f32>(R, W, 0.0, 0.0));
+    field_store(xy, RW_LAYER, vec4<f32>(R, W, S, 0.0));
 }
@@ -0,0 +1,44 @@
+// Resource mip chain: R at full resolution followed by successive 2x2 box averages.
+//
+// Every level lives in one f32 buffer, row-major and back to back. `copy_field`
+// fills level 0 from the field texture; `reduce` writes one coarser level per
+// dispatch from the level before it. On odd sizes the last row or column
+// averages only the source cells it covers.
+
+struct MipLevelParams {
+    src_size: vec2<u32>,
+    dst_size: vec2<u32>,
+    src_offset: u32,
+    dst_offset: u32,
+    _pad: vec2<u32>,
+}
+
+@group(0) @binding(0) var fieldTex: FieldTex;
+@group(0) @binding(1) var<storage, read_write> levels: array<f32>;
+@group(0) @binding(2) var<uniform> params: MipLevelParams;
+
+@compute @workgroup_size(8, 8)
+fn copy_field(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.dst_size.x || gid.y >= params.dst_size.y) {
+        return;
+    }
+    let rw = field_load(fieldTex, vec2<i32>(gid.xy), 0); // layer 0 holds R, W
+    levels[params.dst_offset + gid.y * params.dst_size.x + gid.x] = rw.r;
+}
+
+@compute @workgroup_size(8, 8)
+fn reduce(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.dst_size.x || gid.y >= params.dst_size.y) {
+        return;
+    }
+    let lo = gid.xy * 2u;
+    let hi = min(lo + vec2<u32>(2u), params.src_size);
+    var sum = 0.0;
+    for (var y = lo.y; y < hi.y; y++) {
+        for (var x = lo.x; x < hi.x; x++) {
+            sum += levels[params.src_offset + y * params.src_size.x + x];
+        }
+    }
+    let count = f32((hi.x - lo.x) * (hi.y - lo.y));
+    levels[params.dst_offset + gid.y * params.dst_size.x + gid.x] = sum / count;
+}
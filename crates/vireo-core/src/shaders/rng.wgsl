@@ -0,0 +1,26 @@
+// Random number prelude: a counter-based hash, so a draw depends only on
+// (seed, step, index, stream) and not on thread order.
+
+// PCG-RXS-M-XS output permutation of one LCG step
+fn pcg(v: u32) -> u32 {
+    let state = v * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+// Streams, one per kind of draw. Shaders that hash the same seed, step and
+// index must use different streams, or cell i's noise would be tied to slot i's fate.
+const STREAM_DEATH: u32 = 1u;              // demography.wgsl, per agent slot
+const STREAM_BIRTH: u32 = 2u;
+const STREAM_JITTER_X: u32 = 3u;
+const STREAM_JITTER_Y: u32 = 4u;
+const STREAM_MUTATION_RADIUS: u32 = 5u;
+const STREAM_MUTATION_ANGLE: u32 = 6u;
+const STREAM_NOISE_RADIUS: u32 = 0x100u;   // rd_step.wgsl, per cell
+const STREAM_NOISE_ANGLE: u32 = 0x101u;
+
+// Uniform draw in [0, 1) for `index` on `stream` at `step` of the run seeded with `seed`
+fn draw(seed: u32, step: u32, index: u32, stream: u32) -> f32 {
+    let h = pcg(pcg(pcg(seed ^ pcg(stream)) + step) + index);
+    return f32(h >> 8u) * (1.0 / 16777216.0);
+}
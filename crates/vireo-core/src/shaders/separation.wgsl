@@ -0,0 +1,103 @@
+// Soft-body separation: overlapping agents push each other apart.
+//
+// A spatial hash with one bucket per world cell, in three entry points
+// around a radix sort of the hash keys:
+//   hash        key = cell index of every alive slot (dead slots get the
+//               past-the-end key and sort last), value = slot, and a copy of
+//               (pos, kind, alive) so the push reads a stable state
+//   find_ranges [first, end) of each cell's run in the sorted pairs
+//   push        every alive agent moves away from each neighbour closer than
+//               2 * radius by stiffness * half the overlap, symmetric per pair
+
+struct SeparationParams {
+    size: vec2<u32>,
+    slots: u32,
+    radius: f32,
+    stiffness: f32,
+    cross_species: u32,
+    max_neighbours: u32,
+    _pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
+@group(0) @binding(1) var<uniform> params: SeparationParams;
+@group(0) @binding(2) var<storage, read_write> keys: array<u32>;
+@group(0) @binding(3) var<storage, read_write> slots: array<u32>;
+@group(0) @binding(4) var<storage, read_write> cell_ranges: array<vec2<u32>>; // [first, end) per cell, zeroed before `find_ranges`
+@group(0) @binding(5) var<storage, read_write> positions: array<vec4<f32>>;   // (pos, kind, alive) per slot
+
+const TAU: f32 = 6.2831853;
+
+fn cell_of(pos: vec2<f32>) -> vec2<u32> {
+    return min(vec2<u32>(max(pos, vec2<f32>(0.0, 0.0))), params.size - vec2<u32>(1u, 1u));
+}
+
+@compute @workgroup_size(128)
+fn hash(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.slots) { return; }
+    let a = agents[i];
+    let cell = cell_of(a.pos);
+    keys[i] = select(params.size.x * params.size.y, cell.y * params.size.x + cell.x, a.alive != 0u);
+    slots[i] = i;
+    positions[i] = vec4<f32>(a.pos, f32(a.kind), f32(a.alive));
+}
+
+@compute @workgroup_size(128)
+fn find_ranges(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    let cells = params.size.x * params.size.y;
+    if (i >= params.slots || keys[i] >= cells) { return; }
+    let key = keys[i];
+    if (i == 0u || keys[i - 1u] != key) {
+        cell_ranges[key].x = i;
+    }
+    if (i + 1u == params.slots || keys[i + 1u] != key) {
+        cell_ranges[key].y = i + 1u;
+    }
+}
+
+@compute @workgroup_size(128)
+fn push(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.slots || positions[i].w == 0.0) { return; }
+
+    let p = positions[i].xy;
+    let kind = positions[i].z;
+    let contact = 2.0 * params.radius;
+    let reach = i32(ceil(contact));
+    let cell = vec2<i32>(cell_of(p));
+    var shift = vec2<f32>(0.0, 0.0);
+    var found = 0u;
+
+    for (var dy = -reach; dy <= reach; dy++) {
+        for (var dx = -reach; dx <= reach; dx++) {
+            let c = cell + vec2<i32>(dx, dy);
+            if (c.x < 0 || c.y < 0 || c.x >= i32(params.size.x) || c.y >= i32(params.size.y)) { continue; }
+            let range = cell_ranges[u32(c.y) * params.size.x + u32(c.x)];
+            for (var s = range.x; s < range.y && found < params.max_neighbours; s++) {
+                let j = slots[s];
+                let other = positions[j];
+                if (j == i || (params.cross_species == 0u && other.z != kind)) { continue; }
+                let d = p - other.xy;
+                let dist = length(d);
+                if (dist >= contact) { continue; }
+
+                // Coincident agents split along a direction drawn per pair, opposite for each
+                var dir: vec2<f32>;
+                if (dist > 1e-5) {
+                    dir = d / dist;
+                } else {
+                    let angle = f32(pcg(pcg(min(i, j)) + max(i, j)) >> 8u) * (TAU / 16777216.0);
+                    dir = vec2<f32>(cos(angle), sin(angle)) * select(-1.0, 1.0, i < j);
+                }
+                shift += 0.5 * (contact - dist) * dir;
+                found++;
+            }
+        }
+    }
+
+    if (found > 0u) {
+        agents[i].pos = clamp(p + params.stiffness * shift, vec2<f32>(0.0, 0.0), vec2<f32>(params.size) - vec2<f32>(0.1, 0.1));
+    }
+}
@@ -0,0 +1,30 @@
+// Utility map: the energy landscape as perceived through the chemotaxis gains.
+//
+// One thread per cell writes U = chi_R * S(R) - chi_W * S(W), with the same
+// kappa saturation the agent pass applies to gradients: S(c) = c / (1 + kappa * c).
+
+struct UtilityParams {
+    chi_R: f32,
+    chi_W: f32,
+    kappa: f32,
+    _pad0: u32,
+    size: vec2<u32>,
+    _pad1: vec2<u32>,
+}
+
+@group(0) @binding(0) var fieldTex: FieldTex;
+@group(0) @binding(1) var<uniform> params: UtilityParams;
+@group(0) @binding(2) var<storage, read_write> utility: array<f32>;
+
+fn saturate_conc(c: f32) -> f32 {
+    return c / (1.0 + params.kappa * max(c, 0.0));
+}
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.size.x || gid.y >= params.size.y) {
+        return;
+    }
+    let rw = field_load(fieldTex, vec2<i32>(gid.xy), 0); // layer 0 holds R, W
+    utility[gid.y * params.size.x + gid.x] = params.chi_R * saturate_conc(rw.r) - params.chi_W * saturate_conc(rw.g);
+}
@@ -0,0 +1,46 @@
+use vireo_params::SPECIES;
+use crate::sim::Agent;
+
+/// Alive agents per age class and species (a population pyramid)
+///
+/// Class `i` holds ages `i·bin_steps .. (i + 1)·bin_steps`; the last class is
+/// open-ended and takes every older agent, so nobody falls off the chart. A
+/// population whose young classes empty out is ageing towards extinction even
+/// while its total still looks healthy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgeStructure {
+    pub bin_steps: u32,
+    pub counts: Vec<[u32; SPECIES.len()]>, // Indexed by class, then agent kind
+}
+
+impl AgeStructure {
+    /// Bin the alive `agents` into `classes` classes of `bin_steps` steps
+    pub fn of(agents: &[Agent], bin_steps: u32, classes: u32) -> Self {
+        let bin_steps = bin_steps.max(1);
+        let mut counts = vec![[0; SPECIES.len()]; classes.max(1) as usize];
+        let last = counts.len() - 1;
+        for a in agents.iter().filter(|a| a.is_alive()) {
+            let class = ((a.age / bin_steps) as usize).min(last);
+            if let Some(count) = counts[class].get_mut(a.kind as usize) {
+                *count += 1;
+            }
+        }
+        Self { bin_steps, counts }
+    }
+
+    /// First age of `class` and the first age past it (`None` for the open-ended last class)
+    pub fn class_range(&self, class: usize) -> (u32, Option<u32>) {
+        let start = class as u32 * self.bin_steps;
+        (start, (class + 1 < self.counts.len()).then_some(start + self.bin_steps))
+    }
+
+    /// Alive agents of `kind` over every class
+    pub fn total(&self, kind: usize) -> u32 {
+        self.counts.iter().map(|c| c[kind]).sum()
+    }
+
+    /// Largest class summed over species, the height of the tallest stacked bar
+    pub fn peak(&self) -> u32 {
+        self.counts.iter().map(|c| c.iter().sum()).max().unwrap_or(0)
+    }
+}
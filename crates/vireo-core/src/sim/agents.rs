@@ -1,7 +1,10 @@
 use bytemuck::{Pod, Zeroable};
 use glam::Vec2;
-use rand_chacha::ChaCha8Rng;
-use rand::{Rng, SeedableRng};
+use half::f16;
+use rand::Rng;
+use std::ops::Range;
+use vireo_params::{AgentConfig, GrowthConfig, RngKind, SimulationConfig, SpeciesCapacity};
+use crate::sim::{SimRng, UNTAGGED};
 
 /// Agent data structure for GPU compute
 #[repr(C)]
@@ -12,9 +15,42 @@ pub struct Agent {
     pub energy: f32,        // Current energy
     pub alive: u32,         // Alive flag (1 = alive, 0 = dead)
     pub kind: u32,          // Agent type: 0 = plant, 1 = herbivore, 2 = predator
+    pub cohort: u32,        // Cohort tag (0 = untagged, see sim::cohort)
+    pub memory_pos: [f32; 2], // Remembered best-resource site (spawn or birth position at first)
+    pub memory_value: f32,  // Decayed resource value remembered at `memory_pos`
+    pub state: u32,         // Behaviour: `Agent::FORAGING` or `Agent::DISPERSING`
+    pub age: u32,           // Steps alive since spawn or birth
+    pub chi_gain: f32,      // Heritable multiplier on `chemotaxis.chi_R` (see `evolution`)
+    pub kappa_gain: f32,    // Heritable multiplier on `chemotaxis.kappa`
+    pub parent_gains: u32,  // Parent's (chi_gain, kappa_gain) at birth as two f16 (0 = spawned, no parent)
 }
 
 impl Agent {
+    /// Follows the field gradients (the only state unless `chemotaxis.disperse_energy` > 0)
+    pub const FORAGING: u32 = 0;
+    /// Starving: ignores gradients and moves straight on at `disperse_speed`·v_max
+    pub const DISPERSING: u32 = 1;
+
+    /// WGSL declaration of every field: name, type and byte offset in the Rust struct
+    ///
+    /// `shaders::agent_prelude` generates `struct Agent` from this table, so the
+    /// shaders cannot drift from the buffer layout uploaded here.
+    pub const WGSL_FIELDS: [(&'static str, &'static str, usize); 13] = [
+        ("pos", "vec2<f32>", std::mem::offset_of!(Agent, pos)),
+        ("vel", "vec2<f32>", std::mem::offset_of!(Agent, vel)),
+        ("energy", "f32", std::mem::offset_of!(Agent, energy)),
+        ("alive", "u32", std::mem::offset_of!(Agent, alive)),
+        ("kind", "u32", std::mem::offset_of!(Agent, kind)),
+        ("cohort", "u32", std::mem::offset_of!(Agent, cohort)),
+        ("memory_pos", "vec2<f32>", std::mem::offset_of!(Agent, memory_pos)),
+        ("memory_value", "f32", std::mem::offset_of!(Agent, memory_value)),
+        ("state", "u32", std::mem::offset_of!(Agent, state)),
+        ("age", "u32", std::mem::offset_of!(Agent, age)),
+        ("chi_gain", "f32", std::mem::offset_of!(Agent, chi_gain)),
+        ("kappa_gain", "f32", std::mem::offset_of!(Agent, kappa_gain)),
+        ("parent_gains", "u32", std::mem::offset_of!(Agent, parent_gains)),
+    ];
+
     pub fn new(pos: Vec2, energy: f32, kind: u32) -> Self {
         Self {
             pos: [pos.x, pos.y],
@@ -22,9 +58,22 @@ impl Agent {
             energy,
             alive: 1,
             kind,
+            cohort: UNTAGGED,
+            memory_pos: [pos.x, pos.y],
+            memory_value: 0.0,
+            state: Self::FORAGING,
+            age: 0,
+            chi_gain: 1.0,
+            kappa_gain: 1.0,
+            parent_gains: 0,
         }
     }
 
+    /// Unoccupied slot in the range of `kind`, waiting for a birth
+    pub fn free(kind: u32) -> Self {
+        Self { alive: 0, ..Self::new(Vec2::ZERO, 0.0, kind) }
+    }
+
     pub fn position(&self) -> Vec2 {
         Vec2::new(self.pos[0], self.pos[1])
     }
@@ -33,10 +82,31 @@ impl Agent {
         Vec2::new(self.vel[0], self.vel[1])
     }
 
+    /// Remembered best-resource site (see `chemotaxis.memory_weight`)
+    pub fn memory_position(&self) -> Vec2 {
+        Vec2::new(self.memory_pos[0], self.memory_pos[1])
+    }
+
+    /// Parent's (chi_gain, kappa_gain) when this agent was born, `None` for spawned agents
+    pub fn parent_traits(&self) -> Option<(f32, f32)> {
+        let half = |bits: u32| f16::from_bits(bits as u16).to_f32();
+        (self.parent_gains != 0).then(|| (half(self.parent_gains), half(self.parent_gains >> 16)))
+    }
+
+    /// Record the parent's multipliers, packed as `pack2x16float` in demography.wgsl does
+    pub fn set_parent_traits(&mut self, chi_gain: f32, kappa_gain: f32) {
+        let half = |value: f32| f16::from_f32(value).to_bits() as u32;
+        self.parent_gains = half(chi_gain) | half(kappa_gain) << 16;
+    }
+
     pub fn is_alive(&self) -> bool {
         self.alive == 1
     }
 
+    pub fn is_dispersing(&self) -> bool {
+        self.state == Self::DISPERSING
+    }
+
     pub fn kill(&mut self) {
         self.alive = 0;
     }
@@ -49,7 +119,10 @@ pub struct AgentStats {
     pub total_energy: f32,
     pub mean_energy: f32,
     pub mean_velocity: f32,
-    pub foraging_efficiency: f32,
+    pub foraging_efficiency: f32, // Resource consumed per cell moved since the previous update
+    pub dispersing_count: u32, // Alive agents in the dispersing state
+    pub consumed: f64, // Resource consumed by all agents since spawn (from the foraging tally)
+    pub distance: f64, // Cells moved by all agents since spawn
 }
 
 impl Default for AgentStats {
@@ -60,7 +133,84 @@ impl Default for AgentStats {
             mean_energy: 0.0,
             mean_velocity: 0.0,
             foraging_efficiency: 0.0,
+            dispersing_count: 0,
+            consumed: 0.0,
+            distance: 0.0,
+        }
+    }
+}
+
+/// Aggregate movement metrics computed on the GPU (see `gpu::MovementMetrics`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MovementStats {
+    pub mean_divergence: f32,     // Mean ∇·v̄ over occupied cells (< 0: converging)
+    pub mean_abs_divergence: f32, // Mean |∇·v̄| over occupied cells
+    pub net_flux: f32,            // Mean speed up the resource gradient, v·∇R/|∇R|
+    pub mean_alignment: f32,      // Mean cosine between velocity and ∇R
+}
+
+/// Slot range of each species in the shared agent buffer
+///
+/// Plants, herbivores and predators occupy consecutive ranges, each with its
+/// own capacity. The agent and demography passes dispatch one range at a time,
+/// so a small species does not pay for the threads of a large one; births stay
+/// inside their parent's range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentLayout {
+    ranges: [Range<u32>; 3],
+}
+
+impl AgentLayout {
+    /// Consecutive ranges of `slots[kind]` slots, plants first
+    pub fn new(slots: [u32; 3]) -> Self {
+        let mut start = 0;
+        Self {
+            ranges: slots.map(|n| {
+                start += n;
+                start - n..start
+            }),
+        }
+    }
+
+    /// Slots of agent `kind` (0 = plant, 1 = herbivore, 2 = predator)
+    pub fn range(&self, kind: u32) -> Range<u32> {
+        self.ranges[kind as usize].clone()
+    }
+
+    pub fn capacity(&self, kind: u32) -> u32 {
+        self.ranges[kind as usize].len() as u32
+    }
+
+    /// Slots over all kinds
+    pub fn total(&self) -> u32 {
+        self.ranges[2].end
+    }
+
+    /// Kind whose range holds `slot`
+    pub fn kind_of(&self, slot: u32) -> Option<u32> {
+        self.ranges.iter().position(|r| r.contains(&slot)).map(|kind| kind as u32)
+    }
+
+    /// Where `slot` of this layout lands in `to` (`None` past the end of a smaller range)
+    pub fn remap(&self, slot: u32, to: &AgentLayout) -> Option<u32> {
+        let kind = self.kind_of(slot)?;
+        let index = slot - self.ranges[kind as usize].start;
+        (index < to.capacity(kind)).then(|| to.range(kind).start + index)
+    }
+
+    /// Per-slot `items` of this layout rearranged for `to`
+    ///
+    /// Each kind keeps its slots in order at the start of its new range; slots
+    /// a range gains are `fill(kind)` and slots it loses are dropped.
+    pub fn relayout<T: Clone>(&self, items: &[T], to: &AgentLayout, fill: impl Fn(u32) -> T) -> Vec<T> {
+        assert_eq!(items.len(), self.total() as usize, "items do not match the layout");
+        let mut out = Vec::with_capacity(to.total() as usize);
+        for kind in 0..3 {
+            let range = self.range(kind);
+            out.extend(items[range.start as usize..range.end as usize].iter().take(to.capacity(kind) as usize).cloned());
+            out.resize(to.range(kind).end as usize, fill(kind));
         }
+        out
     }
 }
 
@@ -68,19 +218,31 @@ impl Default for AgentStats {
 pub struct AgentManager {
     pub agents: Vec<Agent>,
     pub stats: AgentStats,
+    pub layout: AgentLayout,
+    pub forage: Vec<[f32; 2]>, // (consumed, distance) per slot, refreshed with the agents (`gpu::ForagingTally`)
 }
 
 impl AgentManager {
+    /// Spawn the initial population using the default generator seeded with `seed`
     pub fn new(herbivore_count: u32, world_size: [f32; 2], initial_energy: f32, seed: u64) -> Self {
-        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
-        
-        // Calculate target counts for each type
-        let total_agents = herbivore_count * 3; // Total agents including plants and predators
-        let plant_count = total_agents / 6; // ~16.7% plants
-        let predator_count = total_agents / 6; // ~16.7% predators
-        let actual_herbivore_count = total_agents - plant_count - predator_count; // ~66.6% herbivores
-        
-        let mut agents = Vec::with_capacity(total_agents as usize);
+        Self::with_rng(herbivore_count, world_size, initial_energy, &mut SimRng::new(RngKind::default(), seed))
+    }
+
+    /// Spawn the initial population drawing all randomness from `rng`
+    pub fn with_rng(herbivore_count: u32, world_size: [f32; 2], initial_energy: f32, rng: &mut SimRng) -> Self {
+        let config = AgentConfig { herbivores: herbivore_count, E0: initial_energy, capacity: SpeciesCapacity::default(), growth: GrowthConfig::default() };
+        Self::spawn(&config, world_size, rng)
+    }
+
+    /// Spawn `config.agents` into per-species ranges of `config.slots()`
+    ///
+    /// Every agent of `spawn_counts` is drawn, so the random stream does not
+    /// depend on the capacities; agents past a species' capacity are dropped
+    /// and unused slots start free.
+    pub fn spawn(config: &AgentConfig, world_size: [f32; 2], rng: &mut SimRng) -> Self {
+        let initial_energy = config.E0;
+        let [plant_count, actual_herbivore_count, predator_count] = config.spawn_counts();
+        let mut agents = Vec::with_capacity((plant_count + actual_herbivore_count + predator_count) as usize);
         
         // Spawn plants first - distribute them evenly across the world
         for i in 0..plant_count {
@@ -124,17 +286,67 @@ impl AgentManager {
             agents.push(agent);
         }
         
+        // Fit each species into its range, padding with free slots of that kind
+        let layout = AgentLayout::new(config.slots());
+        let mut spawned = agents.into_iter().peekable();
+        let mut slots = Vec::with_capacity(layout.total() as usize);
+        for kind in 0..3 {
+            let of_kind: Vec<Agent> = std::iter::from_fn(|| spawned.next_if(|a| a.kind == kind)).collect();
+            slots.extend(of_kind.into_iter().take(layout.capacity(kind) as usize));
+            slots.resize(layout.range(kind).end as usize, Agent::free(kind));
+        }
+        
         Self {
-            agents,
+            forage: vec![[0.0; 2]; slots.len()],
+            agents: slots,
             stats: AgentStats::default(),
+            layout,
         }
     }
     
+    /// Bring up to `count` agents of `kind` to life at uniform points of the
+    /// disc `center`/`radius` (clamped to the world of `config`), in the first
+    /// free slots of the species' range; returns how many fitted
+    ///
+    /// Newcomers start at rest with the energy the initial spawn gives their species.
+    pub fn spawn_within(&mut self, config: &SimulationConfig, kind: u32, center: [f32; 2], radius: f32, count: u32, rng: &mut SimRng) -> u32 {
+        let energy = config.agents.E0 * [1.5, 1.0, 1.2][kind.min(2) as usize];
+        let world_size = [config.world.size[0] as f32, config.world.size[1] as f32];
+        let range = self.layout.range(kind);
+        let free = self.agents[range.start as usize..range.end as usize].iter_mut().filter(|a| !a.is_alive());
+        let mut spawned = 0;
+        for agent in free.take(count as usize) {
+            // sqrt of a uniform radius fraction spreads points evenly over the disc
+            let r = radius * rng.gen_range(0.0f32..1.0).sqrt();
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let pos = Vec2::new(
+                (center[0] + r * angle.cos()).clamp(0.0, world_size[0] - 0.1),
+                (center[1] + r * angle.sin()).clamp(0.0, world_size[1] - 0.1),
+            );
+            *agent = Agent::new(pos, energy, kind);
+            spawned += 1;
+        }
+        spawned
+    }
+    
+    /// Recompute `stats` from `agents` and the foraging tally in `forage`
+    ///
+    /// Foraging efficiency is the resource consumed per cell moved between
+    /// this update and the previous one, over every agent that lived in between.
     pub fn update_stats(&mut self) {
+        let consumed: f64 = self.forage.iter().map(|f| f[0] as f64).sum();
+        let distance: f64 = self.forage.iter().map(|f| f[1] as f64).sum();
+        let moved = distance - self.stats.distance;
+        let foraging_efficiency = if moved > 0.0 {
+            ((consumed - self.stats.consumed) / moved) as f32
+        } else {
+            0.0
+        };
+
         let alive_agents: Vec<_> = self.agents.iter().filter(|a| a.is_alive()).collect();
         
         if alive_agents.is_empty() {
-            self.stats = AgentStats::default();
+            self.stats = AgentStats { foraging_efficiency, consumed, distance, ..AgentStats::default() };
             return;
         }
         
@@ -147,19 +359,15 @@ impl AgentManager {
             .sum();
         let mean_velocity = total_velocity / alive_count as f32;
         
-        // Simple foraging efficiency: energy / (distance traveled)
-        let foraging_efficiency = if mean_velocity > 0.0 {
-            mean_energy / mean_velocity
-        } else {
-            0.0
-        };
-        
         self.stats = AgentStats {
             alive_count,
             total_energy,
             mean_energy,
             mean_velocity,
             foraging_efficiency,
+            dispersing_count: alive_agents.iter().filter(|a| a.is_dispersing()).count() as u32,
+            consumed,
+            distance,
         };
     }
     
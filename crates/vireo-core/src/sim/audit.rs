@@ -0,0 +1,171 @@
+use vireo_params::{FieldChannel, SPECIES};
+use crate::sim::{Agent, FieldManager, ObstacleField};
+
+/// Quantile levels reported for every field channel
+pub const AUDIT_QUANTILES: [f32; 7] = [0.0, 0.01, 0.25, 0.5, 0.75, 0.99, 1.0];
+
+/// Distribution of one field channel over every cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelAudit {
+    pub channel: FieldChannel,
+    pub mean: f32,
+    pub zero_fraction: f32,    // Cells at exactly 0
+    pub non_finite: u32,       // NaN or infinite cells (left out of the rest)
+    pub quantiles: [f32; AUDIT_QUANTILES.len()], // At `AUDIT_QUANTILES`, linearly interpolated
+}
+
+/// Where the alive agents of one species start
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeciesAudit {
+    pub kind: u32,
+    pub count: u32,
+    pub histogram: Vec<u32>,   // Agents per bin, row-major over `InitAudit::bins`
+    pub occupied_bins: u32,
+    pub mean_energy: f32,
+    pub min_energy: f32,
+    pub max_energy: f32,
+    pub mean_resource: f32,    // Mean R of the cells they stand on
+    pub on_bare: u32,          // Standing on cells with R = 0
+    pub in_obstacles: u32,     // Inside a wall (negative obstacle distance)
+    pub outside: u32,          // Outside the world
+    pub no_energy: u32,        // Energy ≤ 0
+}
+
+/// Statistics of a freshly seeded world, before its first step
+///
+/// Catches bad initial conditions (an empty field, agents spawned inside
+/// walls or on bare ground) before a long run is started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitAudit {
+    pub size: [u32; 2],
+    pub bins: [u32; 2],
+    pub channels: Vec<ChannelAudit>,  // One per `FieldChannel::ALL`
+    pub species: Vec<SpeciesAudit>,   // One per `SPECIES`
+}
+
+impl InitAudit {
+    /// Audit `field` and the alive `agents`, binning positions into a
+    /// `bins`×`bins` grid (fewer along an axis shorter than `bins` cells)
+    pub fn of(field: &FieldManager, agents: &[Agent], obstacles: &ObstacleField, bins: u32) -> Self {
+        let size = field.size;
+        let bins = [bins.clamp(1, size[0]), bins.clamp(1, size[1])];
+        let channels = FieldChannel::ALL.iter().map(|&c| ChannelAudit::of(c, &field.channel_f32(c))).collect();
+
+        let mut species: Vec<SpeciesAudit> = (0..SPECIES.len() as u32).map(|kind| SpeciesAudit {
+            kind,
+            count: 0,
+            histogram: vec![0; (bins[0] * bins[1]) as usize],
+            occupied_bins: 0,
+            mean_energy: 0.0,
+            min_energy: 0.0,
+            max_energy: 0.0,
+            mean_resource: 0.0,
+            on_bare: 0,
+            in_obstacles: 0,
+            outside: 0,
+            no_energy: 0,
+        }).collect();
+        let mut energy = vec![(0.0f64, f32::INFINITY, f32::NEG_INFINITY); SPECIES.len()];
+        let mut resource = vec![0.0f64; SPECIES.len()];
+        for a in agents.iter().filter(|a| a.is_alive() && (a.kind as usize) < SPECIES.len()) {
+            let s = &mut species[a.kind as usize];
+            s.count += 1;
+            let e = &mut energy[a.kind as usize];
+            *e = (e.0 + a.energy as f64, e.1.min(a.energy), e.2.max(a.energy));
+            if a.energy <= 0.0 {
+                s.no_energy += 1;
+            }
+            let inside = (0..2).all(|axis| a.pos[axis] >= 0.0 && a.pos[axis] < size[axis] as f32);
+            if !inside {
+                s.outside += 1;
+            }
+            if obstacles.sample(a.pos) < 0.0 {
+                s.in_obstacles += 1;
+            }
+            let cell = |axis: usize| (a.pos[axis].max(0.0) as u32).min(size[axis] - 1);
+            let r = field.get_resource(cell(0), cell(1));
+            resource[a.kind as usize] += r as f64;
+            if r <= 0.0 {
+                s.on_bare += 1;
+            }
+            let bin = |axis: usize| ((a.pos[axis] / size[axis] as f32 * bins[axis] as f32).max(0.0) as u32).min(bins[axis] - 1);
+            s.histogram[(bin(1) * bins[0] + bin(0)) as usize] += 1;
+        }
+        for (s, (&(sum, min, max), &r)) in species.iter_mut().zip(energy.iter().zip(&resource)) {
+            s.occupied_bins = s.histogram.iter().filter(|&&n| n > 0).count() as u32;
+            if s.count > 0 {
+                s.mean_energy = (sum / s.count as f64) as f32;
+                s.min_energy = min;
+                s.max_energy = max;
+                s.mean_resource = (r / s.count as f64) as f32;
+            }
+        }
+
+        Self { size, bins, channels, species }
+    }
+
+    /// Problems worth fixing before the run, one line each (empty when none)
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for c in &self.channels {
+            if c.non_finite > 0 {
+                warnings.push(format!("{} has {} non-finite cells", c.channel.name(), c.non_finite));
+            }
+        }
+        if let Some(r) = self.channels.iter().find(|c| c.channel == FieldChannel::Resource) {
+            if r.quantiles[AUDIT_QUANTILES.len() - 1] <= 0.0 {
+                warnings.push("resource is zero everywhere".to_string());
+            }
+        }
+        if self.species.iter().all(|s| s.count == 0) {
+            warnings.push("no alive agents".to_string());
+        }
+        for s in self.species.iter().filter(|s| s.count > 0) {
+            let name = SPECIES[s.kind as usize];
+            for (n, what) in [
+                (s.outside, "start outside the world"),
+                (s.in_obstacles, "start inside obstacles"),
+                (s.no_energy, "start without energy"),
+            ] {
+                if n > 0 {
+                    warnings.push(format!("{} of {} {} {}", n, s.count, name, what));
+                }
+            }
+            if s.on_bare * 2 > s.count {
+                warnings.push(format!("{} of {} {} start on cells without resource", s.on_bare, s.count, name));
+            }
+        }
+        warnings
+    }
+}
+
+impl ChannelAudit {
+    /// Summarize one channel's row-major `values`
+    pub fn of(channel: FieldChannel, values: &[f32]) -> Self {
+        let mut sorted: Vec<f32> = values.iter().copied().filter(|v| v.is_finite()).collect();
+        sorted.sort_by(f32::total_cmp);
+        let non_finite = (values.len() - sorted.len()) as u32;
+        let quantiles = AUDIT_QUANTILES.map(|q| quantile(&sorted, q));
+        let (mean, zero_fraction) = if sorted.is_empty() {
+            (0.0, 0.0)
+        } else {
+            let n = sorted.len() as f64;
+            let mean = sorted.iter().map(|&v| v as f64).sum::<f64>() / n;
+            let zeros = sorted.iter().filter(|&&v| v == 0.0).count() as f64;
+            (mean as f32, (zeros / n) as f32)
+        };
+        Self { channel, mean, zero_fraction, non_finite, quantiles }
+    }
+}
+
+/// Linearly interpolated `q`-quantile of ascending `sorted` (0 when empty)
+fn quantile(sorted: &[f32], q: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f32;
+    let lower = position.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let t = position - lower as f32;
+    sorted[lower] * (1.0 - t) + sorted[upper] * t
+}
@@ -0,0 +1,92 @@
+use rand::Rng;
+use vireo_params::{BloomConfig, BloomParams, MAX_BLOOMS, MAX_RAIN_CELLS, WorldConfig};
+use crate::sim::SimRng;
+
+/// Offset mixed into the world seed so blooms draw from their own stream
+const BLOOM_SEED_SALT: u64 = 0xB100_3B10_0D5E_ED00;
+
+/// A single active bloom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bloom {
+    pub center: [f32; 2],
+    pub radius: f32,
+    pub amplitude: f32,
+    pub start_step: u32,
+    pub end_step: u32, // First step the bloom is no longer active
+}
+
+/// CPU-side scheduler for resource bloom events
+///
+/// Each step a new bloom starts with probability `rate` at a uniformly random
+/// centre, and lives for `duration` steps. The scheduler owns its own RNG
+/// stream so enabling blooms does not perturb field or agent seeding.
+#[derive(Debug, Clone)]
+pub struct BloomScheduler {
+    config: BloomConfig,
+    size: [u32; 2],
+    rng: SimRng,
+    active: Vec<Bloom>,
+}
+
+impl BloomScheduler {
+    pub fn new(config: BloomConfig, world: &WorldConfig) -> Self {
+        Self {
+            config,
+            size: world.size,
+            rng: SimRng::new(world.rng, world.seed ^ BLOOM_SEED_SALT),
+            active: Vec::with_capacity(MAX_BLOOMS),
+        }
+    }
+
+    /// Advance to `step`, expiring and spawning blooms; returns true if the active set changed
+    pub fn tick(&mut self, step: u32) -> bool {
+        let before = self.active.len();
+        self.active.retain(|b| b.end_step > step);
+        let mut changed = self.active.len() != before;
+
+        if self.config.enabled
+            && self.active.len() < MAX_BLOOMS
+            && self.rng.gen::<f32>() < self.config.rate
+        {
+            let center = [
+                self.rng.gen_range(0.0..self.size[0] as f32),
+                self.rng.gen_range(0.0..self.size[1] as f32),
+            ];
+            self.active.push(Bloom {
+                center,
+                radius: self.config.radius,
+                amplitude: self.config.amplitude,
+                start_step: step,
+                end_step: step.saturating_add(self.config.duration),
+            });
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Currently active blooms
+    pub fn active(&self) -> &[Bloom] {
+        &self.active
+    }
+
+    /// Pack the active set for the injection shader (rain cells are added by `RainScheduler::pack`)
+    pub fn to_params(&self) -> BloomParams {
+        let mut blooms = [[0.0; 4]; MAX_BLOOMS];
+        for (slot, b) in blooms.iter_mut().zip(&self.active) {
+            *slot = [b.center[0], b.center[1], b.radius, b.amplitude];
+        }
+        BloomParams {
+            size: self.size,
+            count: self.active.len() as u32,
+            rain_count: 0,
+            blooms,
+            rain: [[0.0; 4]; MAX_RAIN_CELLS],
+        }
+    }
+
+    /// Drop all active blooms and restart the RNG stream (world reset)
+    pub fn reset(&mut self, world: &WorldConfig) {
+        *self = Self::new(self.config.clone(), world);
+    }
+}
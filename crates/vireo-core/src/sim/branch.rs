@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use vireo_params::{SimulationConfig, SCHEDULABLE_PARAMS};
+
+/// A `--branch` plan: one checkpoint and the parameter variants run forward from it
+///
+/// Every variant restores the same `StateSnapshot`, so differences between
+/// their runs come from the parameters alone and not from initial-condition
+/// noise. Written as YAML:
+///
+/// ```yaml
+/// checkpoint: results/snapshot_1000.bin
+/// steps: 500              # optional, defaults to the rest of world.steps
+/// variants:
+///   - name: baseline
+///   - name: strong_chemotaxis
+///     params: { chemotaxis.chi_R: 12.0 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BranchPlan {
+    pub checkpoint: PathBuf,
+    #[serde(default)]
+    pub steps: Option<u32>, // Steps past the checkpoint
+    pub variants: Vec<BranchVariant>,
+}
+
+/// One named set of parameter overrides
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BranchVariant {
+    pub name: String, // Also the variant's output directory
+    #[serde(default)]
+    pub params: BTreeMap<String, f32>, // Dotted path (one of `SCHEDULABLE_PARAMS`) -> value
+}
+
+impl BranchPlan {
+    /// Parse a plan and check its variants (names usable as directories, known parameters)
+    pub fn parse(yaml: &str) -> Result<Self, String> {
+        let plan: Self = serde_yaml::from_str(yaml).map_err(|e| format!("cannot parse branch plan: {}", e))?;
+        if plan.variants.is_empty() {
+            return Err("branch plan has no variants".to_string());
+        }
+        for (i, v) in plan.variants.iter().enumerate() {
+            let usable = !v.name.is_empty()
+                && v.name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                && v.name != "." && v.name != "..";
+            if !usable {
+                return Err(format!("variant {}: name `{}` must be letters, digits, `_`, `-` or `.`", i, v.name));
+            }
+            if plan.variants[..i].iter().any(|w| w.name == v.name) {
+                return Err(format!("variant name `{}` is used twice", v.name));
+            }
+            for (param, value) in &v.params {
+                if !SCHEDULABLE_PARAMS.contains(&param.as_str()) {
+                    return Err(format!("variant `{}`: `{}` cannot be set; use one of {}", v.name, param, SCHEDULABLE_PARAMS.join(", ")));
+                }
+                if !value.is_finite() {
+                    return Err(format!("variant `{}`: {} must be finite, got {}", v.name, param, value));
+                }
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Read and parse a plan file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| format!("cannot read branch plan {}: {}", path.display(), e))?;
+        Self::parse(&yaml)
+    }
+
+    /// Step every variant runs to, starting from a checkpoint taken at `from`
+    pub fn end_step(&self, from: u32, world_steps: u32) -> Result<u32, String> {
+        match self.steps {
+            Some(0) => Err("branch plan steps must be greater than 0".to_string()),
+            Some(steps) => from.checked_add(steps).ok_or_else(|| format!("{} steps past step {} overflow", steps, from)),
+            None if world_steps > from => Ok(world_steps),
+            None => Err(format!("the checkpoint is at step {}, past world.steps = {}; set `steps` in the branch plan", from, world_steps)),
+        }
+    }
+
+    /// Parameters set by any variant, sorted (the summary columns)
+    pub fn params(&self) -> Vec<&str> {
+        let mut params: Vec<&str> = self.variants.iter().flat_map(|v| v.params.keys().map(String::as_str)).collect();
+        params.sort_unstable();
+        params.dedup();
+        params
+    }
+}
+
+impl BranchVariant {
+    /// `base` with this variant's parameters set and validated
+    ///
+    /// A schedule on an overridden parameter is dropped, so the variant's value
+    /// holds for the whole branch.
+    pub fn apply(&self, base: &SimulationConfig) -> Result<SimulationConfig, String> {
+        let mut config = base.clone();
+        config.schedules.retain(|s| !self.params.contains_key(&s.param));
+        for (param, &value) in &self.params {
+            if let Some(slot) = config.scalar_mut(param) {
+                *slot = value;
+            }
+        }
+        config.validate().map_err(|e| format!("variant `{}`: {}", self.name, e))?;
+        Ok(config)
+    }
+}
@@ -0,0 +1,397 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use vireo_params::CheckpointChainConfig;
+use crate::sim::{Agent, FieldData, StateSnapshot};
+use crate::sim::snapshot::read_records;
+
+/// File signature of a checkpoint delta
+const MAGIC: &[u8; 8] = b"VIREODLT";
+/// Bumped whenever the delta layout changes (`StateSnapshot`'s own version covers the records)
+const VERSION: u32 = 1;
+/// Magic, then version, step, base step, width, height, tile edge, agent
+/// count, changed tiles and changed slots, then the checksum
+const HEADER_BYTES: usize = 8 + 9 * 4 + 8;
+
+/// Origin and extent of tile `index` of a `size` world cut into `tile`-cell squares
+///
+/// Tiles are numbered row-major; those on the right and bottom edges are
+/// clipped to the world.
+fn tile_rect(size: [u32; 2], tile: u32, index: u32) -> ([u32; 2], [u32; 2]) {
+    let across = size[0].div_ceil(tile);
+    let origin = [index % across * tile, index / across * tile];
+    (origin, [tile.min(size[0] - origin[0]), tile.min(size[1] - origin[1])])
+}
+
+fn tile_count(size: [u32; 2], tile: u32) -> u32 {
+    size[0].div_ceil(tile) * size[1].div_ceil(tile)
+}
+
+/// Cells of tile `index`, row-major within the tile
+fn tile_cells(field: &[FieldData], size: [u32; 2], tile: u32, index: u32) -> Vec<FieldData> {
+    let ([x, y], [w, h]) = tile_rect(size, tile, index);
+    (y..y + h)
+        .flat_map(|row| {
+            let start = (row * size[0] + x) as usize;
+            field[start..start + w as usize].iter().copied()
+        })
+        .collect()
+}
+
+/// Append `value` as LEB128
+fn push_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Read a LEB128 value at `*at`, advancing past it
+fn read_varint(bytes: &[u8], at: &mut usize) -> Option<usize> {
+    let mut value = 0usize;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*at)?;
+        *at += 1;
+        value |= ((byte & 0x7f) as usize).checked_shl(shift)?;
+        if byte < 0x80 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// `bytes` as alternating runs: a LEB128 count of zero bytes, a LEB128 count
+/// of literal bytes, then the literals
+///
+/// XOR against the previous checkpoint zeroes every unchanged byte, and the
+/// half-precision field leaves the low mantissa bytes of each f32 zero, so the
+/// runs are long.
+fn pack_zero_runs(bytes: &[u8]) -> Vec<u8> {
+    let mut packed = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let zeros = bytes[i..].iter().take_while(|&&b| b == 0).count();
+        i += zeros;
+        let start = i;
+        while i < bytes.len() && !(bytes[i] == 0 && bytes.get(i + 1).is_none_or(|&b| b == 0)) {
+            i += 1;
+        }
+        push_varint(&mut packed, zeros);
+        push_varint(&mut packed, i - start);
+        packed.extend_from_slice(&bytes[start..i]);
+    }
+    packed
+}
+
+/// Inverse of `pack_zero_runs`, `None` unless it gives exactly `len` bytes
+fn unpack_zero_runs(packed: &[u8], len: usize) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(len);
+    let mut at = 0;
+    while at < packed.len() {
+        let zeros = read_varint(packed, &mut at)?;
+        let literals = read_varint(packed, &mut at)?;
+        if bytes.len() + zeros + literals > len {
+            return None;
+        }
+        bytes.resize(bytes.len() + zeros, 0);
+        bytes.extend_from_slice(packed.get(at..at + literals)?);
+        at += literals;
+    }
+    (bytes.len() == len).then_some(bytes)
+}
+
+/// Bytes of the changed tiles, then of the changed agent slots (zeroed past the end of `agents`)
+fn changed_bytes(state: &StateSnapshot, tile: u32, tiles: &[u32], slots: &[u32]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for &index in tiles {
+        bytes.extend_from_slice(bytemuck::cast_slice(&tile_cells(&state.field, state.size, tile, index)));
+    }
+    for &slot in slots {
+        let agent = state.agents.get(slot as usize).copied().unwrap_or(bytemuck::Zeroable::zeroed());
+        bytes.extend_from_slice(bytemuck::bytes_of(&agent));
+    }
+    bytes
+}
+
+/// Changes from one checkpoint to the next: the field tiles and agent slots that differ
+///
+/// Written between keyframes of a checkpoint chain as `delta_NNNNNN.bin`: a
+/// fixed little-endian header, the changed tile and slot indices, then their
+/// bytes XORed with the previous checkpoint's and packed into zero runs.
+#[derive(Debug, Clone)]
+pub struct SnapshotDelta {
+    pub step: u32,
+    pub base_step: u32,   // Step of the checkpoint it applies to
+    pub size: [u32; 2],
+    pub tile: u32,        // Tile edge in cells; tiles are numbered row-major and clipped to the world
+    pub agent_count: u32, // Agent slots afterwards (the buffer may have grown)
+    pub tiles: Vec<u32>,  // Changed tiles
+    pub slots: Vec<u32>,  // Changed agent slots
+    pub checksum: u64,    // `StateSnapshot::checksum` of the result
+    packed: Vec<u8>,      // XOR of the changed bytes with the base's, as zero runs
+}
+
+impl SnapshotDelta {
+    /// Turn `base` into the state this delta was encoded from, checking the result bit for bit
+    pub fn apply(&self, base: &mut StateSnapshot) -> Result<(), String> {
+        if base.step != self.base_step || base.size != self.size {
+            return Err(format!(
+                "delta to step {} applies to step {} of a {}x{} world, not step {} of a {}x{} world",
+                self.step, self.base_step, self.size[0], self.size[1], base.step, base.size[0], base.size[1],
+            ));
+        }
+        let mut bytes = changed_bytes(base, self.tile, &self.tiles, &self.slots);
+        let xor = unpack_zero_runs(&self.packed, bytes.len())
+            .ok_or_else(|| format!("delta to step {} does not unpack to its tiles and slots", self.step))?;
+        bytes.iter_mut().zip(xor).for_each(|(b, x)| *b ^= x);
+
+        let mut rest = bytes.as_slice();
+        for &index in &self.tiles {
+            let ([x, y], [w, h]) = tile_rect(self.size, self.tile, index);
+            let (tile, tail) = rest.split_at((w * h) as usize * std::mem::size_of::<FieldData>());
+            for (r, row) in read_records::<FieldData>(tile).chunks_exact(w as usize).enumerate() {
+                let start = ((y + r as u32) * self.size[0] + x) as usize;
+                base.field[start..start + w as usize].copy_from_slice(row);
+            }
+            rest = tail;
+        }
+        base.agents.resize(self.agent_count as usize, bytemuck::Zeroable::zeroed());
+        for (&slot, agent) in self.slots.iter().zip(read_records::<Agent>(rest)) {
+            base.agents[slot as usize] = agent;
+        }
+        base.step = self.step;
+        if base.checksum() != self.checksum {
+            return Err(format!("state rebuilt for step {} does not match its checksum", self.step));
+        }
+        Ok(())
+    }
+
+    /// Serialize to the on-disk format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_BYTES + 4 * (self.tiles.len() + self.slots.len()) + self.packed.len());
+        bytes.extend_from_slice(MAGIC);
+        let words = [
+            VERSION, self.step, self.base_step, self.size[0], self.size[1], self.tile,
+            self.agent_count, self.tiles.len() as u32, self.slots.len() as u32,
+        ];
+        for word in words {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.checksum.to_le_bytes());
+        for index in self.tiles.iter().chain(&self.slots) {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.packed);
+        bytes
+    }
+
+    /// Parse the on-disk format, rejecting foreign, old or truncated files
+    ///
+    /// The packed bytes are only checked by `apply`.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |reason: String| io::Error::new(io::ErrorKind::InvalidData, reason);
+        if bytes.len() < HEADER_BYTES || &bytes[..8] != MAGIC {
+            return Err(invalid("not a Vireo checkpoint delta".to_string()));
+        }
+        let word = |i: usize| u32::from_le_bytes(bytes[8 + 4 * i..12 + 4 * i].try_into().unwrap());
+        if word(0) != VERSION {
+            return Err(invalid(format!("delta version {} is not supported (expected {})", word(0), VERSION)));
+        }
+        let (size, tile, agent_count) = ([word(3), word(4)], word(5), word(6));
+        let (tile_total, slot_total) = (word(7) as usize, word(8) as usize);
+        let checksum = u64::from_le_bytes(bytes[HEADER_BYTES - 8..HEADER_BYTES].try_into().unwrap());
+
+        let body = &bytes[HEADER_BYTES..];
+        if body.len() < 4 * (tile_total + slot_total) {
+            return Err(invalid("delta is truncated".to_string()));
+        }
+        let indices: Vec<u32> = read_records(&body[..4 * (tile_total + slot_total)]);
+        let (tiles, slots) = indices.split_at(tile_total);
+        if tile == 0 || tiles.iter().any(|&i| i >= tile_count(size, tile)) || slots.iter().any(|&s| s >= agent_count) {
+            return Err(invalid(format!("delta lists tiles or slots outside a {}x{} world in {}-cell tiles with {} agent slots",
+                size[0], size[1], tile, agent_count)));
+        }
+        Ok(Self {
+            step: word(1),
+            base_step: word(2),
+            size,
+            tile,
+            agent_count,
+            tiles: tiles.to_vec(),
+            slots: slots.to_vec(),
+            checksum,
+            packed: body[4 * (tile_total + slot_total)..].to_vec(),
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+/// The last checkpoint, to encode the next one against
+#[derive(Debug, Clone)]
+pub struct DeltaEncoder {
+    tile: u32,
+    base: StateSnapshot,
+}
+
+impl DeltaEncoder {
+    /// Start from `base` in `tile`-cell tiles
+    pub fn new(base: &StateSnapshot, tile: u32) -> Self {
+        Self { tile: tile.max(1), base: base.clone() }
+    }
+
+    /// Delta from the last state to `state`, which becomes the new base
+    ///
+    /// Tiles and slots are compared byte for byte; agent slots the last state
+    /// did not have are always included.
+    pub fn encode(&mut self, state: &StateSnapshot) -> SnapshotDelta {
+        let (base, tile) = (&self.base, self.tile);
+        let tiles: Vec<u32> = (0..tile_count(state.size, tile))
+            .filter(|&i| {
+                let (old, new) = (tile_cells(&base.field, base.size, tile, i), tile_cells(&state.field, state.size, tile, i));
+                bytemuck::cast_slice::<_, u8>(&old) != bytemuck::cast_slice::<_, u8>(&new)
+            })
+            .collect();
+        let slots: Vec<u32> = (0..state.agents.len())
+            .filter(|&slot| base.agents.get(slot).is_none_or(|a| bytemuck::bytes_of(a) != bytemuck::bytes_of(&state.agents[slot])))
+            .map(|slot| slot as u32)
+            .collect();
+
+        let mut xor = changed_bytes(state, tile, &tiles, &slots);
+        xor.iter_mut().zip(changed_bytes(base, tile, &tiles, &slots)).for_each(|(b, old)| *b ^= old);
+        let delta = SnapshotDelta {
+            step: state.step,
+            base_step: base.step,
+            size: base.size,
+            tile,
+            agent_count: state.agents.len() as u32,
+            tiles,
+            slots,
+            checksum: state.checksum(),
+            packed: pack_zero_runs(&xor),
+        };
+        self.base = state.clone();
+        delta
+    }
+}
+
+/// Full snapshot of a chain checkpoint
+pub fn keyframe_path(dir: &Path, step: u32) -> PathBuf {
+    dir.join(format!("state_{:06}.bin", step))
+}
+
+/// Delta of a chain checkpoint
+pub fn delta_path(dir: &Path, step: u32) -> PathBuf {
+    dir.join(format!("delta_{:06}.bin", step))
+}
+
+/// Writes a checkpoint chain (`output.checkpoints`) into a directory
+#[derive(Debug)]
+pub struct ChainWriter {
+    dir: PathBuf,
+    tile: u32,
+    keyframe_every: u32,
+    encoder: Option<DeltaEncoder>,
+    since_keyframe: u32,
+    pub checkpoints: u32,
+    pub keyframes: u32,
+    pub bytes: u64,      // Written so far
+    pub full_bytes: u64, // Full snapshots of every checkpoint would have taken
+}
+
+impl ChainWriter {
+    pub fn new(dir: &Path, config: &CheckpointChainConfig) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            tile: config.tile,
+            keyframe_every: config.keyframe_every,
+            encoder: None,
+            since_keyframe: 0,
+            checkpoints: 0,
+            keyframes: 0,
+            bytes: 0,
+            full_bytes: 0,
+        })
+    }
+
+    /// Write `state` as the next checkpoint: a keyframe when one is due, a delta otherwise
+    pub fn push(&mut self, state: &StateSnapshot) -> io::Result<PathBuf> {
+        let keyframe_due = self.keyframe_every > 0 && self.since_keyframe >= self.keyframe_every;
+        let full = state.to_bytes();
+        // A delta no smaller than the state itself is written as a keyframe instead
+        let delta = self.encoder.as_mut()
+            .filter(|_| !keyframe_due)
+            .map(|encoder| encoder.encode(state).to_bytes())
+            .filter(|delta| delta.len() < full.len());
+        let (path, bytes) = match delta {
+            Some(delta) => {
+                self.since_keyframe += 1;
+                (delta_path(&self.dir, state.step), delta)
+            }
+            None => {
+                self.encoder = Some(DeltaEncoder::new(state, self.tile));
+                self.since_keyframe = 1;
+                self.keyframes += 1;
+                (keyframe_path(&self.dir, state.step), full.clone())
+            }
+        };
+        std::fs::write(&path, &bytes)?;
+        self.checkpoints += 1;
+        self.bytes += bytes.len() as u64;
+        self.full_bytes += full.len() as u64;
+        Ok(path)
+    }
+}
+
+/// A checkpoint chain on disk: keyframes and deltas, in step order
+#[derive(Debug, Clone)]
+pub struct CheckpointChain {
+    dir: PathBuf,
+    entries: Vec<(u32, bool)>, // Step and whether it is a keyframe
+}
+
+impl CheckpointChain {
+    /// List the `state_NNNNNN.bin` and `delta_NNNNNN.bin` files in `dir`
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let name = entry?.file_name();
+            let Some(name) = name.to_str().and_then(|n| n.strip_suffix(".bin")) else { continue };
+            let parsed = match name.split_once('_') {
+                Some(("state", step)) => step.parse().ok().map(|s| (s, true)),
+                Some(("delta", step)) => step.parse().ok().map(|s| (s, false)),
+                _ => None,
+            };
+            entries.extend(parsed);
+        }
+        entries.sort_unstable();
+        Ok(Self { dir: dir.to_path_buf(), entries })
+    }
+
+    /// Steps of every checkpoint in the chain
+    pub fn steps(&self) -> impl Iterator<Item = u32> + '_ {
+        self.entries.iter().map(|&(step, _)| step)
+    }
+
+    /// State at the last checkpoint at or before `step` (`None` before the first keyframe)
+    ///
+    /// Starts from the keyframe before it and applies each delta in turn,
+    /// failing if one is missing or does not rebuild its recorded checksum.
+    pub fn rebuild(&self, step: u32) -> io::Result<Option<StateSnapshot>> {
+        let end = self.entries.partition_point(|&(s, _)| s <= step);
+        let Some(start) = self.entries[..end].iter().rposition(|&(_, keyframe)| keyframe) else { return Ok(None) };
+        let mut state = StateSnapshot::read(&keyframe_path(&self.dir, self.entries[start].0))?;
+        for &(delta_step, _) in &self.entries[start + 1..end] {
+            let delta = SnapshotDelta::read(&delta_path(&self.dir, delta_step))?;
+            delta.apply(&mut state).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        Ok(Some(state))
+    }
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+/// Maps real time to a whole number of simulation steps per frame
+///
+/// Frontends call `advance` once per frame with the wall-clock time since the
+/// last call, so the step rate follows `steps_per_second` whatever the monitor
+/// refresh rate. A slow frame is caught up with extra steps, at most
+/// `max_burst` per frame; backlog beyond one more burst is dropped so a stall
+/// (window drag, breakpoint) does not fast-forward the world afterwards.
+///
+/// Only `std` is used here: the root sandbox includes this file directly.
+#[derive(Debug, Clone)]
+pub struct StepClock {
+    steps_per_second: f64,
+    max_burst: u32,
+    backlog: f64, // Steps due but not yet run
+}
+
+impl StepClock {
+    pub const DEFAULT_STEPS_PER_SECOND: f64 = 60.0;
+    pub const DEFAULT_MAX_BURST: u32 = 4;
+
+    /// `steps_per_second` must be positive and `max_burst` at least 1
+    pub fn new(steps_per_second: f64, max_burst: u32) -> Self {
+        assert!(steps_per_second > 0.0 && steps_per_second.is_finite(), "steps_per_second must be positive");
+        assert!(max_burst >= 1, "max_burst must be at least 1");
+        Self { steps_per_second, max_burst, backlog: 0.0 }
+    }
+
+    pub fn steps_per_second(&self) -> f64 {
+        self.steps_per_second
+    }
+
+    pub fn max_burst(&self) -> u32 {
+        self.max_burst
+    }
+
+    /// Fraction of a step due but not yet run
+    pub fn backlog(&self) -> f64 {
+        self.backlog
+    }
+
+    /// Steps to run for `elapsed` real time at `speed` times the target rate
+    pub fn advance(&mut self, elapsed: Duration, speed: f32) -> u32 {
+        self.backlog += elapsed.as_secs_f64() * speed.max(0.0) as f64 * self.steps_per_second;
+        let steps = (self.backlog.floor() as u32).min(self.max_burst);
+        self.backlog = (self.backlog - steps as f64).min(self.max_burst as f64);
+        steps
+    }
+
+    /// Drop the backlog, e.g. while paused or after a reset
+    pub fn reset(&mut self) {
+        self.backlog = 0.0;
+    }
+}
+
+impl Default for StepClock {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_STEPS_PER_SECOND, Self::DEFAULT_MAX_BURST)
+    }
+}
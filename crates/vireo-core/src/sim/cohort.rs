@@ -0,0 +1,152 @@
+use vireo_params::CohortEvent;
+use crate::sim::Agent;
+
+/// Cohort id of agents that were never tagged
+pub const UNTAGGED: u32 = 0;
+
+/// A tagged agent and where it was released
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TagRecord {
+    pub index: u32,
+    pub release_pos: [f32; 2],
+}
+
+/// One tagging event: the agents marked with `cohort` at `step`
+#[derive(Debug, Clone)]
+pub struct CohortTag {
+    pub cohort: u32,
+    pub step: u32,
+    pub members: Vec<TagRecord>,
+}
+
+/// Survival and dispersal of one cohort (mark-recapture style summary)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CohortStats {
+    pub cohort: u32,
+    pub tagged: u32,
+    pub alive: u32,
+    pub survival: f32,          // alive / tagged
+    pub mean_dispersal: f32,    // Mean distance of survivors from their release point
+    pub max_dispersal: f32,
+}
+
+/// Tags subsets of agents with a cohort id and tracks them afterwards
+///
+/// The id lives in `Agent::cohort` so it travels with the agent on the GPU;
+/// the tracker only keeps each member's slot and release position. Tagging an
+/// agent that already belongs to a cohort moves it to the new one.
+#[derive(Debug, Clone, Default)]
+pub struct CohortTracker {
+    tags: Vec<CohortTag>,
+}
+
+impl CohortTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tag every alive agent matching `select` with `cohort`; returns how many were tagged
+    pub fn tag<F>(&mut self, agents: &mut [Agent], cohort: u32, step: u32, select: F) -> u32
+    where
+        F: Fn(&Agent) -> bool,
+    {
+        assert_ne!(cohort, UNTAGGED, "cohort id 0 is reserved for untagged agents");
+
+        let mut members = Vec::new();
+        for (i, agent) in agents.iter_mut().enumerate() {
+            if agent.is_alive() && select(agent) {
+                agent.cohort = cohort;
+                members.push(TagRecord { index: i as u32, release_pos: agent.pos });
+            }
+        }
+
+        let count = members.len() as u32;
+        if count > 0 {
+            self.tags.push(CohortTag { cohort, step, members });
+        }
+        count
+    }
+
+    /// Tag alive agents within `radius` of `center`, optionally of one `kind`
+    pub fn tag_within(
+        &mut self,
+        agents: &mut [Agent],
+        cohort: u32,
+        step: u32,
+        center: [f32; 2],
+        radius: f32,
+        kind: Option<u32>,
+    ) -> u32 {
+        let r2 = radius * radius;
+        self.tag(agents, cohort, step, |a| {
+            let dx = a.pos[0] - center[0];
+            let dy = a.pos[1] - center[1];
+            dx * dx + dy * dy <= r2 && kind.is_none_or(|k| a.kind == k)
+        })
+    }
+
+    /// Apply a configured tagging event
+    pub fn apply(&mut self, agents: &mut [Agent], event: &CohortEvent) -> u32 {
+        self.tag_within(agents, event.id, event.step, event.center, event.radius, event.kind)
+    }
+
+    /// Move every release position by `delta` (the world window was shifted under the agents)
+    pub fn translate(&mut self, delta: [f32; 2]) {
+        for m in self.tags.iter_mut().flat_map(|t| t.members.iter_mut()) {
+            m.release_pos[0] += delta[0];
+            m.release_pos[1] += delta[1];
+        }
+    }
+
+    /// All tagging events so far, in order
+    pub fn tags(&self) -> &[CohortTag] {
+        &self.tags
+    }
+
+    /// Smallest cohort id not yet used
+    pub fn next_id(&self) -> u32 {
+        self.tags.iter().map(|t| t.cohort).max().unwrap_or(UNTAGGED) + 1
+    }
+
+    /// Per-cohort survival and dispersal, sorted by cohort id
+    ///
+    /// A member counts as surviving while it is alive and still carries the
+    /// cohort id it was tagged with (re-tagged agents leave their old cohort).
+    pub fn stats(&self, agents: &[Agent]) -> Vec<CohortStats> {
+        let mut ids: Vec<u32> = self.tags.iter().map(|t| t.cohort).collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        ids.into_iter()
+            .map(|cohort| {
+                let mut tagged = 0;
+                let mut alive = 0;
+                let mut sum = 0.0;
+                let mut max: f32 = 0.0;
+                for tag in self.tags.iter().filter(|t| t.cohort == cohort) {
+                    for m in &tag.members {
+                        tagged += 1;
+                        let Some(agent) = agents.get(m.index as usize) else { continue };
+                        if !agent.is_alive() || agent.cohort != cohort {
+                            continue;
+                        }
+                        let dx = agent.pos[0] - m.release_pos[0];
+                        let dy = agent.pos[1] - m.release_pos[1];
+                        let d = (dx * dx + dy * dy).sqrt();
+                        alive += 1;
+                        sum += d;
+                        max = max.max(d);
+                    }
+                }
+                CohortStats {
+                    cohort,
+                    tagged,
+                    alive,
+                    survival: if tagged > 0 { alive as f32 / tagged as f32 } else { 0.0 },
+                    mean_dispersal: if alive > 0 { sum / alive as f32 } else { 0.0 },
+                    max_dispersal: max,
+                }
+            })
+            .collect()
+    }
+}
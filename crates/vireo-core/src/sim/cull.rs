@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use vireo_params::CullParams;
+
+/// Region of the world whose agents a cull kills
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "lowercase")]
+pub enum CullRegion {
+    /// Axis-aligned rectangle between two corners (any order)
+    Rect { a: [f32; 2], b: [f32; 2] },
+    Circle { center: [f32; 2], radius: f32 },
+}
+
+impl CullRegion {
+    /// Whether `pos` is inside the region (same test as cull_region.wgsl)
+    pub fn contains(&self, pos: [f32; 2]) -> bool {
+        match *self {
+            CullRegion::Rect { a, b } => {
+                let (min, max) = Self::corners(a, b);
+                (min[0]..=max[0]).contains(&pos[0]) && (min[1]..=max[1]).contains(&pos[1])
+            }
+            CullRegion::Circle { center, radius } => {
+                let dx = pos[0] - center[0];
+                let dy = pos[1] - center[1];
+                dx * dx + dy * dy <= radius * radius
+            }
+        }
+    }
+
+    /// Pack for the cull shader
+    pub fn to_params(&self) -> CullParams {
+        let (shape, a, b) = match *self {
+            CullRegion::Rect { a, b } => {
+                let (min, max) = Self::corners(a, b);
+                (0, min, max)
+            }
+            CullRegion::Circle { center, radius } => (1, center, [radius, 0.0]),
+        };
+        CullParams { shape, _pad0: 0, a, b, _pad1: [0.0; 2] }
+    }
+
+    fn corners(a: [f32; 2], b: [f32; 2]) -> ([f32; 2], [f32; 2]) {
+        ([a[0].min(b[0]), a[1].min(b[1])], [a[0].max(b[0]), a[1].max(b[1])])
+    }
+}
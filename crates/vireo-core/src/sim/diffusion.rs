@@ -0,0 +1,51 @@
+use vireo_params::DiffusionConfig;
+
+/// Diffusion tensor at every cell centre, as `(Dxx, Dyy, Dxy, 0)` multipliers of `D`
+///
+/// This is the texture the RD pass reads with `diffusion.enabled`: the
+/// configured tensor, with each stream blended in toward its course in
+/// config order.
+#[derive(Debug, Clone)]
+pub struct DiffusionTensorField {
+    size: [u32; 2],
+    tensors: Vec<[f32; 4]>,
+}
+
+impl DiffusionTensorField {
+    /// Evaluate `config` at each cell centre (the identity when disabled)
+    pub fn new(config: &DiffusionConfig, size: [u32; 2]) -> Self {
+        let (base, streams) = if config.enabled { (config.tensor, config.streams.as_slice()) } else { ([1.0, 1.0, 0.0], &[][..]) };
+        let tensors = (0..size[1])
+            .flat_map(|y| (0..size[0]).map(move |x| [x as f32 + 0.5, y as f32 + 0.5]))
+            .map(|p| {
+                let [mut dxx, mut dyy, mut dxy] = base;
+                for stream in streams {
+                    let (w, [tx, ty]) = stream.influence(p);
+                    let (along, across) = (stream.along, stream.across);
+                    dxx += w * (along * tx * tx + across * ty * ty - dxx);
+                    dyy += w * (along * ty * ty + across * tx * tx - dyy);
+                    dxy += w * ((along - across) * tx * ty - dxy);
+                }
+                [dxx, dyy, dxy, 0.0]
+            })
+            .collect();
+        Self { size, tensors }
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// Row-major `(Dxx, Dyy, Dxy, 0)`, one per cell
+    pub fn tensors(&self) -> &[[f32; 4]] {
+        &self.tensors
+    }
+
+    /// `(Dxx, Dyy, Dxy)` at the centre of cell (x, y), clamped to the grid
+    pub fn at(&self, x: i32, y: i32) -> [f32; 3] {
+        let x = x.clamp(0, self.size[0] as i32 - 1) as usize;
+        let y = y.clamp(0, self.size[1] as i32 - 1) as usize;
+        let [dxx, dyy, dxy, _] = self.tensors[y * self.size[0] as usize + x];
+        [dxx, dyy, dxy]
+    }
+}
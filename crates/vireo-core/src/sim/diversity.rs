@@ -0,0 +1,55 @@
+use vireo_params::SPECIES;
+use crate::sim::Agent;
+
+/// Community diversity of the alive agents at a metrics sample
+///
+/// Shannon and Simpson indices are taken over the species abundances of the
+/// whole world. Beta diversity compares the species found in each quadrat of
+/// a `quadrats`×`quadrats` grid with the whole world (Whittaker's β = γ/ᾱ);
+/// quadrats without agents are left out of ᾱ.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Diversity {
+    pub richness: u32,         // Species with at least one alive agent (γ)
+    pub shannon: f32,          // H' = −Σ p ln p (0 for one species, ln 3 for three equally common)
+    pub simpson: f32,          // Gini–Simpson 1 − Σ p²: chance two random agents differ in species
+    pub mean_alpha: f32,       // Mean species count of occupied quadrats (ᾱ)
+    pub beta: f32,             // γ/ᾱ: 1 when every occupied quadrat holds every species (0 without agents)
+    pub occupied_quadrats: u32,
+}
+
+impl Diversity {
+    pub fn of(agents: &[Agent], world_size: [u32; 2], quadrats: u32) -> Self {
+        let q = quadrats.max(1) as usize;
+        let mut counts = [0u32; SPECIES.len()];
+        let mut present = vec![[false; SPECIES.len()]; q * q];
+        for a in agents.iter().filter(|a| a.is_alive() && (a.kind as usize) < SPECIES.len()) {
+            counts[a.kind as usize] += 1;
+            let cell = |axis: usize| ((a.pos[axis] / world_size[axis] as f32 * q as f32).max(0.0) as usize).min(q - 1);
+            present[cell(1) * q + cell(0)][a.kind as usize] = true;
+        }
+
+        let total: u32 = counts.iter().sum();
+        if total == 0 {
+            return Self::default();
+        }
+        let shares = counts.iter().filter(|&&n| n > 0).map(|&n| n as f64 / total as f64);
+        let shannon = 0.0 - shares.clone().map(|p| p * p.ln()).sum::<f64>(); // Not -0 for one species
+        let simpson = 1.0 - shares.map(|p| p * p).sum::<f64>();
+
+        let richness = counts.iter().filter(|&&n| n > 0).count() as u32;
+        let alphas: Vec<u32> = present.iter()
+            .map(|species| species.iter().filter(|&&p| p).count() as u32)
+            .filter(|&alpha| alpha > 0)
+            .collect();
+        let mean_alpha = alphas.iter().sum::<u32>() as f32 / alphas.len() as f32;
+
+        Self {
+            richness,
+            shannon: shannon as f32,
+            simpson: simpson as f32,
+            mean_alpha,
+            beta: richness as f32 / mean_alpha,
+            occupied_quadrats: alphas.len() as u32,
+        }
+    }
+}
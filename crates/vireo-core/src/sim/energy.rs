@@ -0,0 +1,71 @@
+use bytemuck::{Pod, Zeroable};
+use crate::sim::Agent;
+
+/// One step of the selected agent's energy budget, as the agent pass logs it
+///
+/// All terms are energy per step (already multiplied by dt). `movement` is
+/// the terrain cost of the move: positive when climbing, negative when a
+/// descent returns energy.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct EnergyRecord {
+    pub step: u32,       // Step the record was written in (`EnergyRecord::EMPTY` for an unused slot)
+    pub uptake: f32,     // eta_R·R·dt
+    pub metabolism: f32, // eps0·dt
+    pub movement: f32,   // climb_cost or descent_gain × elevation change
+}
+
+impl EnergyRecord {
+    /// Step of a log slot nothing has written since the selection changed
+    pub const EMPTY: u32 = u32::MAX;
+
+    pub fn is_empty(&self) -> bool {
+        self.step == Self::EMPTY
+    }
+}
+
+/// Inflows and outflows of one agent summed over its logged steps
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EnergyBudget {
+    pub steps: u32,
+    pub first_step: u32,
+    pub last_step: u32,
+    pub uptake: f32,
+    pub metabolism: f32,
+    pub movement: f32,
+    pub predation: f32, // Always 0: nothing eats agents yet
+}
+
+impl EnergyBudget {
+    /// Sum `records` (in any order, empty slots skipped)
+    pub fn of(records: &[EnergyRecord]) -> Self {
+        let mut budget = Self { first_step: u32::MAX, ..Self::default() };
+        for r in records.iter().filter(|r| !r.is_empty()) {
+            budget.steps += 1;
+            budget.first_step = budget.first_step.min(r.step);
+            budget.last_step = budget.last_step.max(r.step);
+            budget.uptake += r.uptake;
+            budget.metabolism += r.metabolism;
+            budget.movement += r.movement;
+        }
+        if budget.steps == 0 {
+            budget.first_step = 0;
+        }
+        budget
+    }
+
+    /// Uptake less every outflow
+    pub fn net(&self) -> f32 {
+        self.uptake - self.metabolism - self.movement - self.predation
+    }
+}
+
+/// Slot of the alive agent nearest to `at`, if one is within `radius`
+pub fn nearest_alive(agents: &[Agent], at: [f32; 2], radius: f32) -> Option<u32> {
+    agents.iter().enumerate()
+        .filter(|(_, a)| a.is_alive())
+        .map(|(i, a)| (i as u32, (a.pos[0] - at[0]).powi(2) + (a.pos[1] - at[1]).powi(2)))
+        .filter(|&(_, d2)| d2 <= radius * radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
@@ -0,0 +1,66 @@
+/// Births and deaths per cell over one window of steps (`output.event_window`)
+///
+/// Each map is row-major like the occupancy grid. Starvation deaths happen in
+/// the agent pass when energy runs out; random deaths and births come from
+/// the demography pass. Agents removed by culls are not counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventMaps {
+    pub size: [u32; 2],
+    pub births: Vec<u32>,
+    pub starved: Vec<u32>,
+    pub random_deaths: Vec<u32>,
+}
+
+impl EventMaps {
+    /// Planes in the GPU buffer: births, starved, random deaths
+    pub const PLANES: usize = 3;
+
+    /// Split the counter buffer into its planes
+    pub fn from_planes(size: [u32; 2], planes: Vec<u32>) -> Self {
+        let cells = planes.len() / Self::PLANES;
+        let mut planes = planes.chunks_exact(cells.max(1)).map(<[u32]>::to_vec);
+        let mut next = || planes.next().unwrap_or_default();
+        Self { size, births: next(), starved: next(), random_deaths: next() }
+    }
+
+    /// The planes back to back, as the GPU buffer holds them
+    pub fn planes(&self) -> Vec<u32> {
+        [&self.births, &self.starved, &self.random_deaths].into_iter().flatten().copied().collect()
+    }
+
+    /// Deaths of either cause per cell
+    pub fn deaths(&self) -> Vec<u32> {
+        self.starved.iter().zip(&self.random_deaths).map(|(s, r)| s + r).collect()
+    }
+
+    /// Window totals and how concentrated the deaths were
+    pub fn summary(&self) -> EventSummary {
+        let total = |map: &[u32]| map.iter().map(|&n| n as u64).sum::<u64>();
+        let mut deaths = self.deaths();
+        let death_cells = deaths.iter().filter(|&&n| n > 0).count() as u32;
+        let all_deaths = total(&deaths);
+
+        // Share of the deaths in the busiest 1% of cells (at least one cell)
+        deaths.sort_unstable_by(|a, b| b.cmp(a));
+        let top = deaths.len().div_ceil(100);
+        let hotspot = if all_deaths > 0 { total(&deaths[..top]) as f32 / all_deaths as f32 } else { 0.0 };
+
+        EventSummary {
+            births: total(&self.births),
+            starved: total(&self.starved),
+            random_deaths: total(&self.random_deaths),
+            death_cells,
+            death_hotspot_share: hotspot,
+        }
+    }
+}
+
+/// Totals of one event window, reported in `metrics.csv`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EventSummary {
+    pub births: u64,
+    pub starved: u64,
+    pub random_deaths: u64,
+    pub death_cells: u32,          // Cells with at least one death
+    pub death_hotspot_share: f32,  // Fraction of the deaths in the 1% of cells with the most
+}
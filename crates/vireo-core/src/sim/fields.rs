@@ -1,8 +1,12 @@
 use bytemuck::{Pod, Zeroable};
 use half::f16;
-use rand_chacha::ChaCha8Rng;
-use rand::{Rng, SeedableRng};
+use half::slice::HalfFloatSliceExt;
+use rand::Rng;
+use rayon::prelude::*;
 use std::f32::consts::TAU;
+use std::ops::Range;
+use vireo_params::{FieldChannel, RngKind};
+use crate::sim::SimRng;
 
 /// Field data structure for GPU compute
 #[repr(C)]
@@ -10,7 +14,8 @@ use std::f32::consts::TAU;
 pub struct FieldData {
     pub R: f32,  // Resource concentration
     pub W: f32,  // Waste concentration
-    pub _pad: [f32; 2], // Padding for alignment
+    pub soil: f32, // Soil damage (0 = pristine)
+    pub _pad: f32, // Padding for alignment
 }
 
 impl FieldData {
@@ -18,7 +23,8 @@ impl FieldData {
         Self {
             R: resource,
             W: waste,
-            _pad: [0.0, 0.0],
+            soil: 0.0,
+            _pad: 0.0,
         }
     }
     
@@ -26,7 +32,26 @@ impl FieldData {
         Self {
             R: 0.0,
             W: 0.0,
-            _pad: [0.0, 0.0],
+            soil: 0.0,
+            _pad: 0.0,
+        }
+    }
+    
+    /// Read a channel by its registry entry
+    pub fn get(&self, channel: FieldChannel) -> f32 {
+        match channel {
+            FieldChannel::Resource => self.R,
+            FieldChannel::Waste => self.W,
+            FieldChannel::Soil => self.soil,
+        }
+    }
+    
+    /// Write a channel by its registry entry
+    pub fn set(&mut self, channel: FieldChannel, value: f32) {
+        match channel {
+            FieldChannel::Resource => self.R = value,
+            FieldChannel::Waste => self.W = value,
+            FieldChannel::Soil => self.soil = value,
         }
     }
 }
@@ -47,6 +72,8 @@ pub struct FieldStats {
     pub max_W: f32,
     pub min_R: f32,
     pub min_W: f32,
+    pub mean_soil: f32, // Mean soil damage
+    pub max_soil: f32,  // Worst soil damage
 }
 
 impl Default for FieldStats {
@@ -61,60 +88,370 @@ impl Default for FieldStats {
             max_W: 0.0,
             min_R: 0.0,
             min_W: 0.0,
+            mean_soil: 0.0,
+            max_soil: 0.0,
+        }
+    }
+}
+
+/// Cells converted per batch by `FieldManager::channel_f32`
+const CONVERT_CELLS: usize = 1024;
+
+/// Rows per parallel band of `FieldManager::update_stats`
+const STATS_BAND_ROWS: usize = 16;
+
+/// Accumulator lanes per band, wide enough for the compiler to vectorize
+const STATS_LANES: usize = 8;
+
+/// Sums and extremes of one band of rows; sums are widened to f64 across bands
+#[derive(Debug, Clone, Copy)]
+struct StatsPartial {
+    sum_R: f64,
+    sum_W: f64,
+    sum_R_sq: f64,
+    sum_W_sq: f64,
+    sum_grad_R: f64,
+    max_R: f32,
+    max_W: f32,
+    min_R: f32,
+    min_W: f32,
+}
+
+impl Default for StatsPartial {
+    fn default() -> Self {
+        Self {
+            sum_R: 0.0,
+            sum_W: 0.0,
+            sum_R_sq: 0.0,
+            sum_W_sq: 0.0,
+            sum_grad_R: 0.0,
+            max_R: f32::NEG_INFINITY,
+            max_W: f32::NEG_INFINITY,
+            min_R: f32::INFINITY,
+            min_W: f32::INFINITY,
+        }
+    }
+}
+
+impl StatsPartial {
+    /// Stats of `rows` of a `w`×`h` field given as row-major R and W
+    fn of_band(resource: &[f32], waste: &[f32], w: usize, h: usize, rows: Range<usize>) -> Self {
+        const L: usize = STATS_LANES;
+        let cells = rows.start * w..rows.end * w;
+        let (r, wa) = (&resource[cells.clone()], &waste[cells]);
+        
+        let mut lanes = StatsLanes::default();
+        let (r_chunks, wa_chunks) = (r.chunks_exact(L), wa.chunks_exact(L));
+        let (r_rest, wa_rest) = (r_chunks.remainder(), wa_chunks.remainder());
+        for (r, wa) in r_chunks.zip(wa_chunks) {
+            lanes.add(r.try_into().unwrap(), wa.try_into().unwrap());
+        }
+        for (l, (&r, &wa)) in r_rest.iter().zip(wa_rest).enumerate() {
+            lanes.add_one(l, r, wa);
+        }
+        
+        // Gradient magnitude (central differences) over interior cells only
+        let mut grad = [0.0f32; L];
+        let mut grad_rest = 0.0f32;
+        for y in rows.start.max(1)..rows.end.min(h.saturating_sub(1)) {
+            let n = w.saturating_sub(2);
+            let (up, down) = (&resource[(y - 1) * w + 1..][..n], &resource[(y + 1) * w + 1..][..n]);
+            let (left, right) = (&resource[y * w..][..n], &resource[y * w + 2..][..n]);
+            let magnitude = |l: f32, r: f32, u: f32, d: f32| {
+                let dx = (r - l) * 0.5;
+                let dy = (d - u) * 0.5;
+                (dx * dx + dy * dy).sqrt()
+            };
+            let body = n - n % L;
+            for (((l, r), u), d) in left[..body].chunks_exact(L).zip(right[..body].chunks_exact(L))
+                .zip(up[..body].chunks_exact(L)).zip(down[..body].chunks_exact(L))
+            {
+                let (l, r, u, d): (&[f32; L], &[f32; L], &[f32; L], &[f32; L]) =
+                    (l.try_into().unwrap(), r.try_into().unwrap(), u.try_into().unwrap(), d.try_into().unwrap());
+                for lane in 0..L {
+                    grad[lane] += magnitude(l[lane], r[lane], u[lane], d[lane]);
+                }
+            }
+            grad_rest += (body..n).map(|i| magnitude(left[i], right[i], up[i], down[i])).sum::<f32>();
+        }
+        
+        Self {
+            sum_grad_R: sum_lanes(&grad) + grad_rest as f64,
+            ..lanes.finish()
+        }
+    }
+    
+    fn merge(self, other: Self) -> Self {
+        Self {
+            sum_R: self.sum_R + other.sum_R,
+            sum_W: self.sum_W + other.sum_W,
+            sum_R_sq: self.sum_R_sq + other.sum_R_sq,
+            sum_W_sq: self.sum_W_sq + other.sum_W_sq,
+            sum_grad_R: self.sum_grad_R + other.sum_grad_R,
+            max_R: self.max_R.max(other.max_R),
+            max_W: self.max_W.max(other.max_W),
+            min_R: self.min_R.min(other.min_R),
+            min_W: self.min_W.min(other.min_W),
         }
     }
 }
 
+/// `STATS_LANES`-wide running sums and extremes of one band
+struct StatsLanes {
+    sum: [[f32; STATS_LANES]; 4], // R, W, R², W²
+    max: [[f32; STATS_LANES]; 2], // R, W
+    min: [[f32; STATS_LANES]; 2],
+}
+
+impl Default for StatsLanes {
+    fn default() -> Self {
+        Self {
+            sum: [[0.0; STATS_LANES]; 4],
+            max: [[f32::NEG_INFINITY; STATS_LANES]; 2],
+            min: [[f32::INFINITY; STATS_LANES]; 2],
+        }
+    }
+}
+
+impl StatsLanes {
+    fn add(&mut self, r: &[f32; STATS_LANES], w: &[f32; STATS_LANES]) {
+        for l in 0..STATS_LANES {
+            self.add_one(l, r[l], w[l]);
+        }
+    }
+    
+    #[inline(always)]
+    fn add_one(&mut self, lane: usize, r: f32, w: f32) {
+        self.sum[0][lane] += r;
+        self.sum[1][lane] += w;
+        self.sum[2][lane] += r * r;
+        self.sum[3][lane] += w * w;
+        self.max[0][lane] = self.max[0][lane].max(r);
+        self.max[1][lane] = self.max[1][lane].max(w);
+        self.min[0][lane] = self.min[0][lane].min(r);
+        self.min[1][lane] = self.min[1][lane].min(w);
+    }
+    
+    /// Collapse the lanes (gradient sum left at zero)
+    fn finish(&self) -> StatsPartial {
+        let fold = |lanes: &[f32; STATS_LANES], f: fn(f32, f32) -> f32| lanes.iter().copied().reduce(f).unwrap();
+        StatsPartial {
+            sum_R: sum_lanes(&self.sum[0]),
+            sum_W: sum_lanes(&self.sum[1]),
+            sum_R_sq: sum_lanes(&self.sum[2]),
+            sum_W_sq: sum_lanes(&self.sum[3]),
+            sum_grad_R: 0.0,
+            max_R: fold(&self.max[0], f32::max),
+            max_W: fold(&self.max[1], f32::max),
+            min_R: fold(&self.min[0], f32::min),
+            min_W: fold(&self.min[1], f32::min),
+        }
+    }
+}
+
+fn sum_lanes(lanes: &[f32; STATS_LANES]) -> f64 {
+    lanes.iter().map(|&v| v as f64).sum()
+}
+
+/// Gaussian blobs extend this many sigmas from their center when seeding
+const BLOB_EXTENT_SIGMAS: f32 = 4.0;
+
+/// One Gaussian resource blob of `seed_resources_with`
+struct Blob {
+    x: f32,
+    y: f32,
+    amp: f32,
+    sigma: f32,
+}
+
+impl Blob {
+    /// Columns of row `y` inside the blob's ±4σ box, clipped to `w` cells
+    fn columns(&self, y: usize, w: usize) -> Range<usize> {
+        let reach = BLOB_EXTENT_SIGMAS * self.sigma;
+        if (y as f32 - self.y).abs() > reach {
+            return 0..0;
+        }
+        let lo = (self.x - reach).ceil().max(0.0) as usize;
+        let hi = ((self.x + reach).floor() + 1.0).clamp(0.0, w as f32) as usize;
+        lo..hi.max(lo)
+    }
+
+    fn at(&self, x: usize, y: usize) -> f32 {
+        let dx = x as f32 - self.x;
+        let dy = y as f32 - self.y;
+        let r2 = (dx * dx + dy * dy) / (2.0 * self.sigma * self.sigma);
+        self.amp * (-r2).exp()
+    }
+}
+
 /// Field manager for CPU-side operations
+///
+/// The mirror holds packed RGBA16F texels in exactly the layout of the GPU
+/// field texture, so uploads and readbacks are plain byte copies: 8 bytes per
+/// cell per texture layer instead of a 16-byte `FieldData`. Cells are
+/// converted to f32 on access; use the bulk routines (`cells`,
+/// `channel_f32` and their setters) when touching the whole grid.
 pub struct FieldManager {
     pub size: [u32; 2],
-    pub data: Vec<FieldData>,
+    pub texels: Vec<f16>, // Layer-major RGBA16F, see `FieldChannel`
     pub stats: FieldStats,
 }
 
 impl FieldManager {
     pub fn new(size: [u32; 2]) -> Self {
-        let data = vec![FieldData::zero(); (size[0] * size[1]) as usize];
+        let cells = size[0] as usize * size[1] as usize;
+        let texels = vec![f16::ZERO; cells * (FieldChannel::PER_LAYER * FieldChannel::LAYERS) as usize];
         
         Self {
             size,
-            data,
+            texels,
             stats: FieldStats::default(),
         }
     }
     
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.size[0] as usize * self.size[1] as usize
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
     pub fn get_index(&self, x: u32, y: u32) -> usize {
         (y * self.size[0] + x) as usize
     }
     
+    /// Position of `channel` of cell `index` in `texels`
+    fn texel_offset(&self, index: usize, channel: FieldChannel) -> usize {
+        let texel_len = FieldChannel::PER_LAYER as usize;
+        channel.layer() as usize * self.len() * texel_len + index * texel_len + channel.component()
+    }
+    
+    /// Read one channel of the cell at row-major `index`
+    pub fn channel(&self, index: usize, channel: FieldChannel) -> f32 {
+        self.texels[self.texel_offset(index, channel)].to_f32()
+    }
+    
+    /// Write one channel of the cell at row-major `index` (rounded to f16)
+    pub fn set_channel(&mut self, index: usize, channel: FieldChannel, value: f32) {
+        let offset = self.texel_offset(index, channel);
+        self.texels[offset] = f16::from_f32(value);
+    }
+    
+    /// The cell at row-major `index`
+    pub fn cell(&self, index: usize) -> FieldData {
+        let mut data = FieldData::zero();
+        for channel in FieldChannel::ALL {
+            data.set(channel, self.channel(index, channel));
+        }
+        data
+    }
+    
+    pub fn set_cell(&mut self, index: usize, data: FieldData) {
+        for channel in FieldChannel::ALL {
+            self.set_channel(index, channel, data.get(channel));
+        }
+    }
+    
     pub fn get(&self, x: u32, y: u32) -> FieldData {
-        let idx = self.get_index(x, y);
-        self.data[idx]
+        self.cell(self.get_index(x, y))
     }
     
     pub fn set(&mut self, x: u32, y: u32, data: FieldData) {
-        let idx = self.get_index(x, y);
-        self.data[idx] = data;
+        self.set_cell(self.get_index(x, y), data);
     }
     
     pub fn get_resource(&self, x: u32, y: u32) -> f32 {
-        self.get(x, y).R
+        self.channel(self.get_index(x, y), FieldChannel::Resource)
     }
     
     pub fn get_waste(&self, x: u32, y: u32) -> f32 {
-        self.get(x, y).W
+        self.channel(self.get_index(x, y), FieldChannel::Waste)
     }
     
     pub fn set_resource(&mut self, x: u32, y: u32, value: f32) {
-        let idx = self.get_index(x, y);
-        self.data[idx].R = value;
+        self.set_channel(self.get_index(x, y), FieldChannel::Resource, value);
     }
     
     pub fn set_waste(&mut self, x: u32, y: u32, value: f32) {
-        let idx = self.get_index(x, y);
-        self.data[idx].W = value;
+        self.set_channel(self.get_index(x, y), FieldChannel::Waste, value);
+    }
+    
+    /// Every cell as f32, row-major
+    pub fn cells(&self) -> Vec<FieldData> {
+        (0..self.len()).map(|i| self.cell(i)).collect()
+    }
+    
+    /// Replace every cell from row-major f32 data
+    pub fn set_cells(&mut self, cells: &[FieldData]) {
+        assert_eq!(cells.len(), self.len(), "cell count does not match the {}x{} field", self.size[0], self.size[1]);
+        for (i, data) in cells.iter().enumerate() {
+            self.set_cell(i, *data);
+        }
+    }
+    
+    /// Set every cell to `data`
+    pub fn fill(&mut self, data: FieldData) {
+        for i in 0..self.len() {
+            self.set_cell(i, data);
+        }
+    }
+    
+    /// One channel of every cell as f32, row-major
+    pub fn channel_f32(&self, channel: FieldChannel) -> Vec<f32> {
+        let start = self.texel_offset(0, channel);
+        let texel_len = FieldChannel::PER_LAYER as usize;
+        let layer = &self.texels[start - channel.component()..][..self.len() * texel_len];
+        // Convert whole runs of texels at once (F16C where available), then pick the component
+        let mut values = vec![0.0; self.len()];
+        values.par_chunks_mut(CONVERT_CELLS).zip(layer.par_chunks(CONVERT_CELLS * texel_len)).for_each(|(out, texels)| {
+            let mut converted = [0.0f32; CONVERT_CELLS * FieldChannel::PER_LAYER as usize];
+            texels.convert_to_f32_slice(&mut converted[..texels.len()]);
+            for (v, texel) in out.iter_mut().zip(converted.chunks_exact(texel_len)) {
+                *v = texel[channel.component()];
+            }
+        });
+        values
     }
     
+    /// Replace one channel of every cell from row-major f32 values
+    pub fn set_channel_f32(&mut self, channel: FieldChannel, values: &[f32]) {
+        assert_eq!(values.len(), self.len(), "value count does not match the {}x{} field", self.size[0], self.size[1]);
+        let start = self.texel_offset(0, channel);
+        let texel_len = FieldChannel::PER_LAYER as usize;
+        for (texel, &value) in self.texels[start..].iter_mut().step_by(texel_len).zip(values) {
+            *texel = f16::from_f32(value);
+        }
+    }
+    
+    /// Coarse-grained copy with every channel box-averaged over `factor`×`factor` blocks
+    ///
+    /// The result is ceil(size / factor) cells on each axis; blocks cut off by
+    /// the right or bottom edge average only the cells they cover. Stats are
+    /// recomputed at the reduced resolution.
+    pub fn downsample(&self, factor: u32) -> FieldManager {
+        assert!(factor > 0, "downsample factor must be at least 1");
+        let size = [self.size[0].div_ceil(factor), self.size[1].div_ceil(factor)];
+        let mut coarse = FieldManager::new(size);
+        for channel in FieldChannel::ALL {
+            let fine = self.channel_f32(channel);
+            let mut sums = vec![0.0f32; coarse.len()];
+            let mut counts = vec![0u32; coarse.len()];
+            for y in 0..self.size[1] {
+                for x in 0..self.size[0] {
+                    let at = ((y / factor) * size[0] + x / factor) as usize;
+                    sums[at] += fine[self.get_index(x, y)];
+                    counts[at] += 1;
+                }
+            }
+            let means: Vec<f32> = sums.iter().zip(&counts).map(|(&sum, &count)| sum / count as f32).collect();
+            coarse.set_channel_f32(channel, &means);
+        }
+        coarse.update_stats();
+        coarse
+    }
+
     /// Helper: clamp sigma in pixels so we don't create needle-thin gaussians on tiny worlds
     fn sigma_px(&self, min_dim: f32, pct: f32, min_px: f32) -> f32 {
         (min_dim * pct).max(min_px)
@@ -134,10 +471,17 @@ impl FieldManager {
         }
     }
 
-    /// Initialize field with gaussian blobs for resources
+    /// Initialize field with gaussian blobs for resources, using the default generator
     pub fn seed_resources(&mut self, seed: u64) {
-        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        self.seed_resources_with(&mut SimRng::new(RngKind::default(), seed));
+    }
 
+    /// Initialize field with gaussian blobs for resources, drawing from `rng`
+    ///
+    /// All random draws happen up front in a fixed order; rows are then filled
+    /// in parallel, each cell summing its blobs in that same order, so a seed
+    /// gives the same field on any thread count. Blobs are cut off at ±4σ.
+    pub fn seed_resources_with(&mut self, rng: &mut SimRng) {
         let w = self.size[0] as f32;
         let h = self.size[1] as f32;
         let min_dim = w.min(h);
@@ -147,29 +491,19 @@ impl FieldManager {
             log::warn!("World min dimension < 32; seeding will be very coarse.");
         }
 
-        // 0) Clear / baseline
-        for data in &mut self.data {
-            *data = FieldData::zero();
-        }
-
         // 1) Primary center source
         let center_x = 0.5 * w;
         let center_y = 0.5 * h;
         let amp_center = 0.8;                         // baseline amplitude
         let sig_center = self.sigma_px(min_dim, 0.07, 2.0); // ~7% of min dimension, ≥2px
+        let mut blobs = vec![Blob { x: center_x, y: center_y, amp: amp_center, sigma: sig_center }];
         
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
-                let dx = x as f32 - center_x;
-                let dy = y as f32 - center_y;
-                let r2 = (dx * dx + dy * dy) / (2.0 * sig_center * sig_center);
-                let resource = amp_center * (-r2).exp();
-                self.set_resource(x, y, resource);
-            }
-        }
+        // Long worlds get a set of clusters and sources per short-side square,
+        // so a 1024×128 river is as patchy along its length as a 128×128 world
+        let stretch = (w.max(h) / min_dim).floor().max(1.0) as usize;
         
         // 2) Clusters — scale count by size
-        let num_clusters: usize = if min_dim < 192.0 { 4 } else { 8 };
+        let num_clusters: usize = (if min_dim < 192.0 { 4 } else { 8 }) * stretch;
         let (cx_lo, cx_hi) = self.span_pct(w, 0.15, 0.85);
         let (cy_lo, cy_hi) = self.span_pct(h, 0.15, 0.85);
         
@@ -178,21 +512,11 @@ impl FieldManager {
             let cluster_y = rng.gen_range(cy_lo..cy_hi);
             let amp = rng.gen_range(0.3..0.7);
             let sigma = self.sigma_px(min_dim, 0.05, 2.0);
-            
-            for y in 0..self.size[1] {
-                for x in 0..self.size[0] {
-                    let dx = x as f32 - cluster_x;
-                    let dy = y as f32 - cluster_y;
-                    let r2 = (dx * dx + dy * dy) / (2.0 * sigma * sigma);
-                    let resource = amp * (-r2).exp();
-                    let current = self.get_resource(x, y);
-                    self.set_resource(x, y, current + resource);
-                }
-            }
+            blobs.push(Blob { x: cluster_x, y: cluster_y, amp, sigma });
         }
         
         // 3) Scattered sources — also size-aware
-        let num_sources: usize = if min_dim < 192.0 { 8 } else { 15 };
+        let num_sources: usize = (if min_dim < 192.0 { 8 } else { 15 }) * stretch;
         let (sx_lo, sx_hi) = self.span_pct(w, 0.05, 0.95);
         let (sy_lo, sy_hi) = self.span_pct(h, 0.05, 0.95);
         
@@ -201,17 +525,7 @@ impl FieldManager {
             let cy = rng.gen_range(sy_lo..sy_hi);
             let amp = rng.gen_range(0.2..0.5);
             let sigma = self.sigma_px(min_dim, 0.02, 1.5);
-            
-            for y in 0..self.size[1] {
-                for x in 0..self.size[0] {
-                    let dx = x as f32 - cx;
-                    let dy = y as f32 - cy;
-                    let r2 = (dx * dx + dy * dy) / (2.0 * sigma * sigma);
-                    let resource = amp * (-r2).exp();
-                    let current = self.get_resource(x, y);
-                    self.set_resource(x, y, current + resource);
-                }
-            }
+            blobs.push(Blob { x: cx, y: cy, amp, sigma });
         }
         
         // 4) Gentle gradient (directional ramp)
@@ -219,129 +533,111 @@ impl FieldManager {
         let dir_x = theta.cos();
         let dir_y = theta.sin();
         let grad_amp = 0.15 * amp_center; // subtle
-        
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
+
+        // 5) Rows in parallel: blobs, ramp, then the final clamp (non-negative);
+        // resources accumulate in f32 and are packed once at the end
+        let width = self.size[0] as usize;
+        let mut resource = vec![0.0f32; self.len()];
+        resource.par_chunks_mut(width.max(1)).enumerate().for_each(|(y, row)| {
+            for blob in &blobs {
+                for x in blob.columns(y, width) {
+                    row[x] += blob.at(x, y);
+                }
+            }
+            let dy = y as f32 - center_y;
+            for (x, r) in row.iter_mut().enumerate() {
                 let dx = x as f32 - center_x;
-                let dy = y as f32 - center_y;
                 let proj = dx * dir_x + dy * dir_y;
                 let gradient_factor = (proj / min_dim).max(-0.5).min(0.5);
-                let current = self.get_resource(x, y);
-                self.set_resource(x, y, current + gradient_factor * grad_amp);
+                *r = (*r + gradient_factor * grad_amp).max(0.0);
             }
-        }
-
-        // 5) Final clamp (non-negative)
-        for data in &mut self.data {
-            data.R = data.R.max(0.0);
-        }
+        });
+        self.texels.fill(f16::ZERO);
+        self.set_channel_f32(FieldChannel::Resource, &resource);
     }
     
     /// Calculate field statistics
+    ///
+    /// Rows are split into fixed bands reduced in parallel, each with
+    /// `STATS_LANES`-wide accumulators the compiler vectorizes; the band sums
+    /// are combined in row order, so the result does not depend on the thread
+    /// count.
     pub fn update_stats(&mut self) {
-        let mut sum_R = 0.0;
-        let mut sum_W = 0.0;
-        let mut sum_R_sq = 0.0;
-        let mut sum_W_sq = 0.0;
-        let mut sum_grad_R = 0.0;
-        let mut max_R = f32::NEG_INFINITY;
-        let mut max_W = f32::NEG_INFINITY;
-        let mut min_R = f32::INFINITY;
-        let mut min_W = f32::INFINITY;
+        let [w, h] = [self.size[0] as usize, self.size[1] as usize];
+        let resource = self.channel_f32(FieldChannel::Resource);
+        let waste = self.channel_f32(FieldChannel::Waste);
         
-        let count = self.data.len() as f32;
-        
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
-                let data = self.get(x, y);
-                let R = data.R;
-                let W = data.W;
-                
-                sum_R += R;
-                sum_W += W;
-                sum_R_sq += R * R;
-                sum_W_sq += W * W;
-                
-                max_R = max_R.max(R);
-                max_W = max_W.max(W);
-                min_R = min_R.min(R);
-                min_W = min_W.min(W);
-                
-                // Calculate gradient magnitude (central differences)
-                if x > 0 && x < self.size[0] - 1 && y > 0 && y < self.size[1] - 1 {
-                    let dx = (self.get_resource(x + 1, y) - self.get_resource(x - 1, y)) / 2.0;
-                    let dy = (self.get_resource(x, y + 1) - self.get_resource(x, y - 1)) / 2.0;
-                    let grad_mag = (dx * dx + dy * dy).sqrt();
-                    sum_grad_R += grad_mag;
-                }
-            }
-        }
+        let bands: Vec<StatsPartial> = (0..h.div_ceil(STATS_BAND_ROWS))
+            .into_par_iter()
+            .map(|band| {
+                let rows = band * STATS_BAND_ROWS..((band + 1) * STATS_BAND_ROWS).min(h);
+                StatsPartial::of_band(&resource, &waste, w, h, rows)
+            })
+            .collect();
+        let total = bands.into_iter().fold(StatsPartial::default(), StatsPartial::merge);
+        let (sum_soil, max_soil) = self.channel_f32(FieldChannel::Soil)
+            .par_chunks(STATS_BAND_ROWS * w.max(1))
+            .map(|band| (band.iter().map(|&d| d as f64).sum::<f64>(), band.iter().copied().fold(0.0, f32::max)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .fold((0.0, 0.0), |(sum, max), (s, m)| (sum + s, f32::max(max, m)));
         
-        let mean_R = sum_R / count;
-        let mean_W = sum_W / count;
-        let var_R = (sum_R_sq / count) - (mean_R * mean_R);
-        let var_W = (sum_W_sq / count) - (mean_W * mean_W);
-        let mean_grad_R = sum_grad_R / count;
+        let count = self.len() as f64;
+        let mean_R = total.sum_R / count;
+        let mean_W = total.sum_W / count;
+        let var_R = (total.sum_R_sq / count) - (mean_R * mean_R);
+        let var_W = (total.sum_W_sq / count) - (mean_W * mean_W);
         
         self.stats = FieldStats {
-            mean_R,
-            mean_W,
-            var_R: var_R.max(0.0), // Ensure variance is non-negative
-            var_W: var_W.max(0.0),
-            mean_grad_R,
-            max_R,
-            max_W,
-            min_R,
-            min_W,
+            mean_R: mean_R as f32,
+            mean_W: mean_W as f32,
+            var_R: var_R.max(0.0) as f32, // Ensure variance is non-negative
+            var_W: var_W.max(0.0) as f32,
+            mean_grad_R: (total.sum_grad_R / count) as f32,
+            max_R: total.max_R,
+            max_W: total.max_W,
+            min_R: total.min_R,
+            min_W: total.min_W,
+            mean_soil: (sum_soil / count) as f32,
+            max_soil,
         };
     }
     
-    /// Add noise to resource field
+    /// Add noise to resource field, using the default generator
     pub fn add_noise(&mut self, sigma: f32, seed: u64) {
+        self.add_noise_with(sigma, &mut SimRng::new(RngKind::default(), seed));
+    }
+
+    /// Add noise to resource field, drawing from `rng`
+    pub fn add_noise_with(&mut self, sigma: f32, rng: &mut SimRng) {
         if sigma <= 0.0 {
             return;
         }
         
-        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
-        
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
-                let noise = rng.gen_range(-sigma..sigma);
-                let current = self.get_resource(x, y);
-                self.set_resource(x, y, (current + noise).max(0.0)); // Clamp to non-negative
-            }
+        let mut resource = self.channel_f32(FieldChannel::Resource);
+        for r in &mut resource {
+            let noise = rng.gen_range(-sigma..sigma);
+            *r = (*r + noise).max(0.0); // Clamp to non-negative
         }
+        self.set_channel_f32(FieldChannel::Resource, &resource);
     }
     
-    /// Convert to RGBA16F format for GPU texture
-    pub fn to_rgba16f(&self) -> Vec<f16> {
-        let mut result = Vec::with_capacity(self.data.len() * 4);
-        
-        for data in &self.data {
-            result.push(f16::from_f32(data.R));
-            result.push(f16::from_f32(data.W));
-            result.push(f16::from_f32(0.0)); // Unused channel
-            result.push(f16::from_f32(0.0)); // Unused channel
-        }
-        
-        result
+    /// The packed RGBA16F texels, ready for a texture upload
+    ///
+    /// Layer-major: every texel of array layer 0, then layer 1, and so on,
+    /// with channels placed according to `FieldChannel`.
+    pub fn as_rgba16f(&self) -> &[f16] {
+        &self.texels
     }
     
-    /// Convert from RGBA16F format from GPU texture
-    pub fn from_rgba16f(&mut self, data: &[f16]) {
-        let expected_len = self.data.len() * 4;
-        if data.len() != expected_len {
-            panic!("Invalid data length: expected {}, got {}", expected_len, data.len());
-        }
-        
-        for (i, data_slice) in data.chunks_exact(4).enumerate() {
-            if i < self.data.len() {
-                self.data[i] = FieldData {
-                    R: data_slice[0].to_f32(),
-                    W: data_slice[1].to_f32(),
-                    _pad: [0.0, 0.0],
-                };
-            }
+    /// Replace the mirror with RGBA16F texels read back from the GPU (layer-major, see `as_rgba16f`)
+    ///
+    /// Fails, leaving the mirror untouched, when `data` is not exactly the mirror's size.
+    pub fn from_rgba16f(&mut self, data: &[f16]) -> Result<(), String> {
+        if data.len() != self.texels.len() {
+            return Err(format!("expected {} f16 values for a {}x{} field, got {}", self.texels.len(), self.size[0], self.size[1], data.len()));
         }
+        self.texels.copy_from_slice(data);
+        Ok(())
     }
 }
@@ -0,0 +1,43 @@
+use vireo_params::{ForcingConfig, SimulationConfig};
+
+/// Parameters driven by the `forcing` time series of a config
+///
+/// Keeps each forced parameter's configured value so `scale` series multiply
+/// the value from the config file, not the previous step's.
+#[derive(Debug, Clone)]
+pub struct ParamForcing {
+    series: Vec<(ForcingConfig, f32)>, // Series and the configured value of its parameter
+}
+
+impl ParamForcing {
+    /// The forcing for `config`, or `None` when no series has samples
+    pub fn new(config: &SimulationConfig) -> Option<Self> {
+        let mut config = config.clone();
+        let series: Vec<_> = config.forcing.clone().into_iter()
+            .filter(|f| !f.points.is_empty())
+            .filter_map(|f| config.scalar_mut(&f.param).map(|v| (f, *v)))
+            .collect();
+        (!series.is_empty()).then_some(Self { series })
+    }
+
+    /// The forced parameters' series, in config order
+    pub fn series(&self) -> impl Iterator<Item = &ForcingConfig> {
+        self.series.iter().map(|(f, _)| f)
+    }
+
+    /// Value of each forced parameter during `step`, in config order
+    pub fn values_at(&self, step: u32) -> Vec<f32> {
+        self.series.iter()
+            .map(|(f, base)| f.value_at(step).map_or(*base, |v| f.effective(*base, v)))
+            .collect()
+    }
+
+    /// Set every forced parameter in `config` to its value during `step`
+    pub fn apply(&self, config: &mut SimulationConfig, step: u32) {
+        for ((f, _), value) in self.series.iter().zip(self.values_at(step)) {
+            if let Some(slot) = config.scalar_mut(&f.param) {
+                *slot = value;
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Instant;
+use vireo_params::GuardConfig;
+use crate::sim::{Agent, FieldStats};
+
+/// Why a guard stopped a run
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardTrip {
+    /// Mean R or W (`what`) is NaN or infinite
+    NonFiniteField { what: &'static str, value: f32 },
+    /// `count` alive agents have a NaN or infinite energy
+    NonFiniteEnergy { count: u32 },
+    /// Mean R above `guards.max_mean_R`
+    ResourceBound { mean_R: f32, bound: f32 },
+    /// Mean wall time per step since the previous check above `guards.max_step_ms`
+    SlowSteps { step_ms: f32, limit: f32 },
+}
+
+impl std::fmt::Display for GuardTrip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuardTrip::NonFiniteField { what, value } => write!(f, "{} is {}", what, value),
+            GuardTrip::NonFiniteEnergy { count } => write!(f, "{} agents have a non-finite energy", count),
+            GuardTrip::ResourceBound { mean_R, bound } => write!(f, "mean R {} exceeds guards.max_mean_R = {}", mean_R, bound),
+            GuardTrip::SlowSteps { step_ms, limit } => write!(f, "{:.1} ms per step exceeds guards.max_step_ms = {}", step_ms, limit),
+        }
+    }
+}
+
+/// Kill-switch state of one run (`guards`)
+///
+/// Step times are averaged between checks, so steps queued on the GPU and
+/// waited for at the readback are counted where they ran.
+#[derive(Debug, Clone)]
+pub struct RunGuard {
+    config: GuardConfig,
+    last: Option<(u32, Instant)>, // Step and time of the previous check
+}
+
+impl RunGuard {
+    pub fn new(config: &GuardConfig) -> Self {
+        Self { config: config.clone(), last: None }
+    }
+
+    /// Whether a check reads the field stats (sweeps skip the field readback otherwise)
+    pub fn needs_field(&self) -> bool {
+        self.config.non_finite || self.config.max_mean_R > 0.0
+    }
+
+    /// Start timing steps from `step` at `now` (a run's first check only times from there)
+    pub fn start(&mut self, step: u32, now: Instant) {
+        self.last = Some((step, now));
+    }
+
+    /// Check the state after `step` at `now`; `field` is skipped when `None`
+    pub fn check(&mut self, step: u32, now: Instant, field: Option<&FieldStats>, agents: &[Agent]) -> Option<GuardTrip> {
+        let c = &self.config;
+        if let (Some(field), true) = (field, c.non_finite) {
+            for (what, value) in [("mean R", field.mean_R), ("mean W", field.mean_W)] {
+                if !value.is_finite() {
+                    return Some(GuardTrip::NonFiniteField { what, value });
+                }
+            }
+        }
+        if c.non_finite {
+            let count = agents.iter().filter(|a| a.is_alive() && !a.energy.is_finite()).count() as u32;
+            if count > 0 {
+                return Some(GuardTrip::NonFiniteEnergy { count });
+            }
+        }
+        if let Some(field) = field.filter(|_| c.max_mean_R > 0.0) {
+            if field.mean_R > c.max_mean_R {
+                return Some(GuardTrip::ResourceBound { mean_R: field.mean_R, bound: c.max_mean_R });
+            }
+        }
+
+        let previous = self.last.replace((step, now));
+        if let Some((last_step, last_time)) = previous.filter(|_| c.max_step_ms > 0.0) {
+            if step > last_step {
+                let step_ms = (now.duration_since(last_time).as_secs_f64() * 1000.0 / (step - last_step) as f64) as f32;
+                if step_ms > c.max_step_ms {
+                    return Some(GuardTrip::SlowSteps { step_ms, limit: c.max_step_ms });
+                }
+            }
+        }
+        None
+    }
+}
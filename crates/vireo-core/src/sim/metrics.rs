@@ -0,0 +1,298 @@
+use std::fmt;
+use std::time::Duration;
+use vireo_params::{TimeUniform, SPECIES};
+use crate::sim::{AgentStats, Diversity, EventSummary, FieldStats, MovementStats, SampledField, SpeciesStats, TraitStats};
+
+/// One cell of a metrics row, printed the way its source type prints
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetricValue {
+    Int(u64),
+    F32(f32),
+    F64(f64),
+}
+
+impl fmt::Display for MetricValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricValue::Int(v) => v.fmt(f),
+            MetricValue::F32(v) => v.fmt(f),
+            MetricValue::F64(v) => v.fmt(f),
+        }
+    }
+}
+
+impl From<u32> for MetricValue {
+    fn from(v: u32) -> Self {
+        MetricValue::Int(v as u64)
+    }
+}
+
+impl From<f32> for MetricValue {
+    fn from(v: f32) -> Self {
+        MetricValue::F32(v)
+    }
+}
+
+impl From<f64> for MetricValue {
+    fn from(v: f64) -> Self {
+        MetricValue::F64(v)
+    }
+}
+
+/// Everything a provider may read when a metrics row is sampled
+#[derive(Debug, Clone, Copy)]
+pub struct MetricsSample<'a> {
+    pub step: u32,
+    pub time: &'a TimeUniform,
+    pub field: &'a FieldStats,
+    pub agents: &'a AgentStats,
+    pub movement: &'a MovementStats,
+    pub species: &'a [SpeciesStats],
+    pub step_time: Duration, // Wall time of the step, including its readbacks
+    pub events: Option<&'a EventSummary>, // Last completed event window (`output.event_window`)
+    pub sampling: Option<&'a SampledField>, // Quadtree sample `field` was estimated from (`output.sampling`)
+    pub diversity: Option<&'a Diversity>,   // Species diversity over `output.quadrats`
+    pub traits: Option<&'a [TraitStats]>,  // Chemotaxis trait distributions per species (`evolution`)
+}
+
+/// Source of one or more named metrics columns
+///
+/// `columns` is read once at registration; `record` then pushes exactly one
+/// value per column for every sampled row. Providers may keep state across
+/// rows (histories, running sums).
+pub trait MetricProvider {
+    fn columns(&self) -> Vec<String>;
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>);
+}
+
+/// Ordered set of metric providers; the header is their columns in registration order
+///
+/// Frontends register the built-in providers below and any of their own at
+/// startup, then hand the registry to whatever writes the rows.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    providers: Vec<(Box<dyn MetricProvider>, usize)>, // Provider and its column count
+    columns: Vec<String>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `provider`'s columns; fails without registering on a name already taken
+    pub fn register(&mut self, provider: impl MetricProvider + 'static) -> Result<(), String> {
+        let columns = provider.columns();
+        for (i, column) in columns.iter().enumerate() {
+            if self.columns.contains(column) || columns[..i].contains(column) {
+                return Err(format!("metric column `{}` is registered twice", column));
+            }
+        }
+        self.providers.push((Box::new(provider), columns.len()));
+        self.columns.extend(columns);
+        Ok(())
+    }
+
+    /// Header of every row
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Sample every provider into one row, in column order
+    pub fn row(&mut self, sample: &MetricsSample) -> Vec<MetricValue> {
+        let mut row = Vec::with_capacity(self.columns.len());
+        for (provider, count) in &mut self.providers {
+            let start = row.len();
+            provider.record(sample, &mut row);
+            assert_eq!(row.len() - start, *count, "metric provider wrote {} values for {} columns", row.len() - start, count);
+        }
+        row
+    }
+}
+
+/// `step`, plus the day/night `day_phase` and `light`
+pub struct ClockColumns;
+
+impl MetricProvider for ClockColumns {
+    fn columns(&self) -> Vec<String> {
+        ["step", "day_phase", "light"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        row.push(sample.step.into());
+        row.extend([sample.time.day_phase, sample.time.light].map(MetricValue::from));
+    }
+}
+
+/// Field means, variances and extremes (`FieldStats`)
+pub struct FieldColumns;
+
+impl MetricProvider for FieldColumns {
+    fn columns(&self) -> Vec<String> {
+        ["mean_R", "mean_W", "var_R", "var_W", "mean_grad_R", "max_R", "max_W", "min_R", "min_W"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let f = sample.field;
+        row.extend([f.mean_R, f.mean_W, f.var_R, f.var_W, f.mean_grad_R, f.max_R, f.max_W, f.min_R, f.min_W].map(MetricValue::from));
+    }
+}
+
+/// Soil quality (1 − damage) over the field, for runs with `soil.enabled`
+pub struct SoilColumns;
+
+impl MetricProvider for SoilColumns {
+    fn columns(&self) -> Vec<String> {
+        ["mean_soil_quality", "min_soil_quality"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let f = sample.field;
+        row.extend([1.0 - f.mean_soil, 1.0 - f.max_soil].map(MetricValue::from));
+    }
+}
+
+/// Standard errors of the sampled mean R and W and the cells read, for runs
+/// with `output.sampling` (rows computed from the whole field read zeros)
+pub struct SamplingColumns;
+
+impl MetricProvider for SamplingColumns {
+    fn columns(&self) -> Vec<String> {
+        ["se_mean_R", "se_mean_W", "sampled_cells"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let s = sample.sampling.cloned().unwrap_or_default();
+        row.extend([s.se_R, s.se_W].map(MetricValue::from));
+        row.push(s.cells.into());
+    }
+}
+
+/// Population size, energy and speed (`AgentStats`)
+pub struct AgentColumns;
+
+impl MetricProvider for AgentColumns {
+    fn columns(&self) -> Vec<String> {
+        ["alive_count", "total_energy", "mean_energy", "mean_velocity", "foraging_efficiency"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let a = sample.agents;
+        row.push(a.alive_count.into());
+        row.extend([a.total_energy, a.mean_energy, a.mean_velocity, a.foraging_efficiency].map(MetricValue::from));
+    }
+}
+
+/// Share of alive agents foraging and dispersing (see `chemotaxis.disperse_energy`)
+pub struct BehaviorColumns;
+
+impl MetricProvider for BehaviorColumns {
+    fn columns(&self) -> Vec<String> {
+        ["foraging_fraction", "dispersing_fraction"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let a = sample.agents;
+        let dispersing = if a.alive_count > 0 { a.dispersing_count as f32 / a.alive_count as f32 } else { 0.0 };
+        let foraging = if a.alive_count > 0 { 1.0 - dispersing } else { 0.0 };
+        row.extend([foraging, dispersing].map(MetricValue::from));
+    }
+}
+
+/// Divergence, up-gradient flux and alignment (`MovementStats`)
+pub struct MovementColumns;
+
+impl MetricProvider for MovementColumns {
+    fn columns(&self) -> Vec<String> {
+        ["mean_divergence", "mean_abs_divergence", "net_flux", "mean_alignment"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let m = sample.movement;
+        row.extend([m.mean_divergence, m.mean_abs_divergence, m.net_flux, m.mean_alignment].map(MetricValue::from));
+    }
+}
+
+/// Wall time of the sampled step and the frame rate it implies
+pub struct TimingColumns;
+
+impl MetricProvider for TimingColumns {
+    fn columns(&self) -> Vec<String> {
+        ["wall_time_ms", "fps_proxy"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let wall_time_ms = sample.step_time.as_millis() as f64;
+        let fps_proxy = if wall_time_ms > 0.0 { 1000.0 / wall_time_ms } else { 0.0 };
+        row.extend([wall_time_ms, fps_proxy].map(MetricValue::from));
+    }
+}
+
+/// Births and deaths of the last completed event window and how clustered
+/// the deaths were, for runs with `output.event_window` (zeros before the
+/// first window closes)
+pub struct EventColumns;
+
+impl MetricProvider for EventColumns {
+    fn columns(&self) -> Vec<String> {
+        ["window_births", "window_starved", "window_random_deaths", "death_cells", "death_hotspot_share"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let e = sample.events.copied().unwrap_or_default();
+        row.extend([e.births, e.starved, e.random_deaths].map(MetricValue::Int));
+        row.push(e.death_cells.into());
+        row.push(e.death_hotspot_share.into());
+    }
+}
+
+/// `SpeciesStats::metrics` for every species, e.g. `births_herbivores`
+pub struct SpeciesColumns;
+
+impl MetricProvider for SpeciesColumns {
+    fn columns(&self) -> Vec<String> {
+        SPECIES.iter()
+            .flat_map(|species| SpeciesStats::default().metrics().map(|(metric, _)| format!("{}_{}", metric, species)))
+            .collect()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        for species in sample.species {
+            row.extend(species.metrics().map(|(_, value)| MetricValue::from(value)));
+        }
+    }
+}
+
+/// `TraitStats::metrics` for every species, e.g. `mean_chi_gain_herbivores`
+pub struct TraitColumns;
+
+impl MetricProvider for TraitColumns {
+    fn columns(&self) -> Vec<String> {
+        SPECIES.iter()
+            .flat_map(|species| TraitStats::default().metrics().map(|(metric, _)| format!("{}_{}", metric, species)))
+            .collect()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        for kind in 0..SPECIES.len() {
+            let traits = sample.traits.and_then(|t| t.get(kind)).cloned().unwrap_or_default();
+            row.extend(traits.metrics().map(|(_, value)| MetricValue::from(value)));
+        }
+    }
+}
+
+/// Species richness, Shannon and Gini–Simpson indices and Whittaker's beta
+/// diversity across quadrats (`Diversity`)
+pub struct DiversityColumns;
+
+impl MetricProvider for DiversityColumns {
+    fn columns(&self) -> Vec<String> {
+        ["species_richness", "shannon", "simpson", "mean_alpha", "beta_diversity"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        let d = sample.diversity.copied().unwrap_or_default();
+        row.push(d.richness.into());
+        row.extend([d.shannon, d.simpson, d.mean_alpha, d.beta].map(MetricValue::from));
+    }
+}
@@ -1,5 +1,77 @@
 pub mod fields;
 pub mod agents;
+pub mod rng;
+pub mod bloom;
+pub mod rain;
+pub mod cohort;
+pub mod cull;
+pub mod tracks;
+pub mod stations;
+pub mod streaming;
+pub mod procedural;
+pub mod snapshot;
+pub mod checkpoints;
+pub mod sweep;
+pub mod branch;
+pub mod optimize;
+pub mod species;
+pub mod obstacles;
+pub mod terrain;
+pub mod diffusion;
+pub mod refuges;
+pub mod clock;
+pub mod metrics;
+pub mod age;
+pub mod traits;
+pub mod script;
+#[cfg(feature = "scripting")]
+pub mod scenario;
+pub mod reference;
+pub mod perturbation;
+pub mod forcing;
+pub mod events;
+pub mod energy;
+pub mod population;
+pub mod sampling;
+pub mod diversity;
+pub mod audit;
+pub mod guards;
 
 pub use fields::*;
 pub use agents::*;
+pub use rng::*;
+pub use bloom::*;
+pub use rain::*;
+pub use cohort::*;
+pub use cull::*;
+pub use tracks::*;
+pub use stations::*;
+pub use streaming::*;
+pub use procedural::*;
+pub use snapshot::*;
+pub use checkpoints::*;
+pub use sweep::*;
+pub use branch::*;
+pub use optimize::*;
+pub use species::*;
+pub use obstacles::*;
+pub use terrain::*;
+pub use diffusion::*;
+pub use refuges::*;
+pub use clock::*;
+pub use metrics::*;
+pub use age::*;
+pub use traits::*;
+pub use script::*;
+#[cfg(feature = "scripting")]
+pub use scenario::*;
+pub use reference::*;
+pub use perturbation::*;
+pub use forcing::*;
+pub use events::*;
+pub use energy::*;
+pub use population::*;
+pub use sampling::*;
+pub use diversity::*;
+pub use audit::*;
+pub use guards::*;
@@ -0,0 +1,137 @@
+use vireo_params::{ObstacleBrushParams, ObstacleConfig};
+use crate::sim::Agent;
+
+/// Disk painted into (or cut out of) the obstacle field by the viewer's wall tool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObstacleBrush {
+    pub center: [f32; 2],
+    pub radius: f32,
+    pub erase: bool,
+}
+
+impl ObstacleBrush {
+    /// New distance at `p` given the old one `d` (same as obstacle_brush.wgsl)
+    ///
+    /// Drawing takes the union with the disk and erasing subtracts it. The sign
+    /// stays exact and the magnitude never overestimates the true distance,
+    /// which is all the RD faces and wall avoidance rely on.
+    pub fn apply(&self, p: [f32; 2], d: f32) -> f32 {
+        let dx = p[0] - self.center[0];
+        let dy = p[1] - self.center[1];
+        let disk = (dx * dx + dy * dy).sqrt() - self.radius;
+        if self.erase { d.max(-disk) } else { d.min(disk) }
+    }
+
+    /// Pack for the brush shader
+    pub fn to_params(&self) -> ObstacleBrushParams {
+        ObstacleBrushParams { center: self.center, radius: self.radius, erase: self.erase as u32 }
+    }
+}
+
+/// Signed distance to the nearest obstacle at every cell centre (negative inside)
+///
+/// This is the texture the agent and RD passes read. `sample`, `normal` and
+/// `face_aperture` mirror the shader helpers, so CPU code (spawning, tests)
+/// sees the same walls as the GPU.
+#[derive(Debug, Clone)]
+pub struct ObstacleField {
+    size: [u32; 2],
+    distances: Vec<f32>,
+}
+
+impl ObstacleField {
+    /// Distance stored when there are no obstacles (further than any world edge)
+    pub const FAR: f32 = 1.0e6;
+
+    const PUSH_OUT_ITERATIONS: usize = 8;
+
+    /// Evaluate the union of `config.shapes` at each cell centre (all `FAR` when disabled)
+    pub fn new(config: &ObstacleConfig, size: [u32; 2]) -> Self {
+        let shapes = if config.enabled { config.shapes.as_slice() } else { &[] };
+        let distances = (0..size[1])
+            .flat_map(|y| (0..size[0]).map(move |x| [x as f32 + 0.5, y as f32 + 0.5]))
+            .map(|p| shapes.iter().map(|s| s.distance(p)).fold(Self::FAR, f32::min))
+            .collect();
+        Self { size, distances }
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// Row-major distances, one per cell
+    pub fn distances(&self) -> &[f32] {
+        &self.distances
+    }
+
+    /// Distance at the centre of cell (x, y), clamped to the grid
+    pub fn at(&self, x: i32, y: i32) -> f32 {
+        let x = x.clamp(0, self.size[0] as i32 - 1) as usize;
+        let y = y.clamp(0, self.size[1] as i32 - 1) as usize;
+        self.distances[y * self.size[0] as usize + x]
+    }
+
+    /// Bilinear distance at a world position (same as `obstacle_distance` in agent_step.wgsl)
+    pub fn sample(&self, p: [f32; 2]) -> f32 {
+        let t = [p[0] - 0.5, p[1] - 0.5];
+        let base = [t[0].floor(), t[1].floor()];
+        let f = [t[0] - base[0], t[1] - base[1]];
+        let (x, y) = (base[0] as i32, base[1] as i32);
+        let bottom = self.at(x, y) * (1.0 - f[0]) + self.at(x + 1, y) * f[0];
+        let top = self.at(x, y + 1) * (1.0 - f[0]) + self.at(x + 1, y + 1) * f[0];
+        bottom * (1.0 - f[1]) + top * f[1]
+    }
+
+    /// Unit gradient of the distance (pointing away from the nearest wall), or zero where flat
+    pub fn normal(&self, p: [f32; 2]) -> [f32; 2] {
+        let g = [
+            self.sample([p[0] + 1.0, p[1]]) - self.sample([p[0] - 1.0, p[1]]),
+            self.sample([p[0], p[1] + 1.0]) - self.sample([p[0], p[1] - 1.0]),
+        ];
+        let len = g[0].hypot(g[1]);
+        if len > 1e-6 { [g[0] / len, g[1] / len] } else { [0.0, 0.0] }
+    }
+
+    /// Open fraction of the face between two neighbouring cells (same as rd_step.wgsl)
+    ///
+    /// The distance is interpolated to the face midpoint; the face is fully
+    /// open half a cell or more from a wall and closed half a cell inside one.
+    pub fn face_aperture(phi_a: f32, phi_b: f32) -> f32 {
+        (0.5 * (phi_a + phi_b) + 0.5).clamp(0.0, 1.0)
+    }
+
+    /// Apply `brush` at every cell centre, as the GPU brush does to the texture
+    pub fn paint(&mut self, brush: &ObstacleBrush) {
+        let width = self.size[0] as usize;
+        for (i, d) in self.distances.iter_mut().enumerate() {
+            let p = [(i % width) as f32 + 0.5, (i / width) as f32 + 0.5];
+            *d = brush.apply(p, *d);
+        }
+    }
+
+    /// Move agents spawned inside a wall onto its surface
+    ///
+    /// Interpolation flattens the distance along a wall's medial ridge, so one
+    /// projection can stop short of the surface; a few more finish the job.
+    pub fn push_out(&self, agents: &mut [Agent]) {
+        for agent in agents.iter_mut() {
+            let mut moved = false;
+            for _ in 0..Self::PUSH_OUT_ITERATIONS {
+                let p = agent.pos;
+                let d = self.sample(p);
+                let n = self.normal(p);
+                if d >= 0.0 || n == [0.0, 0.0] {
+                    break;
+                }
+                agent.pos = [
+                    (p[0] - d * n[0]).clamp(0.0, self.size[0] as f32 - 0.1),
+                    (p[1] - d * n[1]).clamp(0.0, self.size[1] as f32 - 0.1),
+                ];
+                moved = true;
+            }
+            if moved {
+                agent.memory_pos = agent.pos;
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use rand::Rng;
+use crate::sim::{ParamRange, SimRng};
+
+/// Observation noise added to the kernel diagonal (objectives come from stochastic runs)
+const NOISE: f64 = 1e-3;
+/// Minimum improvement over the incumbent that expected improvement rewards
+const XI: f64 = 0.01;
+
+/// Gaussian-process Bayesian optimizer over box-bounded parameters (maximizes)
+///
+/// Points are kept in the unit cube; the surrogate is a zero-mean GP with a
+/// squared-exponential kernel on standardized objective values, and each
+/// suggestion is the random candidate with the highest expected improvement.
+/// Cheap enough for the tens of evaluations a simulation budget allows.
+pub struct BayesOpt {
+    ranges: Vec<ParamRange>,
+    length_scale: f64, // Kernel length scale in unit-cube coordinates
+    xs: Vec<Vec<f64>>,
+    ys: Vec<f64>,
+}
+
+impl BayesOpt {
+    pub fn new(ranges: &[ParamRange], length_scale: f64) -> Self {
+        Self {
+            ranges: ranges.to_vec(),
+            length_scale,
+            xs: Vec::new(),
+            ys: Vec::new(),
+        }
+    }
+
+    /// Record the objective value of an evaluated point (in parameter units)
+    pub fn observe(&mut self, point: &[f32], value: f64) {
+        self.xs.push(self.to_unit(point));
+        self.ys.push(value);
+    }
+
+    /// Number of observations so far
+    pub fn len(&self) -> usize {
+        self.ys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ys.is_empty()
+    }
+
+    /// Best observed point and value
+    pub fn best(&self) -> Option<(Vec<f32>, f64)> {
+        let i = (0..self.ys.len()).max_by(|&a, &b| self.ys[a].total_cmp(&self.ys[b]))?;
+        Some((self.to_params(&self.xs[i]), self.ys[i]))
+    }
+
+    /// Next point to evaluate: the best of `candidates` uniform draws by expected improvement
+    ///
+    /// Falls back to a uniform draw until there are two observations.
+    pub fn suggest(&self, rng: &mut SimRng, candidates: usize) -> Vec<f32> {
+        let dims = self.ranges.len();
+        let mut draw = || (0..dims).map(|_| rng.gen::<f64>()).collect::<Vec<f64>>();
+        let Some(gp) = (self.ys.len() >= 2).then(|| self.fit()).flatten() else {
+            return self.to_params(&draw());
+        };
+
+        let best = gp.ys.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mut choice = draw();
+        let mut choice_ei = f64::NEG_INFINITY;
+        for _ in 0..candidates.max(1) {
+            let x = draw();
+            let (mu, sigma) = gp.predict(&x, self.length_scale);
+            let z = (mu - best - XI) / sigma;
+            let ei = (mu - best - XI) * normal_cdf(z) + sigma * normal_pdf(z);
+            if ei > choice_ei {
+                choice = x;
+                choice_ei = ei;
+            }
+        }
+        self.to_params(&choice)
+    }
+
+    /// Posterior mean and standard deviation of the standardized objective at `point`
+    pub fn predict(&self, point: &[f32]) -> Option<(f64, f64)> {
+        self.fit().map(|gp| gp.predict(&self.to_unit(point), self.length_scale))
+    }
+
+    fn to_unit(&self, point: &[f32]) -> Vec<f64> {
+        point
+            .iter()
+            .zip(&self.ranges)
+            .map(|(&v, r)| if r.hi > r.lo { ((v - r.lo) / (r.hi - r.lo)) as f64 } else { 0.5 })
+            .collect()
+    }
+
+    fn to_params(&self, unit: &[f64]) -> Vec<f32> {
+        unit.iter().zip(&self.ranges).map(|(&u, r)| r.lo + (r.hi - r.lo) * u as f32).collect()
+    }
+
+    /// Factorize the kernel matrix of the observations (None if there are none
+    /// or it is not positive definite)
+    fn fit(&self) -> Option<Gp> {
+        let n = self.ys.len();
+        if n == 0 {
+            return None;
+        }
+        let mean = self.ys.iter().sum::<f64>() / n as f64;
+        let var = self.ys.iter().map(|y| (y - mean) * (y - mean)).sum::<f64>() / n as f64;
+        let scale = if var > 0.0 { var.sqrt() } else { 1.0 };
+        let ys: Vec<f64> = self.ys.iter().map(|y| (y - mean) / scale).collect();
+
+        let k = self.xs.iter().enumerate().map(|(i, xi)| {
+            self.xs.iter().enumerate().map(|(j, xj)| {
+                kernel(xi, xj, self.length_scale) + if i == j { NOISE } else { 0.0 }
+            }).collect()
+        }).collect();
+        let l = cholesky(k)?;
+        let alpha = solve_upper_t(&l, &solve_lower(&l, &ys));
+        Some(Gp { xs: self.xs.clone(), ys, l, alpha })
+    }
+}
+
+/// A fitted surrogate: Cholesky factor and weights of the standardized observations
+struct Gp {
+    xs: Vec<Vec<f64>>,
+    ys: Vec<f64>,
+    l: Vec<Vec<f64>>,
+    alpha: Vec<f64>,
+}
+
+impl Gp {
+    fn predict(&self, x: &[f64], length_scale: f64) -> (f64, f64) {
+        let k: Vec<f64> = self.xs.iter().map(|xi| kernel(xi, x, length_scale)).collect();
+        let mu = k.iter().zip(&self.alpha).map(|(a, b)| a * b).sum();
+        let v = solve_lower(&self.l, &k);
+        let var = 1.0 + NOISE - v.iter().map(|v| v * v).sum::<f64>();
+        (mu, var.max(1e-12).sqrt())
+    }
+}
+
+fn kernel(a: &[f64], b: &[f64], length_scale: f64) -> f64 {
+    let d2: f64 = a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum();
+    (-d2 / (2.0 * length_scale * length_scale)).exp()
+}
+
+/// Lower-triangular L with L Lᵀ = `a`
+fn cholesky(mut a: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    for j in 0..n {
+        let d = a[j][j] - (0..j).map(|k| a[j][k] * a[j][k]).sum::<f64>();
+        if d <= 0.0 {
+            return None;
+        }
+        a[j][j] = d.sqrt();
+        for i in j + 1..n {
+            a[i][j] = (a[i][j] - (0..j).map(|k| a[i][k] * a[j][k]).sum::<f64>()) / a[j][j];
+        }
+        a[j][j + 1..].fill(0.0);
+    }
+    Some(a)
+}
+
+/// Solve L x = b
+fn solve_lower(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let mut x = vec![0.0; b.len()];
+    for i in 0..b.len() {
+        x[i] = (b[i] - (0..i).map(|k| l[i][k] * x[k]).sum::<f64>()) / l[i][i];
+    }
+    x
+}
+
+/// Solve Lᵀ x = b
+fn solve_upper_t(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        x[i] = (b[i] - (i + 1..n).map(|k| l[k][i] * x[k]).sum::<f64>()) / l[i][i];
+    }
+    x
+}
+
+fn normal_pdf(z: f64) -> f64 {
+    (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2))
+}
+
+/// Abramowitz & Stegun 7.1.26 (absolute error below 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let y = 1.0 - poly * (-x * x).exp();
+    if x >= 0.0 { y } else { -y }
+}
@@ -0,0 +1,72 @@
+use rand::Rng;
+use vireo_params::{RngKind, SimulationConfig};
+use crate::sim::SimRng;
+
+/// Offset mixed into the world seed so perturbations draw from their own stream
+const PERTURBATION_SEED_SALT: u64 = 0x9E27_0000_5EED_0002;
+
+/// Parameter jitter of `PerturbationConfig` around the configured values
+///
+/// Each draw seeds a fresh generator from the world seed and the draw number,
+/// so the values in effect at a step do not depend on how the run got there
+/// (restores, recoveries, replays).
+#[derive(Debug, Clone)]
+pub struct ParamPerturbation {
+    every: u32,
+    sigma: f32,
+    rng: RngKind,
+    seed: u64,
+    base: Vec<(String, f32)>, // Parameter and its configured value
+}
+
+impl ParamPerturbation {
+    /// The jitter for `config`, or `None` when perturbation is off or lists no parameter
+    pub fn new(config: &SimulationConfig) -> Option<Self> {
+        let p = &config.perturbation;
+        if !p.enabled || p.params.is_empty() {
+            return None;
+        }
+        let mut config = config.clone();
+        let base = p.params.iter()
+            .filter_map(|param| config.scalar_mut(param).map(|v| (param.clone(), *v)))
+            .collect();
+        Some(Self {
+            every: p.every,
+            sigma: p.sigma,
+            rng: config.world.rng,
+            seed: config.world.seed ^ PERTURBATION_SEED_SALT,
+            base,
+        })
+    }
+
+    /// Steps between draws
+    pub fn every(&self) -> u32 {
+        self.every
+    }
+
+    /// Whether a new draw takes effect at `step`
+    pub fn draws_at(&self, step: u32) -> bool {
+        step.is_multiple_of(self.every)
+    }
+
+    /// Names of the perturbed parameters, in config order
+    pub fn params(&self) -> impl Iterator<Item = &str> {
+        self.base.iter().map(|(param, _)| param.as_str())
+    }
+
+    /// Value of each perturbed parameter during `step`, in config order
+    pub fn values_at(&self, step: u32) -> Vec<f32> {
+        let draw = (step / self.every) as u64;
+        let mut rng = SimRng::new(self.rng, self.seed ^ draw.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        self.base.iter().map(|(_, value)| value * (1.0 + rng.gen_range(-self.sigma..=self.sigma))).collect()
+    }
+
+    /// Set every perturbed parameter in `config` to its value during `step`
+    pub fn apply(&self, config: &mut SimulationConfig, step: u32) {
+        for ((param, _), value) in self.base.iter().zip(self.values_at(step)) {
+            if let Some(slot) = config.scalar_mut(param) {
+                *slot = value;
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::Path;
+use crate::sim::{Agent, AgentLayout, AgentManager, AgentStats};
+use crate::{AgentConfig, SimulationConfig, SPECIES};
+
+/// Agents imported from an `agents_NNNN.csv` export (`--init-agents`)
+///
+/// Columns are found by name in the header row. `x`, `y`, `energy` and
+/// `kind` are required; `vx`, `vy`, `age` and `state` default to 0, `cohort`
+/// to untagged, and rows with `alive` = 0 are skipped. Each agent remembers
+/// its own position, so movement memory starts over in the new landscape.
+#[derive(Debug, Clone)]
+pub struct AgentPopulation {
+    pub agents: Vec<Agent>,
+}
+
+impl AgentPopulation {
+    /// Columns every row needs
+    pub const REQUIRED: [&'static str; 4] = ["x", "y", "energy", "kind"];
+
+    pub fn read_csv(path: &Path) -> Result<Self, String> {
+        let mut reader = csv::Reader::from_path(path).map_err(|e| e.to_string())?;
+        let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+        let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+        let required = Self::REQUIRED.map(|name| column(name).ok_or_else(|| {
+            format!("no `{}` column (found {})", name, headers.iter().collect::<Vec<_>>().join(", "))
+        }));
+        let [x, y, energy, kind] = match required {
+            [Ok(x), Ok(y), Ok(e), Ok(k)] => [x, y, e, k],
+            _ => return Err(required.into_iter().find_map(Result::err).unwrap_or_default()),
+        };
+        let optional = ["vx", "vy", "alive", "cohort", "age", "state"].map(column);
+
+        let mut agents = Vec::new();
+        for (row, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| e.to_string())?;
+            let text = |i: usize| record.get(i).unwrap_or_default().trim();
+            let float = |i: usize| text(i).parse::<f32>().ok().filter(|v| v.is_finite())
+                .ok_or_else(|| format!("row {}: `{}` is not a finite number in column `{}`", row + 1, text(i), &headers[i]));
+            let int = |i: usize| text(i).parse::<u32>()
+                .map_err(|_| format!("row {}: `{}` is not a whole number in column `{}`", row + 1, text(i), &headers[i]));
+            let [vx, vy, alive, cohort, age, state] = optional;
+            if alive.map(int).transpose()? == Some(0) {
+                continue;
+            }
+            let k = int(kind)?;
+            if k as usize >= SPECIES.len() {
+                return Err(format!("row {}: kind {} is not 0 (plant), 1 (herbivore) or 2 (predator)", row + 1, k));
+            }
+            let mut agent = Agent::new(glam::Vec2::new(float(x)?, float(y)?), float(energy)?, k);
+            agent.vel = [vx.map(float).transpose()?.unwrap_or(0.0), vy.map(float).transpose()?.unwrap_or(0.0)];
+            agent.cohort = cohort.map(int).transpose()?.unwrap_or(agent.cohort);
+            agent.age = age.map(int).transpose()?.unwrap_or(0);
+            agent.state = state.map(int).transpose()?.unwrap_or(Agent::FORAGING).min(Agent::DISPERSING);
+            agents.push(agent);
+        }
+        Ok(Self { agents })
+    }
+
+    /// Agents per kind (0 = plant, 1 = herbivore, 2 = predator)
+    pub fn counts(&self) -> [u32; 3] {
+        let mut counts = [0; 3];
+        for a in &self.agents {
+            counts[a.kind as usize] += 1;
+        }
+        counts
+    }
+
+    /// Raise `agents.capacity` where a species has more imported agents than slots
+    pub fn fit_capacity(&self, agents: &mut AgentConfig) {
+        let slots = agents.slots();
+        let counts = self.counts();
+        let capacity = [&mut agents.capacity.plants, &mut agents.capacity.herbivores, &mut agents.capacity.predators];
+        for (kind, c) in capacity.into_iter().enumerate() {
+            *c = slots[kind].max(counts[kind]);
+        }
+    }
+
+    /// Fails if an agent lies outside the world or a species has more agents than slots
+    pub fn check_compatible(&self, config: &SimulationConfig) -> Result<(), String> {
+        let size = config.world.size.map(|s| s as f32);
+        if let Some((i, a)) = self.agents.iter().enumerate()
+            .find(|(_, a)| !(0.0..size[0]).contains(&a.pos[0]) || !(0.0..size[1]).contains(&a.pos[1])) {
+            return Err(format!("agent {} at ({}, {}) lies outside the {}x{} world", i, a.pos[0], a.pos[1], size[0], size[1]));
+        }
+        let slots = config.agents.slots();
+        for (kind, &count) in self.counts().iter().enumerate() {
+            if count > slots[kind] {
+                return Err(format!("{} {} but only {} slots (raise agents.capacity.{})", count, SPECIES[kind], slots[kind], SPECIES[kind]));
+            }
+        }
+        Ok(())
+    }
+
+    /// An agent manager holding these agents at the start of their species'
+    /// ranges of `config.slots()`, the other slots free
+    pub fn manager(&self, config: &AgentConfig) -> AgentManager {
+        let layout = AgentLayout::new(config.slots());
+        let mut agents = Vec::with_capacity(layout.total() as usize);
+        for kind in 0..3 {
+            let capacity = layout.capacity(kind) as usize;
+            agents.extend(self.agents.iter().filter(|a| a.kind == kind).take(capacity).copied());
+            agents.resize(layout.range(kind).end as usize, Agent::free(kind));
+        }
+        AgentManager {
+            forage: vec![[0.0; 2]; agents.len()],
+            agents,
+            stats: AgentStats::default(),
+            layout,
+        }
+    }
+}
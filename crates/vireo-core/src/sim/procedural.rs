@@ -0,0 +1,51 @@
+/// Lattice spacings (cells) and weights of the value-noise octaves
+const OCTAVES: [(f32, f32); 3] = [(64.0, 0.55), (24.0, 0.3), (9.0, 0.15)];
+/// Noise level below which a cell holds no resource
+const RESOURCE_THRESHOLD: f32 = 0.45;
+/// Resource at the noise maximum
+const RESOURCE_PEAK: f32 = 0.8;
+
+/// SplitMix64 finaliser, used to hash lattice points
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Uniform value in [0, 1) at an integer lattice point
+fn lattice(seed: u64, octave: usize, x: i64, y: i64) -> f32 {
+    let h = mix(seed ^ mix(octave as u64 ^ mix(x as u64 ^ mix(y as u64))));
+    (h >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Smoothly interpolated lattice noise in [0, 1)
+fn value_noise(seed: u64, octave: usize, x: f32, y: f32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+    let (sx, sy) = (tx * tx * (3.0 - 2.0 * tx), ty * ty * (3.0 - 2.0 * ty));
+    let (ix, iy) = (x0 as i64, y0 as i64);
+    let v00 = lattice(seed, octave, ix, iy);
+    let v10 = lattice(seed, octave, ix + 1, iy);
+    let v01 = lattice(seed, octave, ix, iy + 1);
+    let v11 = lattice(seed, octave, ix + 1, iy + 1);
+    let top = v00 + (v10 - v00) * sx;
+    let bottom = v01 + (v11 - v01) * sx;
+    top + (bottom - top) * sy
+}
+
+/// Initial resource at a global cell of the endless landscape
+///
+/// A pure function of `(seed, x, y)`, so a chunk regenerated after eviction
+/// matches its neighbours seamlessly: patches of resource where layered
+/// value noise rises above `RESOURCE_THRESHOLD`, empty ground elsewhere.
+///
+/// Only `std` is used here: the root sandbox includes this file directly.
+pub fn procedural_resource(seed: u64, x: i64, y: i64) -> f32 {
+    let n: f32 = OCTAVES
+        .iter()
+        .enumerate()
+        .map(|(i, &(scale, weight))| weight * value_noise(seed, i, x as f32 / scale, y as f32 / scale))
+        .sum();
+    ((n - RESOURCE_THRESHOLD) / (1.0 - RESOURCE_THRESHOLD)).max(0.0) * RESOURCE_PEAK
+}
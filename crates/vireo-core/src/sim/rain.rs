@@ -0,0 +1,111 @@
+use rand::Rng;
+use vireo_params::{BloomParams, RainConfig, WorldConfig, MAX_RAIN_CELLS};
+use crate::sim::SimRng;
+
+/// Offset mixed into the world seed so rain draws from its own stream
+const RAIN_SEED_SALT: u64 = 0x4A1D_CE11_5EED_0000;
+
+/// A single rain cell drifting across the world
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RainCell {
+    pub origin: [f32; 2],   // Centre at `start_step`
+    pub velocity: [f32; 2], // Cells per step
+    pub radius: f32,
+    pub amplitude: f32,
+    pub start_step: u32,
+    pub end_step: u32, // First step the cell is no longer active
+}
+
+impl RainCell {
+    /// Centre at `step`
+    pub fn center(&self, step: u32) -> [f32; 2] {
+        let t = step.saturating_sub(self.start_step) as f32;
+        [self.origin[0] + self.velocity[0] * t, self.origin[1] + self.velocity[1] * t]
+    }
+}
+
+/// CPU-side scheduler for moving rain cells
+///
+/// Each step a new cell starts with probability `rate` at a uniformly random
+/// centre and lives for `duration` steps, or until it has drifted three radii
+/// past the world's edge. Like `BloomScheduler` it owns its RNG stream, so
+/// enabling rain does not perturb blooms or seeding.
+#[derive(Debug, Clone)]
+pub struct RainScheduler {
+    config: RainConfig,
+    size: [u32; 2],
+    rng: SimRng,
+    active: Vec<RainCell>,
+    step: u32, // Step of the last tick, where `pack` places the cells
+}
+
+impl RainScheduler {
+    pub fn new(config: RainConfig, world: &WorldConfig) -> Self {
+        Self {
+            config,
+            size: world.size,
+            rng: SimRng::new(world.rng, world.seed ^ RAIN_SEED_SALT),
+            active: Vec::with_capacity(MAX_RAIN_CELLS),
+            step: 0,
+        }
+    }
+
+    /// Advance to `step`, expiring and spawning cells; returns true if the boost map
+    /// needs redrawing (a cell started or ended, or some are moving)
+    pub fn tick(&mut self, step: u32) -> bool {
+        self.step = step;
+        let before = self.active.len();
+        let size = self.size;
+        self.active.retain(|c| {
+            let [x, y] = c.center(step);
+            let margin = 3.0 * c.radius;
+            c.end_step > step && x > -margin && y > -margin && x < size[0] as f32 + margin && y < size[1] as f32 + margin
+        });
+        let mut changed = self.active.len() != before || !self.active.is_empty();
+
+        if self.config.enabled
+            && self.active.len() < MAX_RAIN_CELLS
+            && self.rng.gen::<f32>() < self.config.rate
+        {
+            let origin = [
+                self.rng.gen_range(0.0..self.size[0] as f32),
+                self.rng.gen_range(0.0..self.size[1] as f32),
+            ];
+            let jitter = self.config.velocity_jitter;
+            let mut offset = [0.0; 2];
+            if jitter > 0.0 {
+                offset = [self.rng.gen_range(-jitter..=jitter), self.rng.gen_range(-jitter..=jitter)];
+            }
+            self.active.push(RainCell {
+                origin,
+                velocity: [self.config.velocity[0] + offset[0], self.config.velocity[1] + offset[1]],
+                radius: self.config.radius,
+                amplitude: self.config.amplitude,
+                start_step: step,
+                end_step: step.saturating_add(self.config.duration),
+            });
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Currently active rain cells
+    pub fn active(&self) -> &[RainCell] {
+        &self.active
+    }
+
+    /// Add the active cells, at their centres for the last tick, to the injection params
+    pub fn pack(&self, params: &mut BloomParams) {
+        for (slot, c) in params.rain.iter_mut().zip(&self.active) {
+            let [x, y] = c.center(self.step);
+            *slot = [x, y, c.radius, c.amplitude];
+        }
+        params.rain_count = self.active.len() as u32;
+    }
+
+    /// Drop all active cells and restart the RNG stream (world reset)
+    pub fn reset(&mut self, world: &WorldConfig) {
+        *self = Self::new(self.config.clone(), world);
+    }
+}
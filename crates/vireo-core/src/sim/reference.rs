@@ -0,0 +1,155 @@
+use std::ops::{Add, Div, Mul, Sub};
+use vireo_params::{FieldChannel, RDParams, SimulationConfig};
+use crate::sim::FieldManager;
+
+/// Float type a `ReferenceField` computes and stores in (f32 or f64)
+pub trait Real: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> {
+    fn of(v: f32) -> Self;
+    fn to_f64(self) -> f64;
+}
+
+impl Real for f32 {
+    fn of(v: f32) -> Self {
+        v
+    }
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Real for f64 {
+    fn of(v: f32) -> Self {
+        v as f64
+    }
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+fn max<T: Real>(a: T, b: T) -> T {
+    if a > b { a } else { b }
+}
+
+fn min<T: Real>(a: T, b: T) -> T {
+    if a < b { a } else { b }
+}
+
+/// CPU replica of the reaction-diffusion pass (rd_step.wgsl) in `T` precision
+///
+/// The GPU keeps the field in an rgba16float texture, so every step rounds R, W
+/// and soil to half precision. Stepping this alongside a `Simulation`, fed the
+/// same occupancy, light and uniforms, separates that rounding from the model:
+/// an f32 and an f64 reference bound what the storage format costs. Obstacles,
+/// terrain anisotropy, the diffusion tensor, blooms and rain cells are not replicated (see `check`).
+#[derive(Debug, Clone)]
+pub struct ReferenceField<T: Real> {
+    size: [u32; 2],
+    resource: Vec<T>,
+    waste: Vec<T>,
+    soil: Vec<T>,
+}
+
+impl<T: Real> ReferenceField<T> {
+    /// Start from the values in `field` (the GPU's initial state after a `sync_field`)
+    pub fn new(field: &FieldManager) -> Self {
+        let channel = |c| field.channel_f32(c).into_iter().map(T::of).collect();
+        Self {
+            size: field.size,
+            resource: channel(FieldChannel::Resource),
+            waste: channel(FieldChannel::Waste),
+            soil: channel(FieldChannel::Soil),
+        }
+    }
+
+    /// Whether the reference can follow a world with this config
+    pub fn check(config: &SimulationConfig) -> Result<(), String> {
+        let unsupported = [
+            (config.obstacles.enabled, "obstacles"),
+            (config.terrain.enabled && config.terrain.anisotropy > 0.0, "terrain anisotropy"),
+            (config.diffusion.enabled, "anisotropic diffusion"),
+            (config.blooms.enabled, "blooms"),
+            (config.rain.enabled, "rain cells"),
+            (config.streaming.enabled, "streaming"),
+        ];
+        match unsupported.iter().find(|(on, _)| *on) {
+            Some((_, name)) => Err(format!("the CPU reference does not model {}; disable it to compare", name)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// Row-major values of `channel`, widened to f64
+    pub fn channel(&self, channel: FieldChannel) -> Vec<f64> {
+        let values = match channel {
+            FieldChannel::Resource => &self.resource,
+            FieldChannel::Waste => &self.waste,
+            FieldChannel::Soil => &self.soil,
+        };
+        values.iter().map(|v| v.to_f64()).collect()
+    }
+
+    /// One explicit Euler step under `params`, with the herbivore counts of
+    /// that step's agent pass and the day/night `light` (`TimeUniform::light`)
+    pub fn step(&mut self, params: &RDParams, occupancy: &[u32], light: f32) {
+        let [w, h] = [self.size[0] as usize, self.size[1] as usize];
+        assert_eq!(occupancy.len(), w * h, "occupancy does not match the {}x{} reference", w, h);
+        let (zero, one) = (T::of(0.0), T::of(1.0));
+        let [d_r, d_w, sigma_r, k_r] = [params.D_R, params.D_W, params.sigma_R, params.K_R].map(T::of);
+        let sigma_r = sigma_r * T::of(light);
+        let [alpha, beta, lambda_r, lambda_w, dt] = [params.alpha_H, params.beta_H, params.lambda_R, params.lambda_W, params.dt].map(T::of);
+        let [threshold, degradation, recovery] = [params.soil_threshold, params.soil_degradation, params.soil_recovery].map(T::of);
+
+        // 5-point Laplacian with Neumann boundaries (neighbours clamped to the grid)
+        let laplacian = |v: &[T], x: usize, y: usize| {
+            let c = v[y * w + x];
+            let l = v[y * w + x.saturating_sub(1)];
+            let r = v[y * w + (x + 1).min(w - 1)];
+            let u = v[y.saturating_sub(1) * w + x];
+            let d = v[(y + 1).min(h - 1) * w + x];
+            l + r + u + d - T::of(4.0) * c
+        };
+
+        let mut next = self.clone();
+        for y in 0..h {
+            for x in 0..w {
+                let i = y * w + x;
+                let (r, wa, s) = (self.resource[i], self.waste[i], self.soil[i]);
+                let herbivores = min(T::of(occupancy[i] as f32 * params.H_SCALE), one);
+
+                let growth = sigma_r * (one - s) * r * (one - r / k_r);
+                let uptake = alpha * herbivores * r;
+                let d_res = d_r * laplacian(&self.resource, x, y) + growth - uptake - lambda_r * r;
+                let d_waste = d_w * laplacian(&self.waste, x, y) + beta * herbivores - lambda_w * wa;
+                next.resource[i] = max(zero, r + dt * d_res);
+                next.waste[i] = max(zero, wa + dt * d_waste);
+
+                let d_soil = degradation * max(uptake - threshold, zero) - recovery * s;
+                next.soil[i] = min(max(s + dt * d_soil, zero), one);
+            }
+        }
+        *self = next;
+    }
+}
+
+/// How far one field strays from another: largest and root-mean-square cell difference
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Divergence {
+    pub max_abs: f64,
+    pub rms: f64,
+}
+
+impl Divergence {
+    pub fn between(a: &[f64], b: &[f64]) -> Self {
+        assert_eq!(a.len(), b.len());
+        let (mut max_abs, mut sum_sq) = (0.0f64, 0.0);
+        for (x, y) in a.iter().zip(b) {
+            let d = (x - y).abs();
+            max_abs = max_abs.max(d);
+            sum_sq += d * d;
+        }
+        Self { max_abs, rms: (sum_sq / a.len().max(1) as f64).sqrt() }
+    }
+}
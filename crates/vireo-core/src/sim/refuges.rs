@@ -0,0 +1,40 @@
+use vireo_params::RefugeConfig;
+
+/// Death probability multiplier of the demography pass at every cell centre
+///
+/// 1 outside every refuge, the lowest `mortality` of the regions containing
+/// the cell centre inside. This is the buffer the demography pass reads.
+#[derive(Debug, Clone)]
+pub struct RefugeMask {
+    size: [u32; 2],
+    mortality: Vec<f32>,
+}
+
+impl RefugeMask {
+    /// Rasterize `config.regions` at cell centres (all 1 when disabled)
+    pub fn new(config: &RefugeConfig, size: [u32; 2]) -> Self {
+        let regions = if config.enabled { config.regions.as_slice() } else { &[] };
+        let mortality = (0..size[1])
+            .flat_map(|y| (0..size[0]).map(move |x| [x as f32 + 0.5, y as f32 + 0.5]))
+            .map(|p| regions.iter().filter(|r| r.contains(p)).map(|r| r.mortality).fold(1.0, f32::min))
+            .collect();
+        Self { size, mortality }
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// Row-major multipliers, one per cell
+    pub fn mortality(&self) -> &[f32] {
+        &self.mortality
+    }
+
+    /// Multiplier at a world position (the cell it falls in, clamped to the grid)
+    pub fn at(&self, p: [f32; 2]) -> f32 {
+        let x = (p[0].max(0.0) as usize).min(self.size[0] as usize - 1);
+        let y = (p[1].max(0.0) as usize).min(self.size[1] as usize - 1);
+        self.mortality[y * self.size[0] as usize + x]
+    }
+
+}
@@ -0,0 +1,76 @@
+use rand::{Error, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rand_pcg::Pcg64;
+use rand_xoshiro::Xoshiro256PlusPlus;
+use vireo_params::{RngKind, WorldConfig};
+
+/// Seeded PRNG wrapper through which all CPU-side randomness is routed
+///
+/// The concrete algorithm is chosen by `WorldConfig::rng`; none of them read
+/// from the OS entropy pool, so identical (rng, seed) pairs always produce
+/// identical streams on every platform.
+#[derive(Debug, Clone)]
+pub enum SimRng {
+    ChaCha8(Box<ChaCha8Rng>), // Boxed: its block buffer would otherwise set the size of every variant
+    Pcg64(Pcg64),
+    Xoshiro256(Xoshiro256PlusPlus),
+}
+
+impl SimRng {
+    /// Create a generator of the given kind from a 64-bit seed
+    pub fn new(kind: RngKind, seed: u64) -> Self {
+        match kind {
+            RngKind::ChaCha8 => Self::ChaCha8(Box::new(ChaCha8Rng::seed_from_u64(seed))),
+            RngKind::Pcg64 => Self::Pcg64(Pcg64::seed_from_u64(seed)),
+            RngKind::Xoshiro256 => Self::Xoshiro256(Xoshiro256PlusPlus::seed_from_u64(seed)),
+        }
+    }
+
+    /// Create a generator from the world's configured algorithm and seed
+    pub fn from_world(world: &WorldConfig) -> Self {
+        Self::new(world.rng, world.seed)
+    }
+
+    /// The algorithm backing this generator
+    pub fn kind(&self) -> RngKind {
+        match self {
+            Self::ChaCha8(_) => RngKind::ChaCha8,
+            Self::Pcg64(_) => RngKind::Pcg64,
+            Self::Xoshiro256(_) => RngKind::Xoshiro256,
+        }
+    }
+}
+
+impl RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Self::ChaCha8(rng) => rng.next_u32(),
+            Self::Pcg64(rng) => rng.next_u32(),
+            Self::Xoshiro256(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Self::ChaCha8(rng) => rng.next_u64(),
+            Self::Pcg64(rng) => rng.next_u64(),
+            Self::Xoshiro256(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Self::ChaCha8(rng) => rng.fill_bytes(dest),
+            Self::Pcg64(rng) => rng.fill_bytes(dest),
+            Self::Xoshiro256(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        match self {
+            Self::ChaCha8(rng) => rng.try_fill_bytes(dest),
+            Self::Pcg64(rng) => rng.try_fill_bytes(dest),
+            Self::Xoshiro256(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use rand::Rng;
+use vireo_params::{FieldChannel, MetricsSampling, WorldConfig};
+use crate::sim::{FieldManager, FieldStats, SimRng};
+
+/// Salt mixed into the world seed for the sampled cells
+const SAMPLING_SEED_SALT: u64 = 0x5155_4144_5452_4545;
+
+/// One gathered cell: R, W, soil damage and |∇R| (central differences, 0 on the border)
+pub type CellSample = [f32; 4];
+
+/// Field stats estimated from a quadtree sample, with their error bounds
+#[derive(Debug, Clone, Default)]
+pub struct SampledField {
+    pub stats: FieldStats, // Means and variances are estimates; extremes are over the sampled cells
+    pub se_R: f32,         // Standard error of `stats.mean_R`
+    pub se_W: f32,         // Standard error of `stats.mean_W`
+    pub cells: u32,        // Cells read
+    pub leaves: u32,       // Quadtree leaves the estimate is stratified over
+    pub rounds: u32,       // Gathers (one GPU round trip each)
+}
+
+/// One sampled cell of a node, standing for the quadrant it was drawn in
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    area: f64, // Cells of its quadrant
+    cell: [u32; 2],
+    value: Option<CellSample>, // None until gathered
+}
+
+/// A rectangle of cells with one sample in each of its quadrants
+struct Node {
+    origin: [u32; 2],
+    size: [u32; 2],
+    samples: Vec<Sample>, // In `quadrants` order
+}
+
+impl Node {
+    /// Draw one cell per quadrant, keeping `inherited` for the quadrant it lies in
+    fn new(origin: [u32; 2], size: [u32; 2], inherited: Option<Sample>, rng: &mut SimRng) -> Self {
+        let samples = quadrants(origin, size).into_iter()
+            .map(|(o, s)| {
+                let inside = |p: [u32; 2]| (0..2).all(|a| p[a] >= o[a] && p[a] < o[a] + s[a]);
+                match inherited.filter(|i| inside(i.cell)) {
+                    Some(i) => Sample { area: s[0] as f64 * s[1] as f64, ..i },
+                    None => Sample {
+                        area: s[0] as f64 * s[1] as f64,
+                        cell: [o[0] + rng.gen_range(0..s[0]), o[1] + rng.gen_range(0..s[1])],
+                        value: None,
+                    },
+                }
+            })
+            .collect();
+        Self { origin, size, samples }
+    }
+
+    fn area(&self) -> f64 {
+        self.size[0] as f64 * self.size[1] as f64
+    }
+
+    /// Cells splitting this node would read
+    fn split_cost(&self) -> u32 {
+        quadrants(self.origin, self.size).iter().map(|&(o, s)| quadrants(o, s).len() as u32 - 1).sum()
+    }
+
+    /// Children, one per quadrant, each inheriting the sample drawn there
+    fn split(self, rng: &mut SimRng) -> Vec<Node> {
+        quadrants(self.origin, self.size).into_iter().zip(self.samples)
+            .map(|((o, s), sample)| Node::new(o, s, Some(sample), rng))
+            .collect()
+    }
+
+    /// Variance of this node's share of the world mean of `component`
+    ///
+    /// The quadrant samples are treated as a simple random sample of the node
+    /// (s²/n with the finite-population correction), so a node whose every
+    /// cell was read contributes nothing.
+    fn error(&self, component: usize, total: f64) -> f64 {
+        let n = self.samples.len() as f64;
+        if n < 2.0 {
+            return 0.0;
+        }
+        let values = self.samples.iter().map(|s| s.value.map_or(0.0, |v| v[component] as f64));
+        let mean = values.clone().sum::<f64>() / n;
+        let s2 = values.map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let share = self.area() / total;
+        share * share * s2 / n * (1.0 - n / self.area())
+    }
+}
+
+/// The up to four rectangles a node splits into: each axis longer than one cell is halved
+fn quadrants(origin: [u32; 2], size: [u32; 2]) -> Vec<([u32; 2], [u32; 2])> {
+    let halves = |o: u32, s: u32| if s > 1 { vec![(o, s / 2), (o + s / 2, s - s / 2)] } else { vec![(o, s)] };
+    let mut out = Vec::with_capacity(4);
+    for (y, h) in halves(origin[1], size[1]) {
+        for (x, w) in halves(origin[0], size[0]) {
+            out.push(([x, y], [w, h]));
+        }
+    }
+    out
+}
+
+/// Generator for the cells sampled at `step`, so reruns read the same cells
+pub fn sampling_rng(world: &WorldConfig, step: u32) -> SimRng {
+    SimRng::new(world.rng, world.seed ^ SAMPLING_SEED_SALT ^ (step as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Estimate the field stats of a `size` world from an adaptive quadtree sample
+///
+/// The root covers the world and reads one cell per quadrant; the tree is
+/// then split evenly while that costs at most a quarter of
+/// `settings.max_cells`. Every round, the leaves whose variance contribution to mean R or W is above average
+/// are split into their quadrants, each child keeping the cell its parent
+/// read there, so no cell is read twice. Sampling stops once both standard
+/// errors are within `settings.tolerance`, when the next split would read
+/// more than `settings.max_cells`, or when nothing is left to split. Each
+/// round hands all of its new cells to `gather` at once.
+pub fn sample_field(
+    size: [u32; 2],
+    settings: &MetricsSampling,
+    rng: &mut SimRng,
+    mut gather: impl FnMut(&[[u32; 2]]) -> Vec<CellSample>,
+) -> SampledField {
+    let total = size[0] as f64 * size[1] as f64;
+    let tolerance = settings.tolerance as f64;
+    let mut leaves = vec![Node::new([0, 0], size, None, rng)];
+    // A quarter of the budget is spread evenly, so a flat sample of the root
+    // cannot hide a small patch
+    let mut drawn = leaves[0].samples.len() as u32;
+    loop {
+        let cost: u32 = leaves.iter().map(Node::split_cost).sum();
+        if cost == 0 || drawn + cost > settings.max_cells / 4 {
+            break;
+        }
+        drawn += cost;
+        leaves = leaves.into_iter().flat_map(|l| l.split(rng)).collect();
+    }
+    let (mut cells, mut rounds) = (0u32, 0u32);
+    loop {
+        let pending: Vec<(usize, usize)> = leaves.iter().enumerate()
+            .flat_map(|(n, node)| node.samples.iter().enumerate().filter(|(_, s)| s.value.is_none()).map(move |(i, _)| (n, i)))
+            .collect();
+        if !pending.is_empty() {
+            let positions: Vec<[u32; 2]> = pending.iter().map(|&(n, i)| leaves[n].samples[i].cell).collect();
+            for (&(n, i), value) in pending.iter().zip(gather(&positions)) {
+                leaves[n].samples[i].value = Some(value);
+            }
+            cells += positions.len() as u32;
+            rounds += 1;
+        }
+
+        let var_r: f64 = leaves.iter().map(|l| l.error(0, total)).sum();
+        let var_w: f64 = leaves.iter().map(|l| l.error(1, total)).sum();
+        if var_r.sqrt() <= tolerance && var_w.sqrt() <= tolerance {
+            break;
+        }
+        let mut candidates: Vec<(usize, f64)> = leaves.iter().enumerate()
+            .map(|(n, l)| (n, l.error(0, total) + l.error(1, total)))
+            .filter(|&(_, e)| e > 0.0)
+            .collect();
+        if candidates.is_empty() {
+            break;
+        }
+        let threshold = candidates.iter().map(|c| c.1).sum::<f64>() / candidates.len() as f64;
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut budget = settings.max_cells.saturating_sub(cells);
+        let mut split = vec![false; leaves.len()];
+        for (n, e) in candidates {
+            let cost = leaves[n].split_cost();
+            if e < threshold || cost > budget {
+                break;
+            }
+            budget -= cost;
+            split[n] = true;
+        }
+        if !split.contains(&true) {
+            break;
+        }
+        let mut next = Vec::with_capacity(leaves.len() + 3 * split.len());
+        for (node, split) in leaves.into_iter().zip(split) {
+            if split {
+                next.extend(node.split(rng));
+            } else {
+                next.push(node);
+            }
+        }
+        leaves = next;
+    }
+
+    let mut sums = [0.0f64; 4];
+    let mut squares = [0.0f64; 2];
+    let mut stats = FieldStats {
+        max_R: f32::NEG_INFINITY,
+        max_W: f32::NEG_INFINITY,
+        min_R: f32::INFINITY,
+        min_W: f32::INFINITY,
+        ..FieldStats::default()
+    };
+    for sample in leaves.iter().flat_map(|l| &l.samples) {
+        let v = sample.value.unwrap_or_default();
+        let weight = sample.area / total;
+        for (sum, &x) in sums.iter_mut().zip(&v) {
+            *sum += weight * x as f64;
+        }
+        squares[0] += weight * (v[0] as f64).powi(2);
+        squares[1] += weight * (v[1] as f64).powi(2);
+        stats.max_R = stats.max_R.max(v[0]);
+        stats.max_W = stats.max_W.max(v[1]);
+        stats.min_R = stats.min_R.min(v[0]);
+        stats.min_W = stats.min_W.min(v[1]);
+        stats.max_soil = stats.max_soil.max(v[2]);
+    }
+    stats.mean_R = sums[0] as f32;
+    stats.mean_W = sums[1] as f32;
+    stats.mean_soil = sums[2] as f32;
+    stats.mean_grad_R = sums[3] as f32;
+    stats.var_R = (squares[0] - sums[0] * sums[0]).max(0.0) as f32;
+    stats.var_W = (squares[1] - sums[1] * sums[1]).max(0.0) as f32;
+
+    SampledField {
+        stats,
+        se_R: leaves.iter().map(|l| l.error(0, total)).sum::<f64>().sqrt() as f32,
+        se_W: leaves.iter().map(|l| l.error(1, total)).sum::<f64>().sqrt() as f32,
+        cells,
+        leaves: leaves.len() as u32,
+        rounds,
+    }
+}
+
+/// The sample the cell gather shader reads at `cell`, from the CPU mirror
+pub fn cell_sample(field: &FieldManager, cell: [u32; 2]) -> CellSample {
+    let [x, y] = cell;
+    let [w, h] = field.size;
+    let r = |x: u32, y: u32| field.channel(field.get_index(x, y), FieldChannel::Resource);
+    let grad = if x > 0 && y > 0 && x + 1 < w && y + 1 < h {
+        let dx = (r(x + 1, y) - r(x - 1, y)) * 0.5;
+        let dy = (r(x, y + 1) - r(x, y - 1)) * 0.5;
+        (dx * dx + dy * dy).sqrt()
+    } else {
+        0.0
+    };
+    let index = field.get_index(x, y);
+    [r(x, y), field.channel(index, FieldChannel::Waste), field.channel(index, FieldChannel::Soil), grad]
+}
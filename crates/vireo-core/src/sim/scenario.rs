@@ -0,0 +1,154 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use rhai::{CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use vireo_params::{SimulationConfig, SPECIES};
+use crate::sim::{CullRegion, FieldStats, Interaction, SpeciesStats};
+
+/// Operations one callback may run before it is stopped, so an endless loop fails the run instead of hanging it
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+/// What the script reads and the changes it makes during one callback
+#[derive(Default)]
+struct ScriptView {
+    config: SimulationConfig, // Live config when the callback started, with the callback's own `set_param`s
+    mean_r: f32,              // From the latest metrics sample
+    mean_w: f32,
+    alive: [u32; 3],
+    actions: Vec<Interaction>,
+}
+
+/// A Rhai scenario script with a per-step callback (`scripting` feature)
+///
+/// The script defines `fn on_step(step)`, called before every step with the
+/// number of steps completed so far. It reads the run through `param(name)`,
+/// `alive(species)`, `mean_R()`, `mean_W()`, `width()` and `height()`, and
+/// changes it through `set_param(name, value)`, `spawn_agents(species, x, y,
+/// radius, count)`, `inject(x, y, radius, amount)`, `wall(x, y, radius)`,
+/// `erase_wall(x, y, radius)`, `cull(x, y, radius)` and `tag(id, x, y,
+/// radius)`. Every change becomes an `Interaction`, so a scripted run can be
+/// replayed from its recorded events without the script.
+///
+/// `this` inside `on_step` is a map kept from call to call, for the
+/// script's own state (e.g. `this.released = true`). The population and
+/// field means are those of the latest metrics sample (`observe`).
+pub struct ScenarioScript {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+    memory: Dynamic,
+    view: Rc<RefCell<ScriptView>>,
+}
+
+/// Float from a Rhai integer or float argument
+fn number(value: &Dynamic) -> Result<f32, Box<EvalAltResult>> {
+    value.as_float().map(|v| v as f32)
+        .or_else(|_| value.as_int().map(|v| v as f32))
+        .map_err(|found| format!("expected a number, got {}", found).into())
+}
+
+impl ScenarioScript {
+    /// Compile `source` against the world of `config` and run its top-level statements once
+    pub fn compile(source: &str, config: &SimulationConfig) -> Result<Self, String> {
+        let view = Rc::new(RefCell::new(ScriptView { config: config.clone(), ..Default::default() }));
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+
+        let v = view.clone();
+        engine.register_fn("param", move |name: &str| -> Result<f64, Box<EvalAltResult>> {
+            let mut view = v.borrow_mut();
+            let value = view.config.scalar_mut(name).ok_or_else(|| format!("`{}` is not a parameter", name))?;
+            Ok(*value as f64)
+        });
+        let v = view.clone();
+        engine.register_fn("alive", move |species: &str| -> Result<i64, Box<EvalAltResult>> {
+            let kind = SPECIES.iter().position(|s| *s == species)
+                .ok_or_else(|| format!("unknown species `{}`; use one of {}", species, SPECIES.join(", ")))?;
+            Ok(v.borrow().alive[kind] as i64)
+        });
+        let v = view.clone();
+        engine.register_fn("mean_R", move || v.borrow().mean_r as f64);
+        let v = view.clone();
+        engine.register_fn("mean_W", move || v.borrow().mean_w as f64);
+        let v = view.clone();
+        engine.register_fn("width", move || v.borrow().config.world.size[0] as i64);
+        let v = view.clone();
+        engine.register_fn("height", move || v.borrow().config.world.size[1] as i64);
+
+        // Changes are checked like recorded events and applied by the caller after the callback
+        let act = |view: &Rc<RefCell<ScriptView>>, action: Interaction| -> Result<(), Box<EvalAltResult>> {
+            action.check()?;
+            let mut view = view.borrow_mut();
+            if let Interaction::Param { param, value } = &action {
+                if let Some(slot) = view.config.scalar_mut(param) {
+                    *slot = *value;
+                }
+            }
+            view.actions.push(action);
+            Ok(())
+        };
+        let v = view.clone();
+        engine.register_fn("set_param", move |param: &str, value: Dynamic| {
+            act(&v, Interaction::Param { param: param.to_string(), value: number(&value)? })
+        });
+        let v = view.clone();
+        engine.register_fn("spawn_agents", move |species: &str, x: Dynamic, y: Dynamic, radius: Dynamic, count: i64| {
+            let count = u32::try_from(count).map_err(|_| format!("spawn count must be within [0, {}], got {}", u32::MAX, count))?;
+            act(&v, Interaction::Spawn { species: species.to_string(), center: [number(&x)?, number(&y)?], radius: number(&radius)?, count })
+        });
+        let v = view.clone();
+        engine.register_fn("inject", move |x: Dynamic, y: Dynamic, radius: Dynamic, amount: Dynamic| {
+            act(&v, Interaction::Inject { center: [number(&x)?, number(&y)?], radius: number(&radius)?, amount: number(&amount)? })
+        });
+        for (name, erase) in [("wall", false), ("erase_wall", true)] {
+            let v = view.clone();
+            engine.register_fn(name, move |x: Dynamic, y: Dynamic, radius: Dynamic| {
+                act(&v, Interaction::Wall { center: [number(&x)?, number(&y)?], radius: number(&radius)?, erase })
+            });
+        }
+        let v = view.clone();
+        engine.register_fn("cull", move |x: Dynamic, y: Dynamic, radius: Dynamic| {
+            act(&v, Interaction::Cull { region: CullRegion::Circle { center: [number(&x)?, number(&y)?], radius: number(&radius)? } })
+        });
+        let v = view.clone();
+        engine.register_fn("tag", move |id: i64, x: Dynamic, y: Dynamic, radius: Dynamic| {
+            let id = u32::try_from(id).map_err(|_| format!("cohort id must be within [0, {}], got {}", u32::MAX, id))?;
+            act(&v, Interaction::Tag { id, center: [number(&x)?, number(&y)?], radius: number(&radius)? })
+        });
+
+        let ast = engine.compile(source).map_err(|e| format!("cannot compile scenario script: {}", e))?;
+        if !ast.iter_functions().any(|f| f.name == "on_step" && f.params.len() == 1) {
+            return Err("scenario script must define `fn on_step(step)`".to_string());
+        }
+        let mut scope = Scope::new();
+        engine.run_ast_with_scope(&mut scope, &ast).map_err(|e| format!("scenario script failed: {}", e))?;
+        view.borrow_mut().actions.clear();
+        Ok(Self { engine, ast, scope, memory: Dynamic::from_map(Map::new()), view })
+    }
+
+    /// Read and compile a script file
+    pub fn load(path: &Path, config: &SimulationConfig) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| format!("cannot read scenario script {}: {}", path.display(), e))?;
+        Self::compile(&source, config)
+    }
+
+    /// Population and field means the script sees until the next sample
+    pub fn observe(&mut self, field: &FieldStats, species: &[SpeciesStats]) {
+        let mut view = self.view.borrow_mut();
+        view.mean_r = field.mean_R;
+        view.mean_w = field.mean_W;
+        for s in species {
+            view.alive[s.kind.min(2) as usize] = s.alive;
+        }
+    }
+
+    /// Call `on_step(step)` under the live `config` and return the changes it
+    /// made, in call order, to apply before the step runs
+    pub fn on_step(&mut self, step: u32, config: &SimulationConfig) -> Result<Vec<Interaction>, String> {
+        self.view.borrow_mut().config = config.clone();
+        let options = CallFnOptions::new().eval_ast(false).rewind_scope(false).bind_this_ptr(&mut self.memory);
+        let _: Dynamic = self.engine.call_fn_with_options(options, &mut self.scope, &self.ast, "on_step", (step as i64,))
+            .map_err(|e| format!("scenario script failed at step {}: {}", step, e))?;
+        Ok(std::mem::take(&mut self.view.borrow_mut().actions))
+    }
+}
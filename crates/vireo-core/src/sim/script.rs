@@ -0,0 +1,156 @@
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use vireo_params::{SimulationConfig, SCHEDULABLE_PARAMS, SPECIES};
+use crate::sim::CullRegion;
+
+/// One interactive change to a running world, as the viewer makes it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum Interaction {
+    /// Set a scalar parameter (one of `SCHEDULABLE_PARAMS`)
+    Param { param: String, value: f32 },
+    /// Draw or erase a disc of wall (an `ObstacleBrush` stamp)
+    Wall {
+        center: [f32; 2],
+        radius: f32,
+        #[serde(default)]
+        erase: bool,
+    },
+    /// Kill every agent in a region
+    Cull {
+        #[serde(flatten)]
+        region: CullRegion,
+    },
+    /// Tag alive agents in a disc with a cohort id
+    Tag { id: u32, center: [f32; 2], radius: f32 },
+    /// Add agents of one species at random points of a disc, in free slots of its range
+    Spawn { species: String, center: [f32; 2], radius: f32, count: u32 },
+    /// Add resource to every cell of a disc
+    Inject { center: [f32; 2], radius: f32, amount: f32 },
+}
+
+impl Interaction {
+    /// Whether a `StateSnapshot` leaves this change out (params and walls
+    /// live in the config and obstacle texture, not in the field or agents)
+    pub fn outlives_snapshots(&self) -> bool {
+        matches!(self, Interaction::Param { .. } | Interaction::Wall { .. })
+    }
+
+    /// Check that the parameter or species exists and the numbers are finite
+    pub fn check(&self) -> Result<(), String> {
+        let disc = |center: &[f32; 2], radius: f32| center.iter().all(|v| v.is_finite()) && radius.is_finite() && radius >= 0.0;
+        let finite = match self {
+            Interaction::Param { param, value } => {
+                if !SCHEDULABLE_PARAMS.contains(&param.as_str()) {
+                    return Err(format!("`{}` cannot be set; use one of {}", param, SCHEDULABLE_PARAMS.join(", ")));
+                }
+                value.is_finite()
+            }
+            Interaction::Wall { center, radius, .. } | Interaction::Tag { center, radius, .. } => disc(center, *radius),
+            Interaction::Cull { region: CullRegion::Rect { a, b } } => a.iter().chain(b).all(|v| v.is_finite()),
+            Interaction::Cull { region: CullRegion::Circle { center, radius } } => disc(center, *radius),
+            Interaction::Spawn { species, center, radius, .. } => {
+                if !SPECIES.contains(&species.as_str()) {
+                    return Err(format!("unknown species `{}`; use one of {}", species, SPECIES.join(", ")));
+                }
+                disc(center, *radius)
+            }
+            Interaction::Inject { center, radius, amount } => disc(center, *radius) && amount.is_finite(),
+        };
+        if !finite {
+            return Err(format!("{:?} must be finite (radius non-negative)", self));
+        }
+        Ok(())
+    }
+}
+
+/// An interaction and the step it happened at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptEvent {
+    pub step: u32, // Completed steps when it was made; replay applies it before the next one
+    #[serde(flatten)]
+    pub action: Interaction,
+}
+
+/// Viewer interactions recorded with `--record` (or made by a scenario script)
+/// and replayed by `vireo-headless --replay`
+///
+/// Events are ordered by step, so a replay from step 0 of the same config
+/// and seed makes the same changes at the same points of the run. Written as
+/// YAML:
+///
+/// ```yaml
+/// seed: 1337 # world seed of the recorded run (the viewer's --seed)
+/// events:
+///   - { step: 120, action: wall, center: [32, 40], radius: 3, erase: false }
+///   - { step: 150, action: cull, shape: circle, center: [10, 10], radius: 5 }
+///   - { step: 200, action: tag, id: 1, center: [48, 20], radius: 8 }
+///   - { step: 300, action: param, param: chemotaxis.chi_R, value: 12.0 }
+///   - { step: 400, action: spawn, species: predators, center: [30, 30], radius: 4, count: 20 }
+///   - { step: 500, action: inject, center: [64, 64], radius: 10, amount: 0.5 }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InteractionScript {
+    #[serde(default)]
+    pub seed: Option<u64>, // Replaces world.seed on replay
+    #[serde(default)]
+    pub events: Vec<ScriptEvent>,
+}
+
+impl InteractionScript {
+    /// Parse a script and check its events (ordered steps, known finite parameters)
+    pub fn parse(yaml: &str) -> Result<Self, String> {
+        let script: Self = serde_yaml::from_str(yaml).map_err(|e| format!("cannot parse interaction script: {}", e))?;
+        for (i, e) in script.events.iter().enumerate() {
+            if i > 0 && e.step < script.events[i - 1].step {
+                return Err(format!("event {}: step {} comes before the previous event's step {}", i, e.step, script.events[i - 1].step));
+            }
+            e.action.check().map_err(|error| format!("event {}: {}", i, error))?;
+        }
+        Ok(script)
+    }
+
+    /// Read and parse a script file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| format!("cannot read interaction script {}: {}", path.display(), e))?;
+        Self::parse(&yaml)
+    }
+
+    /// Write the script as YAML
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let yaml = serde_yaml::to_string(self).map_err(|e| format!("cannot write interaction script: {}", e))?;
+        std::fs::write(path, yaml).map_err(|e| format!("cannot write interaction script {}: {}", path.display(), e))
+    }
+
+    /// Check the events against the world they will replay into
+    pub fn check_compatible(&self, config: &SimulationConfig) -> Result<(), String> {
+        if config.streaming.enabled {
+            return Err("scripts replay into a fixed world; streaming moves it under the events".to_string());
+        }
+        Ok(())
+    }
+
+    /// Append an event made at `step`
+    pub fn push(&mut self, step: u32, action: Interaction) {
+        self.events.push(ScriptEvent { step, action });
+    }
+
+    /// Interactions to apply once `step` steps have run
+    pub fn at(&self, step: u32) -> impl Iterator<Item = &Interaction> {
+        self.events.iter().filter(move |e| e.step == step).map(|e| &e.action)
+    }
+
+    /// Param and wall changes before `step`, which restoring a snapshot of that step leaves out
+    pub fn lost_on_restore(&self, step: u32) -> impl Iterator<Item = &Interaction> {
+        self.events.iter().filter(move |e| e.step < step && e.action.outlives_snapshots()).map(|e| &e.action)
+    }
+
+    /// Follow the world back to `step`: drop later events, except params and
+    /// walls, which a rewind does not undo and so hold from `step` on
+    pub fn rewind_to(&mut self, step: u32) {
+        self.events.retain(|e| e.step < step || e.action.outlives_snapshots());
+        for e in self.events.iter_mut().filter(|e| e.step > step) {
+            e.step = step;
+        }
+    }
+}
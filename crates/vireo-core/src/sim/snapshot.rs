@@ -0,0 +1,238 @@
+use std::io;
+use std::path::Path;
+use vireo_params::SimulationConfig;
+use crate::sim::{Agent, FieldData};
+
+/// File signature of a binary state snapshot
+const MAGIC: &[u8; 8] = b"VIREOSNP";
+/// Bumped whenever `FieldData`, `Agent` or the header change layout
+const VERSION: u32 = 4;
+/// Magic, then version, step, width, height, agent count and a reserved word
+const HEADER_BYTES: usize = 8 + 6 * 4;
+
+/// Grid that `StateSnapshot::state_hash` rounds every float value to
+pub const STATE_HASH_QUANTUM: f32 = 1.0 / 4096.0;
+
+/// Complete simulation state at one step: the front field and every agent
+///
+/// Written by the headless runner as `snapshot_NNNN.bin` (see `file_name`) and loaded by the
+/// viewer (`--load-snapshot`) to continue a batch run interactively. The file
+/// is a fixed little-endian header followed by the field as f32 `FieldData`
+/// cells (row-major) and the `Agent` records exactly as they sit in GPU memory.
+#[derive(Debug, Clone)]
+pub struct StateSnapshot {
+    pub step: u32,          // Completed steps when the state was taken
+    pub size: [u32; 2],
+    pub field: Vec<FieldData>,
+    pub agents: Vec<Agent>,
+}
+
+impl StateSnapshot {
+    /// Serialize to the on-disk format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let field: &[u8] = bytemuck::cast_slice(&self.field);
+        let agents: &[u8] = bytemuck::cast_slice(&self.agents);
+        let mut bytes = Vec::with_capacity(HEADER_BYTES + field.len() + agents.len());
+        bytes.extend_from_slice(MAGIC);
+        for word in [VERSION, self.step, self.size[0], self.size[1], self.agents.len() as u32, 0] {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes.extend_from_slice(field);
+        bytes.extend_from_slice(agents);
+        bytes
+    }
+
+    /// Parse the on-disk format, rejecting foreign, old or truncated files
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |reason: String| io::Error::new(io::ErrorKind::InvalidData, reason);
+        if bytes.len() < HEADER_BYTES || &bytes[..8] != MAGIC {
+            return Err(invalid("not a Vireo state snapshot".to_string()));
+        }
+        let word = |i: usize| u32::from_le_bytes(bytes[8 + 4 * i..12 + 4 * i].try_into().unwrap());
+        let (version, step, size, agent_count) = (word(0), word(1), [word(2), word(3)], word(4));
+        if version != VERSION {
+            return Err(invalid(format!("snapshot version {} is not supported (expected {})", version, VERSION)));
+        }
+
+        let field_bytes = size[0] as usize * size[1] as usize * std::mem::size_of::<FieldData>();
+        let agent_bytes = agent_count as usize * std::mem::size_of::<Agent>();
+        let body = &bytes[HEADER_BYTES..];
+        if body.len() != field_bytes + agent_bytes {
+            return Err(invalid(format!(
+                "snapshot body is {} bytes, a {}x{} world with {} agents needs {}",
+                body.len(), size[0], size[1], agent_count, field_bytes + agent_bytes,
+            )));
+        }
+
+        // Copy out rather than cast in place: the file buffer need not be aligned
+        Ok(Self {
+            step,
+            size,
+            field: read_records(&body[..field_bytes]),
+            agents: read_records(&body[field_bytes..]),
+        })
+    }
+
+    /// `snapshot_NNNN.bin` for the stored `step`, so a file's name and header agree
+    pub fn file_name(&self) -> String {
+        format!("snapshot_{:04}.bin", self.step)
+    }
+
+    /// Write the snapshot to `path`
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    /// Read and check a snapshot file
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Stable 64-bit hash of the quantized field and agents, for golden tests
+    ///
+    /// Floats are rounded to multiples of `STATE_HASH_QUANTUM` (so -0 and 0
+    /// agree and noise below the grid is ignored) and fed little-endian through
+    /// FNV-1a, so the value does not depend on the platform or Rust version.
+    /// Dead agent slots contribute only their kind; the step is not hashed.
+    /// Chemotaxis multipliers are hashed only once they have left 1 (`evolution`),
+    /// so runs without evolution keep their hashes. Parent multipliers only feed
+    /// `genetics.csv` and are not hashed.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = Fnv1a::default();
+        hash.write_u32(self.size[0]);
+        hash.write_u32(self.size[1]);
+        for cell in &self.field {
+            hash.write_quantized(&[cell.R, cell.W]);
+        }
+        for agent in &self.agents {
+            hash.write_u32(agent.kind);
+            hash.write_u32(agent.alive);
+            if agent.is_alive() {
+                hash.write_u32(agent.cohort);
+                hash.write_u32(agent.state);
+                hash.write_u32(agent.age);
+                hash.write_quantized(&[agent.pos[0], agent.pos[1], agent.vel[0], agent.vel[1], agent.energy]);
+                hash.write_quantized(&[agent.memory_pos[0], agent.memory_pos[1], agent.memory_value]);
+                if (agent.chi_gain, agent.kappa_gain) != (1.0, 1.0) {
+                    hash.write_quantized(&[agent.chi_gain, agent.kappa_gain]);
+                }
+            }
+        }
+        hash.0
+    }
+
+    /// Exact 64-bit FNV-1a of the on-disk bytes, to check a rebuilt state bit for bit
+    pub fn checksum(&self) -> u64 {
+        fnv1a(&self.to_bytes())
+    }
+
+    /// Check that this state can be loaded into a world built from `config`
+    ///
+    /// GPU buffers are sized from the config, so the world size and agent
+    /// count must match the run that wrote the snapshot.
+    pub fn check_compatible(&self, config: &SimulationConfig) -> Result<(), String> {
+        if self.size != config.world.size {
+            return Err(format!(
+                "snapshot is a {}x{} world but world.size is {}x{}",
+                self.size[0], self.size[1], config.world.size[0], config.world.size[1],
+            ));
+        }
+        let expected = config.agents.total_slots();
+        if self.agents.len() as u64 != expected {
+            return Err(format!(
+                "snapshot has {} agent slots but agents.herbivores = {} with agents.capacity {:?} gives {}",
+                self.agents.len(), config.agents.herbivores, config.agents.slots(), expected,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Difference `other − self`, for two snapshots of the same world
+    ///
+    /// Agents are compared slot by slot: a slot differs when only one side is
+    /// alive, or when position, velocity or energy move by more than `tolerance`.
+    pub fn diff(&self, other: &Self, tolerance: f32) -> Result<SnapshotDiff, String> {
+        if self.size != other.size || self.agents.len() != other.agents.len() {
+            return Err(format!(
+                "cannot compare a {}x{} world with {} agent slots to a {}x{} world with {}",
+                self.size[0], self.size[1], self.agents.len(),
+                other.size[0], other.size[1], other.agents.len(),
+            ));
+        }
+        let moved = |a: &Agent, b: &Agent| {
+            [a.pos[0] - b.pos[0], a.pos[1] - b.pos[1], a.vel[0] - b.vel[0], a.vel[1] - b.vel[1], a.energy - b.energy]
+                .iter()
+                .any(|d| d.abs() > tolerance)
+        };
+        Ok(SnapshotDiff {
+            size: self.size,
+            delta_R: self.field.iter().zip(&other.field).map(|(a, b)| b.R - a.R).collect(),
+            delta_W: self.field.iter().zip(&other.field).map(|(a, b)| b.W - a.W).collect(),
+            agents_differing: self.agents.iter().zip(&other.agents)
+                .filter(|(a, b)| a.is_alive() != b.is_alive() || (a.is_alive() && moved(a, b)))
+                .count(),
+        })
+    }
+}
+
+/// Cell-by-cell difference of two snapshots, from `StateSnapshot::diff`
+#[derive(Debug, Clone)]
+pub struct SnapshotDiff {
+    pub size: [u32; 2],
+    pub delta_R: Vec<f32>,      // b − a per cell, row-major
+    pub delta_W: Vec<f32>,
+    /// Agent slots whose state differs beyond the tolerance
+    pub agents_differing: usize,
+}
+
+impl SnapshotDiff {
+    /// Largest |ΔR| over the grid
+    pub fn max_abs_R(&self) -> f32 {
+        self.delta_R.iter().fold(0.0, |m, d| m.max(d.abs()))
+    }
+
+    /// Largest |ΔW| over the grid
+    pub fn max_abs_W(&self) -> f32 {
+        self.delta_W.iter().fold(0.0, |m, d| m.max(d.abs()))
+    }
+}
+
+/// 64-bit FNV-1a of `bytes` as they are, without quantizing
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = Fnv1a::default();
+    hash.write(bytes);
+    hash.0
+}
+
+/// 64-bit FNV-1a over little-endian words
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u32(&mut self, v: u32) {
+        self.write(&v.to_le_bytes());
+    }
+
+    /// Round each value to the `STATE_HASH_QUANTUM` grid (NaN as one sentinel)
+    fn write_quantized(&mut self, values: &[f32]) {
+        for &v in values {
+            let q = if v.is_nan() { i64::MIN } else { (v as f64 / STATE_HASH_QUANTUM as f64).round() as i64 };
+            self.write(&q.to_le_bytes());
+        }
+    }
+}
+
+pub(crate) fn read_records<T: bytemuck::Pod>(bytes: &[u8]) -> Vec<T> {
+    bytes.chunks_exact(std::mem::size_of::<T>()).map(bytemuck::pod_read_unaligned).collect()
+}
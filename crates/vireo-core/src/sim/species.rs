@@ -0,0 +1,88 @@
+use vireo_params::SPECIES;
+use crate::sim::{Agent, AgentLayout};
+
+/// Population summary of one species at a metrics sample
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpeciesStats {
+    pub kind: u32,
+    pub alive: u32,
+    pub mean_energy: f32, // Over alive agents (0 when extinct)
+    pub births: u32,      // Slots that became alive since the previous sample
+    pub deaths: u32,      // Slots that stopped being alive since the previous sample
+}
+
+impl SpeciesStats {
+    pub fn name(&self) -> &'static str {
+        SPECIES[self.kind as usize]
+    }
+
+    /// (metric, value) pairs in CSV column order
+    pub fn metrics(&self) -> [(&'static str, f32); 4] {
+        [
+            ("alive", self.alive as f32),
+            ("mean_energy", self.mean_energy),
+            ("births", self.births as f32),
+            ("deaths", self.deaths as f32),
+        ]
+    }
+}
+
+/// Per-species counts, energy and demographic events between samples
+///
+/// Births and deaths are read off slot transitions between two samples of
+/// the agent buffer: a slot that dies and is refilled by a birth in between
+/// counts as neither, so with `metrics_every` > 1 they are lower bounds.
+#[derive(Debug, Clone, Default)]
+pub struct SpeciesTracker {
+    previous: Vec<Option<u32>>, // Kind of the alive agent in each slot
+}
+
+impl SpeciesTracker {
+    /// Start tracking from `agents` as they are now (no events counted yet)
+    pub fn new(agents: &[Agent]) -> Self {
+        Self { previous: Self::occupancy(agents) }
+    }
+
+    /// Follow the agents into a reallocated buffer (`GrowthEvent`), so moved
+    /// slots are not counted as deaths and births
+    pub fn relayout(&mut self, from: &AgentLayout, to: &AgentLayout) {
+        self.previous = from.relayout(&self.previous, to, |_| None);
+    }
+
+    /// Stats of every species now, with births and deaths since the last sample
+    pub fn sample(&mut self, agents: &[Agent]) -> Vec<SpeciesStats> {
+        let mut stats: Vec<SpeciesStats> = (0..SPECIES.len() as u32)
+            .map(|kind| SpeciesStats { kind, ..Default::default() })
+            .collect();
+
+        let current = Self::occupancy(agents);
+        self.previous.resize(current.len(), None);
+        for (before, now) in self.previous.iter().zip(&current) {
+            if before == now {
+                continue;
+            }
+            if let Some(s) = before.and_then(|kind| stats.get_mut(kind as usize)) {
+                s.deaths += 1;
+            }
+            if let Some(s) = now.and_then(|kind| stats.get_mut(kind as usize)) {
+                s.births += 1;
+            }
+        }
+        for agent in agents.iter().filter(|a| a.is_alive()) {
+            if let Some(s) = stats.get_mut(agent.kind as usize) {
+                s.alive += 1;
+                s.mean_energy += agent.energy;
+            }
+        }
+        for s in stats.iter_mut().filter(|s| s.alive > 0) {
+            s.mean_energy /= s.alive as f32;
+        }
+
+        self.previous = current;
+        stats
+    }
+
+    fn occupancy(agents: &[Agent]) -> Vec<Option<u32>> {
+        agents.iter().map(|a| a.is_alive().then_some(a.kind)).collect()
+    }
+}
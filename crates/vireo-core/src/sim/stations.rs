@@ -0,0 +1,23 @@
+use vireo_params::StationConfig;
+use crate::sim::{Agent, CellSample};
+
+/// One reading of a sampling station (`stations`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StationSample {
+    pub resource: f32,       // R in the station's cell
+    pub waste: f32,          // W in the station's cell
+    pub occupancy: [u32; 3], // Alive plants, herbivores and predators the station covers
+}
+
+impl StationSample {
+    /// Reading of `station` from its gathered `cell` and the alive `agents` it covers
+    pub fn read(station: &StationConfig, cell: CellSample, agents: &[Agent]) -> Self {
+        let mut occupancy = [0; 3];
+        for a in agents.iter().filter(|a| a.is_alive() && station.covers(a.pos)) {
+            if let Some(count) = occupancy.get_mut(a.kind as usize) {
+                *count += 1;
+            }
+        }
+        Self { resource: cell[0], waste: cell[1], occupancy }
+    }
+}
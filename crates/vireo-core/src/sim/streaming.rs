@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use vireo_params::{StreamingConfig, WorldConfig};
+use crate::sim::{Agent, FieldData, FieldManager, CohortTracker, procedural_resource};
+
+/// Global chunk coordinate
+pub type ChunkCoord = [i32; 2];
+
+/// Streams field chunks in and out as the world window follows a focus point
+///
+/// The GPU field is a fixed-size window of `chunks` chunks whose lower-left
+/// chunk sits at global chunk `origin`. When the focus (e.g. the camera)
+/// leaves the window's central chunk, `shift` moves the window: chunks
+/// leaving it are parked in a cache, chunks entering it come from the cache
+/// or are generated procedurally, and cached chunks farther than
+/// `keep_radius` chunks from the window are evicted (and regenerated fresh
+/// if visited again).
+#[derive(Debug, Clone)]
+pub struct ChunkStreamer {
+    chunk: u32,
+    chunks: [u32; 2],
+    origin: ChunkCoord,
+    keep_radius: u32,
+    seed: u64,
+    cache: HashMap<ChunkCoord, Vec<FieldData>>,
+}
+
+impl ChunkStreamer {
+    /// Window centred on global chunk (0, 0); `world.size` must be a multiple of `config.chunk`
+    pub fn new(config: &StreamingConfig, world: &WorldConfig) -> Self {
+        let chunks = [world.size[0] / config.chunk, world.size[1] / config.chunk];
+        Self {
+            chunk: config.chunk,
+            chunks,
+            origin: [-(chunks[0] as i32 / 2), -(chunks[1] as i32 / 2)],
+            keep_radius: config.keep_radius,
+            seed: world.seed,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Global chunk coordinate of the window's lower-left chunk
+    pub fn origin(&self) -> ChunkCoord {
+        self.origin
+    }
+
+    /// Global cell coordinate of window cell (0, 0)
+    pub fn origin_cells(&self) -> [i64; 2] {
+        [self.origin[0] as i64 * self.chunk as i64, self.origin[1] as i64 * self.chunk as i64]
+    }
+
+    /// Chunks parked outside the window
+    pub fn cached_chunks(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Fill the whole window with freshly generated chunks (new world or reset)
+    pub fn fill(&mut self, field: &mut FieldManager) {
+        self.cache.clear();
+        for cy in 0..self.chunks[1] {
+            for cx in 0..self.chunks[0] {
+                let data = self.generate(self.global([cx, cy]));
+                self.write_chunk(field, [cx, cy], &data);
+            }
+        }
+    }
+
+    /// Chunk shift that re-centres the window on `focus` (window cells); zero while it is in the central chunk
+    pub fn shift_for(&self, focus: [f32; 2]) -> [i32; 2] {
+        let axis = |i: usize| {
+            let chunk = (focus[i] / self.chunk as f32).floor() as i32;
+            chunk - self.chunks[i] as i32 / 2
+        };
+        [axis(0), axis(1)]
+    }
+
+    /// Move the window by `delta` chunks, swapping chunk data through the cache
+    ///
+    /// Agents (with their remembered sites) and cohort release points are translated into the new window
+    /// coordinates; agents that end up outside the window are killed. Returns
+    /// the number of agents lost.
+    pub fn shift(
+        &mut self,
+        delta: [i32; 2],
+        field: &mut FieldManager,
+        agents: &mut [Agent],
+        cohorts: &mut CohortTracker,
+    ) -> u32 {
+        if delta == [0, 0] {
+            return 0;
+        }
+
+        // Park the current window, then move it
+        for cy in 0..self.chunks[1] {
+            for cx in 0..self.chunks[0] {
+                let data = self.read_chunk(field, [cx, cy]);
+                self.cache.insert(self.global([cx, cy]), data);
+            }
+        }
+        self.origin = [self.origin[0] + delta[0], self.origin[1] + delta[1]];
+
+        for cy in 0..self.chunks[1] {
+            for cx in 0..self.chunks[0] {
+                let coord = self.global([cx, cy]);
+                let data = self.cache.remove(&coord).unwrap_or_else(|| self.generate(coord));
+                self.write_chunk(field, [cx, cy], &data);
+            }
+        }
+        self.evict();
+
+        let offset = [
+            -(delta[0] * self.chunk as i32) as f32,
+            -(delta[1] * self.chunk as i32) as f32,
+        ];
+        let size = [(self.chunks[0] * self.chunk) as f32, (self.chunks[1] * self.chunk) as f32];
+        let mut lost = 0;
+        for agent in agents.iter_mut().filter(|a| a.is_alive()) {
+            agent.pos = [agent.pos[0] + offset[0], agent.pos[1] + offset[1]];
+            agent.memory_pos = [agent.memory_pos[0] + offset[0], agent.memory_pos[1] + offset[1]];
+            let inside = (0.0..size[0]).contains(&agent.pos[0]) && (0.0..size[1]).contains(&agent.pos[1]);
+            if !inside {
+                agent.kill();
+                lost += 1;
+            }
+        }
+        cohorts.translate(offset);
+        lost
+    }
+
+    /// Drop cached chunks more than `keep_radius` chunks outside the window
+    fn evict(&mut self) {
+        let lo = [self.origin[0] - self.keep_radius as i32, self.origin[1] - self.keep_radius as i32];
+        let hi = [
+            self.origin[0] + (self.chunks[0] + self.keep_radius) as i32,
+            self.origin[1] + (self.chunks[1] + self.keep_radius) as i32,
+        ];
+        self.cache.retain(|c, _| c[0] >= lo[0] && c[0] < hi[0] && c[1] >= lo[1] && c[1] < hi[1]);
+    }
+
+    fn global(&self, local: [u32; 2]) -> ChunkCoord {
+        [self.origin[0] + local[0] as i32, self.origin[1] + local[1] as i32]
+    }
+
+    fn generate(&self, coord: ChunkCoord) -> Vec<FieldData> {
+        let base = [coord[0] as i64 * self.chunk as i64, coord[1] as i64 * self.chunk as i64];
+        let mut data = Vec::with_capacity((self.chunk * self.chunk) as usize);
+        for y in 0..self.chunk as i64 {
+            for x in 0..self.chunk as i64 {
+                data.push(FieldData::new(procedural_resource(self.seed, base[0] + x, base[1] + y), 0.0));
+            }
+        }
+        data
+    }
+
+    fn read_chunk(&self, field: &FieldManager, local: [u32; 2]) -> Vec<FieldData> {
+        let mut data = Vec::with_capacity((self.chunk * self.chunk) as usize);
+        for y in 0..self.chunk {
+            for x in 0..self.chunk {
+                data.push(field.get(local[0] * self.chunk + x, local[1] * self.chunk + y));
+            }
+        }
+        data
+    }
+
+    fn write_chunk(&self, field: &mut FieldManager, local: [u32; 2], data: &[FieldData]) {
+        for (i, d) in data.iter().enumerate() {
+            let (x, y) = (i as u32 % self.chunk, i as u32 / self.chunk);
+            field.set(local[0] * self.chunk + x, local[1] * self.chunk + y, *d);
+        }
+    }
+}
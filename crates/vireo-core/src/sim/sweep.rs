@@ -0,0 +1,205 @@
+use std::fmt;
+use std::str::FromStr;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use vireo_params::{SimulationConfig, SCHEDULABLE_PARAMS};
+use crate::sim::SimRng;
+
+/// How a parameter sweep places its runs in the declared ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepStrategy {
+    /// Full factorial over `levels` evenly spaced values per parameter
+    Grid,
+    /// One run per stratum of every parameter, strata paired at random
+    LatinHypercube,
+    /// Low-discrepancy Sobol sequence (up to `SOBOL_MAX_DIMS` parameters)
+    Sobol,
+    /// Independent uniform draws
+    Random,
+}
+
+impl FromStr for SweepStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "grid" => Ok(Self::Grid),
+            "lhs" | "latin-hypercube" => Ok(Self::LatinHypercube),
+            "sobol" => Ok(Self::Sobol),
+            "random" => Ok(Self::Random),
+            _ => Err(format!("unknown sweep strategy `{}` (grid, lhs, sobol or random)", s)),
+        }
+    }
+}
+
+/// A swept parameter and its inclusive range, written `field.sigma_R=0.01:0.1`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamRange {
+    pub param: String, // Dotted path, one of `SCHEDULABLE_PARAMS`
+    pub lo: f32,
+    pub hi: f32,
+}
+
+impl FromStr for ParamRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (param, range) = s.split_once('=').ok_or_else(|| format!("expected `param=lo:hi`, got `{}`", s))?;
+        let (lo, hi) = range.split_once(':').ok_or_else(|| format!("expected a `lo:hi` range for {}, got `{}`", param, range))?;
+        let parse = |v: &str| v.trim().parse::<f32>().map_err(|e| format!("bad bound `{}` for {}: {}", v, param, e));
+        let (lo, hi) = (parse(lo)?, parse(hi)?);
+        if !SCHEDULABLE_PARAMS.contains(&param) {
+            return Err(format!("`{}` cannot be swept; use one of {}", param, SCHEDULABLE_PARAMS.join(", ")));
+        }
+        if !(lo.is_finite() && hi.is_finite() && lo <= hi) {
+            return Err(format!("range {}:{} for {} must be finite with lo <= hi", lo, hi, param));
+        }
+        Ok(Self { param: param.to_string(), lo, hi })
+    }
+}
+
+impl fmt::Display for ParamRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}:{}", self.param, self.lo, self.hi)
+    }
+}
+
+/// Parameters a Sobol design can cover (direction numbers below)
+pub const SOBOL_MAX_DIMS: usize = 10;
+
+/// Joe–Kuo direction numbers for dimensions 2..=10: (degree s, coefficients a, initial m)
+const SOBOL_DIRECTIONS: [(u32, u32, &[u32]); SOBOL_MAX_DIMS - 1] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+];
+
+/// Points of a sweep design in the declared ranges, one `Vec` (parallel to `ranges`) per run
+///
+/// `Grid` uses `levels` values per parameter and is truncated to `budget` if
+/// given; the sampling strategies draw exactly `budget` points and need one.
+/// Random draws come from `rng`, so a design is reproducible from its seed.
+pub fn sweep_design(
+    strategy: SweepStrategy,
+    ranges: &[ParamRange],
+    budget: Option<u32>,
+    levels: u32,
+    rng: &mut SimRng,
+) -> Result<Vec<Vec<f32>>, String> {
+    if ranges.is_empty() {
+        return Err("a sweep needs at least one parameter range".to_string());
+    }
+    let dims = ranges.len();
+    let unit = match (strategy, budget) {
+        (SweepStrategy::Grid, _) => {
+            if levels == 0 {
+                return Err("a grid needs at least one level per parameter".to_string());
+            }
+            let cap = budget.map_or(usize::MAX, |b| b as usize);
+            grid(dims, levels as usize, cap)
+        }
+        (_, None) => return Err(format!("{:?} sampling needs a run budget", strategy)),
+        (_, Some(n)) => {
+            let n = n as usize;
+            match strategy {
+                SweepStrategy::LatinHypercube => latin_hypercube(dims, n, rng),
+                SweepStrategy::Sobol => sobol(dims, n)?,
+                _ => (0..n).map(|_| (0..dims).map(|_| rng.gen::<f64>()).collect()).collect(),
+            }
+        }
+    };
+
+    Ok(unit
+        .into_iter()
+        .map(|point| point.iter().zip(ranges).map(|(&u, r)| r.lo + (r.hi - r.lo) * u as f32).collect())
+        .collect())
+}
+
+/// Set every swept parameter of `config` to its value in `point`
+pub fn apply_sweep_point(config: &mut SimulationConfig, ranges: &[ParamRange], point: &[f32]) {
+    for (range, &value) in ranges.iter().zip(point) {
+        if let Some(slot) = config.scalar_mut(&range.param) {
+            *slot = value;
+        }
+    }
+}
+
+/// First `cap` points of the `levels`^`dims` grid, last parameter varying fastest
+fn grid(dims: usize, levels: usize, cap: usize) -> Vec<Vec<f64>> {
+    let level = |i: usize| if levels == 1 { 0.5 } else { i as f64 / (levels - 1) as f64 };
+    let total = (levels as u128).saturating_pow(dims as u32).min(cap as u128) as usize;
+    (0..total)
+        .map(|mut n| {
+            let mut point = vec![0.0; dims];
+            for d in (0..dims).rev() {
+                point[d] = level(n % levels);
+                n /= levels;
+            }
+            point
+        })
+        .collect()
+}
+
+fn latin_hypercube(dims: usize, n: usize, rng: &mut SimRng) -> Vec<Vec<f64>> {
+    let mut points = vec![vec![0.0; dims]; n];
+    let mut strata: Vec<usize> = (0..n).collect();
+    for d in 0..dims {
+        strata.shuffle(rng);
+        for (point, &stratum) in points.iter_mut().zip(&strata) {
+            point[d] = (stratum as f64 + rng.gen::<f64>()) / n as f64;
+        }
+    }
+    points
+}
+
+/// First `n` points of the Sobol sequence (starting at the origin), Gray-code order
+fn sobol(dims: usize, n: usize) -> Result<Vec<Vec<f64>>, String> {
+    if dims > SOBOL_MAX_DIMS {
+        return Err(format!("Sobol designs support at most {} parameters, got {}", SOBOL_MAX_DIMS, dims));
+    }
+    let directions: Vec<[u32; 32]> = (0..dims).map(direction_numbers).collect();
+    let mut x = vec![0u32; dims];
+    let mut points = Vec::with_capacity(n);
+    for i in 0..n {
+        if i > 0 {
+            let c = (i - 1).trailing_ones() as usize; // Rightmost zero bit of i - 1
+            for (x, v) in x.iter_mut().zip(&directions) {
+                *x ^= v[c];
+            }
+        }
+        points.push(x.iter().map(|&x| x as f64 / 4_294_967_296.0).collect());
+    }
+    Ok(points)
+}
+
+fn direction_numbers(dim: usize) -> [u32; 32] {
+    let mut v = [0u32; 32];
+    if dim == 0 {
+        for (k, v) in v.iter_mut().enumerate() {
+            *v = 1 << (31 - k);
+        }
+        return v;
+    }
+    let (s, a, m) = SOBOL_DIRECTIONS[dim - 1];
+    let s = s as usize;
+    for k in 0..32 {
+        v[k] = if k < s {
+            m[k] << (31 - k)
+        } else {
+            let mut value = v[k - s] ^ (v[k - s] >> s);
+            for j in 1..s {
+                if (a >> (s - 1 - j)) & 1 == 1 {
+                    value ^= v[k - j];
+                }
+            }
+            value
+        };
+    }
+    v
+}
@@ -0,0 +1,62 @@
+use vireo_params::TerrainConfig;
+
+/// Elevation at every cell centre, the sum of the configured terrain features
+///
+/// This is the texture the agent and RD passes read. `sample`, `move_cost`
+/// and `face_conductance` mirror the shader helpers so CPU code and tests see
+/// the same landscape as the GPU.
+#[derive(Debug, Clone)]
+pub struct ElevationField {
+    size: [u32; 2],
+    heights: Vec<f32>,
+}
+
+impl ElevationField {
+    /// Evaluate `config.features` at each cell centre (all zero when disabled)
+    pub fn new(config: &TerrainConfig, size: [u32; 2]) -> Self {
+        let features = if config.enabled { config.features.as_slice() } else { &[] };
+        let heights = (0..size[1])
+            .flat_map(|y| (0..size[0]).map(move |x| [x as f32 + 0.5, y as f32 + 0.5]))
+            .map(|p| features.iter().map(|f| f.elevation(p)).sum())
+            .collect();
+        Self { size, heights }
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// Row-major elevations, one per cell
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Elevation at the centre of cell (x, y), clamped to the grid
+    pub fn at(&self, x: i32, y: i32) -> f32 {
+        let x = x.clamp(0, self.size[0] as i32 - 1) as usize;
+        let y = y.clamp(0, self.size[1] as i32 - 1) as usize;
+        self.heights[y * self.size[0] as usize + x]
+    }
+
+    /// Bilinear elevation at a world position (same as `elevation_at` in agent_step.wgsl)
+    pub fn sample(&self, p: [f32; 2]) -> f32 {
+        let t = [p[0] - 0.5, p[1] - 0.5];
+        let base = [t[0].floor(), t[1].floor()];
+        let f = [t[0] - base[0], t[1] - base[1]];
+        let (x, y) = (base[0] as i32, base[1] as i32);
+        let bottom = self.at(x, y) * (1.0 - f[0]) + self.at(x + 1, y) * f[0];
+        let top = self.at(x, y + 1) * (1.0 - f[0]) + self.at(x + 1, y + 1) * f[0];
+        bottom * (1.0 - f[1]) + top * f[1]
+    }
+
+    /// Energy spent moving from `from` to `to` (negative when the descent pays back)
+    pub fn move_cost(&self, from: [f32; 2], to: [f32; 2], climb_cost: f32, descent_gain: f32) -> f32 {
+        let dh = self.sample(to) - self.sample(from);
+        if dh > 0.0 { climb_cost * dh } else { descent_gain * dh }
+    }
+
+    /// Diffusion weight of the face between two neighbouring cells (same as rd_step.wgsl)
+    pub fn face_conductance(h_a: f32, h_b: f32, anisotropy: f32) -> f32 {
+        1.0 / (1.0 + anisotropy * (h_a - h_b).abs())
+    }
+}
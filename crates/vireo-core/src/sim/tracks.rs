@@ -0,0 +1,93 @@
+use rand::seq::index;
+use vireo_params::{TrackConfig, WorldConfig};
+use crate::sim::{Agent, AgentLayout, SimRng};
+
+/// Offset mixed into the world seed so track sampling draws from its own stream
+const TRACK_SEED_SALT: u64 = 0x7AC5_0000_5EED_0001;
+
+/// One recorded position of a tracked agent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fix {
+    pub step: u32,
+    pub pos: [f32; 2],
+    pub energy: f32,
+}
+
+/// Trajectory of one tracked agent
+#[derive(Debug, Clone)]
+pub struct Track {
+    pub id: u32,
+    pub kind: u32,
+    pub cohort: u32,
+    pub fixes: Vec<Fix>,
+    pub ended: bool, // Seen dead; a slot refilled by a birth is a different individual
+    pub slot: u32,   // Current slot (moves from `id` when the agent buffer grows)
+}
+
+/// Records positions of a sampled subset of agents every `every` steps
+///
+/// Ids are drawn once, without replacement, from the world seed so the same
+/// config always tracks the same agents. Fixes stop when an agent dies.
+#[derive(Debug, Clone)]
+pub struct TrackRecorder {
+    every: u32,
+    tracks: Vec<Track>,
+}
+
+impl TrackRecorder {
+    pub fn new(config: &TrackConfig, world: &WorldConfig, agents: &[Agent]) -> Self {
+        let mut rng = SimRng::new(world.rng, world.seed ^ TRACK_SEED_SALT);
+        let amount = (config.sample as usize).min(agents.len());
+        let mut ids: Vec<u32> = index::sample(&mut rng, agents.len(), amount)
+            .into_iter()
+            .map(|i| i as u32)
+            .collect();
+        ids.sort_unstable();
+
+        let tracks = ids
+            .into_iter()
+            .map(|id| {
+                let a = &agents[id as usize];
+                Track { id, kind: a.kind, cohort: a.cohort, fixes: Vec::new(), ended: false, slot: id }
+            })
+            .collect();
+
+        Self {
+            every: config.every.max(1),
+            tracks,
+        }
+    }
+
+    /// Whether `step` is a recording step
+    pub fn is_due(&self, step: u32) -> bool {
+        step.is_multiple_of(self.every)
+    }
+
+    /// Append a fix for every tracked agent still alive in `agents`
+    pub fn record(&mut self, step: u32, agents: &[Agent]) {
+        for track in &mut self.tracks {
+            let Some(a) = agents.get(track.slot as usize) else { continue };
+            if track.ended || !a.is_alive() {
+                track.ended = true;
+                continue;
+            }
+            track.cohort = a.cohort;
+            track.fixes.push(Fix { step, pos: a.pos, energy: a.energy });
+        }
+    }
+
+    /// Follow the tracked agents into a reallocated buffer (`GrowthEvent`);
+    /// track ids stay the slots the agents were sampled from
+    pub fn relayout(&mut self, from: &AgentLayout, to: &AgentLayout) {
+        for track in &mut self.tracks {
+            match from.remap(track.slot, to) {
+                Some(slot) => track.slot = slot,
+                None => track.ended = true,
+            }
+        }
+    }
+
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+}
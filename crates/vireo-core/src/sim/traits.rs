@@ -0,0 +1,183 @@
+use std::fs::File;
+use std::path::Path;
+use vireo_params::SPECIES;
+use crate::sim::Agent;
+
+/// Distribution of one species' heritable chemotaxis multipliers at a metrics sample
+///
+/// Means and population standard deviations over the alive agents (all 0
+/// when extinct). The effective `chi_R` of an agent is `chemotaxis.chi_R`
+/// times its `chi_gain`, and likewise for `kappa`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraitStats {
+    pub kind: u32,
+    pub mean_chi: f32,
+    pub sd_chi: f32,
+    pub mean_kappa: f32,
+    pub sd_kappa: f32,
+}
+
+impl TraitStats {
+    /// Species name (`SPECIES[kind]`), as in the `species` column of `genetics.csv`
+    pub fn name(&self) -> &'static str {
+        SPECIES[self.kind as usize]
+    }
+
+    /// Trait distribution of every species among the alive `agents`
+    pub fn of(agents: &[Agent]) -> Vec<TraitStats> {
+        // Count, then sums and sums of squares of the two multipliers, in f64
+        let mut sums = [[0.0f64; 5]; SPECIES.len()];
+        for agent in agents.iter().filter(|a| a.is_alive()) {
+            if let Some(s) = sums.get_mut(agent.kind as usize) {
+                let (chi, kappa) = (agent.chi_gain as f64, agent.kappa_gain as f64);
+                for (sum, value) in s.iter_mut().zip([1.0, chi, chi * chi, kappa, kappa * kappa]) {
+                    *sum += value;
+                }
+            }
+        }
+        sums.iter().enumerate().map(|(kind, &[n, chi, chi2, kappa, kappa2])| {
+            if n == 0.0 {
+                return TraitStats { kind: kind as u32, ..Default::default() };
+            }
+            let sd = |sum: f64, squares: f64| (squares / n - (sum / n).powi(2)).max(0.0).sqrt() as f32;
+            TraitStats {
+                kind: kind as u32,
+                mean_chi: (chi / n) as f32,
+                sd_chi: sd(chi, chi2),
+                mean_kappa: (kappa / n) as f32,
+                sd_kappa: sd(kappa, kappa2),
+            }
+        }).collect()
+    }
+
+    /// (metric, value) pairs in CSV column order
+    pub fn metrics(&self) -> [(&'static str, f32); 4] {
+        [
+            ("mean_chi_gain", self.mean_chi),
+            ("sd_chi_gain", self.sd_chi),
+            ("mean_kappa_gain", self.mean_kappa),
+            ("sd_kappa_gain", self.sd_kappa),
+        ]
+    }
+}
+
+/// Histogram bins per multiplier in `Genetics`
+pub const GAIN_BINS: usize = 8;
+
+/// Inheritance of one species' chemotaxis multipliers at a metrics sample
+///
+/// `heritability_*` is the slope of ln(offspring multiplier) on ln(parent
+/// multiplier) over the agents born in the last `window` steps, the
+/// generation since the previous sample: near 1 while offspring copy their
+/// parent up to mutation, NaN with fewer than two such births or parents that
+/// all share one value. `*_freq` are allele-frequency-like histograms of the
+/// multiplier over every alive agent of the species: bin k holds the fraction
+/// with log2(gain) in [k/4 - 1, k/4 - 3/4), the first and last bins open-ended,
+/// so bin 4 starts at a gain of 1.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Genetics {
+    pub kind: u32,
+    pub offspring: u32, // Parent-offspring pairs behind the regressions
+    pub heritability_chi: f32,
+    pub heritability_kappa: f32,
+    pub chi_freq: [f32; GAIN_BINS],
+    pub kappa_freq: [f32; GAIN_BINS],
+}
+
+impl Genetics {
+    /// Species name (`SPECIES[kind]`)
+    pub fn name(&self) -> &'static str {
+        SPECIES[self.kind as usize]
+    }
+
+    /// Histogram bin of a multiplier (quarter steps of log2 from 0.5 to 2)
+    pub fn bin(gain: f32) -> usize {
+        ((gain.log2() + 1.0) * 4.0).floor().clamp(0.0, (GAIN_BINS - 1) as f32) as usize
+    }
+
+    /// Heritability and histograms of every species among the alive `agents`,
+    /// regressing the ones younger than `window` steps on their parents
+    pub fn of(agents: &[Agent], window: u32) -> Vec<Genetics> {
+        let mut genetics: Vec<Genetics> = (0..SPECIES.len()).map(|kind| Genetics { kind: kind as u32, ..Default::default() }).collect();
+        // (ln parent, ln offspring) of chi_gain, then of kappa_gain, per species
+        let mut pairs: Vec<[Vec<(f64, f64)>; 2]> = vec![Default::default(); SPECIES.len()];
+        let mut alive = [0u32; SPECIES.len()];
+        for agent in agents.iter().filter(|a| a.is_alive()) {
+            let kind = agent.kind as usize;
+            let Some(g) = genetics.get_mut(kind) else { continue };
+            alive[kind] += 1;
+            g.chi_freq[Self::bin(agent.chi_gain)] += 1.0;
+            g.kappa_freq[Self::bin(agent.kappa_gain)] += 1.0;
+            if let Some((chi, kappa)) = agent.parent_traits().filter(|_| agent.age < window) {
+                let ln = |v: f32| (v as f64).ln();
+                pairs[kind][0].push((ln(chi), ln(agent.chi_gain)));
+                pairs[kind][1].push((ln(kappa), ln(agent.kappa_gain)));
+            }
+        }
+        for (g, ([chi, kappa], &n)) in genetics.iter_mut().zip(pairs.iter().zip(&alive)) {
+            for freq in g.chi_freq.iter_mut().chain(g.kappa_freq.iter_mut()) {
+                *freq /= n.max(1) as f32;
+            }
+            g.offspring = chi.len() as u32;
+            g.heritability_chi = slope(chi);
+            g.heritability_kappa = slope(kappa);
+        }
+        genetics
+    }
+
+    /// (metric, value) pairs in `genetics.csv` order, e.g. `chi_gain_freq_4`
+    pub fn metrics(&self) -> Vec<(String, f32)> {
+        let mut metrics = vec![
+            ("offspring".to_string(), self.offspring as f32),
+            ("heritability_chi_gain".to_string(), self.heritability_chi),
+            ("heritability_kappa_gain".to_string(), self.heritability_kappa),
+        ];
+        metrics.extend(self.chi_freq.iter().enumerate().map(|(k, &f)| (format!("chi_gain_freq_{}", k), f)));
+        metrics.extend(self.kappa_freq.iter().enumerate().map(|(k, &f)| (format!("kappa_gain_freq_{}", k), f)));
+        metrics
+    }
+}
+
+/// Least-squares slope of y on x, NaN when x takes fewer than two values
+fn slope(points: &[(f64, f64)]) -> f32 {
+    let n = points.len() as f64;
+    let mx = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let my = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let cov: f64 = points.iter().map(|(x, y)| (x - mx) * (y - my)).sum();
+    let var: f64 = points.iter().map(|(x, _)| (x - mx).powi(2)).sum();
+    if points.len() < 2 || var <= 1e-12 {
+        return f32::NAN;
+    }
+    (cov / var) as f32
+}
+
+/// Trait distributions and inheritance in long format, one row per species and metric (`genetics.csv`)
+///
+/// Written with every metrics row while `evolution.enabled`. Each sample
+/// writes, species by species, every `TraitStats::metrics` pair
+/// (the `*_gain_*` columns of `metrics.csv`) and then every `Genetics::metrics`
+/// pair as (step, species, metric, value) rows like `species.csv`.
+pub struct GeneticsWriter {
+    csv_writer: csv::Writer<File>,
+}
+
+impl GeneticsWriter {
+    pub fn new(output_dir: &Path) -> csv::Result<Self> {
+        let mut csv_writer = csv::Writer::from_path(output_dir.join("genetics.csv"))?;
+        csv_writer.write_record(["step", "species", "metric", "value"])?;
+        Ok(Self { csv_writer })
+    }
+
+    /// Write every metric of every species for `step`
+    pub fn write_step(&mut self, step: u32, traits: &[TraitStats], genetics: &[Genetics]) -> csv::Result<()> {
+        let step = step.to_string();
+        for (t, g) in traits.iter().zip(genetics) {
+            let metrics = t.metrics().map(|(metric, value)| (metric.to_string(), value)).into_iter().chain(g.metrics());
+            for (metric, value) in metrics {
+                self.csv_writer.write_record([step.as_str(), t.name(), metric.as_str(), value.to_string().as_str()])?;
+            }
+        }
+        self.csv_writer.flush()?;
+        Ok(())
+    }
+}
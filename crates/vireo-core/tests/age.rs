@@ -0,0 +1,49 @@
+mod common;
+
+use common::gpu_or_skip;
+use glam::Vec2;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{AgeStructure, Agent};
+use vireo_core::{SimulationConfig, SpeciesDemography};
+
+fn agent(kind: u32, age: u32) -> Agent {
+    let mut a = Agent::new(Vec2::ZERO, 1.0, kind);
+    a.age = age;
+    a
+}
+
+#[test]
+fn classes_bin_ages_and_the_last_is_open_ended() {
+    let mut agents = vec![agent(1, 0), agent(1, 9), agent(1, 10), agent(2, 25), agent(0, 5000), agent(1, 3)];
+    agents[5].kill();
+
+    let ages = AgeStructure::of(&agents, 10, 3);
+    assert_eq!(ages.counts, vec![[0, 2, 0], [0, 1, 0], [1, 0, 1]]);
+    assert_eq!((ages.total(1), ages.peak()), (3, 2));
+    assert_eq!(ages.class_range(1), (10, Some(20)));
+    assert_eq!(ages.class_range(2), (20, None));
+}
+
+#[test]
+fn ages_count_steps_and_newborns_start_at_zero() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 200;
+    config.agents.capacity.herbivores = 2 * config.agents.spawn_counts()[1];
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 0.0, birth: 1.0 };
+    let mut sim = Simulation::new(&gpu, config);
+    assert!(sim.agent_manager.agents.iter().all(|a| a.age == 0));
+
+    for _ in 0..3 {
+        sim.step(&gpu);
+    }
+    sim.sync_agents(&gpu);
+
+    let alive: Vec<_> = sim.agent_manager.agents.iter().filter(|a| a.is_alive()).collect();
+    assert!(alive.iter().filter(|a| a.kind != 1).all(|a| a.age == 3), "no births outside herbivores");
+    assert!(alive.iter().all(|a| a.age <= 3));
+    let young = alive.iter().filter(|a| a.kind == 1 && a.age < 3).count();
+    assert!(young > 0, "offspring are younger than the founders");
+}
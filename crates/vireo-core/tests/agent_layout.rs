@@ -0,0 +1,67 @@
+//! The agent buffer is uploaded as `[Agent]` from Rust and read as
+//! `array<Agent>` in WGSL; these tests pin the two layouts together.
+
+use vireo_core::{shaders, Agent};
+
+/// (size, alignment) of a WGSL host-shareable type
+fn wgsl_size_align(ty: &str) -> (usize, usize) {
+    match ty {
+        "f32" | "u32" | "i32" => (4, 4),
+        "vec2<f32>" | "vec2<u32>" => (8, 8),
+        "vec4<f32>" | "vec4<u32>" => (16, 16),
+        other => panic!("no layout rule for {}", other),
+    }
+}
+
+fn round_up(value: usize, align: usize) -> usize {
+    value.div_ceil(align) * align
+}
+
+#[test]
+fn rust_offsets_follow_the_wgsl_layout_rules() {
+    let mut offset = 0;
+    let mut struct_align = 0;
+    for (name, ty, rust_offset) in Agent::WGSL_FIELDS {
+        let (size, align) = wgsl_size_align(ty);
+        offset = round_up(offset, align);
+        assert_eq!(rust_offset, offset, "field `{}`", name);
+        offset += size;
+        struct_align = struct_align.max(align);
+    }
+    // The array stride WGSL uses for array<Agent>
+    assert_eq!(round_up(offset, struct_align), std::mem::size_of::<Agent>());
+    assert_eq!(std::mem::size_of::<Agent>(), 64);
+}
+
+#[test]
+fn the_table_covers_every_field_once() {
+    // Fields in declaration order tile the struct with no gap the table misses
+    let mut covered = 0;
+    for (name, ty, offset) in Agent::WGSL_FIELDS {
+        assert_eq!(offset, covered, "field `{}` does not follow the previous one", name);
+        covered += wgsl_size_align(ty).0;
+    }
+    assert_eq!(covered, std::mem::size_of::<Agent>());
+
+    let agent = Agent::free(2);
+    let bytes = bytemuck::bytes_of(&agent);
+    let (_, _, kind) = Agent::WGSL_FIELDS.iter().find(|(name, _, _)| *name == "kind").copied().unwrap();
+    assert_eq!(bytemuck::pod_read_unaligned::<u32>(&bytes[kind..kind + 4]), 2);
+}
+
+#[test]
+fn agent_shaders_declare_only_the_generated_struct() {
+    let prelude = shaders::agent_prelude();
+    for field in Agent::WGSL_FIELDS {
+        assert!(prelude.contains(&format!("{}: {},", field.0, field.1)), "{} missing from the prelude", field.0);
+    }
+    for (name, source) in [
+        ("agent_step", shaders::agent_step()),
+        ("cull_region", shaders::cull_region()),
+        ("demography", shaders::demography()),
+        ("movement_metrics", shaders::movement_metrics()),
+    ] {
+        assert_eq!(source.matches("struct Agent {").count(), 1, "{} declares its own Agent", name);
+        assert!(source.contains(&prelude), "{} does not use the prelude", name);
+    }
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::FieldManager;
+use vireo_core::SimulationConfig;
+
+const RIVER: &str = include_str!("../../../lab/configs/river.yaml");
+
+/// Overrides for a `size` world with `herbivores`
+fn sized(size: [u32; 2], herbivores: u32) -> impl FnOnce(&mut SimulationConfig) {
+    move |config| {
+        config.world.size = size;
+        config.agents.herbivores = herbivores;
+    }
+}
+
+/// Largest resource in each short-side square along the long axis
+fn block_maxima(field: &FieldManager) -> Vec<f32> {
+    let [w, h] = field.size;
+    let side = w.min(h);
+    (0..w.max(h) / side)
+        .map(|block| {
+            let mut max = 0.0f32;
+            for i in block * side..(block + 1) * side {
+                for j in 0..side {
+                    let r = if w >= h { field.get_resource(i, j) } else { field.get_resource(j, i) };
+                    max = max.max(r);
+                }
+            }
+            max
+        })
+        .collect()
+}
+
+#[test]
+fn river_preset_seeds_patches_along_its_whole_length() {
+    let config = vireo_core::parse_config(RIVER).unwrap();
+    assert_eq!(config.world.size, [1024, 128]);
+
+    for size in [[1024, 128], [128, 1024]] {
+        let mut field = FieldManager::new(size);
+        field.seed_resources(config.world.seed);
+        let maxima = block_maxima(&field);
+        assert_eq!(maxima.len(), 8);
+        assert!(maxima.iter().all(|&m| m > 0.3), "{:?}: {:?}", size, maxima);
+    }
+}
+
+#[test]
+fn agents_land_in_the_cell_they_stand_on_in_either_orientation() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    for size in [[1024, 128], [128, 1024]] {
+        let mut sim = Simulation::new(&gpu, small_config(sized(size, 10)));
+        let slot = sim.agent_manager.layout.range(1).next().unwrap() as usize;
+        for a in sim.agent_manager.agents.iter_mut() {
+            a.kill();
+        }
+        let pos = [size[0] as f32 - 20.5, size[1] as f32 - 30.5];
+        sim.agent_manager.agents[slot] = vireo_core::Agent::new(glam::Vec2::from(pos), 1.0, 1);
+        sim.upload_agents(&gpu);
+        sim.step(&gpu);
+        sim.sync_agents(&gpu);
+
+        let occupancy = sim.read_occupancy(&gpu);
+        assert_eq!(occupancy.len(), (size[0] * size[1]) as usize);
+        let filled: Vec<usize> = (0..occupancy.len()).filter(|&i| occupancy[i] != 0).collect();
+        let [x, y] = sim.agent_manager.agents[slot].pos.map(|v| v.floor() as usize);
+        assert_eq!(filled, vec![y * size[0] as usize + x], "{:?}", size);
+    }
+}
+
+#[test]
+fn large_rectangular_worlds_clear_their_grids_in_range() {
+    // 2048 × 704 cells: a flat dispatch over the velocity grid (3 words a
+    // cell) would need more than 65535 workgroups
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, small_config(sized([2048, 704], 50)));
+    sim.step(&gpu);
+    let stats = sim.movement_stats(&gpu);
+    assert!(stats.mean_abs_divergence.is_finite() && stats.mean_alignment.is_finite(), "{:?}", stats);
+    assert_eq!(sim.read_occupancy(&gpu).len(), 2048 * 704);
+}
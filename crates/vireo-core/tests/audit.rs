@@ -0,0 +1,58 @@
+use glam::Vec2;
+use vireo_core::sim::{Agent, ChannelAudit, FieldManager, InitAudit, ObstacleField, AUDIT_QUANTILES};
+use vireo_core::{FieldChannel, ObstacleConfig, ObstacleShape};
+
+#[test]
+fn channel_quantiles_interpolate_and_skip_non_finite_cells() {
+    let values: Vec<f32> = (0..=100).map(|i| i as f32).chain([f32::NAN, f32::INFINITY]).collect();
+    let audit = ChannelAudit::of(FieldChannel::Resource, &values);
+    assert_eq!(audit.non_finite, 2);
+    assert_eq!(audit.mean, 50.0);
+    assert!((audit.zero_fraction - 1.0 / 101.0).abs() < 1e-6);
+    for (q, v) in AUDIT_QUANTILES.iter().zip(audit.quantiles) {
+        assert!((v - q * 100.0).abs() < 1e-4, "q{} = {}", q, v);
+    }
+    assert_eq!(ChannelAudit::of(FieldChannel::Waste, &[0.5, 1.5]).quantiles[3], 1.0);
+}
+
+#[test]
+fn placement_histograms_flag_agents_on_bare_ground_and_in_walls() {
+    // Resource only in the left half; a wall in the top-right corner
+    let mut field = FieldManager::new([64, 64]);
+    for y in 0..64 {
+        for x in 0..32 {
+            field.set_resource(x, y, 1.0);
+        }
+    }
+    let obstacles = ObstacleField::new(&ObstacleConfig {
+        enabled: true,
+        shapes: vec![ObstacleShape::Rect { min: [48.0, 48.0], max: [64.0, 64.0] }],
+        ..ObstacleConfig::default()
+    }, [64, 64]);
+
+    let mut agents: Vec<Agent> = (0..4).map(|i| Agent::new(Vec2::new(8.0 + i as f32, 8.0), 1.0, 0)).collect();
+    agents.push(Agent::new(Vec2::new(40.0, 10.0), 2.0, 1));
+    agents.push(Agent::new(Vec2::new(56.0, 56.0), 0.0, 1));
+    agents.push(Agent::new(Vec2::new(70.0, 10.0), 3.0, 1));
+    let audit = InitAudit::of(&field, &agents, &obstacles, 4);
+
+    assert_eq!(audit.bins, [4, 4]);
+    let plants = &audit.species[0];
+    assert_eq!((plants.count, plants.occupied_bins, plants.histogram[0]), (4, 1, 4));
+    assert_eq!((plants.mean_resource, plants.on_bare), (1.0, 0));
+    let herbivores = &audit.species[1];
+    assert_eq!((herbivores.count, herbivores.occupied_bins, herbivores.histogram[15]), (3, 3, 1));
+    assert_eq!((herbivores.on_bare, herbivores.in_obstacles, herbivores.outside, herbivores.no_energy), (3, 1, 1, 1));
+    assert_eq!((herbivores.mean_energy, herbivores.min_energy, herbivores.max_energy), (5.0 / 3.0, 0.0, 3.0));
+    assert_eq!(audit.species[2].count, 0);
+
+    let r = &audit.channels[0];
+    assert_eq!((r.mean, r.zero_fraction, r.quantiles[6]), (0.5, 0.5, 1.0));
+    let warnings = audit.warnings();
+    assert_eq!(warnings.len(), 4, "{:?}", warnings);
+    assert!(warnings.iter().all(|w| w.contains("herbivores")), "{:?}", warnings);
+
+    // An empty field and no agents
+    let bare = InitAudit::of(&FieldManager::new([64, 64]), &[], &obstacles, 4);
+    assert_eq!(bare.warnings(), ["resource is zero everywhere", "no alive agents"]);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::FieldData;
+use vireo_core::{FieldSampling, SimulationConfig};
+
+const SIZE: u32 = 64;
+
+/// Frozen Gaussian resource bump with agents starting at rest on a lattice around it
+fn frozen_world(gpu: &GpuDevice, sampling: FieldSampling) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [SIZE, SIZE];
+    config.world.dt = 0.5;
+    config.agents.herbivores = 16;
+    config.field.D_R = 0.0;
+    config.field.D_W = 0.0;
+    config.field.sigma_R = 0.0;
+    config.field.alpha_H = 0.0;
+    config.field.beta_H = 0.0;
+    config.field.lambda_R = 0.0;
+    config.field.lambda_W = 0.0;
+    config.chemotaxis.eps0 = 0.0;
+    config.chemotaxis.chi_R = 2.0;
+    config.chemotaxis.sampling = sampling;
+
+    let mut sim = Simulation::new(gpu, config);
+    for (i, agent) in sim.agent_manager.agents.iter_mut().enumerate() {
+        agent.pos = [10.3 + (i % 8) as f32 * 5.7, 10.3 + (i / 8) as f32 * 7.1];
+        agent.vel = [0.0, 0.0];
+    }
+    sim.upload_agents(gpu);
+
+    let c = SIZE as f32 / 2.0;
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (dx, dy) = (x as f32 + 0.5 - c, y as f32 + 0.5 - c);
+            let r = 0.1 + 0.9 * (-(dx * dx + dy * dy) / (2.0 * 12.0 * 12.0)).exp();
+            sim.field_manager.set_cell((y * SIZE + x) as usize, FieldData::new(r, 0.0));
+        }
+    }
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim
+}
+
+/// Largest second difference of velocity per agent, averaged over agents
+fn peak_jerk(gpu: &GpuDevice, sampling: FieldSampling) -> f32 {
+    let mut sim = frozen_world(gpu, sampling);
+    let mut history: Vec<Vec<[f32; 2]>> = Vec::new();
+    for _ in 0..60 {
+        sim.step(gpu);
+        sim.sync_agents(gpu);
+        history.push(sim.agent_manager.agents.iter().map(|a| a.vel).collect());
+    }
+
+    let agents = history[0].len();
+    let mut peak = vec![0.0f32; agents];
+    for w in history.windows(3) {
+        for a in 0..agents {
+            let jx = w[2][a][0] - 2.0 * w[1][a][0] + w[0][a][0];
+            let jy = w[2][a][1] - 2.0 * w[1][a][1] + w[0][a][1];
+            peak[a] = peak[a].max((jx * jx + jy * jy).sqrt());
+        }
+    }
+    assert!(sim.agent_manager.agents.iter().all(|a| a.is_alive()));
+    peak.iter().sum::<f32>() / agents as f32
+}
+
+#[test]
+fn bicubic_gradients_reduce_velocity_jitter() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let nearest = peak_jerk(&gpu, FieldSampling::Nearest);
+    let bicubic = peak_jerk(&gpu, FieldSampling::Bicubic);
+    assert!(
+        bicubic < 0.5 * nearest,
+        "peak jerk: nearest {}, bicubic {}",
+        nearest,
+        bicubic
+    );
+}
+
+#[test]
+fn bicubic_mode_climbs_the_resource_gradient() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = frozen_world(&gpu, FieldSampling::Bicubic);
+    sim.sync_field(&gpu);
+    let resource_at = |sim: &Simulation, p: [f32; 2]| {
+        let (x, y) = (p[0].floor() as u32, p[1].floor() as u32);
+        sim.field_manager.get_resource(x.min(SIZE - 1), y.min(SIZE - 1))
+    };
+    let before: f32 = sim.agent_manager.agents.iter().map(|a| resource_at(&sim, a.pos)).sum();
+    for _ in 0..200 {
+        sim.step(&gpu);
+    }
+    sim.sync_agents(&gpu);
+    let after: f32 = sim.agent_manager.agents.iter().map(|a| resource_at(&sim, a.pos)).sum();
+    assert!(after > before, "mean R under agents fell from {} to {}", before, after);
+}
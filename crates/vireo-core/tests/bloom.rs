@@ -0,0 +1,41 @@
+use vireo_core::sim::BloomScheduler;
+use vireo_core::{BloomConfig, WorldConfig, SimulationConfig, MAX_BLOOMS};
+
+fn world() -> WorldConfig {
+    SimulationConfig::default().world
+}
+
+#[test]
+fn disabled_blooms_never_spawn() {
+    let config = BloomConfig { enabled: false, rate: 1.0, ..Default::default() };
+    let mut scheduler = BloomScheduler::new(config, &world());
+    for step in 0..500 {
+        assert!(!scheduler.tick(step));
+    }
+    assert_eq!(scheduler.to_params().count, 0);
+}
+
+#[test]
+fn blooms_expire_after_duration_and_respect_cap() {
+    let config = BloomConfig { enabled: true, rate: 1.0, duration: 20, ..Default::default() };
+    let mut scheduler = BloomScheduler::new(config, &world());
+    for step in 0..100 {
+        scheduler.tick(step);
+        assert!(scheduler.active().len() <= MAX_BLOOMS);
+        for b in scheduler.active() {
+            assert!(b.start_step <= step && step < b.end_step);
+            assert_eq!(b.end_step - b.start_step, 20);
+        }
+    }
+}
+
+#[test]
+fn bloom_schedule_is_deterministic() {
+    let config = BloomConfig { enabled: true, rate: 0.3, ..Default::default() };
+    let mut a = BloomScheduler::new(config.clone(), &world());
+    let mut b = BloomScheduler::new(config, &world());
+    for step in 0..300 {
+        assert_eq!(a.tick(step), b.tick(step));
+        assert_eq!(a.active(), b.active());
+    }
+}
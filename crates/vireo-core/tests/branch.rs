@@ -0,0 +1,89 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::BranchPlan;
+use vireo_core::{ParamSchedule, ScheduleShape};
+
+const PLAN: &str = "
+checkpoint: results/snapshot_0200.bin
+variants:
+  - name: baseline
+  - name: fast-regrowth
+    params: { field.sigma_R: 0.08, chemotaxis.chi_R: 12.0 }
+  - name: weak.chemotaxis
+    params: { chemotaxis.chi_R: 2.0 }
+";
+
+#[test]
+fn plans_parse_and_reject_bad_variants() {
+    let plan = BranchPlan::parse(PLAN).unwrap();
+    assert_eq!(plan.checkpoint.to_str(), Some("results/snapshot_0200.bin"));
+    assert_eq!(plan.variants.len(), 3);
+    assert!(plan.variants[0].params.is_empty());
+    assert_eq!(plan.params(), vec!["chemotaxis.chi_R", "field.sigma_R"]);
+
+    let error = |yaml: &str| BranchPlan::parse(yaml).unwrap_err();
+    assert!(error("checkpoint: a.bin\nvariants: []\n").contains("no variants"));
+    assert!(error("checkpoint: a.bin\nvariants:\n  - name: a\n  - name: a\n").contains("used twice"));
+    assert!(error("checkpoint: a.bin\nvariants:\n  - name: ../up\n").contains("must be letters"));
+    assert!(error("checkpoint: a.bin\nvariants:\n  - name: a\n    params: { world.dt: 1.0 }\n").contains("cannot be set"));
+    assert!(error("checkpoint: a.bin\nvariants:\n  - name: a\n    parms: {}\n").contains("cannot parse"));
+}
+
+#[test]
+fn branches_end_after_the_checkpoint() {
+    let mut plan = BranchPlan::parse(PLAN).unwrap();
+    assert_eq!(plan.end_step(200, 2000), Ok(2000));
+    assert!(plan.end_step(2000, 2000).unwrap_err().contains("set `steps`"));
+    plan.steps = Some(50);
+    assert_eq!(plan.end_step(2000, 2000), Ok(2050));
+    plan.steps = Some(0);
+    assert!(plan.end_step(200, 2000).is_err());
+}
+
+#[test]
+fn variants_override_params_and_their_schedules() {
+    let mut base = small_config(|_| {});
+    base.schedules.push(ParamSchedule {
+        param: "chemotaxis.chi_R".to_string(), schedule: ScheduleShape::Step, from: 8.0, to: 1.0, start: 10, end: 10,
+    });
+    base.schedules.push(ParamSchedule {
+        param: "chemotaxis.gamma".to_string(), schedule: ScheduleShape::Step, from: 0.1, to: 0.2, start: 10, end: 10,
+    });
+    let plan = BranchPlan::parse(PLAN).unwrap();
+
+    let baseline = plan.variants[0].apply(&base).unwrap();
+    assert_eq!(baseline.schedules, base.schedules);
+
+    let fast = plan.variants[1].apply(&base).unwrap();
+    assert_eq!((fast.field.sigma_R, fast.chemotaxis.chi_R), (0.08, 12.0));
+    assert_eq!(fast.schedules.iter().map(|s| s.param.as_str()).collect::<Vec<_>>(), vec!["chemotaxis.gamma"]);
+
+    let invalid = BranchPlan::parse("checkpoint: a.bin\nvariants:\n  - name: a\n    params: { field.D_R: -1.0 }\n").unwrap();
+    assert!(invalid.variants[0].apply(&base).unwrap_err().contains("variant `a`"));
+}
+
+#[test]
+fn variants_share_the_checkpoint_and_differ_only_by_their_params() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut original = Simulation::new(&gpu, small_config(|_| {}));
+    for _ in 0..20 {
+        original.step(&gpu);
+    }
+    let checkpoint = original.snapshot(&gpu);
+
+    let plan = BranchPlan::parse(PLAN).unwrap();
+    let run = |variant: usize| {
+        let mut sim = Simulation::new(&gpu, plan.variants[variant].apply(&small_config(|_| {})).unwrap());
+        sim.restore(&gpu, &checkpoint).unwrap();
+        for _ in 0..20 {
+            sim.step(&gpu);
+        }
+        sim.state_hash(&gpu)
+    };
+
+    let baseline = run(0);
+    assert_eq!(baseline, run(0));
+    assert_ne!(baseline, run(2));
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::small_config;
+use vireo_core::{check_budget, fit_to_budget, Downscale, MemoryEstimate, SimulationConfig};
+
+/// Overrides for a `size` world with `herbivores`
+fn sized(size: [u32; 2], herbivores: u32) -> impl FnOnce(&mut SimulationConfig) {
+    move |config| {
+        config.world.size = size;
+        config.agents.herbivores = herbivores;
+    }
+}
+
+#[test]
+fn estimate_counts_every_large_allocation() {
+    let estimate = MemoryEstimate::for_config(&small_config(sized([128, 128], 2000)));
+    let cells = 128 * 128;
+    assert_eq!(estimate.field_textures, 2 * cells * 8);
+    assert_eq!(estimate.field_readback, cells * 8);
+    assert_eq!(estimate.cell_buffers, cells * 28);
+    assert_eq!(estimate.agents, 6000 * 64);
+    assert_eq!(estimate.largest_storage, 6000 * 64); // Agents now outgrow the velocity grid (cells * 12)
+    assert_eq!(estimate.total(), cells * 52 + 6000 * 64);
+    assert!(estimate.to_string().starts_with("1.2 MiB"), "{}", estimate);
+}
+
+#[test]
+fn oversized_worlds_fail_with_the_limit_they_hit() {
+    let downlevel = wgpu::Limits::downlevel_defaults();
+    let error = check_budget(&small_config(sized([4096, 1024], 2000)), &downlevel).unwrap_err();
+    assert_eq!(error.resource, "field texture width");
+    assert_eq!((error.required, error.limit), (4096, 2048));
+    assert!(error.to_string().contains("gpu.auto_downscale"), "{}", error);
+
+    let mut config = small_config(sized([2048, 2048], 2000));
+    config.gpu.budget_mb = 64;
+    let error = check_budget(&config, &wgpu::Limits::default()).unwrap_err();
+    assert_eq!(error.resource, "total GPU memory (gpu.budget_mb)");
+    assert!(error.to_string().contains("208.4 MiB but the limit is 64.0 MiB"), "{}", error);
+
+    config.gpu.budget_mb = 0;
+    assert!(check_budget(&config, &wgpu::Limits::default()).is_ok());
+}
+
+#[test]
+fn auto_downscale_halves_until_the_world_fits() {
+    let mut config = small_config(sized([4096, 2048], 40_000));
+    config.gpu.budget_mb = 64;
+    assert!(fit_to_budget(&mut config.clone(), &wgpu::Limits::default()).is_err());
+
+    config.gpu.auto_downscale = true;
+    let (estimate, downscale) = fit_to_budget(&mut config, &wgpu::Limits::default()).unwrap();
+    assert_eq!(downscale, Some(Downscale { from_size: [4096, 2048], from_herbivores: 40_000 }));
+    assert_eq!(config.world.size, [1024, 512]);
+    assert_eq!(config.agents.herbivores, 2500);
+    assert!(estimate.total() <= 64 << 20);
+
+    // Nothing to do when it already fits
+    let (_, downscale) = fit_to_budget(&mut config, &wgpu::Limits::default()).unwrap();
+    assert_eq!(downscale, None);
+}
+
+#[test]
+fn auto_downscale_gives_up_at_the_minimum_world() {
+    let mut config = small_config(sized([128, 128], 100_000));
+    config.gpu.budget_mb = 1;
+    config.gpu.auto_downscale = true;
+    let error = fit_to_budget(&mut config, &wgpu::Limits::default()).unwrap_err();
+    assert_eq!(error.resource, "total GPU memory (gpu.budget_mb)");
+    assert_eq!(config.world.size, [128, 128]);
+}
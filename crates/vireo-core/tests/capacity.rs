@@ -0,0 +1,157 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{Agent, AgentLayout, AgentManager, SimRng};
+use vireo_core::{AgentConfig, GrowthConfig, GrowthPolicy, SimulationConfig, SpeciesCapacity, SpeciesDemography, MAX_AGENTS};
+
+fn agents(herbivores: u32, capacity: SpeciesCapacity) -> AgentConfig {
+    AgentConfig { herbivores, E0: 1.0, capacity, growth: GrowthConfig::default() }
+}
+
+#[test]
+fn layout_ranges_are_consecutive_by_kind() {
+    let layout = AgentLayout::new([2, 5, 0]);
+    assert_eq!([layout.range(0), layout.range(1), layout.range(2)], [0..2, 2..7, 7..7]);
+    assert_eq!(layout.capacity(1), 5);
+    assert_eq!(layout.total(), 7);
+}
+
+#[test]
+fn spawn_fits_each_species_into_its_capacity() {
+    let rng = || SimRng::new(Default::default(), 9);
+    let full = AgentManager::spawn(&agents(20, SpeciesCapacity::default()), [64.0, 64.0], &mut rng());
+    assert_eq!(full.layout, AgentLayout::new([10, 40, 10]));
+    let legacy = AgentManager::with_rng(20, [64.0, 64.0], 1.0, &mut rng());
+    assert!(full.agents.iter().zip(&legacy.agents).all(|(a, b)| a.pos == b.pos && a.kind == b.kind));
+
+    // Fewer plants, spare herbivore slots, predators as spawned
+    let capacity = SpeciesCapacity { plants: 4, herbivores: 50, predators: 0 };
+    let sized = AgentManager::spawn(&agents(20, capacity), [64.0, 64.0], &mut rng());
+    assert_eq!(sized.layout, AgentLayout::new([4, 50, 10]));
+    assert_eq!(sized.agents.len(), 64);
+
+    let slots = |m: &AgentManager, kind: u32| m.agents[m.layout.range(kind).start as usize..m.layout.range(kind).end as usize].to_vec();
+    let bytes = |agents: &[Agent]| bytemuck::cast_slice::<_, u8>(agents).to_vec();
+    for kind in 0..3 {
+        assert!(slots(&sized, kind).iter().all(|a| a.kind == kind));
+    }
+    // The random stream does not depend on the capacities
+    assert_eq!(bytes(&slots(&sized, 0)), bytes(&slots(&full, 0)[..4]));
+    assert_eq!(bytes(&slots(&sized, 1)[..40]), bytes(&slots(&full, 1)));
+    assert!(slots(&sized, 1)[40..].iter().all(|a| !a.is_alive()));
+    assert_eq!(bytes(&slots(&sized, 2)), bytes(&slots(&full, 2)));
+}
+
+#[test]
+fn total_slots_are_limited() {
+    let capacity = SpeciesCapacity { predators: MAX_AGENTS, ..SpeciesCapacity::default() };
+    let mut config = SimulationConfig { agents: agents(10, capacity), ..SimulationConfig::default() };
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("agents.capacity"), "{}", error);
+
+    config.agents.capacity.predators = MAX_AGENTS - 25;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn births_stay_in_their_species_range() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents = agents(200, SpeciesCapacity { plants: 0, herbivores: 800, predators: 8 });
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 0.0, birth: 1.0 };
+    config.demography.predators = SpeciesDemography { death: 0.0, birth: 1.0 };
+
+    let mut sim = Simulation::new(&gpu, config);
+    assert_eq!(sim.agent_count(), 100 + 800 + 8);
+    for _ in 0..3 {
+        sim.step(&gpu);
+    }
+    sim.sync_agents(&gpu);
+
+    let layout = sim.agent_manager.layout.clone();
+    let alive = |kind: u32| sim.agent_manager.agents[layout.range(kind).start as usize..layout.range(kind).end as usize]
+        .iter()
+        .inspect(|a| assert_eq!(a.kind, kind, "slot of kind {} changed species", kind))
+        .filter(|a| a.is_alive())
+        .count();
+    assert_eq!(alive(0), 100);
+    assert!(alive(1) > 400, "{}", alive(1));
+    assert_eq!(alive(2), 8);
+}
+
+#[test]
+fn growth_doubles_full_ranges_to_powers_of_two() {
+    let grow = GrowthConfig { policy: GrowthPolicy::Grow, ..GrowthConfig::default() };
+    assert_eq!(GrowthConfig::default().grown_slots([10, 40, 10], [10, 40, 10]), [10, 40, 10]);
+    assert_eq!(grow.grown_slots([10, 40, 10], [5, 40, 3]), [10, 128, 10]);
+    assert_eq!(grow.grown_slots([10, 64, 0], [6, 33, 0]), [32, 128, 0]);
+
+    // Ceilings and the agent limit bound the new range
+    let capped = GrowthConfig { max_population: SpeciesCapacity { herbivores: 100, ..SpeciesCapacity::default() }, ..grow };
+    assert_eq!(capped.grown_slots([10, 64, 10], [0, 64, 0]), [10, 100, 10]);
+    assert_eq!(capped.grown_slots([10, 100, 10], [0, 100, 0]), [10, 100, 10]);
+    assert_eq!(grow.grown_slots([10, MAX_AGENTS - 20, 10], [0, MAX_AGENTS - 20, 0]), [10, MAX_AGENTS - 20, 10]);
+
+    let mut config = SimulationConfig { agents: agents(20, SpeciesCapacity::default()), ..SimulationConfig::default() };
+    config.agents.growth = GrowthConfig { max_population: SpeciesCapacity { herbivores: 30, ..SpeciesCapacity::default() }, ..grow };
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("agents.growth.max_population"), "{}", error);
+    config.agents.growth.max_population.herbivores = 40;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn relayout_keeps_each_species_at_the_start_of_its_range() {
+    let (from, to) = (AgentLayout::new([2, 3, 1]), AgentLayout::new([2, 8, 1]));
+    let moved = from.relayout(&[1, 2, 10, 11, 12, 20], &to, |kind| kind * 100);
+    assert_eq!(moved, vec![1, 2, 10, 11, 12, 100, 100, 100, 100, 100, 20]);
+    assert_eq!(from.remap(5, &to), Some(10));
+    assert_eq!(from.remap(3, &to), Some(3));
+    assert_eq!(to.remap(9, &from), None);
+}
+
+#[test]
+fn grow_policy_reallocates_a_full_range() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents = agents(100, SpeciesCapacity::default());
+    config.agents.growth = GrowthConfig { policy: GrowthPolicy::Grow, check_every: 2, ..GrowthConfig::default() };
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 0.0, birth: 1.0 };
+
+    let mut sim = Simulation::new(&gpu, config.clone());
+    assert_eq!(sim.agent_manager.layout, AgentLayout::new([50, 200, 50]));
+    for _ in 0..2 {
+        sim.step(&gpu);
+    }
+    // Every spawned range was full and doubled; nobody was lost in the copy
+    let event = sim.growth_events().to_vec();
+    assert_eq!(event.len(), 1, "{:?}", event);
+    assert_eq!((event[0].step, event[0].alive), (2, [50, 200, 50]));
+    assert_eq!(event[0].to, AgentLayout::new([128, 512, 128]));
+    assert_eq!(sim.agent_count(), 768);
+    assert_eq!(sim.config.agents.slots(), [128, 512, 128]);
+
+    for _ in 0..2 {
+        sim.step(&gpu);
+    }
+    sim.sync_agents(&gpu);
+    let layout = sim.agent_manager.layout.clone();
+    let alive = |kind: u32| sim.agent_manager.agents[layout.range(kind).start as usize..layout.range(kind).end as usize]
+        .iter()
+        .inspect(|a| assert_eq!(a.kind, kind, "slot of kind {} changed species", kind))
+        .filter(|a| a.is_alive())
+        .count();
+    assert!(alive(1) > 200, "births stopped at the old capacity: {}", alive(1));
+    assert_eq!((alive(0), alive(2)), (50, 50));
+
+    // A snapshot of the grown world loads into a config with the grown capacity
+    let snapshot = sim.snapshot(&gpu);
+    assert!(snapshot.check_compatible(&config).is_err());
+    let mut restored = Simulation::new(&gpu, sim.config.clone());
+    restored.restore(&gpu, &snapshot).unwrap();
+}
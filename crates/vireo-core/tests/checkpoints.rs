@@ -0,0 +1,121 @@
+mod common;
+
+use common::gpu_or_skip;
+use std::path::PathBuf;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{delta_path, Agent, ChainWriter, CheckpointChain, DeltaEncoder, FieldData, SnapshotDelta, StateSnapshot};
+use vireo_core::{CheckpointChainConfig, SimulationConfig};
+
+/// A 70×40 world: 32-cell tiles leave clipped ones on the right and bottom edges
+fn state(step: u32) -> StateSnapshot {
+    StateSnapshot {
+        step,
+        size: [70, 40],
+        field: (0..70 * 40).map(|i| FieldData::new(i as f32 * 0.01, 0.5)).collect(),
+        agents: (0..50).map(|i| Agent::new(glam::Vec2::new(i as f32, 3.0), 1.0, i % 3)).collect(),
+    }
+}
+
+/// `state` moved on: cell (69, 39) of the clipped corner tile and one agent changed
+fn changed(mut s: StateSnapshot, step: u32) -> StateSnapshot {
+    s.step = step;
+    s.field[39 * 70 + 69].R += 1.0;
+    s.agents[7].energy += step as f32;
+    s
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vireo-checkpoints-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn deltas_hold_only_changed_tiles_and_slots() {
+    let base = state(10);
+    let next = changed(base.clone(), 20);
+    let mut encoder = DeltaEncoder::new(&base, 32);
+    let delta = encoder.encode(&next);
+    assert_eq!((delta.base_step, delta.step), (10, 20));
+    assert_eq!(delta.tiles, vec![5], "bottom-right of the 3×2 tiles");
+    assert_eq!(delta.slots, vec![7]);
+
+    let parsed = SnapshotDelta::from_bytes(&delta.to_bytes()).unwrap();
+    assert!(delta.to_bytes().len() < next.to_bytes().len() / 10);
+    let mut rebuilt = base.clone();
+    parsed.apply(&mut rebuilt).unwrap();
+    assert_eq!(rebuilt.to_bytes(), next.to_bytes());
+
+    // A delta only applies to its own base
+    assert!(parsed.apply(&mut state(11)).is_err());
+    assert!(parsed.apply(&mut rebuilt.clone()).is_err());
+
+    // Grown agent buffers carry their new slots
+    let mut grown = changed(next.clone(), 30);
+    grown.agents.push(Agent::new(glam::Vec2::new(1.0, 1.0), 2.0, 1));
+    let delta = encoder.encode(&grown);
+    assert_eq!(delta.slots, vec![7, 50]);
+    let mut rebuilt = next.clone();
+    delta.apply(&mut rebuilt).unwrap();
+    assert_eq!(rebuilt.to_bytes(), grown.to_bytes());
+
+    let bytes = delta.to_bytes();
+    let truncated = SnapshotDelta::from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+    assert!(truncated.apply(&mut next.clone()).is_err());
+    assert!(SnapshotDelta::from_bytes(&bytes[..40]).is_err());
+    assert!(SnapshotDelta::from_bytes(&next.to_bytes()).is_err());
+}
+
+#[test]
+fn chains_rebuild_every_checkpoint() {
+    let dir = temp_dir("chain");
+    let mut writer = ChainWriter::new(&dir, &CheckpointChainConfig { every: 10, tile: 32, keyframe_every: 3 }).unwrap();
+    let mut states = vec![state(0)];
+    for i in 1..7 {
+        states.push(changed(states[i - 1].clone(), i as u32 * 10));
+    }
+    for s in &states {
+        writer.push(s).unwrap();
+    }
+    assert_eq!((writer.checkpoints, writer.keyframes), (7, 3), "keyframes at steps 0, 30 and 60");
+    assert!(writer.bytes < writer.full_bytes / 2);
+
+    let chain = CheckpointChain::open(&dir).unwrap();
+    assert_eq!(chain.steps().collect::<Vec<_>>(), vec![0, 10, 20, 30, 40, 50, 60]);
+    for s in &states {
+        assert_eq!(chain.rebuild(s.step).unwrap().unwrap().to_bytes(), s.to_bytes(), "step {}", s.step);
+    }
+    assert_eq!(chain.rebuild(25).unwrap().unwrap().step, 20, "last checkpoint before the step");
+
+    // A missing delta breaks the steps after it up to the next keyframe
+    std::fs::remove_file(delta_path(&dir, 10)).unwrap();
+    let chain = CheckpointChain::open(&dir).unwrap();
+    assert!(chain.rebuild(20).is_err());
+    assert!(chain.rebuild(40).is_ok());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn chains_rebuild_a_simulated_run() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    let mut sim = Simulation::new(&gpu, config);
+
+    let dir = temp_dir("sim");
+    let mut writer = ChainWriter::new(&dir, &CheckpointChainConfig { every: 5, tile: 16, keyframe_every: 0 }).unwrap();
+    writer.push(&sim.snapshot(&gpu)).unwrap();
+    for _ in 0..3 {
+        for _ in 0..5 {
+            sim.step(&gpu);
+        }
+        writer.push(&sim.snapshot(&gpu)).unwrap();
+    }
+    assert_eq!(writer.keyframes, 1);
+
+    let rebuilt = CheckpointChain::open(&dir).unwrap().rebuild(15).unwrap().unwrap();
+    assert_eq!(rebuilt.step, 15);
+    assert_eq!(rebuilt.checksum(), sim.snapshot(&gpu).checksum());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
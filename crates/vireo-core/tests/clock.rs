@@ -0,0 +1,42 @@
+use std::time::Duration;
+use vireo_core::sim::StepClock;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn step_rate_does_not_depend_on_the_frame_rate() {
+    // One second at 144 Hz and at 30 Hz both run 60 steps
+    for (frames, frame_ms) in [(144, 1000.0 / 144.0), (30, 1000.0 / 30.0)] {
+        let mut clock = StepClock::new(60.0, 4);
+        let total: u32 = (0..frames).map(|_| clock.advance(Duration::from_secs_f64(frame_ms / 1000.0), 1.0)).sum();
+        assert!((59..=60).contains(&total), "{} Hz ran {} steps", frames, total);
+    }
+
+    let mut clock = StepClock::new(60.0, 4);
+    assert_eq!(clock.advance(ms(50), 2.0), 4); // Speed scales the target rate (6 due, 4 run)
+}
+
+#[test]
+fn catch_up_is_limited_to_bursts() {
+    let mut clock = StepClock::new(100.0, 4);
+    assert_eq!(clock.advance(ms(25), 1.0), 2);
+
+    // A one-second stall is caught up with one burst now and at most one more
+    assert_eq!(clock.advance(ms(1000), 1.0), 4);
+    assert!(clock.backlog() <= 4.0);
+    assert_eq!(clock.advance(Duration::ZERO, 1.0), 4);
+    assert_eq!(clock.advance(Duration::ZERO, 1.0), 0);
+}
+
+#[test]
+fn reset_drops_the_backlog() {
+    let mut clock = StepClock::default();
+    assert_eq!(clock.advance(ms(10), 1.0), 0);
+    assert!(clock.backlog() > 0.5);
+    clock.reset();
+    assert_eq!(clock.backlog(), 0.0);
+    assert_eq!(clock.advance(ms(10), 1.0), 0);
+    assert_eq!(clock.advance(ms(0), 0.0), 0);
+}
@@ -0,0 +1,63 @@
+use vireo_core::sim::{Agent, CohortTracker, UNTAGGED};
+use vireo_core::CohortEvent;
+use glam::Vec2;
+
+fn line_of_agents(n: usize) -> Vec<Agent> {
+    (0..n).map(|i| Agent::new(Vec2::new(i as f32, 0.0), 1.0, 1)).collect()
+}
+
+#[test]
+fn agent_layout_carries_cohort() {
+    // pos, vel, energy, alive, kind, cohort, memory_pos, memory_value, state, age, pad — matches the WGSL Agent stride
+    assert_eq!(std::mem::size_of::<Agent>(), 64);
+    assert_eq!(Agent::new(Vec2::ZERO, 1.0, 1).cohort, UNTAGGED);
+}
+
+#[test]
+fn tag_within_marks_only_alive_agents_in_disc() {
+    let mut agents = line_of_agents(10);
+    agents[2].kill();
+
+    let mut tracker = CohortTracker::new();
+    let event = CohortEvent { id: 3, step: 0, center: [0.0, 0.0], radius: 4.0, kind: None };
+    let tagged = tracker.apply(&mut agents, &event);
+
+    // x = 0..=4 are inside, x = 2 is dead
+    assert_eq!(tagged, 4);
+    for (i, a) in agents.iter().enumerate() {
+        let expect = if i <= 4 && i != 2 { 3 } else { UNTAGGED };
+        assert_eq!(a.cohort, expect, "agent {}", i);
+    }
+    assert_eq!(tracker.next_id(), 4);
+}
+
+#[test]
+fn stats_report_survival_and_dispersal() {
+    let mut agents = line_of_agents(4);
+    let mut tracker = CohortTracker::new();
+    tracker.tag(&mut agents, 1, 0, |_| true);
+
+    agents[0].kill();
+    agents[1].pos = [1.0, 3.0]; // moved 3 cells
+    agents[2].pos = [2.0, 1.0]; // moved 1 cell
+
+    let stats = tracker.stats(&agents);
+    assert_eq!(stats.len(), 1);
+    let c = &stats[0];
+    assert_eq!((c.cohort, c.tagged, c.alive), (1, 4, 3));
+    assert!((c.survival - 0.75).abs() < 1e-6);
+    assert!((c.mean_dispersal - 4.0 / 3.0).abs() < 1e-6);
+    assert!((c.max_dispersal - 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn retagged_agents_leave_their_old_cohort() {
+    let mut agents = line_of_agents(4);
+    let mut tracker = CohortTracker::new();
+    tracker.tag(&mut agents, 1, 0, |_| true);
+    tracker.tag(&mut agents, 2, 10, |a| a.pos[0] < 2.0);
+
+    let stats = tracker.stats(&agents);
+    assert_eq!((stats[0].cohort, stats[0].tagged, stats[0].alive), (1, 4, 2));
+    assert_eq!((stats[1].cohort, stats[1].tagged, stats[1].alive), (2, 2, 2));
+}
@@ -0,0 +1,29 @@
+use vireo_core::gpu::GpuDevice;
+use vireo_core::SimulationConfig;
+
+/// 64x64 world with 100 herbivores, adjusted by `overrides`
+#[allow(dead_code)] // The determinism suite starts from a preset via `small_world`
+pub fn small_config(overrides: impl FnOnce(&mut SimulationConfig)) -> SimulationConfig {
+    small_world(SimulationConfig::default(), overrides)
+}
+
+/// `base` (e.g. a parsed preset) shrunk to 64x64 with 100 herbivores, adjusted by `overrides`
+pub fn small_world(mut base: SimulationConfig, overrides: impl FnOnce(&mut SimulationConfig)) -> SimulationConfig {
+    base.world.size = [64, 64];
+    base.agents.herbivores = 100;
+    overrides(&mut base);
+    base
+}
+
+/// Device for a GPU test, or `None` (and a note on stderr) so the test is
+/// skipped on machines with no adapter at all
+#[allow(dead_code)] // Not every test binary that includes `common` needs a device
+pub fn gpu_or_skip() -> Option<GpuDevice> {
+    match pollster::block_on(GpuDevice::try_new()) {
+        Ok(gpu) => Some(gpu),
+        Err(e) => {
+            eprintln!("skipping GPU test: {}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use proptest::prelude::*;
+use vireo_core::sim::{AgentManager, FieldManager, SimRng};
+use vireo_core::{AgentColoring, LoadError, SimulationConfig, parse_config, load_config, MAX_AGENTS, MAX_AGE_CLASSES, MAX_KDE_BANDWIDTH, MAX_MUTATION, MAX_REWIND_SLOTS};
+
+const DEMO: &str = include_str!("../../../lab/configs/best-demo.yaml");
+
+/// The demo config with the first `key:` line replaced by `key: value`
+fn with_value(key: &str, value: &str) -> String {
+    let prefix = format!("{}:", key);
+    let mut replaced = false;
+    DEMO.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if !replaced && trimmed.starts_with(&prefix) {
+                replaced = true;
+                let indent = &line[..line.len() - trimmed.len()];
+                format!("{}{} {}", indent, prefix, value)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn demo() -> SimulationConfig {
+    parse_config(DEMO).expect("demo config is valid")
+}
+
+fn invalid_field(result: Result<SimulationConfig, LoadError>) -> String {
+    match result {
+        Err(LoadError::Invalid(e)) => e.field,
+        other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+    }
+}
+
+/// Mostly plausible floats, mixed with non-finite, negative and extreme ones
+fn awkward_f32() -> impl Strategy<Value = f32> {
+    prop_oneof![
+        6 => 0.0f32..10.0,
+        1 => prop::sample::select(vec![
+            f32::NAN, f32::INFINITY, f32::NEG_INFINITY, -1.0, 0.0, f32::MAX, f32::MIN_POSITIVE,
+        ]),
+    ]
+}
+
+/// World edges either side of the minimum
+fn awkward_edge() -> impl Strategy<Value = u32> {
+    prop_oneof![1 => 0u32..64, 3 => 64u32..160]
+}
+
+#[test]
+fn rejections_name_the_offending_field() {
+    assert_eq!(invalid_field(parse_config(&with_value("size", "[0, 0]"))), "world.size");
+    assert_eq!(invalid_field(parse_config(&with_value("size", "[100000, 64]"))), "world.size");
+    assert_eq!(invalid_field(parse_config(&with_value("steps", "0"))), "world.steps");
+    assert_eq!(invalid_field(parse_config(&with_value("dt", ".nan"))), "world.dt");
+    assert_eq!(invalid_field(parse_config(&with_value("D_R", "-.inf"))), "field.D_R");
+    assert_eq!(invalid_field(parse_config(&with_value("herbivores", "4000000000"))), "agents.herbivores");
+
+    let message = parse_config(&with_value("dt", "-0.5")).unwrap_err().to_string();
+    assert!(message.contains("world.dt") && message.contains("-0.5"), "{}", message);
+}
+
+#[test]
+fn output_cadence_defaults_and_rejects_zero() {
+    let output = demo().output;
+    assert_eq!((output.metrics_every, output.hud_every, output.debug_every), (50, 30, 100));
+
+    let text = format!("{}\noutput:\n  metrics_every: 5\n", DEMO);
+    assert_eq!(parse_config(&text).expect("valid").output.metrics_every, 5);
+    let text = format!("{}\noutput:\n  hud_every: 0\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "output.hud_every");
+}
+
+#[test]
+fn age_classes_are_bounded() {
+    let output = demo().output;
+    assert_eq!((output.age_structure, output.age_bin, output.age_classes), (false, 100, 20));
+
+    let text = format!("{}\noutput:\n  age_bin: 0\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "output.age_bin");
+    let text = format!("{}\noutput:\n  age_classes: {}\n", DEMO, MAX_AGE_CLASSES + 1);
+    assert_eq!(invalid_field(parse_config(&text)), "output.age_classes");
+}
+
+#[test]
+fn rewind_ring_is_bounded() {
+    let gpu = demo().gpu;
+    assert_eq!((gpu.rewind_slots, gpu.rewind_every), (8, 100));
+
+    let text = format!("{}\ngpu:\n  rewind_slots: 0\n", DEMO);
+    assert_eq!(parse_config(&text).expect("valid").gpu.rewind_slots, 0);
+    let text = format!("{}\ngpu:\n  rewind_slots: {}\n", DEMO, MAX_REWIND_SLOTS + 1);
+    assert_eq!(invalid_field(parse_config(&text)), "gpu.rewind_slots");
+    let text = format!("{}\ngpu:\n  rewind_every: 0\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "gpu.rewind_every");
+}
+
+#[test]
+fn demography_is_off_by_default_and_rates_are_probabilities() {
+    assert!(!demo().demography.enabled);
+
+    let text = format!("{}\ndemography:\n  enabled: true\n  herbivores: {{ death: 0.01 }}\n", DEMO);
+    let demography = parse_config(&text).expect("valid").demography;
+    assert_eq!((demography.herbivores.death, demography.herbivores.birth), (0.01, 0.0));
+    let text = format!("{}\ndemography:\n  predators: {{ birth: 1.5 }}\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "demography.predators.birth");
+}
+
+#[test]
+fn agent_colors_default_to_the_energy_gradient() {
+    let render = demo().render;
+    assert_eq!(render.agent_color, AgentColoring::Energy);
+
+    let text = format!("{}\nrender:\n  agent_color: species\n  energy_range: [0.5, 1.5]\n", DEMO);
+    let render = parse_config(&text).expect("valid").render;
+    assert_eq!((render.agent_color, render.energy_range), (AgentColoring::Species, [0.5, 1.5]));
+    let text = format!("{}\nrender:\n  energy_range: [1.0, 1.0]\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "render.energy_range");
+    let text = format!("{}\nrender:\n  thriving_color: [0.2, 1.5, 0.4]\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "render.thriving_color");
+    let text = format!("{}\nrender:\n  dead_alpha: -0.1\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "render.dead_alpha");
+}
+
+#[test]
+fn density_map_thresholds_are_validated() {
+    let render = demo().render;
+    assert_eq!((render.density_below, render.density_saturation), (2.0, 4.0));
+
+    let text = format!("{}
+render:
+  density_below: 0.0
+", DEMO);
+    assert_eq!(parse_config(&text).expect("0 keeps quads at every zoom").render.density_below, 0.0);
+    let text = format!("{}
+render:
+  density_below: -1.0
+", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "render.density_below");
+    let text = format!("{}
+render:
+  density_saturation: 0.0
+", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "render.density_saturation");
+}
+
+#[test]
+fn kde_bandwidth_is_validated() {
+    let render = demo().render;
+    assert_eq!((render.kde, render.kde_bandwidth), (false, 2.0));
+
+    let text = format!("{}\nrender:\n  kde: true\n  kde_bandwidth: {}\n", DEMO, MAX_KDE_BANDWIDTH);
+    let render = parse_config(&text).expect("the widest bandwidth is allowed").render;
+    assert!(render.kde);
+    let text = format!("{}\nrender:\n  kde_bandwidth: 0.0\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "render.kde_bandwidth");
+    let text = format!("{}\nrender:\n  kde_bandwidth: {}\n", DEMO, MAX_KDE_BANDWIDTH * 2.0);
+    assert_eq!(invalid_field(parse_config(&text)), "render.kde_bandwidth");
+}
+
+#[test]
+fn evolution_needs_demography_and_bounded_mutation() {
+    let evolution = demo().evolution;
+    assert!(!evolution.enabled);
+    assert_eq!((evolution.herbivores.chi, evolution.herbivores.kappa), (0.0, 0.0));
+
+    let text = format!("{}\nevolution:\n  enabled: true\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "evolution.enabled");
+    let text = format!("{}\ndemography:\n  enabled: true\nevolution:\n  enabled: true\n  herbivores: {{ chi: 0.1, kappa: {} }}\n", DEMO, MAX_MUTATION);
+    assert_eq!(parse_config(&text).expect("mutation at the limit").evolution.herbivores.kappa, MAX_MUTATION);
+    let text = format!("{}\nevolution:\n  predators: {{ chi: -0.1 }}\n", DEMO);
+    assert_eq!(invalid_field(parse_config(&text)), "evolution.predators.chi");
+    let text = format!("{}\nevolution:\n  plants: {{ kappa: {} }}\n", DEMO, MAX_MUTATION * 2.0);
+    assert_eq!(invalid_field(parse_config(&text)), "evolution.plants.kappa");
+}
+
+#[test]
+fn soil_rates_must_be_non_negative() {
+    assert!(!demo().soil.enabled);
+    let text = with_value("recovery", "-0.1");
+    assert!(parse_config(&text).is_ok(), "disabled soil is not validated");
+    let text = text.replace("  enabled: false   # Lasting", "  enabled: true   # Lasting");
+    assert_eq!(invalid_field(parse_config(&text)), "soil.recovery");
+    let text = with_value("degradation", ".nan").replace("  enabled: false   # Lasting", "  enabled: true   # Lasting");
+    assert_eq!(invalid_field(parse_config(&text)), "soil.degradation");
+}
+
+#[test]
+fn inline_schedules_move_to_the_schedule_list() {
+    let text = with_value("chi_R", "{schedule: linear, from: 2, to: 10, start: 0, end: 2000}");
+    let config = parse_config(&text).expect("valid");
+    assert_eq!(config.chemotaxis.chi_R, 2.0);
+    assert_eq!(config.schedules.len(), 1);
+    let schedule = &config.schedules[0];
+    assert_eq!((schedule.param.as_str(), schedule.to, schedule.end), ("chemotaxis.chi_R", 10.0, 2000));
+
+    let text = format!("{}\nschedules:\n  - {{param: field.sigma_R, schedule: step, from: 0.1, to: 0.0, start: 500}}\n", DEMO);
+    assert_eq!(parse_config(&text).expect("valid").schedules[0].param, "field.sigma_R");
+}
+
+#[test]
+fn schedules_are_validated() {
+    let schedule = |param: &str, shape: &str| format!("{}\nschedules:\n  - {{param: {}, schedule: {}, from: 1, to: -1, start: 10, end: 5}}\n", DEMO, param, shape);
+    assert_eq!(invalid_field(parse_config(&schedule("world.dt", "step"))), "schedules[0].param");
+    assert_eq!(invalid_field(parse_config(&schedule("field.D_R", "linear"))), "schedules[0].end");
+    assert_eq!(invalid_field(parse_config(&schedule("field.D_R", "step"))), "schedules[0].to");
+    assert!(parse_config(&schedule("chemotaxis.chi_W", "step")).is_ok(), "chi_W may be negative");
+    assert!(matches!(parse_config(&with_value("chi_R", "{schedule: sine, from: 1, to: 2, start: 0}")), Err(LoadError::Parse(_))));
+}
+
+#[test]
+fn malformed_yaml_and_missing_files_are_errors() {
+    assert!(matches!(parse_config(&with_value("steps", "lots")), Err(LoadError::Parse(_))));
+    assert!(matches!(parse_config("world: ["), Err(LoadError::Parse(_))));
+    assert!(matches!(load_config("does/not/exist.yaml".as_ref()), Err(LoadError::Io { .. })));
+}
+
+#[test]
+fn partial_configs_overlay_the_defaults_or_a_preset() {
+    // The full demo file spells out the defaults
+    let full = demo();
+    let empty = parse_config("{}").expect("valid");
+    assert_eq!(format!("{:?}", empty), format!("{:?}", full));
+
+    let partial = parse_config("world: { steps: 500 }\nchemotaxis: { chi_R: 4.0 }\nblooms: { enabled: true }\n").expect("valid");
+    assert_eq!((partial.world.steps, partial.world.size, partial.world.dt), (500, [128, 128], 0.1));
+    assert_eq!((partial.chemotaxis.chi_R, partial.chemotaxis.chi_W), (4.0, 4.0));
+    assert!(partial.blooms.enabled && partial.blooms.rate == 0.01);
+
+    // A preset is merged key by key; lists replace the preset's whole
+    let river = parse_config("preset: river\nagents: { E0: 2.0 }\n").expect("valid");
+    assert_eq!((river.world.size, river.agents.herbivores, river.agents.E0), ([1024, 128], 16_000, 2.0));
+    let scheduled = parse_config("preset: rd-only\nfield:\n  sigma_R: {schedule: linear, from: 0.1, to: 0.2, start: 0, end: 100}\n").expect("valid");
+    assert_eq!((scheduled.agents.herbivores, scheduled.field.sigma_R, scheduled.schedules.len()), (0, 0.1, 1));
+
+    match parse_config("preset: lake\n") {
+        Err(e @ LoadError::UnknownPreset(_)) => assert!(e.to_string().contains("default, river, rd-only"), "{}", e),
+        other => panic!("expected an unknown preset, got {:?}", other.map(|_| ())),
+    }
+    assert_eq!(invalid_field(parse_config("preset: river\nworld: { dt: -1 }\n")), "world.dt");
+}
+
+#[test]
+fn agent_limit_is_inclusive() {
+    let mut config = demo();
+    config.agents.herbivores = MAX_AGENTS / 3;
+    assert!(config.validate().is_ok());
+    config.agents.herbivores += 1;
+    assert!(config.validate().is_err());
+}
+
+proptest! {
+    /// Any scalar replaced by any token either loads or fails with an error
+    #[test]
+    fn parsing_mutated_values_never_panics(
+        key in prop::sample::select(vec![
+            "size", "steps", "dt", "seed", "D_R", "D_W", "sigma_R", "alpha_H", "lambda_W",
+            "chi_R", "chi_W", "kappa", "gamma", "v_max", "herbivores", "E0", "sigma",
+        ]),
+        value in prop_oneof![
+            prop::sample::select(vec![
+                ".nan", ".inf", "-.inf", "0", "-1", "4294967296", "1e39", "[]", "~", "[0, 0]",
+                "[4294967295, 4294967295]", "[64]", "\"text\"",
+            ]).prop_map(str::to_string),
+            any::<f32>().prop_map(|v| format!("{:?}", v)),
+            any::<u32>().prop_map(|v| v.to_string()),
+            "[a-z0-9.\\-\\[\\], ]{0,12}",
+        ],
+    ) {
+        let _ = parse_config(&with_value(key, &value));
+    }
+
+    /// Arbitrary text never panics the loader
+    #[test]
+    fn parsing_garbage_never_panics(text in ".{0,256}") {
+        let _ = parse_config(&text);
+    }
+
+    /// A config that validates can be seeded on the CPU without panicking
+    #[test]
+    fn validated_configs_seed_cleanly(
+        w in awkward_edge(),
+        h in awkward_edge(),
+        herbivores in 0u32..3000,
+        dt in awkward_f32(),
+        e0 in awkward_f32(),
+        sigma in awkward_f32(),
+        d_r in awkward_f32(),
+        seed in any::<u64>(),
+    ) {
+        let mut config = demo();
+        config.world.size = [w, h];
+        config.world.seed = seed;
+        config.world.dt = dt;
+        config.agents.herbivores = herbivores;
+        config.agents.E0 = e0;
+        config.noise.sigma = sigma;
+        config.field.D_R = d_r;
+
+        if config.validate().is_ok() {
+            let mut field = FieldManager::new(config.world.size);
+            field.seed_resources_with(&mut SimRng::from_world(&config.world));
+            field.add_noise_with(config.noise.sigma, &mut SimRng::from_world(&config.world));
+
+            let agents = AgentManager::with_rng(
+                config.agents.herbivores,
+                [w as f32, h as f32],
+                config.agents.E0,
+                &mut SimRng::from_world(&config.world),
+            );
+            prop_assert_eq!(agents.agents.len() as u32, herbivores * 3);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use vireo_core::sim::CullRegion;
+
+#[test]
+fn rect_accepts_corners_in_any_order() {
+    let a = CullRegion::Rect { a: [10.0, 40.0], b: [30.0, 20.0] };
+    let b = CullRegion::Rect { a: [30.0, 20.0], b: [10.0, 40.0] };
+    for region in [a, b] {
+        assert!(region.contains([10.0, 20.0]));
+        assert!(region.contains([25.0, 35.0]));
+        assert!(!region.contains([9.9, 30.0]));
+        assert!(!region.contains([20.0, 40.1]));
+
+        // The shader sees normalized min/max corners
+        let params = region.to_params();
+        assert_eq!((params.shape, params.a, params.b), (0, [10.0, 20.0], [30.0, 40.0]));
+    }
+}
+
+#[test]
+fn circle_is_inclusive_of_radius() {
+    let region = CullRegion::Circle { center: [50.0, 50.0], radius: 5.0 };
+    assert!(region.contains([55.0, 50.0]));
+    assert!(region.contains([53.0, 54.0]));
+    assert!(!region.contains([54.0, 54.0]));
+
+    let params = region.to_params();
+    assert_eq!((params.shape, params.a, params.b[0]), (1, [50.0, 50.0], 5.0));
+}
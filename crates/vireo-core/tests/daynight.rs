@@ -0,0 +1,120 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::FieldData;
+use vireo_core::{SimulationConfig, TimeUniform, WorldConfig};
+
+fn cycle(day_period: f32, night_light: f32) -> WorldConfig {
+    WorldConfig { day_period, night_light, ..SimulationConfig::default().world }
+}
+
+#[test]
+fn light_follows_the_day_phase() {
+    // No cycle: permanent noon at full light
+    let world = cycle(0.0, 0.2);
+    for step in [0, 17, 100_000] {
+        assert_eq!((world.day_phase(step), world.light(step)), (0.5, 1.0));
+    }
+
+    // dt 0.1, 10 time units per day = 100 steps
+    let world = cycle(10.0, 0.2);
+    assert_eq!(world.day_phase(0), 0.0);
+    assert!((world.light(0) - 0.2).abs() < 1e-6);
+    assert!((world.day_phase(50) - 0.5).abs() < 1e-6);
+    assert!((world.light(50) - 1.0).abs() < 1e-6);
+    assert!((world.light(25) - 0.6).abs() < 1e-5, "dawn {}", world.light(25));
+    assert!((world.light(25) - world.light(75)).abs() < 1e-5);
+    assert!((world.day_phase(1050) - 0.5).abs() < 1e-4);
+
+    let time = TimeUniform::new(&world, 25);
+    assert_eq!((time.step, time.day_phase, time.light), (25, world.day_phase(25), world.light(25)));
+    assert!((time.time - 2.5).abs() < 1e-6);
+}
+
+#[test]
+fn cycle_settings_are_validated() {
+    for (day_period, night_light, key) in [(-1.0, 0.2, "world.day_period"), (f32::NAN, 0.2, "world.day_period"), (10.0, 1.5, "world.night_light")] {
+        let config = SimulationConfig { world: cycle(day_period, night_light), ..SimulationConfig::default() };
+        let error = config.validate().unwrap_err();
+        assert_eq!(error.field, key);
+    }
+    let config = SimulationConfig { world: cycle(10.0, 0.0), ..SimulationConfig::default() };
+    assert!(config.validate().is_ok());
+}
+
+/// 64x64 world with `world`, uniform R = 0.5 and agents killed unless `agents`
+fn world(gpu: &GpuDevice, world: WorldConfig, agents: bool) -> Simulation {
+    let mut config = SimulationConfig { world, ..SimulationConfig::default() };
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.field.sigma_R = 0.2;
+    config.field.lambda_R = 0.02;
+    config.field.K_R = 1.5;
+
+    let mut sim = Simulation::new(gpu, config);
+    if !agents {
+        for agent in &mut sim.agent_manager.agents {
+            agent.kill();
+        }
+        sim.upload_agents(gpu);
+    }
+    sim.field_manager.fill(FieldData::new(0.5, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim
+}
+
+fn mean_r(sim: &mut Simulation, gpu: &GpuDevice) -> f32 {
+    sim.sync_field(gpu);
+    let cells = sim.field_manager.cells();
+    cells.iter().map(|d| d.R).sum::<f32>() / cells.len() as f32
+}
+
+#[test]
+fn resource_growth_stops_in_the_dark() {
+    let Some(gpu) = gpu_or_skip() else { return };
+
+    // dt 1 and a 2-unit day: step 0 runs at midnight, step 1 at noon
+    let mut sim = world(&gpu, cycle(2.0, 0.0), false);
+    assert_eq!(sim.time().light, 0.0);
+    sim.step(&gpu);
+    let night = mean_r(&mut sim, &gpu);
+    assert!((night - 0.49).abs() < 1e-3, "decay only at midnight: {}", night);
+
+    assert!((sim.time().light - 1.0).abs() < 1e-6);
+    sim.field_manager.fill(FieldData::new(0.5, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim.step(&gpu);
+    let noon = mean_r(&mut sim, &gpu);
+
+    let mut daylight = world(&gpu, cycle(0.0, 0.0), false);
+    daylight.step(&gpu);
+    let constant = mean_r(&mut daylight, &gpu);
+    assert!((noon - constant).abs() < 1e-3, "noon {} vs constant daylight {}", noon, constant);
+    assert!(noon > 0.55, "{}", noon);
+}
+
+#[test]
+fn night_lowers_the_top_speed() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let max_speed = |sim: &mut Simulation| {
+        for _ in 0..5 {
+            sim.step(&gpu);
+        }
+        sim.sync_agents(&gpu);
+        sim.agent_manager.agents.iter()
+            .filter(|a| a.is_alive())
+            .map(|a| (a.vel[0] * a.vel[0] + a.vel[1] * a.vel[1]).sqrt())
+            .fold(0.0f32, f32::max)
+    };
+
+    // A 1000-unit day stays near midnight for the first steps
+    let mut night = world(&gpu, cycle(1000.0, 0.25), true);
+    let v_max = night.config.chemotaxis.v_max;
+    let limit = v_max * night.config.world.light(4);
+    let night_speed = max_speed(&mut night);
+    assert!(night_speed <= limit * 1.001, "{} above the night limit {}", night_speed, limit);
+
+    let day_speed = max_speed(&mut world(&gpu, cycle(0.0, 0.25), true));
+    assert!(day_speed > limit * 1.5 && day_speed <= v_max * 1.001, "daylight speed {}", day_speed);
+}
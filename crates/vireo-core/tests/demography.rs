@@ -0,0 +1,115 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+use vireo_core::{SimulationConfig, SpeciesDemography, SpeciesMutation};
+
+fn alive_by_kind(sim: &Simulation) -> [usize; 3] {
+    let mut counts = [0; 3];
+    for a in sim.agent_manager.agents.iter().filter(|a| a.is_alive()) {
+        counts[a.kind as usize] += 1;
+    }
+    counts
+}
+
+#[test]
+fn certain_death_removes_only_that_species() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut baseline = Simulation::new(&gpu, small_config(|config| config.agents.herbivores = 200));
+
+    let mut config = small_config(|config| config.agents.herbivores = 200);
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 1.0, birth: 0.5 };
+    let mut sim = Simulation::new(&gpu, config);
+
+    baseline.step(&gpu);
+    sim.step(&gpu);
+    baseline.sync_agents(&gpu);
+    sim.sync_agents(&gpu);
+
+    let (before, after) = (alive_by_kind(&baseline), alive_by_kind(&sim));
+    assert!(before[1] > 0);
+    assert_eq!(after, [before[0], 0, before[2]]);
+}
+
+#[test]
+fn births_fill_free_slots_and_split_parent_energy() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|config| config.agents.herbivores = 200);
+    // Twice the spawned herbivores' slots so births have room; other species stay full
+    config.agents.capacity.herbivores = 2 * config.agents.spawn_counts()[1];
+    let mut baseline = Simulation::new(&gpu, config.clone());
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 0.0, birth: 1.0 };
+    let mut sim = Simulation::new(&gpu, config);
+
+    for s in [&mut baseline, &mut sim] {
+        s.step(&gpu);
+        s.sync_agents(&gpu);
+    }
+
+    let (before, after) = (alive_by_kind(&baseline), alive_by_kind(&sim));
+    assert!(after[1] > before[1] + before[1] / 4, "{:?} -> {:?}", before, after);
+    assert_eq!((after[0], after[2]), (before[0], before[2]), "offspring keep the parent's kind");
+
+    // Parents hand over exactly half: total energy matches the birth-free run
+    let energy = |s: &Simulation| s.agent_manager.agents.iter().filter(|a| a.is_alive()).map(|a| a.energy as f64).sum::<f64>();
+    let (e0, e1) = (energy(&baseline), energy(&sim));
+    assert!((e0 - e1).abs() < 1e-3 * e0, "{} vs {}", e0, e1);
+}
+
+#[test]
+fn draws_repeat_for_a_seed() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|config| config.agents.herbivores = 200);
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 0.05, birth: 0.05 };
+    config.demography.predators = SpeciesDemography { death: 0.02, birth: 0.0 };
+
+    let run = |config: &SimulationConfig| {
+        let mut sim = Simulation::new(&gpu, config.clone());
+        for _ in 0..20 {
+            sim.step(&gpu);
+        }
+        sim.snapshot(&gpu).agents
+    };
+    let bytes = |agents: &[vireo_core::sim::Agent]| bytemuck::cast_slice::<_, u8>(agents).to_vec();
+
+    let a = run(&config);
+    assert_eq!(bytes(&a), bytes(&run(&config)));
+
+    config.world.seed += 1;
+    assert_ne!(bytes(&a), bytes(&run(&config)));
+}
+
+#[test]
+fn offspring_inherit_mutated_chemotaxis_traits() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|config| config.agents.herbivores = 200);
+    config.agents.capacity.herbivores = 2 * config.agents.spawn_counts()[1];
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 0.0, birth: 1.0 };
+    config.evolution.enabled = true;
+    config.evolution.herbivores = SpeciesMutation { chi: 0.2, kappa: 0.0 };
+    let spawned = config.agents.spawn_counts()[1] as usize;
+    let mut sim = Simulation::new(&gpu, config);
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    let alive: Vec<_> = sim.agent_manager.agents.iter().filter(|a| a.is_alive()).collect();
+    assert!(alive.iter().all(|a| a.kappa_gain == 1.0), "kappa does not mutate at 0");
+    assert!(alive.iter().filter(|a| a.kind != 1).all(|a| a.chi_gain == 1.0), "other species have no mutation");
+
+    // Spawned herbivores keep 1; each newborn draws log(chi_gain) ~ N(0, 0.2)
+    let newborns: Vec<f64> = alive.iter().filter(|a| a.kind == 1 && a.chi_gain != 1.0).map(|a| (a.chi_gain as f64).ln()).collect();
+    assert_eq!(newborns.len(), alive_by_kind(&sim)[1] - spawned);
+    let n = newborns.len() as f64;
+    let mean = newborns.iter().sum::<f64>() / n;
+    let sd = (newborns.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    assert!(mean.abs() < 0.05 && (0.15..0.25).contains(&sd), "log chi_gain mean {} sd {} over {} births", mean, sd, n);
+
+    // Newborns record their spawned parent's multipliers for the heritability regression
+    let born = alive.iter().filter(|a| a.parent_traits().is_some()).count();
+    assert_eq!(born, newborns.len());
+    assert!(alive.iter().filter_map(|a| a.parent_traits()).all(|p| p == (1.0, 1.0)));
+}
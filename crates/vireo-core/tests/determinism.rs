@@ -0,0 +1,80 @@
+//! GPU determinism suite, run with `cargo test -p vireo-core --features gpu-tests`
+
+mod common;
+
+use common::{gpu_or_skip, small_world};
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::SimulationConfig;
+
+const DEMO: &str = include_str!("../../../lab/configs/best-demo.yaml");
+const STEPS: u32 = 200;
+
+/// The demo world shrunk to 128×128 with blooms on, so every pass runs
+fn config() -> SimulationConfig {
+    small_world(vireo_core::parse_config(DEMO).expect("demo config is valid"), |config| {
+        config.world.size = [128, 128];
+        config.agents.herbivores = 2000;
+        config.blooms.enabled = true;
+    })
+}
+
+/// Binary snapshot of the field and agent buffers after `STEPS` steps
+fn run(gpu: &GpuDevice, config: SimulationConfig) -> (Simulation, Vec<u8>) {
+    let mut sim = Simulation::new(gpu, config);
+    for _ in 0..STEPS {
+        sim.step(gpu);
+    }
+    sim.sync_field(gpu);
+    sim.sync_agents(gpu);
+
+    let mut snapshot = bytemuck::cast_slice(&sim.field_manager.texels).to_vec();
+    snapshot.extend_from_slice(bytemuck::cast_slice(&sim.agent_manager.agents));
+    (sim, snapshot)
+}
+
+#[test]
+fn same_seed_gives_identical_snapshots() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let (_, first) = run(&gpu, config());
+    let (_, second) = run(&gpu, config());
+    assert!(first == second, "snapshots differ after {} steps", STEPS);
+
+    let mut reseeded = config();
+    reseeded.world.seed += 1;
+    let (_, third) = run(&gpu, reseeded);
+    assert!(first != third, "a different seed produced the same snapshot");
+}
+
+/// Summary statistics after `STEPS` steps, recorded on the Mesa llvmpipe adapter.
+/// Other drivers may round differently, so these are compared with a tolerance.
+const REFERENCE_MEAN_R: f32 = 0.15783;
+const REFERENCE_MEAN_W: f32 = 0.041909;
+const REFERENCE_MEAN_ENERGY: f32 = 1.5801;
+const REFERENCE_MEAN_SPEED: f32 = 0.22934;
+const REFERENCE_ALIVE: u32 = 6000;
+
+fn assert_close(name: &str, value: f32, reference: f32, rel: f32) {
+    assert!(
+        (value - reference).abs() <= rel * reference.abs(),
+        "{} = {} is more than {}% away from the reference {}",
+        name,
+        value,
+        rel * 100.0,
+        reference
+    );
+}
+
+#[test]
+fn summary_matches_reference_within_tolerance() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let (mut sim, _) = run(&gpu, config());
+    sim.field_manager.update_stats();
+    sim.agent_manager.update_stats();
+    let (field, agents) = (&sim.field_manager.stats, &sim.agent_manager.stats);
+
+    assert_close("mean R", field.mean_R, REFERENCE_MEAN_R, 0.01);
+    assert_close("mean W", field.mean_W, REFERENCE_MEAN_W, 0.01);
+    assert_close("mean energy", agents.mean_energy, REFERENCE_MEAN_ENERGY, 0.02);
+    assert_close("mean speed", agents.mean_velocity, REFERENCE_MEAN_SPEED, 0.05);
+    assert_eq!(agents.alive_count, REFERENCE_ALIVE);
+}
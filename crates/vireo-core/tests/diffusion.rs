@@ -0,0 +1,111 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{DiffusionTensorField, FieldData};
+use vireo_core::{parse_config, DiffusionConfig, DiffusionStream, SimulationConfig};
+
+/// A river along the diagonal of a 64×64 world, in still air
+fn river() -> DiffusionConfig {
+    DiffusionConfig {
+        enabled: true,
+        tensor: [1.0, 1.0, 0.0],
+        streams: vec![DiffusionStream { from: [0.0, 0.0], to: [64.0, 64.0], width: 4.0, along: 5.0, across: 1.0 }],
+    }
+}
+
+#[test]
+fn streams_turn_the_tensor_along_their_course() {
+    let field = DiffusionTensorField::new(&river(), [64, 64]);
+    assert_eq!(field.tensors().len(), 64 * 64);
+    let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-4);
+    assert!(close(field.at(31, 31), [3.0, 3.0, 2.0]), "{:?}", field.at(31, 31));
+    assert!(close(field.at(60, 2), [1.0, 1.0, 0.0]), "{:?}", field.at(60, 2));
+    let [dxx, dyy, dxy] = field.at(33, 31); // Off the course: partly blended
+    assert!(dxx > 1.0 && dxx < 3.0 && dxx == dyy && dxy > 0.0 && dxy < 2.0);
+
+    // Disabled diffusion is the identity everywhere
+    let plain = DiffusionTensorField::new(&DiffusionConfig { enabled: false, ..river() }, [64, 64]);
+    assert!(plain.tensors().iter().all(|&t| t == [1.0, 1.0, 0.0, 0.0]));
+
+    let text = "diffusion:\n  enabled: true\n  tensor: [2.0, 0.5, 0.25]\n  streams:\n    - { from: [0, 10], to: [64, 10], width: 3, along: 4, across: 0.5 }\n";
+    let diffusion = parse_config(&format!("{}{}", include_str!("../../../lab/configs/best-demo.yaml"), text)).unwrap().diffusion;
+    let config = SimulationConfig { diffusion, ..SimulationConfig::default() };
+    assert_eq!(config.diffusion.tensor, [2.0, 0.5, 0.25]);
+    assert_eq!(config.diffusion.streams[0].along, 4.0);
+    assert!(config.validate().is_ok());
+
+    let reject = |edit: fn(&mut SimulationConfig), field: &str| {
+        let mut bad = SimulationConfig { diffusion: river(), ..SimulationConfig::default() };
+        edit(&mut bad);
+        let error = bad.validate().unwrap_err().to_string();
+        assert!(error.contains(field), "{}", error);
+    };
+    reject(|c| c.diffusion.tensor = [1.0, 1.0, 1.0], "diffusion.tensor");
+    reject(|c| c.diffusion.tensor = [-1.0, 1.0, 0.0], "diffusion.tensor");
+    reject(|c| c.diffusion.streams[0].to = [0.0, 0.0], "diffusion.streams[0]");
+    reject(|c| c.diffusion.streams[0].across = 0.0, "diffusion.streams[0]");
+    reject(|c| c.streaming.enabled = true, "diffusion.enabled");
+}
+
+/// Mass, variances along x and y and covariance of R after spreading a point source
+fn spread(gpu: &GpuDevice, diffusion: DiffusionConfig) -> (f32, f32, f32, f32) {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.agents.herbivores = 1;
+    config.field.D_R = 0.1;
+    config.field.sigma_R = 0.0;
+    config.field.lambda_R = 0.0;
+    config.diffusion = diffusion;
+    let mut sim = Simulation::new(gpu, config);
+    for agent in &mut sim.agent_manager.agents {
+        agent.kill();
+    }
+    sim.upload_agents(gpu);
+
+    sim.field_manager.fill(FieldData::new(0.0, 0.0));
+    sim.field_manager.set(32, 32, FieldData::new(1.0, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    for _ in 0..60 {
+        sim.step(gpu);
+    }
+
+    sim.sync_field(gpu);
+    let cells: Vec<(f32, f32, f32)> = (0..64 * 64)
+        .map(|i| ((i % 64) as f32, (i / 64) as f32, sim.field_manager.cell(i as usize).R))
+        .collect();
+    let total: f32 = cells.iter().map(|c| c.2).sum();
+    let mx = cells.iter().map(|c| c.0 * c.2).sum::<f32>() / total;
+    let my = cells.iter().map(|c| c.1 * c.2).sum::<f32>() / total;
+    let moment = |f: &dyn Fn(&(f32, f32, f32)) -> f32| cells.iter().map(|c| f(c) * c.2).sum::<f32>() / total;
+    (
+        total,
+        moment(&|c| (c.0 - mx).powi(2)),
+        moment(&|c| (c.1 - my).powi(2)),
+        moment(&|c| (c.0 - mx) * (c.1 - my)),
+    )
+}
+
+#[test]
+fn the_tensor_stretches_a_plume_along_its_principal_axis() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let tensor = |tensor| DiffusionConfig { enabled: true, tensor, streams: Vec::new() };
+
+    // The fp16 field loses a little mass on its own, so runs compare against the plain one
+    let (plain_mass, plain_x, plain_y, plain_xy) = spread(&gpu, DiffusionConfig::default());
+    let (mass, x, y, xy) = spread(&gpu, tensor([1.0, 1.0, 0.0]));
+    assert!((mass / plain_mass - 1.0).abs() < 0.01, "mass {} vs {}", mass, plain_mass);
+    assert!((x / plain_x - 1.0).abs() < 0.02 && (y / plain_y - 1.0).abs() < 0.02, "{} {} vs {} {}", x, y, plain_x, plain_y);
+    assert!(xy.abs() < 0.05 && plain_xy.abs() < 0.05, "{} {}", xy, plain_xy);
+
+    // A wind along x: variance grows with Dxx and Dyy
+    let (mass, x, y, _) = spread(&gpu, tensor([2.0, 0.5, 0.0]));
+    assert!((mass / plain_mass - 1.0).abs() < 0.01, "mass {} vs {}", mass, plain_mass);
+    assert!(x > 3.0 * y, "along {} vs across {}", x, y);
+
+    // A diagonal wind: the plume leans along x = y, and R never goes negative
+    let (mass, x, y, xy) = spread(&gpu, tensor([1.0, 1.0, 0.8]));
+    assert!((mass / plain_mass - 1.0).abs() < 0.01, "mass {} vs {}", mass, plain_mass);
+    assert!(xy > 0.5 * x && xy > 0.5 * y, "covariance {} with variances {} {}", xy, x, y);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{Agent, FieldData};
+use vireo_core::SimulationConfig;
+
+/// 64x64 world with uniform R = `resource`, every agent at `energy`
+fn world(gpu: &GpuDevice, disperse_energy: f32, resource: f32, energy: f32) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.field.sigma_R = 0.0;
+    config.chemotaxis.disperse_energy = disperse_energy;
+    config.chemotaxis.settle_resource = 0.3;
+    config.chemotaxis.disperse_speed = 1.5;
+
+    let mut sim = Simulation::new(gpu, config);
+    for agent in &mut sim.agent_manager.agents {
+        agent.energy = energy;
+    }
+    sim.upload_agents(gpu);
+    sim.field_manager.fill(FieldData::new(resource, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim
+}
+
+fn alive(sim: &mut Simulation, gpu: &GpuDevice) -> Vec<Agent> {
+    sim.sync_agents(gpu);
+    sim.agent_manager.agents.iter().filter(|a| a.is_alive()).copied().collect()
+}
+
+fn speed(a: &Agent) -> f32 {
+    (a.vel[0] * a.vel[0] + a.vel[1] * a.vel[1]).sqrt()
+}
+
+#[test]
+fn starving_agents_disperse_ballistically() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = world(&gpu, 0.8, 0.0, 0.5);
+    let v_max = sim.config.chemotaxis.v_max;
+    for _ in 0..10 {
+        sim.step(&gpu);
+    }
+    let agents = alive(&mut sim, &gpu);
+    assert!(!agents.is_empty());
+    assert!(agents.iter().all(Agent::is_dispersing));
+
+    // Dispersers coast at 1.5 v_max; a wall bounce costs 30% for a single step
+    let fast = agents.iter().filter(|a| (speed(a) - 1.5 * v_max).abs() < 1e-3).count();
+    assert!(fast as f32 > 0.9 * agents.len() as f32, "{} of {} at dispersal speed", fast, agents.len());
+    assert!(agents.iter().all(|a| speed(a) <= 1.5 * v_max * 1.001));
+
+    sim.agent_manager.update_stats();
+    assert_eq!(sim.agent_manager.stats.dispersing_count, agents.len() as u32);
+}
+
+#[test]
+fn dispersers_settle_on_rich_ground() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = world(&gpu, 0.8, 0.0, 0.5);
+    sim.step(&gpu);
+    assert!(alive(&mut sim, &gpu).iter().all(Agent::is_dispersing));
+
+    sim.field_manager.fill(FieldData::new(0.5, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim.step(&gpu);
+    let agents = alive(&mut sim, &gpu);
+    assert!(agents.iter().all(|a| a.state == Agent::FORAGING));
+    assert!(agents.iter().all(|a| speed(a) <= sim.config.chemotaxis.v_max * 1.001));
+}
+
+#[test]
+fn well_fed_or_disabled_agents_keep_foraging() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    // Energy above the threshold
+    let mut fed = world(&gpu, 0.8, 0.0, 1.0);
+    // Starving, but dispersal is off
+    let mut disabled = world(&gpu, 0.0, 0.0, 0.5);
+    for sim in [&mut fed, &mut disabled] {
+        for _ in 0..3 {
+            sim.step(&gpu);
+        }
+        assert!(alive(sim, &gpu).iter().all(|a| a.state == Agent::FORAGING));
+    }
+}
@@ -0,0 +1,49 @@
+use glam::Vec2;
+use vireo_core::sim::{Agent, Diversity};
+use vireo_core::SimulationConfig;
+
+fn agent(kind: u32, x: f32, y: f32) -> Agent {
+    Agent::new(Vec2::new(x, y), 1.0, kind)
+}
+
+#[test]
+fn even_mixed_communities_score_highest() {
+    // Every species in each of the four quadrats, equally common
+    let mut agents: Vec<Agent> = [(10.0, 10.0), (50.0, 10.0), (10.0, 50.0), (50.0, 50.0)].iter()
+        .flat_map(|&(x, y)| (0..3).map(move |kind| agent(kind, x, y)))
+        .collect();
+    let mut dead = agent(2, 10.0, 10.0);
+    dead.kill();
+    agents.push(dead);
+
+    let d = Diversity::of(&agents, [64, 64], 2);
+    assert_eq!((d.richness, d.occupied_quadrats), (3, 4));
+    assert!((d.shannon - 3.0f32.ln()).abs() < 1e-6);
+    assert!((d.simpson - 2.0 / 3.0).abs() < 1e-6);
+    assert_eq!((d.mean_alpha, d.beta), (3.0, 1.0));
+
+    let lone = Diversity::of(&agents[..1], [64, 64], 2);
+    assert_eq!((lone.richness, lone.shannon, lone.simpson, lone.beta), (1, 0.0, 0.0, 1.0));
+    assert!(lone.shannon.is_sign_positive());
+    assert_eq!(Diversity::of(&[dead], [64, 64], 2), Diversity::default());
+}
+
+#[test]
+fn segregated_species_have_high_beta_diversity() {
+    // Plants on the left, herbivores on the right of a wide world; 9:1 abundance
+    let mut agents: Vec<Agent> = (0..9).map(|i| agent(0, 10.0 + i as f32, 30.0)).collect();
+    agents.push(agent(1, 120.0, 60.0));
+    let d = Diversity::of(&agents, [128, 64], 4);
+    assert_eq!((d.richness, d.occupied_quadrats, d.mean_alpha, d.beta), (2, 2, 1.0, 2.0));
+    let (p, q) = (0.9f32, 0.1f32);
+    assert!((d.shannon - -(p * p.ln() + q * q.ln())).abs() < 1e-6);
+    assert!((d.simpson - (1.0 - p * p - q * q)).abs() < 1e-6);
+
+    // The same agents in a single quadrat mix completely
+    assert_eq!(Diversity::of(&agents, [128, 64], 1).beta, 1.0);
+
+    let mut config = SimulationConfig::default();
+    config.output.quadrats = 0;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("output.quadrats"), "{}", error);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{FieldData, FieldManager};
+use vireo_core::{FieldChannel, SimulationConfig};
+
+/// `size` field with R = x + 10·y and W = 1
+fn ramp(size: [u32; 2]) -> FieldManager {
+    let mut field = FieldManager::new(size);
+    for y in 0..size[1] {
+        for x in 0..size[0] {
+            field.set(x, y, FieldData::new((x + 10 * y) as f32, 1.0));
+        }
+    }
+    field
+}
+
+#[test]
+fn blocks_are_box_averaged() {
+    let field = ramp([4, 4]);
+    let coarse = field.downsample(2);
+    assert_eq!(coarse.size, [2, 2]);
+    // Block (0, 0) covers R = 0, 1, 10, 11
+    assert_eq!(coarse.channel_f32(FieldChannel::Resource), [5.5, 7.5, 25.5, 27.5]);
+    assert_eq!(coarse.channel_f32(FieldChannel::Waste), [1.0; 4]);
+    assert_eq!(coarse.stats.mean_R, 16.5);
+
+    assert_eq!(field.downsample(1).texels, field.texels);
+    let whole = field.downsample(8);
+    assert_eq!((whole.size, whole.get_resource(0, 0)), ([1, 1], 16.5));
+}
+
+#[test]
+fn edge_blocks_average_only_the_cells_they_cover() {
+    let coarse = ramp([5, 3]).downsample(2);
+    assert_eq!(coarse.size, [3, 2]);
+    // Right column: x = 4 of rows 0-1; bottom row: y = 2 only
+    assert_eq!(coarse.get_resource(2, 0), 9.0);
+    assert_eq!(coarse.get_resource(0, 1), 20.5);
+    assert_eq!(coarse.get_resource(2, 1), 24.0);
+}
+
+#[test]
+fn gpu_mips_match_the_cpu_downsample() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [128, 64];
+    let mut sim = Simulation::new(&gpu, config);
+    sim.sync_field(&gpu);
+
+    assert_eq!(sim.resource_mip_levels(), 8);
+    for level in [0, 1, 3, 7] {
+        let (size, mip) = sim.resource_mip(&gpu, level);
+        let cpu = sim.field_manager.downsample(1 << level);
+        assert_eq!(size, cpu.size, "level {}", level);
+        for (i, (g, c)) in mip.iter().zip(cpu.channel_f32(FieldChannel::Resource)).enumerate() {
+            assert!((g - c).abs() <= 1e-3 * c.abs().max(1.0), "level {} cell {}: gpu {} cpu {}", level, i, g, c);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::gpu_or_skip;
+use glam::Vec2;
+use vireo_core::gpu::{EnergyProbe, GpuDevice, Simulation};
+use vireo_core::sim::{nearest_alive, Agent, EnergyBudget, EnergyRecord, FieldData};
+use vireo_core::SimulationConfig;
+
+fn probed_sim(gpu: &GpuDevice) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    config.field.sigma_R = 0.0;
+    let mut sim = Simulation::new(gpu, config);
+    sim.field_manager.fill(FieldData::new(0.5, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim
+}
+
+#[test]
+fn selected_agent_logs_a_budget_that_adds_up() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = probed_sim(&gpu);
+    sim.step(&gpu);
+    assert!(sim.read_energy_probe(&gpu).is_empty(), "nothing is logged without a selection");
+
+    let slot = 7;
+    sim.select_energy_probe(&gpu, Some(slot));
+    sim.sync_agents(&gpu);
+    let before = sim.agent_manager.agents[slot as usize].energy;
+    for _ in 0..10 {
+        sim.step(&gpu);
+    }
+    sim.sync_agents(&gpu);
+    let agent = sim.agent_manager.agents[slot as usize];
+    assert!(agent.is_alive());
+
+    let records = sim.read_energy_probe(&gpu);
+    assert_eq!(records.iter().map(|r| r.step).collect::<Vec<_>>(), (1..11).collect::<Vec<_>>());
+    let metabolism = sim.config.chemotaxis.eps0 * sim.config.world.dt;
+    assert!(records.iter().all(|r| (r.metabolism - metabolism).abs() < 1e-7 && r.movement == 0.0 && r.uptake > 0.0));
+    let budget = EnergyBudget::of(&records);
+    assert_eq!((budget.steps, budget.first_step, budget.last_step, budget.predation), (10, 1, 10, 0.0));
+    assert!((agent.energy - before - budget.net()).abs() < 1e-4, "{} -> {} but net {}", before, agent.energy, budget.net());
+
+    // The log keeps the latest steps once it wraps, and a new selection starts it over
+    for _ in 0..EnergyProbe::STEPS {
+        sim.step(&gpu);
+    }
+    let records = sim.read_energy_probe(&gpu);
+    assert_eq!(records.len(), EnergyProbe::STEPS as usize);
+    assert_eq!(records.last().unwrap().step, sim.current_step() - 1);
+    sim.select_energy_probe(&gpu, None);
+    sim.step(&gpu);
+    assert!(sim.read_energy_probe(&gpu).is_empty());
+    assert_eq!(sim.energy_probe_slot(), None);
+}
+
+#[test]
+fn budget_sums_records_and_picking_finds_the_nearest_alive_agent() {
+    let empty = EnergyRecord { step: EnergyRecord::EMPTY, uptake: 9.0, metabolism: 9.0, movement: 9.0 };
+    let records = [
+        EnergyRecord { step: 5, uptake: 1.0, metabolism: 0.25, movement: 0.5 },
+        empty,
+        EnergyRecord { step: 4, uptake: 0.5, metabolism: 0.25, movement: -0.25 },
+    ];
+    let budget = EnergyBudget::of(&records);
+    assert_eq!((budget.steps, budget.first_step, budget.last_step), (2, 4, 5));
+    assert_eq!((budget.uptake, budget.metabolism, budget.movement, budget.net()), (1.5, 0.5, 0.25, 0.75));
+    assert_eq!(EnergyBudget::of(&[empty]), EnergyBudget::default());
+
+    let mut agents = vec![Agent::new(Vec2::new(10.0, 10.0), 1.0, 1), Agent::new(Vec2::new(12.0, 10.0), 1.0, 1), Agent::new(Vec2::new(11.0, 10.5), 1.0, 1)];
+    agents[2].kill();
+    assert_eq!(nearest_alive(&agents, [11.2, 10.0], 5.0), Some(1));
+    assert_eq!(nearest_alive(&agents, [30.0, 30.0], 5.0), None);
+}
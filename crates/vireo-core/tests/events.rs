@@ -0,0 +1,77 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::EventMaps;
+use vireo_core::{SimulationConfig, SpeciesDemography};
+
+fn counted(config: &mut SimulationConfig) {
+    config.agents.herbivores = 200;
+    config.output.event_window = 10;
+}
+
+fn cell_of(pos: [f32; 2], size: [u32; 2]) -> usize {
+    let [x, y] = [0, 1].map(|i| (pos[i].max(0.0) as u32).min(size[i] - 1));
+    (y * size[0] + x) as usize
+}
+
+#[test]
+fn starvation_deaths_are_counted_where_agents_die() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(counted);
+    config.chemotaxis.eps0 = 1e4; // Nobody survives the first step
+    let mut sim = Simulation::new(&gpu, config);
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    let maps = sim.read_events(&gpu).unwrap();
+    let mut expected = vec![0; 64 * 64];
+    for a in sim.agent_manager.agents.iter().filter(|a| !a.is_alive()) {
+        expected[cell_of(a.pos, [64, 64])] += 1;
+    }
+    assert!(expected.iter().sum::<u32>() > 0);
+    assert_eq!(maps.starved, expected);
+    assert!(maps.births.iter().chain(&maps.random_deaths).all(|&n| n == 0));
+
+    sim.clear_events(&gpu);
+    assert!(sim.read_events(&gpu).unwrap().planes().iter().all(|&n| n == 0));
+}
+
+#[test]
+fn demography_births_and_deaths_match_the_slots_that_changed() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(counted);
+    config.agents.capacity.herbivores = 2 * config.agents.spawn_counts()[1];
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 0.2, birth: 0.5 };
+    let mut sim = Simulation::new(&gpu, config);
+    let before = sim.agent_manager.agents.clone();
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    // Starvation aside, an alive slot either survived, died, or died and was refilled
+    let maps = sim.read_events(&gpu).unwrap().summary();
+    let born = before.iter().zip(&sim.agent_manager.agents).filter(|(a, b)| b.is_alive() && (!a.is_alive() || b.age == 0)).count();
+    let died = before.iter().zip(&sim.agent_manager.agents).filter(|(a, b)| a.is_alive() && (!b.is_alive() || b.age == 0)).count();
+    assert!(born > 0 && died > 0);
+    assert_eq!((maps.births, maps.random_deaths + maps.starved), (born as u64, died as u64));
+}
+
+#[test]
+fn summary_reports_totals_and_death_concentration() {
+    let mut planes = vec![0; 3 * 200];
+    planes[5] = 4;              // Births
+    planes[200 + 7] = 6;        // Starved, all in one cell
+    planes[400 + 7] = 2;        // Random deaths in the same cell
+    planes[400 + 150] = 2;      // ...and one more cell
+    let maps = EventMaps::from_planes([20, 10], planes.clone());
+    assert_eq!(maps.planes(), planes);
+
+    let s = maps.summary();
+    assert_eq!((s.births, s.starved, s.random_deaths, s.death_cells), (4, 6, 4, 2));
+    // 2 of 200 cells are the busiest 1%, and they hold every death
+    assert_eq!(s.death_hotspot_share, 1.0);
+
+    let spread = EventMaps::from_planes([20, 10], [vec![0; 200], vec![1; 200], vec![0; 200]].concat());
+    assert_eq!(spread.summary().death_hotspot_share, 0.01);
+}
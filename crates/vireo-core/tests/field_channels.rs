@@ -0,0 +1,91 @@
+mod common;
+
+use common::gpu_or_skip;
+use half::f16;
+use vireo_core::gpu::{Simulation, TexelRows};
+use vireo_core::sim::fields::FieldManager;
+use vireo_core::{FieldChannel, SimulationConfig};
+
+#[test]
+fn channel_registry_packs_four_per_layer() {
+    for (i, channel) in FieldChannel::ALL.iter().enumerate() {
+        assert_eq!(channel.index() as usize, i);
+        assert_eq!(channel.layer(), channel.index() / 4);
+        assert_eq!(channel.component(), i % 4);
+    }
+    assert_eq!(FieldChannel::LAYERS, FieldChannel::COUNT.div_ceil(4));
+}
+
+#[test]
+fn rgba16f_round_trip_preserves_channels() {
+    let mut fm = FieldManager::new([32, 32]);
+    fm.seed_resources(7);
+    for i in 0..fm.len() {
+        fm.set_channel(i, FieldChannel::Waste, (i % 17) as f32 * 0.125);
+    }
+    
+    let packed = fm.as_rgba16f();
+    assert_eq!(packed.len(), 32 * 32 * 4 * FieldChannel::LAYERS as usize);
+    
+    let mut restored = FieldManager::new([32, 32]);
+    let err = restored.from_rgba16f(&packed[1..]).unwrap_err();
+    assert!(err.contains("32x32"), "{}", err);
+    restored.from_rgba16f(packed).unwrap();
+    for (a, b) in fm.cells().iter().zip(&restored.cells()) {
+        for channel in FieldChannel::ALL {
+            // f16 keeps ~3 significant digits
+            assert!((a.get(channel) - b.get(channel)).abs() <= a.get(channel).abs() * 1e-3 + 1e-4);
+        }
+    }
+}
+
+#[test]
+fn mirror_is_packed_and_bulk_routines_agree_with_cells() {
+    let mut fm = FieldManager::new([64, 32]);
+    assert_eq!(std::mem::size_of_val(fm.as_rgba16f()), 64 * 32 * 8 * FieldChannel::LAYERS as usize);
+
+    let resource: Vec<f32> = (0..fm.len()).map(|i| i as f32 / 256.0).collect();
+    fm.set_channel_f32(FieldChannel::Resource, &resource);
+    fm.set_waste(3, 1, 0.75);
+    assert_eq!(fm.channel_f32(FieldChannel::Resource), resource, "multiples of 1/256 below 8 are exact in f16");
+
+    let cells = fm.cells();
+    assert_eq!((cells[64 + 3].R, cells[64 + 3].W), (fm.get_resource(3, 1), 0.75));
+    let mut copy = FieldManager::new([64, 32]);
+    copy.set_cells(&cells);
+    assert_eq!(copy.as_rgba16f(), fm.as_rgba16f());
+}
+
+#[test]
+fn texel_rows_pad_to_the_copy_alignment() {
+    for (width, stride) in [(32, 256), (100, 1024), (129, 1280)] {
+        let rows = TexelRows::new([width, 3], 2);
+        assert_eq!((rows.bytes_per_row(), rows.padded_bytes_per_row()), (width * 8, stride));
+
+        let texels: Vec<f16> = (0..rows.texel_len()).map(|i| f16::from_f32((i % 1000) as f32)).collect();
+        let padded = rows.pad(&texels).unwrap();
+        assert_eq!(padded.len(), stride as usize * 6);
+        assert_eq!(rows.unpad(&padded).unwrap(), texels);
+
+        assert!(rows.pad(&texels[1..]).is_err());
+        assert!(rows.unpad(&padded[..padded.len() - 8]).is_err());
+    }
+}
+
+#[test]
+fn fields_of_unaligned_widths_survive_upload_and_readback() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    for width in [100, 129] {
+        let mut config = SimulationConfig::default();
+        config.world.size = [width, 64];
+        config.agents.herbivores = 10;
+        let mut sim = Simulation::new(&gpu, config);
+        let resource: Vec<f32> = (0..sim.field_manager.len()).map(|i| (i % 200) as f32 / 256.0).collect();
+        sim.field_manager.set_channel_f32(FieldChannel::Resource, &resource);
+        sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+
+        sim.field_manager.set_channel_f32(FieldChannel::Resource, &vec![0.0; resource.len()]);
+        sim.sync_field(&gpu);
+        assert_eq!(sim.field_manager.channel_f32(FieldChannel::Resource), resource, "width {}", width);
+    }
+}
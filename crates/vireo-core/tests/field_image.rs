@@ -0,0 +1,48 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{ImageKind, Simulation};
+use vireo_core::SimulationConfig;
+
+/// 8-bit level of `v` in [0, 1], truncated like the old CPU writer
+fn level(v: f32) -> i32 {
+    (v * 255.0).clamp(0.0, 255.0) as i32
+}
+
+#[test]
+fn gpu_images_match_the_cpu_colour_map() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [96, 64]; // Rows of 384 bytes, padded to 512 for the copy
+    config.agents.herbivores = 400;
+    let mut sim = Simulation::new(&gpu, config);
+    for _ in 0..5 {
+        sim.step(&gpu);
+    }
+
+    let image = sim.render_image(&gpu, ImageKind::Field);
+    assert_eq!(image.len(), 96 * 64 * 4);
+    sim.sync_field(&gpu);
+    let field = &sim.field_manager;
+    let (min, max) = (0..64).flat_map(|y| (0..96).map(move |x| (x, y)))
+        .map(|(x, y)| field.get(x, y).R)
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), r| (lo.min(r), hi.max(r)));
+    assert!(max > min, "a stepped field is not flat");
+    for (i, texel) in image.chunks_exact(4).enumerate() {
+        let cell = field.get(i as u32 % 96, i as u32 / 96);
+        let expected = [level((cell.R - min) / (max - min)), level(cell.W), level(cell.soil), 255];
+        for (got, want) in texel.iter().zip(expected) {
+            assert!((*got as i32 - want).abs() <= 1, "cell {}: {:?} vs {:?}", i, texel, expected);
+        }
+    }
+
+    let occupancy = sim.read_occupancy(&gpu);
+    let busiest = *occupancy.iter().max().unwrap() as f32;
+    assert!(busiest > 0.0);
+    let image = sim.render_image(&gpu, ImageKind::Occupancy);
+    for (texel, &count) in image.chunks_exact(4).zip(&occupancy) {
+        let grey = level(count as f32 / busiest);
+        assert!((texel[0] as i32 - grey).abs() <= 1, "{:?} vs {}", texel, grey);
+        assert_eq!((texel[0], texel[1], texel[3]), (texel[2], texel[0], 255));
+    }
+}
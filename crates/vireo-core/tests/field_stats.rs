@@ -0,0 +1,47 @@
+use vireo_core::sim::{FieldData, FieldManager};
+
+/// `size` field with R = x + 10·y and W = 2
+fn ramp(size: [u32; 2]) -> FieldManager {
+    let mut field = FieldManager::new(size);
+    for y in 0..size[1] {
+        for x in 0..size[0] {
+            field.set(x, y, FieldData::new((x + 10 * y) as f32, 2.0));
+        }
+    }
+    field.update_stats();
+    field
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() <= 1e-5 * b.abs().max(1.0)
+}
+
+#[test]
+fn stats_of_a_ramp_match_closed_form() {
+    // 11 columns leave a remainder after the 8-wide lanes
+    let s = ramp([11, 3]).stats;
+    assert!(close(s.mean_R, 15.0), "{:?}", s);
+    // var(x) + 100·var(y) = 10 + 200/3
+    assert!(close(s.var_R, 10.0 + 200.0 / 3.0), "{:?}", s);
+    // 9 interior cells with |∇R| = √(1² + 10²)
+    assert!(close(s.mean_grad_R, 9.0 * 101f32.sqrt() / 33.0), "{:?}", s);
+    assert_eq!([s.min_R, s.max_R], [0.0, 30.0]);
+    assert_eq!([s.mean_W, s.var_W, s.min_W, s.max_W], [2.0, 0.0, 2.0, 2.0]);
+}
+
+#[test]
+fn stats_span_band_boundaries() {
+    // 40 rows cross several reduction bands
+    let s = ramp([3, 40]).stats;
+    assert!(close(s.mean_R, 1.0 + 195.0), "{:?}", s);
+    assert!(close(s.mean_grad_R, 38.0 * 101f32.sqrt() / 120.0), "{:?}", s);
+    assert_eq!([s.min_R, s.max_R], [0.0, 392.0]);
+}
+
+#[test]
+fn single_row_has_no_gradient() {
+    let s = ramp([17, 1]).stats;
+    assert!(close(s.mean_R, 8.0), "{:?}", s);
+    assert_eq!(s.mean_grad_R, 0.0);
+    assert_eq!([s.min_R, s.max_R], [0.0, 16.0]);
+}
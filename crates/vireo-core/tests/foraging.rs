@@ -0,0 +1,63 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{AgentManager, FieldData, SimRng};
+use vireo_core::SimulationConfig;
+
+#[test]
+fn tally_matches_energy_gained_and_distance_moved() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    config.field.sigma_R = 0.0;
+    config.chemotaxis.eps0 = 0.0;
+
+    let mut sim = Simulation::new(&gpu, config);
+    sim.field_manager.fill(FieldData::new(0.5, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    let before = sim.agent_manager.agents.clone();
+
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+    let gain = sim.config.chemotaxis.eta_R * 0.5 * sim.config.world.dt;
+    for ((a, b), f) in before.iter().zip(&sim.agent_manager.agents).zip(&sim.agent_manager.forage) {
+        // Nothing drains energy, so every unit gained came from the resource
+        assert!((f[0] - gain).abs() < 1e-4 * gain.max(1.0), "consumed {} vs {}", f[0], gain);
+        assert!((b.energy - a.energy - f[0]).abs() < 1e-4, "energy {} -> {} with {} consumed", a.energy, b.energy, f[0]);
+        let moved = ((b.pos[0] - a.pos[0]).powi(2) + (b.pos[1] - a.pos[1]).powi(2)).sqrt();
+        assert!((f[1] - moved).abs() < 1e-4, "moved {} but tallied {}", moved, f[1]);
+    }
+
+    // The efficiency covers the steps since the previous update
+    sim.agent_manager.update_stats();
+    let stats = sim.agent_manager.stats.clone();
+    assert!((stats.foraging_efficiency as f64 - stats.consumed / stats.distance).abs() < 1e-4);
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+    sim.agent_manager.update_stats();
+    let next = &sim.agent_manager.stats;
+    let window = (next.consumed - stats.consumed) / (next.distance - stats.distance);
+    assert!((next.foraging_efficiency as f64 - window).abs() < 1e-4, "{} vs {}", next.foraging_efficiency, window);
+
+    // A restore starts the tally over
+    let snapshot = sim.snapshot(&gpu);
+    sim.restore(&gpu, &snapshot).unwrap();
+    sim.sync_agents(&gpu);
+    assert!(sim.agent_manager.forage.iter().all(|f| *f == [0.0; 2]));
+}
+
+#[test]
+fn efficiency_is_consumption_per_distance_since_the_last_update() {
+    let mut agents = AgentManager::with_rng(10, [64.0, 64.0], 1.0, &mut SimRng::new(Default::default(), 1));
+    agents.update_stats();
+    assert_eq!(agents.stats.foraging_efficiency, 0.0);
+
+    agents.forage[0] = [3.0, 2.0];
+    agents.forage[1] = [1.0, 2.0];
+    agents.update_stats();
+    assert_eq!(agents.stats.foraging_efficiency, 1.0);
+    agents.update_stats();
+    assert_eq!(agents.stats.foraging_efficiency, 0.0);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::gpu_or_skip;
+use std::path::PathBuf;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::ParamForcing;
+use vireo_core::{ForcingConfig, ForcingMode, LoadError, SimulationConfig};
+
+const DEMO: &str = include_str!("../../../lab/configs/best-demo.yaml");
+
+/// A config file forcing `field.sigma_R` from `rain.csv`, both in a fresh directory
+fn write_scenario(name: &str, csv: &str, forcing: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vireo-forcing-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("rain.csv"), csv).unwrap();
+    let path = dir.join("config.yaml");
+    std::fs::write(&path, format!("{}\nforcing:\n{}", DEMO, forcing)).unwrap();
+    path
+}
+
+fn forced(mode: ForcingMode, points: &[[f32; 2]]) -> SimulationConfig {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    config.forcing = vec![ForcingConfig {
+        param: "field.sigma_R".into(),
+        file: "rain.csv".into(),
+        step_column: "step".into(),
+        value_column: "rain".into(),
+        mode,
+        points: points.to_vec(),
+    }];
+    config
+}
+
+#[test]
+fn load_config_reads_the_series_next_to_the_config() {
+    let forcing = "  - {param: field.sigma_R, file: rain.csv, value_column: rain}\n";
+    let path = write_scenario("load", "day,step,rain\n1,0,0.5\n2,100,1.5\n3,300,1.0\n", forcing);
+    let config = vireo_core::load_config(&path).unwrap();
+    let f = &config.forcing[0];
+    assert_eq!((f.mode, f.step_column.as_str()), (ForcingMode::Scale, "step"));
+    assert_eq!(f.file, path.parent().unwrap().join("rain.csv"));
+    assert_eq!(f.points, [[0.0, 0.5], [100.0, 1.5], [300.0, 1.0]]);
+
+    // Linear between samples, held at both ends
+    assert_eq!(f.value_at(50), Some(1.0));
+    assert_eq!(f.value_at(200), Some(1.25));
+    assert_eq!(f.value_at(1000), Some(1.0));
+    let p = ParamForcing::new(&config).unwrap();
+    assert_eq!(p.values_at(50), [config.field.sigma_R]);
+
+    // Parsing without a file leaves the series empty, so nothing is forced
+    let text = format!("{}\nforcing:\n{}", DEMO, forcing);
+    assert!(ParamForcing::new(&vireo_core::parse_config(&text).unwrap()).is_none());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn bad_series_are_rejected() {
+    let forcing = "  - {param: field.sigma_R, file: rain.csv, value_column: rainfall}\n";
+    let path = write_scenario("column", "step,rain\n0,1\n", forcing);
+    let err = vireo_core::load_config(&path).unwrap_err();
+    assert!(matches!(err, LoadError::Forcing { .. }) && err.to_string().contains("`rainfall`"), "{}", err);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    let forcing = "  - {param: field.sigma_R, file: rain.csv, value_column: rain, mode: set}\n";
+    let path = write_scenario("negative", "step,rain\n0,0.1\n10,-0.1\n", forcing);
+    let err = vireo_core::load_config(&path).unwrap_err().to_string();
+    assert!(err.contains("forcing[0].file") && err.contains("field.sigma_R"), "{}", err);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    let reason = |config: SimulationConfig| config.validate().unwrap_err().to_string();
+    assert!(reason(forced(ForcingMode::Scale, &[[10.0, 1.0], [10.0, 2.0]])).contains("must increase"));
+    let mut config = forced(ForcingMode::Scale, &[]);
+    config.forcing[0].param = "world.dt".into();
+    assert!(reason(config).contains("forcing[0].param"));
+    let mut config = forced(ForcingMode::Scale, &[]);
+    config.forcing[0].file = "rain.parquet".into();
+    assert!(reason(config).contains("Parquet"));
+    let mut config = forced(ForcingMode::Scale, &[]);
+    config.forcing.push(config.forcing[0].clone());
+    assert!(reason(config).contains("forcing[1].param"));
+}
+
+#[test]
+fn simulation_applies_the_series_value_of_each_step() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let config = forced(ForcingMode::Scale, &[[0.0, 1.0], [10.0, 3.0]]);
+    let base = config.field.sigma_R;
+    let mut sim = Simulation::new(&gpu, config);
+    for _ in 0..6 {
+        sim.step(&gpu);
+    }
+    // The last step run was step 5, halfway along the ramp, scaling from the configured value
+    assert_eq!((sim.config.field.sigma_R, sim.rd_params.sigma_R), (base * 2.0, base * 2.0));
+
+    let mut sim = Simulation::new(&gpu, forced(ForcingMode::Set, &[[0.0, 0.02], [4.0, 0.04]]));
+    for _ in 0..20 {
+        sim.step(&gpu);
+    }
+    assert_eq!(sim.rd_params.sigma_R, 0.04);
+}
@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+use glam::Vec2;
+use vireo_core::sim::{Agent, FieldStats, GuardTrip, RunGuard};
+use vireo_core::{GuardConfig, SimulationConfig};
+
+fn field(mean_r: f32) -> FieldStats {
+    FieldStats { mean_R: mean_r, mean_W: 0.1, ..FieldStats::default() }
+}
+
+#[test]
+fn guards_trip_on_non_finite_state_and_resource_blowup() {
+    let agents = vec![Agent::new(Vec2::new(10.0, 10.0), 1.0, 1); 3];
+    let now = Instant::now();
+
+    // Defaults only catch NaN and infinities
+    let mut guard = RunGuard::new(&GuardConfig::default());
+    assert!(guard.needs_field());
+    assert_eq!(guard.check(10, now, Some(&field(1.0e4)), &agents), None);
+    assert_eq!(guard.check(20, now, Some(&field(f32::NAN)), &agents).map(|t| t.to_string()), Some("mean R is NaN".to_string()));
+    let mut blown = agents.clone();
+    blown[1].energy = f32::INFINITY;
+    blown[2].energy = f32::NAN;
+    blown[2].kill(); // Dead slots are not checked
+    assert_eq!(guard.check(30, now, None, &blown), Some(GuardTrip::NonFiniteEnergy { count: 1 }));
+
+    let config = GuardConfig { non_finite: false, max_mean_R: 5.0, max_step_ms: 0.0 };
+    let mut bounded = RunGuard::new(&config);
+    assert_eq!(bounded.check(10, now, Some(&field(f32::NAN)), &blown), None);
+    assert_eq!(bounded.check(20, now, Some(&field(5.0)), &agents), None);
+    assert_eq!(bounded.check(30, now, Some(&field(5.5)), &agents), Some(GuardTrip::ResourceBound { mean_R: 5.5, bound: 5.0 }));
+    assert_eq!(bounded.check(40, now, None, &agents), None);
+
+    let off = GuardConfig { non_finite: false, max_mean_R: 0.0, max_step_ms: 100.0 };
+    assert!(!RunGuard::new(&off).needs_field());
+}
+
+#[test]
+fn slow_steps_are_averaged_between_checks() {
+    let config = GuardConfig { non_finite: true, max_mean_R: 0.0, max_step_ms: 20.0 };
+    let mut guard = RunGuard::new(&config);
+    let t0 = Instant::now();
+    guard.start(0, t0);
+
+    // 50 steps in 0.9 s, then 50 in 1.5 s
+    assert_eq!(guard.check(50, t0 + Duration::from_millis(900), Some(&field(1.0)), &[]), None);
+    match guard.check(100, t0 + Duration::from_millis(2400), Some(&field(1.0)), &[]) {
+        Some(GuardTrip::SlowSteps { step_ms, limit }) => assert!((step_ms - 30.0).abs() < 1e-3 && limit == 20.0, "{} ms", step_ms),
+        other => panic!("expected a slow-step trip, got {:?}", other),
+    }
+
+    // Restarting the clock (after a device recovery) forgets the time in between
+    guard.start(50, t0 + Duration::from_secs(60));
+    assert_eq!(guard.check(100, t0 + Duration::from_secs(61), None, &[]), None);
+
+    let mut bad = SimulationConfig::default();
+    bad.guards.max_step_ms = -1.0;
+    let error = bad.validate().unwrap_err();
+    assert!(error.to_string().contains("guards.max_step_ms"), "{}", error);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::CullRegion;
+use vireo_core::SimulationConfig;
+
+fn compiled(sim: &Simulation) -> Vec<&'static str> {
+    sim.pipelines.compile_times().into_iter().map(|(name, _)| name).collect()
+}
+
+#[test]
+fn optional_pipelines_compile_on_first_use() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+
+    // Only the per-step passes are built up front
+    let mut sim = Simulation::new(&gpu, config.clone());
+    assert_eq!(compiled(&sim), ["rd", "agent", "clear_occupancy", "bloom"]);
+    sim.step(&gpu);
+    assert!(sim.pipelines.demography_pipeline.compile_time().is_none());
+    assert!(!compiled(&sim).contains(&"cull"));
+
+    sim.cull(&gpu, CullRegion::Circle { center: [32.0, 32.0], radius: 8.0 });
+    sim.cull(&gpu, CullRegion::Circle { center: [16.0, 16.0], radius: 8.0 });
+    assert_eq!(compiled(&sim).iter().filter(|&&n| n == "cull").count(), 1, "compiled once, reused after");
+
+    config.demography.enabled = true;
+    let mut sim = Simulation::new(&gpu, config);
+    assert!(sim.pipelines.demography_pipeline.compile_time().is_none());
+    sim.step(&gpu);
+    assert!(sim.pipelines.demography_pipeline.compile_time().is_some());
+    assert_eq!(sim.pipelines.demography_pipeline.name(), "demography");
+}
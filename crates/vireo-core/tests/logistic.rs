@@ -0,0 +1,79 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{CullRegion, FieldData, MovementStats};
+use vireo_core::SimulationConfig;
+
+/// Agent-free world with a uniform initial resource level `r0`
+fn empty_world(gpu: &GpuDevice, r0: f32) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.agents.herbivores = 0;
+    config.field.D_R = 0.1;
+    config.field.D_W = 0.1;
+    config.field.sigma_R = 0.2;
+    config.field.lambda_R = 0.02;
+    config.field.K_R = 1.5;
+
+    let mut sim = Simulation::new(gpu, config);
+    sim.field_manager.fill(FieldData::new(r0, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim
+}
+
+fn resource_range(sim: &mut Simulation, gpu: &GpuDevice) -> (f32, f32) {
+    sim.sync_field(gpu);
+    sim.field_manager.cells().iter().fold((f32::MAX, f32::MIN), |(lo, hi), d| (lo.min(d.R), hi.max(d.R)))
+}
+
+#[test]
+fn agent_free_resource_saturates_below_capacity() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = empty_world(&gpu, 0.1);
+    for _ in 0..300 {
+        sim.step(&gpu);
+    }
+
+    // Logistic equilibrium K_R (1 - lambda_R / sigma_R) = 1.5 * 0.9
+    let (lo, hi) = resource_range(&mut sim, &gpu);
+    assert!((lo - 1.35).abs() < 0.01 && (hi - 1.35).abs() < 0.01, "R in [{}, {}]", lo, hi);
+
+    // Overfull cells decay back to the same level
+    let mut sim = empty_world(&gpu, 3.0);
+    for _ in 0..300 {
+        sim.step(&gpu);
+    }
+    let (lo, hi) = resource_range(&mut sim, &gpu);
+    assert!((lo - 1.35).abs() < 0.01 && (hi - 1.35).abs() < 0.01, "R in [{}, {}]", lo, hi);
+}
+
+#[test]
+fn empty_cells_do_not_grow() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = empty_world(&gpu, 0.0);
+    for _ in 0..50 {
+        sim.step(&gpu);
+    }
+    assert_eq!(resource_range(&mut sim, &gpu), (0.0, 0.0));
+}
+
+#[test]
+fn worlds_without_agent_slots_skip_the_agent_passes() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = empty_world(&gpu, 0.5);
+    assert!(sim.agents_buffer().is_none());
+
+    sim.cull(&gpu, CullRegion::Circle { center: [32.0, 32.0], radius: 10.0 });
+    for _ in 0..10 {
+        sim.step(&gpu);
+    }
+    assert_eq!(sim.movement_stats(&gpu), MovementStats::default());
+    assert!(sim.read_occupancy(&gpu).iter().all(|&n| n == 0));
+
+    let snapshot = sim.snapshot(&gpu);
+    assert!(snapshot.agents.is_empty());
+    assert!(snapshot.field.iter().all(|d| d.R > 0.5), "resource grows on its own");
+    sim.restore(&gpu, &snapshot).unwrap();
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+
+fn positions(sim: &Simulation) -> Vec<[f32; 2]> {
+    sim.agent_manager.agents.iter().map(|a| a.pos).collect()
+}
+
+#[test]
+fn memoryless_agents_only_record_their_best_site() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|_| {});
+    let mut a = Simulation::new(&gpu, config.clone());
+    config.chemotaxis.memory_decay = 0.5;
+    let mut b = Simulation::new(&gpu, config);
+
+    for sim in [&mut a, &mut b] {
+        for _ in 0..10 {
+            sim.step(&gpu);
+        }
+        sim.sync_agents(&gpu);
+    }
+
+    // With memory_weight = 0 the remembered site never feeds back into movement
+    assert_eq!(positions(&a), positions(&b));
+    let recorded = a.agent_manager.agents.iter().filter(|a| a.is_alive() && a.memory_value > 0.0).count();
+    assert!(recorded > 0);
+    for agent in a.agent_manager.agents.iter().filter(|a| a.is_alive()) {
+        assert!(agent.memory_value.is_finite() && agent.memory_value >= 0.0, "{}", agent.memory_value);
+    }
+}
+
+#[test]
+fn homing_pulls_agents_back_to_the_remembered_site() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|_| {});
+    config.chemotaxis.chi_R = 0.0;
+    config.chemotaxis.chi_W = 0.0;
+    config.chemotaxis.memory_decay = 0.0;
+    config.agents.E0 = 100.0;
+    let site = [32.0, 32.0];
+
+    let run = |weight: f32| {
+        let mut config = config.clone();
+        config.chemotaxis.memory_weight = weight;
+        let mut sim = Simulation::new(&gpu, config);
+        // A remembered value above any resource level keeps the site fixed
+        for agent in sim.agent_manager.agents.iter_mut() {
+            agent.memory_pos = site;
+            agent.memory_value = 10.0;
+        }
+        sim.upload_agents(&gpu);
+        for _ in 0..200 {
+            sim.step(&gpu);
+        }
+        sim.sync_agents(&gpu);
+        let alive: Vec<_> = sim.agent_manager.agents.iter().filter(|a| a.is_alive()).copied().collect();
+        let mean_distance = alive.iter()
+            .map(|a| ((a.pos[0] - site[0]).powi(2) + (a.pos[1] - site[1]).powi(2)).sqrt())
+            .sum::<f32>() / alive.len() as f32;
+        (mean_distance, alive)
+    };
+
+    let (memoryless, _) = run(0.0);
+    let (homing, agents) = run(5.0);
+    assert!(homing < 0.5 * memoryless, "{} vs {}", homing, memoryless);
+    assert!(agents.iter().all(|a| a.memory_pos == site && a.memory_value == 10.0));
+}
+
+#[test]
+fn remembered_value_decays_per_time_unit() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|_| {});
+    config.chemotaxis.memory_decay = 0.5;
+    let mut sim = Simulation::new(&gpu, config);
+    for agent in sim.agent_manager.agents.iter_mut() {
+        agent.memory_value = 10.0;
+    }
+    sim.upload_agents(&gpu);
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    // 10 × (1 − 0.5 × dt) with dt = 0.1
+    for agent in sim.agent_manager.agents.iter().filter(|a| a.is_alive()) {
+        assert!((agent.memory_value - 9.5).abs() < 1e-5, "{}", agent.memory_value);
+    }
+}
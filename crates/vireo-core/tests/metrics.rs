@@ -0,0 +1,90 @@
+use std::time::Duration;
+use vireo_core::sim::{
+    AgentColumns, AgentStats, BehaviorColumns, ClockColumns, FieldStats, MetricProvider, MetricValue, MetricsRegistry,
+    MetricsSample, MovementStats, SpeciesColumns, SpeciesStats, TimingColumns,
+};
+use vireo_core::TimeUniform;
+
+/// Downstream observer: running count of sampled rows
+#[derive(Default)]
+struct RowCounter {
+    rows: u32,
+}
+
+impl MetricProvider for RowCounter {
+    fn columns(&self) -> Vec<String> {
+        vec!["rows_seen".to_string()]
+    }
+
+    fn record(&mut self, _sample: &MetricsSample, row: &mut Vec<MetricValue>) {
+        self.rows += 1;
+        row.push(self.rows.into());
+    }
+}
+
+fn sample<'a>(time: &'a TimeUniform, field: &'a FieldStats, agents: &'a AgentStats, movement: &'a MovementStats, species: &'a [SpeciesStats]) -> MetricsSample<'a> {
+    MetricsSample { step: 40, time, field, agents, movement, species, step_time: Duration::from_millis(8), events: None, sampling: None, diversity: None, traits: None }
+}
+
+#[test]
+fn header_and_rows_follow_registration_order() {
+    let mut registry = MetricsRegistry::new();
+    registry.register(ClockColumns).unwrap();
+    registry.register(RowCounter::default()).unwrap();
+    registry.register(AgentColumns).unwrap();
+    registry.register(TimingColumns).unwrap();
+    assert_eq!(registry.columns(), [
+        "step", "day_phase", "light", "rows_seen",
+        "alive_count", "total_energy", "mean_energy", "mean_velocity", "foraging_efficiency",
+        "wall_time_ms", "fps_proxy",
+    ]);
+
+    let time = TimeUniform { step: 40, time: 4.0, day_phase: 0.5, light: 1.0 };
+    let agents = AgentStats { alive_count: 6000, mean_energy: 0.75, ..Default::default() };
+    let (field, movement) = (FieldStats::default(), MovementStats::default());
+    let s = sample(&time, &field, &agents, &movement, &[]);
+
+    let text = |row: Vec<MetricValue>| row.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    assert_eq!(text(registry.row(&s)), ["40", "0.5", "1", "1", "6000", "0", "0.75", "0", "0", "8", "125"]);
+    // Providers keep their state across rows
+    assert_eq!(text(registry.row(&s))[3], "2");
+}
+
+#[test]
+fn species_columns_cover_every_species() {
+    let mut registry = MetricsRegistry::new();
+    registry.register(SpeciesColumns).unwrap();
+    assert_eq!(registry.columns().len(), 12);
+    assert_eq!(&registry.columns()[4..8], ["alive_herbivores", "mean_energy_herbivores", "births_herbivores", "deaths_herbivores"]);
+
+    let species: Vec<SpeciesStats> = (0..3).map(|kind| SpeciesStats { kind, alive: 10 * kind, ..Default::default() }).collect();
+    let time = TimeUniform { step: 0, time: 0.0, day_phase: 0.5, light: 1.0 };
+    let (field, agents, movement) = (FieldStats::default(), AgentStats::default(), MovementStats::default());
+    let row = registry.row(&sample(&time, &field, &agents, &movement, &species));
+    assert_eq!([row[0], row[4], row[8]], [0.0f32, 10.0, 20.0].map(MetricValue::from));
+}
+
+#[test]
+fn duplicate_columns_are_rejected() {
+    let mut registry = MetricsRegistry::new();
+    registry.register(ClockColumns).unwrap();
+    let error = registry.register(ClockColumns).unwrap_err();
+    assert!(error.contains("`step`"), "{}", error);
+    assert_eq!(registry.columns().len(), 3);
+}
+
+#[test]
+fn behavior_columns_are_shares_of_the_living() {
+    let mut registry = MetricsRegistry::new();
+    registry.register(BehaviorColumns).unwrap();
+    assert_eq!(registry.columns(), ["foraging_fraction", "dispersing_fraction"]);
+
+    let time = TimeUniform { step: 0, time: 0.0, day_phase: 0.5, light: 1.0 };
+    let (field, movement) = (FieldStats::default(), MovementStats::default());
+    let agents = AgentStats { alive_count: 400, dispersing_count: 100, ..Default::default() };
+    assert_eq!(registry.row(&sample(&time, &field, &agents, &movement, &[])), [0.75f32, 0.25].map(MetricValue::from));
+
+    // An extinct population has no behaviour to split
+    let agents = AgentStats::default();
+    assert_eq!(registry.row(&sample(&time, &field, &agents, &movement, &[])), [0.0f32, 0.0].map(MetricValue::from));
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::FieldData;
+use vireo_core::SimulationConfig;
+
+const R0: f32 = 1.0;
+
+/// Agent-free world at a uniform R0 where nothing but the noise changes R
+fn still_world(gpu: &GpuDevice, sigma: f32, seed: u64) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.world.seed = seed;
+    config.agents.herbivores = 0;
+    config.field.D_R = 0.0;
+    config.field.sigma_R = 0.0;
+    config.field.lambda_R = 0.0;
+    config.noise.sigma = sigma;
+
+    let mut sim = Simulation::new(gpu, config);
+    sim.field_manager.fill(FieldData::new(R0, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim
+}
+
+/// Mean and variance of R over the grid
+fn resource_moments(sim: &mut Simulation, gpu: &GpuDevice) -> (f64, f64) {
+    sim.sync_field(gpu);
+    let r: Vec<f64> = sim.field_manager.cells().iter().map(|d| d.R as f64).collect();
+    let mean = r.iter().sum::<f64>() / r.len() as f64;
+    (mean, r.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / r.len() as f64)
+}
+
+#[test]
+fn variance_grows_as_sigma_squared_times_time() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let sigma = 0.02;
+    let mut sim = still_world(&gpu, sigma, 7);
+    let dt = sim.config.world.dt as f64;
+
+    // A random walk per cell: var = sigma² t, far from the clamp at 0 (R0 is over 10 sd away)
+    for steps in [10, 20] {
+        while sim.current_step() < steps {
+            sim.step(&gpu);
+        }
+        let (mean, variance) = resource_moments(&mut sim, &gpu);
+        let expected = (sigma as f64).powi(2) * steps as f64 * dt;
+        // The f16 field may round each step's value toward 0, up to an ulp of drift per step
+        assert!((mean - R0 as f64).abs() < 0.25 * expected.sqrt(), "mean {} after {} steps", mean, steps);
+        assert!((variance / expected - 1.0).abs() < 0.1, "variance {} after {} steps, expected {}", variance, steps, expected);
+    }
+}
+
+#[test]
+fn noise_repeats_for_a_seed_and_is_off_at_zero() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let run = |sigma: f32, seed: u64| {
+        let mut sim = still_world(&gpu, sigma, seed);
+        for _ in 0..5 {
+            sim.step(&gpu);
+        }
+        sim.sync_field(&gpu);
+        sim.field_manager.cells().iter().map(|d| d.R).collect::<Vec<_>>()
+    };
+
+    let field = run(0.05, 1);
+    assert_eq!(field, run(0.05, 1));
+    assert_ne!(field, run(0.05, 2));
+    assert!(run(0.0, 1).iter().all(|&r| r == R0), "sigma 0 leaves the field untouched");
+}
+
+/// R of every cell, row-major
+fn resource(sim: &mut Simulation, gpu: &GpuDevice) -> Vec<f64> {
+    sim.sync_field(gpu);
+    sim.field_manager.cells().iter().map(|d| d.R as f64).collect()
+}
+
+/// Pearson correlation of two equally long samples
+fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len() as f64;
+    let (ma, mb) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+    let (va, vb): (f64, f64) = (a.iter().map(|x| (x - ma).powi(2)).sum(), b.iter().map(|y| (y - mb).powi(2)).sum());
+    cov / (va * vb).sqrt()
+}
+
+#[test]
+fn increments_are_independent_standard_normals() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let sigma = 0.1;
+    let mut sim = still_world(&gpu, sigma, 3);
+    let scale = sigma as f64 * (sim.config.world.dt as f64).sqrt();
+
+    // One step's increments per cell, in units of the noise sd
+    let mut before = vec![R0 as f64; sim.field_manager.len()];
+    let mut increments = Vec::new();
+    for _ in 0..2 {
+        sim.step(&gpu);
+        let after = resource(&mut sim, &gpu);
+        increments.push(after.iter().zip(&before).map(|(a, b)| (a - b) / scale).collect::<Vec<_>>());
+        before = after;
+    }
+
+    // 4096 cells: a 4 sd band on each statistic
+    let z = &increments[0];
+    let n = z.len() as f64;
+    let mean = z.iter().sum::<f64>() / n;
+    let variance = z.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    let within = |k: f64| z.iter().filter(|x| x.abs() < k).count() as f64 / n;
+    assert!(mean.abs() < 0.07, "mean {}", mean);
+    assert!((variance - 1.0).abs() < 0.09, "variance {}", variance);
+    assert!((within(1.0) - 0.6827).abs() < 0.03, "{} within 1 sd", within(1.0));
+    assert!((within(2.0) - 0.9545).abs() < 0.013, "{} within 2 sd", within(2.0));
+
+    // No correlation between horizontal neighbours, nor between a cell's successive steps
+    let width = sim.config.world.size[0] as usize;
+    let (left, right): (Vec<f64>, Vec<f64>) = (0..z.len())
+        .filter(|i| (i + 1) % width != 0)
+        .map(|i| (z[i], z[i + 1]))
+        .unzip();
+    assert!(correlation(&left, &right).abs() < 0.07, "neighbours {}", correlation(&left, &right));
+    assert!(correlation(z, &increments[1]).abs() < 0.07, "steps {}", correlation(z, &increments[1]));
+}
@@ -0,0 +1,211 @@
+mod common;
+
+use common::gpu_or_skip;
+use glam::Vec2;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{Agent, FieldData, ObstacleBrush, ObstacleField};
+use vireo_core::{parse_config, ObstacleConfig, ObstacleShape, SimulationConfig};
+
+fn walls(shapes: Vec<ObstacleShape>) -> ObstacleConfig {
+    ObstacleConfig { enabled: true, shapes, ..ObstacleConfig::default() }
+}
+
+#[test]
+fn shapes_give_signed_distances() {
+    let circle = ObstacleShape::Circle { center: [10.0, 10.0], radius: 4.0 };
+    assert_eq!(circle.distance([10.0, 10.0]), -4.0);
+    assert_eq!(circle.distance([17.0, 10.0]), 3.0);
+
+    let rect = ObstacleShape::Rect { min: [0.0, 0.0], max: [10.0, 4.0] };
+    assert_eq!(rect.distance([5.0, 2.0]), -2.0);
+    assert_eq!(rect.distance([5.0, 7.0]), 3.0);
+    assert_eq!(rect.distance([13.0, 8.0]), 5.0); // Nearest point is the corner
+}
+
+#[test]
+fn field_is_the_union_of_shapes_and_far_when_disabled() {
+    let config = walls(vec![
+        ObstacleShape::Circle { center: [8.5, 8.5], radius: 2.0 },
+        ObstacleShape::Rect { min: [20.0, 0.0], max: [24.0, 64.0] },
+    ]);
+    let field = ObstacleField::new(&config, [64, 64]);
+    assert_eq!(field.distances().len(), 64 * 64);
+    assert_eq!(field.at(8, 8), -2.0);
+    assert_eq!(field.at(21, 30), -1.5);
+    assert_eq!(field.at(14, 30), 5.5);
+    // Bilinear sampling is exact between centres along a wall normal
+    assert_eq!(field.sample([17.0, 30.0]), 3.0);
+    assert_eq!(field.normal([17.0, 30.0]), [-1.0, 0.0]);
+
+    let disabled = ObstacleField::new(&ObstacleConfig { enabled: false, ..config }, [64, 64]);
+    assert!(disabled.distances().iter().all(|&d| d == ObstacleField::FAR));
+}
+
+#[test]
+fn faces_close_across_the_interface() {
+    assert_eq!(ObstacleField::face_aperture(3.0, 2.0), 1.0);
+    assert_eq!(ObstacleField::face_aperture(0.5, -0.5), 0.5);
+    assert_eq!(ObstacleField::face_aperture(-1.0, -2.0), 0.0);
+    assert_eq!(ObstacleField::face_aperture(0.2, 0.1), ObstacleField::face_aperture(0.1, 0.2));
+}
+
+#[test]
+fn spawned_agents_are_pushed_out_of_walls() {
+    let field = ObstacleField::new(&walls(vec![ObstacleShape::Rect { min: [20.0, 0.0], max: [30.0, 64.0] }]), [64, 64]);
+    let mut agents = vec![Agent::new(Vec2::new(22.0, 32.0), 1.0, 1), Agent::new(Vec2::new(10.0, 32.0), 1.0, 1)];
+    field.push_out(&mut agents);
+
+    assert!((agents[0].pos[0] - 20.0).abs() < 1e-4, "{:?}", agents[0].pos);
+    assert_eq!(agents[0].memory_pos, agents[0].pos);
+    assert_eq!(agents[1].pos, [10.0, 32.0]);
+}
+
+#[test]
+fn obstacle_configs_are_validated() {
+    let yaml = |obstacles: &str| format!(
+        "world: {{size: [64, 64], steps: 10, dt: 0.1, seed: 1}}\n\
+         field: {{D_R: 0.5, D_W: 0.2, sigma_R: 0.05, alpha_H: 0.1, beta_H: 0.05, lambda_R: 0.005, lambda_W: 0.005}}\n\
+         chemotaxis: {{chi_R: 8.0, chi_W: 4.0, kappa: 2.0, gamma: 0.05, v_max: 2.0, eps0: 0.01, eta_R: 0.1}}\n\
+         agents: {{herbivores: 10, E0: 1.0}}\n\
+         noise: {{sigma: 0.0}}\n\
+         obstacles: {}\n",
+        obstacles,
+    );
+
+    let config = parse_config(&yaml("{enabled: true, shapes: [{shape: circle, center: [32, 32], radius: 5}, \
+        {shape: rect, min: [0, 0], max: [8, 64]}]}")).unwrap();
+    assert_eq!(config.obstacles.shapes.len(), 2);
+    assert_eq!(config.obstacles.avoid_distance, ObstacleConfig::default().avoid_distance);
+
+    let error = parse_config(&yaml("{enabled: true, shapes: [{shape: rect, min: [8, 0], max: [0, 64]}]}")).unwrap_err();
+    assert!(error.to_string().contains("obstacles.shapes[0]"), "{}", error);
+    let error = parse_config(&yaml("{enabled: true, avoid_distance: 0}")).unwrap_err();
+    assert!(error.to_string().contains("obstacles.avoid_distance"), "{}", error);
+    // Disabled obstacles are not checked
+    assert!(parse_config(&yaml("{enabled: false, avoid_distance: 0}")).is_ok());
+}
+
+/// World split by a wall at x in [28, 36), resource only on the left
+fn split_world(gpu: &GpuDevice, herbivores: u32) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.agents.herbivores = herbivores;
+    config.field.D_R = 0.2;
+    config.field.D_W = 0.2;
+    config.obstacles = walls(vec![ObstacleShape::Rect { min: [28.0, -8.0], max: [36.0, 72.0] }]);
+
+    let mut sim = Simulation::new(gpu, config);
+    for y in 0..64 {
+        for x in 0..64 {
+            let r = if x < 28 { 1.0 } else { 0.0 };
+            sim.field_manager.set(x, y, FieldData::new(r, 0.0));
+        }
+    }
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim
+}
+
+#[test]
+fn diffusion_does_not_cross_walls() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = split_world(&gpu, 1);
+    for agent in &mut sim.agent_manager.agents {
+        agent.kill();
+    }
+    sim.upload_agents(&gpu);
+    for _ in 0..100 {
+        sim.step(&gpu);
+    }
+
+    sim.sync_field(&gpu);
+    let column_max = |x: u32| (0..64).map(|y| sim.field_manager.get_resource(x, y)).fold(0.0f32, f32::max);
+    assert!((28..36).all(|x| column_max(x) == 0.0), "resource inside the wall");
+    assert!((36..64).all(|x| column_max(x) == 0.0), "resource leaked through the wall");
+    assert!(column_max(27) > 0.5, "{}", column_max(27));
+}
+
+#[test]
+fn agents_stay_out_of_walls() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = split_world(&gpu, 200);
+    sim.sync_agents(&gpu);
+    let field = sim.obstacles.field.clone();
+    assert!(sim.agent_manager.agents.iter().all(|a| field.sample(a.pos) >= -1e-3));
+
+    for _ in 0..100 {
+        sim.step(&gpu);
+    }
+    sim.sync_agents(&gpu);
+    for agent in sim.agent_manager.agents.iter().filter(|a| a.is_alive()) {
+        assert!(field.sample(agent.pos) >= -1e-3, "agent at {:?} is inside a wall", agent.pos);
+    }
+}
+
+#[test]
+fn brush_draws_and_erases_discs() {
+    let mut field = ObstacleField::new(&ObstacleConfig::default(), [32, 32]);
+    field.paint(&ObstacleBrush { center: [16.5, 16.5], radius: 4.0, erase: false });
+    assert_eq!(field.at(16, 16), -4.0);
+    assert_eq!(field.at(20, 16), 0.0);
+    assert_eq!(field.at(0, 0), (16.0f32 * 16.0 * 2.0).sqrt() - 4.0);
+
+    // Erasing the middle leaves a ring; outside both discs nothing changes
+    field.paint(&ObstacleBrush { center: [16.5, 16.5], radius: 2.0, erase: true });
+    assert_eq!(field.at(16, 16), 2.0);
+    assert!(field.at(19, 16) < 0.0);
+    assert_eq!(field.at(0, 0), (16.0f32 * 16.0 * 2.0).sqrt() - 4.0);
+}
+
+/// Same world as `split_world` without configured walls, no agents and the wall painted at x = 32
+fn painted_world(gpu: &GpuDevice) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.agents.herbivores = 1;
+    config.field.D_R = 0.2;
+    config.field.D_W = 0.2;
+
+    let mut sim = Simulation::new(gpu, config);
+    for agent in &mut sim.agent_manager.agents {
+        agent.kill();
+    }
+    sim.upload_agents(gpu);
+    for y in 0..64 {
+        for x in 0..64 {
+            let r = if x < 28 { 1.0 } else { 0.0 };
+            sim.field_manager.set(x, y, FieldData::new(r, 0.0));
+        }
+    }
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+
+    assert_eq!(sim.rd_params.obstacles, 0);
+    for y in (-4..=68).step_by(2) {
+        sim.paint_obstacles(gpu, ObstacleBrush { center: [32.0, y as f32], radius: 3.0, erase: false });
+    }
+    assert!(sim.config.obstacles.enabled);
+    assert_eq!((sim.rd_params.obstacles, sim.agent_params.avoid_distance), (1, sim.config.obstacles.avoid_distance));
+    sim
+}
+
+#[test]
+fn painted_walls_block_diffusion_until_erased() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = painted_world(&gpu);
+    for _ in 0..100 {
+        sim.step(&gpu);
+    }
+    sim.sync_field(&gpu);
+    let column_max = |sim: &Simulation, x: u32| (0..64).map(|y| sim.field_manager.get_resource(x, y)).fold(0.0f32, f32::max);
+    assert!((36..64).all(|x| column_max(&sim, x) == 0.0), "resource leaked through the painted wall");
+    assert!(column_max(&sim, 27) > 0.5, "{}", column_max(&sim, 27));
+
+    // A gap cut by the eraser lets it through
+    sim.paint_obstacles(&gpu, ObstacleBrush { center: [32.0, 32.0], radius: 6.0, erase: true });
+    assert!(sim.obstacles.field.sample([32.0, 32.0]) > 0.0);
+    for _ in 0..100 {
+        sim.step(&gpu);
+    }
+    sim.sync_field(&gpu);
+    assert!(column_max(&sim, 40) > 0.0, "no resource through the erased gap");
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+
+#[test]
+fn totals_add_up_every_step_of_occupancy() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, small_config(|config| config.output.occupancy_total = true));
+    assert!(sim.read_occupancy_total(&gpu).unwrap().iter().all(|&n| n == 0));
+
+    let mut expected = vec![0u32; 64 * 64];
+    for _ in 0..20 {
+        sim.step(&gpu);
+        for (e, n) in expected.iter_mut().zip(sim.read_occupancy(&gpu)) {
+            *e += n;
+        }
+    }
+    let totals = sim.read_occupancy_total(&gpu).unwrap();
+    assert_eq!(totals, expected);
+    let alive = sim.agent_manager.agents.iter().filter(|a| a.is_alive()).count() as u32;
+    assert_eq!(totals.iter().sum::<u32>(), alive * 20);
+
+    // Resuming from a checkpoint puts the totals back
+    sim.restore_occupancy_total(&gpu, &vec![7; 64 * 64]);
+    assert!(sim.read_occupancy_total(&gpu).unwrap().iter().all(|&n| n == 7));
+
+    assert!(Simulation::new(&gpu, small_config(|_| {})).read_occupancy_total(&gpu).is_none());
+}
@@ -0,0 +1,57 @@
+use vireo_core::sim::{sweep_design, BayesOpt, ParamRange, SimRng, SweepStrategy};
+use vireo_core::RngKind;
+
+fn ranges(n: usize) -> Vec<ParamRange> {
+    ["chemotaxis.chi_R=0:10", "field.sigma_R=0:1"][..n].iter().map(|r| r.parse().unwrap()).collect()
+}
+
+/// LHS start then `iterations` expected-improvement suggestions on `f`
+fn optimize(ranges: &[ParamRange], iterations: usize, f: impl Fn(&[f32]) -> f64) -> (Vec<f32>, f64) {
+    let mut rng = SimRng::new(RngKind::default(), 11);
+    let mut opt = BayesOpt::new(ranges, 0.2);
+    for point in sweep_design(SweepStrategy::LatinHypercube, ranges, Some(4), 0, &mut rng).unwrap() {
+        opt.observe(&point, f(&point));
+    }
+    for _ in 0..iterations {
+        let point = opt.suggest(&mut rng, 500);
+        opt.observe(&point, f(&point));
+    }
+    opt.best().unwrap()
+}
+
+#[test]
+fn surrogate_interpolates_observations() {
+    let mut opt = BayesOpt::new(&ranges(1), 0.2);
+    assert!(opt.best().is_none() && opt.predict(&[1.0]).is_none());
+    opt.observe(&[2.0], 1.0);
+    opt.observe(&[8.0], 3.0);
+
+    let (mu, sigma) = opt.predict(&[8.0]).unwrap();
+    assert!((mu - 1.0).abs() < 0.01 && sigma < 0.1, "standardized 3.0 is +1: {} ± {}", mu, sigma);
+    let (_, far) = opt.predict(&[5.0]).unwrap();
+    assert!(far > 0.5, "uncertain between observations: {}", far);
+    assert_eq!(opt.best(), Some((vec![8.0], 3.0)));
+}
+
+#[test]
+fn finds_the_peak_of_a_smooth_objective() {
+    let (best, value) = optimize(&ranges(1), 8, |p| -((p[0] - 7.0) as f64).powi(2));
+    assert!((best[0] - 7.0).abs() < 0.3, "{:?} {}", best, value);
+
+    let (best, _) = optimize(&ranges(2), 16, |p| -((p[0] - 3.0) as f64 / 10.0).powi(2) - ((p[1] - 0.8) as f64).powi(2));
+    assert!((best[0] - 3.0).abs() < 1.0 && (best[1] - 0.8).abs() < 0.1, "{:?}", best);
+}
+
+#[test]
+fn suggestions_stay_in_range_and_repeat_for_a_seed() {
+    let mut opt = BayesOpt::new(&ranges(2), 0.2);
+    let first = opt.suggest(&mut SimRng::new(RngKind::default(), 3), 100);
+    assert!((0.0..=10.0).contains(&first[0]) && (0.0..=1.0).contains(&first[1]));
+
+    opt.observe(&[1.0, 0.5], 0.0);
+    opt.observe(&[9.0, 0.5], 1.0);
+    let a = opt.suggest(&mut SimRng::new(RngKind::default(), 3), 100);
+    let b = opt.suggest(&mut SimRng::new(RngKind::default(), 3), 100);
+    assert_eq!(a, b);
+    assert!((0.0..=10.0).contains(&a[0]) && (0.0..=1.0).contains(&a[1]));
+}
@@ -0,0 +1,177 @@
+//! The GPU parameter structs are built from `SimulationConfig` in vireo-params
+//! and mirrored by hand in WGSL; these tests pin both sides together.
+
+use vireo_core::bindings::H_SCALE;
+use vireo_core::{shaders, AgentParams, FieldSampling, GradientStencil, PerStep, PerTime, RDParams, SimulationConfig, Speed};
+
+/// A config where every value feeding the GPU structs is distinct
+fn distinct_config() -> SimulationConfig {
+    let mut config = SimulationConfig::default();
+    config.world.size = [96, 80];
+    config.world.dt = 0.25;
+    config.field.D_R = 0.11;
+    config.field.D_W = 0.12;
+    config.field.sigma_R = 0.13;
+    config.field.K_R = 1.4;
+    config.field.alpha_H = 0.15;
+    config.field.beta_H = 0.16;
+    config.field.lambda_R = 0.017;
+    config.field.lambda_W = 0.018;
+    config.chemotaxis.chi_R = 2.1;
+    config.chemotaxis.chi_W = 2.2;
+    config.chemotaxis.kappa = 2.3;
+    config.chemotaxis.gamma = 0.024;
+    config.chemotaxis.v_max = 2.5;
+    config.chemotaxis.eps0 = 0.026;
+    config.chemotaxis.eta_R = 0.27;
+    config.chemotaxis.sampling = FieldSampling::Bicubic;
+    config.chemotaxis.gradient = GradientStencil::Scharr;
+    config.chemotaxis.memory_weight = 2.8;
+    config.chemotaxis.memory_decay = 0.029;
+    config.chemotaxis.disperse_energy = 0.31;
+    config.chemotaxis.settle_resource = 0.32;
+    config.chemotaxis.disperse_speed = 1.33;
+    config.obstacles.enabled = true;
+    config.obstacles.avoid_distance = 3.1;
+    config.obstacles.avoid_strength = 3.2;
+    config.terrain.enabled = true;
+    config.terrain.climb_cost = 3.3;
+    config.terrain.descent_gain = 3.0;
+    config.terrain.anisotropy = 3.4;
+    config.soil.enabled = true;
+    config.soil.threshold = 0.035;
+    config.soil.degradation = 3.6;
+    config.soil.recovery = 0.037;
+    config.diffusion.enabled = true;
+    config.noise.sigma = 0.038;
+    config.world.seed = (1 << 32) + 39; // Only the low 32 bits reach the shaders
+    config
+}
+
+/// Field names of `struct <name> { ... }` in a WGSL source, in declaration order
+fn wgsl_fields(source: &str, name: &str) -> Vec<String> {
+    let start = source.find(&format!("struct {} {{", name)).expect("struct declared");
+    let body = &source[start..];
+    let body = &body[body.find('{').unwrap() + 1..body.find('}').unwrap()];
+    body.lines()
+        .filter_map(|line| line.split("//").next().unwrap().split(':').next())
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn rd_params_carry_every_config_value() {
+    let config = distinct_config();
+    // Exhaustive destructuring: a new field fails to compile here until it is covered
+    let RDParams { D_R, D_W, sigma_R, alpha_H, beta_H, lambda_R, lambda_W, dt, size, H_SCALE: h_scale, K_R, obstacles, anisotropy,
+        soil_threshold, soil_degradation, soil_recovery, tensor, noise, seed } =
+        RDParams::from(&config);
+
+    assert_eq!(
+        [D_R, D_W, sigma_R, alpha_H, beta_H, lambda_R, lambda_W, dt, K_R],
+        [0.11, 0.12, 0.13, 0.15, 0.16, 0.017, 0.018, 0.25, 1.4]
+    );
+    assert_eq!(size, [96, 80]);
+    assert_eq!(h_scale, H_SCALE);
+    assert_eq!((obstacles, anisotropy, tensor), (1, 3.4, 1));
+    assert_eq!((noise, seed), (0.038, 39));
+    assert_eq!([soil_threshold, soil_degradation, soil_recovery], [0.035, 3.6, 0.037]);
+    assert_eq!(std::mem::size_of::<RDParams>(), 80);
+
+    assert_eq!(
+        wgsl_fields(&shaders::rd_step(), "RDParams"),
+        ["D_R", "D_W", "sigma_R", "alpha_H", "beta_H", "lambda_R", "lambda_W", "dt", "size", "H_SCALE", "K_R",
+            "obstacles", "anisotropy", "soil_threshold", "soil_degradation", "soil_recovery", "tensor", "noise", "seed"]
+    );
+}
+
+#[test]
+fn agent_params_carry_every_config_value() {
+    let config = distinct_config();
+    let AgentParams {
+        chi_R, chi_W, kappa, gamma, v_max, eps0, eta_R, dt, size, sampling, gradient, memory_weight, memory_decay,
+        avoid_distance, avoid_strength, climb_cost, descent_gain, disperse_energy, settle_resource, disperse_speed, _pad,
+    } = AgentParams::from(&config);
+
+    assert_eq!([chi_R, chi_W, kappa, gamma, v_max, eps0, eta_R, dt], [2.1, 2.2, 2.3, 0.024, 2.5, 0.026, 0.27, 0.25]);
+    assert_eq!(size, [96.0, 80.0]);
+    assert_eq!(sampling, FieldSampling::Bicubic as u32);
+    assert_eq!(gradient, GradientStencil::Scharr as u32);
+    assert_eq!([memory_weight, memory_decay], [2.8, 0.029]);
+    assert_eq!([avoid_distance, avoid_strength], [3.1, 3.2]);
+    assert_eq!([climb_cost, descent_gain], [3.3, 3.0]);
+    assert_eq!([disperse_energy, settle_resource, disperse_speed], [0.31, 0.32, 1.33]);
+    assert_eq!(_pad, [0; 3]);
+    assert_eq!(std::mem::size_of::<AgentParams>(), 96);
+
+    assert_eq!(
+        wgsl_fields(&shaders::agent_step(), "AgentParams"),
+        ["chi_R", "chi_W", "kappa", "gamma", "v_max", "eps0", "eta_R", "dt", "size", "sampling", "gradient",
+            "memory_weight", "memory_decay", "avoid_distance", "avoid_strength", "climb_cost", "descent_gain",
+            "disperse_energy", "settle_resource", "disperse_speed", "_pad0", "_pad1", "_pad2"]
+    );
+}
+
+#[test]
+fn yaml_round_trips_through_the_shared_config() {
+    let config = distinct_config();
+    let yaml = serde_yaml::to_string(&config).unwrap();
+    let parsed = vireo_core::parse_config(&yaml).unwrap();
+    assert_eq!(bytemuck::bytes_of(&RDParams::from(&parsed)), bytemuck::bytes_of(&RDParams::from(&config)));
+    assert_eq!(bytemuck::bytes_of(&AgentParams::from(&parsed)), bytemuck::bytes_of(&AgentParams::from(&config)));
+}
+
+#[test]
+fn rates_carry_the_units_the_shaders_apply() {
+    let config = distinct_config();
+    let field = config.field.rates();
+    let chemotaxis = config.chemotaxis.rates();
+
+    // Decay and drain are scaled by dt in the shaders; damping is removed once per step
+    assert_eq!(field.lambda_R, PerTime(0.017));
+    assert_eq!(chemotaxis.eps0, PerTime(0.026));
+    assert_eq!(chemotaxis.gamma, PerStep(0.024));
+    assert_eq!(chemotaxis.v_max, Speed(2.5));
+
+    let dt = config.world.dt;
+    assert_eq!(field.lambda_R.per_step(dt), PerStep(0.017 * 0.25));
+    assert!((chemotaxis.gamma.per_time(dt).0 - 0.096).abs() < 1e-6);
+    assert!((chemotaxis.gamma.per_time(dt).per_step(dt).0 - 0.024).abs() < 1e-6);
+    assert_eq!(chemotaxis.v_max.per_step(dt), 0.625);
+
+    // Obstacle, terrain, soil, diffusion and noise terms come from their own sections, not from the rates
+    let rd = RDParams {
+        noise: 0.038,
+        seed: 39,
+        obstacles: 1,
+        anisotropy: 3.4,
+        tensor: 1,
+        soil_threshold: 0.035,
+        soil_degradation: 3.6,
+        soil_recovery: 0.037,
+        ..RDParams::new(field, dt, config.world.size)
+    };
+    assert_eq!(bytemuck::bytes_of(&rd), bytemuck::bytes_of(&RDParams::from(&config)));
+    let agent = AgentParams {
+        avoid_distance: 3.1,
+        avoid_strength: 3.2,
+        climb_cost: 3.3,
+        descent_gain: 3.0,
+        ..AgentParams::new(chemotaxis, config.chemotaxis.sampling, config.chemotaxis.gradient, dt, config.world.size)
+    };
+    assert_eq!(bytemuck::bytes_of(&agent), bytemuck::bytes_of(&AgentParams::from(&config)));
+}
+
+#[test]
+fn set_scalar_replaces_known_paths_and_rejects_unknown_ones() {
+    let mut config = SimulationConfig::default();
+    let previous = config.chemotaxis.chi_R;
+    assert_eq!(config.set_scalar("chemotaxis.chi_R", 6.0), Ok(previous));
+    assert_eq!(config.chemotaxis.chi_R, 6.0);
+
+    assert_eq!(config.set_scalar("chemotaxis.chi_Q", 1.0), Err("unknown parameter `chemotaxis.chi_Q`".to_string()));
+    assert_eq!(config.set_scalar("chi_R", 1.0), Err("unknown parameter `chi_R`".to_string()));
+    assert_eq!(config.chemotaxis.chi_R, 6.0);
+}
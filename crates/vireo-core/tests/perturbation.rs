@@ -0,0 +1,80 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::ParamPerturbation;
+use vireo_core::{ParamSchedule, PerturbationConfig, ScheduleShape, SimulationConfig};
+
+const DEMO: &str = include_str!("../../../lab/configs/best-demo.yaml");
+
+fn perturbed(params: &[&str]) -> SimulationConfig {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    config.perturbation = PerturbationConfig {
+        enabled: true,
+        params: params.iter().map(|p| p.to_string()).collect(),
+        every: 10,
+        sigma: 0.1,
+    };
+    config
+}
+
+#[test]
+fn config_parses_and_rejects_bad_perturbations() {
+    let text = format!("{}\nperturbation:\n  enabled: true\n  params: [chemotaxis.chi_R]\n", DEMO);
+    let config = vireo_core::parse_config(&text).unwrap();
+    assert_eq!(config.perturbation, PerturbationConfig { enabled: true, params: vec!["chemotaxis.chi_R".into()], ..Default::default() });
+    assert!(ParamPerturbation::new(&vireo_core::parse_config(DEMO).unwrap()).is_none());
+
+    let reason = |config: SimulationConfig| config.validate().unwrap_err().to_string();
+    assert!(reason(perturbed(&["world.dt"])).contains("perturbation.params[0]"));
+    let mut config = perturbed(&["chemotaxis.chi_R"]);
+    config.perturbation.sigma = 1.0;
+    assert!(reason(config).contains("perturbation.sigma"));
+    let mut config = perturbed(&["chemotaxis.chi_R"]);
+    config.perturbation.every = 0;
+    assert!(reason(config).contains("perturbation.every"));
+    let mut config = perturbed(&["field.sigma_R", "chemotaxis.chi_R"]);
+    config.schedules = vec![ParamSchedule {
+        param: "chemotaxis.chi_R".into(), schedule: ScheduleShape::Step, from: 2.0, to: 4.0, start: 10, end: 0,
+    }];
+    assert!(reason(config).contains("perturbation.params[1]"));
+}
+
+#[test]
+fn draws_stay_within_sigma_hold_between_draws_and_repeat() {
+    let config = perturbed(&["chemotaxis.chi_R", "field.D_R"]);
+    let p = ParamPerturbation::new(&config).unwrap();
+    assert_eq!(p.params().collect::<Vec<_>>(), ["chemotaxis.chi_R", "field.D_R"]);
+
+    let base = [config.chemotaxis.chi_R, config.field.D_R];
+    let draws: Vec<Vec<f32>> = (0..50).map(|d| p.values_at(d * 10)).collect();
+    for values in &draws {
+        for (v, b) in values.iter().zip(base) {
+            assert!((v / b - 1.0).abs() <= 0.1 + 1e-6, "{} from {}", v, b);
+        }
+    }
+    assert_eq!(p.values_at(13), draws[1]);
+    assert!(p.draws_at(20) && !p.draws_at(25));
+    assert_ne!(draws[0], draws[1]);
+    assert_eq!(ParamPerturbation::new(&config).unwrap().values_at(490), draws[49]);
+
+    let mut other = config.clone();
+    other.world.seed += 1;
+    assert_ne!(ParamPerturbation::new(&other).unwrap().values_at(0), draws[0]);
+}
+
+#[test]
+fn simulation_applies_the_draw_of_each_step() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, perturbed(&["chemotaxis.chi_R", "field.sigma_R"]));
+    let p = sim.perturbation.clone().unwrap();
+    for _ in 0..15 {
+        sim.step(&gpu);
+    }
+    // The last step run was step 14, in the second draw
+    let [chi_r, sigma_r] = p.values_at(14)[..] else { unreachable!() };
+    assert_eq!((sim.config.chemotaxis.chi_R, sim.agent_params.chi_R, sim.rd_params.sigma_R), (chi_r, chi_r, sigma_r));
+    assert_ne!(chi_r, p.values_at(0)[0]);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use std::path::PathBuf;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::AgentPopulation;
+use vireo_core::SimulationConfig;
+
+fn write_csv(name: &str, text: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("vireo-population-{}-{}.csv", name, std::process::id()));
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+fn few_agents(config: &mut SimulationConfig) {
+    config.agents.herbivores = 2; // Spawn counts [1, 4, 1]
+}
+
+#[test]
+fn exported_agents_start_a_new_run() {
+    // The columns of agents_NNNN.csv; the dead row is dropped
+    let path = write_csv("export", "id,x,y,vx,vy,energy,alive,cohort,age,kind,state\n\
+        0,10.5,20.0,0.1,-0.2,1.5,1,3,40,1,1\n\
+        1,30.0,40.0,0,0,0.5,0,0,12,1,0\n\
+        2,50.0,5.0,0,0,2.0,1,0,7,2,0\n\
+        3,12.0,12.0,0,0,1.0,1,0,0,1,0\n");
+    let population = AgentPopulation::read_csv(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(population.counts(), [0, 2, 1]);
+    let a = population.agents[0];
+    assert_eq!((a.pos, a.vel, a.energy, a.cohort, a.age, a.state), ([10.5, 20.0], [0.1, -0.2], 1.5, 3, 40, 1));
+    assert_eq!(a.memory_pos, a.pos);
+
+    let mut config = small_config(few_agents);
+    population.fit_capacity(&mut config.agents);
+    assert_eq!(config.agents.slots(), [1, 4, 1]);
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::with_population(&gpu, config, &population).unwrap();
+    let agents = &sim.agent_manager.agents;
+    assert_eq!(agents.len(), 6);
+    assert!(!agents[0].is_alive(), "no plants were imported");
+    assert_eq!((agents[1].pos, agents[2].pos, agents[5].pos), ([10.5, 20.0], [12.0, 12.0], [50.0, 5.0]));
+    assert!(!agents[3].is_alive() && !agents[4].is_alive());
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+    assert_eq!(sim.agent_manager.agents[1].age, 41);
+}
+
+#[test]
+fn populations_that_do_not_fit_are_rejected() {
+    let path = write_csv("columns", "id,x,y,energy\n0,1,1,1\n");
+    let err = AgentPopulation::read_csv(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(err.contains("`kind`"), "{}", err);
+
+    let path = write_csv("kind", "x,y,energy,kind\n1,1,1,4\n");
+    let err = AgentPopulation::read_csv(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(err.contains("row 1") && err.contains("kind 4"), "{}", err);
+
+    let path = write_csv("crowd", "x,y,energy,kind\n1,1,1,0\n2,2,1,0\n3,3,1,0\n70,3,1,1\n");
+    let population = AgentPopulation::read_csv(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let err = population.check_compatible(&small_config(few_agents)).unwrap_err();
+    assert!(err.contains("outside the 64x64 world"), "{}", err);
+
+    let mut config = small_config(few_agents);
+    config.world.size = [128, 64];
+    let err = population.check_compatible(&config).unwrap_err();
+    assert!(err.contains("3 plants but only 1 slots"), "{}", err);
+    population.fit_capacity(&mut config.agents);
+    assert_eq!(config.agents.slots(), [3, 4, 1]);
+    assert!(population.check_compatible(&config).is_ok());
+}
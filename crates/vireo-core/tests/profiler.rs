@@ -0,0 +1,98 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuProfiler, PassTimeline};
+
+#[test]
+fn timeline_stacks_layers_in_first_seen_order() {
+    let mut timeline = PassTimeline::new(4);
+    timeline.push(&[("agents", 1.0), ("rd", 2.0)]);
+    timeline.push(&[("rd", 3.0), ("agents", 0.5), ("render", 1.5), ("agents", 0.5)]);
+
+    assert_eq!(timeline.names(), ["agents", "rd", "render"]);
+    assert_eq!(timeline.len(), 2);
+    let stacked = timeline.stacked();
+    assert_eq!(stacked.len(), 2 * PassTimeline::MAX_LAYERS);
+    // Frame 0 has no render layer yet; unused layers repeat the frame total
+    assert_eq!(&stacked[..4], &[1.0, 3.0, 3.0, 3.0]);
+    assert_eq!(&stacked[PassTimeline::MAX_LAYERS..PassTimeline::MAX_LAYERS + 4], &[1.0, 4.0, 5.5, 5.5]);
+    assert_eq!(timeline.peak(), 5.5);
+    assert_eq!(timeline.means(), vec![1.0, 2.5, 0.75]);
+}
+
+#[test]
+fn timeline_drops_oldest_frames_and_folds_extra_layers() {
+    let mut timeline = PassTimeline::new(3);
+    for i in 0..5 {
+        timeline.push(&[("host", i as f32)]);
+    }
+    assert_eq!(timeline.len(), 3);
+    assert_eq!((0..3).map(|f| timeline.get(f, 0)).collect::<Vec<_>>(), vec![2.0, 3.0, 4.0]);
+
+    let names: Vec<String> = (0..PassTimeline::MAX_LAYERS + 2).map(|i| format!("pass{}", i)).collect();
+    let samples: Vec<(&str, f32)> = names.iter().map(|n| (n.as_str(), 1.0)).collect();
+    timeline.push(&samples);
+    // "host" holds layer 0, pass0..pass6 the rest; pass7..pass9 fold into the last
+    assert_eq!(timeline.names().len(), PassTimeline::MAX_LAYERS);
+    assert_eq!(timeline.get(2, PassTimeline::MAX_LAYERS - 1), 4.0);
+}
+
+#[test]
+fn profiler_without_timestamps_reports_host_samples() {
+    // The default device requests no optional features
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut profiler = GpuProfiler::new(&gpu.device, &gpu.queue);
+    assert!(!profiler.is_enabled());
+
+    let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    profiler.begin_scope(&mut encoder, "agents");
+    profiler.end_scope(&mut encoder);
+    gpu.submit(encoder.finish());
+    profiler.record_cpu("readback", 2.5);
+    profiler.finish_frame(&gpu.device, &gpu.queue);
+
+    assert_eq!(profiler.poll(&gpu.device), Some(vec![("readback", 2.5)]));
+    assert_eq!(profiler.poll(&gpu.device), None);
+}
+
+#[test]
+fn profiler_reads_back_timestamps_when_supported() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    if !gpu.adapter.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+        return;
+    }
+    let (device, queue) = pollster::block_on(gpu.adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: None,
+            required_features: wgpu::Features::TIMESTAMP_QUERY,
+            required_limits: wgpu::Limits::default(),
+        },
+        None,
+    )).unwrap();
+    let mut profiler = GpuProfiler::new(&device, &queue);
+    assert!(profiler.is_enabled());
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 1 << 20,
+        usage: wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    for _ in 0..2 {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        profiler.begin_scope(&mut encoder, "clear");
+        encoder.clear_buffer(&buffer, 0, None);
+        profiler.end_scope(&mut encoder);
+        queue.submit(Some(encoder.finish()));
+    }
+    profiler.record_cpu("host", 1.0);
+    profiler.finish_frame(&device, &queue);
+
+    device.poll(wgpu::Maintain::Wait);
+    let samples = profiler.poll(&device).expect("frame read back after a full wait");
+    // Both scopes share a name and are summed into one sample
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].0, "clear");
+    assert!(samples[0].1.is_finite() && samples[0].1 >= 0.0, "{}", samples[0].1);
+    assert_eq!(samples[1], ("host", 1.0));
+}
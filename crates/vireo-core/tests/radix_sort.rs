@@ -0,0 +1,54 @@
+mod common;
+
+use common::gpu_or_skip;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use vireo_core::gpu::{ComputePipelines, GpuDevice, RadixSort, read_buffer_from};
+use vireo_core::gpu::layouts::Layouts;
+
+/// Sort `keys` (values = original index) on the GPU and read the pairs back
+fn gpu_sort(gpu: &GpuDevice, keys: &[u32], key_bits: u32) -> (Vec<u32>, Vec<u32>) {
+    let layouts = Layouts::new(&gpu.device);
+    let pipelines = ComputePipelines::new(&gpu.device, &layouts);
+    let sorter = RadixSort::new(&gpu.device, &layouts, keys.len() as u32);
+
+    let values: Vec<u32> = (0..keys.len() as u32).collect();
+    gpu.queue.write_buffer(sorter.keys(), 0, bytemuck::cast_slice(keys));
+    gpu.queue.write_buffer(sorter.values(), 0, bytemuck::cast_slice(&values));
+
+    let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    sorter.sort(&gpu.device, &gpu.queue, &mut encoder, &pipelines, keys.len() as u32, key_bits);
+    gpu.submit(encoder.finish());
+
+    let size = keys.len() as u64 * 4;
+    (
+        read_buffer_from(&gpu.device, &gpu.queue, sorter.keys(), size),
+        read_buffer_from(&gpu.device, &gpu.queue, sorter.values(), size),
+    )
+}
+
+/// Reference: stable CPU sort of (key, index) pairs
+fn cpu_sort(keys: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut pairs: Vec<(u32, u32)> = keys.iter().copied().zip(0..).collect();
+    pairs.sort_by_key(|&(k, _)| k);
+    pairs.into_iter().unzip()
+}
+
+#[test]
+fn sorts_one_million_pairs_like_cpu() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut rng = Pcg64::seed_from_u64(7);
+    let keys: Vec<u32> = (0..1_000_000).map(|_| rng.gen()).collect();
+
+    assert_eq!(gpu_sort(&gpu, &keys, 32), cpu_sort(&keys));
+}
+
+#[test]
+fn small_keys_sort_stably_with_fewer_passes() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut rng = Pcg64::seed_from_u64(11);
+    // Many duplicates and a length that is not a multiple of the block size
+    let keys: Vec<u32> = (0..100_003).map(|_| rng.gen_range(0..4096)).collect();
+
+    assert_eq!(gpu_sort(&gpu, &keys, 12), cpu_sort(&keys));
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::RainScheduler;
+use vireo_core::{RainConfig, SimulationConfig, WorldConfig, MAX_RAIN_CELLS};
+
+fn world() -> WorldConfig {
+    SimulationConfig::default().world
+}
+
+#[test]
+fn rain_cells_drift_by_their_velocity_and_expire() {
+    let config = RainConfig { enabled: false, rate: 1.0, ..Default::default() };
+    let mut scheduler = RainScheduler::new(config, &world());
+    assert!((0..200).all(|step| !scheduler.tick(step)));
+
+    let config = RainConfig { enabled: true, rate: 1.0, duration: 40, velocity: [0.5, -0.25], velocity_jitter: 0.1, ..Default::default() };
+    let mut scheduler = RainScheduler::new(config.clone(), &world());
+    for step in 0..120 {
+        assert!(scheduler.tick(step), "moving cells redraw the map every step");
+        assert!(scheduler.active().len() <= MAX_RAIN_CELLS);
+        for c in scheduler.active() {
+            assert!(c.start_step <= step && step < c.end_step);
+            assert!((c.velocity[0] - 0.5).abs() <= 0.1 && (c.velocity[1] + 0.25).abs() <= 0.1, "{:?}", c.velocity);
+            let [x, y] = c.center(step);
+            let t = (step - c.start_step) as f32;
+            assert!((x - c.origin[0] - c.velocity[0] * t).abs() < 1e-4 && (y - c.origin[1] - c.velocity[1] * t).abs() < 1e-4);
+        }
+    }
+
+    // Same seed, same weather
+    let mut a = RainScheduler::new(config.clone(), &world());
+    let mut b = RainScheduler::new(config, &world());
+    for step in 0..100 {
+        assert_eq!(a.tick(step), b.tick(step));
+        assert_eq!(a.active(), b.active());
+    }
+
+    let rain = RainConfig { enabled: true, velocity_jitter: -0.1, ..Default::default() };
+    let bad = SimulationConfig { rain, ..SimulationConfig::default() };
+    let error = bad.validate().unwrap_err();
+    assert!(error.to_string().contains("rain.velocity_jitter"), "{}", error);
+}
+
+#[test]
+fn the_boost_map_draws_each_cell_where_it_has_drifted() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 1;
+    config.rain = RainConfig { enabled: true, rate: 0.3, duration: 1000, radius: 4.0, velocity: [0.5, 0.25], ..Default::default() };
+    let mut sim = Simulation::new(&gpu, config);
+    for _ in 0..30 {
+        sim.step(&gpu);
+    }
+
+    // The last tick was for step 29, where the cells are drawn
+    let cells = sim.blooms.rain.active().to_vec();
+    assert!(cells.iter().any(|c| c.start_step < 20), "{:?}", cells);
+    let boost = gpu.read_buffer::<f32>(sim.blooms.boost_buffer(), 64 * 64 * 4);
+    for (i, &b) in boost.iter().enumerate() {
+        let p = [(i % 64) as f32 + 0.5, (i / 64) as f32 + 0.5];
+        let expected: f32 = cells.iter().map(|c| {
+            let [x, y] = c.center(29);
+            let d2 = (p[0] - x).powi(2) + (p[1] - y).powi(2);
+            c.amplitude * (-0.5 * d2 / (c.radius * c.radius)).exp()
+        }).sum();
+        assert!((b - expected).abs() < 1e-3, "cell {:?}: {} vs {}", p, b, expected);
+    }
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{Divergence, FieldManager, ReferenceField};
+use vireo_core::{FieldChannel, RDParams};
+
+#[test]
+fn f32_and_f64_references_agree_until_rounding_accumulates() {
+    let config = small_config(|_| {});
+    let mut field = FieldManager::new(config.world.size);
+    field.seed_resources(config.world.seed);
+    let params = RDParams::from(&config);
+    let occupancy: Vec<u32> = (0..64 * 64).map(|i| (i % 7 == 0) as u32 * 3).collect();
+
+    let mut single = ReferenceField::<f32>::new(&field);
+    let mut double = ReferenceField::<f64>::new(&field);
+    for _ in 0..200 {
+        single.step(&params, &occupancy, 1.0);
+        double.step(&params, &occupancy, 1.0);
+    }
+    for c in [FieldChannel::Resource, FieldChannel::Waste] {
+        let d = Divergence::between(&single.channel(c), &double.channel(c));
+        assert!(d.max_abs > 0.0 && d.max_abs < 1e-5, "{:?}: {:?}", c, d);
+    }
+    assert!(double.channel(FieldChannel::Waste).iter().any(|&w| w > 0.0), "herbivores emit waste");
+
+    assert_eq!(Divergence::between(&[1.0, 2.0], &[1.0, 2.0]), Divergence::default());
+    assert_eq!(Divergence::between(&[0.0, 0.0], &[3.0, -4.0]), Divergence { max_abs: 4.0, rms: 12.5f64.sqrt() });
+
+    let mut walled = config.clone();
+    walled.obstacles.enabled = true;
+    assert!(ReferenceField::<f64>::check(&config).is_ok());
+    assert!(ReferenceField::<f64>::check(&walled).unwrap_err().contains("obstacles"));
+}
+
+#[test]
+fn one_gpu_step_matches_the_reference_to_half_precision() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, small_config(|_| {}));
+    sim.sync_field(&gpu);
+    let mut reference = ReferenceField::<f64>::new(&sim.field_manager);
+
+    let light = sim.time().light;
+    sim.step(&gpu);
+    let occupancy = sim.read_occupancy(&gpu);
+    assert!(occupancy.iter().any(|&n| n > 0));
+    reference.step(&sim.rd_params, &occupancy, light);
+
+    // The texture keeps 11 significant bits, so each cell is off by at most one f16 step
+    sim.sync_field(&gpu);
+    for c in [FieldChannel::Resource, FieldChannel::Waste] {
+        let expected = reference.channel(c);
+        for (i, (g, r)) in sim.field_manager.channel_f32(c).into_iter().zip(&expected).enumerate() {
+            assert!((g as f64 - r).abs() <= r.abs() / 1024.0 + 1e-7, "{:?} cell {}: gpu {} reference {}", c, i, g, r);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::RefugeMask;
+use vireo_core::{parse_config, RefugeConfig, RefugeRegion, SimulationConfig, SpeciesDemography};
+
+/// The left half of a 64×64 world as a reserve without random deaths
+fn left_half() -> RefugeRegion {
+    RefugeRegion { polygon: vec![[0.0, 0.0], [32.0, 0.0], [32.0, 64.0], [0.0, 64.0]], mortality: 0.0 }
+}
+
+#[test]
+fn regions_rasterize_into_the_lowest_mortality() {
+    let triangle = RefugeRegion { polygon: vec![[40.0, 10.0], [60.0, 10.0], [50.0, 30.0]], mortality: 0.5 };
+    assert!(triangle.contains([50.0, 15.0]) && !triangle.contains([42.0, 28.0]));
+
+    let config = RefugeConfig { enabled: true, regions: vec![left_half(), triangle] };
+    let mask = RefugeMask::new(&config, [64, 64]);
+    assert_eq!(mask.mortality().len(), 64 * 64);
+    assert_eq!((mask.at([10.0, 50.0]), mask.at([50.0, 15.0]), mask.at([50.0, 50.0])), (0.0, 0.5, 1.0));
+    assert_eq!(mask.at([31.9, 5.0]), 0.0);
+    assert_eq!(mask.at([32.1, 5.0]), 1.0);
+
+    // Overlaps keep the lowest; disabled refuges protect nothing
+    let nested = RefugeConfig { enabled: true, regions: vec![RefugeRegion { mortality: 0.8, ..left_half() }, left_half()] };
+    assert_eq!(RefugeMask::new(&nested, [64, 64]).at([10.0, 10.0]), 0.0);
+    let off = RefugeMask::new(&RefugeConfig { enabled: false, ..config }, [64, 64]);
+    assert!(off.mortality().iter().all(|&m| m == 1.0));
+
+    let text = "demography: { enabled: true }\nrefuges:\n  enabled: true\n  regions:\n    - { polygon: [[0, 0], [20, 0], [20, 20]], mortality: 0.25 }\n";
+    let parsed = parse_config(&format!("{}{}", include_str!("../../../lab/configs/best-demo.yaml"), text)).unwrap();
+    assert_eq!(parsed.refuges.regions[0].polygon.len(), 3);
+    assert_eq!(parsed.refuges.regions[0].mortality, 0.25);
+
+    let reject = |edit: fn(&mut SimulationConfig), field: &str| {
+        let mut bad = SimulationConfig { refuges: RefugeConfig { enabled: true, regions: vec![left_half()] }, ..SimulationConfig::default() };
+        bad.demography.enabled = true;
+        edit(&mut bad);
+        let error = bad.validate().unwrap_err().to_string();
+        assert!(error.contains(field), "{}", error);
+    };
+    reject(|c| c.demography.enabled = false, "refuges.enabled");
+    reject(|c| c.refuges.regions[0].polygon.truncate(2), "refuges.regions[0].polygon");
+    reject(|c| c.refuges.regions[0].mortality = 1.5, "refuges.regions[0].mortality");
+}
+
+#[test]
+fn certain_death_spares_agents_inside_a_refuge() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 200;
+    let mut baseline = Simulation::new(&gpu, config.clone());
+
+    config.demography.enabled = true;
+    config.demography.herbivores = SpeciesDemography { death: 1.0, birth: 0.0 };
+    config.refuges = RefugeConfig { enabled: true, regions: vec![left_half()] };
+    let mut sim = Simulation::new(&gpu, config);
+
+    for s in [&mut baseline, &mut sim] {
+        s.step(&gpu);
+        s.sync_agents(&gpu);
+    }
+
+    // Demography runs after movement, so the baseline shows where each agent stood
+    let sheltered = |s: &Simulation| {
+        let herbivores: Vec<_> = s.agent_manager.agents.iter().filter(|a| a.is_alive() && a.kind == 1).collect();
+        let inside = herbivores.iter().filter(|a| a.pos[0] < 32.0).count();
+        (inside, herbivores.len() - inside)
+    };
+    let (inside, outside) = sheltered(&baseline);
+    assert!(inside > 0 && outside > 0, "{} inside, {} outside", inside, outside);
+    assert_eq!(sheltered(&sim), (inside, 0));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, RewindRing, Simulation};
+use vireo_core::SimulationConfig;
+
+fn small_sim(gpu: &GpuDevice) -> Simulation {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    Simulation::new(gpu, config)
+}
+
+fn capture(ring: &mut RewindRing, sim: &mut Simulation, gpu: &GpuDevice) {
+    sim.sync_agents(gpu);
+    let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    ring.capture(&mut encoder, &sim.field, sim.current_step(), sim.agent_manager.agents.clone());
+    gpu.submit(encoder.finish());
+}
+
+#[test]
+fn rewinding_restores_the_captured_field_and_drops_later_points() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = small_sim(&gpu);
+    let mut ring = RewindRing::new(&gpu.device, sim.config.world.size, 4);
+
+    let mut saved = Vec::new();
+    for _ in 0..3 {
+        for _ in 0..10 {
+            sim.step(&gpu);
+        }
+        capture(&mut ring, &mut sim, &gpu);
+        saved.push(sim.snapshot(&gpu));
+    }
+    for _ in 0..10 {
+        sim.step(&gpu);
+    }
+
+    let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let point = ring.rewind(&mut encoder, &sim.field, 1).expect("three points held");
+    gpu.submit(encoder.finish());
+    assert_eq!(point.step, 20);
+    assert_eq!(ring.points().map(|p| p.step).collect::<Vec<_>>(), vec![10, 20]);
+
+    sim.agent_manager.agents = point.agents;
+    sim.upload_agents(&gpu);
+    let restored = sim.snapshot(&gpu);
+    assert_eq!(bytemuck::cast_slice::<_, u8>(&restored.field), bytemuck::cast_slice::<_, u8>(&saved[1].field));
+    assert_eq!(bytemuck::cast_slice::<_, u8>(&restored.agents), bytemuck::cast_slice::<_, u8>(&saved[1].agents));
+
+    let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    assert!(ring.rewind(&mut encoder, &sim.field, 2).is_none());
+}
+
+#[test]
+fn a_full_ring_overwrites_its_oldest_point() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = small_sim(&gpu);
+    let mut ring = RewindRing::new(&gpu.device, sim.config.world.size, 2);
+
+    let mut fields = Vec::new();
+    for _ in 0..3 {
+        sim.step(&gpu);
+        capture(&mut ring, &mut sim, &gpu);
+        fields.push(sim.snapshot(&gpu).field);
+    }
+    assert_eq!(ring.points().map(|p| p.step).collect::<Vec<_>>(), vec![2, 3]);
+
+    // Step 2's slot was not the one reused for step 3
+    let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    ring.rewind(&mut encoder, &sim.field, 1).expect("two points held");
+    gpu.submit(encoder.finish());
+    sim.sync_field(&gpu);
+    assert_eq!(bytemuck::cast_slice::<_, u8>(&sim.field_manager.cells()), bytemuck::cast_slice::<_, u8>(&fields[1]));
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::gpu_or_skip;
+use rand::Rng;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{cell_sample, sample_field, sampling_rng, FieldManager, SimRng};
+use vireo_core::{FieldChannel, MetricsSampling, RngKind, SimulationConfig};
+
+fn settings(max_cells: u32, tolerance: f32) -> MetricsSampling {
+    MetricsSampling { enabled: true, max_cells, tolerance }
+}
+
+#[test]
+fn reading_every_cell_reproduces_the_full_field_stats() {
+    let mut field = FieldManager::new([32, 24]);
+    for i in 0..field.len() {
+        field.set_channel(i, FieldChannel::Resource, 0.25 + i as f32 / 4096.0);
+        field.set_channel(i, FieldChannel::Waste, ((i * 7) % 97) as f32 / 256.0);
+        field.set_channel(i, FieldChannel::Soil, (i % 5) as f32 / 8.0);
+    }
+    field.update_stats();
+
+    let mut rng = SimRng::new(RngKind::default(), 3);
+    let sampled = sample_field(field.size, &settings(10_000, 0.0), &mut rng, |cells| {
+        cells.iter().map(|&c| cell_sample(&field, c)).collect()
+    });
+    // No cell is read twice, and the tree only stops once nothing is left to refine
+    assert_eq!(sampled.cells, 32 * 24);
+    assert_eq!((sampled.se_R, sampled.se_W), (0.0, 0.0));
+    let (s, f) = (&sampled.stats, &field.stats);
+    for (a, b) in [
+        (s.mean_R, f.mean_R), (s.mean_W, f.mean_W), (s.var_R, f.var_R), (s.var_W, f.var_W),
+        (s.mean_grad_R, f.mean_grad_R), (s.mean_soil, f.mean_soil), (s.max_soil, f.max_soil),
+        (s.max_R, f.max_R), (s.min_R, f.min_R), (s.max_W, f.max_W), (s.min_W, f.min_W),
+    ] {
+        assert!((a - b).abs() < 1e-5, "{} vs {}", a, b);
+    }
+}
+
+#[test]
+fn sampling_concentrates_on_the_patchy_region_within_budget() {
+    // Flat everywhere but a noisy 64×64 corner, 1/16 of the world
+    let mut field = FieldManager::new([256, 256]);
+    let mut noise = SimRng::new(RngKind::default(), 11);
+    for y in 0..256 {
+        for x in 0..256 {
+            let r = if x < 64 && y < 64 { noise.gen_range(0.0..2.0) } else { 0.5 };
+            field.set_channel(field.get_index(x, y), FieldChannel::Resource, r);
+        }
+    }
+    field.update_stats();
+
+    let run = |tolerance: f32| {
+        let mut in_patch = 0;
+        let mut rng = sampling_rng(&SimulationConfig::default().world, 200);
+        let sampled = sample_field(field.size, &settings(2048, tolerance), &mut rng, |cells| {
+            in_patch += cells.iter().filter(|c| c[0] < 64 && c[1] < 64).count() as u32;
+            cells.iter().map(|&c| cell_sample(&field, c)).collect()
+        });
+        (sampled, in_patch)
+    };
+    let (sampled, in_patch) = run(0.0);
+    assert!(sampled.cells <= 2048 && sampled.cells > 1024, "{} cells", sampled.cells);
+    assert!(in_patch * 2 > sampled.cells, "{} of {} cells in the patch", in_patch, sampled.cells);
+    assert!(sampled.se_R > 0.0 && sampled.se_W == 0.0);
+    let error = (sampled.stats.mean_R - field.stats.mean_R).abs();
+    assert!(error < 4.0 * sampled.se_R, "mean R off by {} with standard error {}", error, sampled.se_R);
+    assert_eq!(sampled.stats.mean_W, 0.0);
+
+    // Same step, same cells; a looser tolerance stops early
+    assert_eq!(run(0.0).0.stats.mean_R, sampled.stats.mean_R);
+    let (coarse, _) = run(0.01);
+    assert!(coarse.se_R <= 0.01 && coarse.cells < sampled.cells, "{} cells, se {}", coarse.cells, coarse.se_R);
+}
+
+#[test]
+fn gpu_gather_matches_the_field_readback() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    config.output.sampling = settings(4096, 0.0);
+    let mut sim = Simulation::new(&gpu, config);
+    // A ramp, so no two samples of a node agree and the tree refines to single cells
+    for y in 0..64 {
+        for x in 0..64 {
+            let i = sim.field_manager.get_index(x, y);
+            sim.field_manager.set_channel(i, FieldChannel::Resource, x as f32 / 64.0 + y as f32 / 8.0);
+            sim.field_manager.set_channel(i, FieldChannel::Waste, 0.5 - y as f32 / 256.0);
+        }
+    }
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    let sampled = sim.sample_field(&gpu);
+    assert_eq!(sampled.cells, 64 * 64);
+    assert!(sampled.rounds > 1);
+
+    sim.field_manager.update_stats();
+    let (s, f) = (&sampled.stats, &sim.field_manager.stats);
+    for (a, b) in [(s.mean_R, f.mean_R), (s.mean_W, f.mean_W), (s.var_R, f.var_R), (s.mean_grad_R, f.mean_grad_R), (s.max_R, f.max_R), (s.min_W, f.min_W)] {
+        assert!((a - b).abs() < 1e-5 * (1.0 + b.abs()), "{} vs {}", a, b);
+    }
+}
@@ -0,0 +1,55 @@
+use vireo_core::sim::{FieldStats, Interaction, ScenarioScript, SpeciesStats};
+use vireo_core::SimulationConfig;
+
+const SCRIPT: &str = r#"
+fn on_step(step) {
+    if step == 2 {
+        this.chi = param("chemotaxis.chi_R");
+        set_param("chemotaxis.chi_R", this.chi * 2);
+    }
+    if step == 4 {
+        set_param("chemotaxis.chi_R", this.chi);
+    }
+    if alive("herbivores") < 10 {
+        inject(width() / 2, height() / 2, 3, mean_R());
+        spawn_agents("herbivores", 10.5, 12, 2, 10);
+    }
+}
+"#;
+fn species(alive: u32) -> Vec<SpeciesStats> {
+    vec![SpeciesStats { kind: 1, alive, mean_energy: 1.0, births: 0, deaths: 0 }]
+}
+#[test]
+fn callbacks_turn_script_calls_into_interactions() {
+    let config = SimulationConfig::default();
+    let chi = config.chemotaxis.chi_R;
+    let mut script = ScenarioScript::compile(SCRIPT, &config).unwrap();
+    script.observe(&FieldStats { mean_R: 0.4, ..Default::default() }, &species(50));
+    assert_eq!(script.on_step(0, &config).unwrap(), vec![]);
+    // `this` carries the old value from step 2 to step 4
+    assert_eq!(script.on_step(2, &config).unwrap(), vec![Interaction::Param { param: "chemotaxis.chi_R".into(), value: chi * 2.0 }]);
+    assert_eq!(script.on_step(4, &config).unwrap(), vec![Interaction::Param { param: "chemotaxis.chi_R".into(), value: chi }]);
+    // Observations come from the latest sample
+    script.observe(&FieldStats { mean_R: 0.4, ..Default::default() }, &species(3));
+    let [w, h] = config.world.size.map(|n| (n / 2) as f32);
+    assert_eq!(script.on_step(5, &config).unwrap(), vec![
+        Interaction::Inject { center: [w, h], radius: 3.0, amount: 0.4 },
+        Interaction::Spawn { species: "herbivores".into(), center: [10.5, 12.0], radius: 2.0, count: 10 },
+    ]);
+}
+
+#[test]
+fn bad_scripts_fail_with_the_reason() {
+    let config = SimulationConfig::default();
+    let error = |source: &str| ScenarioScript::compile(source, &config).err().unwrap();
+    assert!(error("fn step(n) {}").contains("fn on_step(step)"));
+    assert!(error("fn on_step(step) { let }").contains("cannot compile"));
+    let fails_at = |body: &str| {
+        let mut script = ScenarioScript::compile(&format!("fn on_step(step) {{ {} }}", body), &config).unwrap();
+        script.on_step(7, &config).unwrap_err()
+    };
+    assert!(fails_at(r#"set_param("world.dt", 1.0)"#).contains("cannot be set"));
+    assert!(fails_at(r#"spawn_agents("wolves", 1, 1, 1, 1)"#).contains("unknown species `wolves`"));
+    assert!(fails_at("inject(1, 1, -2, 0.5)").contains("must be finite"));
+    assert!(fails_at("loop {}").contains("step 7"));
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::{ParamSchedule, ScheduleShape, SimulationConfig};
+
+fn schedule(param: &str, schedule: ScheduleShape) -> ParamSchedule {
+    ParamSchedule { param: param.to_string(), schedule, from: 2.0, to: 10.0, start: 100, end: 300 }
+}
+
+#[test]
+fn shapes_hold_then_change() {
+    let linear = schedule("chemotaxis.chi_R", ScheduleShape::Linear);
+    let values: Vec<f32> = [0, 100, 150, 200, 300, 5000].iter().map(|&s| linear.value_at(s)).collect();
+    assert_eq!(values, [2.0, 2.0, 4.0, 6.0, 10.0, 10.0]);
+
+    let step = schedule("chemotaxis.chi_R", ScheduleShape::Step);
+    assert_eq!((step.value_at(99), step.value_at(100), step.value_at(1000)), (2.0, 10.0, 10.0));
+}
+
+#[test]
+fn simulation_refreshes_scheduled_uniforms() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    config.schedules = vec![
+        ParamSchedule { start: 0, end: 10, ..schedule("chemotaxis.chi_R", ScheduleShape::Linear) },
+        ParamSchedule { start: 4, to: 0.0, ..schedule("field.sigma_R", ScheduleShape::Step) },
+    ];
+
+    let mut sim = Simulation::new(&gpu, config);
+    for _ in 0..5 {
+        sim.step(&gpu);
+    }
+    // The last step run was step 4
+    assert_eq!(sim.agent_params.chi_R, 5.2);
+    assert_eq!((sim.config.chemotaxis.chi_R, sim.rd_params.sigma_R), (5.2, 0.0));
+}
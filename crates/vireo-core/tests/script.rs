@@ -0,0 +1,112 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{CullRegion, Interaction, InteractionScript, ScriptEvent};
+use vireo_core::{FieldChannel, SpeciesCapacity};
+
+const SCRIPT: &str = "
+seed: 7
+events:
+  - { step: 2, action: wall, center: [32, 40], radius: 3 }
+  - { step: 3, action: cull, shape: circle, center: [10, 10], radius: 12 }
+  - { step: 3, action: tag, id: 1, center: [48, 20], radius: 8 }
+  - { step: 5, action: param, param: chemotaxis.chi_R, value: 12.0 }
+";
+
+#[test]
+fn scripts_parse_round_trip_and_reject_bad_events() {
+    let script = InteractionScript::parse(SCRIPT).unwrap();
+    assert_eq!(script.seed, Some(7));
+    assert_eq!(script.events[0], ScriptEvent {
+        step: 2,
+        action: Interaction::Wall { center: [32.0, 40.0], radius: 3.0, erase: false },
+    });
+    assert_eq!(script.events[1].action, Interaction::Cull { region: CullRegion::Circle { center: [10.0, 10.0], radius: 12.0 } });
+    assert_eq!(script.at(3).count(), 2);
+    assert_eq!(InteractionScript::parse(&serde_yaml::to_string(&script).unwrap()).unwrap(), script);
+
+    let bad = |yaml: &str| InteractionScript::parse(yaml).unwrap_err();
+    assert!(bad("events:\n  - { step: 4, action: tag, id: 1, center: [0, 0], radius: 1 }\n  - { step: 3, action: tag, id: 2, center: [0, 0], radius: 1 }\n")
+        .contains("comes before"));
+    assert!(bad("events:\n  - { step: 1, action: param, param: world.dt, value: 1.0 }\n").contains("cannot be set"));
+    assert!(bad("events:\n  - { step: 1, action: wall, center: [0, 0], radius: -1 }\n").contains("must be finite"));
+    assert!(bad("events:\n  - { step: 1, action: spawn, species: wolves, center: [0, 0], radius: 1, count: 5 }\n").contains("unknown species `wolves`"));
+    let spawn = InteractionScript::parse("events:\n  - { step: 1, action: spawn, species: predators, center: [8, 8], radius: 2, count: 5 }\n").unwrap();
+    assert_eq!(spawn.events[0].action, Interaction::Spawn { species: "predators".into(), center: [8.0, 8.0], radius: 2.0, count: 5 });
+
+    let mut streaming = small_config(|config| config.agents.herbivores = 200);
+    streaming.streaming.enabled = true;
+    assert!(script.check_compatible(&streaming).is_err());
+}
+
+#[test]
+fn rewinding_keeps_params_and_walls_from_the_rewound_step() {
+    let mut script = InteractionScript::parse(SCRIPT).unwrap();
+    assert_eq!(script.lost_on_restore(4).count(), 1);
+
+    // The cull and tag are undone with the agents; the wall and param stay
+    script.rewind_to(3);
+    let kept: Vec<_> = script.events.iter().map(|e| (e.step, matches!(e.action, Interaction::Param { .. }))).collect();
+    assert_eq!(kept, vec![(2, false), (3, true)]);
+}
+
+#[test]
+fn replaying_a_script_repeats_the_run() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let script = InteractionScript::parse(SCRIPT).unwrap();
+    let replay = || {
+        let mut sim = Simulation::new(&gpu, small_config(|config| config.agents.herbivores = 200));
+        for step in 0..8 {
+            for action in script.at(step) {
+                sim.apply_interaction(&gpu, action).unwrap();
+            }
+            if step == 3 {
+                // The cull ran before the step; the tag saw only the survivors
+                sim.sync_agents(&gpu);
+                let region = CullRegion::Circle { center: [10.0, 10.0], radius: 12.0 };
+                assert!(!sim.agent_manager.agents.iter().any(|a| a.is_alive() && region.contains(a.pos)));
+                assert!(sim.agent_manager.agents.iter().any(|a| a.cohort == 1));
+            }
+            sim.step(&gpu);
+        }
+        assert_eq!(sim.config.chemotaxis.chi_R, 12.0);
+        assert!(sim.obstacles.field.sample([32.0, 40.0]) < 0.0);
+        sim.state_hash(&gpu)
+    };
+    assert_eq!(replay(), replay());
+}
+
+#[test]
+fn spawns_and_injections_land_in_their_disc() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|config| config.agents.herbivores = 200);
+    config.agents.capacity = SpeciesCapacity { plants: 0, herbivores: 430, predators: 0 }; // 400 spawned
+    let mut sim = Simulation::new(&gpu, config);
+    let inside = |p: [f32; 2], center: [f32; 2], radius: f32| (p[0] - center[0]).powi(2) + (p[1] - center[1]).powi(2) <= radius * radius;
+
+    // Only the 30 free slots are filled, all inside the disc
+    let spawn = Interaction::Spawn { species: "herbivores".into(), center: [20.0, 30.0], radius: 4.0, count: 50 };
+    sim.apply_interaction(&gpu, &spawn).unwrap();
+    sim.sync_agents(&gpu);
+    let herbivores = &sim.agent_manager.agents[sim.agent_manager.layout.range(1).start as usize..][..430];
+    assert!(herbivores.iter().all(|a| a.is_alive()));
+    let spawned: Vec<_> = herbivores[400..].iter().map(|a| a.pos).collect();
+    assert!(spawned.iter().all(|&p| inside(p, [20.0, 30.0], 4.0)), "{:?}", spawned);
+
+    // Resource rises by `amount` at cells whose centres are in the disc and nowhere else
+    sim.sync_field(&gpu);
+    let before = sim.field_manager.channel_f32(FieldChannel::Resource);
+    let inject = Interaction::Inject { center: [40.0, 10.0], radius: 3.0, amount: 0.25 };
+    sim.apply_interaction(&gpu, &inject).unwrap();
+    sim.sync_field(&gpu);
+    let after = sim.field_manager.channel_f32(FieldChannel::Resource);
+    for y in 0..64 {
+        for x in 0..64 {
+            let i = sim.field_manager.get_index(x, y);
+            let rise = after[i] - before[i];
+            let expected = if inside([x as f32 + 0.5, y as f32 + 0.5], [40.0, 10.0], 3.0) { 0.25 } else { 0.0 };
+            assert!((rise - expected).abs() < 1e-3, "cell ({}, {}): {}", x, y, rise);
+        }
+    }
+}
@@ -25,3 +25,16 @@ fn seeding_runs_for_common_sizes() {
         assert!(has_resources, "World {}x{} should have some resources", w, h);
     }
 }
+
+#[test]
+fn seeding_is_independent_of_thread_count() {
+    let seed_on = |threads| {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        pool.install(|| {
+            let mut fm = FieldManager::new([300, 200]);
+            fm.seed_resources(42);
+            fm.texels
+        })
+    };
+    assert_eq!(seed_on(1), seed_on(4));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::{MemoryEstimate, SeparationConfig, SimulationConfig};
+
+/// Distance after one step between a herbivore and an agent of `other_kind`
+/// that start on the same point, every other slot dead
+fn gap_after_one_step(gpu: &GpuDevice, separation: SeparationConfig, other_kind: u32) -> f32 {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 100;
+    config.separation = separation;
+    let mut sim = Simulation::new(gpu, config);
+    sim.sync_agents(gpu);
+
+    let agents = &mut sim.agent_manager.agents;
+    let first = |kind| agents.iter().position(|a| a.kind == kind).unwrap();
+    let pair = [first(1), if other_kind == 1 { first(1) + 1 } else { first(other_kind) }];
+    for (i, a) in agents.iter_mut().enumerate() {
+        a.alive = pair.contains(&i) as u32;
+        a.pos = [32.3, 32.6];
+        a.vel = [0.0, 0.0];
+    }
+    sim.upload_agents(gpu);
+    sim.step(gpu);
+    sim.sync_agents(gpu);
+
+    let [a, b] = pair.map(|i| sim.agent_manager.agents[i].position());
+    a.distance(b)
+}
+
+#[test]
+fn overlapping_agents_push_apart() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let on = SeparationConfig { enabled: true, radius: 0.5, stiffness: 1.0, ..Default::default() };
+
+    let piled = gap_after_one_step(&gpu, SeparationConfig::default(), 1);
+    let separated = gap_after_one_step(&gpu, on.clone(), 1);
+    assert!(separated > piled + 0.5, "{} vs {}", separated, piled);
+
+    // Across species only with `cross_species`
+    let predator = gap_after_one_step(&gpu, on.clone(), 2);
+    assert!(predator > piled + 0.5, "{} vs {}", predator, piled);
+    let same_only = SeparationConfig { cross_species: false, ..on.clone() };
+    assert!((gap_after_one_step(&gpu, same_only, 2) - gap_after_one_step(&gpu, SeparationConfig::default(), 2)).abs() < 1e-4);
+
+    let mut config = SimulationConfig { separation: on.clone(), ..SimulationConfig::default() };
+    let plain = MemoryEstimate::for_config(&SimulationConfig::default());
+    let hashed = MemoryEstimate::for_config(&config);
+    assert_eq!(hashed.agents - plain.agents, config.agents.total_slots() * 32);
+
+    config.separation.radius = 3.0;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("separation.radius"), "{}", error);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{Agent, FieldData, StateSnapshot, STATE_HASH_QUANTUM};
+use vireo_core::SimulationConfig;
+
+fn sample_snapshot() -> StateSnapshot {
+    let mut agent = Agent::new(glam::Vec2::new(3.5, 7.25), 0.8, 1);
+    agent.cohort = 4;
+    StateSnapshot {
+        step: 1234,
+        size: [64, 64],
+        field: (0..64 * 64).map(|i| FieldData::new(i as f32 * 0.01, 0.5)).collect(),
+        agents: vec![agent; 300],
+    }
+}
+
+#[test]
+fn bytes_round_trip() {
+    let snapshot = sample_snapshot();
+    let bytes = snapshot.to_bytes();
+    assert_eq!(&bytes[..8], b"VIREOSNP");
+    assert_eq!(bytes.len(), 32 + 64 * 64 * 16 + 300 * 64);
+
+    let parsed = StateSnapshot::from_bytes(&bytes).unwrap();
+    assert_eq!((parsed.step, parsed.size), (1234, [64, 64]));
+    assert_eq!(bytemuck::cast_slice::<_, u8>(&parsed.field), bytemuck::cast_slice::<_, u8>(&snapshot.field));
+    assert_eq!(bytemuck::cast_slice::<_, u8>(&parsed.agents), bytemuck::cast_slice::<_, u8>(&snapshot.agents));
+}
+
+#[test]
+fn foreign_and_damaged_files_are_rejected() {
+    let bytes = sample_snapshot().to_bytes();
+    assert!(StateSnapshot::from_bytes(b"not a snapshot at all, really not").is_err());
+    assert!(StateSnapshot::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    let mut future = bytes.clone();
+    future[8] = 99;
+    let error = StateSnapshot::from_bytes(&future).unwrap_err();
+    assert!(error.to_string().contains("version 99"), "{}", error);
+}
+
+#[test]
+fn compatibility_requires_matching_world_and_agent_count() {
+    let snapshot = sample_snapshot();
+    assert!(snapshot.check_compatible(&small_config(|_| {})).is_ok());
+
+    let mut config = small_config(|_| {});
+    config.world.size = [128, 64];
+    assert!(snapshot.check_compatible(&config).unwrap_err().contains("world.size"));
+
+    let mut config = small_config(|_| {});
+    config.agents.herbivores = 99;
+    assert!(snapshot.check_compatible(&config).unwrap_err().contains("agents.herbivores"));
+}
+
+#[test]
+fn diff_reports_cell_deltas_and_changed_agents() {
+    let a = sample_snapshot();
+    let same = a.diff(&a, 0.0).unwrap();
+    assert_eq!((same.max_abs_R(), same.max_abs_W(), same.agents_differing), (0.0, 0.0, 0));
+
+    let mut b = a.clone();
+    b.field[5].R -= 0.25;
+    b.field[9].W += 0.125;
+    b.agents[0].pos[0] += 0.01;
+    b.agents[1].alive = 0;
+    let diff = a.diff(&b, 0.0).unwrap();
+    assert_eq!((diff.delta_R[5], diff.delta_W[9]), (-0.25, 0.125));
+    assert_eq!((diff.max_abs_R(), diff.max_abs_W()), (0.25, 0.125));
+    assert_eq!(diff.agents_differing, 2);
+    // Small moves fall under the tolerance; a death never does
+    assert_eq!(a.diff(&b, 0.1).unwrap().agents_differing, 1);
+
+    let mut other_world = a.clone();
+    other_world.agents.pop();
+    assert!(a.diff(&other_world, 0.0).unwrap_err().contains("agent slots"));
+}
+
+#[test]
+fn restored_run_continues_like_the_original() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut original = Simulation::new(&gpu, small_config(|_| {}));
+    for _ in 0..50 {
+        original.step(&gpu);
+    }
+    let saved = StateSnapshot::from_bytes(&original.snapshot(&gpu).to_bytes()).unwrap();
+    assert_eq!(saved.step, 50);
+    for _ in 0..30 {
+        original.step(&gpu);
+    }
+
+    let mut resumed = Simulation::new(&gpu, small_config(|_| {}));
+    resumed.restore(&gpu, &saved).unwrap();
+    assert_eq!(resumed.current_step(), 50);
+    for _ in 0..30 {
+        resumed.step(&gpu);
+    }
+
+    let (a, b) = (original.snapshot(&gpu), resumed.snapshot(&gpu));
+    assert_eq!(a.to_bytes(), b.to_bytes());
+}
+
+#[test]
+fn state_hash_ignores_rounding_noise_and_dead_slots() {
+    let snapshot = sample_snapshot();
+    let hash = snapshot.state_hash();
+    assert_eq!(hash, sample_snapshot().state_hash());
+
+    // Below the quantum and at another step: same state
+    let mut noisy = sample_snapshot();
+    noisy.step += 10;
+    noisy.field[5].R += STATE_HASH_QUANTUM * 0.1;
+    noisy.agents[0].pos[0] += STATE_HASH_QUANTUM * 0.1;
+    assert_eq!(noisy.state_hash(), hash);
+
+    // Whatever a dead slot still holds does not count, its death does
+    let mut dead = sample_snapshot();
+    dead.agents[7].kill();
+    let dead_hash = dead.state_hash();
+    assert_ne!(dead_hash, hash);
+    dead.agents[7].energy = 42.0;
+    dead.agents[7].pos = [1.0, 2.0];
+    assert_eq!(dead.state_hash(), dead_hash);
+
+    let mut moved = sample_snapshot();
+    moved.agents[299].vel[1] += 0.01;
+    assert_ne!(moved.state_hash(), hash);
+    let mut grown = sample_snapshot();
+    grown.field[64 * 64 - 1].W += 0.01;
+    assert_ne!(grown.state_hash(), hash);
+}
+
+#[test]
+fn state_hash_sequences_repeat_for_the_same_seed() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let hashes = |config: SimulationConfig| {
+        let mut sim = Simulation::new(&gpu, config);
+        (0..3).map(|_| {
+            sim.step(&gpu);
+            sim.state_hash(&gpu)
+        }).collect::<Vec<_>>()
+    };
+
+    let first = hashes(small_config(|_| {}));
+    assert_eq!(first, hashes(small_config(|_| {})));
+    assert!(first[0] != first[1] && first[1] != first[2], "{:x?}", first);
+
+    let mut reseeded = small_config(|_| {});
+    reseeded.world.seed += 1;
+    assert_ne!(hashes(reseeded), first);
+}
+
+#[test]
+fn file_name_matches_the_stored_step() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, small_config(|_| {}));
+    for _ in 0..3 {
+        sim.step(&gpu);
+    }
+    let state = sim.snapshot(&gpu);
+    assert_eq!(state.file_name(), "snapshot_0003.bin");
+
+    let dir = std::env::temp_dir().join(format!("vireo-snapshot-name-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(state.file_name());
+    state.write(&path).unwrap();
+    let loaded = StateSnapshot::read(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(loaded.step, 3);
+    assert_eq!(loaded.file_name(), state.file_name());
+}
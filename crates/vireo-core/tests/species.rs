@@ -0,0 +1,49 @@
+use glam::Vec2;
+use vireo_core::sim::{Agent, SpeciesTracker};
+use vireo_core::SPECIES;
+
+fn agent(kind: u32, energy: f32) -> Agent {
+    Agent::new(Vec2::ZERO, energy, kind)
+}
+
+#[test]
+fn counts_alive_and_mean_energy_per_species() {
+    let mut agents = vec![agent(0, 2.0), agent(1, 1.0), agent(1, 3.0), agent(2, 5.0)];
+    agents[3].kill();
+    let mut tracker = SpeciesTracker::new(&agents);
+    let stats = tracker.sample(&agents);
+
+    assert_eq!(stats.iter().map(|s| s.name()).collect::<Vec<_>>(), SPECIES);
+    assert_eq!(stats.iter().map(|s| s.alive).collect::<Vec<_>>(), vec![1, 2, 0]);
+    assert_eq!(stats.iter().map(|s| s.mean_energy).collect::<Vec<_>>(), vec![2.0, 2.0, 0.0]);
+    assert!(stats.iter().all(|s| s.births == 0 && s.deaths == 0));
+}
+
+#[test]
+fn births_and_deaths_come_from_slot_transitions_between_samples() {
+    let mut agents = vec![agent(1, 1.0), agent(1, 1.0), agent(2, 1.0), agent(0, 1.0)];
+    agents[3].kill();
+    let mut tracker = SpeciesTracker::new(&agents);
+
+    // One herbivore dies, a predator is born into the free slot, and a slot
+    // changes hands from a herbivore to a plant
+    agents[0].kill();
+    agents[3] = agent(2, 1.0);
+    agents[1] = agent(0, 1.0);
+    let stats = tracker.sample(&agents);
+    assert_eq!(stats.iter().map(|s| (s.births, s.deaths)).collect::<Vec<_>>(), vec![(1, 0), (0, 2), (1, 0)]);
+
+    // Events are counted once
+    let stats = tracker.sample(&agents);
+    assert!(stats.iter().all(|s| s.births == 0 && s.deaths == 0));
+}
+
+#[test]
+fn metrics_are_listed_in_column_order() {
+    let agents = vec![agent(1, 4.0)];
+    let stats = SpeciesTracker::new(&agents).sample(&agents);
+    let names: Vec<_> = stats[1].metrics().iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, ["alive", "mean_energy", "births", "deaths"]);
+    assert_eq!(stats[1].metrics()[0].1, 1.0);
+    assert_eq!(stats[1].metrics()[1].1, 4.0);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::small_config;
+use vireo_core::sim::{FieldManager, ReferenceField};
+use vireo_core::{DiffusionStream, DtPolicy, FieldChannel, RDParams, SimulationConfig, DT_SAFETY, PRESETS};
+
+#[test]
+fn presets_run_within_their_limit() {
+    for name in PRESETS {
+        let config = SimulationConfig::preset(name).unwrap();
+        assert!(config.world.dt <= config.dt_limit().max_dt(), "{}: dt {} above {}", name, config.world.dt, config.dt_limit());
+    }
+}
+
+#[test]
+fn limits_follow_the_fastest_field_and_agent() {
+    let mut config = small_config(|_| {});
+    config.field.D_R = 0.5;
+    config.field.D_W = 0.2;
+    config.chemotaxis.v_max = 2.0;
+    config.chemotaxis.disperse_energy = 0.0;
+    let limit = config.dt_limit();
+    assert!((limit.diffusion - 0.5).abs() < 1e-6, "1 / (4 D_R): {}", limit);
+    assert!((limit.advection - 0.5).abs() < 1e-6, "1 / v_max: {}", limit);
+
+    // Dispersers outrun foragers, but only when agents disperse at all
+    config.chemotaxis.disperse_energy = 0.5;
+    config.chemotaxis.disperse_speed = 2.0;
+    assert!((config.dt_limit().advection - 0.25).abs() < 1e-6);
+    assert_eq!(config.dt_limit().bound_by(), "advection");
+
+    // A stream three times as fast along its course lowers the diffusion limit by its spread
+    config.diffusion.enabled = true;
+    config.diffusion.streams.push(DiffusionStream { from: [0.0, 32.0], to: [64.0, 32.0], width: 4.0, along: 3.0, across: 1.0 });
+    assert!((config.dt_limit().diffusion - 0.5 / (0.5 * 5.0)).abs() < 1e-6, "{}", config.dt_limit());
+
+    // Nothing diffusing or moving leaves no limit
+    config.field.D_R = 0.0;
+    config.field.D_W = 0.0;
+    config.chemotaxis.v_max = 0.0;
+    assert_eq!(config.dt_limit().max_dt(), f32::INFINITY);
+}
+
+#[test]
+fn dt_policy_clamps_refuses_or_ignores() {
+    let mut config = small_config(|_| {});
+    config.world.dt = 2.0;
+    let max_dt = config.dt_limit().max_dt();
+
+    let mut clamped = config.clone();
+    assert_eq!(clamped.enforce_dt_limit(), Ok(Some(2.0)));
+    assert!((clamped.world.dt - max_dt * DT_SAFETY).abs() < 1e-6);
+    assert_eq!(clamped.enforce_dt_limit(), Ok(None), "a clamped dt is within the limit");
+
+    let mut refused = config.clone();
+    refused.world.dt_policy = DtPolicy::Refuse;
+    let error = refused.enforce_dt_limit().unwrap_err();
+    assert_eq!(error.field, "world.dt");
+    assert_eq!(refused.world.dt, 2.0);
+
+    let mut ignored = config.clone();
+    ignored.world.dt_policy = DtPolicy::Ignore;
+    assert_eq!(ignored.enforce_dt_limit(), Ok(None));
+    assert_eq!(ignored.world.dt, 2.0);
+
+    let mut stable = small_config(|_| {});
+    stable.world.dt_policy = DtPolicy::Refuse;
+    assert_eq!(stable.enforce_dt_limit(), Ok(None));
+}
+
+#[test]
+fn clamped_dt_keeps_the_reference_field_bounded() {
+    let mut config = small_config(|_| {});
+    config.field.D_R = 1.0;
+    config.field.D_W = 1.0;
+    config.world.dt = 0.4;
+    assert!(config.world.dt > config.dt_limit().diffusion);
+
+    let mut field = FieldManager::new(config.world.size);
+    field.seed_resources(config.world.seed);
+    let occupancy: Vec<u32> = (0..64 * 64).map(|i| (i % 7 == 0) as u32 * 3).collect();
+    // Largest R or W seen over 200 steps, infinite once a value stops being finite
+    let peak = |config: &SimulationConfig| {
+        let mut reference = ReferenceField::<f64>::new(&field);
+        let params = RDParams::from(config);
+        let mut peak = 0.0f64;
+        for _ in 0..200 {
+            reference.step(&params, &occupancy, 1.0);
+            for c in [FieldChannel::Resource, FieldChannel::Waste] {
+                peak = reference.channel(c).into_iter().fold(peak, |p, v| if v.is_finite() { p.max(v) } else { f64::INFINITY });
+            }
+        }
+        peak
+    };
+
+    let unstable = peak(&config);
+    assert!(unstable > 1e6, "dt above the limit should blow up, peaked at {}", unstable);
+
+    config.enforce_dt_limit().unwrap();
+    let stable = peak(&config);
+    assert!(stable < 100.0, "clamped dt should stay bounded, peaked at {}", stable);
+}
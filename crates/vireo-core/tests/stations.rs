@@ -0,0 +1,76 @@
+mod common;
+
+use common::gpu_or_skip;
+use glam::Vec2;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::{Agent, StationSample};
+use vireo_core::{FieldChannel, SimulationConfig, StationConfig};
+
+fn agent(kind: u32, x: f32, y: f32) -> Agent {
+    Agent::new(Vec2::new(x, y), 1.0, kind)
+}
+
+fn station(name: &str, pos: [f32; 2], radius: f32) -> StationConfig {
+    StationConfig { name: name.to_string(), pos, radius, every: 1 }
+}
+
+#[test]
+fn stations_count_the_alive_agents_they_cover() {
+    let mut dead = agent(1, 20.5, 20.5);
+    dead.kill();
+    let agents = vec![
+        agent(0, 20.2, 20.9), agent(1, 20.5, 20.5), agent(1, 23.0, 20.5), agent(2, 25.0, 20.5), dead,
+    ];
+
+    // Without a radius only the station's own cell counts
+    let cell = station("pond", [20.5, 20.5], 0.0);
+    assert_eq!(cell.cell(), [20, 20]);
+    let sample = StationSample::read(&cell, [0.7, 0.1, 0.0, 0.0], &agents);
+    assert_eq!(sample, StationSample { resource: 0.7, waste: 0.1, occupancy: [1, 1, 0] });
+
+    let disc = station("pond", [20.5, 20.5], 3.0);
+    assert_eq!(StationSample::read(&disc, [0.0; 4], &agents).occupancy, [1, 2, 0]);
+
+    let every = StationConfig { every: 5, ..disc };
+    assert!(every.is_due(0) && every.is_due(10) && !every.is_due(3));
+}
+
+#[test]
+fn invalid_stations_are_rejected() {
+    let with = |stations: Vec<StationConfig>| SimulationConfig { stations, ..SimulationConfig::default() }.validate();
+    assert!(with(vec![station("a", [10.0, 10.0], 2.0), station("b-2", [0.0, 127.5], 0.0)]).is_ok());
+
+    let field = |stations| with(stations).unwrap_err().field;
+    assert_eq!(field(vec![station("../up", [10.0, 10.0], 0.0)]), "stations[0].name");
+    assert_eq!(field(vec![station("a", [10.0, 10.0], 0.0), station("a", [5.0, 5.0], 0.0)]), "stations[1].name");
+    assert_eq!(field(vec![station("a", [128.0, 10.0], 0.0)]), "stations[0].pos");
+    assert_eq!(field(vec![station("a", [10.0, 10.0], -1.0)]), "stations[0].radius");
+    assert_eq!(field(vec![StationConfig { every: 0, ..station("a", [10.0, 10.0], 0.0) }]), "stations[0].every");
+}
+
+#[test]
+fn station_readings_match_the_synced_field() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 200;
+    config.stations = vec![station("centre", [32.5, 32.5], 8.0), station("corner", [3.0, 60.0], 0.0)];
+    let mut sim = Simulation::new(&gpu, config.clone());
+    for _ in 0..20 {
+        sim.step(&gpu);
+    }
+    sim.sync_field(&gpu);
+    sim.sync_agents(&gpu);
+
+    let stations: Vec<_> = config.stations.iter().collect();
+    let samples = sim.read_stations(&gpu, &stations);
+    assert_eq!(samples.len(), 2);
+    let (r, w) = (sim.field_manager.channel_f32(FieldChannel::Resource), sim.field_manager.channel_f32(FieldChannel::Waste));
+    for (station, sample) in config.stations.iter().zip(&samples) {
+        let [x, y] = station.cell();
+        let i = (y * 64 + x) as usize;
+        assert_eq!((sample.resource, sample.waste), (r[i], w[i]), "{}", station.name);
+        assert_eq!(*sample, StationSample::read(station, [r[i], w[i], 0.0, 0.0], &sim.agent_manager.agents));
+    }
+    assert!(samples[0].occupancy.iter().sum::<u32>() > 0, "herbivores spawn around the centre");
+}
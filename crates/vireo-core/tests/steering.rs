@@ -0,0 +1,194 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::{shaders, SimulationConfig, SpeciesAttraction, SteeringParams, SteeringWeights};
+
+const DEMO: &str = include_str!("../../../lab/configs/best-demo.yaml");
+
+/// Herbivores driven only by the given weights (no field or memory pull)
+fn herbivores_only(weights: SteeringWeights) -> SimulationConfig {
+    let mut config = small_config(|_| {});
+    config.steering.herbivores = SteeringWeights { chemotaxis: 0.0, memory: 0.0, ..weights };
+    config
+}
+
+/// Kill every agent, then place live herbivores at `agents` (position, velocity)
+fn place(sim: &mut Simulation, gpu: &GpuDevice, agents: &[([f32; 2], [f32; 2])]) -> Vec<usize> {
+    let kinds: Vec<_> = agents.iter().map(|&(pos, vel)| (1, pos, vel)).collect();
+    place_kinds(sim, gpu, &kinds)
+}
+
+/// Kill every agent, then place live agents at `agents` (kind, position, velocity)
+fn place_kinds(sim: &mut Simulation, gpu: &GpuDevice, agents: &[(u32, [f32; 2], [f32; 2])]) -> Vec<usize> {
+    for a in sim.agent_manager.agents.iter_mut() {
+        a.kill();
+    }
+    let mut next = [0, 1, 2].map(|kind| sim.agent_manager.layout.range(kind));
+    let mut slots = Vec::new();
+    for &(kind, pos, vel) in agents {
+        let slot = next[kind as usize].next().expect("a free slot of the kind") as usize;
+        let a = &mut sim.agent_manager.agents[slot];
+        *a = vireo_core::Agent::new(glam::Vec2::from(pos), 1.0, kind);
+        a.vel = vel;
+        slots.push(slot);
+    }
+    sim.upload_agents(gpu);
+    slots
+}
+
+#[test]
+fn weights_parse_per_species_and_default_to_the_old_behavior() {
+    let text = format!("{}\nsteering:\n  herbivores: {{ noise: 0.5, crowding: 2.0 }}\n", DEMO);
+    let config = vireo_core::parse_config(&text).unwrap();
+    assert_eq!(config.steering.plants, SteeringWeights::default());
+    assert_eq!(config.steering.herbivores, SteeringWeights { noise: 0.5, crowding: 2.0, ..SteeringWeights::default() });
+    assert!(config.steering.needs_neighbours());
+    assert!(!small_config(|_| {}).steering.needs_neighbours());
+
+    let params = SteeringParams::new(&config);
+    assert_eq!(params.noise, [0.0, 0.5, 0.0, 0.0]);
+    assert_eq!(params.crowding, [0.0, 2.0, 0.0, 0.0]);
+    assert_eq!(params.chemotaxis, [1.0, 1.0, 1.0, 0.0]);
+    assert_eq!((params.size, params.neighbours), (config.world.size, 1));
+    assert_eq!(params.attraction, [[0.0; 4]; 3]);
+    assert_eq!(std::mem::size_of::<SteeringParams>(), 160);
+
+    let mut bad = small_config(|_| {});
+    bad.steering.predators.flocking = f32::NAN;
+    assert!(bad.validate().unwrap_err().to_string().contains("steering.predators.flocking"));
+}
+
+#[test]
+fn both_shaders_declare_the_same_steering_params() {
+    let fields = |source: &str| {
+        let body = &source[source.find("struct SteeringParams {").expect("struct declared")..];
+        body[body.find('{').unwrap() + 1..body.find('}').unwrap()]
+            .lines()
+            .filter_map(|line| line.split(':').next())
+            .map(|field| field.trim().to_string())
+            .filter(|field| !field.is_empty() && !field.starts_with("//"))
+            .collect::<Vec<_>>()
+    };
+    let expected = ["chemotaxis", "noise", "crowding", "flocking", "memory", "obstacles", "attraction", "size", "seed", "neighbours"];
+    assert_eq!(fields(&shaders::agent_step()), expected);
+    assert_eq!(fields(&shaders::neighbour_grid()), expected);
+}
+
+#[test]
+fn a_zero_weight_removes_only_that_species_term() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut baseline = Simulation::new(&gpu, small_config(|_| {}));
+    let mut config = small_config(|_| {});
+    config.steering.herbivores.chemotaxis = 0.0;
+    let mut sim = Simulation::new(&gpu, config);
+
+    // One step: every species still reads the same field
+    for s in [&mut baseline, &mut sim] {
+        s.step(&gpu);
+        s.sync_agents(&gpu);
+    }
+    let moved = |s: &Simulation, kind: u32| {
+        s.agent_manager.agents.iter().filter(|a| a.kind == kind).map(|a| a.vel).collect::<Vec<_>>()
+    };
+    assert_eq!(moved(&sim, 0), moved(&baseline, 0));
+    assert_eq!(moved(&sim, 2), moved(&baseline, 2));
+    assert_ne!(moved(&sim, 1), moved(&baseline, 1));
+
+    // With no other term herbivores only coast on their spawn velocity
+    let damping = 1.0 - sim.config.chemotaxis.gamma;
+    let spawned = Simulation::new(&gpu, small_config(|_| {})).agent_manager.agents;
+    for (a, b) in sim.agent_manager.agents.iter().zip(&spawned).filter(|(a, _)| a.kind == 1) {
+        assert_eq!(a.vel, [b.vel[0] * damping, b.vel[1] * damping]);
+    }
+}
+
+#[test]
+fn noise_kicks_every_agent_by_its_weight_and_repeats_for_a_seed() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let config = herbivores_only(SteeringWeights { noise: 0.4, ..SteeringWeights::default() });
+    let spawned = Simulation::new(&gpu, config.clone()).agent_manager.agents;
+    let run = || {
+        let mut sim = Simulation::new(&gpu, config.clone());
+        sim.step(&gpu);
+        sim.sync_agents(&gpu);
+        sim.agent_manager.agents.clone()
+    };
+    let agents = run();
+
+    // Undamped, each herbivore moved off its spawn velocity by exactly weight · dt
+    let damping = 1.0 - config.chemotaxis.gamma;
+    let kicks: Vec<_> = agents.iter().zip(&spawned)
+        .filter(|(a, _)| a.kind == 1 && a.is_alive())
+        .map(|(a, b)| a.velocity() / damping - b.velocity())
+        .collect();
+    assert!(!kicks.is_empty());
+    for kick in &kicks {
+        assert!((kick.length() - 0.4 * config.world.dt).abs() < 1e-5, "{:?}", kick);
+    }
+    assert_ne!(kicks[0], kicks[1], "each slot draws its own heading");
+    assert_eq!(bytemuck::cast_slice::<_, u8>(&agents), bytemuck::cast_slice::<_, u8>(&run()));
+}
+
+#[test]
+fn crowding_pushes_neighbours_apart() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, herbivores_only(SteeringWeights { crowding: 3.0, ..SteeringWeights::default() }));
+    let slots = place(&mut sim, &gpu, &[([20.5, 20.5], [0.0; 2]), ([21.5, 20.5], [0.0; 2])]);
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    // Each sees one agent across the central difference: |∇n| = 1/2, saturated to 1/3
+    let config = &sim.config;
+    let expected = 3.0 / 3.0 * config.world.dt * (1.0 - config.chemotaxis.gamma);
+    let [left, right] = [slots[0], slots[1]].map(|s| sim.agent_manager.agents[s].vel);
+    assert!((left[0] + expected).abs() < 1e-5 && left[1] == 0.0, "{:?}", left);
+    assert!((right[0] - expected).abs() < 1e-5 && right[1] == 0.0, "{:?}", right);
+}
+
+#[test]
+fn flocking_pulls_toward_the_local_mean_velocity() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, herbivores_only(SteeringWeights { flocking: 2.0, ..SteeringWeights::default() }));
+    let slots = place(&mut sim, &gpu, &[([30.2, 30.5], [1.0, 0.0]), ([30.8, 30.5], [0.0, 0.0]), ([50.5, 50.5], [0.0, 1.0])]);
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    // The first two share a mean of (0.5, 0); the third is alone and keeps its heading
+    let [fast, slow, alone] = [slots[0], slots[1], slots[2]].map(|s| sim.agent_manager.agents[s].vel);
+    assert!(slow[0] > 0.0 && fast[0] < 1.0 - sim.config.chemotaxis.gamma, "{:?} {:?}", fast, slow);
+    assert_eq!(alone[0], 0.0);
+    assert!((alone[1] - (1.0 - sim.config.chemotaxis.gamma)).abs() < 1e-6);
+}
+
+#[test]
+fn predators_climb_herbivore_density_while_herbivores_flee_them() {
+    let text = format!("{}\nsteering:\n  predators: {{ attraction: {{ herbivores: 3.0 }} }}\n", DEMO);
+    let parsed = vireo_core::parse_config(&text).unwrap();
+    assert_eq!(parsed.steering.predators.attraction, SpeciesAttraction { herbivores: 3.0, ..SpeciesAttraction::default() });
+    assert!(parsed.steering.needs_neighbours());
+    assert_eq!(SteeringParams::new(&parsed).attraction[1], [0.0, 0.0, 3.0, 0.0]);
+
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = small_config(|_| {});
+    let still = SteeringWeights { chemotaxis: 0.0, memory: 0.0, ..SteeringWeights::default() };
+    config.steering.plants = still;
+    config.steering.herbivores = SteeringWeights { attraction: SpeciesAttraction { predators: -3.0, ..SpeciesAttraction::default() }, ..still };
+    config.steering.predators = SteeringWeights { attraction: SpeciesAttraction { herbivores: 3.0, ..SpeciesAttraction::default() }, ..still };
+    let mut sim = Simulation::new(&gpu, config);
+    let slots = place_kinds(&mut sim, &gpu, &[(2, [20.5, 20.5], [0.0; 2]), (1, [21.5, 20.5], [0.0; 2]), (1, [40.5, 40.5], [0.0; 2])]);
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    // Each sees the other across the central difference: |∇n| = 1/2, saturated to 1/3
+    let config = &sim.config;
+    let expected = 3.0 / 3.0 * config.world.dt * (1.0 - config.chemotaxis.gamma);
+    let [predator, prey, alone] = [slots[0], slots[1], slots[2]].map(|s| sim.agent_manager.agents[s].vel);
+    assert!((predator[0] - expected).abs() < 1e-5 && predator[1] == 0.0, "{:?}", predator);
+    assert!((prey[0] - expected).abs() < 1e-5 && prey[1] == 0.0, "{:?}", prey);
+    assert_eq!(alone, [0.0, 0.0], "herbivores ignore their own density");
+
+    let mut bad = small_config(|_| {});
+    bad.steering.herbivores.attraction.plants = f32::INFINITY;
+    assert!(bad.validate().unwrap_err().to_string().contains("steering.herbivores.attraction.plants"));
+}
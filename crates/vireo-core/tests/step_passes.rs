@@ -0,0 +1,36 @@
+mod common;
+
+use common::{gpu_or_skip, small_config};
+use vireo_core::gpu::{Simulation, StepPass};
+
+#[test]
+fn skipped_passes_leave_their_state_alone() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, small_config(|_| {}));
+    let start = sim.snapshot(&gpu);
+    let bytes = |s: &vireo_core::sim::StateSnapshot| (
+        bytemuck::cast_slice::<_, u8>(&s.field).to_vec(),
+        bytemuck::cast_slice::<_, u8>(&s.agents).to_vec(),
+    );
+
+    // Without the RD pass the field is untouched while agents still move
+    sim.skip = vec![StepPass::Rd];
+    sim.step(&gpu);
+    let (field, agents) = bytes(&sim.snapshot(&gpu));
+    assert_eq!(sim.current_step(), 1);
+    assert_eq!(field, bytes(&start).0);
+    assert_ne!(agents, bytes(&start).1);
+
+    // Without the agent pass nobody moves, and the field changes again
+    sim.restore(&gpu, &start).unwrap();
+    sim.skip = vec![StepPass::Agents];
+    sim.step(&gpu);
+    let (field, agents) = bytes(&sim.snapshot(&gpu));
+    assert_ne!(field, bytes(&start).0);
+    assert_eq!(agents, bytes(&start).1);
+
+    assert_eq!("demography".parse::<StepPass>(), Ok(StepPass::Demography));
+    assert!(StepPass::ALL.iter().all(|p| p.name().parse() == Ok(*p)));
+    let error = "diffusion".parse::<StepPass>().unwrap_err();
+    assert!(error.contains("steering, agents, demography, blooms, rd"), "{}", error);
+}
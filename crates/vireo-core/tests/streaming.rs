@@ -0,0 +1,95 @@
+use glam::Vec2;
+use half::f16;
+use vireo_core::sim::{Agent, ChunkStreamer, CohortTracker, FieldManager, procedural_resource};
+use vireo_core::{SimulationConfig, StreamingConfig};
+
+const CHUNK: u32 = 16;
+
+fn setup() -> (SimulationConfig, ChunkStreamer, FieldManager) {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.streaming = StreamingConfig { enabled: true, chunk: CHUNK, keep_radius: 1 };
+    let mut streamer = ChunkStreamer::new(&config.streaming, &config.world);
+    let mut field = FieldManager::new(config.world.size);
+    streamer.fill(&mut field);
+    (config, streamer, field)
+}
+
+#[test]
+fn window_matches_the_procedural_landscape() {
+    let (config, streamer, field) = setup();
+    let [ox, oy] = streamer.origin_cells();
+    assert_eq!([ox, oy], [-32, -32]);
+
+    let mut non_zero = 0;
+    for y in 0..64 {
+        for x in 0..64 {
+            let expect = procedural_resource(config.world.seed, ox + x as i64, oy + y as i64);
+            assert_eq!(field.get_resource(x, y), f16::from_f32(expect).to_f32());
+            non_zero += (expect > 0.0) as u32;
+        }
+    }
+    assert!(non_zero > 0 && non_zero < 64 * 64, "landscape has patches and gaps ({} cells)", non_zero);
+}
+
+#[test]
+fn shift_follows_the_focus_and_keeps_evolved_chunks() {
+    let (config, mut streamer, mut field) = setup();
+    let mut cohorts = CohortTracker::new();
+
+    // Window is 4x4 chunks; the central chunk is (2, 2)
+    assert_eq!(streamer.shift_for([40.0, 40.0]), [0, 0]);
+    assert_eq!(streamer.shift_for([50.0, 20.0]), [1, -1]);
+
+    // Mark the evolved state of window cell (0, 0), then move right and back
+    field.set_waste(0, 0, 0.25);
+    assert_eq!(streamer.shift([1, 0], &mut field, &mut [], &mut cohorts), 0);
+    let [ox, oy] = streamer.origin_cells();
+    assert_eq!([ox, oy], [-16, -32]);
+    assert_eq!(field.get_resource(0, 0), f16::from_f32(procedural_resource(config.world.seed, -16, -32)).to_f32());
+    assert_eq!(streamer.cached_chunks(), 4, "the column that left the window is cached");
+
+    streamer.shift([-1, 0], &mut field, &mut [], &mut cohorts);
+    assert_eq!(field.get_waste(0, 0), 0.25, "cached chunk comes back with its state");
+}
+
+#[test]
+fn far_chunks_are_evicted_and_regenerated() {
+    let (_, mut streamer, mut field) = setup();
+    let mut cohorts = CohortTracker::new();
+
+    field.set_waste(0, 0, 0.25);
+    streamer.shift([3, 0], &mut field, &mut [], &mut cohorts);
+    assert!(streamer.cached_chunks() > 0);
+    streamer.shift([3, 0], &mut field, &mut [], &mut cohorts);
+    // The marked chunk is now 5 chunks left of the window, beyond keep_radius = 1
+    streamer.shift([-6, 0], &mut field, &mut [], &mut cohorts);
+    assert_eq!(field.get_waste(0, 0), 0.0);
+}
+
+#[test]
+fn agents_move_with_the_window_and_leavers_die() {
+    let (_, mut streamer, mut field) = setup();
+    let mut agents = vec![
+        Agent::new(Vec2::new(40.0, 30.0), 1.0, 1),
+        Agent::new(Vec2::new(5.0, 30.0), 1.0, 1),
+    ];
+    let mut cohorts = CohortTracker::new();
+    cohorts.tag(&mut agents, 1, 0, |_| true);
+
+    let lost = streamer.shift([1, 0], &mut field, &mut agents, &mut cohorts);
+    assert_eq!(lost, 1);
+    assert_eq!(agents[0].pos, [24.0, 30.0]);
+    assert!(agents[0].is_alive() && !agents[1].is_alive());
+    assert_eq!(cohorts.stats(&agents)[0].max_dispersal, 0.0, "release points moved too");
+}
+
+#[test]
+fn streaming_requires_whole_chunks() {
+    let (mut config, _, _) = setup();
+    assert!(config.validate().is_ok());
+    config.streaming.chunk = 24;
+    assert_eq!(config.validate().unwrap_err().field, "streaming.chunk");
+    config.streaming.chunk = 32;
+    assert!(config.validate().is_err(), "64/32 leaves only 2x2 chunks");
+}
@@ -0,0 +1,72 @@
+use vireo_core::sim::{apply_sweep_point, sweep_design, ParamRange, SimRng, SweepStrategy};
+use vireo_core::{RngKind, SimulationConfig};
+
+fn ranges(n: usize) -> Vec<ParamRange> {
+    let names = ["chemotaxis.chi_R", "field.sigma_R", "field.D_R", "chemotaxis.gamma"];
+    (0..n).map(|i| ParamRange { param: names[i % 4].to_string(), lo: 0.0, hi: 1.0 }).collect()
+}
+
+fn rng() -> SimRng {
+    SimRng::new(RngKind::default(), 7)
+}
+
+#[test]
+fn ranges_parse_and_reject_unknown_or_inverted() {
+    let range: ParamRange = "chemotaxis.chi_R=2:10".parse().unwrap();
+    assert_eq!(range, ParamRange { param: "chemotaxis.chi_R".to_string(), lo: 2.0, hi: 10.0 });
+    assert_eq!(range.to_string(), "chemotaxis.chi_R=2:10");
+    assert!("world.dt=0:1".parse::<ParamRange>().unwrap_err().contains("cannot be swept"));
+    assert!("field.D_R=1:0".parse::<ParamRange>().is_err());
+    assert!("field.D_R=1".parse::<ParamRange>().is_err());
+    assert_eq!("lhs".parse::<SweepStrategy>(), Ok(SweepStrategy::LatinHypercube));
+}
+
+#[test]
+fn grid_is_a_full_factorial_capped_by_the_budget() {
+    let r = ["field.D_R=1:3".parse().unwrap(), "chemotaxis.chi_R=0:10".parse().unwrap()];
+    let points = sweep_design(SweepStrategy::Grid, &r, None, 3, &mut rng()).unwrap();
+    assert_eq!(points.len(), 9);
+    assert_eq!((points[0].clone(), points[1].clone(), points[8].clone()), (vec![1.0, 0.0], vec![1.0, 5.0], vec![3.0, 10.0]));
+    assert_eq!(sweep_design(SweepStrategy::Grid, &r, Some(4), 3, &mut rng()).unwrap().len(), 4);
+}
+
+#[test]
+fn latin_hypercube_fills_every_stratum_once() {
+    let n = 20;
+    let points = sweep_design(SweepStrategy::LatinHypercube, &ranges(3), Some(n), 0, &mut rng()).unwrap();
+    assert_eq!(points.len(), n as usize);
+    for d in 0..3 {
+        let mut strata: Vec<usize> = points.iter().map(|p| (p[d] * n as f32) as usize).collect();
+        strata.sort();
+        assert_eq!(strata, (0..n as usize).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn sobol_matches_the_reference_sequence() {
+    let points = sweep_design(SweepStrategy::Sobol, &ranges(3), Some(8), 0, &mut rng()).unwrap();
+    let expected = [
+        [0.0, 0.0, 0.0], [0.5, 0.5, 0.5], [0.75, 0.25, 0.25], [0.25, 0.75, 0.75],
+        [0.375, 0.375, 0.625], [0.875, 0.875, 0.125], [0.625, 0.125, 0.875], [0.125, 0.625, 0.375],
+    ];
+    for (p, e) in points.iter().zip(expected) {
+        assert_eq!(p.as_slice(), e.as_slice());
+    }
+    assert!(sweep_design(SweepStrategy::Sobol, &ranges(11), Some(8), 0, &mut rng()).is_err());
+}
+
+#[test]
+fn sampling_needs_a_budget_and_repeats_for_a_seed() {
+    assert!(sweep_design(SweepStrategy::Random, &ranges(2), None, 3, &mut rng()).is_err());
+    let a = sweep_design(SweepStrategy::Random, &ranges(2), Some(16), 0, &mut rng()).unwrap();
+    let b = sweep_design(SweepStrategy::Random, &ranges(2), Some(16), 0, &mut rng()).unwrap();
+    assert_eq!(a, b);
+    assert!(a.iter().flatten().all(|v| (0.0..=1.0).contains(v)));
+}
+
+#[test]
+fn points_are_written_into_the_config() {
+    let mut config = SimulationConfig::default();
+    apply_sweep_point(&mut config, &ranges(2), &[4.5, 0.25]);
+    assert_eq!((config.chemotaxis.chi_R, config.field.sigma_R), (4.5, 0.25));
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{ElevationField, FieldData};
+use vireo_core::{parse_config, SimulationConfig, TerrainConfig, TerrainFeature};
+
+/// Valley 20 deep and 8 wide running along x through the middle of a 64×64 world
+fn valley(anisotropy: f32) -> TerrainConfig {
+    TerrainConfig {
+        enabled: true,
+        features: vec![TerrainFeature::Valley { from: [0.0, 32.0], to: [64.0, 32.0], width: 8.0, depth: 20.0 }],
+        anisotropy,
+        ..TerrainConfig::default()
+    }
+}
+
+#[test]
+fn features_shape_the_elevation() {
+    let crater = TerrainFeature::Crater { center: [32.0, 32.0], radius: 10.0, depth: 4.0, rim: 1.0 };
+    assert!((crater.elevation([32.0, 32.0]) + 4.0).abs() < 1e-3);
+    assert!((crater.elevation([42.0, 32.0]) - 1.0).abs() < 1e-6); // Rim crest at the radius
+    assert!(crater.elevation([60.0, 32.0]).abs() < 1e-6);
+
+    let field = ElevationField::new(&valley(0.0), [64, 64]);
+    assert_eq!(field.heights().len(), 64 * 64);
+    assert!(field.at(10, 31) < -19.0 && field.at(10, 31) == field.at(50, 31));
+    assert!(field.at(10, 20) > field.at(10, 28));
+    assert!(field.sample([10.5, 31.5]) == field.at(10, 31));
+
+    // Disabled terrain is flat
+    let flat = ElevationField::new(&TerrainConfig { enabled: false, ..valley(0.0) }, [64, 64]);
+    assert!(flat.heights().iter().all(|&h| h == 0.0));
+}
+
+#[test]
+fn terrain_configs_are_validated() {
+    let yaml = |terrain: &str| format!(
+        "world: {{size: [64, 64], steps: 10, dt: 0.1, seed: 1}}\n\
+         field: {{D_R: 0.5, D_W: 0.2, sigma_R: 0.05, alpha_H: 0.1, beta_H: 0.05, lambda_R: 0.005, lambda_W: 0.005}}\n\
+         chemotaxis: {{chi_R: 8.0, chi_W: 4.0, kappa: 2.0, gamma: 0.05, v_max: 2.0, eps0: 0.01, eta_R: 0.1}}\n\
+         agents: {{herbivores: 10, E0: 1.0}}\n\
+         noise: {{sigma: 0.0}}\n\
+         obstacles: {{enabled: false}}\n\
+         terrain: {}\n",
+        terrain,
+    );
+
+    let config = parse_config(&yaml("{enabled: true, features: [{shape: crater, center: [32, 32], radius: 10, depth: 3, rim: 1}, \
+        {shape: valley, from: [0, 10], to: [64, 10], width: 4, depth: 2}]}")).unwrap();
+    assert_eq!(config.terrain.features.len(), 2);
+    assert_eq!(config.terrain.climb_cost, TerrainConfig::default().climb_cost);
+
+    let error = parse_config(&yaml("{enabled: true, features: [{shape: valley, from: [0, 0], to: [1, 1], width: 0, depth: 1}]}")).unwrap_err();
+    assert!(error.to_string().contains("terrain.features[0]"), "{}", error);
+    // Descending may not pay back more than climbing costs
+    let error = parse_config(&yaml("{enabled: true, climb_cost: 0.2, descent_gain: 0.3}")).unwrap_err();
+    assert!(error.to_string().contains("terrain.descent_gain"), "{}", error);
+    assert!(parse_config(&yaml("{enabled: false, descent_gain: 0.3}")).is_ok());
+}
+
+#[test]
+fn climbing_costs_energy_and_descending_returns_part() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.agents.herbivores = 16;
+    config.chemotaxis.chi_R = 0.0;
+    config.chemotaxis.chi_W = 0.0;
+    config.chemotaxis.gamma = 0.0;
+    config.chemotaxis.eps0 = 0.0;
+    config.chemotaxis.eta_R = 0.0;
+    config.terrain = TerrainConfig { climb_cost: 0.1, descent_gain: 0.04, ..valley(0.0) };
+    let mut sim = Simulation::new(&gpu, config);
+
+    // Half walk up the valley's north flank, half down it, one cell per step
+    for (i, agent) in sim.agent_manager.agents.iter_mut().enumerate() {
+        agent.pos = [8.3 + (i / 2) as f32 * 2.1, 38.0];
+        agent.vel = if i % 2 == 0 { [0.0, 1.0] } else { [0.0, -1.0] };
+    }
+    sim.upload_agents(&gpu);
+    let before = sim.agent_manager.agents.clone();
+
+    sim.step(&gpu);
+    sim.sync_agents(&gpu);
+
+    let terrain = &sim.terrain.field;
+    for (i, (a, b)) in before.iter().zip(&sim.agent_manager.agents).enumerate() {
+        let spent = a.energy - b.energy;
+        let expected = terrain.move_cost(a.pos, b.pos, 0.1, 0.04);
+        assert!((spent - expected).abs() < 1e-4, "agent {}: spent {}, expected {}", i, spent, expected);
+        if i % 2 == 0 {
+            assert!(spent > 0.05, "uphill agent {} spent {}", i, spent);
+        } else {
+            assert!(spent < -0.02, "downhill agent {} spent {}", i, spent);
+        }
+    }
+}
+
+/// Variance of R along x and y after spreading a blob on the valley flank
+fn spread(gpu: &GpuDevice, anisotropy: f32) -> (f32, f32) {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.world.dt = 1.0;
+    config.agents.herbivores = 1;
+    config.field.D_R = 0.2;
+    config.field.sigma_R = 0.0;
+    config.field.lambda_R = 0.0;
+    config.terrain = valley(anisotropy);
+    let mut sim = Simulation::new(gpu, config);
+    for agent in &mut sim.agent_manager.agents {
+        agent.kill();
+    }
+    sim.upload_agents(gpu);
+
+    // Steepest part of the flank is width / √2 ≈ 6 cells off the axis
+    sim.field_manager.fill(FieldData::new(0.0, 0.0));
+    sim.field_manager.set(32, 38, FieldData::new(1.0, 0.0));
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    for _ in 0..40 {
+        sim.step(gpu);
+    }
+
+    sim.sync_field(gpu);
+    let (mut total, mut mx, mut my) = (0.0f32, 0.0f32, 0.0f32);
+    let cells: Vec<(f32, f32, f32)> = (0..64 * 64)
+        .map(|i| ((i % 64) as f32, (i / 64) as f32, sim.field_manager.cell(i as usize).R))
+        .collect();
+    for &(x, y, r) in &cells {
+        total += r;
+        mx += x * r;
+        my += y * r;
+    }
+    let (mx, my) = (mx / total, my / total);
+    let var = |axis: fn(&(f32, f32, f32)) -> f32, mean: f32| {
+        cells.iter().map(|c| (axis(c) - mean).powi(2) * c.2).sum::<f32>() / total
+    };
+    (var(|c| c.0, mx), var(|c| c.1, my))
+}
+
+#[test]
+fn diffusion_follows_contours() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let (along, across) = spread(&gpu, 0.0);
+    assert!((along / across - 1.0).abs() < 0.1, "isotropic: {} vs {}", along, across);
+
+    let (along, across) = spread(&gpu, 2.0);
+    assert!(along > 2.0 * across, "anisotropic: along {} vs across {}", along, across);
+}
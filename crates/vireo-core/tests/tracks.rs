@@ -0,0 +1,65 @@
+use vireo_core::sim::{Agent, TrackRecorder};
+use vireo_core::{SimulationConfig, TrackConfig};
+use glam::Vec2;
+
+fn agents(n: usize) -> Vec<Agent> {
+    (0..n).map(|i| Agent::new(Vec2::new(i as f32, 0.0), 1.0, 1)).collect()
+}
+
+fn config(sample: u32) -> TrackConfig {
+    TrackConfig { enabled: true, every: 5, sample, ..Default::default() }
+}
+
+#[test]
+fn sampling_is_deterministic_and_distinct() {
+    let world = SimulationConfig::default().world;
+    let pop = agents(200);
+    let ids = |r: &TrackRecorder| r.tracks().iter().map(|t| t.id).collect::<Vec<_>>();
+
+    let a = TrackRecorder::new(&config(20), &world, &pop);
+    let b = TrackRecorder::new(&config(20), &world, &pop);
+    assert_eq!(ids(&a), ids(&b));
+    assert_eq!(a.tracks().len(), 20);
+    assert!(ids(&a).windows(2).all(|w| w[0] < w[1]), "ids sorted and unique");
+
+    // Asking for more than the population tracks everyone
+    assert_eq!(TrackRecorder::new(&config(500), &world, &pop).tracks().len(), 200);
+}
+
+#[test]
+fn record_skips_dead_agents_and_respects_interval() {
+    let world = SimulationConfig::default().world;
+    let mut pop = agents(4);
+    let mut recorder = TrackRecorder::new(&config(4), &world, &pop);
+
+    assert!(recorder.is_due(0) && recorder.is_due(10));
+    assert!(!recorder.is_due(3));
+
+    recorder.record(0, &pop);
+    pop[1].kill();
+    pop[0].pos = [7.0, 3.0];
+    recorder.record(5, &pop);
+
+    let tracks = recorder.tracks();
+    assert_eq!(tracks[0].fixes.len(), 2);
+    assert_eq!(tracks[0].fixes[1].pos, [7.0, 3.0]);
+    assert_eq!(tracks[0].fixes[1].step, 5);
+    assert_eq!(tracks[1].fixes.len(), 1, "no fixes after death");
+}
+
+#[test]
+fn slot_refilled_after_death_is_not_tracked() {
+    let world = SimulationConfig::default().world;
+    let mut pop = agents(2);
+    let mut recorder = TrackRecorder::new(&config(2), &world, &pop);
+
+    recorder.record(0, &pop);
+    pop[0].kill();
+    recorder.record(5, &pop);
+    pop[0] = Agent::new(Vec2::new(9.0, 9.0), 0.5, 1); // A birth reusing the slot
+    recorder.record(10, &pop);
+
+    assert!(recorder.tracks()[0].ended);
+    assert_eq!(recorder.tracks()[0].fixes.len(), 1);
+    assert_eq!(recorder.tracks()[1].fixes.len(), 3);
+}
@@ -0,0 +1,89 @@
+use glam::Vec2;
+use std::collections::HashMap;
+use vireo_core::sim::{Agent, Genetics, GeneticsWriter, TraitStats};
+
+fn herbivore(chi_gain: f32, kappa_gain: f32) -> Agent {
+    Agent { chi_gain, kappa_gain, ..Agent::new(Vec2::ZERO, 1.0, 1) }
+}
+
+#[test]
+fn spawned_agents_carry_unit_multipliers() {
+    let agent = Agent::new(Vec2::new(3.0, 4.0), 1.0, 2);
+    assert_eq!((agent.chi_gain, agent.kappa_gain), (1.0, 1.0));
+    assert_eq!((Agent::free(0).chi_gain, Agent::free(0).kappa_gain), (1.0, 1.0));
+}
+
+#[test]
+fn stats_are_means_and_spreads_over_alive_agents_per_species() {
+    let mut agents = vec![herbivore(1.0, 2.0), herbivore(3.0, 2.0), herbivore(100.0, 100.0), Agent::new(Vec2::ZERO, 1.0, 0)];
+    agents[2].kill();
+    let stats = TraitStats::of(&agents);
+
+    assert_eq!(stats.iter().map(|s| s.kind).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!((stats[0].mean_chi, stats[0].sd_chi, stats[0].mean_kappa), (1.0, 0.0, 1.0));
+    assert_eq!((stats[1].mean_chi, stats[1].sd_chi), (2.0, 1.0));
+    assert_eq!((stats[1].mean_kappa, stats[1].sd_kappa), (2.0, 0.0));
+    assert_eq!(stats[2], TraitStats { kind: 2, ..Default::default() }, "extinct species read 0");
+}
+
+#[test]
+fn parent_multipliers_round_trip_through_the_packed_field() {
+    let mut agent = herbivore(2.0, 0.5);
+    assert_eq!(agent.parent_traits(), None, "spawned agents have no parent");
+    agent.set_parent_traits(1.5, 0.75);
+    assert_eq!(agent.parent_traits(), Some((1.5, 0.75)));
+}
+
+/// Herbivore with `chi_gain` born `age` steps ago to a parent with `parent_chi` (kappa 1 throughout)
+fn offspring(chi_gain: f32, parent_chi: f32, age: u32) -> Agent {
+    let mut agent = Agent { age, ..herbivore(chi_gain, 1.0) };
+    agent.set_parent_traits(parent_chi, 1.0);
+    agent
+}
+
+#[test]
+fn genetics_csv_holds_heritability_and_trait_histograms() {
+    // ln(offspring chi) = ln(parent chi) / 2 over the recent births; the old one is left out
+    let agents = vec![
+        offspring(1.0, 1.0, 3),
+        offspring(2.0, 4.0, 0),
+        offspring(4.0, 16.0, 9),
+        offspring(0.25, 1.0, 10),
+        herbivore(1.0, 1.0),
+        Agent::new(Vec2::ZERO, 1.0, 0),
+    ];
+    let genetics = Genetics::of(&agents, 10);
+    assert_eq!(genetics[1].offspring, 3);
+    assert!((genetics[1].heritability_chi - 0.5).abs() < 1e-6, "{}", genetics[1].heritability_chi);
+    assert!(genetics[1].heritability_kappa.is_nan(), "parents all share kappa 1");
+    assert_eq!(genetics[1].chi_freq, [0.2, 0.0, 0.0, 0.0, 0.4, 0.0, 0.0, 0.4]);
+    assert_eq!(genetics[1].kappa_freq, [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+    assert_eq!((genetics[2].offspring, genetics[2].chi_freq), (0, [0.0; 8]), "extinct species");
+
+    let dir = std::env::temp_dir().join(format!("vireo-genetics-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut writer = GeneticsWriter::new(&dir).unwrap();
+    writer.write_step(40, &TraitStats::of(&agents), &genetics).unwrap();
+    drop(writer);
+    let mut reader = csv::Reader::from_path(dir.join("genetics.csv")).unwrap();
+    assert_eq!(reader.headers().unwrap(), vec!["step", "species", "metric", "value"]);
+    let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // 4 trait statistics, offspring, 2 heritabilities and 2×8 bins per species
+    assert_eq!(rows.len(), 3 * 23);
+    assert!(rows.iter().all(|r| &r[0] == "40"));
+    let herbivores: Vec<&str> = rows.iter().filter(|r| &r[1] == "herbivores").map(|r| &r[2]).collect();
+    assert_eq!(herbivores[..7], ["mean_chi_gain", "sd_chi_gain", "mean_kappa_gain", "sd_kappa_gain",
+        "offspring", "heritability_chi_gain", "heritability_kappa_gain"]);
+    assert_eq!((herbivores[7], herbivores[22]), ("chi_gain_freq_0", "kappa_gain_freq_7"));
+
+    let value: HashMap<(&str, &str), &str> = rows.iter().map(|r| ((&r[1], &r[2]), &r[3])).collect();
+    assert_eq!(value[&("herbivores", "mean_chi_gain")], "1.65");
+    assert_eq!(value[&("herbivores", "offspring")], "3");
+    assert_eq!(value[&("herbivores", "heritability_chi_gain")], "0.5");
+    assert_eq!(value[&("herbivores", "heritability_kappa_gain")], "NaN");
+    assert_eq!(value[&("herbivores", "chi_gain_freq_7")], "0.4");
+    assert_eq!(value[&("plants", "kappa_gain_freq_4")], "1");
+    assert_eq!(value[&("predators", "heritability_chi_gain")], "NaN");
+}
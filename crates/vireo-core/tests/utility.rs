@@ -0,0 +1,46 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::sim::FieldData;
+use vireo_core::SimulationConfig;
+
+fn expected(d: FieldData, chi_R: f32, chi_W: f32, kappa: f32) -> f32 {
+    let s = |c: f32| c / (1.0 + kappa * c);
+    chi_R * s(d.R) - chi_W * s(d.W)
+}
+
+#[test]
+fn utility_map_matches_saturated_gains() {
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut config = SimulationConfig::default();
+    config.world.size = [96, 64];
+    let mut sim = Simulation::new(&gpu, config);
+
+    // R rises along x, W along y
+    for y in 0..64 {
+        for x in 0..96 {
+            sim.field_manager.set(x, y, FieldData::new(x as f32 / 32.0, y as f32 / 24.0));
+        }
+    }
+    sim.field.upload_field_data(&gpu.queue, &sim.field_manager).unwrap();
+    sim.sync_field(&gpu); // Compare against the f16-rounded field the GPU sees
+
+    let check = |sim: &Simulation, utility: &[f32]| {
+        let p = &sim.agent_params;
+        assert_eq!(utility.len(), 96 * 64);
+        for (i, u) in utility.iter().enumerate() {
+            let want = expected(sim.field_manager.cell(i), p.chi_R, p.chi_W, p.kappa);
+            assert!((u - want).abs() < 1e-4, "cell {}: {} vs {}", i, u, want);
+        }
+    };
+    check(&sim, &sim.utility_map(&gpu));
+
+    // Live parameter changes are picked up on the next map
+    sim.agent_params.chi_W *= 3.0;
+    sim.agent_params.kappa = 0.0;
+    let utility = sim.utility_map(&gpu);
+    check(&sim, &utility);
+    assert_eq!(utility[0], 0.0);
+    assert!(utility[95] > utility[0] && utility[63 * 96] < utility[0]);
+}
@@ -0,0 +1,25 @@
+mod common;
+
+use common::gpu_or_skip;
+use vireo_core::gpu::Simulation;
+use vireo_core::SimulationConfig;
+
+// A single test: destroying a GLES device tears down the process-wide EGL
+// display, so no other test in this binary may hold a device meanwhile.
+#[test]
+fn lost_device_is_reported_instead_of_panicking() {
+    let mut config = SimulationConfig::default();
+    config.world.size = [64, 64];
+    config.agents.herbivores = 50;
+
+    let Some(gpu) = gpu_or_skip() else { return };
+    let mut sim = Simulation::new(&gpu, config);
+    sim.step(&gpu);
+    assert_eq!(gpu.lost(), None);
+
+    // Work submitted to the lost device is dropped, not a panic
+    gpu.device.destroy();
+    sim.step(&gpu);
+    let cause = gpu.lost().expect("loss not detected");
+    assert!(cause.contains("lost"), "{}", cause);
+}
@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::path::Path;
+use anyhow::Result;
+use csv::Writer;
+use vireo_core::{SimulationConfig, SPECIES};
+use vireo_core::gpu::{GpuDevice, ImageKind, Simulation};
+use vireo_core::sim::{AgentPopulation, InitAudit, AUDIT_QUANTILES};
+use crate::snapshots::{save_count_heatmap, SnapshotWriter};
+
+/// Seed the world of `config` (or place `population`), audit it and exit
+///
+/// Writes `audit_field.csv` (per-channel quantiles), `audit_species.csv`
+/// (per-species placement), `audit_<species>.csv` and `.png` (spatial
+/// histograms over a `bins`×`bins` grid), plus the usual step-0 field image
+/// and agents CSV. Returns an error when the audit finds problems, so scripts
+/// can stop before the long run.
+pub fn run(gpu: &GpuDevice, config: SimulationConfig, population: Option<&AgentPopulation>, bins: u32, out: &Path) -> Result<()> {
+    println!("Auditing initial conditions (rng={:?}, seed={})...", config.world.rng, config.world.seed);
+    let mut sim = match population {
+        Some(population) => Simulation::with_population(gpu, config, population)
+            .map_err(|e| anyhow::anyhow!("initial agents do not fit the world: {}", e))?,
+        None => Simulation::new(gpu, config),
+    };
+    // What the first step will read, not the CPU seeding
+    sim.sync_field(gpu);
+    sim.sync_agents(gpu);
+    let audit = InitAudit::of(&sim.field_manager, &sim.agent_manager.agents, &sim.obstacles.field, bins);
+
+    let snapshots = SnapshotWriter::new(&out.to_path_buf())?;
+    snapshots.write_field_snapshot(0, sim.config.world.size, sim.render_image(gpu, ImageKind::Field))?;
+    snapshots.write_agents_snapshot(0, &sim.agent_manager)?;
+    write_field(&audit, &out.join("audit_field.csv"))?;
+    write_species(&audit, &out.join("audit_species.csv"))?;
+    for s in &audit.species {
+        let name = SPECIES[s.kind as usize];
+        let mut csv_writer = Writer::from_writer(File::create(out.join(format!("audit_{}.csv", name)))?);
+        for row in s.histogram.chunks(audit.bins[0] as usize) {
+            csv_writer.write_record(row.iter().map(|n| n.to_string()))?;
+        }
+        csv_writer.flush()?;
+        // One pixel per cell, so the heatmap lines up with R_0000.png
+        let bin = |v: u32, axis: usize| v * audit.bins[axis] / audit.size[axis];
+        let pixels: Vec<u32> = (0..audit.size[1])
+            .flat_map(|y| (0..audit.size[0]).map(move |x| (x, y)))
+            .map(|(x, y)| s.histogram[(bin(y, 1) * audit.bins[0] + bin(x, 0)) as usize])
+            .collect();
+        save_count_heatmap(&pixels, audit.size, &out.join(format!("audit_{}.png", name)))?;
+    }
+
+    println!("Field (quantiles {:?}):", AUDIT_QUANTILES);
+    for c in &audit.channels {
+        println!("  {:<8} mean {:.4}, zero {:.1}%, {:?}", c.channel.name(), c.mean, c.zero_fraction * 100.0, c.quantiles);
+    }
+    println!("Agents ({}x{} bins):", audit.bins[0], audit.bins[1]);
+    for s in &audit.species {
+        println!("  {:<10} {:>6} in {:>4} bins, energy {:.3} [{:.3}, {:.3}], R underfoot {:.4}",
+            SPECIES[s.kind as usize], s.count, s.occupied_bins, s.mean_energy, s.min_energy, s.max_energy, s.mean_resource);
+    }
+    println!("Audit written to {}", out.display());
+
+    let warnings = audit.warnings();
+    for w in &warnings {
+        eprintln!("Warning: {}", w);
+    }
+    if !warnings.is_empty() {
+        anyhow::bail!("initial condition audit found {} problem(s)", warnings.len());
+    }
+    Ok(())
+}
+
+fn write_field(audit: &InitAudit, path: &Path) -> Result<()> {
+    let mut csv_writer = Writer::from_writer(File::create(path)?);
+    let mut header = vec!["channel".to_string(), "mean".to_string(), "zero_fraction".to_string(), "non_finite".to_string()];
+    header.extend(AUDIT_QUANTILES.iter().map(|q| format!("q{}", q)));
+    csv_writer.write_record(&header)?;
+    for c in &audit.channels {
+        let mut row = vec![c.channel.name().to_string(), c.mean.to_string(), c.zero_fraction.to_string(), c.non_finite.to_string()];
+        row.extend(c.quantiles.iter().map(|q| q.to_string()));
+        csv_writer.write_record(&row)?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+fn write_species(audit: &InitAudit, path: &Path) -> Result<()> {
+    let mut csv_writer = Writer::from_writer(File::create(path)?);
+    csv_writer.write_record([
+        "species", "count", "occupied_bins", "mean_energy", "min_energy", "max_energy",
+        "mean_resource", "on_bare", "in_obstacles", "outside", "no_energy",
+    ])?;
+    for s in &audit.species {
+        csv_writer.write_record([
+            SPECIES[s.kind as usize].to_string(),
+            s.count.to_string(),
+            s.occupied_bins.to_string(),
+            s.mean_energy.to_string(),
+            s.min_energy.to_string(),
+            s.max_energy.to_string(),
+            s.mean_resource.to_string(),
+            s.on_bare.to_string(),
+            s.in_obstacles.to_string(),
+            s.outside.to_string(),
+            s.no_energy.to_string(),
+        ])?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
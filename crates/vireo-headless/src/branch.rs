@@ -0,0 +1,128 @@
+use std::fs::File;
+use std::path::Path;
+use std::time::Instant;
+use csv::Writer;
+use vireo_core::{SimulationConfig, TimeUniform};
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{BranchPlan, Diversity, MetricsSample, SpeciesTracker, StateSnapshot, TraitStats};
+use crate::metrics::{self, MetricsWriter};
+
+/// Run every variant of the plan at `plan_path` from its checkpoint
+///
+/// Each variant writes `metrics.csv` and its final `snapshot_NNNN.bin` to
+/// `out/<name>/`, so any two of them can be put side by side with `compare`.
+/// `branches.csv` in `out` sums up the final state of every variant.
+pub fn run(gpu: &GpuDevice, config: &SimulationConfig, plan_path: &Path, out: &Path) -> Result<(), anyhow::Error> {
+    let plan = BranchPlan::load(plan_path).map_err(anyhow::Error::msg)?;
+    let checkpoint = StateSnapshot::read(&plan.checkpoint)
+        .map_err(|e| anyhow::anyhow!("cannot read checkpoint {}: {}", plan.checkpoint.display(), e))?;
+    checkpoint.check_compatible(config).map_err(anyhow::Error::msg)?;
+    let end = plan.end_step(checkpoint.step, config.world.steps).map_err(anyhow::Error::msg)?;
+
+    // Reject the whole plan up front rather than failing halfway through
+    let configs = plan.variants
+        .iter()
+        .map(|v| v.apply(config))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(anyhow::Error::msg)?;
+    // Variants continue the checkpointed run at its dt, so an unstable one is only reported
+    for (variant, run_config) in plan.variants.iter().zip(&configs) {
+        let limit = run_config.dt_limit();
+        if run_config.world.dt > limit.max_dt() {
+            eprintln!("Warning: variant {} puts world.dt = {} above the stability limit {}", variant.name, run_config.world.dt, limit);
+        }
+    }
+
+    println!("Branch: {} variants from {} (step {}) to step {}",
+        plan.variants.len(), plan.checkpoint.display(), checkpoint.step, end);
+    let start_time = Instant::now();
+
+    let params = plan.params();
+    let path = out.join("branches.csv");
+    let mut writer = Writer::from_writer(File::create(&path)?);
+    let mut header = vec!["variant".to_string()];
+    header.extend(params.iter().map(|p| p.to_string()));
+    header.extend(["extinction_step", "final_alive", "mean_R", "mean_W", "mean_energy"].map(String::from));
+    writer.write_record(&header)?;
+
+    for (i, (variant, mut run_config)) in plan.variants.iter().zip(configs).enumerate() {
+        let dir = out.join(&variant.name);
+        std::fs::create_dir_all(&dir)?;
+        let mut sim = Simulation::new(gpu, run_config.clone());
+        sim.restore(gpu, &checkpoint).map_err(anyhow::Error::msg)?;
+        let extinction_step = run_variant(gpu, &mut sim, &run_config, end, &dir)?;
+
+        println!("Variant {}/{} {}: {}",
+            i + 1,
+            plan.variants.len(),
+            variant.name,
+            match extinction_step {
+                Some(step) => format!("extinct at step {}", step),
+                None => format!("{} alive", sim.agent_manager.stats.alive_count),
+            }
+        );
+
+        // Parameters the variant leaves alone read as the base config's value
+        let mut record = vec![variant.name.clone()];
+        record.extend(params.iter().map(|p| run_config.scalar_mut(p).map(|v| v.to_string()).unwrap_or_default()));
+        record.push(extinction_step.map(|s| s.to_string()).unwrap_or_default());
+        record.extend([
+            sim.agent_manager.stats.alive_count.to_string(),
+            sim.field_manager.stats.mean_R.to_string(),
+            sim.field_manager.stats.mean_W.to_string(),
+            sim.agent_manager.stats.mean_energy.to_string(),
+        ]);
+        writer.write_record(&record)?;
+        writer.flush()?;
+    }
+
+    println!("Branch completed in {:?}", start_time.elapsed());
+    println!("Results written to {}", path.display());
+
+    Ok(())
+}
+
+/// Step one restored variant to `end` (or extinction), writing its metrics and final snapshot to `dir`
+///
+/// Returns the extinction step, if any. The field and agent stats are current on return.
+fn run_variant(gpu: &GpuDevice, sim: &mut Simulation, config: &SimulationConfig, end: u32, dir: &Path) -> Result<Option<u32>, anyhow::Error> {
+    let registry = metrics::standard_registry(config).map_err(anyhow::Error::msg)?;
+    let mut metrics_writer = MetricsWriter::new(dir, registry)?;
+    let mut species_tracker = SpeciesTracker::new(&sim.agent_manager.agents);
+
+    // The first row is the shared checkpoint, the same for every variant
+    let start = sim.current_step();
+    let mut step_start = Instant::now();
+    loop {
+        let step = sim.current_step();
+        if step == start || step == end || step.is_multiple_of(config.output.metrics_every) {
+            sim.sync_field(gpu);
+            sim.sync_agents(gpu);
+            sim.field_manager.update_stats();
+            sim.agent_manager.update_stats();
+            metrics_writer.write_step(&MetricsSample {
+                step,
+                time: &TimeUniform::new(&config.world, step),
+                field: &sim.field_manager.stats,
+                agents: &sim.agent_manager.stats,
+                movement: &sim.movement_stats(gpu),
+                species: &species_tracker.sample(&sim.agent_manager.agents),
+                step_time: step_start.elapsed(),
+                events: None,
+                sampling: None,
+                diversity: Some(&Diversity::of(&sim.agent_manager.agents, config.world.size, config.output.quadrats)),
+                traits: config.evolution.enabled.then(|| TraitStats::of(&sim.agent_manager.agents)).as_deref(),
+            })?;
+
+            let extinct = sim.agent_count() > 0 && sim.agent_manager.get_alive_count() == 0;
+            if step >= end || extinct {
+                let state = sim.snapshot(gpu);
+                state.write(&dir.join(state.file_name()))?;
+                return Ok(extinct.then_some(step));
+            }
+        }
+
+        step_start = Instant::now();
+        sim.step(gpu);
+    }
+}
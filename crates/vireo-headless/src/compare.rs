@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use clap::Args;
+use csv::{Reader, Writer};
+use image::{ImageBuffer, Rgb, RgbImage};
+use vireo_core::sim::{MetricProvider, StateSnapshot, TimingColumns};
+
+/// Result directory comparison options
+#[derive(Args)]
+pub struct CompareArgs {
+    /// Baseline result directory
+    pub a: PathBuf,
+
+    /// Result directory compared against the baseline
+    pub b: PathBuf,
+
+    /// Absolute difference up to which a metric, cell or agent counts as unchanged
+    #[arg(long, default_value_t = 0.0)]
+    pub tolerance: f64,
+}
+
+/// metrics.csv as step → column → value
+struct MetricsTable {
+    columns: Vec<String>,
+    rows: BTreeMap<u64, Vec<f64>>,
+}
+
+impl MetricsTable {
+    fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let mut reader = Reader::from_path(path)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        let columns: Vec<String> = reader.headers()?.iter().map(String::from).collect();
+        if columns.first().map(String::as_str) != Some("step") {
+            anyhow::bail!("{}: first column is not `step`", path.display());
+        }
+
+        let mut rows = BTreeMap::new();
+        for record in reader.records() {
+            let record = record?;
+            let step = record[0].parse()?;
+            // Empty or non-numeric cells compare as NaN
+            let values = record.iter().skip(1).map(|v| v.parse().unwrap_or(f64::NAN)).collect();
+            rows.insert(step, values);
+        }
+        Ok(Self { columns: columns[1..].to_vec(), rows })
+    }
+
+    fn column(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == name)
+    }
+}
+
+/// Divergence of one metric over the aligned steps
+struct MetricDivergence {
+    name: String,
+    max_abs: f64,
+    first_step: Option<u64>,
+}
+
+/// Compare two result directories and write the differences to `out`
+///
+/// metrics.csv files are aligned on `step` and every column present in both
+/// (except wall-clock timing) is differenced into `divergence.csv`; each
+/// `snapshot_NNNN.bin` found in both directories gets `diff_R_NNNN.png` and
+/// `diff_W_NNNN.png` (red where b is higher, blue where it is lower, scaled to
+/// the largest difference). Fails when anything differs beyond `--tolerance`,
+/// so it can gate scripts.
+pub fn run(args: &CompareArgs, out: &Path) -> Result<(), anyhow::Error> {
+    if args.tolerance.is_nan() || args.tolerance < 0.0 {
+        anyhow::bail!("--tolerance must be non-negative.");
+    }
+    std::fs::create_dir_all(out)?;
+    println!("Comparing {} (a) with {} (b)", args.a.display(), args.b.display());
+
+    let metrics_differ = compare_metrics(args, out)?;
+    let snapshots_differ = compare_snapshots(args, out)?;
+
+    println!("Differences written to {}", out.display());
+    if metrics_differ || snapshots_differ {
+        anyhow::bail!("runs differ beyond tolerance {}", args.tolerance);
+    }
+    println!("Runs match within tolerance {}", args.tolerance);
+    Ok(())
+}
+
+/// Write `divergence.csv` and report per-metric divergence; true if any metric differs
+fn compare_metrics(args: &CompareArgs, out: &Path) -> Result<bool, anyhow::Error> {
+    let a = MetricsTable::read(&args.a.join("metrics.csv"))?;
+    let b = MetricsTable::read(&args.b.join("metrics.csv"))?;
+
+    let timing = TimingColumns.columns();
+    let shared: Vec<(&String, usize, usize)> = a.columns.iter()
+        .filter(|name| !timing.contains(name))
+        .filter_map(|name| Some((name, a.column(name)?, b.column(name)?)))
+        .collect();
+    for (table, other, label) in [(&a, &b, "a"), (&b, &a, "b")] {
+        let only: Vec<&str> = table.columns.iter()
+            .filter(|name| other.column(name).is_none())
+            .map(String::as_str)
+            .collect();
+        if !only.is_empty() {
+            println!("Metrics only in {}: {}", label, only.join(", "));
+        }
+    }
+
+    let steps: Vec<u64> = a.rows.keys().filter(|step| b.rows.contains_key(step)).copied().collect();
+    let unmatched = a.rows.len() + b.rows.len() - 2 * steps.len();
+    println!("Metrics: {} shared columns over {} aligned steps ({} steps in only one run)",
+        shared.len(), steps.len(), unmatched);
+
+    let mut divergence: Vec<MetricDivergence> = shared.iter()
+        .map(|(name, _, _)| MetricDivergence { name: name.to_string(), max_abs: 0.0, first_step: None })
+        .collect();
+    let path = out.join("divergence.csv");
+    let mut writer = Writer::from_writer(File::create(&path)?);
+    let mut header = vec!["step".to_string()];
+    header.extend(shared.iter().map(|(name, _, _)| name.to_string()));
+    writer.write_record(&header)?;
+
+    for step in &steps {
+        let (row_a, row_b) = (&a.rows[step], &b.rows[step]);
+        let mut record = vec![step.to_string()];
+        for ((_, ia, ib), metric) in shared.iter().zip(&mut divergence) {
+            let (va, vb) = (row_a[*ia], row_b[*ib]);
+            // NaN on both sides is a match; NaN on one side is maximal divergence
+            let delta = match (va.is_nan(), vb.is_nan()) {
+                (true, true) => 0.0,
+                (false, false) => (vb - va).abs(),
+                _ => f64::INFINITY,
+            };
+            metric.max_abs = metric.max_abs.max(delta);
+            if delta > args.tolerance && metric.first_step.is_none() {
+                metric.first_step = Some(*step);
+            }
+            record.push(delta.to_string());
+        }
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+
+    let diverged: Vec<&MetricDivergence> = divergence.iter().filter(|m| m.first_step.is_some()).collect();
+    for metric in &diverged {
+        println!("  {:<30} first diverges at step {:>6}, max |b - a| = {:.3e}",
+            metric.name, metric.first_step.unwrap(), metric.max_abs);
+    }
+    if diverged.is_empty() {
+        println!("  all shared metrics match");
+    }
+    Ok(!diverged.is_empty() || unmatched > 0)
+}
+
+/// Render difference images of matching snapshots; true if any snapshot differs
+fn compare_snapshots(args: &CompareArgs, out: &Path) -> Result<bool, anyhow::Error> {
+    let mut names: Vec<String> = std::fs::read_dir(&args.a)?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("snapshot_") && name.ends_with(".bin") && args.b.join(name).is_file())
+        .collect();
+    names.sort();
+    println!("Snapshots: {} in both runs", names.len());
+
+    let mut differ = false;
+    for name in names {
+        let a = StateSnapshot::read(&args.a.join(&name))?;
+        let b = StateSnapshot::read(&args.b.join(&name))?;
+        let diff = a.diff(&b, args.tolerance as f32)
+            .map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+
+        let tag = &name["snapshot_".len()..name.len() - ".bin".len()];
+        save_diff_png(&diff.delta_R, diff.size, &out.join(format!("diff_R_{}.png", tag)))?;
+        save_diff_png(&diff.delta_W, diff.size, &out.join(format!("diff_W_{}.png", tag)))?;
+
+        let (max_r, max_w) = (diff.max_abs_R(), diff.max_abs_W());
+        println!("  step {:>6}: max |ΔR| = {:.3e}, max |ΔW| = {:.3e}, {} agent slots differ",
+            a.step, max_r, max_w, diff.agents_differing);
+        differ |= max_r as f64 > args.tolerance || max_w as f64 > args.tolerance || diff.agents_differing > 0;
+    }
+    Ok(differ)
+}
+
+/// Signed difference image: red where positive, blue where negative, black where equal
+pub fn save_diff_png(delta: &[f32], size: [u32; 2], path: &Path) -> Result<(), anyhow::Error> {
+    let max_abs = delta.iter().fold(0.0f32, |m, d| m.max(d.abs()));
+    let scale = if max_abs > 0.0 { 255.0 / max_abs } else { 0.0 };
+    let img: RgbImage = ImageBuffer::from_fn(size[0], size[1], |x, y| {
+        let d = delta[(y * size[0] + x) as usize];
+        let v = (d.abs() * scale).round().min(255.0) as u8;
+        if d > 0.0 { Rgb([v, 0, 0]) } else { Rgb([0, 0, v]) }
+    });
+    img.save(path)?;
+    Ok(())
+}
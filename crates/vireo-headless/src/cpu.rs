@@ -0,0 +1,77 @@
+use std::path::Path;
+use std::time::Instant;
+use vireo_core::{FieldChannel, RDParams, SimulationConfig, TimeUniform};
+use vireo_core::sim::{
+    AgentStats, ClockColumns, FieldColumns, FieldManager, MetricsRegistry, MetricsSample, MovementStats, ReferenceField, SimRng, SoilColumns,
+};
+use crate::metrics::MetricsWriter;
+
+/// Run the field alone on the CPU reference and write `metrics.csv` to `out`
+///
+/// The fallback for machines where wgpu finds no adapter, not even a software
+/// one. `ReferenceField` replicates only the reaction-diffusion pass, so a
+/// config that spawns agents is an error rather than a run that silently
+/// drops them. The field is seeded as on the GPU, then grows, diffuses and
+/// decays under the config's schedules and day/night light. The metrics hold
+/// the clock and field columns only.
+pub fn run(config: &SimulationConfig, out: &Path) -> Result<(), anyhow::Error> {
+    ReferenceField::<f32>::check(config).map_err(anyhow::Error::msg)?;
+    if config.agents.herbivores > 0 {
+        anyhow::bail!("the CPU reference simulates the field only, not the {} agents this config spawns; \
+            set agents.herbivores to 0 to run the field alone, or install a Vulkan, Metal, DX12 or GL driver",
+            config.agents.spawn_counts().iter().sum::<u32>());
+    }
+    let mut config = config.clone();
+
+    let mut registry = MetricsRegistry::new();
+    registry.register(ClockColumns).map_err(anyhow::Error::msg)?;
+    registry.register(FieldColumns).map_err(anyhow::Error::msg)?;
+    if config.soil.enabled {
+        registry.register(SoilColumns).map_err(anyhow::Error::msg)?;
+    }
+    let mut metrics_writer = MetricsWriter::new(out, registry)?;
+
+    let mut field_manager = FieldManager::new(config.world.size);
+    field_manager.seed_resources_with(&mut SimRng::from_world(&config.world));
+    let mut field = ReferenceField::<f32>::new(&field_manager);
+    let occupancy = vec![0u32; field_manager.len()];
+    let mut params = RDParams::from(&config);
+
+    println!("Starting CPU field simulation for {} steps", config.world.steps);
+    let start_time = Instant::now();
+    for step in 0..=config.world.steps {
+        if config.apply_schedules(step) {
+            params = RDParams::from(&config);
+        }
+        let time = TimeUniform::new(&config.world, step);
+        let step_start = Instant::now();
+        field.step(&params, &occupancy, time.light);
+
+        if step.is_multiple_of(config.output.metrics_every) {
+            for c in FieldChannel::ALL {
+                let values: Vec<f32> = field.channel(c).into_iter().map(|v| v as f32).collect();
+                field_manager.set_channel_f32(c, &values);
+            }
+            field_manager.update_stats();
+            let stats = &field_manager.stats;
+            metrics_writer.write_step(&MetricsSample {
+                step,
+                time: &time,
+                field: stats,
+                agents: &AgentStats::default(),
+                movement: &MovementStats::default(),
+                species: &[],
+                step_time: step_start.elapsed(),
+                events: None,
+                sampling: None,
+                diversity: None,
+                traits: None,
+            })?;
+            println!("Step {}: R={:.3}, W={:.3}, Time={:?}", step, stats.mean_R, stats.mean_W, step_start.elapsed());
+        }
+    }
+
+    println!("CPU field simulation completed in {:?}", start_time.elapsed());
+    println!("Results written to {}", out.display());
+    Ok(())
+}
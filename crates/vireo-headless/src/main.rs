@@ -1,40 +1,141 @@
+mod audit;
+mod branch;
+mod compare;
+mod cpu;
+mod frame_diff;
 mod metrics;
+mod optimize;
+mod precision;
+mod preview;
+mod progress;
+mod pva;
+mod reconstruct;
+mod recovery;
+mod render_video;
+mod sample;
 mod snapshots;
+mod startup;
+mod sweep;
+mod sweep_report;
+mod tracks;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use clap::ValueEnum;
 use std::path::PathBuf;
 use std::time::Instant;
-use vireo_core::SimulationConfig;
-use vireo_core::gpu::GpuDevice;
-use vireo_core::gpu::{FieldTextures, ComputePipelines};
-use vireo_core::sim::{FieldManager, AgentManager};
-use vireo_core::{RDParams, AgentParams};
-use metrics::MetricsWriter;
+use vireo_core::{GrowthPolicy, SimulationConfig};
+use vireo_core::gpu::{GpuDevice, ImageKind, Simulation};
+use vireo_core::sim::{AgentPopulation, ChainWriter, GuardTrip, Interaction, InteractionScript, RunGuard, SpeciesTracker, TrackRecorder};
+use metrics::{HashWriter, PerturbationWriter, StationWriter};
+use preview::Preview;
+use progress::Progress;
+use recovery::Checkpoint;
+use sample::Sampler;
 use snapshots::SnapshotWriter;
+use startup::StartupTimes;
+#[cfg(feature = "scripting")]
+use vireo_core::sim::ScenarioScript;
+
+/// Stand-in for `ScenarioScript` in builds without the `scripting` feature
+///
+/// It has no values, so `--script` fails at load and the scenario branches
+/// of `run` never execute, without pulling in the Rhai engine.
+#[cfg(not(feature = "scripting"))]
+enum ScenarioScript {}
+
+#[cfg(not(feature = "scripting"))]
+impl ScenarioScript {
+    fn load(_path: &std::path::Path, _config: &SimulationConfig) -> Result<Self, String> {
+        Err("scenario scripts need the `scripting` feature: rebuild with `cargo build -p vireo-headless --features scripting`".to_string())
+    }
+
+    fn observe(&mut self, _field: &vireo_core::sim::FieldStats, _species: &[vireo_core::sim::SpeciesStats]) {
+        match *self {}
+    }
+
+    fn on_step(&mut self, _step: u32, _config: &SimulationConfig) -> Result<Vec<Interaction>, String> {
+        match *self {}
+    }
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
     /// Configuration file path
-    #[arg(short, long, default_value = "lab/configs/best-demo.yaml")]
+    #[arg(short, long, global = true, default_value = "lab/configs/best-demo.yaml")]
     config: PathBuf,
-    
+
     /// Output directory for results
-    #[arg(short, long, default_value = "results")]
+    #[arg(short, long, global = true, default_value = "results")]
     out: PathBuf,
-    
+
     /// Enable strict validation
     #[arg(long)]
     strict: bool,
-    
+
     /// Enable debug scenarios for testing individual components
     #[arg(long)]
     debug_scenario: bool,
-    
+
+    /// Show the live field and agents in a window while the run writes its metrics (single runs only)
+    #[arg(long)]
+    preview: bool,
+
+    /// Print a line per metrics sample instead of drawing the progress bar (single runs only)
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Write the quantized state hash every N steps to state_hashes.csv (golden regression tests)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    hash_every: Option<u32>,
+
+    /// Run the parameter variants of a branch plan (YAML) forward from its shared checkpoint
+    #[arg(long, value_name = "VARIANTS")]
+    branch: Option<PathBuf>,
+
+    /// Replay viewer interactions recorded with `vireo-app --record` (single runs only)
+    #[arg(long, value_name = "SCRIPT")]
+    replay: Option<PathBuf>,
+
+    /// Call a Rhai scenario script's `on_step` before every step; its changes are written to script_events.yaml (single runs only; needs the `scripting` feature)
+    #[arg(long, value_name = "RHAI")]
+    script: Option<PathBuf>,
+
+    /// Start from the agents of an exported agents_NNNN.csv instead of spawning them (single runs only)
+    #[arg(long, value_name = "CSV")]
+    init_agents: Option<PathBuf>,
+
+    /// Write statistics and images of the seeded world without stepping, binning agents into BINS×BINS (default 32)
+    #[arg(long, value_name = "BINS", num_args = 0..=1, default_missing_value = "32", value_parser = clap::value_parser!(u32).range(1..))]
+    audit_init: Option<u32>,
+
     /// Test specific scenario: reaction-only, diffusion-only, uptake-only, damping-only
     #[arg(long, value_enum)]
     scenario: Option<Scenario>,
+
+    /// Batch mode to run instead of a single simulation
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Population viability analysis: run replicates and summarize extinction risk
+    Pva(pva::PvaArgs),
+    /// Parameter sweep: run a grid, Latin hypercube, Sobol or random design over parameter ranges
+    Sweep(sweep::SweepArgs),
+    /// Bayesian optimization: search parameter ranges for the config that maximizes an objective
+    Optimize(optimize::OptimizeArgs),
+    /// Compare two result directories: metric divergence over time and snapshot difference images
+    Compare(compare::CompareArgs),
+    /// Precision report: divergence of the half-precision GPU field from f32 and f64 CPU references
+    Precision(precision::PrecisionArgs),
+    /// Render the field and agent snapshots of a result directory into a video (GIF, or anything ffmpeg writes)
+    RenderVideo(render_video::RenderVideoArgs),
+    /// Step one state with and without a pass (or with parameters zeroed) and write the difference images
+    FrameDiff(frame_diff::FrameDiffArgs),
+    /// Rebuild the state at any step from a checkpoint chain (output.checkpoints), stepping on from the last checkpoint before it
+    Reconstruct(reconstruct::ReconstructArgs),
 }
 
 #[derive(ValueEnum, Clone)]
@@ -47,65 +148,176 @@ enum Scenario {
 
 fn main() -> Result<(), anyhow::Error> {
     let cli = Cli::parse();
-    
-    // Load configuration
-    println!("Loading configuration from {}", cli.config.display());
-    let config: SimulationConfig = serde_yaml::from_str(
-        &std::fs::read_to_string(&cli.config)?
-    )?;
-    
-    // Validate configuration
-    let w = config.world.size[0] as u32;
-    let h = config.world.size[1] as u32;
-    if w < 32 || h < 32 {
-        anyhow::bail!("World size too small ({}x{}). Minimum supported is 32x32.", w, h);
+
+    // Comparing finished runs needs neither a config nor a GPU
+    if let Some(Command::Compare(args)) = &cli.command {
+        return compare::run(args, &cli.out);
     }
-    if config.world.steps == 0 {
-        anyhow::bail!("Step count must be greater than 0.");
+    // Neither does rendering one; agent colors come from the config's `render` section if there is one
+    if let Some(Command::RenderVideo(args)) = &cli.command {
+        let render = if cli.config.exists() { vireo_core::load_config(&cli.config)?.render } else { Default::default() };
+        return render_video::run(args, &cli.out, &render);
     }
-    if config.world.dt <= 0.0 {
-        anyhow::bail!("Time step (dt) must be positive.");
+
+    // Load configuration
+    println!("Loading configuration from {}", cli.config.display());
+    let config_start = Instant::now();
+    let mut config = vireo_core::load_config(&cli.config)?;
+    let mut startup = StartupTimes { config_ms: startup::ms(config_start.elapsed()), ..Default::default() };
+
+    // A dt the solvers cannot keep stable is lowered (or refused) before anything runs
+    if let Some(dt) = config.enforce_dt_limit()? {
+        eprintln!("Warning: world.dt = {} exceeds the stability limit {}, clamped to {}",
+            dt, config.dt_limit(), config.world.dt);
     }
-    
+
+    // A bad script fails before the GPU is set up
+    let script = match &cli.replay {
+        Some(path) => {
+            if cli.command.is_some() || cli.branch.is_some() || cli.debug_scenario || cli.scenario.is_some() {
+                anyhow::bail!("--replay applies to single runs, without --branch, debug scenarios or a batch subcommand.");
+            }
+            let script = InteractionScript::load(path).map_err(anyhow::Error::msg)?;
+            script.check_compatible(&config)
+                .map_err(|e| anyhow::anyhow!("script {} does not match {}: {}", path.display(), cli.config.display(), e))?;
+            println!("Replaying {} interactions from {}", script.events.len(), path.display());
+            if let Some(seed) = script.seed {
+                println!("Using the recorded seed {}", seed);
+                config.world.seed = seed;
+            }
+            if let Some(e) = script.events.last().filter(|e| e.step > config.world.steps) {
+                eprintln!("Warning: script runs to step {} but world.steps = {}; later events are skipped", e.step, config.world.steps);
+            }
+            Some(script)
+        }
+        None => None,
+    };
+
+    // Scenario scripts are checked against the config they will run under
+    let scenario = match &cli.script {
+        Some(path) => {
+            if cli.command.is_some() || cli.branch.is_some() || cli.debug_scenario || cli.scenario.is_some() || cli.replay.is_some() {
+                anyhow::bail!("--script applies to single runs, without --replay, --branch, debug scenarios or a batch subcommand.");
+            }
+            let scenario = ScenarioScript::load(path, &config).map_err(anyhow::Error::msg)?;
+            println!("Running scenario script {}", path.display());
+            Some(scenario)
+        }
+        None => None,
+    };
+
+    // Imported agents get slots before the budget check sizes the buffers
+    let population = match &cli.init_agents {
+        Some(path) => {
+            if cli.command.is_some() || cli.branch.is_some() {
+                anyhow::bail!("--init-agents applies to single runs, without --branch or a batch subcommand.");
+            }
+            let population = AgentPopulation::read_csv(path)
+                .map_err(|e| anyhow::anyhow!("cannot read agents {}: {}", path.display(), e))?;
+            population.fit_capacity(&mut config.agents);
+            config.validate().map_err(|e| anyhow::anyhow!("agents {} do not fit {}: {}", path.display(), cli.config.display(), e))?;
+            let counts = population.counts();
+            println!("Initial agents from {}: {} plants, {} herbivores, {} predators", path.display(), counts[0], counts[1], counts[2]);
+            Some(population)
+        }
+        None => None,
+    };
+
     // Create output directory
     std::fs::create_dir_all(&cli.out)?;
-    
+
     // Initialize GPU
     println!("Initializing GPU...");
-    let gpu = pollster::block_on(GpuDevice::new());
+    let gpu_start = Instant::now();
+    let mut gpu = match pollster::block_on(GpuDevice::try_new()) {
+        Ok(gpu) => gpu,
+        // A plain single run still produces its field on the CPU reference
+        Err(e) if cli.command.is_none() && cli.branch.is_none() && cli.audit_init.is_none() && !cli.preview && !cli.debug_scenario
+            && cli.scenario.is_none() && script.is_none() && scenario.is_none() && population.is_none() => {
+            eprintln!("Warning: {}", e);
+            eprintln!("Warning: falling back to the CPU reference field, which has no agents; metrics.csv holds the field columns only");
+            println!("Backend: CPU reference (f32, field only)");
+            return cpu::run(&config, &cli.out);
+        }
+        Err(e) => return Err(anyhow::Error::msg(e)),
+    };
+    startup.gpu_ms = startup::ms(gpu_start.elapsed());
     println!("{}", gpu.info());
-    
-    // Create simulation components
-    let mut field_manager = FieldManager::new(config.world.size);
-    let mut agent_manager = AgentManager::new(
-        config.agents.herbivores,
-        [config.world.size[0] as f32, config.world.size[1] as f32],
-        config.agents.E0,
-        config.world.seed,
-    );
-    
-    // Seed the field
-    println!("Seeding field with resources...");
-    field_manager.seed_resources(config.world.seed);
-    
-    // Create GPU resources
-    let field_textures = FieldTextures::new(&gpu.device, config.world.size);
-    let compute_pipelines = ComputePipelines::new(&gpu.device);
-    
-    // Upload initial data
-    field_textures.upload_field_data(&gpu.queue, &field_manager);
-    
-    // Create GPU buffers
-    let rd_params = RDParams::from(&config);
-    let agent_params = AgentParams::from(&config);
-    
+    if gpu.is_software() {
+        eprintln!("Warning: no hardware GPU adapter, running on the CPU through {}; steps will be much slower",
+            gpu.adapter.get_info().name);
+    }
+
+    // Fail (or shrink the world) before allocating anything that would not fit
+    let (estimate, downscale) = vireo_core::fit_to_budget(&mut config, &gpu.device.limits())?;
+    if let Some(d) = downscale {
+        eprintln!("Warning: world {}x{} with {} herbivores does not fit the GPU, downscaled to {}x{} with {}",
+            d.from_size[0], d.from_size[1], d.from_herbivores,
+            config.world.size[0], config.world.size[1], config.agents.herbivores);
+    }
+    println!("GPU memory estimate: {}", estimate);
+
+    if cli.preview && cli.command.is_some() {
+        anyhow::bail!("--preview only applies to single runs, not batch subcommands.");
+    }
+    if cli.branch.is_some() && (cli.command.is_some() || cli.preview) {
+        anyhow::bail!("--branch runs on its own, without --preview or a batch subcommand.");
+    }
+
+    if let Some(bins) = cli.audit_init {
+        if cli.command.is_some() || cli.branch.is_some() || cli.preview || cli.replay.is_some() || cli.script.is_some() {
+            anyhow::bail!("--audit-init runs on its own, without --preview, --replay, --script, --branch or a batch subcommand.");
+        }
+        return audit::run(&gpu, config, population.as_ref(), bins, &cli.out);
+    }
+
+    match &cli.command {
+        Some(Command::Pva(args)) => pva::run(&gpu, &config, args, &cli.out),
+        Some(Command::Sweep(args)) => sweep::run(&gpu, &config, args, &cli.out),
+        Some(Command::Optimize(args)) => optimize::run(&gpu, &config, args, &cli.out),
+        Some(Command::Precision(args)) => precision::run(&gpu, &config, args, &cli.out),
+        Some(Command::FrameDiff(args)) => frame_diff::run(&gpu, &config, args, &cli.out),
+        Some(Command::Reconstruct(args)) => reconstruct::run(&gpu, &config, args, &cli.out),
+        Some(Command::Compare(_)) => unreachable!("compare runs before GPU setup"),
+        Some(Command::RenderVideo(_)) => unreachable!("render-video runs before GPU setup"),
+        None => match &cli.branch {
+            Some(plan) => branch::run(&gpu, &config, plan, &cli.out),
+            None => run(&mut gpu, config, &cli, script.as_ref(), scenario, population.as_ref(), startup),
+        },
+    }
+}
+
+/// Run a single simulation, writing metrics and snapshots to `cli.out`
+///
+/// A lost device is recreated and the run resumes from the latest in-memory
+/// checkpoint (every `gpu.checkpoint_every` steps). Steps already written are
+/// replayed without writing their outputs again. Interactions from `script`
+/// are made between steps, at the step they were recorded at; those of a
+/// `scenario` script as it makes them, logged to `script_events.yaml`. With a
+/// `population`, the run starts from those agents instead of spawning.
+/// `run.json` records the `startup` breakdown and pipeline compile times.
+fn run(gpu: &mut GpuDevice, config: SimulationConfig, cli: &Cli, script: Option<&InteractionScript>, mut scenario: Option<ScenarioScript>, population: Option<&AgentPopulation>, mut startup: StartupTimes) -> Result<(), anyhow::Error> {
+    // Create simulation components (seeds field and agents, uploads to GPU)
+    println!("Seeding field with resources (rng={:?}, seed={})...", config.world.rng, config.world.seed);
+    let simulation_start = Instant::now();
+    let mut sim = match population {
+        Some(population) => Simulation::with_population(gpu, config.clone(), population)
+            .map_err(|e| anyhow::anyhow!("initial agents do not fit the world: {}", e))?,
+        None => Simulation::new(gpu, config.clone()),
+    };
+    startup.simulation_ms = startup::ms(simulation_start.elapsed());
+    println!("Startup: {}", startup.describe(&sim.pipelines));
+
+    let rd_params = sim.rd_params;
+    let agent_params = sim.agent_params;
+
     // Debug scenario: Modify parameters to produce obvious changes
     let mut debug_rd_params = rd_params;
     let mut debug_agent_params = agent_params;
-    
+
     if cli.debug_scenario || cli.scenario.is_some() {
         println!("DEBUG SCENARIO: Using modified parameters for testing");
-        
+
         match cli.scenario.as_ref() {
             Some(Scenario::ReactionOnly) => {
                 println!("SCENARIO: Reaction-only (σ>0, λ=0, D=0) → mean R ↑");
@@ -148,261 +360,244 @@ fn main() -> Result<(), anyhow::Error> {
                 debug_rd_params.lambda_W = 0.0;
                 debug_rd_params.alpha_H = 0.0; // No herbivore uptake
                 debug_rd_params.sigma_R = 0.02; // High replenishment
-                
+
                 // Test 2: Pure damping (no chemotaxis) - velocity should decay
                 debug_agent_params.chi_R = 0.0;
                 debug_agent_params.chi_W = 0.0;
                 debug_agent_params.gamma = 0.2; // High damping
             }
         }
-        
-        println!("DEBUG RD params: D_R={} sigma_R={} lambda_R={} alpha_H={}", 
+
+        println!("DEBUG RD params: D_R={} sigma_R={} lambda_R={} alpha_H={}",
             debug_rd_params.D_R, debug_rd_params.sigma_R, debug_rd_params.lambda_R, debug_rd_params.alpha_H);
         println!("DEBUG Agent params: chi_R={} gamma={}", debug_agent_params.chi_R, debug_agent_params.gamma);
     }
-    
-    let rd_params_buffer = gpu.create_rd_params_buffer(&debug_rd_params);
-    let agent_params_buffer = gpu.create_agent_params_buffer(&debug_agent_params);
-    let agents_buffer = gpu.create_agents_buffer(&agent_manager.agents);
-    let occupancy_buffer = gpu.create_occupancy_buffer(config.world.size);
-    
+
+    sim.rd_params = debug_rd_params;
+    sim.agent_params = debug_agent_params;
+
     // Log initial parameters for debugging
     println!("RD params: D_R={} D_W={} sigma_R={} alpha_H={} beta_H={} lambda_R={} lambda_W={} dt={}",
         rd_params.D_R, rd_params.D_W, rd_params.sigma_R, rd_params.alpha_H, rd_params.beta_H, rd_params.lambda_R, rd_params.lambda_W, rd_params.dt);
     println!("Agent params: chi_R={} chi_W={} gamma={} eps0={} eta_R={} dt={}",
         agent_params.chi_R, agent_params.chi_W, agent_params.gamma, agent_params.eps0, agent_params.eta_R, agent_params.dt);
-    
-    // Create bind groups for both ping-pong states
-    let rd_bg_a = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-        layout: &compute_pipelines.rd_bgl,
-        entries: &[
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::TextureView(&field_textures.view_a_sample),
-            },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: wgpu::BindingResource::TextureView(&field_textures.view_b_store),
-            },
-            wgpu::BindGroupEntry {
-                binding: 2,
-                resource: rd_params_buffer.as_entire_binding(),
-            },
-            wgpu::BindGroupEntry {
-                binding: 3,
-                resource: occupancy_buffer.as_entire_binding(),
-            },
-        ],
-        label: Some("rd_bg_a"),
-    });
-    
-    let rd_bg_b = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-        layout: &compute_pipelines.rd_bgl,
-        entries: &[
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::TextureView(&field_textures.view_b_sample),
-            },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: wgpu::BindingResource::TextureView(&field_textures.view_a_store),
-            },
-            wgpu::BindGroupEntry {
-                binding: 2,
-                resource: rd_params_buffer.as_entire_binding(),
-            },
-            wgpu::BindGroupEntry {
-                binding: 3,
-                resource: occupancy_buffer.as_entire_binding(),
-            },
-        ],
-        label: Some("rd_bg_b"),
-    });
-    
-    let agent_bg = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-        layout: &compute_pipelines.agent_bgl,
-        entries: &[
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: agents_buffer.as_entire_binding(),
-            },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: wgpu::BindingResource::TextureView(&field_textures.view_a_sample),
-            },
-            wgpu::BindGroupEntry {
-                binding: 2,
-                resource: agent_params_buffer.as_entire_binding(),
-            },
-            wgpu::BindGroupEntry {
-                binding: 3,
-                resource: occupancy_buffer.as_entire_binding(),
-            },
-        ],
-        label: Some("agent_bg"),
-    });
-    
+    for s in &config.schedules {
+        println!("Schedule: {} {:?} {} -> {} (steps {}..{})", s.param, s.schedule, s.from, s.to, s.start, s.end);
+    }
+    let growth = &config.agents.growth;
+    match growth.policy {
+        GrowthPolicy::Reject => println!("Agent growth: reject (slots {:?} are the population ceilings)", config.agents.slots()),
+        GrowthPolicy::Grow => println!("Agent growth: grow ranges past {}% full to the next power of two, checked every {} steps (slots {:?}, ceilings {:?})",
+            growth.fill * 100.0, growth.check_every, config.agents.slots(), growth.max_population.by_kind()),
+    }
+    if let Some(p) = &sim.perturbation {
+        println!("Perturbation: {} by up to ±{}% every {} steps",
+            p.params().collect::<Vec<_>>().join(", "), config.perturbation.sigma * 100.0, p.every());
+    }
+    for f in sim.forcing.iter().flat_map(|f| f.series()) {
+        let (first, last) = (f.points[0][0], f.points[f.points.len() - 1][0]);
+        println!("Forcing: {} ({:?}) by `{}` of {} ({} samples, steps {}-{})",
+            f.param, f.mode, f.value_column, f.file.display(), f.points.len(), first, last);
+    }
+
     // Initialize metrics collection
-    let mut metrics_writer = MetricsWriter::new(&cli.out)?;
-    let mut snapshot_writer = SnapshotWriter::new(&cli.out)?;
-    
+    let mut sampler = Sampler::new(&config, &cli.out, &sim)?;
+    let snapshot_writer = SnapshotWriter::new(&cli.out)?;
+    let mut station_writer = if config.stations.is_empty() { None } else { Some(StationWriter::new(&cli.out, &config.stations)?) };
+    let mut hash_writer = cli.hash_every.map(|every| HashWriter::new(&cli.out, every)).transpose()?;
+    let mut perturbation_writer = sim.perturbation.as_ref().map(|p| PerturbationWriter::new(&cli.out, p)).transpose()?;
+    let mut chain_writer = (config.output.checkpoints.every > 0)
+        .then(|| ChainWriter::new(&cli.out.join("checkpoints"), &config.output.checkpoints))
+        .transpose()?;
+    if let Some(chain) = chain_writer.as_mut() {
+        chain.push(&sim.snapshot(gpu))?;
+    }
+    if let Some(scenario) = scenario.as_mut() {
+        // The script sees the seeded world until the first metrics sample
+        sim.field_manager.update_stats();
+        scenario.observe(&sim.field_manager.stats, &SpeciesTracker::new(&sim.agent_manager.agents).sample(&sim.agent_manager.agents));
+    }
+    let mut track_recorder = config.tracks.enabled
+        .then(|| TrackRecorder::new(&config.tracks, &config.world, &sim.agent_manager.agents));
+    let mut preview = cli.preview
+        .then(|| Preview::new(gpu, &sim))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("cannot open the preview window: {}", e))?;
+
+    // State to rebuild from after a device loss
+    let mut checkpoint = (config.gpu.checkpoint_every > 0).then(|| Checkpoint::take(&mut sim, gpu));
+    let mut tracked_layout = sim.agent_manager.layout.clone(); // Layout the species and track slots refer to
+    let mut growth_seen = 0; // Entries of sim.growth_events() already reported
+    let mut recoveries = 0;
+    let mut replay_until = 0; // Outputs of earlier steps were written before a device loss
+    let mut scripted = InteractionScript { seed: Some(config.world.seed), events: Vec::new() }; // Changes made by the scenario script
+    let mut scripted_until = 0; // The script was already called for earlier steps
+    let mut guard = RunGuard::new(&config.guards);
+    let mut aborted: Option<(u32, GuardTrip)> = None;
+    let aborted_path = cli.out.join("ABORTED");
+    if aborted_path.exists() {
+        std::fs::remove_file(&aborted_path)?;
+    }
+
     // Main simulation loop
     println!("Starting simulation for {} steps...", config.world.steps);
+    let progress = Progress::new(config.world.steps, cli.quiet);
     let start_time = Instant::now();
-    let mut use_a_as_src = true;
-    
-    for step in 0..=config.world.steps {
+    guard.start(0, start_time);
+
+    let mut step = 0;
+    while step <= config.world.steps {
         let step_start = Instant::now();
-        
-        // Update uniform buffers each frame with current parameters
-        gpu.queue.write_buffer(&rd_params_buffer, 0, bytemuck::bytes_of(&debug_rd_params));
-        gpu.queue.write_buffer(&agent_params_buffer, 0, bytemuck::bytes_of(&debug_agent_params));
-        
-        // Zero occupancy buffer
-        let zero_occupancy = vec![0u32; (config.world.size[0] * config.world.size[1]) as usize];
-        gpu.queue.write_buffer(&occupancy_buffer, 0, bytemuck::cast_slice(&zero_occupancy));
-        
-        // Agents pass -> occupancy
-        {
-            let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("agent_pass"),
-            });
-            
-            {
-                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                    label: Some("agent pass"),
-                    timestamp_writes: None,
-                });
-                cpass.set_pipeline(&compute_pipelines.agent_pipeline);
-                cpass.set_bind_group(0, &agent_bg, &[]);
-                
-                let gx = (config.agents.herbivores + 127) / 128;
-                cpass.dispatch_workgroups(gx, 1, 1);
-            } // cpass is dropped here
-            
-            gpu.submit(encoder.finish());
+
+        if cli.debug_scenario && step % config.output.debug_every == 0 {
+            progress.println(format!("Step {}: RD dispatch - groups=({}, {}), ping_pong={}",
+                step,
+                config.world.size[0].div_ceil(8),
+                config.world.size[1].div_ceil(8),
+                if sim.field.front_is_a() { "A->B" } else { "B->A" }
+            ));
+        }
+
+        // Scenario script changes; steps redone after a device loss repeat the logged ones
+        if let Some(scenario) = scenario.as_mut().filter(|_| step >= scripted_until) {
+            for action in scenario.on_step(step, &sim.config).map_err(anyhow::Error::msg)? {
+                scripted.push(step, action);
+            }
+            scripted_until = step + 1;
+        }
+
+        // Recorded viewer interactions made at this point of the run
+        for action in script.into_iter().chain(Some(&scripted)).flat_map(|s| s.at(step)) {
+            sim.apply_interaction(gpu, action).map_err(anyhow::Error::msg)?;
+            // Changed parameters keep the run's dt, so an unstable one is only reported
+            if let Interaction::Param { param, value } = action {
+                let limit = sim.config.dt_limit();
+                if sim.config.world.dt > limit.max_dt() {
+                    progress.eprintln(format!("Warning: {} = {} at step {} puts world.dt = {} above the stability limit {}",
+                        param, value, step, sim.config.world.dt, limit));
+                }
+            }
+        }
+
+        // Full states after 0, 200, 1000 and 2000 completed steps, named by the step in their header
+        if matches!(step, 0 | 200 | 1000 | 2000) {
+            let state = sim.snapshot(gpu);
+            state.write(&cli.out.join(state.file_name()))?;
+        }
+
+        // Agents pass -> occupancy -> RD pass -> flip ping-pong
+        sim.step(gpu);
+        if startup.first_step_ms.is_none() {
+            startup.first_step_ms = Some(startup::ms(step_start.elapsed()));
+        }
+
+        // Device lost: recreate it and resume from the checkpoint
+        if let Some(cause) = gpu.lost() {
+            let Some(saved) = checkpoint.as_ref().filter(|_| recoveries < config.gpu.max_recoveries) else {
+                anyhow::bail!("GPU {} at step {}; no checkpoint to recover from (gpu.checkpoint_every = {}, {} of {} recoveries used)",
+                    cause, step, config.gpu.checkpoint_every, recoveries, config.gpu.max_recoveries);
+            };
+            recoveries += 1;
+            progress.eprintln(format!("Warning: GPU {} at step {}; recreating the device and resuming from step {} (recovery {} of {})",
+                cause, step, saved.state.step, recoveries, config.gpu.max_recoveries));
+            sim = saved.recover(gpu, &config, (debug_rd_params, debug_agent_params), script.into_iter().chain(Some(&scripted)))?;
+            progress.println(gpu.info());
+            growth_seen = 0;
+            if preview.take().is_some() {
+                progress.println("Preview closed with the lost device, continuing without it");
+            }
+            replay_until = replay_until.max(step);
+            step = saved.state.step;
+            guard.start(step, Instant::now());
+            continue;
         }
-        
+
+        // Reallocations of the agent buffer during this step (agents.growth.policy: grow)
+        for event in &sim.growth_events()[growth_seen..] {
+            if step >= replay_until {
+                progress.println(format!("Step {}: agent buffer grew from {} to {} slots (alive {:?}, slots {:?} -> {:?})",
+                    event.step, event.from.total(), event.to.total(), event.alive,
+                    [0, 1, 2].map(|k| event.from.capacity(k)), [0, 1, 2].map(|k| event.to.capacity(k))));
+            }
+        }
+        growth_seen = sim.growth_events().len();
+        if sim.agent_manager.layout != tracked_layout {
+            sampler.tracker.relayout(&tracked_layout, &sim.agent_manager.layout);
+            if let Some(recorder) = track_recorder.as_mut() {
+                recorder.relayout(&tracked_layout, &sim.agent_manager.layout);
+            }
+            tracked_layout = sim.agent_manager.layout.clone();
+        }
+
+        // Births and deaths per cell over the window ending with this step; the
+        // counters restart even while replaying so checkpoints hold a partial window
+        let window = config.output.event_window;
+        if window > 0 && (step + 1).is_multiple_of(window) {
+            if let Some(maps) = sim.read_events(gpu) {
+                if step >= replay_until {
+                    snapshot_writer.write_event_maps(step + 1, &maps)?;
+                }
+                sampler.events = Some(maps.summary());
+                sim.clear_events(gpu);
+            }
+        }
+
+        // Checkpoint the state after this step
+        if config.gpu.checkpoint_every > 0 && (step + 1).is_multiple_of(config.gpu.checkpoint_every) {
+            checkpoint = Some(Checkpoint::take(&mut sim, gpu));
+        }
+        if step < replay_until {
+            progress.set_step(step);
+            step += 1;
+            continue;
+        }
+
+        // Draw the new front textures; closing the window leaves the run going
+        if let Some(window) = preview.as_mut() {
+            if !window.frame(gpu, &sim)? {
+                progress.println(format!("Preview closed at step {}, continuing without it", step));
+                preview = None;
+            }
+        }
+
         // Save occupancy PNG at specific steps
         if step == 0 || step == 200 || step == 1000 || step == 2000 {
-            // Read back occupancy buffer for PNG dump
-            let staging_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("occupancy_png_staging"),
-                size: (config.world.size[0] * config.world.size[1] * 4) as u64,
-                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-                mapped_at_creation: false,
-            });
-            
-            let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("occupancy_png_copy"),
-            });
-            encoder.copy_buffer_to_buffer(&occupancy_buffer, 0, &staging_buffer, 0, (config.world.size[0] * config.world.size[1] * 4) as u64);
-            gpu.submit(encoder.finish());
-            
-            staging_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
-            gpu.device.poll(wgpu::Maintain::Wait);
-            
-            let data = staging_buffer.slice(..).get_mapped_range();
-            let occupancy_data: Vec<u32> = data
-                .chunks_exact(4)
-                .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-                .collect();
-            
-            drop(data);
-            staging_buffer.unmap();
-            
+            let occupancy_image = sim.render_image(gpu, ImageKind::Occupancy);
+
             // Save occupancy PNG
             let png_path = cli.out.join(format!("occupancy_{:04}.png", step));
-            if let Err(e) = snapshots::save_occupancy_png(&occupancy_data, config.world.size, &png_path) {
-                eprintln!("Warning: Failed to save occupancy PNG: {}", e);
+            if let Err(e) = snapshots::save_rgba_png(occupancy_image, config.world.size, &png_path) {
+                progress.eprintln(format!("Warning: Failed to save occupancy PNG: {}", e));
             } else {
-                println!("Saved occupancy PNG: {}", png_path.display());
+                progress.println(format!("Saved occupancy PNG: {}", png_path.display()));
             }
         }
-        
-        // Debug: Check occupancy after agent pass (every 100 steps)
-        if cli.debug_scenario && step % 100 == 0 {
+
+        // Debug: Check occupancy after agent pass (every `output.debug_every` steps)
+        if cli.debug_scenario && step % config.output.debug_every == 0 {
             // Read back a small portion of the occupancy buffer to verify it's working
-            let staging_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("occupancy_debug"),
-                size: 1024, // Read first 256 u32s
-                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-                mapped_at_creation: false,
-            });
-            
-            let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("occupancy_debug_copy"),
-            });
-            encoder.copy_buffer_to_buffer(&occupancy_buffer, 0, &staging_buffer, 0, 1024);
-            gpu.submit(encoder.finish());
-            
-            staging_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
-            gpu.device.poll(wgpu::Maintain::Wait);
-            
-            let data = staging_buffer.slice(..).get_mapped_range();
-            let occupancy_sample: Vec<u32> = data.chunks_exact(4).map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect();
-            drop(data);
-            staging_buffer.unmap();
-            
+            let occupancy_sample = gpu.read_buffer::<u32>(sim.occupancy_buffer(), 1024); // First 256 u32s
             let total_occupancy: u32 = occupancy_sample.iter().sum();
-            println!("Step {}: Total occupancy after agents: {}", step, total_occupancy);
-        }
-        
-        // RD pass uses occupancy - select correct bind group based on ping-pong state
-        let current_rd_bg = if use_a_as_src { &rd_bg_a } else { &rd_bg_b };
-        
-        if cli.debug_scenario && step % 100 == 0 {
-            println!("Step {}: RD dispatch - groups=({}, {}), ping_pong={}", 
-                step, 
-                (config.world.size[0] + 7) / 8, 
-                (config.world.size[1] + 7) / 8,
-                if use_a_as_src { "A->B" } else { "B->A" }
-            );
+            progress.println(format!("Step {}: Total occupancy after agents: {}", step, total_occupancy));
         }
-        
-        {
-            let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("rd_pass"),
-            });
-            
-            {
-                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                    label: Some("rd pass"),
-                    timestamp_writes: None,
-                });
-                cpass.set_pipeline(&compute_pipelines.rd_pipeline);
-                cpass.set_bind_group(0, current_rd_bg, &[]);
-                
-                let gx = (config.world.size[0] + 7) / 8;
-                let gy = (config.world.size[1] + 7) / 8;
-                cpass.dispatch_workgroups(gx, gy, 1);
-            } // cpass is dropped here
-            
-            gpu.submit(encoder.finish());
-        }
-        
-        // Flip ping-pong
-        use_a_as_src = !use_a_as_src;
-        
-        // Debug: Check if field is actually changing (every 100 steps)
-        if cli.debug_scenario && step % 100 == 0 {
+
+        // Debug: Check if field is actually changing (every `output.debug_every` steps)
+        if cli.debug_scenario && step % config.output.debug_every == 0 {
             // Read back a single pixel from the current front texture to verify changes
-            let current_texture = if use_a_as_src { &field_textures.texture_a } else { &field_textures.texture_b };
-            
             let staging_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
                 label: Some("pixel_debug"),
                 size: 8, // Single pixel (4 channels × 2 bytes f16)
                 usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
                 mapped_at_creation: false,
             });
-            
+
             let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("pixel_debug_copy"),
             });
-            
+
             encoder.copy_texture_to_buffer(
                 wgpu::ImageCopyTexture {
-                    texture: current_texture,
+                    texture: sim.field.front_texture(),
                     mip_level: 0,
                     origin: wgpu::Origin3d::ZERO,
                     aspect: wgpu::TextureAspect::All,
@@ -411,7 +606,7 @@ fn main() -> Result<(), anyhow::Error> {
                     buffer: &staging_buffer,
                     layout: wgpu::ImageDataLayout {
                         offset: 0,
-                        bytes_per_row: Some(((8 + 255) / 256) * 256), // Align to 256-byte boundary
+                        bytes_per_row: Some(256), // Align to 256-byte boundary
                         rows_per_image: Some(1),
                     },
                 },
@@ -421,71 +616,122 @@ fn main() -> Result<(), anyhow::Error> {
                     depth_or_array_layers: 1,
                 },
             );
-            
+
             gpu.submit(encoder.finish());
-            
+
             staging_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
             gpu.device.poll(wgpu::Maintain::Wait);
-            
+
             let data = staging_buffer.slice(..).get_mapped_range();
             let r_bytes = [data[0], data[1]];
             let r_value = half::f16::from_le_bytes(r_bytes).to_f32();
             drop(data);
             staging_buffer.unmap();
-            
-            println!("Step {}: Pixel (0,0) R value: {:.6}", step, r_value);
+
+            progress.println(format!("Step {}: Pixel (0,0) R value: {:.6}", step, r_value));
         }
-        
-        // Optional: add noise to R
-        if config.noise.sigma > 0.0 {
-            // For now, we'll add noise on the CPU side after downloading
-            // In a production version, this could be done on GPU
+
+        // Agent tracks every `tracks.every` steps
+        if let Some(recorder) = track_recorder.as_mut().filter(|r| r.is_due(step)) {
+            sim.sync_agents(gpu);
+            recorder.record(step, &sim.agent_manager.agents);
         }
-        
-        // Metrics and logging every 50 steps
-        if step % 50 == 0 {
-            // Download field data for metrics
-            field_textures.download_field_data(&gpu.device, &gpu.queue, &mut field_manager);
-            
-            // Update statistics
-            field_manager.update_stats();
-            agent_manager.update_stats();
-            
-            // Write metrics
-            let step_time = step_start.elapsed();
-            metrics_writer.write_step(step, &field_manager.stats, &agent_manager.stats, step_time)?;
-            
-            println!("Step {}: R={:.3}, W={:.3}, Agents={}, Time={:?}", 
-                step, 
-                field_manager.stats.mean_R, 
-                field_manager.stats.mean_W,
-                agent_manager.stats.alive_count,
-                step_time
-            );
+
+        // Checkpoint chain every `output.checkpoints.every` steps, as deltas between keyframes
+        if let Some(chain) = chain_writer.as_mut().filter(|_| (step + 1).is_multiple_of(config.output.checkpoints.every)) {
+            chain.push(&sim.snapshot(gpu))?;
+        }
+
+        // Sampling stations due at this step
+        if let Some(writer) = station_writer.as_mut() {
+            let due: Vec<usize> = (0..config.stations.len()).filter(|&i| config.stations[i].is_due(step)).collect();
+            if !due.is_empty() {
+                sim.sync_agents(gpu);
+                let stations: Vec<_> = due.iter().map(|&i| &config.stations[i]).collect();
+                writer.write_step(step, step as f32 * config.world.dt, &due, &sim.read_stations(gpu, &stations))?;
+            }
+        }
+
+        // Metrics and logging every `output.metrics_every` steps
+        if step.is_multiple_of(config.output.metrics_every) {
+            let sample = sampler.write(&mut sim, gpu, &config, step, step_start, &progress)?;
+            if let Some(scenario) = scenario.as_mut() {
+                scenario.observe(&sample.field, &sample.species);
+            }
+
+            // Kill switches (`guards`): stop before a blown-up run wastes more time
+            if let Some(trip) = guard.check(step, Instant::now(), Some(&sample.field), &sim.agent_manager.agents) {
+                progress.eprintln(format!("Error: aborting at step {}: {}", step, trip));
+                aborted = Some((step, trip));
+                break;
+            }
+        }
+
+        // Parameter values of each perturbation draw
+        if let (Some(writer), Some(p)) = (perturbation_writer.as_mut(), sim.perturbation.as_ref().filter(|p| p.draws_at(step))) {
+            writer.write_draw(step, &p.values_at(step))?;
         }
-        
+
+        // State hashes every `--hash-every` steps
+        if let Some(writer) = hash_writer.as_mut().filter(|w| w.is_due(step)) {
+            writer.write_step(step, sim.state_hash(gpu))?;
+        }
+
         // Snapshots at specific steps
         if matches!(step, 0 | 200 | 1000 | 2000) {
-            // Download field data for snapshot
-            field_textures.download_field_data(&gpu.device, &gpu.queue, &mut field_manager);
-            
-            // Write snapshots
-            snapshot_writer.write_field_snapshot(step, &field_manager)?;
-            snapshot_writer.write_agents_snapshot(step, &agent_manager)?;
-            
-            println!("Snapshot written for step {}", step);
+            sim.sync_agents(gpu);
+            snapshot_writer.write_field_snapshot(step, config.world.size, sim.render_image(gpu, ImageKind::Field))?;
+            snapshot_writer.write_agents_snapshot(step, &sim.agent_manager)?;
+            if config.output.utility_maps {
+                snapshot_writer.write_utility_snapshot(step, &sim.utility_map(gpu), config.world.size)?;
+            }
+            progress.println(format!("Snapshot written for step {}", step));
+        } else if config.output.frames_every > 0 && step % config.output.frames_every == 0 {
+            // Video frames only need the field image and the agents
+            sim.sync_agents(gpu);
+            snapshot_writer.write_field_snapshot(step, config.world.size, sim.render_image(gpu, ImageKind::Field))?;
+            snapshot_writer.write_agents_snapshot(step, &sim.agent_manager)?;
         }
-        
-        // Check for extinction
-        if agent_manager.get_alive_count() == 0 {
-            println!("Warning: All agents died at step {}", step);
+
+        // Check for extinction (a world without agent slots is a pure reaction-diffusion run)
+        if sim.agent_count() > 0 && sim.agent_manager.get_alive_count() == 0 {
+            progress.println(format!("Warning: All agents died at step {}", step));
             break;
         }
+        progress.set_step(step);
+        step += 1;
     }
-    
+    progress.finish();
+
+    if let Some(recorder) = &track_recorder {
+        let path = tracks::write_tracks(&cli.out, recorder.tracks(), config.tracks.format, config.world.dt)?;
+        println!("Tracks for {} agents written to {}", recorder.tracks().len(), path.display());
+    }
+
+    if let Some(chain) = &chain_writer {
+        println!("Checkpoint chain of {} states ({} keyframes) written to checkpoints/: {:.1} MiB, {:.1}% of full snapshots",
+            chain.checkpoints, chain.keyframes, chain.bytes as f64 / (1 << 20) as f64, 100.0 * chain.bytes as f64 / chain.full_bytes as f64);
+    }
+
+    if scenario.is_some() {
+        scripted.save(&cli.out.join("script_events.yaml")).map_err(anyhow::Error::msg)?;
+        println!("{} scenario script changes written to script_events.yaml (replay with --replay)", scripted.events.len());
+    }
+
+    if let Some(totals) = sim.read_occupancy_total(gpu) {
+        snapshot_writer.write_occupancy_total(&totals, config.world.size)?;
+        println!("Occupancy totals over {} steps written to occupancy_total.csv and occupancy_total.png", sim.current_step());
+    }
+
     let total_time = start_time.elapsed();
+    startup::write_run_json(&cli.out, sim.current_step(), &startup, &sim.pipelines, total_time)?;
+    if let Some((step, trip)) = aborted {
+        std::fs::write(&aborted_path, format!("step {}: {}\n", step, trip))?;
+        anyhow::bail!("run aborted at step {} after {:?}: {}; partial results in {} (marked by ABORTED)",
+            step, total_time, trip, cli.out.display());
+    }
     println!("Simulation completed in {:?}", total_time);
     println!("Results written to {}", cli.out.display());
-    
+
     Ok(())
 }
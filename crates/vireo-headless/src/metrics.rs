@@ -1,46 +1,87 @@
-use std::path::PathBuf;
+use std::path::Path;
 use std::fs::File;
 use std::io::Write;
 use csv::Writer;
-use std::time::Duration;
-use vireo_core::sim::{FieldStats, AgentStats};
+use vireo_core::{SimulationConfig, StationConfig, SPECIES};
+use vireo_core::sim::{
+    AgeStructure, AgentColumns, AgentStats, BehaviorColumns, ClockColumns, CohortStats, DiversityColumns, EventColumns, FieldColumns, MetricProvider, MetricValue,
+    MetricsRegistry, MetricsSample, MovementColumns, ParamPerturbation, SamplingColumns, SoilColumns, SpeciesColumns, SpeciesStats, StationSample, TimingColumns, TraitColumns,
+};
 
-/// Metrics writer for CSV output and performance logging
+/// Writes one `metrics.csv` row per sample from a `MetricsRegistry`
+///
+/// The header is the registry's columns, so providers registered before
+/// `new` (see `standard_registry`) add columns without touching the writer.
 pub struct MetricsWriter {
     csv_writer: Writer<File>,
-    step_count: u32,
-    herbivore_history: Vec<u32>, // Track herbivore counts for cycle detection
-    energy_history: Vec<f32>,    // Track energy for cycle detection
+    registry: MetricsRegistry,
 }
 
 impl MetricsWriter {
     /// Create a new metrics writer
-    pub fn new(output_dir: &PathBuf) -> Result<Self, anyhow::Error> {
+    pub fn new(output_dir: &Path, registry: MetricsRegistry) -> Result<Self, anyhow::Error> {
         let csv_path = output_dir.join("metrics.csv");
         let file = File::create(&csv_path)?;
         
         let mut csv_writer = Writer::from_writer(file);
-        
-        // Write CSV header with enhanced metrics
-        csv_writer.write_record(&[
-            "step",
-            "mean_R", "mean_W", "var_R", "var_W", "mean_grad_R",
-            "max_R", "max_W", "min_R", "min_W",
-            "alive_count", "total_energy", "mean_energy", "mean_velocity", "foraging_efficiency",
-            "cycle_score", "foraging_efficiency_enhanced",
-            "wall_time_ms", "fps_proxy"
-        ])?;
+        csv_writer.write_record(registry.columns())?;
         
         Ok(Self {
             csv_writer,
-            step_count: 0,
-            herbivore_history: Vec::new(),
-            energy_history: Vec::new(),
+            registry,
         })
     }
     
+    /// Write metrics for a single simulation step
+    pub fn write_step(&mut self, sample: &MetricsSample) -> Result<(), anyhow::Error> {
+        let record = self.registry.row(sample);
+        self.csv_writer.write_record(record.iter().map(|v| v.to_string()))?;
+        
+        self.csv_writer.flush()?;
+        
+        Ok(())
+    }
+}
+
+/// The `metrics.csv` columns of a headless run: clock, field, sampling errors
+/// (with `output.sampling`), soil (with `soil.enabled`), agents, event windows (with `output.event_window`), cycle scores, movement, timing, per-species
+/// columns (e.g. `births_herbivores`), diversity indices, then chemotaxis trait
+/// distributions (with `evolution.enabled`)
+pub fn standard_registry(config: &SimulationConfig) -> Result<MetricsRegistry, String> {
+    let mut registry = MetricsRegistry::new();
+    registry.register(ClockColumns)?;
+    registry.register(FieldColumns)?;
+    if config.output.sampling.enabled {
+        registry.register(SamplingColumns)?;
+    }
+    if config.soil.enabled {
+        registry.register(SoilColumns)?;
+    }
+    registry.register(AgentColumns)?;
+    registry.register(BehaviorColumns)?;
+    if config.output.event_window > 0 {
+        registry.register(EventColumns)?;
+    }
+    registry.register(CycleColumns::default())?;
+    registry.register(MovementColumns)?;
+    registry.register(TimingColumns)?;
+    registry.register(SpeciesColumns)?;
+    registry.register(DiversityColumns)?;
+    if config.evolution.enabled {
+        registry.register(TraitColumns)?;
+    }
+    Ok(registry)
+}
+
+/// Cycle score and enhanced foraging efficiency, from a window of recent samples
+#[derive(Default)]
+pub struct CycleColumns {
+    herbivore_history: Vec<u32>, // Track herbivore counts for cycle detection
+}
+
+impl CycleColumns {
     /// Compute cycle score based on autocorrelation of herbivore count
-    fn compute_cycle_score(&self, current_count: u32) -> f32 {
+    fn compute_cycle_score(&self) -> f32 {
         if self.herbivore_history.len() < 50 {
             return 0.0; // Need more data for meaningful cycle detection
         }
@@ -69,7 +110,7 @@ impl MetricsWriter {
     }
     
     /// Compute enhanced foraging efficiency (energy gain per unit movement)
-    fn compute_enhanced_foraging_efficiency(&self, agent_stats: &AgentStats) -> f32 {
+    fn compute_enhanced_foraging_efficiency(agent_stats: &AgentStats) -> f32 {
         if agent_stats.mean_velocity > 0.0 {
             // Enhanced efficiency: energy per unit movement, normalized
             let base_efficiency = agent_stats.mean_energy / agent_stats.mean_velocity;
@@ -79,61 +120,209 @@ impl MetricsWriter {
             0.0
         }
     }
-    
-    /// Write metrics for a single simulation step
-    pub fn write_step(
-        &mut self,
-        step: u32,
-        field_stats: &FieldStats,
-        agent_stats: &AgentStats,
-        step_time: Duration,
-    ) -> Result<(), anyhow::Error> {
-        // Update history for cycle detection
-        self.herbivore_history.push(agent_stats.alive_count);
-        self.energy_history.push(agent_stats.mean_energy);
-        
+}
+
+impl MetricProvider for CycleColumns {
+    fn columns(&self) -> Vec<String> {
+        ["cycle_score", "foraging_efficiency_enhanced"].map(String::from).to_vec()
+    }
+
+    fn record(&mut self, sample: &MetricsSample, row: &mut Vec<MetricValue>) {
         // Keep only last 200 entries to avoid memory bloat
+        self.herbivore_history.push(sample.agents.alive_count);
         if self.herbivore_history.len() > 200 {
             self.herbivore_history.remove(0);
-            self.energy_history.remove(0);
         }
-        
-        let wall_time_ms = step_time.as_millis() as f64;
-        let fps_proxy = if wall_time_ms > 0.0 { 1000.0 / wall_time_ms } else { 0.0 };
-        
-        let cycle_score = self.compute_cycle_score(agent_stats.alive_count);
-        let foraging_efficiency_enhanced = self.compute_enhanced_foraging_efficiency(agent_stats);
-        
-        self.csv_writer.write_record(&[
-            &step.to_string(),
-            &field_stats.mean_R.to_string(),
-            &field_stats.mean_W.to_string(),
-            &field_stats.var_R.to_string(),
-            &field_stats.var_W.to_string(),
-            &field_stats.mean_grad_R.to_string(),
-            &field_stats.max_R.to_string(),
-            &field_stats.max_W.to_string(),
-            &field_stats.min_R.to_string(),
-            &field_stats.min_W.to_string(),
-            &agent_stats.alive_count.to_string(),
-            &agent_stats.total_energy.to_string(),
-            &agent_stats.mean_energy.to_string(),
-            &agent_stats.mean_velocity.to_string(),
-            &agent_stats.foraging_efficiency.to_string(),
-            &cycle_score.to_string(),
-            &foraging_efficiency_enhanced.to_string(),
-            &wall_time_ms.to_string(),
-            &fps_proxy.to_string(),
+        row.push(self.compute_cycle_score().into());
+        row.push(Self::compute_enhanced_foraging_efficiency(sample.agents).into());
+    }
+}
+
+/// Per-cohort survival and dispersal, one row per cohort per sample (`cohorts.csv`)
+pub struct CohortWriter {
+    csv_writer: Writer<File>,
+}
+
+impl CohortWriter {
+    pub fn new(output_dir: &Path) -> Result<Self, anyhow::Error> {
+        let file = File::create(output_dir.join("cohorts.csv"))?;
+        let mut csv_writer = Writer::from_writer(file);
+
+        csv_writer.write_record([
+            "step", "cohort", "tagged", "alive", "survival", "mean_dispersal", "max_dispersal"
         ])?;
-        
+
+        Ok(Self { csv_writer })
+    }
+
+    /// Write one row per cohort for `step`
+    pub fn write_step(&mut self, step: u32, stats: &[CohortStats]) -> Result<(), anyhow::Error> {
+        for c in stats {
+            self.csv_writer.write_record([
+                &step.to_string(),
+                &c.cohort.to_string(),
+                &c.tagged.to_string(),
+                &c.alive.to_string(),
+                &c.survival.to_string(),
+                &c.mean_dispersal.to_string(),
+                &c.max_dispersal.to_string(),
+            ])?;
+        }
         self.csv_writer.flush()?;
-        self.step_count += 1;
-        
         Ok(())
     }
-    
-    /// Get the number of steps written
-    pub fn step_count(&self) -> u32 {
-        self.step_count
+}
+
+/// Writes `stations/<name>.csv` for every sampling station: R and W in its
+/// cell and the alive agents of each species it covers, one row per recording
+pub struct StationWriter {
+    csv_writers: Vec<Writer<File>>, // In `stations` order
+}
+
+impl StationWriter {
+    pub fn new(output_dir: &Path, stations: &[StationConfig]) -> Result<Self, anyhow::Error> {
+        let dir = output_dir.join("stations");
+        std::fs::create_dir_all(&dir)?;
+        let csv_writers = stations.iter()
+            .map(|station| {
+                let mut csv_writer = Writer::from_writer(File::create(dir.join(format!("{}.csv", station.name)))?);
+                csv_writer.write_record(["step", "time", "R", "W"].into_iter().chain(SPECIES))?;
+                Ok(csv_writer)
+            })
+            .collect::<Result<_, anyhow::Error>>()?;
+        Ok(Self { csv_writers })
+    }
+
+    /// Write the readings of the stations at `indices` for `step`, `time` units into the run
+    pub fn write_step(&mut self, step: u32, time: f32, indices: &[usize], samples: &[StationSample]) -> Result<(), anyhow::Error> {
+        for (&i, sample) in indices.iter().zip(samples) {
+            let csv_writer = &mut self.csv_writers[i];
+            let mut record = vec![step.to_string(), time.to_string(), sample.resource.to_string(), sample.waste.to_string()];
+            record.extend(sample.occupancy.iter().map(|n| n.to_string()));
+            csv_writer.write_record(&record)?;
+            csv_writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-species metrics in long format, one row per species and metric (`species.csv`)
+pub struct SpeciesWriter {
+    csv_writer: Writer<File>,
+}
+
+impl SpeciesWriter {
+    pub fn new(output_dir: &Path) -> Result<Self, anyhow::Error> {
+        let file = File::create(output_dir.join("species.csv"))?;
+        let mut csv_writer = Writer::from_writer(file);
+
+        csv_writer.write_record(["step", "species", "metric", "value"])?;
+
+        Ok(Self { csv_writer })
+    }
+
+    /// Write every metric of every species for `step`
+    pub fn write_step(&mut self, step: u32, stats: &[SpeciesStats]) -> Result<(), anyhow::Error> {
+        for s in stats {
+            for (metric, value) in s.metrics() {
+                self.csv_writer.write_record([step.to_string().as_str(), s.name(), metric, value.to_string().as_str()])?;
+            }
+        }
+        self.csv_writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Age structure in long format, one row per species and age class (`ages.csv`)
+///
+/// `age_to` is the first age past the class and empty for the open-ended last
+/// class, so a population pyramid is one group-by away.
+pub struct AgeWriter {
+    csv_writer: Writer<File>,
+}
+
+impl AgeWriter {
+    pub fn new(output_dir: &Path) -> Result<Self, anyhow::Error> {
+        let file = File::create(output_dir.join("ages.csv"))?;
+        let mut csv_writer = Writer::from_writer(file);
+
+        csv_writer.write_record(["step", "species", "age_from", "age_to", "count"])?;
+
+        Ok(Self { csv_writer })
+    }
+
+    /// Write every class of every species for `step`
+    pub fn write_step(&mut self, step: u32, ages: &AgeStructure) -> Result<(), anyhow::Error> {
+        for (kind, species) in SPECIES.iter().enumerate() {
+            for (class, counts) in ages.counts.iter().enumerate() {
+                let (from, to) = ages.class_range(class);
+                self.csv_writer.write_record([
+                    step.to_string().as_str(),
+                    species,
+                    from.to_string().as_str(),
+                    to.map(|t| t.to_string()).unwrap_or_default().as_str(),
+                    counts[kind].to_string().as_str(),
+                ])?;
+            }
+        }
+        self.csv_writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Quantized state hashes (`state_hashes.csv`), one row per `--hash-every` steps
+///
+/// Golden tests compare these sequences instead of full snapshots; the hash
+/// is `StateSnapshot::state_hash`, written as 16 hex digits.
+pub struct HashWriter {
+    csv_writer: Writer<File>,
+    every: u32,
+}
+
+impl HashWriter {
+    pub fn new(output_dir: &Path, every: u32) -> Result<Self, anyhow::Error> {
+        let file = File::create(output_dir.join("state_hashes.csv"))?;
+        let mut csv_writer = Writer::from_writer(file);
+
+        csv_writer.write_record(["step", "hash"])?;
+
+        Ok(Self { csv_writer, every })
+    }
+
+    pub fn is_due(&self, step: u32) -> bool {
+        step.is_multiple_of(self.every)
+    }
+
+    pub fn write_step(&mut self, step: u32, hash: u64) -> Result<(), anyhow::Error> {
+        self.csv_writer.write_record([step.to_string(), format!("{:016x}", hash)])?;
+        self.csv_writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes `perturbations.csv`: the parameter values of each perturbation draw
+pub struct PerturbationWriter {
+    csv_writer: Writer<File>,
+}
+
+impl PerturbationWriter {
+    pub fn new(output_dir: &Path, perturbation: &ParamPerturbation) -> Result<Self, anyhow::Error> {
+        let file = File::create(output_dir.join("perturbations.csv"))?;
+        let mut csv_writer = Writer::from_writer(file);
+
+        let mut header = vec!["step"];
+        header.extend(perturbation.params());
+        csv_writer.write_record(&header)?;
+
+        Ok(Self { csv_writer })
+    }
+
+    /// Record the draw taking effect at `step`
+    pub fn write_draw(&mut self, step: u32, values: &[f32]) -> Result<(), anyhow::Error> {
+        let mut record = vec![step.to_string()];
+        record.extend(values.iter().map(|v| v.to_string()));
+        self.csv_writer.write_record(&record)?;
+        self.csv_writer.flush()?;
+        Ok(())
     }
 }
@@ -0,0 +1,193 @@
+use std::fs::File;
+use std::path::Path;
+use std::time::Instant;
+use clap::{Args, ValueEnum};
+use csv::Writer;
+use vireo_core::SimulationConfig;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{apply_sweep_point, sweep_design, BayesOpt, ParamRange, SimRng, SweepStrategy};
+
+/// Bayesian optimization options
+#[derive(Args)]
+pub struct OptimizeArgs {
+    /// Searched parameter and range, e.g. `chemotaxis.chi_R=2:10` (repeat for more parameters)
+    #[arg(short, long = "param", required = true)]
+    pub params: Vec<ParamRange>,
+
+    /// Quantity to maximize
+    #[arg(long, value_enum, default_value = "coexistence")]
+    pub objective: Objective,
+
+    /// Total number of runs, including the initial design
+    #[arg(short, long, default_value_t = 30)]
+    pub budget: u32,
+
+    /// Latin hypercube runs before the Gaussian process takes over (default: 2 per parameter, at least 4)
+    #[arg(long)]
+    pub init: Option<u32>,
+
+    /// Kernel length scale as a fraction of each parameter range
+    #[arg(long, default_value_t = 0.25)]
+    pub length_scale: f64,
+
+    /// Random candidates scored by expected improvement per suggestion
+    #[arg(long, default_value_t = 2000)]
+    pub candidates: usize,
+
+    /// Read back the population every N steps (runs stop early on extinction)
+    #[arg(long, default_value_t = 50)]
+    pub sample_every: u32,
+
+    /// Number of best configs written as best_N.yaml
+    #[arg(long, default_value_t = 3)]
+    pub keep: usize,
+}
+
+/// Objectives computed from the sampled population and the final state
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Objective {
+    /// Alive agents at the end of the run
+    Alive,
+    /// Steps until the first species (plants, herbivores or predators) dies out
+    Coexistence,
+    /// Half the peak-to-trough herbivore count over the second half of the run, relative to its mean
+    CycleAmplitude,
+    /// Mean energy of the surviving agents at the end
+    MeanEnergy,
+    /// Mean resource concentration at the end
+    MeanResource,
+}
+
+/// Search the parameter ranges, writing `optimize.csv` and the best configs to `out`
+///
+/// All runs share `world.seed` (as in `sweep`), so the objective differences
+/// come from the parameters. The initial design and the candidate draws are
+/// seeded from it too, so an optimization reruns identically.
+pub fn run(gpu: &GpuDevice, config: &SimulationConfig, args: &OptimizeArgs, out: &Path) -> Result<(), anyhow::Error> {
+    if args.sample_every == 0 {
+        anyhow::bail!("--sample-every must be greater than 0.");
+    }
+    if !(args.length_scale.is_finite() && args.length_scale > 0.0) {
+        anyhow::bail!("--length-scale must be positive.");
+    }
+    let init = args.init.unwrap_or((2 * args.params.len() as u32).max(4)).min(args.budget);
+    if init == 0 {
+        anyhow::bail!("--budget must allow at least one run.");
+    }
+
+    let mut rng = SimRng::new(config.world.rng, config.world.seed);
+    let initial = sweep_design(SweepStrategy::LatinHypercube, &args.params, Some(init), 0, &mut rng)
+        .map_err(anyhow::Error::msg)?;
+
+    println!("Optimize: maximize {:?} with {} runs ({} initial) × {} steps",
+        args.objective, args.budget, init, config.world.steps);
+    let start_time = Instant::now();
+
+    let path = out.join("optimize.csv");
+    let mut writer = Writer::from_writer(File::create(&path)?);
+    let mut header = vec!["run".to_string(), "phase".to_string()];
+    header.extend(args.params.iter().map(|p| p.param.clone()));
+    header.push("objective".to_string());
+    writer.write_record(&header)?;
+
+    let mut optimizer = BayesOpt::new(&args.params, args.length_scale);
+    let mut evaluated: Vec<(SimulationConfig, f64)> = Vec::new();
+    for i in 0..args.budget as usize {
+        let (phase, point) = match initial.get(i) {
+            Some(point) => ("init", point.clone()),
+            None => ("gp", optimizer.suggest(&mut rng, args.candidates)),
+        };
+
+        let mut run_config = config.clone();
+        apply_sweep_point(&mut run_config, &args.params, &point);
+        // Points outside what the config accepts score as the worst possible run
+        let value = match run_config.validate().and_then(|_| run_config.enforce_dt_limit()) {
+            Ok(clamped) => {
+                if let Some(dt) = clamped {
+                    eprintln!("Warning: run {}: world.dt = {} exceeds the stability limit {}, clamped to {}",
+                        i + 1, dt, run_config.dt_limit(), run_config.world.dt);
+                }
+                evaluate(gpu, run_config.clone(), args)
+            }
+            Err(e) => {
+                println!("Run {}: {}", i + 1, e);
+                f64::NEG_INFINITY
+            }
+        };
+        let best = optimizer.best().map_or(f64::NEG_INFINITY, |(_, v)| v).max(value);
+        println!("Run {}/{} ({}) {:?}: {:?} = {} (best {})", i + 1, args.budget, phase, point, args.objective, value, best);
+
+        let mut record = vec![i.to_string(), phase.to_string()];
+        record.extend(point.iter().map(|v| v.to_string()));
+        record.push(value.to_string());
+        writer.write_record(&record)?;
+        writer.flush()?;
+
+        if value.is_finite() {
+            optimizer.observe(&point, value);
+            evaluated.push((run_config, value));
+        }
+    }
+
+    evaluated.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (rank, (best_config, value)) in evaluated.iter().take(args.keep).enumerate() {
+        let best_path = out.join(format!("best_{}.yaml", rank + 1));
+        let yaml = serde_yaml::to_string(best_config)?;
+        std::fs::write(&best_path, format!("# {:?} = {}\n{}", args.objective, value, yaml))?;
+        println!("#{} {:?} = {} -> {}", rank + 1, args.objective, value, best_path.display());
+    }
+
+    println!("Optimize completed in {:?}", start_time.elapsed());
+    println!("Runs written to {}", path.display());
+
+    Ok(())
+}
+
+/// Run one configuration and score it
+fn evaluate(gpu: &GpuDevice, config: SimulationConfig, args: &OptimizeArgs) -> f64 {
+    let steps = config.world.steps;
+    let mut sim = Simulation::new(gpu, config);
+    let mut herbivores = Vec::new(); // (step, herbivore count) samples
+    let mut coexistence = steps;
+
+    for step in 1..=steps {
+        sim.step(gpu);
+
+        if step % args.sample_every != 0 && step != steps {
+            continue;
+        }
+
+        sim.sync_agents(gpu);
+        let mut by_kind = [0u32; 3];
+        for a in sim.agent_manager.agents.iter().filter(|a| a.is_alive()) {
+            by_kind[(a.kind as usize).min(2)] += 1;
+        }
+        herbivores.push((step, by_kind[1] as f64));
+        if coexistence == steps && by_kind.contains(&0) {
+            coexistence = step;
+        }
+        if by_kind.iter().all(|&n| n == 0) {
+            break;
+        }
+    }
+
+    match args.objective {
+        Objective::Alive => sim.agent_manager.get_alive_count() as f64,
+        Objective::Coexistence => coexistence as f64,
+        Objective::CycleAmplitude => {
+            let late: Vec<f64> = herbivores.iter().filter(|(s, _)| *s > steps / 2).map(|(_, n)| *n).collect();
+            let mean = late.iter().sum::<f64>() / late.len().max(1) as f64;
+            let (lo, hi) = late.iter().fold((f64::MAX, f64::MIN), |(lo, hi), &n| (lo.min(n), hi.max(n)));
+            if late.is_empty() || mean == 0.0 { 0.0 } else { 0.5 * (hi - lo) / mean }
+        }
+        Objective::MeanEnergy => {
+            sim.agent_manager.update_stats();
+            sim.agent_manager.stats.mean_energy as f64
+        }
+        Objective::MeanResource => {
+            sim.sync_field(gpu);
+            sim.field_manager.update_stats();
+            sim.field_manager.stats.mean_R as f64
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::fs::File;
+use std::path::Path;
+use std::time::Instant;
+use clap::Args;
+use csv::Writer;
+use vireo_core::{FieldChannel, SimulationConfig};
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{Divergence, ReferenceField};
+
+/// Precision report options
+#[derive(Args)]
+pub struct PrecisionArgs {
+    /// Steps to run (replaces world.steps)
+    #[arg(long, default_value_t = 10_000)]
+    pub steps: u32,
+
+    /// Compare the fields every N steps
+    #[arg(long, default_value_t = 100)]
+    pub every: u32,
+}
+
+/// Trajectories compared in the report, as (column prefix, first, second)
+const PAIRS: [(&str, usize, usize); 3] = [("gpu_f64", 0, 2), ("f32_f64", 1, 2), ("gpu_f32", 0, 1)];
+
+/// Run the GPU world next to f32 and f64 CPU references of its field and write `precision.csv` to `out`
+///
+/// The references follow the GPU's herbivores (its occupancy counts), light and
+/// uniforms step by step, so only arithmetic and storage precision separate the
+/// three fields: rgba16f texels on the GPU, f32 and f64 on the CPU.
+pub fn run(gpu: &GpuDevice, config: &SimulationConfig, args: &PrecisionArgs, out: &Path) -> Result<(), anyhow::Error> {
+    if args.every == 0 {
+        anyhow::bail!("--every must be greater than 0.");
+    }
+    ReferenceField::<f64>::check(config).map_err(anyhow::Error::msg)?;
+
+    println!("Precision report: {} steps, f16 GPU field against f32 and f64 CPU references", args.steps);
+    let start_time = Instant::now();
+
+    let mut sim = Simulation::new(gpu, config.clone());
+    sim.sync_field(gpu);
+    let mut single = ReferenceField::<f32>::new(&sim.field_manager);
+    let mut double = ReferenceField::<f64>::new(&sim.field_manager);
+
+    let path = out.join("precision.csv");
+    let mut writer = Writer::from_writer(File::create(&path)?);
+    let mut header = vec!["step".to_string()];
+    for c in FieldChannel::ALL {
+        header.extend(["gpu", "f32", "f64"].map(|t| format!("mean_{}_{}", c.name(), t)));
+        for (pair, _, _) in PAIRS {
+            header.extend(["max", "rms"].map(|s| format!("{}_{}_{}", c.name(), pair, s)));
+        }
+    }
+    writer.write_record(&header)?;
+
+    let mut last = Vec::new();
+    for step in 0..=args.steps {
+        if step > 0 {
+            let light = sim.time().light;
+            sim.step(gpu);
+            let occupancy = sim.read_occupancy(gpu);
+            single.step(&sim.rd_params, &occupancy, light);
+            double.step(&sim.rd_params, &occupancy, light);
+        }
+        if step % args.every != 0 && step != args.steps {
+            continue;
+        }
+
+        sim.sync_field(gpu);
+        let mut row = vec![step as f64];
+        last.clear();
+        for c in FieldChannel::ALL {
+            let gpu_values: Vec<f64> = sim.field_manager.channel_f32(c).into_iter().map(f64::from).collect();
+            let fields = [gpu_values, single.channel(c), double.channel(c)];
+            row.extend(fields.iter().map(|f| f.iter().sum::<f64>() / f.len() as f64));
+            for (pair, a, b) in PAIRS {
+                let d = Divergence::between(&fields[a], &fields[b]);
+                row.extend([d.max_abs, d.rms]);
+                last.push((c, pair, d));
+            }
+        }
+        writer.write_record(row.iter().map(|v| v.to_string()))?;
+    }
+    writer.flush()?;
+
+    for (c, pair, d) in last.iter().filter(|(c, _, _)| *c != FieldChannel::Soil || config.soil.enabled) {
+        println!("{} {}: max |Δ| = {:.3e}, rms = {:.3e}", c.name(), pair, d.max_abs, d.rms);
+    }
+    println!("Precision report completed in {:?}", start_time.elapsed());
+    println!("Divergence written to {}", path.display());
+
+    Ok(())
+}
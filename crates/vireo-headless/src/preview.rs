@@ -0,0 +1,137 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use winit::dpi::LogicalSize;
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoop;
+use winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+use winit::window::{Window, WindowBuilder};
+use vireo_app::agent_view::AgentViewParams;
+use vireo_app::camera::Camera;
+use vireo_app::palette::AgentColorParams;
+use vireo_app::renderer::{FrameInputs, FrameUniforms, Renderer};
+use vireo_core::gpu::{GpuDevice, Simulation};
+
+/// Shortest time between two presented frames (about 30 FPS)
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Window showing the live field and agents of a headless run
+///
+/// Draws straight from the simulation's front field texture and agent buffer
+/// with the viewer's `Renderer`, so previewing costs no readback. Frames are
+/// throttled to `FRAME_INTERVAL`; steps in between are not drawn.
+pub struct Preview {
+    event_loop: EventLoop<()>,
+    window: Arc<Window>,
+    surface: wgpu::Surface<'static>,
+    config: wgpu::SurfaceConfiguration,
+    renderer: Renderer,
+    uniforms: FrameUniforms,
+    camera: Camera,
+    last_frame: Option<Instant>,
+}
+
+impl Preview {
+    /// Open a window sized for `sim`'s world on the device the simulation runs on
+    pub fn new(gpu: &GpuDevice, sim: &Simulation) -> Result<Self, anyhow::Error> {
+        let event_loop = EventLoop::new()?;
+        let window = Arc::new(WindowBuilder::new()
+            .with_title("Vireo headless preview")
+            .with_inner_size(LogicalSize::new(768.0, 768.0))
+            .build(&event_loop)?);
+
+        // The surface must come from the instance that created the device
+        let surface = gpu.instance.create_surface(window.clone())?;
+        let caps = surface.get_capabilities(&gpu.adapter);
+        let Some(&fallback_format) = caps.formats.first() else {
+            anyhow::bail!("the GPU adapter cannot present to the preview window");
+        };
+        let size = window.inner_size();
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: caps.formats.iter().copied().find(|f| f.is_srgb()).unwrap_or(fallback_format),
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: caps.present_modes[0],
+            alpha_mode: caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&gpu.device, &config);
+
+        let renderer = Renderer::new(&gpu.device, &config, &sim.layouts)?;
+        Ok(Self {
+            event_loop,
+            window,
+            surface,
+            config,
+            renderer,
+            uniforms: FrameUniforms::new(&gpu.device),
+            camera: Camera::fit(sim.config.world.size),
+            last_frame: None,
+        })
+    }
+
+    /// Handle window events and draw `sim` if a frame is due
+    ///
+    /// Returns false once the window has been closed.
+    pub fn frame(&mut self, gpu: &GpuDevice, sim: &Simulation) -> Result<bool, anyhow::Error> {
+        let mut open = true;
+        let mut resized = None;
+        let window_id = self.window.id();
+        let status = self.event_loop.pump_events(Some(Duration::ZERO), |event, _| match event {
+            Event::WindowEvent { window_id: id, event: WindowEvent::CloseRequested } if id == window_id => open = false,
+            Event::WindowEvent { window_id: id, event: WindowEvent::Resized(size) } if id == window_id => resized = Some(size),
+            _ => {}
+        });
+        if !open || matches!(status, PumpStatus::Exit(_)) {
+            return Ok(false);
+        }
+        if let Some(size) = resized.filter(|s| s.width > 0 && s.height > 0) {
+            self.config.width = size.width;
+            self.config.height = size.height;
+            self.surface.configure(&gpu.device, &self.config);
+        }
+
+        if self.last_frame.is_some_and(|t| t.elapsed() < FRAME_INTERVAL) {
+            return Ok(true);
+        }
+        self.last_frame = Some(Instant::now());
+
+        let output = match self.surface.get_current_texture() {
+            Ok(output) => output,
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                self.surface.configure(&gpu.device, &self.config);
+                return Ok(true);
+            }
+            Err(wgpu::SurfaceError::Timeout) => return Ok(true),
+            Err(e) => return Err(e.into()),
+        };
+        let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let surface_size = [self.config.width, self.config.height];
+        self.uniforms.write_camera(&gpu.queue, &self.camera.uniform(sim.config.world.size, surface_size));
+        self.uniforms.write_agent_colors(&gpu.queue, &AgentColorParams::new(&sim.config.render));
+        let agent_view = AgentViewParams::new(&self.camera, sim.config.world.size, surface_size, sim.agent_count(), &sim.config.render);
+        self.uniforms.write_agent_view(&gpu.queue, &agent_view);
+
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("preview_encoder"),
+        });
+        let frame = FrameInputs {
+            camera: self.uniforms.camera(),
+            particles: sim.agents_buffer().map(|buffer| (buffer, &agent_view)),
+            agent_colors: self.uniforms.agent_colors(),
+            agent_view: self.uniforms.agent_view(),
+            field_texture: sim.field.front_sample_view(),
+            field_sampler: sim.field_sampler(),
+            contours: None,
+            minimap: None,
+            charts: &[],
+        };
+        self.renderer.render(&gpu.device, &mut encoder, &view, &sim.layouts, &frame)?;
+        gpu.submit(encoder.finish());
+        output.present();
+
+        Ok(true)
+    }
+}
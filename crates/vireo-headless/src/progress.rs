@@ -0,0 +1,73 @@
+use std::fmt::Write;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
+
+/// Progress bar of a single run: steps done, steps per second, ETA and the
+/// alive count of the last metrics sample
+///
+/// Only drawn when stderr is a terminal and `--quiet` is not given. Without a
+/// bar the run prints a `Step N` line per metrics sample instead, so logs of
+/// redirected runs keep their record.
+pub struct Progress {
+    bar: Option<ProgressBar>,
+}
+
+impl Progress {
+    pub fn new(steps: u32, quiet: bool) -> Self {
+        let target = ProgressDrawTarget::stderr();
+        if quiet || target.is_hidden() {
+            return Self { bar: None };
+        }
+        let bar = ProgressBar::with_draw_target(Some(steps as u64 + 1), target);
+        bar.set_style(
+            ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} steps  {rate} steps/s  ETA {eta}  {msg}")
+                .expect("valid progress template")
+                .with_key("rate", |state: &ProgressState, w: &mut dyn Write| {
+                    let _ = write!(w, "{:.1}", state.per_sec());
+                })
+                .progress_chars("=> "),
+        );
+        Self { bar: Some(bar) }
+    }
+
+    /// Whether the bar is drawn (otherwise callers print their own step lines)
+    pub fn is_drawn(&self) -> bool {
+        self.bar.is_some()
+    }
+
+    /// Mark `step` done
+    pub fn set_step(&self, step: u32) {
+        if let Some(bar) = &self.bar {
+            bar.set_position(step as u64 + 1);
+        }
+    }
+
+    /// Show the alive count of the latest metrics sample
+    pub fn set_alive(&self, alive: u32) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(format!("{} alive", alive));
+        }
+    }
+
+    /// Print a line to stdout above the bar
+    pub fn println(&self, line: impl AsRef<str>) {
+        match &self.bar {
+            Some(bar) => bar.suspend(|| println!("{}", line.as_ref())),
+            None => println!("{}", line.as_ref()),
+        }
+    }
+
+    /// Print a line to stderr above the bar
+    pub fn eprintln(&self, line: impl AsRef<str>) {
+        match &self.bar {
+            Some(bar) => bar.suspend(|| eprintln!("{}", line.as_ref())),
+            None => eprintln!("{}", line.as_ref()),
+        }
+    }
+
+    /// Remove the bar, e.g. before the closing summary
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::path::Path;
+use std::time::Instant;
+use clap::Args;
+use rand::Rng;
+use serde::Serialize;
+use vireo_core::SimulationConfig;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::SimRng;
+
+/// Population viability analysis options
+#[derive(Args)]
+pub struct PvaArgs {
+    /// Number of independent replicates (seeds world.seed .. world.seed + M)
+    #[arg(short = 'm', long, default_value_t = 20)]
+    pub replicates: u32,
+
+    /// Read back the population every N steps
+    #[arg(long, default_value_t = 50)]
+    pub sample_every: u32,
+
+    /// Number of bootstrap resamples for confidence intervals
+    #[arg(long, default_value_t = 1000)]
+    pub bootstrap: u32,
+
+    /// Fraction of the run discarded before measuring quasi-stationary size
+    #[arg(long, default_value_t = 0.5)]
+    pub burn_in: f64,
+
+    /// Confidence level of the bootstrap intervals
+    #[arg(long, default_value_t = 0.95)]
+    pub confidence: f64,
+}
+
+/// Outcome of a single replicate
+#[derive(Serialize)]
+struct Replicate {
+    seed: u64,
+    extinct: bool,
+    /// Step at which the last agent died, if it did
+    extinction_step: Option<u32>,
+    final_alive: u32,
+    /// Mean alive count over post-burn-in samples, survivors only
+    quasi_stationary_size: Option<f64>,
+}
+
+/// Point estimate with a bootstrap percentile interval
+#[derive(Serialize)]
+struct Estimate {
+    value: Option<f64>,
+    ci: Option<[f64; 2]>,
+}
+
+#[derive(Serialize)]
+struct PvaSummary {
+    replicates: u32,
+    steps: u32,
+    sample_every: u32,
+    burn_in: f64,
+    bootstrap: u32,
+    confidence: f64,
+    extinction_probability: Estimate,
+    median_time_to_extinction: Estimate,
+    quasi_stationary_size: Estimate,
+    runs: Vec<Replicate>,
+}
+
+/// Run the replicates and write `pva.json` to `out`
+pub fn run(gpu: &GpuDevice, config: &SimulationConfig, args: &PvaArgs, out: &Path) -> Result<(), anyhow::Error> {
+    if args.replicates == 0 {
+        anyhow::bail!("PVA needs at least one replicate.");
+    }
+    if args.sample_every == 0 {
+        anyhow::bail!("--sample-every must be greater than 0.");
+    }
+    if !(0.0..1.0).contains(&args.burn_in) {
+        anyhow::bail!("--burn-in must be in [0, 1).");
+    }
+    if !(0.0 < args.confidence && args.confidence < 1.0) {
+        anyhow::bail!("--confidence must be in (0, 1).");
+    }
+
+    println!("PVA: {} replicates × {} steps", args.replicates, config.world.steps);
+    let start_time = Instant::now();
+
+    let mut runs = Vec::with_capacity(args.replicates as usize);
+    for r in 0..args.replicates {
+        let mut replicate_config = config.clone();
+        replicate_config.world.seed = config.world.seed.wrapping_add(r as u64);
+
+        let replicate = run_replicate(gpu, replicate_config, args);
+        println!("Replicate {}/{} (seed={}): {}",
+            r + 1,
+            args.replicates,
+            replicate.seed,
+            match replicate.extinction_step {
+                Some(step) => format!("extinct at step {}", step),
+                None => format!("{} alive", replicate.final_alive),
+            }
+        );
+        runs.push(replicate);
+    }
+
+    // Bootstrap resampling is seeded from the base config so reruns match
+    let mut rng = SimRng::new(config.world.rng, config.world.seed);
+
+    let extinction_probability = estimate(&runs, args, &mut rng, |sample| {
+        Some(sample.iter().filter(|r| r.extinct).count() as f64 / sample.len() as f64)
+    });
+    let median_time_to_extinction = estimate(&runs, args, &mut rng, |sample| {
+        median(sample.iter().filter_map(|r| r.extinction_step.map(|s| s as f64)).collect())
+    });
+    let quasi_stationary_size = estimate(&runs, args, &mut rng, |sample| {
+        mean(sample.iter().filter_map(|r| r.quasi_stationary_size).collect())
+    });
+
+    let summary = PvaSummary {
+        replicates: args.replicates,
+        steps: config.world.steps,
+        sample_every: args.sample_every,
+        burn_in: args.burn_in,
+        bootstrap: args.bootstrap,
+        confidence: args.confidence,
+        extinction_probability,
+        median_time_to_extinction,
+        quasi_stationary_size,
+        runs,
+    };
+
+    let path = out.join("pva.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&summary)?)?;
+
+    println!("P(extinction) = {:?}, median TTE = {:?}, QSD size = {:?}",
+        summary.extinction_probability.value,
+        summary.median_time_to_extinction.value,
+        summary.quasi_stationary_size.value
+    );
+    println!("PVA completed in {:?}", start_time.elapsed());
+    println!("Summary written to {}", path.display());
+
+    Ok(())
+}
+
+/// Step one replicate to extinction or `world.steps`, sampling the population
+fn run_replicate(gpu: &GpuDevice, config: SimulationConfig, args: &PvaArgs) -> Replicate {
+    let seed = config.world.seed;
+    let steps = config.world.steps;
+    let burn_in_step = (steps as f64 * args.burn_in) as u32;
+
+    let mut sim = Simulation::new(gpu, config);
+    let mut post_burn_in = Vec::new();
+    let mut alive = sim.agent_manager.get_alive_count();
+    let mut extinction_step = None;
+
+    for step in 1..=steps {
+        sim.step(gpu);
+
+        if step % args.sample_every != 0 && step != steps {
+            continue;
+        }
+
+        sim.sync_agents(gpu);
+        alive = sim.agent_manager.get_alive_count();
+
+        if alive == 0 {
+            extinction_step = Some(step);
+            break;
+        }
+        if step >= burn_in_step {
+            post_burn_in.push(alive as f64);
+        }
+    }
+
+    Replicate {
+        seed,
+        extinct: extinction_step.is_some(),
+        extinction_step,
+        final_alive: alive,
+        quasi_stationary_size: if extinction_step.is_none() { mean(post_burn_in) } else { None },
+    }
+}
+
+/// Point estimate of `stat` plus its bootstrap percentile interval
+fn estimate<F>(runs: &[Replicate], args: &PvaArgs, rng: &mut SimRng, stat: F) -> Estimate
+where
+    F: Fn(&[&Replicate]) -> Option<f64>,
+{
+    let all: Vec<&Replicate> = runs.iter().collect();
+    let value = stat(&all);
+
+    let mut resampled = Vec::with_capacity(args.bootstrap as usize);
+    let mut sample = Vec::with_capacity(runs.len());
+    for _ in 0..args.bootstrap {
+        sample.clear();
+        sample.extend((0..runs.len()).map(|_| &runs[rng.gen_range(0..runs.len())]));
+        // Resamples where the statistic is undefined (e.g. no extinctions) are dropped
+        if let Some(v) = stat(&sample) {
+            resampled.push(v);
+        }
+    }
+
+    let ci = if value.is_some() && !resampled.is_empty() {
+        resampled.sort_by(f64::total_cmp);
+        let alpha = (1.0 - args.confidence) / 2.0;
+        Some([percentile(&resampled, alpha), percentile(&resampled, 1.0 - alpha)])
+    } else {
+        None
+    };
+
+    Estimate { value, ci }
+}
+
+fn mean(values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    Some(percentile(&values, 0.5))
+}
+
+/// Linear-interpolated percentile of sorted, non-empty `values`
+fn percentile(values: &[f64], q: f64) -> f64 {
+    let pos = q * (values.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    values[lo] + (values[hi] - values[lo]) * (pos - lo as f64)
+}
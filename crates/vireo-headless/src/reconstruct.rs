@@ -0,0 +1,49 @@
+use std::path::{Path, PathBuf};
+use clap::Args;
+use vireo_core::SimulationConfig;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::CheckpointChain;
+
+/// Reconstruct options
+#[derive(Args)]
+pub struct ReconstructArgs {
+    /// Checkpoint chain directory, the checkpoints/ of a run with output.checkpoints.every
+    #[arg(long, default_value = "results/checkpoints")]
+    pub chain: PathBuf,
+
+    /// Step to rebuild (completed steps, as in the chain's file names)
+    #[arg(long)]
+    pub step: u32,
+}
+
+/// Write the state after `args.step` steps to `out/state_NNNNNN.bin`
+///
+/// The last checkpoint at or before the step is rebuilt from its keyframe and
+/// deltas, each checked against its recorded checksum. Steps past it are
+/// simulated again from there with `config`, so they match the original run
+/// only as far as a restored state does (interactions replayed or scripted
+/// into that run are not repeated).
+pub fn run(gpu: &GpuDevice, config: &SimulationConfig, args: &ReconstructArgs, out: &Path) -> Result<(), anyhow::Error> {
+    let chain = CheckpointChain::open(&args.chain)
+        .map_err(|e| anyhow::anyhow!("cannot open checkpoint chain {}: {}", args.chain.display(), e))?;
+    let mut state = chain.rebuild(args.step)
+        .map_err(|e| anyhow::anyhow!("cannot rebuild step {} from {}: {}", args.step, args.chain.display(), e))?
+        .ok_or_else(|| anyhow::anyhow!("{} has no keyframe at or before step {}", args.chain.display(), args.step))?;
+    println!("Rebuilt checkpoint at step {} from {}", state.step, args.chain.display());
+
+    if state.step < args.step {
+        let from = state.step;
+        let mut sim = Simulation::new(gpu, config.clone());
+        sim.restore(gpu, &state).map_err(anyhow::Error::msg)?;
+        while sim.current_step() < args.step {
+            sim.step(gpu);
+        }
+        state = sim.snapshot(gpu);
+        println!("Simulated steps {} to {} again from the checkpoint", from, args.step);
+    }
+
+    let path = out.join(format!("state_{:06}.bin", state.step));
+    state.write(&path)?;
+    println!("State of step {} written to {} (open with --load-snapshot)", state.step, path.display());
+    Ok(())
+}
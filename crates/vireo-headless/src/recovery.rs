@@ -0,0 +1,53 @@
+use vireo_core::{AgentParams, RDParams, SimulationConfig, SpeciesCapacity};
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{EventMaps, InteractionScript, StateSnapshot};
+
+/// State to rebuild the run from after a device loss, taken every
+/// `gpu.checkpoint_every` steps
+pub struct Checkpoint {
+    pub state: StateSnapshot,
+    totals: Option<Vec<u32>>,
+    events: Option<EventMaps>,
+    capacity: SpeciesCapacity, // Slots the state was taken with (grows under agents.growth)
+}
+
+impl Checkpoint {
+    pub fn take(sim: &mut Simulation, gpu: &GpuDevice) -> Self {
+        Self {
+            state: sim.snapshot(gpu),
+            totals: sim.read_occupancy_total(gpu),
+            events: sim.read_events(gpu),
+            capacity: sim.config.agents.capacity,
+        }
+    }
+
+    /// Recreate the lost device in `gpu` and rebuild the run of `config` from this checkpoint
+    ///
+    /// The simulation gets the run's field and agent `params` again (they
+    /// differ from the config's under `--debug-scenario`), and the
+    /// interactions of `scripts` the state does not hold are made again.
+    pub fn recover<'a>(
+        &self,
+        gpu: &mut GpuDevice,
+        config: &SimulationConfig,
+        params: (RDParams, AgentParams),
+        scripts: impl Iterator<Item = &'a InteractionScript>,
+    ) -> Result<Simulation, anyhow::Error> {
+        *gpu = pollster::block_on(GpuDevice::try_new()).map_err(anyhow::Error::msg)?;
+        let mut resumed = config.clone();
+        resumed.agents.capacity = self.capacity;
+        let mut sim = Simulation::new(gpu, resumed);
+        (sim.rd_params, sim.agent_params) = params;
+        sim.restore(gpu, &self.state).map_err(anyhow::Error::msg)?;
+        if let Some(totals) = &self.totals {
+            sim.restore_occupancy_total(gpu, totals);
+        }
+        if let Some(maps) = &self.events {
+            sim.restore_events(gpu, maps);
+        }
+        for action in scripts.flat_map(|s| s.lost_on_restore(self.state.step)) {
+            sim.apply_interaction(gpu, action).map_err(anyhow::Error::msg)?;
+        }
+        Ok(sim)
+    }
+}
@@ -0,0 +1,154 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use clap::Args;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, Rgb, RgbImage, RgbaImage};
+use vireo_app::palette::AgentColorParams;
+use vireo_core::sim::AgentPopulation;
+use vireo_core::RenderConfig;
+
+/// Video rendering options
+#[derive(Args)]
+pub struct RenderVideoArgs {
+    /// Result directory holding R_NNNN.png and agents_NNNN.csv snapshots
+    pub dir: PathBuf,
+
+    /// Frames per second
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..=100))]
+    pub fps: u32,
+
+    /// Pixels per field cell
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..=16))]
+    pub scale: u32,
+}
+
+/// Field snapshots of a result directory in step order, with their agent exports if present
+fn snapshot_frames(dir: &Path) -> Result<Vec<(u32, PathBuf, Option<PathBuf>)>, anyhow::Error> {
+    let mut frames = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| anyhow::anyhow!("{}: {}", dir.display(), e))? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        let step = name.strip_prefix("R_").and_then(|s| s.strip_suffix(".png")).and_then(|s| s.parse::<u32>().ok());
+        if let Some(step) = step {
+            let agents = dir.join(format!("agents_{:04}.csv", step));
+            frames.push((step, dir.join(&name), agents.exists().then_some(agents)));
+        }
+    }
+    frames.sort_by_key(|(step, _, _)| *step);
+    Ok(frames)
+}
+
+/// One frame: the field snapshot upscaled by `scale`, agents drawn over it
+fn compose(field: &RgbImage, agents: Option<&AgentPopulation>, colors: &AgentColorParams, scale: u32) -> RgbImage {
+    let mut frame = RgbImage::from_fn(field.width() * scale, field.height() * scale, |x, y| {
+        *field.get_pixel(x / scale, y / scale)
+    });
+    let radius = (scale / 2).max(1) as i64;
+    for agent in agents.map(|p| p.agents.as_slice()).unwrap_or_default() {
+        let [r, g, b, a] = colors.color(agent.kind, agent.energy, agent.is_alive());
+        let cx = (agent.pos[0] * scale as f32) as i64;
+        let cy = (agent.pos[1] * scale as f32) as i64;
+        for y in (cy - radius + 1).max(0)..(cy + radius).min(frame.height() as i64) {
+            for x in (cx - radius + 1).max(0)..(cx + radius).min(frame.width() as i64) {
+                let pixel = frame.get_pixel_mut(x as u32, y as u32);
+                let blend = |under: u8, over: f32| (under as f32 * (1.0 - a) + over.clamp(0.0, 1.0) * 255.0 * a) as u8;
+                *pixel = Rgb([blend(pixel[0], r), blend(pixel[1], g), blend(pixel[2], b)]);
+            }
+        }
+    }
+    frame
+}
+
+/// Frame sink: an in-process GIF encoder or an ffmpeg child for every other container
+enum Encoder {
+    Gif(GifEncoder<File>, Delay),
+    Ffmpeg(std::process::Child),
+}
+
+impl Encoder {
+    fn open(path: &Path, size: (u32, u32), fps: u32) -> Result<Self, anyhow::Error> {
+        if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("gif")) {
+            let mut encoder = GifEncoder::new(File::create(path)?);
+            encoder.set_repeat(Repeat::Infinite)?;
+            return Ok(Encoder::Gif(encoder, Delay::from_numer_denom_ms(1000, fps)));
+        }
+        let child = Command::new("ffmpeg")
+            .args(["-y", "-loglevel", "error", "-f", "rawvideo", "-pix_fmt", "rgb24"])
+            .args(["-s", &format!("{}x{}", size.0, size.1), "-framerate", &fps.to_string(), "-i", "-"])
+            // yuv420p needs even dimensions
+            .args(["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2", "-pix_fmt", "yuv420p"])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("could not start ffmpeg to encode {} ({}); write a .gif instead", path.display(), e))?;
+        Ok(Encoder::Ffmpeg(child))
+    }
+
+    fn push(&mut self, frame: RgbImage) -> Result<(), anyhow::Error> {
+        match self {
+            Encoder::Gif(encoder, delay) => {
+                let rgba = RgbaImage::from_fn(frame.width(), frame.height(), |x, y| {
+                    let [r, g, b] = frame.get_pixel(x, y).0;
+                    image::Rgba([r, g, b, 255])
+                });
+                encoder.encode_frame(Frame::from_parts(rgba, 0, 0, *delay))?;
+            }
+            Encoder::Ffmpeg(child) => {
+                child.stdin.as_mut().expect("ffmpeg stdin is piped").write_all(frame.as_raw())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), anyhow::Error> {
+        if let Encoder::Ffmpeg(mut child) = self {
+            drop(child.stdin.take());
+            let status = child.wait()?;
+            if !status.success() {
+                anyhow::bail!("ffmpeg failed ({})", status);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Render every field snapshot in `args.dir` with its agents into a video at
+/// `out` (`out/video.gif` when `out` has no extension)
+pub fn run(args: &RenderVideoArgs, out: &Path, render: &RenderConfig) -> Result<(), anyhow::Error> {
+    let frames = snapshot_frames(&args.dir)?;
+    if frames.is_empty() {
+        anyhow::bail!("{} holds no R_NNNN.png snapshots", args.dir.display());
+    }
+    let path = if out.extension().is_some() { out.to_path_buf() } else { out.join("video.gif") };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    println!("Rendering {} snapshots from {} at {} fps", frames.len(), args.dir.display(), args.fps);
+
+    let colors = AgentColorParams::new(render);
+    let mut encoder = None;
+    let mut size = None;
+    for (step, field_path, agents_path) in &frames {
+        let field = image::open(field_path)?.to_rgb8();
+        if *size.get_or_insert(field.dimensions()) != field.dimensions() {
+            anyhow::bail!("{} is {}x{}, earlier snapshots are {}x{}", field_path.display(),
+                field.width(), field.height(), size.unwrap().0, size.unwrap().1);
+        }
+        let agents = agents_path.as_deref()
+            .map(|p| AgentPopulation::read_csv(p).map_err(|e| anyhow::anyhow!("{}: {}", p.display(), e)))
+            .transpose()?;
+        if agents.is_none() {
+            println!("Warning: no agents_{:04}.csv, step {} is drawn without agents", step, step);
+        }
+        let frame = compose(&field, agents.as_ref(), &colors, args.scale);
+        if encoder.is_none() {
+            encoder = Some(Encoder::open(&path, frame.dimensions(), args.fps)?);
+        }
+        encoder.as_mut().expect("opened above").push(frame)?;
+    }
+    encoder.expect("at least one frame").finish()?;
+
+    println!("Video written to {}", path.display());
+    Ok(())
+}
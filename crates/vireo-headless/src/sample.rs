@@ -0,0 +1,117 @@
+use std::path::Path;
+use std::time::Instant;
+use vireo_core::{GrowthPolicy, SimulationConfig, TimeUniform, SPECIES};
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{AgeStructure, Diversity, EventSummary, FieldStats, Genetics, GeneticsWriter, MetricsSample, SpeciesStats, SpeciesTracker, TraitStats};
+use crate::metrics::{self, AgeWriter, CohortWriter, MetricsWriter, SpeciesWriter};
+use crate::progress::Progress;
+
+/// Outputs written every `output.metrics_every` steps, and what they keep
+/// between samples
+pub struct Sampler {
+    metrics: MetricsWriter,
+    species: Option<SpeciesWriter>,
+    cohorts: Option<CohortWriter>,
+    ages: Option<AgeWriter>,
+    genetics: Option<GeneticsWriter>,
+    pub tracker: SpeciesTracker,
+    pub events: Option<EventSummary>, // Last completed `output.event_window`
+    full_reported: [bool; 3], // Species reported as filling their range under `reject`
+}
+
+/// Statistics of a sample the run loop acts on
+pub struct Sample {
+    pub field: FieldStats,
+    pub species: Vec<SpeciesStats>,
+}
+
+impl Sampler {
+    /// Create the output files of `config` in `out`, tracking the species of `sim`
+    pub fn new(config: &SimulationConfig, out: &Path, sim: &Simulation) -> Result<Self, anyhow::Error> {
+        let registry = metrics::standard_registry(config).map_err(anyhow::Error::msg)?;
+        Ok(Self {
+            metrics: MetricsWriter::new(out, registry)?,
+            species: config.output.species_long.then(|| SpeciesWriter::new(out)).transpose()?,
+            cohorts: (!config.cohorts.is_empty()).then(|| CohortWriter::new(out)).transpose()?,
+            ages: config.output.age_structure.then(|| AgeWriter::new(out)).transpose()?,
+            genetics: config.evolution.enabled.then(|| GeneticsWriter::new(out)).transpose()?,
+            tracker: SpeciesTracker::new(&sim.agent_manager.agents),
+            events: None,
+            full_reported: [false; 3],
+        })
+    }
+
+    /// Download the state after `step` and write every per-sample output
+    ///
+    /// A sampled field (`output.sampling`) reads a few thousand cells instead
+    /// of the whole grid. Without a drawn progress bar this prints the
+    /// `Step N` line.
+    pub fn write(&mut self, sim: &mut Simulation, gpu: &GpuDevice, config: &SimulationConfig, step: u32, step_start: Instant, progress: &Progress) -> Result<Sample, anyhow::Error> {
+        let sampled = config.output.sampling.enabled.then(|| sim.sample_field(gpu));
+        if sampled.is_none() {
+            sim.sync_field(gpu);
+            sim.field_manager.update_stats();
+        }
+        sim.sync_agents(gpu);
+        sim.agent_manager.update_stats();
+        let field_stats = sampled.as_ref().map_or(&sim.field_manager.stats, |s| &s.stats);
+
+        let step_time = step_start.elapsed();
+        let movement_stats = sim.movement_stats(gpu);
+        let species_stats = self.tracker.sample(&sim.agent_manager.agents);
+        let diversity = Diversity::of(&sim.agent_manager.agents, config.world.size, config.output.quadrats);
+        let traits = config.evolution.enabled.then(|| TraitStats::of(&sim.agent_manager.agents));
+        if config.demography.enabled && config.agents.growth.policy == GrowthPolicy::Reject {
+            for s in &species_stats {
+                let kind = s.kind as usize;
+                let slots = sim.agent_manager.layout.capacity(s.kind);
+                if slots > 0 && s.alive == slots && !self.full_reported[kind] {
+                    self.full_reported[kind] = true;
+                    progress.println(format!("Step {}: {} fill all {} slots; further births are rejected (agents.growth.policy: reject)",
+                        step, SPECIES[kind], slots));
+                }
+            }
+        }
+        self.metrics.write_step(&MetricsSample {
+            step,
+            time: &TimeUniform::new(&config.world, step),
+            field: field_stats,
+            agents: &sim.agent_manager.stats,
+            movement: &movement_stats,
+            species: &species_stats,
+            step_time,
+            events: self.events.as_ref(),
+            sampling: sampled.as_ref(),
+            diversity: Some(&diversity),
+            traits: traits.as_deref(),
+        })?;
+        if let Some(writer) = self.species.as_mut() {
+            writer.write_step(step, &species_stats)?;
+        }
+        if let Some(writer) = self.cohorts.as_mut() {
+            writer.write_step(step, &sim.cohorts.stats(&sim.agent_manager.agents))?;
+        }
+        if let Some(writer) = self.ages.as_mut() {
+            let o = &config.output;
+            writer.write_step(step, &AgeStructure::of(&sim.agent_manager.agents, o.age_bin, o.age_classes))?;
+        }
+        if let Some((writer, traits)) = self.genetics.as_mut().zip(traits.as_deref()) {
+            writer.write_step(step, traits, &Genetics::of(&sim.agent_manager.agents, config.output.metrics_every))?;
+        }
+
+        // The bar replaces the per-sample line when it is drawn
+        progress.set_alive(sim.agent_manager.stats.alive_count);
+        if !progress.is_drawn() {
+            println!("Step {}: R={:.3}, W={:.3}, Agents={}, Align={:.3}, Div={:.4}, Time={:?}",
+                step,
+                field_stats.mean_R,
+                field_stats.mean_W,
+                sim.agent_manager.stats.alive_count,
+                movement_stats.mean_alignment,
+                movement_stats.mean_divergence,
+                step_time
+            );
+        }
+        Ok(Sample { field: field_stats.clone(), species: species_stats })
+    }
+}
@@ -2,8 +2,8 @@ use std::path::PathBuf;
 use std::fs::File;
 use std::io::Write;
 use csv::Writer;
-use image::{ImageBuffer, Rgb, RgbImage, Luma};
-use vireo_core::sim::{FieldManager, AgentManager};
+use image::{ImageBuffer, Rgb, RgbImage, RgbaImage, Luma};
+use vireo_core::sim::{AgentManager, EventMaps};
 use anyhow::Result;
 
 /// Snapshot writer for field images and agent data
@@ -20,50 +20,12 @@ impl SnapshotWriter {
     }
     
     /// Write a field snapshot as PNG image
-    pub fn write_field_snapshot(
-        &self,
-        step: u32,
-        field_manager: &FieldManager,
-    ) -> Result<(), anyhow::Error> {
-        let filename = format!("R_{:04}.png", step);
-        let filepath = self.output_dir.join(&filename);
-        
-        // Create RGB image from field data
-        let mut img: RgbImage = ImageBuffer::new(field_manager.size[0], field_manager.size[1]);
-        
-        // Find value range for normalization
-        let mut min_val = f32::INFINITY;
-        let mut max_val = f32::NEG_INFINITY;
-        
-        for y in 0..field_manager.size[1] {
-            for x in 0..field_manager.size[0] {
-                let data = field_manager.get(x, y);
-                min_val = min_val.min(data.R);
-                max_val = max_val.max(data.R);
-            }
-        }
-        
-        let range = max_val - min_val;
-        let range = if range > 0.0 { range } else { 1.0 };
-        
-        // Convert to RGB (R channel = resource, G channel = waste)
-        for y in 0..field_manager.size[1] {
-            for x in 0..field_manager.size[0] {
-                let data = field_manager.get(x, y);
-                
-                // Normalize resource value to [0, 255]
-                let r_val = ((data.R - min_val) / range * 255.0) as u8;
-                let g_val = ((data.W * 255.0).min(255.0)) as u8; // Waste in green channel
-                let b_val = 0; // Blue channel unused
-                
-                img.put_pixel(x, y, Rgb([r_val, g_val, b_val]));
-            }
-        }
-        
-        // Save PNG
-        img.save(&filepath)?;
-        
-        Ok(())
+    ///
+    /// `rgba` comes from `Simulation::render_image` with `ImageKind::Field`:
+    /// R scaled to its range in red, W in green, soil damage in blue.
+    pub fn write_field_snapshot(&self, step: u32, size: [u32; 2], rgba: Vec<u8>) -> Result<(), anyhow::Error> {
+        let filepath = self.output_dir.join(format!("R_{:04}.png", step));
+        save_rgba_png(rgba, size, &filepath)
     }
     
     /// Write agent positions and states to CSV
@@ -80,7 +42,7 @@ impl SnapshotWriter {
         
         // Write CSV header
         csv_writer.write_record(&[
-            "id", "x", "y", "vx", "vy", "energy", "alive"
+            "id", "x", "y", "vx", "vy", "energy", "alive", "cohort", "age", "kind", "state"
         ])?;
         
         // Write agent data
@@ -93,6 +55,10 @@ impl SnapshotWriter {
                 &agent.vel[1].to_string(),
                 &agent.energy.to_string(),
                 &agent.alive.to_string(),
+                &agent.cohort.to_string(),
+                &agent.age.to_string(),
+                &agent.kind.to_string(),
+                &agent.state.to_string(),
             ])?;
         }
         
@@ -100,6 +66,70 @@ impl SnapshotWriter {
         
         Ok(())
     }
+    
+    /// Write a perceived utility map as CSV, one row per grid row
+    pub fn write_utility_snapshot(
+        &self,
+        step: u32,
+        utility: &[f32],
+        size: [u32; 2],
+    ) -> Result<(), anyhow::Error> {
+        let filename = format!("utility_{:04}.csv", step);
+        let filepath = self.output_dir.join(&filename);
+        
+        let file = File::create(&filepath)?;
+        let mut csv_writer = Writer::from_writer(file);
+        
+        for row in utility.chunks(size[0] as usize) {
+            csv_writer.write_record(row.iter().map(|u| u.to_string()))?;
+        }
+        
+        csv_writer.flush()?;
+        
+        Ok(())
+    }
+
+    /// Write run-long occupancy totals as CSV (one row per grid row) and a heatmap PNG
+    ///
+    /// The heatmap is log-scaled, black through red and yellow to white at the
+    /// busiest cell, so rarely visited cells stay visible next to hotspots.
+    pub fn write_occupancy_total(&self, totals: &[u32], size: [u32; 2]) -> Result<()> {
+        self.write_count_grid("occupancy_total.csv", totals, size)?;
+        save_count_heatmap(totals, size, &self.output_dir.join("occupancy_total.png"))
+    }
+
+    /// Write one event window: `events_{births,starved,random_deaths}_NNNN.csv`
+    /// (one row per grid row) and a heatmap of all deaths, `deaths_NNNN.png`
+    pub fn write_event_maps(&self, step: u32, maps: &EventMaps) -> Result<()> {
+        for (name, counts) in [("births", &maps.births), ("starved", &maps.starved), ("random_deaths", &maps.random_deaths)] {
+            self.write_count_grid(&format!("events_{}_{:04}.csv", name, step), counts, maps.size)?;
+        }
+        save_count_heatmap(&maps.deaths(), maps.size, &self.output_dir.join(format!("deaths_{:04}.png", step)))
+    }
+
+    /// Per-cell counts as CSV, one row per grid row
+    fn write_count_grid(&self, filename: &str, counts: &[u32], size: [u32; 2]) -> Result<()> {
+        let file = File::create(self.output_dir.join(filename))?;
+        let mut csv_writer = Writer::from_writer(file);
+        for row in counts.chunks(size[0] as usize) {
+            csv_writer.write_record(row.iter().map(|n| n.to_string()))?;
+        }
+        csv_writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Save per-cell counts as a log-scaled heatmap, black through red and yellow
+/// to white at the largest count
+pub fn save_count_heatmap(counts: &[u32], size: [u32; 2], path: &std::path::Path) -> Result<()> {
+    let max = (*counts.iter().max().unwrap_or(&0) as f32).ln_1p().max(f32::MIN_POSITIVE);
+    let img: RgbImage = ImageBuffer::from_fn(size[0], size[1], |x, y| {
+        let t = (counts[(y * size[0] + x) as usize] as f32).ln_1p() / max;
+        let channel = |lo: f32| ((t * 3.0 - lo).clamp(0.0, 1.0) * 255.0) as u8;
+        Rgb([channel(0.0), channel(1.0), channel(2.0)])
+    });
+    img.save(path)?;
+    Ok(())
 }
 
 /// Save field data as PNG image
@@ -125,31 +155,10 @@ pub fn save_field_png(
     Ok(())
 }
 
-/// Save occupancy data as PNG image
-pub fn save_occupancy_png(
-    occupancy_data: &[u32],
-    size: [u32; 2],
-    output_path: &PathBuf,
-) -> Result<()> {
-    let mut img = ImageBuffer::new(size[0], size[1]);
-    
-    // Find max occupancy for normalization
-    let max_occupancy = *occupancy_data.iter().max().unwrap_or(&1) as f32;
-    
-    for (x, y, pixel) in img.enumerate_pixels_mut() {
-        let idx = (y * size[0] + x) as usize;
-        if idx < occupancy_data.len() {
-            let value = occupancy_data[idx] as f32;
-            // Normalize to 0-255 range based on max occupancy
-            let normalized = if max_occupancy > 0.0 {
-                ((value / max_occupancy) * 255.0).clamp(0.0, 255.0) as u8
-            } else {
-                0
-            };
-            *pixel = Luma([normalized]);
-        }
-    }
-    
+/// Save an RGBA8 image read back from the GPU (`Simulation::render_image`) as PNG
+pub fn save_rgba_png(rgba: Vec<u8>, size: [u32; 2], output_path: &std::path::Path) -> Result<()> {
+    let img = RgbaImage::from_raw(size[0], size[1], rgba)
+        .ok_or_else(|| anyhow::anyhow!("image of {} × {} cells has the wrong length", size[0], size[1]))?;
     img.save(output_path)?;
     Ok(())
 }
@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::Serialize;
+use vireo_core::gpu::ComputePipelines;
+
+/// Wall-clock time of each phase before a single run's loop gets going
+#[derive(Default, Serialize)]
+pub struct StartupTimes {
+    pub config_ms: f64,
+    pub gpu_ms: f64,
+    /// Seeding, buffers and the per-step pipelines
+    pub simulation_ms: f64,
+    /// The first step, including the lazy pipelines it compiles
+    pub first_step_ms: Option<f64>,
+}
+
+/// One compiled pipeline in `run.json`
+#[derive(Serialize)]
+struct PipelineTime {
+    name: &'static str,
+    compile_ms: f64,
+}
+
+/// Contents of `run.json`
+#[derive(Serialize)]
+struct RunSummary<'a> {
+    steps: u32,
+    startup: &'a StartupTimes,
+    /// Every pipeline compiled during the run, per-step ones first
+    pipelines: Vec<PipelineTime>,
+    pipelines_ms: f64,
+    total_ms: f64,
+}
+
+pub fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+impl StartupTimes {
+    /// One-line summary for the console
+    pub fn describe(&self, pipelines: &ComputePipelines) -> String {
+        let compiled: Duration = pipelines.compile_times().iter().map(|(_, t)| *t).sum();
+        format!("config {:.1} ms, GPU {:.1} ms, simulation {:.1} ms (pipelines {:.1} ms)",
+            self.config_ms, self.gpu_ms, self.simulation_ms, ms(compiled))
+    }
+}
+
+/// Write `run.json` to `out`: the startup breakdown and the compile time of
+/// every pipeline the run used, next to the steps run and the loop's total
+pub fn write_run_json(out: &Path, steps: u32, startup: &StartupTimes, pipelines: &ComputePipelines, total: Duration) -> Result<PathBuf, anyhow::Error> {
+    let pipelines: Vec<PipelineTime> = pipelines.compile_times().into_iter()
+        .map(|(name, t)| PipelineTime { name, compile_ms: ms(t) })
+        .collect();
+    let summary = RunSummary {
+        steps,
+        startup,
+        pipelines_ms: pipelines.iter().map(|p| p.compile_ms).sum(),
+        pipelines,
+        total_ms: ms(total),
+    };
+    let path = out.join("run.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&summary)?)?;
+    Ok(path)
+}
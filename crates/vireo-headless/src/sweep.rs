@@ -0,0 +1,173 @@
+use std::fs::File;
+use std::path::Path;
+use std::time::Instant;
+use clap::Args;
+use csv::Writer;
+use vireo_core::SimulationConfig;
+use vireo_core::gpu::{GpuDevice, Simulation};
+use vireo_core::sim::{apply_sweep_point, sweep_design, ParamRange, RunGuard, SimRng, SweepStrategy};
+use crate::sweep_report::write_sweep_report;
+
+/// Parameter sweep options
+#[derive(Args)]
+pub struct SweepArgs {
+    /// Swept parameter and range, e.g. `chemotaxis.chi_R=2:10` (repeat for more parameters)
+    #[arg(short, long = "param", required = true)]
+    pub params: Vec<ParamRange>,
+
+    /// Design: grid, lhs (Latin hypercube), sobol or random
+    #[arg(short, long, default_value = "grid")]
+    pub strategy: SweepStrategy,
+
+    /// Number of runs (required for lhs, sobol and random; caps a grid)
+    #[arg(short, long)]
+    pub budget: Option<u32>,
+
+    /// Values per parameter for the grid strategy
+    #[arg(long, default_value_t = 3)]
+    pub levels: u32,
+
+    /// Read back the population every N steps (runs stop early on extinction)
+    #[arg(long, default_value_t = 50)]
+    pub sample_every: u32,
+}
+
+/// Run every point of the design and write `sweep.csv` to `out`, then plot
+/// it in `sweep_report.html`
+///
+/// All runs share `world.seed`, so differences between rows come from the
+/// swept parameters rather than from the initial conditions. A run stopped by
+/// `guards` keeps its row, with the reason in the `aborted` column.
+pub fn run(gpu: &GpuDevice, config: &SimulationConfig, args: &SweepArgs, out: &Path) -> Result<(), anyhow::Error> {
+    if args.sample_every == 0 {
+        anyhow::bail!("--sample-every must be greater than 0.");
+    }
+
+    // The design is seeded from the base config so reruns match
+    let mut rng = SimRng::new(config.world.rng, config.world.seed);
+    let design = sweep_design(args.strategy, &args.params, args.budget, args.levels, &mut rng)
+        .map_err(anyhow::Error::msg)?;
+
+    // Reject the whole sweep up front rather than failing halfway through
+    let configs = design
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let mut run_config = config.clone();
+            apply_sweep_point(&mut run_config, &args.params, point);
+            run_config.validate().map_err(|e| anyhow::anyhow!("sweep run {}: {}", i, e))?;
+            if let Some(dt) = run_config.enforce_dt_limit().map_err(|e| anyhow::anyhow!("sweep run {}: {}", i, e))? {
+                eprintln!("Warning: sweep run {}: world.dt = {} exceeds the stability limit {}, clamped to {}",
+                    i, dt, run_config.dt_limit(), run_config.world.dt);
+            }
+            Ok(run_config)
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    println!("Sweep: {:?} design, {} runs × {} steps over {}",
+        args.strategy,
+        configs.len(),
+        config.world.steps,
+        args.params.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", "),
+    );
+    let start_time = Instant::now();
+
+    let path = out.join("sweep.csv");
+    let mut writer = Writer::from_writer(File::create(&path)?);
+    let mut header = vec!["run".to_string()];
+    header.extend(args.params.iter().map(|p| p.param.clone()));
+    header.extend(["extinction_step", "final_alive", "mean_R", "mean_W", "mean_energy", "aborted"].map(String::from));
+    writer.write_record(&header)?;
+
+    let mut runs = Vec::with_capacity(design.len());
+    for (i, (point, run_config)) in design.iter().zip(configs).enumerate() {
+        let outcome = run_point(gpu, run_config, args.sample_every);
+        println!("Run {}/{} {:?}: {}",
+            i + 1,
+            design.len(),
+            point,
+            match (&outcome.aborted, outcome.extinction_step) {
+                (Some(reason), _) => format!("aborted at {}", reason),
+                (None, Some(step)) => format!("extinct at step {}", step),
+                (None, None) => format!("{} alive", outcome.final_alive),
+            }
+        );
+
+        let mut record = vec![i.to_string()];
+        record.extend(point.iter().map(|v| v.to_string()));
+        record.push(outcome.extinction_step.map(|s| s.to_string()).unwrap_or_default());
+        record.extend([
+            outcome.final_alive.to_string(),
+            outcome.mean_r.to_string(),
+            outcome.mean_w.to_string(),
+            outcome.mean_energy.to_string(),
+            outcome.aborted.clone().unwrap_or_default(),
+        ]);
+        writer.write_record(&record)?;
+        writer.flush()?;
+        runs.push((point.clone(), outcome));
+    }
+
+    println!("Sweep completed in {:?}", start_time.elapsed());
+    println!("Results written to {}", path.display());
+    let report = write_sweep_report(out, &args.params, &runs, config.world.steps)?;
+    println!("Report written to {}", report.display());
+
+    Ok(())
+}
+
+/// Final state of one sweep run
+pub struct Outcome {
+    pub extinction_step: Option<u32>,
+    pub final_alive: u32,
+    pub mean_r: f32,
+    pub mean_w: f32,
+    pub mean_energy: f32,
+    pub aborted: Option<String>, // "step N: reason" when a guard stopped the run
+}
+
+/// Step one configuration to extinction, a guard trip or `world.steps`
+fn run_point(gpu: &GpuDevice, config: SimulationConfig, sample_every: u32) -> Outcome {
+    let steps = config.world.steps;
+    let mut guard = RunGuard::new(&config.guards);
+    let mut sim = Simulation::new(gpu, config);
+    let mut extinction_step = None;
+    let mut aborted = None;
+    guard.start(0, Instant::now());
+
+    for step in 1..=steps {
+        sim.step(gpu);
+
+        if step % sample_every != 0 && step != steps {
+            continue;
+        }
+
+        sim.sync_agents(gpu);
+        if guard.needs_field() {
+            sim.sync_field(gpu);
+            sim.field_manager.update_stats();
+        }
+        let field = guard.needs_field().then_some(&sim.field_manager.stats);
+        if let Some(trip) = guard.check(step, Instant::now(), field, &sim.agent_manager.agents) {
+            aborted = Some(format!("step {}: {}", step, trip));
+            break;
+        }
+        if sim.agent_count() > 0 && sim.agent_manager.get_alive_count() == 0 {
+            extinction_step = Some(step);
+            break;
+        }
+    }
+
+    sim.sync_field(gpu);
+    sim.field_manager.update_stats();
+    sim.agent_manager.update_stats();
+
+    Outcome {
+        extinction_step,
+        final_alive: sim.agent_manager.get_alive_count(),
+        mean_r: sim.field_manager.stats.mean_R,
+        mean_w: sim.field_manager.stats.mean_W,
+        mean_energy: sim.agent_manager.stats.mean_energy,
+        aborted,
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use plotters::prelude::*;
+use vireo_core::sim::ParamRange;
+use crate::sweep::Outcome;
+
+/// Size of each scatter plot in pixels
+const PLOT_SIZE: (u32, u32) = (360, 260);
+
+/// Point colours: survived, extinct, aborted
+const STATUS_COLORS: [RGBColor; 3] = [RGBColor(31, 119, 180), RGBColor(255, 127, 14), RGBColor(150, 150, 150)];
+
+/// A plotted metric: its name and its value for a run of `world.steps`
+type Metric = (&'static str, fn(&Outcome, u32) -> f64);
+
+/// Metrics plotted against every swept parameter
+///
+/// A run that survived counts all `world.steps` as its survival.
+const METRICS: [Metric; 5] = [
+    ("survived_steps", |o, steps| o.extinction_step.unwrap_or(steps) as f64),
+    ("final_alive", |o, _| o.final_alive as f64),
+    ("mean_R", |o, _| o.mean_r as f64),
+    ("mean_W", |o, _| o.mean_w as f64),
+    ("mean_energy", |o, _| o.mean_energy as f64),
+];
+
+/// Index into `STATUS_COLORS`
+fn status(outcome: &Outcome) -> usize {
+    match (&outcome.aborted, outcome.extinction_step) {
+        (Some(_), _) => 2,
+        (None, Some(_)) => 1,
+        (None, None) => 0,
+    }
+}
+
+/// Write `sweep_report.html` to `out`: each metric of `sweep.csv` against
+/// each swept parameter, one run per point, as inline SVG
+///
+/// Meant as a first look at a sweep; the CSV next to it holds the numbers.
+pub fn write_sweep_report(out: &Path, params: &[ParamRange], runs: &[(Vec<f32>, Outcome)], steps: u32) -> Result<PathBuf, anyhow::Error> {
+    let count = |s: usize| runs.iter().filter(|(_, o)| status(o) == s).count();
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Sweep report</title>")?;
+    writeln!(html, "<style>body {{ font-family: sans-serif; margin: 2em; }} .row {{ display: flex; flex-wrap: wrap; gap: 8px; }} .dot {{ font-size: 1.3em; }}</style>")?;
+    writeln!(html, "</head><body>\n<h1>Sweep report</h1>")?;
+    writeln!(html, "<p>{} runs of {} steps over {}. Values per run are in <code>sweep.csv</code>.</p>",
+        runs.len(), steps, params.iter().map(|p| format!("<code>{}</code>", p)).collect::<Vec<_>>().join(", "))?;
+    let legend = ["survived", "extinct", "aborted"].iter().zip(STATUS_COLORS).enumerate()
+        .map(|(s, (name, c))| format!("<span class=\"dot\" style=\"color: rgb({}, {}, {})\">&#9679;</span> {} ({})", c.0, c.1, c.2, name, count(s)))
+        .collect::<Vec<_>>().join(" &nbsp; ");
+    writeln!(html, "<p>{}</p>", legend)?;
+
+    for (name, value) in METRICS {
+        writeln!(html, "<h2>{}</h2>\n<div class=\"row\">", name)?;
+        for (i, range) in params.iter().enumerate() {
+            let points: Vec<(f64, f64, usize)> = runs.iter()
+                .map(|(point, outcome)| (point[i] as f64, value(outcome, steps), status(outcome)))
+                .collect();
+            html.push_str(&scatter_svg(&range.param, name, &points)?);
+        }
+        writeln!(html, "</div>")?;
+    }
+    writeln!(html, "</body></html>")?;
+
+    let path = out.join("sweep_report.html");
+    std::fs::write(&path, html)?;
+    Ok(path)
+}
+
+/// Lower and upper bound of `values` with a 5% margin (a unit wide if they are all equal)
+fn axis_range(values: impl Iterator<Item = f64>) -> std::ops::Range<f64> {
+    let (lo, hi) = values.filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if lo > hi {
+        return 0.0..1.0;
+    }
+    let margin = if hi > lo { (hi - lo) * 0.05 } else { 0.5 };
+    lo - margin..hi + margin
+}
+
+/// One scatter plot of (x, y, status) points as an SVG element
+fn scatter_svg(x_label: &str, y_label: &str, points: &[(f64, f64, usize)]) -> Result<String, anyhow::Error> {
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, PLOT_SIZE).into_drawing_area();
+        root.fill(&WHITE)?;
+        let mut chart = ChartBuilder::on(&root)
+            .margin(10)
+            .x_label_area_size(36)
+            .y_label_area_size(52)
+            .build_cartesian_2d(axis_range(points.iter().map(|p| p.0)), axis_range(points.iter().map(|p| p.1)))?;
+        chart.configure_mesh()
+            .x_desc(x_label)
+            .y_desc(y_label)
+            .x_labels(5)
+            .y_labels(5)
+            .draw()?;
+        chart.draw_series(points.iter()
+            .filter(|(x, y, _)| x.is_finite() && y.is_finite())
+            .map(|&(x, y, s)| Circle::new((x, y), 3, STATUS_COLORS[s].filled())))?;
+        root.present()?;
+    }
+    Ok(svg)
+}
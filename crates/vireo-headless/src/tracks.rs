@@ -0,0 +1,103 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use csv::Writer;
+use serde_json::json;
+use vireo_core::TrackFormat;
+use vireo_core::sim::Track;
+
+/// Write recorded tracks to `out` in `format`; returns the file written
+///
+/// Coordinates are in cells (x → `location-long`, y → `location-lat`) and
+/// timestamps are synthetic: `step * dt` seconds after 1970-01-01 00:00:00.
+pub fn write_tracks(out: &Path, tracks: &[Track], format: TrackFormat, dt: f32) -> Result<PathBuf, anyhow::Error> {
+    match format {
+        TrackFormat::Csv => write_movebank_csv(&out.join("tracks.csv"), tracks, dt),
+        TrackFormat::GeoJson => write_geojson_lines(&out.join("tracks.geojsonl"), tracks),
+    }
+}
+
+/// Movebank-style CSV, one fix per row
+fn write_movebank_csv(path: &Path, tracks: &[Track], dt: f32) -> Result<PathBuf, anyhow::Error> {
+    let mut csv_writer = Writer::from_writer(File::create(path)?);
+    csv_writer.write_record([
+        "event-id", "individual-local-identifier", "timestamp", "location-long", "location-lat",
+        "step", "kind", "cohort", "energy",
+    ])?;
+
+    let mut event_id = 0u64;
+    for track in tracks {
+        for fix in &track.fixes {
+            event_id += 1;
+            csv_writer.write_record([
+                &event_id.to_string(),
+                &track.id.to_string(),
+                &timestamp(fix.step as f64 * dt as f64),
+                &fix.pos[0].to_string(),
+                &fix.pos[1].to_string(),
+                &fix.step.to_string(),
+                &track.kind.to_string(),
+                &track.cohort.to_string(),
+                &fix.energy.to_string(),
+            ])?;
+        }
+    }
+    csv_writer.flush()?;
+    Ok(path.to_path_buf())
+}
+
+/// Newline-delimited GeoJSON, one feature per agent with at least one fix
+fn write_geojson_lines(path: &Path, tracks: &[Track]) -> Result<PathBuf, anyhow::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for track in tracks.iter().filter(|t| !t.fixes.is_empty()) {
+        let coordinates: Vec<[f32; 2]> = track.fixes.iter().map(|f| f.pos).collect();
+        // A LineString needs two positions; a single fix becomes a Point
+        let geometry = if coordinates.len() == 1 {
+            json!({ "type": "Point", "coordinates": coordinates[0] })
+        } else {
+            json!({ "type": "LineString", "coordinates": coordinates })
+        };
+        let feature = json!({
+            "type": "Feature",
+            "geometry": geometry,
+            "properties": {
+                "id": track.id,
+                "kind": track.kind,
+                "cohort": track.cohort,
+                "steps": track.fixes.iter().map(|f| f.step).collect::<Vec<_>>(),
+            },
+        });
+        writeln!(writer, "{}", feature)?;
+    }
+    writer.flush()?;
+    Ok(path.to_path_buf())
+}
+
+/// Movebank timestamp (`yyyy-MM-dd HH:mm:ss.SSS`) for `secs` after the Unix epoch
+fn timestamp(secs: f64) -> String {
+    let millis = (secs * 1000.0).round() as i64;
+    let days = millis.div_euclid(86_400_000);
+    let ms_of_day = millis.rem_euclid(86_400_000);
+    let (y, m, d) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+        y, m, d,
+        ms_of_day / 3_600_000,
+        ms_of_day / 60_000 % 60,
+        ms_of_day / 1000 % 60,
+        ms_of_day % 1000,
+    )
+}
+
+/// Proleptic Gregorian date for a day count since 1970-01-01 (H. Hinnant's algorithm)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
@@ -5,67 +5,1390 @@
 
 use bytemuck::{Pod, Zeroable};
 
+/// Pseudo-random number generator used for all CPU-side randomness
+///
+/// Every algorithm is seeded from `WorldConfig::seed`, so a (rng, seed) pair
+/// fully determines the initial field and agent placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RngKind {
+    /// ChaCha with 8 rounds (default, matches pre-0.2 seeding)
+    #[default]
+    ChaCha8,
+    /// PCG XSL RR 128/64
+    Pcg64,
+    /// Xoshiro256++
+    Xoshiro256,
+}
+
+/// Registry of scalar field channels, in storage order
+///
+/// Channels are packed four to an RGBA16F texel: channel `i` lives in array
+/// layer `i / 4`, component `i % 4`. Adding a fifth channel grows the field
+/// textures by another array layer; no bind group changes are needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FieldChannel {
+    /// Resource concentration (R)
+    Resource = 0,
+    /// Waste concentration (W)
+    Waste = 1,
+    /// Soil damage left by overgrazing: 0 = pristine, 1 = barren (see `SoilConfig`)
+    Soil = 2,
+}
+
+impl FieldChannel {
+    /// Every channel, indexed by `FieldChannel::index`
+    pub const ALL: [FieldChannel; 3] = [FieldChannel::Resource, FieldChannel::Waste, FieldChannel::Soil];
+
+    /// Number of registered channels
+    pub const COUNT: u32 = Self::ALL.len() as u32;
+
+    /// Channels per texel (RGBA)
+    pub const PER_LAYER: u32 = 4;
+
+    /// Texture array layers needed to hold every channel
+    pub const LAYERS: u32 = Self::COUNT.div_ceil(Self::PER_LAYER);
+
+    /// Position in the registry
+    pub fn index(self) -> u32 {
+        self as u32
+    }
+
+    /// Texture array layer holding this channel
+    pub fn layer(self) -> u32 {
+        self.index() / Self::PER_LAYER
+    }
+
+    /// RGBA component within the layer (0 = r … 3 = a)
+    pub fn component(self) -> usize {
+        (self.index() % Self::PER_LAYER) as usize
+    }
+
+    /// Short name used in metrics and logs
+    pub fn name(self) -> &'static str {
+        match self {
+            FieldChannel::Resource => "R",
+            FieldChannel::Waste => "W",
+            FieldChannel::Soil => "soil",
+        }
+    }
+}
+
 /// World configuration parameters
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct WorldConfig {
     pub size: [u32; 2],
     pub steps: u32,
     pub dt: f32,
     pub seed: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rng: RngKind,  // CPU PRNG algorithm
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub day_period: f32,  // Length of a day/night cycle in time units (0 = constant daylight)
+    #[cfg_attr(feature = "serde", serde(default = "default_night_light"))]
+    pub night_light: f32, // Light level at midnight, as a fraction of noon
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dt_policy: DtPolicy, // What a run does with a `dt` above `SimulationConfig::dt_limit`
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            size: [128, 128],
+            steps: 2000,
+            dt: 0.1,
+            seed: 1337,
+            rng: RngKind::ChaCha8,
+            day_period: 0.0,
+            night_light: default_night_light(),
+            dt_policy: DtPolicy::Clamp,
+        }
+    }
+}
+
+fn default_night_light() -> f32 {
+    0.2
+}
+
+impl WorldConfig {
+    /// Position in the day/night cycle at `step`: 0 = midnight, 0.5 = noon
+    ///
+    /// Without a cycle the world stays at noon.
+    pub fn day_phase(&self, step: u32) -> f32 {
+        if self.day_period <= 0.0 {
+            return 0.5;
+        }
+        let time = step as f64 * self.dt as f64;
+        (time / self.day_period as f64).fract() as f32
+    }
+
+    /// Light level at `step`, a cosine from `night_light` at midnight to 1 at noon
+    ///
+    /// Scales plant resource growth and agent top speed; exactly 1 without a cycle.
+    pub fn light(&self, step: u32) -> f32 {
+        if self.day_period <= 0.0 {
+            return 1.0;
+        }
+        let daylight = 0.5 * (1.0 - (std::f32::consts::TAU * self.day_phase(step)).cos());
+        self.night_light + (1.0 - self.night_light) * daylight
+    }
+}
+
+/// What a run does when `world.dt` exceeds the stability limit of its parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum DtPolicy {
+    /// Lower `dt` to `DT_SAFETY` times the limit and warn (default)
+    #[default]
+    Clamp,
+    /// Refuse to start
+    Refuse,
+    /// Run with the configured `dt` regardless
+    Ignore,
+}
+
+/// Field reaction-diffusion parameters
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FieldConfig {
+    pub D_R: f32,      // Resource diffusion coefficient (cells² per time unit)
+    pub D_W: f32,      // Waste diffusion coefficient (cells² per time unit)
+    pub sigma_R: f32,  // Resource logistic growth rate (per time unit)
+    #[cfg_attr(feature = "serde", serde(default = "default_carrying_capacity"))]
+    pub K_R: f32,      // Resource carrying capacity per cell
+    pub alpha_H: f32,  // Herbivore resource uptake rate (per time unit)
+    pub beta_H: f32,   // Herbivore waste emission rate (per time unit)
+    pub lambda_R: f32, // Resource decay rate (per time unit)
+    pub lambda_W: f32, // Waste decay rate (per time unit)
+}
+
+impl Default for FieldConfig {
+    fn default() -> Self {
+        Self {
+            D_R: 0.5,
+            D_W: 0.2,
+            sigma_R: 0.05,
+            K_R: 1.0,
+            alpha_H: 0.1,
+            beta_H: 0.05,
+            lambda_R: 0.005,
+            lambda_W: 0.005,
+        }
+    }
+}
+
+impl FieldConfig {
+    /// The rates with their units, for building `RDParams`
+    pub fn rates(&self) -> FieldRates {
+        FieldRates {
+            D_R: Diffusivity(self.D_R),
+            D_W: Diffusivity(self.D_W),
+            sigma_R: PerTime(self.sigma_R),
+            K_R: PerArea(self.K_R),
+            alpha_H: PerTime(self.alpha_H),
+            beta_H: PerTime(self.beta_H),
+            lambda_R: PerTime(self.lambda_R),
+            lambda_W: PerTime(self.lambda_W),
+        }
+    }
+}
+
+/// Carrying capacity used when a config omits `field.K_R`
+#[cfg(feature = "serde")]
+fn default_carrying_capacity() -> f32 {
+    1.0
+}
+
+/// How agents sample the field and its gradient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[repr(u32)]
+pub enum FieldSampling {
+    /// Value of the containing cell, central differences one cell apart (default)
+    #[default]
+    Nearest = 0,
+    /// Catmull-Rom bicubic interpolation with its analytic gradient (C1 across cells)
+    Bicubic = 1,
+}
+
+/// Finite-difference stencil for the gradient under `FieldSampling::Nearest`
+///
+/// Cross-shaped stencils weigh the axes differently from the diagonals, so on
+/// coarse grids agents drift toward the grid axes; the 3×3 Sobel and Scharr
+/// stencils smooth across the other axis and are closer to rotation invariant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[repr(u32)]
+pub enum GradientStencil {
+    /// (f(x+1) − f(x−1)) / 2 per axis (default)
+    #[default]
+    Central = 0,
+    /// f(x+1) − f(x) per axis (2 points, half a cell off the agent)
+    Forward = 1,
+    /// Central differences smoothed 1:2:1 across the other axis, over 3×3 cells
+    Sobel = 2,
+    /// Central differences smoothed 3:10:3 across the other axis, over 3×3 cells
+    Scharr = 3,
+}
+
+impl GradientStencil {
+    pub const ALL: [GradientStencil; 4] = [Self::Central, Self::Forward, Self::Sobel, Self::Scharr];
+}
+
+/// Chemotaxis parameters
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChemotaxisConfig {
+    pub chi_R: f32,    // Resource attraction strength
+    pub chi_W: f32,    // Waste repulsion strength
+    pub kappa: f32,    // Gradient saturation parameter
+    pub gamma: f32,    // Velocity damping (fraction per step, not scaled by dt)
+    pub v_max: f32,    // Maximum velocity (cells per time unit)
+    pub eps0: f32,     // Basal energy drain rate (per time unit)
+    pub eta_R: f32,    // Energy gain from resource (per time unit)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sampling: FieldSampling, // Field/gradient sampling used for chemotaxis
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gradient: GradientStencil, // Difference stencil of the nearest-cell gradient
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub memory_weight: f32, // Pull toward the remembered best-resource site (0 = memoryless)
+    #[cfg_attr(feature = "serde", serde(default = "default_memory_decay"))]
+    pub memory_decay: f32,  // Decay of the remembered resource value (per time unit)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub disperse_energy: f32, // Energy below which a forager starts dispersing (0 = never)
+    #[cfg_attr(feature = "serde", serde(default = "default_settle_resource"))]
+    pub settle_resource: f32, // Local R at which a disperser resumes foraging
+    #[cfg_attr(feature = "serde", serde(default = "default_disperse_speed"))]
+    pub disperse_speed: f32,  // v_max multiplier while dispersing
+}
+
+impl Default for ChemotaxisConfig {
+    fn default() -> Self {
+        Self {
+            chi_R: 8.0,
+            chi_W: 4.0,
+            kappa: 2.0,
+            gamma: 0.05,
+            v_max: 2.0,
+            eps0: 0.02,
+            eta_R: 0.2,
+            sampling: FieldSampling::Nearest,
+            gradient: GradientStencil::Central,
+            memory_weight: 0.0,
+            memory_decay: 0.05,
+            disperse_energy: 0.0,
+            settle_resource: 0.3,
+            disperse_speed: 1.5,
+        }
+    }
+}
+
+/// Memory decay used when a config omits `chemotaxis.memory_decay`
+#[cfg(feature = "serde")]
+fn default_memory_decay() -> f32 {
+    0.05
+}
+
+/// Settling threshold used when a config omits `chemotaxis.settle_resource`
+#[cfg(feature = "serde")]
+fn default_settle_resource() -> f32 {
+    0.3
+}
+
+/// Dispersal speed-up used when a config omits `chemotaxis.disperse_speed`
+#[cfg(feature = "serde")]
+fn default_disperse_speed() -> f32 {
+    1.5
+}
+
+impl ChemotaxisConfig {
+    /// The rates with their units, for building `AgentParams`
+    pub fn rates(&self) -> ChemotaxisRates {
+        ChemotaxisRates {
+            chi_R: self.chi_R,
+            chi_W: self.chi_W,
+            kappa: self.kappa,
+            gamma: PerStep(self.gamma),
+            v_max: Speed(self.v_max),
+            eps0: PerTime(self.eps0),
+            eta_R: PerTime(self.eta_R),
+            memory_weight: self.memory_weight,
+            memory_decay: PerTime(self.memory_decay),
+            disperse_energy: self.disperse_energy,
+            settle_resource: self.settle_resource,
+            disperse_speed: self.disperse_speed,
+        }
+    }
+}
+
+/// Agent configuration
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AgentConfig {
+    pub herbivores: u32, // Spawns herbivores * 3 agents: 1/6 plants, 1/6 predators, the rest herbivores
+    pub E0: f32,       // Initial energy
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capacity: SpeciesCapacity,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub growth: GrowthConfig,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            herbivores: 2000,
+            E0: 1.0,
+            capacity: SpeciesCapacity::default(),
+            growth: GrowthConfig::default(),
+        }
+    }
+}
+
+/// Agent buffer slots reserved per species (0 = exactly the spawned count)
+///
+/// Slots beyond the spawned agents start free for births; a capacity below
+/// the spawned count spawns only that many.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SpeciesCapacity {
+    pub plants: u32,
+    pub herbivores: u32,
+    pub predators: u32,
+}
+
+impl SpeciesCapacity {
+    /// Capacities indexed by agent kind (0 = plant, 1 = herbivore, 2 = predator)
+    pub fn by_kind(&self) -> [u32; 3] {
+        [self.plants, self.herbivores, self.predators]
+    }
+}
+
+/// What happens once births fill a species' slot range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum GrowthPolicy {
+    /// Ranges keep their capacity; a birth that finds no free slot is dropped (default)
+    #[default]
+    Reject,
+    /// A range that fills past `fill` is reallocated at the next power of two,
+    /// up to `max_population`
+    Grow,
+}
+
+/// Agent buffer growth when births exceed the slots (`agents.growth`)
+///
+/// Under `reject` the capacities are the population ceilings. Under `grow`
+/// the world reads its agents back every `check_every` steps and copies them
+/// into a larger buffer when a species holds more than `fill` of its range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GrowthConfig {
+    pub policy: GrowthPolicy,
+    pub max_population: SpeciesCapacity, // Slot ceiling per species under `grow` (0 = only the agent limit)
+    pub check_every: u32,                // Steps between fill checks
+    pub fill: f32,                       // Fraction of a range alive that triggers a grow, in (0, 1]
+}
+
+impl Default for GrowthConfig {
+    fn default() -> Self {
+        Self {
+            policy: GrowthPolicy::Reject,
+            max_population: SpeciesCapacity::default(),
+            check_every: 100,
+            fill: 0.5,
+        }
+    }
+}
+
+impl GrowthConfig {
+    /// Slots per kind after a fill check finding `alive` agents in ranges of `slots`
+    ///
+    /// A range past `fill` doubles to the next power of two, clamped to its
+    /// `max_population` and to what the other ranges leave of `MAX_AGENTS`.
+    /// Kinds are grown in order, so plants get the budget first. Under
+    /// `reject`, or when nothing is past `fill`, the slots come back unchanged.
+    pub fn grown_slots(&self, slots: [u32; 3], alive: [u32; 3]) -> [u32; 3] {
+        let mut next = slots;
+        if self.policy != GrowthPolicy::Grow {
+            return next;
+        }
+        let ceiling = self.max_population.by_kind();
+        for kind in 0..3 {
+            if slots[kind] == 0 || (alive[kind] as f32) <= self.fill * slots[kind] as f32 {
+                continue;
+            }
+            let others: u64 = next.iter().enumerate().filter(|&(k, _)| k != kind).map(|(_, &n)| n as u64).sum();
+            let budget = (MAX_AGENTS as u64).saturating_sub(others) as u32;
+            let limit = if ceiling[kind] == 0 { budget } else { ceiling[kind].min(budget) };
+            let doubled = slots[kind].saturating_mul(2).checked_next_power_of_two().unwrap_or(u32::MAX);
+            next[kind] = doubled.min(limit).max(slots[kind]);
+        }
+        next
+    }
+}
+
+impl AgentConfig {
+    /// Agents spawned per kind before capacities apply
+    pub fn spawn_counts(&self) -> [u32; 3] {
+        let total = self.herbivores * 3;
+        let plants = total / 6;
+        let predators = total / 6;
+        [plants, total - plants - predators, predators]
+    }
+
+    /// Agent buffer slots per kind
+    pub fn slots(&self) -> [u32; 3] {
+        let spawned = self.spawn_counts();
+        let capacity = self.capacity.by_kind();
+        std::array::from_fn(|kind| if capacity[kind] == 0 { spawned[kind] } else { capacity[kind] })
+    }
+
+    /// Agent buffer slots over all kinds
+    pub fn total_slots(&self) -> u64 {
+        self.slots().iter().map(|&n| n as u64).sum()
+    }
+}
+
+/// Environmental noise on the resource field
+///
+/// Each step the reaction-diffusion pass adds `sigma`·√dt·z to every cell's
+/// R, with z a standard normal drawn from `world.seed`, the step and the
+/// cell, then clamps at 0. `sigma` is thus in units of R per √time unit: left
+/// alone, a cell's variance grows by sigma² per time unit whatever `world.dt`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NoiseConfig {
+    pub sigma: f32,    // Standard deviation of R added per √time unit (0 = no noise)
+}
+
+/// Static obstacle shape in cell coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "shape", rename_all = "lowercase"))]
+pub enum ObstacleShape {
+    Circle { center: [f32; 2], radius: f32 },
+    /// Axis-aligned rectangle between `min` and `max`
+    Rect { min: [f32; 2], max: [f32; 2] },
+}
+
+impl ObstacleShape {
+    /// Signed distance from `p` to the shape's boundary (negative inside)
+    pub fn distance(&self, p: [f32; 2]) -> f32 {
+        match *self {
+            ObstacleShape::Circle { center, radius } => {
+                (p[0] - center[0]).hypot(p[1] - center[1]) - radius
+            }
+            ObstacleShape::Rect { min, max } => {
+                let half = [(max[0] - min[0]) * 0.5, (max[1] - min[1]) * 0.5];
+                let q = [
+                    (p[0] - (min[0] + half[0])).abs() - half[0],
+                    (p[1] - (min[1] + half[1])).abs() - half[1],
+                ];
+                q[0].max(0.0).hypot(q[1].max(0.0)) + q[0].max(q[1]).min(0.0)
+            }
+        }
+    }
+}
+
+/// Static walls, stored on the GPU as a signed distance field
+///
+/// Agents steer away along the distance gradient once closer than
+/// `avoid_distance` and are projected back out if they still cross a wall.
+/// The RD pass closes cell faces by the fraction the field puts inside a wall.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ObstacleConfig {
+    pub enabled: bool,
+    pub shapes: Vec<ObstacleShape>,
+    pub avoid_distance: f32, // Distance from a wall (cells) at which steering starts
+    pub avoid_strength: f32, // Repulsion at the wall surface, fading linearly to 0 at avoid_distance
+}
+
+impl Default for ObstacleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shapes: Vec::new(),
+            avoid_distance: 4.0,
+            avoid_strength: 20.0,
+        }
+    }
+}
+
+/// Static terrain feature in cell coordinates, summed into the elevation field
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "shape", rename_all = "lowercase"))]
+pub enum TerrainFeature {
+    /// Parabolic bowl `depth` deep at `center`, level with the plain at `radius`,
+    /// with a Gaussian rim `rim` high around the edge
+    Crater { center: [f32; 2], radius: f32, depth: f32, rim: f32 },
+    /// Gaussian trough `depth` deep and `width` wide along the segment `from`–`to`
+    Valley { from: [f32; 2], to: [f32; 2], width: f32, depth: f32 },
+}
+
+impl TerrainFeature {
+    /// Elevation contributed at `p` (zero on the open plain)
+    pub fn elevation(&self, p: [f32; 2]) -> f32 {
+        match *self {
+            TerrainFeature::Crater { center, radius, depth, rim } => {
+                let r = (p[0] - center[0]).hypot(p[1] - center[1]) / radius;
+                let bowl = if r < 1.0 { -depth * (1.0 - r * r) } else { 0.0 };
+                bowl + rim * (-((r - 1.0) / 0.25).powi(2)).exp()
+            }
+            TerrainFeature::Valley { from, to, width, depth } => {
+                let d = [to[0] - from[0], to[1] - from[1]];
+                let len2 = d[0] * d[0] + d[1] * d[1];
+                let t = if len2 > 0.0 {
+                    (((p[0] - from[0]) * d[0] + (p[1] - from[1]) * d[1]) / len2).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let dist = (p[0] - from[0] - t * d[0]).hypot(p[1] - from[1] - t * d[1]);
+                -depth * (-(dist / width).powi(2)).exp()
+            }
+        }
+    }
+}
+
+/// Static elevation field shaping movement costs and diffusion
+///
+/// An agent pays `climb_cost` energy per unit of elevation gained in a step and
+/// recovers `descent_gain` per unit lost, so downhill travel is cheaper than
+/// flat ground. With `anisotropy > 0` the RD pass scales each cell face's
+/// diffusion by `1 / (1 + anisotropy·|Δh|)`, so R and W spread along contours
+/// faster than across slopes.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TerrainConfig {
+    pub enabled: bool,
+    pub features: Vec<TerrainFeature>,
+    pub climb_cost: f32,   // Energy per unit of elevation climbed
+    pub descent_gain: f32, // Energy returned per unit of elevation descended (at most climb_cost)
+    pub anisotropy: f32,   // Cross-slope diffusion damping (0 = isotropic)
+}
+
+impl Default for TerrainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            features: Vec::new(),
+            climb_cost: 0.5,
+            descent_gain: 0.25,
+            anisotropy: 0.0,
+        }
+    }
+}
+
+/// A course through the diffusion direction field, e.g. a river
+///
+/// Within about `width` cells of the segment `from`–`to` the diffusion tensor
+/// turns to follow it: `along` times `D` along the course and `across` times
+/// `D` across it, blended into the surrounding tensor by a Gaussian falloff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffusionStream {
+    pub from: [f32; 2],
+    pub to: [f32; 2],
+    pub width: f32,  // Gaussian half-width in cells
+    pub along: f32,  // D multiplier along the course
+    pub across: f32, // D multiplier across it
+}
+
+impl DiffusionStream {
+    /// `Dxx + Dyy + |Dxy|` on the course, where the tensor is the stream's own
+    pub fn spread(&self) -> f32 {
+        self.along + self.across + 0.5 * (self.along - self.across).abs()
+    }
+
+    /// Weight of the stream at `p` (1 on the course) and its unit direction
+    pub fn influence(&self, p: [f32; 2]) -> (f32, [f32; 2]) {
+        let d = [self.to[0] - self.from[0], self.to[1] - self.from[1]];
+        let len = d[0].hypot(d[1]);
+        let t = (((p[0] - self.from[0]) * d[0] + (p[1] - self.from[1]) * d[1]) / (len * len)).clamp(0.0, 1.0);
+        let dist = (p[0] - self.from[0] - t * d[0]).hypot(p[1] - self.from[1] - t * d[1]);
+        ((-(dist / self.width).powi(2)).exp(), [d[0] / len, d[1] / len])
+    }
+}
+
+/// Anisotropic diffusion of R and W, for directional transport such as
+/// prevailing winds or rivers
+///
+/// `D_R` and `D_W` are scaled by a symmetric tensor per cell, given as
+/// `(Dxx, Dyy, Dxy)` multipliers: `tensor` everywhere, turned along each of
+/// `streams` near its course. `[1, 1, 0]` without streams is plain diffusion.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DiffusionConfig {
+    pub enabled: bool,
+    pub tensor: [f32; 3], // (Dxx, Dyy, Dxy) multipliers away from the streams
+    pub streams: Vec<DiffusionStream>,
+}
+
+impl Default for DiffusionConfig {
+    fn default() -> Self {
+        Self { enabled: false, tensor: [1.0, 1.0, 0.0], streams: Vec::new() }
+    }
+}
+
+/// Resource bloom events: random regions where sigma_R temporarily spikes
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BloomConfig {
+    pub enabled: bool,
+    pub rate: f32,      // Probability of a new bloom per step
+    pub duration: u32,  // Bloom lifetime in steps
+    pub radius: f32,    // Bloom radius in cells
+    pub amplitude: f32, // sigma_R multiplier inside the bloom (sigma_R * (1 + amplitude))
+}
+
+impl Default for BloomConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rate: 0.01,
+            duration: 200,
+            radius: 12.0,
+            amplitude: 10.0,
+        }
+    }
+}
+
+/// Moving rain cells: Gaussian patches of boosted sigma_R drifting across the world
+///
+/// Spawned like blooms, but each cell travels at `velocity` plus a random
+/// offset of up to `velocity_jitter` per axis, so the good patches move and
+/// foragers have to track them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RainConfig {
+    pub enabled: bool,
+    pub rate: f32,            // Probability of a new rain cell per step
+    pub duration: u32,        // Rain cell lifetime in steps
+    pub radius: f32,          // Gaussian standard deviation in cells
+    pub amplitude: f32,       // sigma_R multiplier at the centre (sigma_R * (1 + amplitude))
+    pub velocity: [f32; 2],   // Mean drift in cells per step
+    pub velocity_jitter: f32, // Largest random offset added to each velocity component
+}
+
+impl Default for RainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rate: 0.02,
+            duration: 300,
+            radius: 10.0,
+            amplitude: 5.0,
+            velocity: [0.2, 0.0],
+            velocity_jitter: 0.05,
+        }
+    }
+}
+
+/// Lasting soil damage from overgrazing
+///
+/// Where herbivore uptake (`alpha_H·H·R`) exceeds `threshold`, the cell's soil
+/// damage grows by `degradation` per unit of excess and time; it heals by
+/// `recovery` per time unit towards pristine. Regrowth runs at
+/// `sigma_R·(1 − damage)`, so an overgrazed patch stays poor long after the
+/// herd has left. Damage is held in the `FieldChannel::Soil` channel.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SoilConfig {
+    pub enabled: bool,
+    pub threshold: f32,   // Uptake rate per cell the soil tolerates (per time unit)
+    pub degradation: f32, // Damage per unit of uptake above the threshold
+    pub recovery: f32,    // Fraction of the damage healed per time unit
+}
+
+impl Default for SoilConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.02,
+            degradation: 5.0,
+            recovery: 0.002,
+        }
+    }
+}
+
+/// Output format of exported agent tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum TrackFormat {
+    /// Movebank-style CSV, one fix per row (`tracks.csv`)
+    #[default]
+    Csv,
+    /// Newline-delimited GeoJSON, one LineString feature per agent (`tracks.geojsonl`)
+    GeoJson,
+}
+
+/// Per-agent trajectory export for movement-ecology analysis
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TrackConfig {
+    pub enabled: bool,
+    pub every: u32,          // Record a fix every K steps
+    pub sample: u32,         // Number of agent ids tracked (drawn from world.seed)
+    pub format: TrackFormat,
+}
+
+impl Default for TrackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            every: 10,
+            sample: 100,
+            format: TrackFormat::Csv,
+        }
+    }
+}
+
+/// A virtual sampling station: a fixed point whose local field and agents are
+/// recorded to `stations/<name>.csv`, like a field site visited on a schedule
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StationConfig {
+    pub name: String,        // File stem: letters, digits, `-` and `_`
+    pub pos: [f32; 2],       // Position in cells; R and W are read in the cell it lies in
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub radius: f32,         // Agents within this many cells are counted (0 = the station's cell)
+    #[cfg_attr(feature = "serde", serde(default = "default_station_every"))]
+    pub every: u32,          // Record every K steps
+}
+
+fn default_station_every() -> u32 {
+    1
+}
+
+impl StationConfig {
+    /// Cell the station reads R and W from
+    pub fn cell(&self) -> [u32; 2] {
+        self.pos.map(|p| p.max(0.0) as u32)
+    }
+
+    /// Whether `step` is a recording step
+    pub fn is_due(&self, step: u32) -> bool {
+        step.is_multiple_of(self.every.max(1))
+    }
+
+    /// Whether an agent at `pos` counts toward the station's occupancy
+    pub fn covers(&self, pos: [f32; 2]) -> bool {
+        if self.radius > 0.0 {
+            (pos[0] - self.pos[0]).hypot(pos[1] - self.pos[1]) <= self.radius
+        } else {
+            pos.map(|p| p.max(0.0) as u32) == self.cell()
+        }
+    }
+}
+
+/// Experimental endless world: the field is a window onto a procedural
+/// landscape that follows the viewer camera chunk by chunk
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StreamingConfig {
+    pub enabled: bool,
+    pub chunk: u32,       // Chunk edge in cells; world.size must be a multiple
+    pub keep_radius: u32, // Chunks outside the window kept in memory up to this distance (in chunks)
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            chunk: 32,
+            keep_radius: 4,
+        }
+    }
+}
+
+/// How often runs report: metrics rows, viewer HUD and debug readbacks
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OutputConfig {
+    pub metrics_every: u32, // Steps between metrics.csv rows (and console progress lines)
+    pub hud_every: u32,     // Viewer frames between HUD printouts
+    pub debug_every: u32,   // Steps between debug readbacks/logs (headless --debug-scenario, viewer)
+    pub species_long: bool, // Also write species.csv as (step, species, metric, value) rows
+    pub utility_maps: bool, // Also write utility_NNNN.csv (perceived chemotaxis landscape) with each snapshot
+    pub age_structure: bool, // Also write ages.csv (alive agents per age class and species) with each metrics row
+    pub age_bin: u32,       // Steps per age class (ages.csv and the viewer's age chart)
+    pub age_classes: u32,   // Age classes, the last one open-ended
+    pub occupancy_total: bool, // Sum occupancy over the run on the GPU; write occupancy_total.{csv,png} at the end
+    pub event_window: u32,  // Count births and deaths per cell over windows of this many steps (0 = off)
+    pub sampling: MetricsSampling, // Estimate the field columns of metrics.csv from an adaptive sample
+    pub quadrats: u32,      // Quadrats per axis for the beta diversity column of metrics.csv
+    pub frames_every: u32,  // Also write R_NNNN.png and agents_NNNN.csv every this many steps, for render-video (0 = off)
+    pub checkpoints: CheckpointChainConfig, // On-disk checkpoint chain in checkpoints/
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            metrics_every: 50,
+            hud_every: 30,
+            debug_every: 100,
+            species_long: false,
+            utility_maps: false,
+            age_structure: false,
+            age_bin: 100,
+            age_classes: 20,
+            occupancy_total: false,
+            event_window: 0,
+            sampling: MetricsSampling::default(),
+            quadrats: 8,
+            frames_every: 0,
+            checkpoints: CheckpointChainConfig::default(),
+        }
+    }
+}
+
+/// Checkpoint chain written to `checkpoints/` (`output.checkpoints`)
+///
+/// The first checkpoint and every `keyframe_every`-th after it are full state
+/// snapshots; the ones in between store only the field tiles and agent slots
+/// that changed since the previous checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CheckpointChainConfig {
+    pub every: u32,          // Steps between checkpoints (0 = off)
+    pub tile: u32,           // Edge of the field tiles compared between checkpoints, in cells
+    pub keyframe_every: u32, // Checkpoints per full snapshot (0 = only the first), bounding the deltas a rebuild applies
+}
+
+impl Default for CheckpointChainConfig {
+    fn default() -> Self {
+        Self { every: 0, tile: 32, keyframe_every: 100 }
+    }
+}
+
+/// Adaptive quadtree sampling of the field for metrics (`output.sampling`)
+///
+/// Instead of reading back every cell, a quadtree over the world draws a few
+/// cells per node and splits the nodes whose samples spread the most until
+/// the standard errors of mean R and W fall below `tolerance` or `max_cells`
+/// cells have been read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MetricsSampling {
+    pub enabled: bool,
+    pub max_cells: u32,  // Cells read per metrics row at most
+    pub tolerance: f32,  // Target standard error of mean R and mean W
+}
+
+impl Default for MetricsSampling {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_cells: 4096,
+            tolerance: 1e-3,
+        }
+    }
+}
+
+/// Species names indexed by agent kind, as used in config keys and CSV output
+pub const SPECIES: [&str; 3] = ["plants", "herbivores", "predators"];
+
+/// Per-step demographic event probabilities of one species
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SpeciesDemography {
+    pub death: f32, // Probability that an alive agent dies this step
+    pub birth: f32, // Probability that an alive agent attempts to give birth this step
+}
+
+/// Demographic stochasticity: random deaths and births drawn per agent on the GPU
+///
+/// Starvation (energy <= 0) still kills regardless. A birth fills the free slot
+/// paired with the parent this step, so births slow down as the agent buffer fills.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DemographyConfig {
+    pub enabled: bool,
+    pub plants: SpeciesDemography,
+    pub herbivores: SpeciesDemography,
+    pub predators: SpeciesDemography,
+}
+
+impl DemographyConfig {
+    /// Species rates indexed by agent kind (0 = plant, 1 = herbivore, 2 = predator)
+    pub fn by_kind(&self) -> [SpeciesDemography; 3] {
+        [self.plants, self.herbivores, self.predators]
+    }
+}
+
+/// A protected area: a polygon in cell coordinates where random deaths are reduced
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefugeRegion {
+    pub polygon: Vec<[f32; 2]>, // Vertices in order; the last connects back to the first
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mortality: f32,         // Fraction of the demography death probability kept inside (0 = no random deaths)
+}
+
+impl RefugeRegion {
+    /// Whether `p` lies inside the polygon (even-odd rule)
+    pub fn contains(&self, p: [f32; 2]) -> bool {
+        let mut inside = false;
+        let n = self.polygon.len();
+        for i in 0..n {
+            let (a, b) = (self.polygon[i], self.polygon[(i + n - 1) % n]);
+            if (a[1] > p[1]) != (b[1] > p[1]) && p[0] < a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+/// Protected areas for reserve-design experiments (e.g. single large vs several small)
+///
+/// Inside a region the demography pass scales each species' death probability
+/// by the region's `mortality`; where regions overlap the lowest applies.
+/// Starvation still kills, and nothing preys on agents yet.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RefugeConfig {
+    pub enabled: bool,
+    pub regions: Vec<RefugeRegion>,
+}
+
+/// Largest `evolution` mutation step, a standard deviation of the log trait
+pub const MAX_MUTATION: f32 = 1.0;
+
+/// Mutation of one species' heritable chemotaxis traits at birth
+///
+/// Each is the standard deviation of the normal step added to the log of the
+/// offspring's multiplier, so traits stay positive and drift symmetrically
+/// up and down by ratio; 0 copies the parent exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SpeciesMutation {
+    pub chi: f32,   // On the multiplier of `chemotaxis.chi_R`
+    pub kappa: f32, // On the multiplier of `chemotaxis.kappa`
+}
+
+/// Heritable chemotaxis: every agent carries its own multipliers on
+/// `chemotaxis.chi_R` and `chemotaxis.kappa`
+///
+/// Agents spawn with both at 1 and newborns inherit their parent's, mutated
+/// by their species' `SpeciesMutation`, so selection through starvation and
+/// births can evolve foraging strategies. Births come from the demography
+/// pass, so evolution needs `demography.enabled`. Schedules and `set` on the
+/// global parameters still scale every agent.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EvolutionConfig {
+    pub enabled: bool,
+    pub plants: SpeciesMutation,
+    pub herbivores: SpeciesMutation,
+    pub predators: SpeciesMutation,
+}
+
+impl EvolutionConfig {
+    /// Mutation indexed by agent kind (0 = plant, 1 = herbivore, 2 = predator)
+    pub fn by_kind(&self) -> [SpeciesMutation; 3] {
+        [self.plants, self.herbivores, self.predators]
+    }
+}
+
+/// Pull of one species up (positive) or down (negative) the density gradient of each species
+///
+/// A row of the cross-attraction matrix: predators climbing herbivore density
+/// set `herbivores` above 0, herbivores fleeing predators set `predators` below 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SpeciesAttraction {
+    pub plants: f32,
+    pub herbivores: f32,
+    pub predators: f32,
+}
+
+impl SpeciesAttraction {
+    /// Weights indexed by the kind followed (0 = plant, 1 = herbivore, 2 = predator)
+    pub fn by_kind(&self) -> [f32; 3] {
+        [self.plants, self.herbivores, self.predators]
+    }
+}
+
+/// Steering terms of one species, each scaling its contribution to the agent's acceleration
+///
+/// Chemotaxis, memory homing and wall avoidance keep their own strengths
+/// (`chi_R`, `memory_weight`, `avoid_strength`) and are multiplied by their
+/// weight here; noise, crowding and flocking have no other gain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SteeringWeights {
+    pub chemotaxis: f32, // Saturated ∇R attraction and ∇W repulsion
+    pub noise: f32,      // Random heading per step, cells per time unit²
+    pub crowding: f32,   // Push down the local agent density gradient
+    pub flocking: f32,   // Pull toward the mean velocity of the surrounding cells
+    pub memory: f32,     // Homing toward the remembered site
+    pub obstacles: f32,  // Wall avoidance
+    pub attraction: SpeciesAttraction, // Saturated pull along each species' density gradient
+}
+
+impl Default for SteeringWeights {
+    /// Chemotaxis, memory and walls as before; no noise, crowding, flocking or attraction
+    fn default() -> Self {
+        Self { chemotaxis: 1.0, noise: 0.0, crowding: 0.0, flocking: 0.0, memory: 1.0, obstacles: 1.0, attraction: SpeciesAttraction::default() }
+    }
+}
+
+impl SteeringWeights {
+    /// (name, weight) pairs, in `SteeringParams` order
+    pub fn named(&self) -> [(&'static str, f32); 6] {
+        [
+            ("chemotaxis", self.chemotaxis),
+            ("noise", self.noise),
+            ("crowding", self.crowding),
+            ("flocking", self.flocking),
+            ("memory", self.memory),
+            ("obstacles", self.obstacles),
+        ]
+    }
+
+    /// Whether the agent pass needs the neighbour grid for this species
+    pub fn needs_neighbours(&self) -> bool {
+        self.crowding != 0.0 || self.flocking != 0.0 || self.attraction.by_kind().iter().any(|&w| w != 0.0)
+    }
+}
+
+/// Per-species weights of the steering terms summed by the agent pass
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SteeringConfig {
+    pub plants: SteeringWeights,
+    pub herbivores: SteeringWeights,
+    pub predators: SteeringWeights,
+}
+
+impl SteeringConfig {
+    /// Species weights indexed by agent kind (0 = plant, 1 = herbivore, 2 = predator)
+    pub fn by_kind(&self) -> [SteeringWeights; 3] {
+        [self.plants, self.herbivores, self.predators]
+    }
+
+    /// Whether any species crowds, flocks or follows another species' density
+    pub fn needs_neighbours(&self) -> bool {
+        self.by_kind().iter().any(SteeringWeights::needs_neighbours)
+    }
+}
+
+/// Soft-body separation: overlapping agents push each other apart
+///
+/// Agents are hashed into world cells every step (a radix sort of cell
+/// indices), and each alive agent is moved away from every neighbour closer
+/// than two radii, by `stiffness` times its half of the overlap. Runs just
+/// before the agent pass, so dense aggregations keep a spatial extent instead
+/// of piling up on one point.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SeparationConfig {
+    pub enabled: bool,
+    pub radius: f32,          // Agent radius in cells; two agents overlap below 2 * radius
+    pub stiffness: f32,       // Fraction of each overlap resolved per step, in (0, 1]
+    pub cross_species: bool,  // Separate agents of different species too (otherwise only the same species)
+    pub max_neighbours: u32,  // Overlaps resolved per agent and step, bounding the cost in pile-ups
+}
+
+impl Default for SeparationConfig {
+    fn default() -> Self {
+        Self { enabled: false, radius: 0.5, stiffness: 0.5, cross_species: true, max_neighbours: 16 }
+    }
+}
+
+/// GPU memory guard rails, checked before any field or agent buffer is allocated,
+/// and recovery from a lost device
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GpuConfig {
+    pub budget_mb: u32,       // Total estimate must fit this many MiB (0 = adapter limits only)
+    pub auto_downscale: bool, // Halve the world (and agents) until it fits instead of failing
+    pub checkpoint_every: u32, // Steps between in-memory checkpoints to recover from (0 = no recovery)
+    pub max_recoveries: u32,   // Device losses survived per run before giving up
+    pub rewind_slots: u32,     // Field states the viewer keeps on the GPU to rewind to (0 = no rewind)
+    pub rewind_every: u32,     // Steps between rewind captures
+}
+
+impl Default for GpuConfig {
+    fn default() -> Self {
+        Self {
+            budget_mb: 0,
+            auto_downscale: false,
+            checkpoint_every: 500,
+            max_recoveries: 3,
+            rewind_slots: 8,
+            rewind_every: 100,
+        }
+    }
+}
+
+/// Kill switches that stop a headless run once it has clearly blown up
+///
+/// Checked at every metrics sample (`output.metrics_every`) of a single run,
+/// and at every population read of a sweep run.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GuardConfig {
+    pub non_finite: bool, // Stop when mean R, mean W or any alive agent's energy is NaN or infinite
+    pub max_mean_R: f32,  // Stop when mean R exceeds this (0 = no bound)
+    pub max_step_ms: f32, // Stop when the mean wall time per step since the last check exceeds this (0 = no limit)
+}
+
+impl Default for GuardConfig {
+    fn default() -> Self {
+        Self { non_finite: true, max_mean_R: 0.0, max_step_ms: 0.0 }
+    }
+}
+
+/// What the agent color of the viewer and headless preview encodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AgentColoring {
+    /// One fixed color per species
+    Species,
+    /// `starving_color` to `thriving_color` along `energy_range`
+    #[default]
+    Energy,
+}
+
+/// Widest kernel density bandwidth (`render.kde_bandwidth`), in cells; the
+/// kernel is summed out to three bandwidths on either side of each cell
+pub const MAX_KDE_BANDWIDTH: f32 = 16.0;
+
+/// Agent colors of the viewer and headless preview
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RenderConfig {
+    pub agent_color: AgentColoring,
+    pub starving_color: [f32; 3], // RGB in [0, 1] at energy_range[0] and below
+    pub thriving_color: [f32; 3], // RGB in [0, 1] at energy_range[1] and above
+    pub energy_range: [f32; 2],   // Energies mapped to the two ends of the gradient
+    pub dead_alpha: f32,          // Opacity of dead agents where they died (0 hides them)
+    pub density_below: f32,       // Cell size in pixels below which agents are drawn as a density map (0 = always quads)
+    pub density_saturation: f32,  // Agents per cell drawn fully opaque in the density map, and at the top of the kernel density colormap
+    pub kde: bool,                // Draw agents as a kernel density estimate at every zoom (viewer: toggled with `k`)
+    pub kde_bandwidth: f32,       // Gaussian kernel standard deviation in cells
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            agent_color: AgentColoring::Energy,
+            starving_color: [0.9, 0.25, 0.1],
+            thriving_color: [0.2, 0.95, 0.4],
+            energy_range: [0.0, 2.0],
+            dead_alpha: 0.25,
+            density_below: 2.0,
+            density_saturation: 4.0,
+            kde: false,
+            kde_bandwidth: 2.0,
+        }
+    }
+}
+
+/// Tag every alive agent inside a disc with a cohort id at a given step
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CohortEvent {
+    pub id: u32,            // Cohort id (0 is reserved for untagged agents)
+    pub step: u32,          // Step at which the tag is applied
+    pub center: [f32; 2],   // Disc centre in cells
+    pub radius: f32,        // Disc radius in cells
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: Option<u32>,  // Only tag this agent kind (None = any)
+}
+
+/// Shape of a parameter schedule between `start` and `end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ScheduleShape {
+    /// Straight ramp from `from` at `start` to `to` at `end`
+    #[default]
+    Linear,
+    /// `from` before `start`, `to` from `start` on (`end` is ignored)
+    Step,
+}
+
+/// A time-varying scalar parameter, applied whenever the uniforms are refreshed
+///
+/// Written inline in YAML as `chi_R: {schedule: linear, from: 2, to: 10, start: 0, end: 2000}`
+/// (the loader moves it here and sets `chi_R` to `from`) or listed under
+/// `schedules:` with an explicit `param: chemotaxis.chi_R`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamSchedule {
+    pub param: String,           // Dotted path, one of `SCHEDULABLE_PARAMS`
+    pub schedule: ScheduleShape,
+    pub from: f32,
+    pub to: f32,
+    pub start: u32,              // First step of the change
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub end: u32,                // Step at which a linear ramp reaches `to`
+}
+
+impl ParamSchedule {
+    /// Value of the parameter during `step`
+    pub fn value_at(&self, step: u32) -> f32 {
+        match self.schedule {
+            _ if step < self.start => self.from,
+            ScheduleShape::Step => self.to,
+            ScheduleShape::Linear if step >= self.end => self.to,
+            ScheduleShape::Linear => {
+                let t = (step - self.start) as f32 / (self.end - self.start) as f32;
+                self.from + (self.to - self.from) * t
+            }
+        }
+    }
+}
+
+/// Parameters that can be scheduled: everything copied into `RDParams` and
+/// `AgentParams` on each uniform refresh
+pub const SCHEDULABLE_PARAMS: [&str; 20] = [
+    "field.D_R", "field.D_W", "field.sigma_R", "field.K_R", "field.alpha_H",
+    "field.beta_H", "field.lambda_R", "field.lambda_W",
+    "chemotaxis.chi_R", "chemotaxis.chi_W", "chemotaxis.kappa", "chemotaxis.gamma",
+    "chemotaxis.v_max", "chemotaxis.eps0", "chemotaxis.eta_R",
+    "chemotaxis.memory_weight", "chemotaxis.memory_decay",
+    "chemotaxis.disperse_energy", "chemotaxis.settle_resource", "chemotaxis.disperse_speed",
+];
+
+/// Random jitter of scalar parameters, redrawn every `every` steps
+///
+/// Each draw sets every listed parameter to its configured value times
+/// `1 + u`, with `u` uniform in [−`sigma`, `sigma`]. Draws depend only on
+/// `world.seed` and the draw number, so reruns and recoveries see the same
+/// values. A regime that survives the jitter does not hinge on exact values.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerturbationConfig {
+    pub enabled: bool,
+    pub params: Vec<String>, // Dotted paths, from `SCHEDULABLE_PARAMS`
+    pub every: u32,          // Steps between draws
+    pub sigma: f32,          // Largest relative change per draw, in [0, 1)
+}
+
+impl Default for PerturbationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            params: Vec::new(),
+            every: 500,
+            sigma: 0.05,
+        }
+    }
 }
 
-/// Field reaction-diffusion parameters
-#[derive(Debug, Clone)]
+/// How a forcing series acts on its parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct FieldConfig {
-    pub D_R: f32,      // Resource diffusion coefficient
-    pub D_W: f32,      // Waste diffusion coefficient
-    pub sigma_R: f32,  // Resource replenishment rate
-    pub alpha_H: f32,  // Herbivore resource uptake rate
-    pub beta_H: f32,   // Herbivore waste emission rate
-    pub lambda_R: f32, // Resource decay rate
-    pub lambda_W: f32, // Waste decay rate
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ForcingMode {
+    /// Configured value times the series value (default)
+    #[default]
+    Scale,
+    /// The series value replaces the configured value
+    Set,
 }
 
-/// Chemotaxis parameters
-#[derive(Debug, Clone)]
+/// An external time series (e.g. rainfall) driving a scalar parameter
+///
+/// `file` is a CSV table with a header row; `step_column` gives the step of
+/// each sample and `value_column` its value. Between samples the value is
+/// interpolated linearly; before the first and after the last sample it
+/// holds the end value. `load_config` reads the file into `points`,
+/// resolving a relative path against the config file's directory.
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct ChemotaxisConfig {
-    pub chi_R: f32,    // Resource attraction strength
-    pub chi_W: f32,    // Waste repulsion strength
-    pub kappa: f32,    // Gradient saturation parameter
-    pub gamma: f32,    // Velocity damping
-    pub v_max: f32,    // Maximum velocity
-    pub eps0: f32,     // Basal energy drain rate
-    pub eta_R: f32,    // Energy gain from resource
+pub struct ForcingConfig {
+    pub param: String,          // Dotted path, one of `SCHEDULABLE_PARAMS`
+    pub file: std::path::PathBuf,
+    #[cfg_attr(feature = "serde", serde(default = "default_step_column"))]
+    pub step_column: String,
+    pub value_column: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mode: ForcingMode,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub points: Vec<[f32; 2]>,  // (step, value) samples, steps strictly increasing
 }
 
-/// Agent configuration
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AgentConfig {
-    pub herbivores: u32,
-    pub E0: f32,       // Initial energy
-}
+impl ForcingConfig {
+    /// Series value during `step` (`None` before the samples are loaded)
+    pub fn value_at(&self, step: u32) -> Option<f32> {
+        let (first, last) = (self.points.first()?, self.points.last()?);
+        let t = step as f32;
+        if t <= first[0] {
+            return Some(first[1]);
+        }
+        if t >= last[0] {
+            return Some(last[1]);
+        }
+        let i = self.points.partition_point(|p| p[0] <= t);
+        let ([s0, v0], [s1, v1]) = (self.points[i - 1], self.points[i]);
+        Some(v0 + (v1 - v0) * (t - s0) / (s1 - s0))
+    }
 
-/// Noise configuration
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct NoiseConfig {
-    pub sigma: f32,    // Noise standard deviation
+    /// Parameter value for a series value of `value`, given the configured `base`
+    pub fn effective(&self, base: f32, value: f32) -> f32 {
+        match self.mode {
+            ForcingMode::Scale => base * value,
+            ForcingMode::Set => value,
+        }
+    }
 }
 
-/// Obstacle configuration
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct ObstacleConfig {
-    pub enabled: bool,
+/// Step column used when a forcing entry omits `step_column`
+#[cfg(feature = "serde")]
+fn default_step_column() -> String {
+    "step".to_string()
 }
 
 /// Complete simulation configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct SimulationConfig {
     pub world: WorldConfig,
     pub field: FieldConfig,
@@ -73,6 +1396,315 @@ pub struct SimulationConfig {
     pub agents: AgentConfig,
     pub noise: NoiseConfig,
     pub obstacles: ObstacleConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub terrain: TerrainConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub diffusion: DiffusionConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blooms: BloomConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rain: RainConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub soil: SoilConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cohorts: Vec<CohortEvent>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tracks: TrackConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stations: Vec<StationConfig>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub streaming: StreamingConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub output: OutputConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gpu: GpuConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub guards: GuardConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub render: RenderConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub demography: DemographyConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refuges: RefugeConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub evolution: EvolutionConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub steering: SteeringConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub separation: SeparationConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub schedules: Vec<ParamSchedule>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub perturbation: PerturbationConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub forcing: Vec<ForcingConfig>,
+}
+
+/// Smallest world edge in cells (agents spawn 30 cells away from the border)
+pub const MIN_WORLD_SIZE: u32 = 64;
+
+/// Largest world edge in cells (wgpu's default `max_texture_dimension_2d`)
+pub const MAX_WORLD_SIZE: u32 = 8192;
+
+/// Largest world area in cells: the 12-byte-per-cell movement grid must fit
+/// wgpu's default 128 MiB storage binding
+pub const MAX_WORLD_CELLS: u32 = (128 << 20) / 12;
+
+/// Largest total agent count (plants, herbivores and predators): one
+/// 64-wide workgroup per 64 agents within the 65535 dispatch limit
+pub const MAX_AGENTS: u32 = 65_535 * 64;
+
+/// Most age classes in `ages.csv` and the viewer's age chart
+pub const MAX_AGE_CLASSES: u32 = 256;
+
+/// Most quadrats per axis for beta diversity: at least one cell each in the smallest world
+pub const MAX_QUADRATS: u32 = 64;
+
+/// Most rewind slots: all of them share one texture array, and wgpu's default
+/// limit is 256 array layers
+pub const MAX_REWIND_SLOTS: u32 = 256 / FieldChannel::LAYERS;
+
+/// Largest finite value a field channel can hold (RGBA16F storage)
+pub const MAX_FIELD_VALUE: f32 = 65504.0;
+
+/// A `SimulationConfig` value rejected by `SimulationConfig::validate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub field: String,  // Dotted path of the offending value, e.g. `world.dt`
+    pub reason: String,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid `{}`: {}", self.field, self.reason)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+mod validate;
+
+impl SimulationConfig {
+    /// Mutable access to a scalar parameter by its dotted path (see `SCHEDULABLE_PARAMS`)
+    pub fn scalar_mut(&mut self, path: &str) -> Option<&mut f32> {
+        let (f, c) = (&mut self.field, &mut self.chemotaxis);
+        Some(match path {
+            "field.D_R" => &mut f.D_R,
+            "field.D_W" => &mut f.D_W,
+            "field.sigma_R" => &mut f.sigma_R,
+            "field.K_R" => &mut f.K_R,
+            "field.alpha_H" => &mut f.alpha_H,
+            "field.beta_H" => &mut f.beta_H,
+            "field.lambda_R" => &mut f.lambda_R,
+            "field.lambda_W" => &mut f.lambda_W,
+            "chemotaxis.chi_R" => &mut c.chi_R,
+            "chemotaxis.chi_W" => &mut c.chi_W,
+            "chemotaxis.kappa" => &mut c.kappa,
+            "chemotaxis.gamma" => &mut c.gamma,
+            "chemotaxis.v_max" => &mut c.v_max,
+            "chemotaxis.eps0" => &mut c.eps0,
+            "chemotaxis.eta_R" => &mut c.eta_R,
+            "chemotaxis.memory_weight" => &mut c.memory_weight,
+            "chemotaxis.memory_decay" => &mut c.memory_decay,
+            "chemotaxis.disperse_energy" => &mut c.disperse_energy,
+            "chemotaxis.settle_resource" => &mut c.settle_resource,
+            "chemotaxis.disperse_speed" => &mut c.disperse_speed,
+            _ => return None,
+        })
+    }
+
+    /// Set a scalar parameter by its dotted path, returning the value it replaces
+    pub fn set_scalar(&mut self, path: &str, value: f32) -> Result<f32, String> {
+        let slot = self.scalar_mut(path).ok_or_else(|| format!("unknown parameter `{}`", path))?;
+        Ok(std::mem::replace(slot, value))
+    }
+
+    /// Set every scheduled parameter to its value during `step`
+    ///
+    /// Returns false (and changes nothing) when there are no schedules, so
+    /// callers can skip rebuilding the uniforms.
+    pub fn apply_schedules(&mut self, step: u32) -> bool {
+        let schedules = std::mem::take(&mut self.schedules);
+        for s in &schedules {
+            if let Some(slot) = self.scalar_mut(&s.param) {
+                *slot = s.value_at(step);
+            }
+        }
+        self.schedules = schedules;
+        !self.schedules.is_empty()
+    }
+}
+
+/// Fraction of `SimulationConfig::dt_limit` a clamped `world.dt` is set to
+pub const DT_SAFETY: f32 = 0.9;
+
+/// Largest `world.dt` at which the explicit updates stay stable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DtLimit {
+    /// Diffusion: `D × dt × 2(Dxx + Dyy + |Dxy|) ≤ 1` for the fastest field and
+    /// the widest tensor, so no cell gives away more than it holds
+    pub diffusion: f32,
+    /// Advection: the fastest agent (dispersing at noon) moves at most one cell per step
+    pub advection: f32,
+}
+
+impl DtLimit {
+    pub fn max_dt(&self) -> f32 {
+        self.diffusion.min(self.advection)
+    }
+
+    /// Which of the two limits `max_dt` comes from
+    pub fn bound_by(&self) -> &'static str {
+        if self.diffusion <= self.advection { "diffusion" } else { "advection" }
+    }
+}
+
+impl std::fmt::Display for DtLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.4} (diffusion {:.4}, advection {:.4})", self.max_dt(), self.diffusion, self.advection)
+    }
+}
+
+impl SimulationConfig {
+    /// Stability limit of `world.dt` for the current field and chemotaxis parameters
+    ///
+    /// Either part is infinite when nothing diffuses or nothing moves.
+    /// Terrain and obstacles only close faces, so they never lower the limit.
+    pub fn dt_limit(&self) -> DtLimit {
+        let d = &self.diffusion;
+        let spread = if d.enabled {
+            let [dxx, dyy, dxy] = d.tensor;
+            d.streams.iter().map(DiffusionStream::spread).fold(dxx + dyy + dxy.abs(), f32::max)
+        } else {
+            2.0
+        };
+        let rates = self.field.rates();
+        let diffusivity = rates.D_R.0.max(rates.D_W.0) * spread;
+
+        let c = &self.chemotaxis;
+        let boost = if c.disperse_energy > 0.0 { c.disperse_speed.max(1.0) } else { 1.0 };
+        let speed = c.rates().v_max.0 * boost;
+
+        DtLimit {
+            diffusion: if diffusivity > 0.0 { 0.5 / diffusivity } else { f32::INFINITY },
+            advection: if speed > 0.0 { 1.0 / speed } else { f32::INFINITY },
+        }
+    }
+
+    /// Apply `world.dt_policy` when `world.dt` is above `dt_limit`
+    ///
+    /// `Clamp` lowers `world.dt` to `DT_SAFETY` times the limit and returns the
+    /// original value for the caller to warn about; `Refuse` fails on `world.dt`.
+    pub fn enforce_dt_limit(&mut self) -> Result<Option<f32>, ConfigError> {
+        let limit = self.dt_limit();
+        let dt = self.world.dt;
+        if dt <= limit.max_dt() {
+            return Ok(None);
+        }
+        match self.world.dt_policy {
+            DtPolicy::Clamp => {
+                self.world.dt = limit.max_dt() * DT_SAFETY;
+                Ok(Some(dt))
+            }
+            DtPolicy::Refuse => Err(ConfigError {
+                field: "world.dt".to_string(),
+                reason: format!("{} exceeds the stability limit {}; lower it or set `world.dt_policy: clamp`", dt, limit),
+            }),
+            DtPolicy::Ignore => Ok(None),
+        }
+    }
+}
+
+pub use units::{Diffusivity, PerArea, PerStep, PerTime, Speed};
+
+/// Units of measure for the rate parameters
+///
+/// Config values are plain numbers, but the shaders treat them differently:
+/// most rates are per time unit and multiplied by `world.dt` every step, while
+/// velocity damping is a fraction removed once per step. These newtypes carry
+/// that distinction to the places where rates are converted or combined, and
+/// the `RDParams`/`AgentParams` constructors only accept the unit the shader
+/// expects.
+pub mod units {
+    /// Rate per unit of simulated time; applied as `rate × dt` each step
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct PerTime(pub f32);
+
+    /// Fraction applied once per step, whatever the step length
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct PerStep(pub f32);
+
+    /// Amount per unit cell area (one cell is one unit of area)
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct PerArea(pub f32);
+
+    /// Cells travelled per unit of simulated time
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct Speed(pub f32);
+
+    /// Diffusion coefficient in cells² per unit of simulated time
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct Diffusivity(pub f32);
+
+    impl PerTime {
+        /// Fraction applied in one step of `dt` by the shaders' explicit Euler update (`rate × dt`)
+        pub fn per_step(self, dt: f32) -> PerStep {
+            PerStep(self.0 * dt)
+        }
+    }
+
+    impl PerStep {
+        /// Rate per time unit that applies this fraction in a step of `dt`
+        pub fn per_time(self, dt: f32) -> PerTime {
+            PerTime(self.0 / dt)
+        }
+    }
+
+    impl Speed {
+        /// Cells travelled in a step of `dt`
+        pub fn per_step(self, dt: f32) -> f32 {
+            self.0 * dt
+        }
+    }
+
+    impl Diffusivity {
+        /// `D × dt` for unit cells; the explicit 5-point Laplacian is stable up to 0.25
+        pub fn per_step(self, dt: f32) -> f32 {
+            self.0 * dt
+        }
+    }
+}
+
+/// Reaction-diffusion rates in their units (see `FieldConfig::rates`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldRates {
+    pub D_R: Diffusivity,
+    pub D_W: Diffusivity,
+    pub sigma_R: PerTime,
+    pub K_R: PerArea,
+    pub alpha_H: PerTime,
+    pub beta_H: PerTime,
+    pub lambda_R: PerTime,
+    pub lambda_W: PerTime,
+}
+
+/// Chemotaxis rates in their units (see `ChemotaxisConfig::rates`)
+///
+/// `chi_*`, `kappa` and `memory_weight` are gains on saturated directions and stay plain numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChemotaxisRates {
+    pub chi_R: f32,
+    pub chi_W: f32,
+    pub kappa: f32,
+    pub gamma: PerStep,
+    pub v_max: Speed,
+    pub eps0: PerTime,
+    pub eta_R: PerTime,
+    pub memory_weight: f32,
+    pub memory_decay: PerTime,
+    pub disperse_energy: f32,
+    pub settle_resource: f32,
+    pub disperse_speed: f32,
 }
 
 /// GPU-compatible parameters for reaction-diffusion shader
@@ -89,7 +1721,267 @@ pub struct RDParams {
     pub dt: f32,
     pub size: [u32; 2],
     pub H_SCALE: f32,  // Herbivore density scale factor
-    pub _pad: u32,     // Padding for alignment
+    pub K_R: f32,      // Resource carrying capacity per cell
+    pub obstacles: u32, // 1 = close cell faces by the obstacle distance field
+    pub anisotropy: f32, // Cross-slope diffusion damping from the elevation field (0 = isotropic)
+    pub soil_threshold: f32,   // Uptake rate the soil tolerates (soil.threshold)
+    pub soil_degradation: f32, // Damage per unit of excess uptake (0 = soil never degrades)
+    pub soil_recovery: f32,    // Fraction of the damage healed per time unit
+    pub tensor: u32,           // 1 = scale diffusion by the per-cell tensor (diffusion.enabled)
+    pub noise: f32,            // Standard deviation of R added per √time unit (noise.sigma)
+    pub seed: u32,             // Low 32 bits of world.seed, hashed with the step and cell for the noise
+}
+
+/// Maximum number of simultaneously active blooms
+pub const MAX_BLOOMS: usize = 8;
+
+/// Maximum number of simultaneously active rain cells
+pub const MAX_RAIN_CELLS: usize = 8;
+
+/// GPU-compatible parameters for the bloom injection shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct BloomParams {
+    pub size: [u32; 2],
+    pub count: u32,      // Active entries in `blooms`
+    pub rain_count: u32, // Active entries in `rain`
+    pub blooms: [[f32; 4]; MAX_BLOOMS], // (center x, center y, radius, amplitude)
+    pub rain: [[f32; 4]; MAX_RAIN_CELLS], // (center x, center y, standard deviation, amplitude) this step
+}
+
+/// GPU-compatible parameters for the movement metrics shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct MovementParams {
+    pub size: [u32; 2],
+    pub agent_groups: u32, // Workgroups of the per-agent pass (offset of the per-cell partials)
+    pub _pad: u32,         // Padding for alignment
+}
+
+/// GPU-compatible simulation clock, rewritten before every step
+///
+/// Bound next to the RD and agent parameters so shaders can follow the
+/// day/night cycle without rebuilding the parameter uniforms.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct TimeUniform {
+    pub step: u32,
+    pub time: f32,      // step * dt
+    pub day_phase: f32, // 0 = midnight, 0.5 = noon
+    pub light: f32,     // Growth and speed multiplier in [night_light, 1]
+}
+
+impl TimeUniform {
+    pub fn new(world: &WorldConfig, step: u32) -> Self {
+        Self {
+            step,
+            time: step as f32 * world.dt,
+            day_phase: world.day_phase(step),
+            light: world.light(step),
+        }
+    }
+}
+
+/// GPU-compatible parameters for the utility map shader (perceived energy landscape)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct UtilityParams {
+    pub chi_R: f32,
+    pub chi_W: f32,
+    pub kappa: f32,     // Saturation S(c) = c / (1 + kappa * c)
+    pub _pad0: u32,     // Padding for alignment
+    pub size: [u32; 2],
+    pub _pad1: [u32; 2], // Pad to a multiple of 16 bytes for the uniform
+}
+
+impl From<&AgentParams> for UtilityParams {
+    /// Take the chemotaxis gains of the live agent uniform
+    fn from(params: &AgentParams) -> Self {
+        Self {
+            chi_R: params.chi_R,
+            chi_W: params.chi_W,
+            kappa: params.kappa,
+            _pad0: 0,
+            size: [params.size[0] as u32, params.size[1] as u32],
+            _pad1: [0; 2],
+        }
+    }
+}
+
+/// GPU-compatible parameters of one level of the resource mip chain
+///
+/// One entry per level, bound at a dynamic offset; level 0 copies R out of
+/// the field texture, every later level box-averages 2×2 cells of the one before.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct MipLevelParams {
+    pub src_size: [u32; 2],
+    pub dst_size: [u32; 2],
+    pub src_offset: u32, // First cell of the source level in the chain buffer
+    pub dst_offset: u32, // First cell of the level being written
+    pub _pad: [u32; 2],  // Pad to a multiple of 16 bytes for the uniform
+}
+
+/// GPU-compatible parameters for the cell gather shader (sampled metrics)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct CellGatherParams {
+    pub size: [u32; 2], // Field size in cells
+    pub count: u32,     // Cells to read this dispatch
+    pub _pad: u32,      // Pad to a multiple of 16 bytes for the uniform
+}
+
+/// GPU-compatible parameters for the field image export shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct FieldImageParams {
+    pub size: [u32; 2], // Field size in cells
+    pub kind: u32,      // 0 = field (R, W, soil as red, green, blue), 1 = occupancy (grey)
+    pub _pad: u32,      // Pad to a multiple of 16 bytes for the uniform
+}
+
+/// GPU-compatible parameters for the region cull shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct CullParams {
+    pub shape: u32,      // 0 = rectangle [a, b], 1 = circle centred at a with radius b[0]
+    pub _pad0: u32,      // Padding for alignment
+    pub a: [f32; 2],
+    pub b: [f32; 2],
+    pub _pad1: [f32; 2], // Padding for alignment
+}
+
+/// GPU-compatible parameters for the obstacle brush shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct ObstacleBrushParams {
+    pub center: [f32; 2], // Disk centre in cells
+    pub radius: f32,
+    pub erase: u32,       // 0 = add the disk to the walls, 1 = cut it out
+}
+
+/// GPU-compatible parameters for one digit pass of the radix sort shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct RadixSortParams {
+    pub len: u32,    // Elements to sort
+    pub shift: u32,  // Bit offset of this pass's 8-bit digit
+    pub blocks: u32, // Blocks of 1024 elements (workgroups of the histogram/scatter passes)
+    pub _pad: u32,   // Padding for alignment
+}
+
+/// GPU-compatible parameters for the demography shader (one step's draws)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct DemographyParams {
+    pub death: [f32; 4], // Per-step death probability by kind (index 3 unused)
+    pub birth: [f32; 4], // Per-step birth probability by kind (index 3 unused)
+    pub seed: u32,       // Low 32 bits of world.seed
+    pub step: u32,       // Step being drawn for
+    pub size: [u32; 2],  // World size in cells, for the event counters and refuge mask (slot counts come from AgentRange)
+    pub refuges: u32,    // 1 = scale death probabilities by the per-cell refuge mask (refuges.enabled)
+    pub _pad: [u32; 3],  // Pad to a multiple of 16 bytes for the uniform
+    pub chi_mutation: [f32; 4],   // Log-normal step on a newborn's chi_R multiplier by kind (0 without evolution)
+    pub kappa_mutation: [f32; 4], // Log-normal step on a newborn's kappa multiplier by kind
+}
+
+impl DemographyParams {
+    pub fn new(config: &DemographyConfig, evolution: &EvolutionConfig, refuges: &RefugeConfig, seed: u64, size: [u32; 2]) -> Self {
+        let rates = config.by_kind();
+        let mutation = evolution.by_kind().map(|m| if evolution.enabled { m } else { SpeciesMutation::default() });
+        Self {
+            death: [rates[0].death, rates[1].death, rates[2].death, 0.0],
+            birth: [rates[0].birth, rates[1].birth, rates[2].birth, 0.0],
+            chi_mutation: [mutation[0].chi, mutation[1].chi, mutation[2].chi, 0.0],
+            kappa_mutation: [mutation[0].kappa, mutation[1].kappa, mutation[2].kappa, 0.0],
+            seed: seed as u32,
+            step: 0,
+            size,
+            refuges: refuges.enabled as u32,
+            _pad: [0; 3],
+        }
+    }
+}
+
+/// Largest `separation.radius`, keeping the neighbour search within 9×9 cells
+pub const MAX_SEPARATION_RADIUS: f32 = 2.0;
+
+/// GPU-compatible parameters for the separation shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Pod, Zeroable)]
+pub struct SeparationParams {
+    pub size: [u32; 2],      // World size in cells (the hash has one bucket per cell)
+    pub slots: u32,          // Agent slots hashed and pushed
+    pub radius: f32,
+    pub stiffness: f32,
+    pub cross_species: u32,  // 1 = agents of different species separate too
+    pub max_neighbours: u32,
+    pub _pad: u32,           // Pad to a multiple of 16 bytes for the uniform
+}
+
+impl SeparationParams {
+    pub fn new(config: &SeparationConfig, size: [u32; 2], slots: u32) -> Self {
+        Self {
+            size,
+            slots,
+            radius: config.radius,
+            stiffness: config.stiffness,
+            cross_species: config.cross_species as u32,
+            max_neighbours: config.max_neighbours,
+            _pad: 0,
+        }
+    }
+}
+
+/// GPU-compatible steering weights of the agent pass, indexed by kind
+///
+/// Also the uniform of the neighbour grid deposit, which needs the world size.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct SteeringParams {
+    pub chemotaxis: [f32; 4], // Per-kind weights (index 3 unused)
+    pub noise: [f32; 4],
+    pub crowding: [f32; 4],
+    pub flocking: [f32; 4],
+    pub memory: [f32; 4],
+    pub obstacles: [f32; 4],
+    pub attraction: [[f32; 4]; 3], // [j][k]: weight of kind k on the density gradient of kind j
+    pub size: [u32; 2],       // World size in cells (neighbour grid dimensions)
+    pub seed: u32,            // Low 32 bits of world.seed (noise draws)
+    pub neighbours: u32,      // 1 when some species crowds, flocks or follows a density (the grid is filled each step)
+}
+
+impl SteeringParams {
+    pub fn new(config: &SimulationConfig) -> Self {
+        let weights = config.steering.by_kind();
+        let by_kind = |pick: fn(&SteeringWeights) -> f32| [pick(&weights[0]), pick(&weights[1]), pick(&weights[2]), 0.0];
+        Self {
+            chemotaxis: by_kind(|w| w.chemotaxis),
+            noise: by_kind(|w| w.noise),
+            crowding: by_kind(|w| w.crowding),
+            flocking: by_kind(|w| w.flocking),
+            memory: by_kind(|w| w.memory),
+            obstacles: by_kind(|w| w.obstacles),
+            attraction: [
+                by_kind(|w| w.attraction.plants),
+                by_kind(|w| w.attraction.herbivores),
+                by_kind(|w| w.attraction.predators),
+            ],
+            size: config.world.size,
+            seed: config.world.seed as u32,
+            neighbours: config.steering.needs_neighbours() as u32,
+        }
+    }
+}
+
+/// GPU-compatible slot range of one species in the agent buffer, bound with a
+/// dynamic offset for each per-species dispatch
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct AgentRange {
+    pub offset: u32,   // First slot of the species
+    pub count: u32,    // Slots of the species (threads dispatched)
+    pub _pad: [u32; 2], // Pad to 16 bytes for the uniform
 }
 
 /// GPU-compatible parameters for agent chemotaxis shader
@@ -105,82 +1997,139 @@ pub struct AgentParams {
     pub eta_R: f32,
     pub dt: f32,
     pub size: [f32; 2],
-    pub _pad: [f32; 2], // Padding for alignment
-}
-
-impl Default for SimulationConfig {
-    fn default() -> Self {
-        Self {
-            world: WorldConfig {
-                size: [128, 128],
-                steps: 2000,
-                dt: 0.1,
-                seed: 1337,
-            },
-            field: FieldConfig {
-                D_R: 0.5,
-                D_W: 0.2,
-                sigma_R: 0.005,
-                alpha_H: 0.1,
-                beta_H: 0.05,
-                lambda_R: 0.005,
-                lambda_W: 0.005,
-            },
-            chemotaxis: ChemotaxisConfig {
-                chi_R: 8.0,
-                chi_W: 4.0,
-                kappa: 2.0,
-                gamma: 0.05,
-                v_max: 2.0,
-                eps0: 0.02,
-                eta_R: 0.2,
-            },
-            agents: AgentConfig {
-                herbivores: 2000,
-                E0: 1.0,
-            },
-            noise: NoiseConfig {
-                sigma: 0.0,
-            },
-            obstacles: ObstacleConfig {
-                enabled: false,
-            },
+    pub sampling: u32,  // FieldSampling: 0 = nearest, 1 = bicubic
+    pub gradient: u32,  // GradientStencil: 0 = central, 1 = forward, 2 = Sobel, 3 = Scharr
+    pub memory_weight: f32, // Homing gain toward the remembered site (0 = memoryless)
+    pub memory_decay: f32,  // Remembered value decay per time unit
+    pub avoid_distance: f32, // Wall steering range in cells (0 = no obstacles)
+    pub avoid_strength: f32, // Wall repulsion at the surface
+    pub climb_cost: f32,     // Energy per unit of elevation climbed (0 = flat terrain)
+    pub descent_gain: f32,   // Energy per unit of elevation descended
+    pub disperse_energy: f32, // Energy below which foragers disperse (0 = never)
+    pub settle_resource: f32, // Local R at which dispersers resume foraging
+    pub disperse_speed: f32,  // v_max multiplier while dispersing
+    pub _pad: [u32; 3],      // Pad to a multiple of 16 bytes for the uniform
+}
+
+/// Names accepted by `SimulationConfig::preset`
+pub const PRESETS: [&str; 3] = ["default", "river", "rd-only"];
+
+impl SimulationConfig {
+    /// Starting point of a partial config with `preset: <name>` (`None` for a
+    /// name not in `PRESETS`)
+    ///
+    /// `default` is `SimulationConfig::default()` (the best-demo world),
+    /// `river` the 1024×128 strip of `lab/configs/river.yaml` at the same
+    /// herbivore density, and `rd-only` a pure reaction-diffusion world
+    /// without agents.
+    pub fn preset(name: &str) -> Option<Self> {
+        let mut config = Self::default();
+        match name {
+            "default" => {}
+            "river" => {
+                config.world.size = [1024, 128];
+                config.agents.herbivores = 16_000;
+            }
+            "rd-only" => config.agents.herbivores = 0,
+            _ => return None,
         }
+        Some(config)
     }
 }
 
-impl From<&SimulationConfig> for RDParams {
-    fn from(config: &SimulationConfig) -> Self {
+impl RDParams {
+    /// Uniform for `rates` advanced in steps of `dt` on a `size` grid (rd_step.wgsl scales every rate by `dt`)
+    pub fn new(rates: FieldRates, dt: f32, size: [u32; 2]) -> Self {
         Self {
-            D_R: config.field.D_R,
-            D_W: config.field.D_W,
-            sigma_R: config.field.sigma_R,
-            alpha_H: config.field.alpha_H,
-            beta_H: config.field.beta_H,
-            lambda_R: config.field.lambda_R,
-            lambda_W: config.field.lambda_W,
-            dt: config.world.dt,
-            size: config.world.size,
+            D_R: rates.D_R.0,
+            D_W: rates.D_W.0,
+            sigma_R: rates.sigma_R.0,
+            alpha_H: rates.alpha_H.0,
+            beta_H: rates.beta_H.0,
+            lambda_R: rates.lambda_R.0,
+            lambda_W: rates.lambda_W.0,
+            dt,
+            size,
             H_SCALE: bindings::H_SCALE, // Use constant from bindings module
-            _pad: 0,
+            K_R: rates.K_R.0,
+            obstacles: 0,
+            anisotropy: 0.0,
+            soil_threshold: 0.0,
+            soil_degradation: 0.0,
+            soil_recovery: 0.0,
+            tensor: 0,
+            noise: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+impl AgentParams {
+    /// Uniform for `rates` advanced in steps of `dt` (agent_step.wgsl applies `gamma` once per step, the rest × `dt`)
+    pub fn new(rates: ChemotaxisRates, sampling: FieldSampling, gradient: GradientStencil, dt: f32, size: [u32; 2]) -> Self {
+        Self {
+            chi_R: rates.chi_R,
+            chi_W: rates.chi_W,
+            kappa: rates.kappa,
+            gamma: rates.gamma.0,
+            v_max: rates.v_max.0,
+            eps0: rates.eps0.0,
+            eta_R: rates.eta_R.0,
+            dt,
+            size: [size[0] as f32, size[1] as f32],
+            sampling: sampling as u32,
+            gradient: gradient as u32,
+            memory_weight: rates.memory_weight,
+            memory_decay: rates.memory_decay.0,
+            avoid_distance: 0.0,
+            avoid_strength: 0.0,
+            climb_cost: 0.0,
+            descent_gain: 0.0,
+            disperse_energy: rates.disperse_energy,
+            settle_resource: rates.settle_resource,
+            disperse_speed: rates.disperse_speed,
+            _pad: [0; 3],
+        }
+    }
+}
+
+impl From<&SimulationConfig> for RDParams {
+    fn from(config: &SimulationConfig) -> Self {
+        let (t, so) = (&config.terrain, &config.soil);
+        let params = Self {
+            obstacles: config.obstacles.enabled as u32,
+            anisotropy: if t.enabled { t.anisotropy } else { 0.0 },
+            tensor: config.diffusion.enabled as u32,
+            noise: config.noise.sigma,
+            seed: config.world.seed as u32,
+            ..Self::new(config.field.rates(), config.world.dt, config.world.size)
+        };
+        if !so.enabled {
+            return params;
         }
+        Self { soil_threshold: so.threshold, soil_degradation: so.degradation, soil_recovery: so.recovery, ..params }
     }
 }
 
 impl From<&SimulationConfig> for AgentParams {
     fn from(config: &SimulationConfig) -> Self {
-        Self {
-            chi_R: config.chemotaxis.chi_R,
-            chi_W: config.chemotaxis.chi_W,
-            kappa: config.chemotaxis.kappa,
-            gamma: config.chemotaxis.gamma,
-            v_max: config.chemotaxis.v_max,
-            eps0: config.chemotaxis.eps0,
-            eta_R: config.chemotaxis.eta_R,
-            dt: config.world.dt,
-            size: [config.world.size[0] as f32, config.world.size[1] as f32],
-            _pad: [0.0, 0.0],
+        let params = Self::new(
+            config.chemotaxis.rates(),
+            config.chemotaxis.sampling,
+            config.chemotaxis.gradient,
+            config.world.dt,
+            config.world.size,
+        );
+        let (o, t) = (&config.obstacles, &config.terrain);
+        let params = if o.enabled {
+            Self { avoid_distance: o.avoid_distance, avoid_strength: o.avoid_strength, ..params }
+        } else {
+            params
+        };
+        if !t.enabled {
+            return params;
         }
+        Self { climb_cost: t.climb_cost, descent_gain: t.descent_gain, ..params }
     }
 }
 
@@ -194,22 +2143,143 @@ pub mod bindings {
     /// Reaction-Diffusion compute shader bindings (group 0)
     /// 
     /// ```wgsl
-    /// @group(0) @binding(0) var srcTex: texture_2d<f32>;
-    /// @group(0) @binding(1) var dstTex: texture_storage_2d<rgba16float, write>;
+    /// @group(0) @binding(0) var srcTex: FieldTex;     // texture_2d<f32>, or _array past 4 channels
+    /// @group(0) @binding(1) var dstTex: FieldStorage; // texture_storage_2d<rgba16float, write>, or _array
     /// @group(0) @binding(2) var<uniform> params: RDParams;
     /// @group(0) @binding(3) var<storage, read> herbDensity: array<u32>;
+    /// @group(0) @binding(4) var<storage, read> sigmaBoost: array<f32>;
+    /// @group(0) @binding(5) var obstacleSdf: texture_2d<f32>;
+    /// @group(0) @binding(6) var elevation: texture_2d<f32>;
+    /// @group(0) @binding(7) var<uniform> time: TimeUniform;
+    /// @group(0) @binding(8) var diffusion: texture_2d<f32>;
+    /// ```
+    pub const RD_BINDINGS: &str = "RD Group 0: srcTex(FieldTex), dstTex(FieldStorage write), RDParams(uniform), OccBuf(storage r32uint), SigmaBoost(storage f32), ObstacleSdf(texture r32float), Elevation(texture r32float), TimeUniform(uniform), DiffusionTensor(texture rgba32float)";
+    
+    /// Bloom injection compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var<uniform> params: BloomParams;
+    /// @group(0) @binding(1) var<storage, read_write> sigmaBoost: array<f32>;
+    /// ```
+    pub const BLOOM_BINDINGS: &str = "Bloom Group 0: BloomParams(uniform), SigmaBoost(storage f32)";
+    
+    /// Movement metrics compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var<storage, read> agents: array<Agent>;
+    /// @group(0) @binding(1) var fieldTex: FieldTex;
+    /// @group(0) @binding(2) var<uniform> params: MovementParams;
+    /// @group(0) @binding(3) var<storage, read_write> velGrid: array<atomic<i32>>;
+    /// @group(0) @binding(4) var<storage, read_write> partials: array<vec4<f32>>;
+    /// ```
+    pub const MOVEMENT_BINDINGS: &str = "Movement Group 0: Agents SSBO (read), FieldTex(FieldTex), MovementParams(uniform), VelGrid(storage atomic i32), Partials(storage vec4f)";
+    
+    /// Utility map compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var fieldTex: FieldTex;
+    /// @group(0) @binding(1) var<uniform> params: UtilityParams;
+    /// @group(0) @binding(2) var<storage, read_write> utility: array<f32>;
+    /// ```
+    pub const UTILITY_BINDINGS: &str = "Utility Group 0: FieldTex(FieldTex), UtilityParams(uniform), Utility(storage f32)";
+    
+    /// Resource mip chain compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var fieldTex: FieldTex;
+    /// @group(0) @binding(1) var<storage, read_write> levels: array<f32>;
+    /// @group(0) @binding(2) var<uniform> params: MipLevelParams; // dynamic offset per level
+    /// ```
+    pub const RESOURCE_MIP_BINDINGS: &str = "Resource Mip Group 0: FieldTex(FieldTex), Levels(storage f32), MipLevelParams(uniform dynamic)";
+    
+    /// Field image export compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var fieldTex: FieldTex;
+    /// @group(0) @binding(1) var<storage, read> occupancy: array<u32>;
+    /// @group(0) @binding(2) var<uniform> params: FieldImageParams;
+    /// @group(0) @binding(3) var<storage, read_write> bounds: array<atomic<u32>, 4>;
+    /// @group(0) @binding(4) var image: texture_storage_2d<rgba8unorm, write>;
+    /// ```
+    pub const FIELD_IMAGE_BINDINGS: &str = "Field Image Group 0: FieldTex(FieldTex), OccBuf(storage r32uint read), FieldImageParams(uniform), Bounds(storage atomic u32), Image(storage texture rgba8unorm write)";
+    
+    /// Region cull compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
+    /// @group(0) @binding(1) var<uniform> params: CullParams;
+    /// ```
+    pub const CULL_BINDINGS: &str = "Cull Group 0: Agents SSBO, CullParams(uniform)";
+    
+    /// Radix sort compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var<storage, read> keysIn: array<u32>;
+    /// @group(0) @binding(1) var<storage, read> valuesIn: array<u32>;
+    /// @group(0) @binding(2) var<storage, read_write> keysOut: array<u32>;
+    /// @group(0) @binding(3) var<storage, read_write> valuesOut: array<u32>;
+    /// @group(0) @binding(4) var<storage, read_write> hist: array<u32>;
+    /// @group(0) @binding(5) var<uniform> params: RadixSortParams;
+    /// ```
+    pub const RADIX_SORT_BINDINGS: &str = "Radix Sort Group 0: KeysIn(storage read), ValuesIn(storage read), KeysOut(storage), ValuesOut(storage), Hist(storage), RadixSortParams(uniform)";
+    
+    /// Demography compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
+    /// @group(0) @binding(1) var<storage, read> prev: array<Agent>;
+    /// @group(0) @binding(2) var<uniform> params: DemographyParams;
+    /// @group(0) @binding(3) var<uniform> range: AgentRange; // dynamic offset per species
+    /// @group(0) @binding(4) var<storage, read_write> events: array<atomic<u32>>;
+    /// @group(0) @binding(5) var<storage, read> refuge: array<f32>;
     /// ```
-    pub const RD_BINDINGS: &str = "RD Group 0: srcTex(sampler2D), dstTex(storage2D write), RDParams(uniform), OccBuf(storage r32uint)";
+    pub const DEMOGRAPHY_BINDINGS: &str = "Demography Group 0: Agents SSBO, PrevAgents(storage read), DemographyParams(uniform), AgentRange(uniform dynamic), EventCounters(storage atomic u32), RefugeMask(storage f32)";
     
     /// Agent chemotaxis compute shader bindings (group 0)
     /// 
     /// ```wgsl
     /// @group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
-    /// @group(0) @binding(1) var fieldTex: texture_2d<f32>;
+    /// @group(0) @binding(1) var fieldTex: FieldTex;
     /// @group(0) @binding(2) var<uniform> params: AgentParams;
-    /// @group(0) @binding(3) var<storage, read_write> herbOcc: array<u32>;
+    /// @group(0) @binding(3) var<storage, read_write> herbOcc: array<atomic<u32>>;
+    /// @group(0) @binding(4) var obstacleSdf: texture_2d<f32>;
+    /// @group(0) @binding(5) var<uniform> range: AgentRange; // dynamic offset per species
+    /// @group(0) @binding(6) var elevation: texture_2d<f32>;
+    /// @group(0) @binding(7) var<uniform> time: TimeUniform;
+    /// @group(0) @binding(8) var<uniform> steering: SteeringParams;
+    /// @group(0) @binding(9) var<storage, read> neighbours: array<i32>;
+    /// ```
+    pub const AGENT_BINDINGS: &str = "Agents Group 0: Agents SSBO, FieldTex(FieldTex), AgentParams(uniform), OccBuf(storage r32uint), ObstacleSdf(texture r32float), AgentRange(uniform dynamic), Elevation(texture r32float), TimeUniform(uniform), SteeringParams(uniform), Neighbours(storage i32 read)";
+
+    /// Neighbour grid deposit compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var<storage, read> agents: array<Agent>;
+    /// @group(0) @binding(1) var<storage, read_write> grid: array<atomic<i32>>;
+    /// @group(0) @binding(2) var<uniform> steering: SteeringParams;
+    /// ```
+    pub const NEIGHBOUR_BINDINGS: &str = "Neighbour Grid Group 0: Agents SSBO (read), Grid(storage atomic i32), SteeringParams(uniform)";
+    
+    /// Separation compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var<storage, read_write> agents: array<Agent>;
+    /// @group(0) @binding(1) var<uniform> params: SeparationParams;
+    /// @group(0) @binding(2) var<storage, read_write> keys: array<u32>;
+    /// @group(0) @binding(3) var<storage, read_write> slots: array<u32>;
+    /// @group(0) @binding(4) var<storage, read_write> cell_ranges: array<vec2<u32>>;
+    /// @group(0) @binding(5) var<storage, read_write> positions: array<vec4<f32>>;
+    /// ```
+    pub const SEPARATION_BINDINGS: &str = "Separation Group 0: Agents SSBO, SeparationParams(uniform), Keys(storage u32), Slots(storage u32), CellRanges(storage vec2u), Positions(storage vec4f)";
+    
+    /// Obstacle brush compute shader bindings (group 0)
+    /// 
+    /// ```wgsl
+    /// @group(0) @binding(0) var previous: texture_2d<f32>;
+    /// @group(0) @binding(1) var obstacleSdf: texture_storage_2d<r32float, write>;
+    /// @group(0) @binding(2) var<uniform> brush: ObstacleBrushParams;
     /// ```
-    pub const AGENT_BINDINGS: &str = "Agents Group 0: Agents SSBO, FieldTex(sampler2D), AgentParams(uniform), OccBuf(storage r32uint)";
+    pub const OBSTACLE_BRUSH_BINDINGS: &str = "Obstacle Brush Group 0: Previous SDF(texture), SDF(storage r32float write), ObstacleBrushParams(uniform)";
     
     /// H_SCALE constant value (must be identical in both simulations)
     pub const H_SCALE: f32 = 0.125; // 1/8 per agent per cell
@@ -237,6 +2307,16 @@ pub mod bindings {
     pub fn log_binding_layouts() {
         log::info!("RD Bindings: {}", RD_BINDINGS);
         log::info!("Agent Bindings: {}", AGENT_BINDINGS);
+        log::info!("Bloom Bindings: {}", BLOOM_BINDINGS);
+        log::info!("Movement Bindings: {}", MOVEMENT_BINDINGS);
+        log::info!("Utility Bindings: {}", UTILITY_BINDINGS);
+        log::info!("Field Image Bindings: {}", FIELD_IMAGE_BINDINGS);
+        log::info!("Cull Bindings: {}", CULL_BINDINGS);
+        log::info!("Radix Sort Bindings: {}", RADIX_SORT_BINDINGS);
+        log::info!("Neighbour Bindings: {}", NEIGHBOUR_BINDINGS);
+        log::info!("Separation Bindings: {}", SEPARATION_BINDINGS);
+        log::info!("Obstacle Brush Bindings: {}", OBSTACLE_BRUSH_BINDINGS);
         log::info!("H_SCALE: {}", H_SCALE);
+        log::info!("Field channels: {} in {} layer(s)", FieldChannel::COUNT, FieldChannel::LAYERS);
     }
 }
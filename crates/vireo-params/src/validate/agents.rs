@@ -0,0 +1,98 @@
+use crate::{
+    AgentConfig, ChemotaxisConfig, ConfigError, GrowthPolicy, SeparationConfig, SteeringConfig, MAX_AGENTS, MAX_SEPARATION_RADIUS, SPECIES,
+};
+use super::{fail, non_negative};
+
+impl ChemotaxisConfig {
+    /// Check the gradient response, memory and dispersal parameters
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        non_negative([
+            ("chemotaxis.kappa", self.kappa),
+            ("chemotaxis.gamma", self.gamma),
+            ("chemotaxis.v_max", self.v_max),
+            ("chemotaxis.eps0", self.eps0),
+            ("chemotaxis.eta_R", self.eta_R),
+            ("chemotaxis.memory_weight", self.memory_weight),
+            ("chemotaxis.memory_decay", self.memory_decay),
+            ("chemotaxis.disperse_energy", self.disperse_energy),
+            ("chemotaxis.settle_resource", self.settle_resource),
+            ("chemotaxis.disperse_speed", self.disperse_speed),
+        ])?;
+        for (field, value) in [("chemotaxis.chi_R", self.chi_R), ("chemotaxis.chi_W", self.chi_W)] {
+            if !value.is_finite() {
+                return fail(field, format!("must be finite, got {}", value));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AgentConfig {
+    /// Check the starting energy, agent counts against `MAX_AGENTS` and the growth policy
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        non_negative([("agents.E0", self.E0)])?;
+
+        // Spawning creates three agents per configured herbivore
+        if self.herbivores as u64 * 3 > MAX_AGENTS as u64 {
+            return fail("agents.herbivores", format!(
+                "{} herbivores spawn {} agents, above the {} agent limit",
+                self.herbivores, self.herbivores as u64 * 3, MAX_AGENTS,
+            ));
+        }
+        if self.total_slots() > MAX_AGENTS as u64 {
+            return fail("agents.capacity", format!(
+                "{:?} slots add up to {}, above the {} agent limit", self.slots(), self.total_slots(), MAX_AGENTS,
+            ));
+        }
+        let g = &self.growth;
+        if g.check_every == 0 {
+            return fail("agents.growth.check_every", "must be greater than 0".to_string());
+        }
+        if !(g.fill > 0.0 && g.fill <= 1.0) {
+            return fail("agents.growth.fill", format!("must be within (0, 1], got {}", g.fill));
+        }
+        if g.policy == GrowthPolicy::Grow {
+            for ((species, &ceiling), &slots) in SPECIES.iter().zip(&g.max_population.by_kind()).zip(&self.slots()) {
+                if ceiling != 0 && ceiling < slots {
+                    return fail("agents.growth.max_population", format!(
+                        "{} ceiling {} is below its {} starting slots", species, ceiling, slots,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SteeringConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (species, weights) in SPECIES.iter().zip(self.by_kind()) {
+            for (name, weight) in weights.named() {
+                if !weight.is_finite() {
+                    return fail(&format!("steering.{}.{}", species, name), format!("must be finite, got {}", weight));
+                }
+            }
+            for (followed, weight) in SPECIES.iter().zip(weights.attraction.by_kind()) {
+                if !weight.is_finite() {
+                    return fail(&format!("steering.{}.attraction.{}", species, followed), format!("must be finite, got {}", weight));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SeparationConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.radius > 0.0 && self.radius <= MAX_SEPARATION_RADIUS) {
+            return fail("separation.radius", format!("must be within (0, {}], got {}", MAX_SEPARATION_RADIUS, self.radius));
+        }
+        if !(self.stiffness > 0.0 && self.stiffness <= 1.0) {
+            return fail("separation.stiffness", format!("must be within (0, 1], got {}", self.stiffness));
+        }
+        if self.max_neighbours == 0 {
+            return fail("separation.max_neighbours", "must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
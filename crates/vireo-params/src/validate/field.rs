@@ -0,0 +1,110 @@
+use crate::{BloomConfig, ConfigError, DiffusionConfig, FieldConfig, NoiseConfig, RainConfig, SoilConfig, MAX_FIELD_VALUE};
+use super::{fail, non_negative};
+
+impl FieldConfig {
+    /// Check the reaction-diffusion rates and the carrying capacity
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        non_negative([
+            ("field.D_R", self.D_R),
+            ("field.D_W", self.D_W),
+            ("field.sigma_R", self.sigma_R),
+            ("field.K_R", self.K_R),
+            ("field.alpha_H", self.alpha_H),
+            ("field.beta_H", self.beta_H),
+            ("field.lambda_R", self.lambda_R),
+            ("field.lambda_W", self.lambda_W),
+        ])?;
+        if !(self.K_R > 0.0 && self.K_R <= MAX_FIELD_VALUE) {
+            return fail("field.K_R", format!("must be positive and at most {}, got {}", MAX_FIELD_VALUE, self.K_R));
+        }
+        Ok(())
+    }
+}
+
+impl NoiseConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        non_negative([("noise.sigma", self.sigma)])?;
+        if self.sigma > MAX_FIELD_VALUE {
+            return fail("noise.sigma", format!("{} exceeds the largest field value {}", self.sigma, MAX_FIELD_VALUE));
+        }
+        Ok(())
+    }
+}
+
+impl BloomConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !(0.0..=1.0).contains(&self.rate) {
+            return fail("blooms.rate", format!("must be a probability in [0, 1], got {}", self.rate));
+        }
+        if !(self.radius.is_finite() && self.radius > 0.0) {
+            return fail("blooms.radius", format!("must be a positive finite number, got {}", self.radius));
+        }
+        if !(self.amplitude.is_finite() && self.amplitude >= -1.0) {
+            return fail("blooms.amplitude", format!("must be finite and at least -1, got {}", self.amplitude));
+        }
+        Ok(())
+    }
+}
+
+impl RainConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !(0.0..=1.0).contains(&self.rate) {
+            return fail("rain.rate", format!("must be a probability in [0, 1], got {}", self.rate));
+        }
+        if !(self.radius.is_finite() && self.radius > 0.0) {
+            return fail("rain.radius", format!("must be a positive finite number, got {}", self.radius));
+        }
+        if !(self.amplitude.is_finite() && self.amplitude >= -1.0) {
+            return fail("rain.amplitude", format!("must be finite and at least -1, got {}", self.amplitude));
+        }
+        if !self.velocity.iter().all(|v| v.is_finite()) {
+            return fail("rain.velocity", format!("must be finite, got {:?}", self.velocity));
+        }
+        if !(self.velocity_jitter.is_finite() && self.velocity_jitter >= 0.0) {
+            return fail("rain.velocity_jitter", format!("must be non-negative and finite, got {}", self.velocity_jitter));
+        }
+        Ok(())
+    }
+}
+
+impl SoilConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        non_negative([
+            ("soil.threshold", self.threshold),
+            ("soil.degradation", self.degradation),
+            ("soil.recovery", self.recovery),
+        ])
+    }
+}
+
+impl DiffusionConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        // Positive definite, so nothing diffuses against its gradient
+        let [dxx, dyy, dxy] = self.tensor;
+        if !(self.tensor.iter().all(|v| v.is_finite()) && dxx > 0.0 && dyy > 0.0 && dxy * dxy < dxx * dyy) {
+            return fail("diffusion.tensor", format!("must have Dxx, Dyy > 0 and Dxy² < Dxx·Dyy, got {:?}", self.tensor));
+        }
+        for (i, stream) in self.streams.iter().enumerate() {
+            let valid = stream.from.iter().chain(&stream.to).chain(&[stream.width, stream.along, stream.across]).all(|v| v.is_finite())
+                && stream.width > 0.0 && stream.along > 0.0 && stream.across > 0.0 && stream.from != stream.to;
+            if !valid {
+                return fail(&format!("diffusion.streams[{}]", i), format!(
+                    "{:?} must be finite with distinct ends and a positive width, along and across", stream,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
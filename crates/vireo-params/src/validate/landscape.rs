@@ -0,0 +1,77 @@
+use crate::{ConfigError, ObstacleConfig, ObstacleShape, StreamingConfig, TerrainConfig, TerrainFeature};
+use super::{fail, non_negative};
+
+impl ObstacleConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !(self.avoid_distance.is_finite() && self.avoid_distance > 0.0) {
+            return fail("obstacles.avoid_distance", format!("must be a positive finite number, got {}", self.avoid_distance));
+        }
+        non_negative([("obstacles.avoid_strength", self.avoid_strength)])?;
+        for (i, shape) in self.shapes.iter().enumerate() {
+            let valid = match *shape {
+                ObstacleShape::Circle { center, radius } => {
+                    center.iter().all(|v| v.is_finite()) && radius.is_finite() && radius > 0.0
+                }
+                ObstacleShape::Rect { min, max } => {
+                    min.iter().chain(&max).all(|v| v.is_finite()) && min[0] < max[0] && min[1] < max[1]
+                }
+            };
+            if !valid {
+                return fail(&format!("obstacles.shapes[{}]", i), format!(
+                    "{:?} must be finite with a positive radius or min < max", shape,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TerrainConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        non_negative([("terrain.climb_cost", self.climb_cost)])?;
+        // A round trip must never pay out energy
+        if !(self.descent_gain.is_finite() && (0.0..=self.climb_cost).contains(&self.descent_gain)) {
+            return fail("terrain.descent_gain", format!("must be in [0, climb_cost = {}], got {}", self.climb_cost, self.descent_gain));
+        }
+        non_negative([("terrain.anisotropy", self.anisotropy)])?;
+        for (i, feature) in self.features.iter().enumerate() {
+            let valid = match *feature {
+                TerrainFeature::Crater { center, radius, depth, rim } => {
+                    center.iter().chain(&[depth, rim]).all(|v| v.is_finite()) && radius.is_finite() && radius > 0.0
+                }
+                TerrainFeature::Valley { from, to, width, depth } => {
+                    from.iter().chain(&to).chain(&[depth]).all(|v| v.is_finite()) && width.is_finite() && width > 0.0
+                }
+            };
+            if !valid {
+                return fail(&format!("terrain.features[{}]", i), format!(
+                    "{:?} must be finite with a positive radius or width", feature,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StreamingConfig {
+    /// Check the chunk size against the `size` world (the world scrolls a chunk at a time)
+    pub fn validate(&self, size: [u32; 2]) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let [w, h] = size;
+        if self.chunk < 8 || w % self.chunk != 0 || h % self.chunk != 0 {
+            return fail("streaming.chunk", format!("must be at least 8 and divide the {}x{} world, got {}", w, h, self.chunk));
+        }
+        if w / self.chunk < 3 || h / self.chunk < 3 {
+            return fail("streaming.chunk", format!("{} leaves fewer than 3x3 chunks in the {}x{} world", self.chunk, w, h));
+        }
+        Ok(())
+    }
+}
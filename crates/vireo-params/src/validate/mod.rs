@@ -0,0 +1,92 @@
+//! `SimulationConfig::validate` and the per-section checks it runs
+//!
+//! Every section checks its own values in `validate()`. Checks that span
+//! sections (a feature that needs another one, a value bounded by the world
+//! size) stay in `SimulationConfig::validate`, or take what they need as an
+//! argument.
+
+mod agents;
+mod field;
+mod landscape;
+mod output;
+mod population;
+mod render;
+mod schedules;
+mod world;
+
+use crate::{ConfigError, SimulationConfig};
+
+/// `ConfigError` for the value at the dotted path `field`
+fn fail(field: &str, reason: String) -> Result<(), ConfigError> {
+    Err(ConfigError { field: field.to_string(), reason })
+}
+
+/// Fail on the first value that is negative or not finite
+fn non_negative<'a>(values: impl IntoIterator<Item = (&'a str, f32)>) -> Result<(), ConfigError> {
+    for (field, value) in values {
+        if !(value.is_finite() && value >= 0.0) {
+            return fail(field, format!("must be a non-negative finite number, got {}", value));
+        }
+    }
+    Ok(())
+}
+
+impl SimulationConfig {
+    /// Check every value the seeding code and GPU buffer sizing rely on
+    ///
+    /// Returns the first problem found, so a config that passes can be handed
+    /// to `Simulation::new` without panicking in spawn ranges, allocation or
+    /// dispatch-size math.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.world.validate()?;
+        self.field.validate()?;
+        self.chemotaxis.validate()?;
+        self.agents.validate()?;
+        self.noise.validate()?;
+        self.blooms.validate()?;
+        self.rain.validate()?;
+        self.soil.validate()?;
+        population::validate_cohorts(&self.cohorts)?;
+        self.obstacles.validate()?;
+        self.terrain.validate()?;
+        self.diffusion.validate()?;
+        self.tracks.validate()?;
+        output::validate_stations(&self.stations, self.world.size)?;
+
+        self.demography.validate()?;
+        if self.refuges.enabled && !self.demography.enabled {
+            return fail("refuges.enabled", "refuges scale demography deaths; enable demography".to_string());
+        }
+        if self.evolution.enabled && !self.demography.enabled {
+            return fail("evolution.enabled", "traits mutate at demography births; enable demography".to_string());
+        }
+        self.evolution.validate()?;
+        self.refuges.validate()?;
+        self.steering.validate()?;
+        self.separation.validate()?;
+
+        self.validate_schedules()?;
+        self.validate_perturbation()?;
+        self.validate_forcing()?;
+
+        self.output.validate()?;
+        self.gpu.validate()?;
+        self.guards.validate()?;
+        self.render.validate()?;
+
+        self.streaming.validate(self.world.size)?;
+        if self.streaming.enabled {
+            if self.obstacles.enabled {
+                return fail("obstacles.enabled", "obstacles are fixed to the grid and cannot scroll with streaming".to_string());
+            }
+            if self.terrain.enabled {
+                return fail("terrain.enabled", "terrain is fixed to the grid and cannot scroll with streaming".to_string());
+            }
+            if self.diffusion.enabled {
+                return fail("diffusion.enabled", "the diffusion tensor is fixed to the grid and cannot scroll with streaming".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
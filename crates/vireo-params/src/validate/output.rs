@@ -0,0 +1,92 @@
+use crate::{ConfigError, GpuConfig, GuardConfig, OutputConfig, StationConfig, TrackConfig, MAX_AGE_CLASSES, MAX_QUADRATS, MAX_REWIND_SLOTS};
+use super::fail;
+
+impl OutputConfig {
+    /// Check the sampling intervals, output shapes and checkpoint tiling
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.checkpoints.every > 0 && self.checkpoints.tile == 0 {
+            return fail("output.checkpoints.tile", "must be greater than 0".to_string());
+        }
+        for (field, every) in [
+            ("output.metrics_every", self.metrics_every),
+            ("output.hud_every", self.hud_every),
+            ("output.debug_every", self.debug_every),
+        ] {
+            if every == 0 {
+                return fail(field, "must be greater than 0".to_string());
+            }
+        }
+        if self.age_bin == 0 {
+            return fail("output.age_bin", "must be greater than 0".to_string());
+        }
+        if !(1..=MAX_AGE_CLASSES).contains(&self.age_classes) {
+            return fail("output.age_classes", format!("must be within [1, {}], got {}", MAX_AGE_CLASSES, self.age_classes));
+        }
+        if !(1..=MAX_QUADRATS).contains(&self.quadrats) {
+            return fail("output.quadrats", format!("must be within [1, {}], got {}", MAX_QUADRATS, self.quadrats));
+        }
+        if self.sampling.max_cells < 4 {
+            return fail("output.sampling.max_cells", format!("must be at least 4, got {}", self.sampling.max_cells));
+        }
+        if !(self.sampling.tolerance.is_finite() && self.sampling.tolerance >= 0.0) {
+            return fail("output.sampling.tolerance", format!("must be non-negative, got {}", self.sampling.tolerance));
+        }
+        Ok(())
+    }
+}
+
+impl TrackConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.enabled && self.every == 0 {
+            return fail("tracks.every", "must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Check the sampling stations (`stations`): unique names, inside the `size` world
+pub(super) fn validate_stations(stations: &[StationConfig], size: [u32; 2]) -> Result<(), ConfigError> {
+    for (i, station) in stations.iter().enumerate() {
+        let field = |name: &str| format!("stations[{}].{}", i, name);
+        let name = &station.name;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return fail(&field("name"), format!("must be letters, digits, `-` and `_`, got `{}`", name));
+        }
+        if stations[..i].iter().any(|s| s.name == *name) {
+            return fail(&field("name"), format!("`{}` is already used by another station", name));
+        }
+        if !(0..2).all(|a| station.pos[a].is_finite() && station.pos[a] >= 0.0 && station.pos[a] < size[a] as f32) {
+            return fail(&field("pos"), format!("must lie within the {}x{} world, got {:?}", size[0], size[1], station.pos));
+        }
+        if !(station.radius.is_finite() && station.radius >= 0.0) {
+            return fail(&field("radius"), format!("must be a non-negative finite number, got {}", station.radius));
+        }
+        if station.every == 0 {
+            return fail(&field("every"), "must be greater than 0".to_string());
+        }
+    }
+    Ok(())
+}
+
+impl GpuConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.rewind_slots > MAX_REWIND_SLOTS {
+            return fail("gpu.rewind_slots", format!("must be at most {}, got {}", MAX_REWIND_SLOTS, self.rewind_slots));
+        }
+        if self.rewind_every == 0 {
+            return fail("gpu.rewind_every", "must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl GuardConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (field, value) in [("guards.max_mean_R", self.max_mean_R), ("guards.max_step_ms", self.max_step_ms)] {
+            if !(value.is_finite() && value >= 0.0) {
+                return fail(field, format!("must be non-negative (0 = off), got {}", value));
+            }
+        }
+        Ok(())
+    }
+}
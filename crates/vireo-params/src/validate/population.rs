@@ -0,0 +1,57 @@
+use crate::{CohortEvent, ConfigError, DemographyConfig, EvolutionConfig, RefugeConfig, MAX_MUTATION, SPECIES};
+use super::fail;
+
+/// Check the cohort tagging events (`cohorts`)
+pub(super) fn validate_cohorts(cohorts: &[CohortEvent]) -> Result<(), ConfigError> {
+    for (i, event) in cohorts.iter().enumerate() {
+        if event.id == 0 {
+            return fail(&format!("cohorts[{}].id", i), "0 is reserved for untagged agents".to_string());
+        }
+        if !(event.center.iter().all(|v| v.is_finite()) && event.radius.is_finite() && event.radius >= 0.0) {
+            return fail(&format!("cohorts[{}]", i), format!(
+                "center {:?} and radius {} must be finite (radius non-negative)", event.center, event.radius,
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl DemographyConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (species, rates) in SPECIES.iter().zip(self.by_kind()) {
+            for (name, p) in [("death", rates.death), ("birth", rates.birth)] {
+                if !(0.0..=1.0).contains(&p) {
+                    return fail(&format!("demography.{}.{}", species, name), format!("must be a probability in [0, 1], got {}", p));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EvolutionConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (species, mutation) in SPECIES.iter().zip(self.by_kind()) {
+            for (name, sd) in [("chi", mutation.chi), ("kappa", mutation.kappa)] {
+                if !(0.0..=MAX_MUTATION).contains(&sd) {
+                    return fail(&format!("evolution.{}.{}", species, name), format!("must be within [0, {}], got {}", MAX_MUTATION, sd));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RefugeConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (i, region) in self.regions.iter().enumerate() {
+            if region.polygon.len() < 3 || region.polygon.iter().flatten().any(|v| !v.is_finite()) {
+                return fail(&format!("refuges.regions[{}].polygon", i), format!("must have at least 3 finite vertices, got {:?}", region.polygon));
+            }
+            if !(0.0..=1.0).contains(&region.mortality) {
+                return fail(&format!("refuges.regions[{}].mortality", i), format!("must be within [0, 1], got {}", region.mortality));
+            }
+        }
+        Ok(())
+    }
+}
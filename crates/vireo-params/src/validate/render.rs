@@ -0,0 +1,30 @@
+use crate::{ConfigError, RenderConfig, MAX_KDE_BANDWIDTH};
+use super::fail;
+
+impl RenderConfig {
+    /// Check the agent colours and the density map settings
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (field, rgb) in [("render.starving_color", self.starving_color), ("render.thriving_color", self.thriving_color)] {
+            if !rgb.iter().all(|v| (0.0..=1.0).contains(v)) {
+                return fail(field, format!("must be RGB values within [0, 1], got {:?}", rgb));
+            }
+        }
+        let [low, high] = self.energy_range;
+        if !(low.is_finite() && high.is_finite() && low < high) {
+            return fail("render.energy_range", format!("must be finite with low < high, got {:?}", self.energy_range));
+        }
+        if !(0.0..=1.0).contains(&self.dead_alpha) {
+            return fail("render.dead_alpha", format!("must be within [0, 1], got {}", self.dead_alpha));
+        }
+        if !(self.density_below.is_finite() && self.density_below >= 0.0) {
+            return fail("render.density_below", format!("must be non-negative (0 = always quads), got {}", self.density_below));
+        }
+        if !(self.density_saturation.is_finite() && self.density_saturation > 0.0) {
+            return fail("render.density_saturation", format!("must be positive, got {}", self.density_saturation));
+        }
+        if !(self.kde_bandwidth > 0.0 && self.kde_bandwidth <= MAX_KDE_BANDWIDTH) {
+            return fail("render.kde_bandwidth", format!("must be within (0, {}] cells, got {}", MAX_KDE_BANDWIDTH, self.kde_bandwidth));
+        }
+        Ok(())
+    }
+}
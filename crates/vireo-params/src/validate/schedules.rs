@@ -0,0 +1,104 @@
+//! Checks on time-varying parameters. Each one validates the whole config
+//! again with the parameter at its extremes, so they live on `SimulationConfig`.
+
+use crate::{ConfigError, ScheduleShape, SimulationConfig, SCHEDULABLE_PARAMS};
+use super::fail;
+
+impl SimulationConfig {
+    /// Check the parameter schedules (`schedules`)
+    pub(super) fn validate_schedules(&self) -> Result<(), ConfigError> {
+        for (i, s) in self.schedules.iter().enumerate() {
+            let field = |name: &str| format!("schedules[{}].{}", i, name);
+            if !SCHEDULABLE_PARAMS.contains(&s.param.as_str()) {
+                return fail(&field("param"), format!("`{}` cannot be scheduled; use one of {}", s.param, SCHEDULABLE_PARAMS.join(", ")));
+            }
+            if s.schedule == ScheduleShape::Linear && s.end <= s.start {
+                return fail(&field("end"), format!("a linear schedule must end after it starts (step {}), got {}", s.start, s.end));
+            }
+            // Both endpoints must be values the parameter itself would accept
+            for (name, value) in [("from", s.from), ("to", s.to)] {
+                let mut endpoint = self.clone();
+                endpoint.schedules.clear();
+                *endpoint.scalar_mut(&s.param).unwrap() = value;
+                if let Err(e) = endpoint.validate() {
+                    return fail(&field(name), format!("{} for `{}`", e.reason, s.param));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the per-run parameter perturbation (`perturbation`)
+    pub(super) fn validate_perturbation(&self) -> Result<(), ConfigError> {
+        let p = &self.perturbation;
+        if !p.enabled {
+            return Ok(());
+        }
+        if p.every == 0 {
+            return fail("perturbation.every", "must be greater than 0".to_string());
+        }
+        if !(0.0..1.0).contains(&p.sigma) {
+            return fail("perturbation.sigma", format!("must be within [0, 1), got {}", p.sigma));
+        }
+        for (i, param) in p.params.iter().enumerate() {
+            let field = format!("perturbation.params[{}]", i);
+            if !SCHEDULABLE_PARAMS.contains(&param.as_str()) {
+                return fail(&field, format!("`{}` cannot be perturbed; use one of {}", param, SCHEDULABLE_PARAMS.join(", ")));
+            }
+            if self.schedules.iter().any(|s| &s.param == param) {
+                return fail(&field, format!("`{}` is scheduled; a parameter is either scheduled or perturbed", param));
+            }
+            // Both extremes of a draw must be values the parameter itself would accept
+            for factor in [1.0 - p.sigma, 1.0 + p.sigma] {
+                let mut extreme = self.clone();
+                extreme.perturbation.enabled = false;
+                *extreme.scalar_mut(param).unwrap() *= factor;
+                if let Err(e) = extreme.validate() {
+                    return fail(&field, format!("{} for `{}` scaled by {}", e.reason, param, factor));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the external forcing series (`forcing`)
+    pub(super) fn validate_forcing(&self) -> Result<(), ConfigError> {
+        for (i, f) in self.forcing.iter().enumerate() {
+            let field = |name: &str| format!("forcing[{}].{}", i, name);
+            if !SCHEDULABLE_PARAMS.contains(&f.param.as_str()) {
+                return fail(&field("param"), format!("`{}` cannot be forced; use one of {}", f.param, SCHEDULABLE_PARAMS.join(", ")));
+            }
+            if self.schedules.iter().any(|s| s.param == f.param)
+                || (self.perturbation.enabled && self.perturbation.params.contains(&f.param))
+                || self.forcing[..i].iter().any(|g| g.param == f.param)
+            {
+                return fail(&field("param"), format!("`{}` is already scheduled, perturbed or forced", f.param));
+            }
+            if f.file.extension().is_some_and(|e| e.eq_ignore_ascii_case("parquet")) {
+                return fail(&field("file"), "Parquet files are not supported; export the series to CSV".to_string());
+            }
+            if f.step_column == f.value_column {
+                return fail(&field("value_column"), format!("must differ from step_column `{}`", f.step_column));
+            }
+            if let Some(p) = f.points.iter().find(|p| !p[0].is_finite() || !p[1].is_finite()) {
+                return fail(&field("file"), format!("samples must be finite, got step {} value {}", p[0], p[1]));
+            }
+            if let Some(w) = f.points.windows(2).find(|w| w[1][0] <= w[0][0]) {
+                return fail(&field("file"), format!("sample steps must increase, got {} after {}", w[1][0], w[0][0]));
+            }
+            // The smallest and largest series values must give values the parameter itself would accept
+            let values = f.points.iter().map(|p| p[1]);
+            let extremes = values.clone().reduce(f32::min).into_iter().chain(values.reduce(f32::max));
+            for value in extremes {
+                let mut extreme = self.clone();
+                extreme.forcing.clear();
+                let slot = extreme.scalar_mut(&f.param).unwrap();
+                *slot = f.effective(*slot, value);
+                if let Err(e) = extreme.validate() {
+                    return fail(&field("file"), format!("{} for `{}` at series value {}", e.reason, f.param, value));
+                }
+            }
+        }
+        Ok(())
+    }
+}
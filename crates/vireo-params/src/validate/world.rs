@@ -0,0 +1,31 @@
+use crate::{ConfigError, WorldConfig, MAX_WORLD_CELLS, MAX_WORLD_SIZE, MIN_WORLD_SIZE};
+use super::fail;
+
+impl WorldConfig {
+    /// Check the grid size, step count and clock
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let [w, h] = self.size;
+        if w < MIN_WORLD_SIZE || h < MIN_WORLD_SIZE {
+            return fail("world.size", format!("{}x{} is below the {}x{} minimum", w, h, MIN_WORLD_SIZE, MIN_WORLD_SIZE));
+        }
+        if w > MAX_WORLD_SIZE || h > MAX_WORLD_SIZE {
+            return fail("world.size", format!("{}x{} exceeds the {} cell edge limit", w, h, MAX_WORLD_SIZE));
+        }
+        if w as u64 * h as u64 > MAX_WORLD_CELLS as u64 {
+            return fail("world.size", format!("{}x{} exceeds the {} cell area limit", w, h, MAX_WORLD_CELLS));
+        }
+        if self.steps == 0 {
+            return fail("world.steps", "must be greater than 0".to_string());
+        }
+        if !(self.dt.is_finite() && self.dt > 0.0) {
+            return fail("world.dt", format!("must be a positive finite number, got {}", self.dt));
+        }
+        if !(self.day_period.is_finite() && self.day_period >= 0.0) {
+            return fail("world.day_period", format!("must be a non-negative finite number, got {}", self.day_period));
+        }
+        if !(0.0..=1.0).contains(&self.night_light) {
+            return fail("world.night_light", format!("must be within [0, 1], got {}", self.night_light));
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,24 @@
 use std::cmp::min;
+use std::collections::HashMap;
 use std::f32;
 use std::sync::Arc;
 use std::time::Instant;
 
+// Real-time to step mapping shared with vireo-app (std only, so the file is included as is)
+#[path = "../crates/vireo-core/src/sim/clock.rs"]
+#[allow(dead_code)]
+mod clock;
+use clock::StepClock;
+
+// Procedural landscape shared with vireo-core's streaming mode (std only as well)
+#[path = "../crates/vireo-core/src/sim/procedural.rs"]
+mod procedural;
+use procedural::procedural_resource;
+
 use bytemuck::{Pod, Zeroable};
 use glam::{vec2, Vec2};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use wgpu::util::DeviceExt;
 use winit::{
     dpi::PhysicalSize,
@@ -20,9 +33,12 @@ use winit::{
 const DEFAULT_GRID_W: u32 = 1024;
 const DEFAULT_GRID_H: u32 = 576;
 const DEFAULT_PARTICLES: u32 = 20_000; // try 50_000 on stronger GPUs
+const DEFAULT_SEED: u64 = 1337; // same default as WorldConfig::seed
                                        // Removed: was used for ring-spring groups, now unused
 const WORKGROUP_2D: (u32, u32) = (16, 16);
 const WORKGROUP_1D: u32 = 256;
+const DEFAULT_CHUNK: u32 = 64; // Streaming chunk edge in cells; must divide the grid
+const DEFAULT_KEEP_RADIUS: u32 = 2; // Chunks kept around the window before regenerating
 
 // ------------------------ Camera ------------------------
 
@@ -73,6 +89,152 @@ impl Camera {
     }
 }
 
+// ------------------------ Streaming ------------------------
+
+/// Endless world for `VIREO_STREAMING=1`: the grid becomes a window onto
+/// procedural resource patches that follows the camera a chunk at a time
+///
+/// Chunks leaving the window are parked with their evolved field, chunks
+/// entering it come from that cache or are generated fresh, and cached chunks
+/// more than `keep_radius` chunks outside the window are evicted. Field data
+/// is RGBA16F, row-major over the window, as uploaded to the textures.
+struct Streamer {
+    chunk: u32,
+    chunks: [u32; 2],
+    origin: [i32; 2], // Global chunk of the window's lower-left chunk
+    keep_radius: u32,
+    seed: u64,
+    cache: HashMap<[i32; 2], Vec<half::f16>>,
+}
+
+impl Streamer {
+    fn new(grid_w: u32, grid_h: u32, chunk: u32, keep_radius: u32, seed: u64) -> Result<Self, String> {
+        if chunk < 8 || !grid_w.is_multiple_of(chunk) || !grid_h.is_multiple_of(chunk) {
+            return Err(format!("chunk {} must be at least 8 and divide the {}x{} grid", chunk, grid_w, grid_h));
+        }
+        let chunks = [grid_w / chunk, grid_h / chunk];
+        if chunks[0] < 3 || chunks[1] < 3 {
+            return Err(format!("chunk {} leaves fewer than 3x3 chunks in the {}x{} grid", chunk, grid_w, grid_h));
+        }
+        Ok(Self {
+            chunk,
+            chunks,
+            origin: [-(chunks[0] as i32 / 2), -(chunks[1] as i32 / 2)],
+            keep_radius,
+            seed,
+            cache: HashMap::new(),
+        })
+    }
+
+    /// Global cell of window cell (0, 0)
+    fn origin_cells(&self) -> [i64; 2] {
+        [self.origin[0] as i64 * self.chunk as i64, self.origin[1] as i64 * self.chunk as i64]
+    }
+
+    /// Fresh window centred on global chunk (0, 0), forgetting every parked chunk
+    fn fill(&mut self) -> Vec<half::f16> {
+        self.cache.clear();
+        self.origin = [-(self.chunks[0] as i32 / 2), -(self.chunks[1] as i32 / 2)];
+        let mut field = vec![half::f16::ZERO; (self.chunks[0] * self.chunks[1] * self.chunk * self.chunk * 4) as usize];
+        for cy in 0..self.chunks[1] {
+            for cx in 0..self.chunks[0] {
+                let data = self.generate(self.global([cx, cy]));
+                self.write_chunk(&mut field, [cx, cy], &data);
+            }
+        }
+        field
+    }
+
+    /// Chunk shift that re-centres the window on `focus` (window cells); zero while it is in the central chunk
+    fn shift_for(&self, focus: Vec2) -> [i32; 2] {
+        let axis = |v: f32, chunks: u32| (v / self.chunk as f32).floor() as i32 - chunks as i32 / 2;
+        [axis(focus.x, self.chunks[0]), axis(focus.y, self.chunks[1])]
+    }
+
+    /// Move the window by `delta` chunks, swapping `field` data through the cache
+    ///
+    /// Particles move with the landscape; the sandbox has no deaths, so those
+    /// left outside the window re-enter at its opposite edge. Returns how many did.
+    fn shift(&mut self, delta: [i32; 2], field: &mut [half::f16], particles: &mut [Particle]) -> u32 {
+        for cy in 0..self.chunks[1] {
+            for cx in 0..self.chunks[0] {
+                let data = self.read_chunk(field, [cx, cy]);
+                self.cache.insert(self.global([cx, cy]), data);
+            }
+        }
+        self.origin = [self.origin[0] + delta[0], self.origin[1] + delta[1]];
+        for cy in 0..self.chunks[1] {
+            for cx in 0..self.chunks[0] {
+                let coord = self.global([cx, cy]);
+                let data = self.cache.remove(&coord).unwrap_or_else(|| self.generate(coord));
+                self.write_chunk(field, [cx, cy], &data);
+            }
+        }
+        self.evict();
+
+        let offset = vec2(delta[0] as f32, delta[1] as f32) * -(self.chunk as f32);
+        let size = vec2((self.chunks[0] * self.chunk) as f32, (self.chunks[1] * self.chunk) as f32);
+        let mut wrapped = 0;
+        for p in particles.iter_mut() {
+            let pos = Vec2::from(p.pos) + offset;
+            let inside = pos.cmpge(Vec2::ZERO).all() && pos.cmplt(size).all();
+            if !inside {
+                wrapped += 1;
+            }
+            p.pos = [pos.x.rem_euclid(size.x), pos.y.rem_euclid(size.y)];
+        }
+        wrapped
+    }
+
+    /// Drop parked chunks more than `keep_radius` chunks outside the window
+    fn evict(&mut self) {
+        let r = self.keep_radius as i32;
+        let lo = [self.origin[0] - r, self.origin[1] - r];
+        let hi = [self.origin[0] + self.chunks[0] as i32 + r, self.origin[1] + self.chunks[1] as i32 + r];
+        self.cache.retain(|c, _| c[0] >= lo[0] && c[0] < hi[0] && c[1] >= lo[1] && c[1] < hi[1]);
+    }
+
+    fn global(&self, local: [u32; 2]) -> [i32; 2] {
+        [self.origin[0] + local[0] as i32, self.origin[1] + local[1] as i32]
+    }
+
+    /// Resource in channel 0, nothing in the others
+    fn generate(&self, coord: [i32; 2]) -> Vec<half::f16> {
+        let base = [coord[0] as i64 * self.chunk as i64, coord[1] as i64 * self.chunk as i64];
+        let mut data = Vec::with_capacity((self.chunk * self.chunk * 4) as usize);
+        for y in 0..self.chunk as i64 {
+            for x in 0..self.chunk as i64 {
+                let food = procedural_resource(self.seed, base[0] + x, base[1] + y);
+                data.extend_from_slice(&[half::f16::from_f32(food), half::f16::ZERO, half::f16::ZERO, half::f16::ZERO]);
+            }
+        }
+        data
+    }
+
+    /// Offset of the first channel of window cell (`x`, `y`) in the field data
+    fn index(&self, x: u32, y: u32) -> usize {
+        ((y * self.chunks[0] * self.chunk + x) * 4) as usize
+    }
+
+    fn read_chunk(&self, field: &[half::f16], local: [u32; 2]) -> Vec<half::f16> {
+        let row = (self.chunk * 4) as usize;
+        let mut data = Vec::with_capacity(row * self.chunk as usize);
+        for y in 0..self.chunk {
+            let start = self.index(local[0] * self.chunk, local[1] * self.chunk + y);
+            data.extend_from_slice(&field[start..start + row]);
+        }
+        data
+    }
+
+    fn write_chunk(&self, field: &mut [half::f16], local: [u32; 2], data: &[half::f16]) {
+        let row = (self.chunk * 4) as usize;
+        for (y, src) in data.chunks_exact(row).enumerate() {
+            let start = self.index(local[0] * self.chunk, local[1] * self.chunk + y as u32);
+            field[start..start + row].copy_from_slice(src);
+        }
+    }
+}
+
 // ------------------------ GPU Data ------------------------
 
 #[repr(C)]
@@ -189,6 +351,16 @@ struct Gfx {
 
     pipelines: Pipelines,
     use_a_as_src: bool,
+
+    // Steps per second of real time, whatever the refresh rate
+    clock: StepClock,
+    pending_steps: u32, // Steps due at the next frame
+
+    // Seeded CPU randomness (spawning and re-seeding draw from one stream)
+    rng: ChaCha8Rng,
+
+    // Endless procedural world following the camera (VIREO_STREAMING=1)
+    streamer: Option<Streamer>,
 }
 
 impl Gfx {
@@ -197,6 +369,9 @@ impl Gfx {
         grid_w: u32,
         grid_h: u32,
         particle_count: u32,
+        seed: u64,
+        clock: StepClock,
+        streamer: Option<Streamer>,
     ) -> Self {
         let size = window.inner_size();
         let instance = wgpu::Instance::default();
@@ -321,7 +496,7 @@ impl Gfx {
         });
 
         // --- Particles buffer ---
-        let mut rng = rand::thread_rng();
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
         let world_w = grid_w as f32;
         let world_h = grid_h as f32;
         let mut particles = Vec::with_capacity(particle_count as usize);
@@ -397,7 +572,7 @@ impl Gfx {
         let particle_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("particles"),
             contents: bytemuck::cast_slice(&particles),
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
         });
 
         // --- Camera ---
@@ -405,7 +580,7 @@ impl Gfx {
         
         // --- Params ---
         let params = SimParams {
-            dt: 1.0 / 60.0,
+            dt: (1.0 / clock.steps_per_second()) as f32,
             world_w,
             world_h,
             _pad0: 0.0,
@@ -631,6 +806,10 @@ impl Gfx {
             render_bg,
             pipelines,
             use_a_as_src: true,
+            clock,
+            pending_steps: 0,
+            rng,
+            streamer,
         };
 
         gfx.seed_field();
@@ -644,16 +823,28 @@ impl Gfx {
         println!("  Esc   - Quit");
         println!("  Mouse wheel - Zoom in/out");
         println!("  Left click + drag - Pan camera");
+        if let Some(streamer) = &gfx.streamer {
+            println!("Streaming: {}x{} chunks of {} cells, {} kept around the window (panning scrolls the world)",
+                streamer.chunks[0], streamer.chunks[1], streamer.chunk, streamer.keep_radius);
+        }
         
         gfx
     }
 
     fn seed_field(&mut self) {
+        // Streaming: a fresh procedural window, with the camera back on it
+        if let Some(streamer) = self.streamer.as_mut() {
+            let field = streamer.fill();
+            self.upload_field(&self.field_a, &field);
+            self.reset_camera();
+            return;
+        }
+
         // seed channel 0 with gaussian blobs as "food/scent"
         let w = self.params.grid_w as usize;
         let h = self.params.grid_h as usize;
         let mut data = vec![0f32; w * h * 4];
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         // Create more distributed food sources instead of heavy clustering
         let center_x = self.params.world_w * 0.5;
@@ -738,6 +929,13 @@ impl Gfx {
         for &val in &data {
             half_data.push(half::f16::from_f32(val));
         }
+        self.upload_field(&self.field_a, &half_data);
+    }
+
+    /// Write RGBA16F `half_data` (unpadded rows) into `texture`
+    fn upload_field(&self, texture: &wgpu::Texture, half_data: &[half::f16]) {
+        let w = self.params.grid_w as usize;
+        let h = self.params.grid_h as usize;
 
         // Calculate padded bytes per row to meet WebGPU's 256-byte alignment requirement
         let bytes_per_pixel = 8; // 4 channels × 2 bytes (f16)
@@ -780,7 +978,7 @@ impl Gfx {
 
         self.queue.write_texture(
             wgpu::ImageCopyTexture {
-                texture: &self.field_a,
+                texture,
                 mip_level: 0,
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
@@ -791,6 +989,112 @@ impl Gfx {
         );
     }
 
+    /// Field texture the next step reads from
+    fn source_texture(&self) -> &wgpu::Texture {
+        if self.use_a_as_src { &self.field_a } else { &self.field_b }
+    }
+
+    /// Copy `size` bytes of `source` into a mapped staging buffer and hand them to `read`
+    fn read_back<T>(&self, size: u64, source: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::Buffer), read: impl FnOnce(&[u8]) -> T) -> T {
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("readback encoder"),
+            });
+        source(&mut encoder, &staging);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = staging.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        self.device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let result = read(&data);
+        drop(data);
+        staging.unmap();
+        result
+    }
+
+    /// Download the source field as RGBA16F with unpadded rows
+    fn read_field(&self) -> Vec<half::f16> {
+        let w = self.params.grid_w as usize;
+        let h = self.params.grid_h as usize;
+        let unpadded_bpr = w * 8; // 4 channels × 2 bytes (f16)
+        let padded_bpr = unpadded_bpr.div_ceil(256) * 256;
+        let copy = |encoder: &mut wgpu::CommandEncoder, buffer: &wgpu::Buffer| {
+            encoder.copy_texture_to_buffer(
+                wgpu::ImageCopyTexture {
+                    texture: self.source_texture(),
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::ImageCopyBuffer {
+                    buffer,
+                    layout: wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(padded_bpr as u32),
+                        rows_per_image: Some(h as u32),
+                    },
+                },
+                wgpu::Extent3d {
+                    width: self.params.grid_w,
+                    height: self.params.grid_h,
+                    depth_or_array_layers: 1,
+                },
+            );
+        };
+        self.read_back((padded_bpr * h) as u64, copy, |bytes| {
+            bytes
+                .chunks_exact(padded_bpr)
+                .flat_map(|row| row[..unpadded_bpr].chunks_exact(2))
+                .map(|b| half::f16::from_le_bytes([b[0], b[1]]))
+                .collect()
+        })
+    }
+
+    fn read_particles(&self) -> Vec<Particle> {
+        let size = self.particle_count as u64 * std::mem::size_of::<Particle>() as u64;
+        let copy = |encoder: &mut wgpu::CommandEncoder, buffer: &wgpu::Buffer| {
+            encoder.copy_buffer_to_buffer(&self.particle_buf, 0, buffer, 0, size);
+        };
+        self.read_back(size, copy, |bytes| {
+            bytes
+                .chunks_exact(std::mem::size_of::<Particle>())
+                .map(bytemuck::pod_read_unaligned)
+                .collect()
+        })
+    }
+
+    /// Shift the streamed window once the camera leaves its central chunk
+    fn stream_world(&mut self) {
+        let Some(delta) = self.streamer.as_ref()
+            .map(|s| s.shift_for(self.camera.pos))
+            .filter(|d| *d != [0, 0])
+        else {
+            return;
+        };
+
+        let mut field = self.read_field();
+        let mut particles = self.read_particles();
+        let Some(streamer) = self.streamer.as_mut() else { return };
+        let wrapped = streamer.shift(delta, &mut field, &mut particles);
+        let chunk = streamer.chunk as f32;
+        println!("Streamed world by {:?} chunks: origin ({}, {}), {} chunks cached, {} particles wrapped",
+            delta, streamer.origin_cells()[0], streamer.origin_cells()[1], streamer.cache.len(), wrapped);
+
+        self.upload_field(self.source_texture(), &field);
+        self.queue
+            .write_buffer(&self.particle_buf, 0, bytemuck::cast_slice(&particles));
+        self.camera.pos -= vec2(delta[0] as f32, delta[1] as f32) * chunk;
+        self.update_camera();
+    }
+
     fn resize(&mut self, new_size: PhysicalSize<u32>) {
         if new_size.width > 0 && new_size.height > 0 {
             self.size = new_size;
@@ -800,11 +1104,16 @@ impl Gfx {
         }
     }
 
-    fn update_params(&mut self, dt: f32) {
+    /// Queue the steps due for `elapsed` real time (none while paused)
+    fn update_params(&mut self, elapsed: std::time::Duration) {
         if self.params.paused == 0 {
-            self.params.time += dt;
+            let steps = self.clock.advance(elapsed, 1.0);
+            self.params.time += self.params.dt * steps as f32;
+            // Frames may be skipped (e.g. minimised); never queue more than one burst
+            self.pending_steps = (self.pending_steps + steps).min(self.clock.max_burst());
+        } else {
+            self.clock.reset();
         }
-        self.params.dt = dt;
         self.queue
             .write_buffer(&self.params_buf, 0, bytemuck::bytes_of(&self.params));
     }
@@ -840,18 +1149,8 @@ impl Gfx {
         self.update_camera();
     }
 
-    fn frame(&mut self) -> Result<(), wgpu::SurfaceError> {
-        let frame = self.surface.get_current_texture()?;
-        let view = frame
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("frame encoder"),
-            });
-
+    /// Encode one simulation step: diffuse, particles, emissions
+    fn encode_step(&mut self, encoder: &mut wgpu::CommandEncoder) {
         // --- Diffuse pass (ping-pong) ---
         {
             let (pipeline, bg) = if self.use_a_as_src {
@@ -906,6 +1205,27 @@ impl Gfx {
             let gx = (self.particle_count + WORKGROUP_1D - 1) / WORKGROUP_1D;
             cpass.dispatch_workgroups(gx, 1, 1);
         }
+    }
+
+    fn frame(&mut self) -> Result<(), wgpu::SurfaceError> {
+        // Streaming: follow the camera with the world window before stepping
+        self.stream_world();
+
+        let frame = self.surface.get_current_texture()?;
+        let view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("frame encoder"),
+            });
+
+        // --- Simulation steps due since the last frame ---
+        for _ in 0..std::mem::take(&mut self.pending_steps) {
+            self.encode_step(&mut encoder);
+        }
 
         // --- Render ---
         {
@@ -1203,10 +1523,31 @@ fn env_u32(key: &str, default: u32) -> u32 {
         .unwrap_or(default)
 }
 
+fn env_u64(key: &str, default: u64) -> u64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(default)
+}
+
 fn main() {
     let grid_w = env_u32("VIREO_GRID_W", DEFAULT_GRID_W);
     let grid_h = env_u32("VIREO_GRID_H", DEFAULT_GRID_H);
     let particle_count = env_u32("VIREO_PARTICLES", DEFAULT_PARTICLES);
+    let seed = env_u64("VIREO_SEED", DEFAULT_SEED);
+    println!("Seed: {} (set VIREO_SEED to reproduce)", seed);
+    let steps_per_second = env_u32("VIREO_STEPS_PER_SECOND", StepClock::DEFAULT_STEPS_PER_SECOND as u32).max(1);
+    let max_burst = env_u32("VIREO_MAX_BURST", StepClock::DEFAULT_MAX_BURST).max(1);
+    let clock = StepClock::new(steps_per_second as f64, max_burst);
+    let streamer = if env_u32("VIREO_STREAMING", 0) != 0 {
+        let chunk = env_u32("VIREO_CHUNK", DEFAULT_CHUNK);
+        let keep_radius = env_u32("VIREO_KEEP_RADIUS", DEFAULT_KEEP_RADIUS);
+        Streamer::new(grid_w, grid_h, chunk, keep_radius, seed)
+            .map_err(|e| eprintln!("Streaming disabled: {}", e))
+            .ok()
+    } else {
+        None
+    };
 
     env_logger::init();
 
@@ -1219,7 +1560,7 @@ fn main() {
             .unwrap(),
     );
 
-    let mut state = pollster::block_on(Gfx::new(window.clone(), grid_w, grid_h, particle_count));
+    let mut state = pollster::block_on(Gfx::new(window.clone(), grid_w, grid_h, particle_count, seed, clock, streamer));
 
     let mut last = Instant::now();
     let mut mouse_pressed = false;
@@ -1318,9 +1659,9 @@ fn main() {
                 },
                 Event::AboutToWait => {
                     let now = Instant::now();
-                    let dt = (now - last).as_secs_f32().min(1.0 / 30.0);
+                    let elapsed = now - last;
                     last = now;
-                    state.update_params(dt);
+                    state.update_params(elapsed);
                     // request redraw
                     window.request_redraw();
                 }